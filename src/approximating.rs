@@ -0,0 +1,174 @@
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+#[cfg(feature = "num-traits")]
+use crate::UniNum;
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+// The comparisons of `approx` (like `assert_relative_eq!`), as `approx` implements them for f32
+// and f64.
+
+/// Absolute difference, at most `epsilon`, which defaults to `UniFloat::EPSILON`.
+impl <const C: UniFloatChoice> AbsDiffEq for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Epsilon = Self;
+
+    fn default_epsilon() -> Self {
+        Self::EPSILON
+    }
+
+    fn abs_diff_eq(&self, other: &Self, mut epsilon: Self) -> bool {
+        epsilon.refixed();
+        let mut difference = self.abs_difference(other);
+        difference.copied();
+        difference <= epsilon
+    }
+}
+
+/// Relative difference (to the larger magnitude), at most `max_relative`, which defaults to
+/// `UniFloat::EPSILON`. Values also compare equal if their absolute difference is at most
+/// `epsilon` (for values near zero), and infinities only compare equal to themselves.
+impl <const C: UniFloatChoice> RelativeEq for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn default_max_relative() -> Self {
+        Self::EPSILON
+    }
+
+    fn relative_eq(&self, other: &Self, mut epsilon: Self, mut max_relative: Self) -> bool {
+        epsilon.refixed();
+        max_relative.refixed();
+        if self == other {
+            return true;
+        }
+        if self.is_infinite() || other.is_infinite() {
+            return false;
+        }
+        let mut difference = self.abs_difference(other);
+        difference.copied();
+        if difference <= epsilon {
+            return true;
+        }
+        let (mut magnitude, mut other_magnitude) = (self.abs(), other.abs());
+        magnitude.copied();
+        other_magnitude.copied();
+        let mut largest = magnitude.max(&other_magnitude);
+        largest.copied();
+        largest *= &max_relative;
+        difference <= largest
+    }
+}
+
+/// Distance in steps on the grid (see `UniFloat::ulp_distance()`), at most `max_ulps`, which
+/// defaults to 4. Values also compare equal if their absolute difference is at most `epsilon`,
+/// and values of opposite signs only then.
+impl <const C: UniFloatChoice> UlpsEq for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self, max_ulps: u32) -> bool {
+        if self.abs_diff_eq(other, epsilon) {
+            return true;
+        }
+        if self.is_sign_negative() != other.is_sign_negative() {
+            return false;
+        }
+        self.ulp_distance(other).map_or(false, |distance| distance <= max_ulps.into())
+    }
+}
+
+/// As for `UniFloat`.
+#[cfg(feature = "num-traits")]
+impl <const C: UniFloatChoice> AbsDiffEq for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Epsilon = Self;
+
+    fn default_epsilon() -> Self {
+        Self::new(&UniFloat::default_epsilon())
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self) -> bool {
+        let (mut x, mut y) = (self.get(), other.get());
+        x.copied();
+        y.copied();
+        x.abs_diff_eq(&y, epsilon.get())
+    }
+}
+
+/// As for `UniFloat`.
+#[cfg(feature = "num-traits")]
+impl <const C: UniFloatChoice> RelativeEq for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn default_max_relative() -> Self {
+        Self::new(&UniFloat::default_max_relative())
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Self, max_relative: Self) -> bool {
+        let (mut x, mut y) = (self.get(), other.get());
+        x.copied();
+        y.copied();
+        x.relative_eq(&y, epsilon.get(), max_relative.get())
+    }
+}
+
+/// As for `UniFloat`.
+#[cfg(feature = "num-traits")]
+impl <const C: UniFloatChoice> UlpsEq for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn default_max_ulps() -> u32 {
+        UniFloat::<C>::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self, max_ulps: u32) -> bool {
+        let (mut x, mut y) = (self.get(), other.get());
+        x.copied();
+        y.copied();
+        x.ulps_eq(&y, epsilon.get(), max_ulps)
+    }
+}
+
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// |`self` - `other`|. NaN if either is NaN, or if they are the same infinity.
+    fn abs_difference(&self, other: &Self) -> Self {
+        let mut difference = *self;
+        difference.copied();
+        difference -= other;
+        difference.abs()
+    }
+}
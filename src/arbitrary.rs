@@ -0,0 +1,167 @@
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Random bits per chunk of a significand. Any chunk (as an integer) is exact for all choices.
+const CHUNK_BITS: usize = 24;
+
+/// Exponents of general values (see `UniFloat::arbitrary_from_seed()`) are within this (and the
+/// range of the choice) half the time, so that values of everyday magnitudes are common, too.
+const EVERYDAY_EXPONENT: i64 = 64;
+
+/// SplitMix64: a tiny generator that spreads any seed (even 0, 1, 2...) well.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [`low`, `high`].
+    fn between(&mut self, low: i64, high: i64) -> i64 {
+        let span = (high as i128 - low as i128 + 1) as u128;
+        (low as i128 + (self.next() as u128 % span) as i128) as i64
+    }
+}
+
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// A value for property testing, determined by `seed`, with optional feature `quickcheck` or
+    /// `proptest` (whose `Arbitrary` for `UniFloat` use this). Values are spread over what tends
+    /// to break numeric code, for any choice:
+    /// - NaN, infinities and zeros (each 1/16 of the time),
+    /// - exact powers of two over the whole exponent range, from `MIN_POSITIVE` up (1/8),
+    /// - `MAX`, or up to 3 steps below it (1/16),
+    /// - the least positive value, times up to 1024 (so subnormals for F32, F64 and TwoFloat)
+    ///   (1/16),
+    /// - small integers, up to 1000 (1/16),
+    /// - and otherwise values with all bits of the significand random, with an exponent within
+    ///   +-64 half the time, and anywhere in the range otherwise.
+    ///
+    /// All but NaN have a random sign. For fixed-point formats all finite values are on their grid,
+    /// and within their range.
+    pub fn arbitrary_from_seed(seed: u64) -> Self {
+        let mut random = SplitMix64(seed);
+        let negative = random.next() & 1 == 1;
+        let mut result = match random.next() % 16 {
+            0 => return Self::NAN,
+            1 => Self::INFINITY,
+            2 => Self::ZERO,
+            3 | 4 => {
+                let (min_exponent, max_exponent) = Self::exponent_range();
+                Self::power_of_two(random.between(min_exponent - 1, max_exponent - 1))
+            },
+            5 => {
+                let mut result = Self::MAX;
+                result.copied();
+                for _ in 0..random.next() % 4 {
+                    result <<= result.next_down();
+                }
+                result.released()
+            },
+            6 => {
+                let mut least = Self::ZERO;
+                least.copied();
+                let mut least = least.next_up();
+                least.copied();
+                let mut multiple = Self::from_f64(random.between(1, 1 << 10) as f64);
+                multiple.copied();
+                least *= &multiple;
+                least.released()
+            },
+            7 => {
+                let mut integer = Self::from_f64(random.between(0, 1000) as f64);
+                integer.copied();
+                // An operation (rather than a conversion), so that fixed-point formats wrap it.
+                integer.ldexp(0)
+            },
+            _ => Self::general(&mut random)
+        };
+        result.copied();
+        if negative {
+            result <<= -result;
+        }
+        result.released()
+    }
+
+    /// The exponents of `MIN_POSITIVE` and of `MAX` (as by `frexp()`, so 2^(exponent - 1) <=
+    /// |value| < 2^exponent). For fixed-point formats that's the grid, and their range.
+    fn exponent_range() -> (i64, i64) {
+        let (mut min, mut max) = (Self::MIN_POSITIVE, Self::MAX);
+        min.copied();
+        max.copied();
+        (min.frexp().1, max.frexp().1)
+    }
+
+    /// 2^`exponent`.
+    fn power_of_two(exponent: i64) -> Self {
+        let mut one = Self::ONE;
+        one.copied();
+        one.ldexp(exponent)
+    }
+
+    /// (1 + k / 2^(p - 1)) * 2^(e - 1), for a random integer 0 <= k < 2^(p - 1), where p is the
+    /// precision of `C` (`int_bits` + `frac_bits` for fixed-point formats), and a random
+    /// exponent e.
+    fn general(random: &mut SplitMix64) -> Self {
+        let (mut min_exponent, mut max_exponent) = Self::exponent_range();
+        if random.next() & 1 == 1 {
+            min_exponent = min_exponent.max(-EVERYDAY_EXPONENT);
+            max_exponent = max_exponent.min(EVERYDAY_EXPONENT);
+        }
+        let exponent = random.between(min_exponent, max_exponent);
+        let bits = C.precision_bits() - 1;
+        let mut result = Self::ONE;
+        result.copied();
+        let mut done = 0;
+        while done < bits {
+            let chunk_bits = (bits - done).min(CHUNK_BITS);
+            done += chunk_bits;
+            let mut chunk = Self::from_f64((random.next() >> (64 - chunk_bits)) as f64);
+            chunk.copied();
+            let mut part = chunk.ldexp(-(done as i64));
+            part.copied();
+            result += &part;
+        }
+        result.ldexp(exponent - 1)
+    }
+}
+
+/// By `UniFloat::arbitrary_from_seed()`, with a seed from `g`. There's no shrinking.
+#[cfg(feature = "quickcheck")]
+impl <const C: UniFloatChoice> quickcheck::Arbitrary for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Self::arbitrary_from_seed(<u64 as quickcheck::Arbitrary>::arbitrary(g))
+    }
+}
+
+/// By `UniFloat::arbitrary_from_seed()`, with a seed from `any::<u64>()`. Shrinking shrinks the
+/// seed, so it doesn't lead to simpler values.
+#[cfg(feature = "proptest")]
+impl <const C: UniFloatChoice> proptest::arbitrary::Arbitrary for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Parameters = ();
+    type Strategy = proptest::strategy::Map<proptest::arbitrary::StrategyFor<u64>, fn(u64) -> Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        proptest::strategy::Strategy::prop_map(proptest::arbitrary::any::<u64>(), Self::arbitrary_from_seed as fn(u64) -> Self)
+    }
+}
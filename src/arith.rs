@@ -0,0 +1,331 @@
+use core::ops;
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use core::mem::MaybeUninit;
+#[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+use crate::mpfr;
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(feature = "twofloat")]
+use crate::quadfloat::{two_prod, two_sum};
+
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Fused multiply-add: `self * a + b` with one rounding only (as in Horner's scheme or dot
+    /// products). For TwoFloat the product is exact (see `mul_add_twofloat()`), so it rounds at
+    /// double-double precision once, after the addition.
+    pub fn mul_add(&self, a: &Self, b: &Self) -> Self {
+        self.ternary(a, b, libm::fmaf, libm::fma, twofloat_op!(|x, a, b| mul_add_twofloat(x, a, b)),
+            mpfr_op!(|r, x, a, b| unsafe { mpfr::fma(r, x, a, b, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Absolute value. Exact (for MPFR it only clears the sign).
+    pub fn abs(&self) -> Self {
+        self.unary(libm::fabsf, libm::fabs, twofloat_op!(|x| x.abs()),
+            mpfr_op!(|r, x| unsafe { mpfr::abs(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Same as Rust's `f64::signum()`: 1 if `self` is positive (including +0 and +Inf), -1 if
+    /// `self` is negative (including -0 and -Inf), and NaN if `self` is NaN.
+    pub fn signum(&self) -> Self {
+        self.unary(
+            |x| if x.is_nan() { x } else { libm::copysignf(1.0, x) },
+            |x| if x.is_nan() { x } else { libm::copysign(1.0, x) },
+            twofloat_op!(|x| if x.hi().is_nan() { x } else { TwoFloat::from(libm::copysign(1.0, x.hi())) }),
+            mpfr_op!(|r, x| unsafe {
+                if mpfr::nan_p(x) != 0 {
+                    mpfr::set_nan(r);
+                    0
+                } else {
+                    mpfr::set_si(r, 1, mpfr::rnd_t::RNDN);
+                    mpfr::copysign(r, r, x, mpfr::rnd_t::RNDN)
+                }
+            }))
+    }
+
+    /// Magnitude of `self` with the sign of `sign`. Exact (for MPFR it only sets the sign).
+    pub fn copysign(&self, sign: &Self) -> Self {
+        self.binary(sign, libm::copysignf, libm::copysign,
+            twofloat_op!(|x, sign| if x.hi().is_sign_negative() == sign.hi().is_sign_negative() { x } else { -x }),
+            mpfr_op!(|r, x, sign| unsafe { mpfr::copysign(r, x, sign, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// IEEE 754 remainder: `self - n * other`, where `n` is `self / other` rounded to the nearest
+    /// integer (ties to even). The result is in [-|other|/2, |other|/2]. Exact for f32, f64 and
+    /// MPFR. For TwoFloat the quotient is rounded at double-double precision first, so the
+    /// result may be off when the quotient is huge, or near a half-way case.
+    pub fn rem(&self, other: &Self) -> Self {
+        self.binary(other, libm::remainderf, libm::remainder,
+            twofloat_op!(|x, y| {
+                let quotient = x / y;
+                let mut n = quotient.round();
+                if (quotient - quotient.trunc()).abs() == TwoFloat::from(0.5) {
+                    n = (quotient / 2.0).round() * 2.0;
+                }
+                x - n * y
+            }),
+            mpfr_op!(|r, x, y| unsafe { mpfr::remainder(r, x, y, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Remainder of a truncated division: `self - n * other`, where `n` is `self / other`
+    /// rounded toward zero. The result has the sign of `self`. Same as Rust's `%` operator on
+    /// `f64`. Exact for f32, f64 and MPFR. For TwoFloat see `rem()`.
+    pub fn fmod(&self, other: &Self) -> Self {
+        self.binary(other, libm::fmodf, libm::fmod,
+            twofloat_op!(|x, y| x - (x / y).trunc() * y),
+            mpfr_op!(|r, x, y| unsafe { mpfr::fmod(r, x, y, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// sqrt(self^2 + other^2), without overflow or underflow where the result itself doesn't
+    /// overflow or underflow. +Inf if either side is infinite (even if the other is NaN).
+    /// Correctly rounded for MPFR, within 1 ulp for f64 (as `libm`), and nearly always correctly
+    /// rounded for f32 (computed with f64). TwoFloat scales by a power of two, and computes in
+    /// double-double.
+    pub fn hypot(&self, other: &Self) -> Self {
+        self.binary(other, |x, y| libm::hypot(x as f64, y as f64) as f32, libm::hypot,
+            twofloat_op!(|x, y| hypot_twofloat([x, y].iter().copied())),
+            mpfr_op!(|r, x, y| unsafe { mpfr::hypot(r, x, y, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// sqrt(self^2 + a^2 + b^2), without overflow or underflow (as `hypot()`). Rounded once for
+    /// MPFR (see `mpfr_hypot3()`). f64 is computed with TwoFloat, so it's nearly always correctly
+    /// rounded, and f32 with f64. Needs feature `twofloat` for f64.
+    pub fn hypot3(&self, a: &Self, b: &Self) -> Self {
+        self.ternary(a, b,
+            |x, a, b| {
+                let (x, a, b) = (x as f64, a as f64, b as f64);
+                // No overflow, and the squares are exact.
+                let sum = x * x + a * a + b * b;
+                if sum.is_nan() { libm::hypot(libm::hypot(x, a), b) as f32 } else { libm::sqrt(sum) as f32 }
+            },
+            twofloat_op!(|x, a, b| hypot_twofloat([TwoFloat::from(x), TwoFloat::from(a), TwoFloat::from(b)].iter().copied()).hi()),
+            twofloat_op!(|x, a, b| hypot_twofloat([x, a, b].iter().copied())),
+            mpfr_op!(|r, x, a, b| unsafe { mpfr_hypot3(r, x, a, b) }))
+    }
+}
+
+/// Exact (for MPFR it only flips the sign).
+impl <const C: UniFloatChoice> ops::Neg for &UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Output = UniFloat<C>;
+    fn neg(self) -> Self::Output {
+        self.unary(|x| -x, |x| -x, twofloat_op!(|x| -x),
+            softmpfr_op!(|r, x| unsafe { mpfr::neg(r, x, mpfr::rnd_t::RNDN) }))
+    }
+}
+
+/// This flips the sign only, so it doesn't need `self` to be "copy fixed".
+impl <const C: UniFloatChoice> ops::Neg for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Output = Self;
+    fn neg(mut self) -> Self {
+        for f in self.f32s.iter_mut() {
+            *f = -*f;
+        }
+        #[cfg(not(feature = "f32_only"))] {
+            for f in self.f64s.iter_mut() {
+                *f = -*f;
+            }
+            #[cfg(feature = "twofloat")]
+            for twofloat in self.twofloats.iter_mut() {
+                *twofloat = -*twofloat;
+            }
+            for fixed in self.mpfr_fixeds.iter_mut() {
+                fixed.sign = -fixed.sign;
+            }
+            if let UniFloatChoice::Decimal { .. } = C {
+                let decimal = self.decimal();
+                self.set_decimal(-decimal);
+            }
+        }
+        self
+    }
+}
+
+// Modify-and-assign operators (+=, -=, *=, /=). They modify the left side in place. Hence, for
+// MPFR they reuse its limbs, and there is no temporary. QuadFloat and decimals have
+// native arithmetic for these (see `Quad` and `Dec`), rather than MPFR.
+// The left side must be "copy fixed" (as for any read). So must be the right side, if it's
+// passed by reference. If it's passed by value, it gets fixed here.
+macro_rules! impl_assign_op {
+    ($trait:ident, $method:ident, $mpfr_op:path, |$x:ident, $y:ident| $native_op:expr) => {
+        impl <const C: UniFloatChoice> ops::$trait<&Self> for UniFloat<C> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        {
+            #[inline]
+            fn $method(&mut self, rhs: &Self) {
+                self.assign_binary(rhs, |$x, $y| $native_op, |$x, $y| $native_op, twofloat_op!(|$x, $y| $native_op),
+                    |$x, $y| $native_op, |$x, $y| $native_op, softmpfr_op!(|r, x, y| unsafe { $mpfr_op(r, x, y, mpfr::rnd_t::RNDN) }));
+            }
+        }
+
+        impl <const C: UniFloatChoice> ops::$trait for UniFloat<C> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        {
+            #[inline]
+            fn $method(&mut self, mut rhs: Self) {
+                rhs.copied();
+                ops::$trait::$method(self, &rhs);
+            }
+        }
+    };
+}
+
+impl_assign_op!(AddAssign, add_assign, mpfr::add, |x, y| x + y);
+impl_assign_op!(SubAssign, sub_assign, mpfr::sub, |x, y| x - y);
+impl_assign_op!(MulAssign, mul_assign, mpfr::mul, |x, y| x * y);
+impl_assign_op!(DivAssign, div_assign, mpfr::div, |x, y| x / y);
+
+// Binary operators (+, -, *, /, %) on references. The operands may have different choices. Then
+// both get promoted to `UniFloatChoice::promoted()` of the two, and the result has that choice.
+// Promotion is exact (see `UniFloat::convert()`), so the operation rounds once only. For the same
+// choice on both sides the result has that choice, too.
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, |$x:ident, $y:ident| $op:expr) => {
+        impl <const A: UniFloatChoice, const B: UniFloatChoice> ops::$trait<&UniFloat<B>> for &UniFloat<A> where
+        [f32; f32_parts_length(A)]: Sized,
+        [f64; f64_parts_length(A)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(A)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(A)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(A)]: Sized,
+        [f32; f32_parts_length(B)]: Sized,
+        [f64; f64_parts_length(B)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(B)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(B)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(B)]: Sized,
+        [f32; f32_parts_length(UniFloatChoice::promoted(&A, &B))]: Sized,
+        [f64; f64_parts_length(UniFloatChoice::promoted(&A, &B))]: Sized,
+        [TwoFloatPart; twofloat_parts_length(UniFloatChoice::promoted(&A, &B))]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(UniFloatChoice::promoted(&A, &B))]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(UniFloatChoice::promoted(&A, &B))]: Sized,
+        {
+            type Output = UniFloat<{ UniFloatChoice::promoted(&A, &B) }>;
+            fn $method(self, rhs: &UniFloat<B>) -> Self::Output {
+                let mut $x = self.convert::<{ UniFloatChoice::promoted(&A, &B) }>();
+                $x.copied();
+                let mut $y = rhs.convert::<{ UniFloatChoice::promoted(&A, &B) }>();
+                $y.copied();
+                $op
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, |x, y| { x += &y; x.released() });
+impl_binary_op!(Sub, sub, |x, y| { x -= &y; x.released() });
+impl_binary_op!(Mul, mul, |x, y| { x *= &y; x.released() });
+impl_binary_op!(Div, div, |x, y| { x /= &y; x.released() });
+// Same as `fmod()` (like Rust's `%` on `f64`).
+impl_binary_op!(Rem, rem, |x, y| x.fmod(&y));
+
+/// `x * a + b`, rounded once to double-double. The product of the parts is exact, as four
+/// `two_prod()`s (apart from underflow of their errors). It's summed up with the parts of `b` by
+/// `two_sum()`s: two passes that move the sum to the last term, and leave the errors in the others,
+/// small enough that adding them up plainly is accurate (Ogita, Rump and Oishi's SumK, with K = 3).
+/// So the result is as if summed at three times f64's precision, and the only rounding of note is
+/// the final one, even when the terms cancel. NaN, infinities and overflows are as for `x * a + b`.
+#[cfg(feature = "twofloat")]
+fn mul_add_twofloat(x: TwoFloat, a: TwoFloat, b: TwoFloat) -> TwoFloat {
+    let (product, product_error) = two_prod(x.hi(), a.hi());
+    if !product.is_finite() || !b.hi().is_finite() {
+        return x * a + b;
+    }
+    let (high_low, high_low_error) = two_prod(x.hi(), a.lo());
+    let (low_high, low_high_error) = two_prod(x.lo(), a.hi());
+    let (low_low, low_low_error) = two_prod(x.lo(), a.lo());
+    let mut terms = [low_low_error, low_low, low_high_error, high_low_error, b.lo(), low_high, high_low,
+        product_error, b.hi(), product];
+    for _ in 0..2 {
+        for index in 1..terms.len() {
+            let (sum, error) = two_sum(terms[index], terms[index - 1]);
+            terms[index] = sum;
+            terms[index - 1] = error;
+        }
+    }
+    let (sum, errors) = terms.split_last().unwrap();
+    let (high, low) = two_sum(*sum, errors.iter().sum());
+    if high == 0.0 {
+        // Exactly zero: -0 only for -0 + -0 (as in IEEE 754), and a zero product needs b to be zero.
+        TwoFloat::from(if product == 0.0 { product + b.hi() } else { 0.0 })
+    } else if !high.is_finite() || low == 0.0 {
+        TwoFloat::from(high)
+    } else {
+        TwoFloat::new_add(high, low)
+    }
+}
+
+/// sqrt of the sum of squares of `values`. They get scaled by a power of two, so that the result is
+/// near 1. Both parts of each value are scaled (exactly, apart from underflow of the lower part).
+#[cfg(feature = "twofloat")]
+pub(crate) fn hypot_twofloat(values: impl Iterator<Item = TwoFloat> + Clone) -> TwoFloat {
+    // libm::hypot() handles NaN and infinities as IEEE 754 (infinities win over NaN).
+    let approximation = values.clone().fold(0.0, |approximation, value| libm::hypot(approximation, value.hi()));
+    if !approximation.is_finite() || approximation == 0.0 {
+        return TwoFloat::from(approximation);
+    }
+    let (_, exponent) = libm::frexp(approximation);
+    let scale = |value: TwoFloat, exponent: i32| TwoFloat::new_add(libm::scalbn(value.hi(), exponent),
+        libm::scalbn(value.lo(), exponent));
+    let sum = values.fold(TwoFloat::from(0.0), |sum, value| {
+        let value = scale(value, -exponent);
+        sum + value * value
+    });
+    scale(sum.sqrt(), exponent)
+}
+
+/// sqrt(x^2 + a^2 + b^2), rounded once, by Ziv's strategy (as `mpfr_log()` in the logarithm
+/// module): `mpfr_hypot()` twice at a higher (working) precision. If the result is exact (like
+/// for 2, 3 and 6) or halfway between two neighbors, that never gets decided, so the working
+/// precision is limited, and then the result is rounded as it is. That is correct for exact
+/// results, and it may be off by 1 ulp for halfway ones.
+#[cfg(feature = "mpfr")]
+unsafe fn mpfr_hypot3(r: mpfr::mpfr_ptr, x: mpfr::mpfr_srcptr, a: mpfr::mpfr_srcptr, b: mpfr::mpfr_srcptr) -> i32 {
+    let precision = mpfr::get_prec(r);
+    let mut working = precision + 32;
+    let mut partial = MaybeUninit::uninit();
+    mpfr::init2(partial.as_mut_ptr(), working);
+    let partial = partial.as_mut_ptr();
+    let ternary = loop {
+        mpfr::hypot(partial, x, a, mpfr::rnd_t::RNDN);
+        mpfr::hypot(partial, partial, b, mpfr::rnd_t::RNDN);
+        // Specials (including zero) are exact.
+        if mpfr::regular_p(partial) == 0 {
+            break mpfr::set(r, partial, mpfr::rnd_t::RNDN);
+        }
+        // Two roundings, each with relative error up to 2^-working. The first one carries over
+        // at most as it is. That's less than 2^(1 - working) relative, hence less than
+        // 2^(exponent + 1 - working) absolute.
+        if mpfr::can_round(partial, (working - 1) as mpfr::exp_t, mpfr::rnd_t::RNDN, mpfr::rnd_t::RNDZ, precision + 1) != 0
+            || working > 2 * precision + 256 {
+            break mpfr::set(r, partial, mpfr::rnd_t::RNDN);
+        }
+        working += working / 2;
+        mpfr::set_prec(partial, working);
+    };
+    mpfr::clear(partial);
+    ternary
+}
@@ -0,0 +1,298 @@
+use core::{ops, panic::Location, ptr, sync::atomic::{AtomicPtr, AtomicU64, Ordering}};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// The number of call sites that `UniAudited` records errors for. Operations at further call sites
+/// are only counted (see `AuditSite::unrecorded()`).
+pub const AUDIT_SITES: usize = 256;
+
+/// A call site's slot. `location` is null until an operation there claims the slot.
+struct Slot {
+    location: AtomicPtr<Location<'static>>,
+    /// Bits of a non-negative f64, so that they order as the values do.
+    max_error: AtomicU64,
+    operations: AtomicU64,
+}
+
+const EMPTY_SLOT: Slot = Slot {
+    location: AtomicPtr::new(ptr::null_mut()),
+    max_error: AtomicU64::new(0),
+    operations: AtomicU64::new(0),
+};
+
+/// Shared by all threads (like the emulated `Flags`), and by all choices.
+static SLOTS: [Slot; AUDIT_SITES] = [EMPTY_SLOT; AUDIT_SITES];
+static UNRECORDED: AtomicU64 = AtomicU64::new(0);
+
+/// What `UniAudited` recorded for a call site (in the source that calls `UniAudited`'s
+/// operators and methods): the maximum relative error of results there, and how many operations
+/// there were.
+#[derive(Clone, Copy, Debug)]
+pub struct AuditSite {
+    location: &'static Location<'static>,
+    max_relative_error: f64,
+    operations: u64,
+}
+
+impl AuditSite {
+    /// All recorded call sites, in the order they were first reached (by any thread).
+    pub fn read() -> impl Iterator<Item = Self> {
+        SLOTS.iter().filter_map(|slot| {
+            let location = slot.location.load(Ordering::Acquire);
+            if location.is_null() {
+                None
+            } else {
+                Some(Self {
+                    // Claimed slots hold a `&'static Location` (see `record()`).
+                    location: unsafe { &*location },
+                    max_relative_error: f64::from_bits(slot.max_error.load(Ordering::Relaxed)),
+                    operations: slot.operations.load(Ordering::Relaxed),
+                })
+            }
+        })
+    }
+
+    /// Forgets all call sites (and the unrecorded count). Operations running meanwhile (in other
+    /// threads) may be lost, or recorded partially.
+    pub fn clear() {
+        for slot in SLOTS.iter() {
+            slot.max_error.store(0, Ordering::Relaxed);
+            slot.operations.store(0, Ordering::Relaxed);
+            slot.location.store(ptr::null_mut(), Ordering::Release);
+        }
+        UNRECORDED.store(0, Ordering::Relaxed);
+    }
+
+    /// The number of operations at call sites beyond the first `AUDIT_SITES`.
+    pub fn unrecorded() -> u64 {
+        UNRECORDED.load(Ordering::Relaxed)
+    }
+
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// See `UniAudited::relative_error()`.
+    pub fn max_relative_error(&self) -> f64 {
+        self.max_relative_error
+    }
+
+    pub fn operations(&self) -> u64 {
+        self.operations
+    }
+}
+
+/// The same call site (which may be at different addresses, if inlined).
+fn same_site(x: &Location, y: &Location) -> bool {
+    (x.file(), x.line(), x.column()) == (y.file(), y.line(), y.column())
+}
+
+/// Records `error` (non-negative) for `location`, claiming a slot if it has none yet.
+fn record(location: &'static Location<'static>, error: f64) {
+    let pointer = location as *const Location<'static> as *mut Location<'static>;
+    for slot in SLOTS.iter() {
+        let claimed = slot.location.compare_exchange(ptr::null_mut(), pointer, Ordering::AcqRel, Ordering::Acquire);
+        let recorded = match claimed {
+            Ok(_) => pointer,
+            Err(current) => current,
+        };
+        if same_site(unsafe { &*recorded }, location) {
+            slot.max_error.fetch_max(error.to_bits(), Ordering::Relaxed);
+            slot.operations.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+    }
+    UNRECORDED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Shadow execution, for accuracy auditing: a value of `C`, along with a shadow of it at (more
+/// precise) choice `D`, typically `oracle_choice(C)` (see module `testing`). Every operation runs
+/// on both, and records the relative error of its result (against the shadow) for its call site
+/// (see `AuditSite`). So, with `UniAudited` in place of `UniFloat` in an existing pipeline, that
+/// shows where it loses accuracy. It's several times slower than `UniFloat`, and not meant for
+/// production.
+///
+/// Errors accumulate: the shadow of a result comes from the shadows of the operands, so a result's
+/// error includes the errors of earlier steps (as they propagate), not just its own rounding.
+#[derive(Clone, Copy)]
+pub struct UniAudited<const C: UniFloatChoice, const D: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+[f32; f32_parts_length(D)]: Sized,
+[f64; f64_parts_length(D)]: Sized,
+[TwoFloatPart; twofloat_parts_length(D)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(D)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(D)]: Sized,
+{
+    value: UniFloat<C>,
+    shadow: UniFloat<D>,
+}
+
+impl <const C: UniFloatChoice, const D: UniFloatChoice> UniAudited<C, D> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+[f32; f32_parts_length(D)]: Sized,
+[f64; f64_parts_length(D)]: Sized,
+[TwoFloatPart; twofloat_parts_length(D)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(D)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(D)]: Sized,
+{
+    /// `value`, with an exact shadow (so inputs count as exact). Panics if `D` is less than twice
+    /// as precise as `C`.
+    pub fn new(value: &UniFloat<C>) -> Self {
+        assert!(D.precision_bits() >= 2 * C.precision_bits(), "UniAudited needs D at least twice as precise as C (see oracle_choice()).");
+        Self { value: *value, shadow: value.convert() }.released()
+    }
+
+    /// From results returned by value, recording the error for the call site.
+    #[track_caller]
+    fn recorded(mut value: UniFloat<C>, mut shadow: UniFloat<D>) -> Self {
+        value.copied();
+        shadow.copied();
+        record(Location::caller(), Self::error(&value, &shadow));
+        Self { value, shadow }.released()
+    }
+
+    /// As `UniFloat::copied()`, for both the value and the shadow.
+    #[inline]
+    pub fn copied(&mut self) -> &mut Self {
+        self.value.copied();
+        self.shadow.copied();
+        self
+    }
+
+    /// As `UniFloat::released()`, for both the value and the shadow.
+    fn released(self) -> Self {
+        Self { value: self.value.released(), shadow: self.shadow.released() }
+    }
+
+    pub fn value(&self) -> &UniFloat<C> {
+        &self.value
+    }
+
+    pub fn shadow(&self) -> &UniFloat<D> {
+        &self.shadow
+    }
+
+    /// |value - shadow| / |shadow| (computed at `D`). 0 if both are NaN, or the same infinity, or
+    /// zeros; infinite if only one of them is NaN, or infinite, or zero.
+    pub fn relative_error(&self) -> f64 {
+        Self::error(&self.value, &self.shadow)
+    }
+
+    /// The relative error of `value` against `shadow`, as `relative_error()`.
+    fn error(value: &UniFloat<C>, shadow: &UniFloat<D>) -> f64 {
+        let mut value = value.convert::<D>();
+        value.copied();
+        let mut zero = UniFloat::<D>::ZERO;
+        zero.copied();
+        if value.is_nan() || shadow.is_nan() {
+            return if value.is_nan() && shadow.is_nan() { 0.0 } else { f64::INFINITY };
+        }
+        if value.is_infinite() || shadow.is_infinite() || *shadow == zero {
+            return if value == *shadow { 0.0 } else { f64::INFINITY };
+        }
+        value -= shadow;
+        value /= shadow;
+        let mut error = value.abs();
+        error.copied().to_f64_nearest()
+    }
+
+    /// As `UniFloat::mul_add()`.
+    #[track_caller]
+    pub fn mul_add(&self, a: &Self, b: &Self) -> Self {
+        Self::recorded(self.value.mul_add(&a.value, &b.value), self.shadow.mul_add(&a.shadow, &b.shadow))
+    }
+}
+
+// Functions of `UniFloat`, on both the value and the shadow.
+macro_rules! impl_functions {
+    ($($function:ident),+; $($binary_function:ident),+) => {
+        impl <const C: UniFloatChoice, const D: UniFloatChoice> UniAudited<C, D> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        [f32; f32_parts_length(D)]: Sized,
+        [f64; f64_parts_length(D)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(D)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(D)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(D)]: Sized,
+        {
+            $(
+                #[track_caller]
+                pub fn $function(&self) -> Self {
+                    Self::recorded(self.value.$function(), self.shadow.$function())
+                }
+            )+
+            $(
+                #[track_caller]
+                pub fn $binary_function(&self, other: &Self) -> Self {
+                    Self::recorded(self.value.$binary_function(&other.value), self.shadow.$binary_function(&other.shadow))
+                }
+            )+
+        }
+    };
+}
+
+impl_functions!(abs, sqrt, cbrt, exp, exp2, exp10, exp_m1, ln, log2, log10, ln_1p, sin, cos, tan, asin, acos,
+    atan, sinh, cosh, tanh, asinh, acosh, atanh, floor, ceil, trunc, round; hypot, atan2, pow);
+
+/// Exact, so it's not recorded.
+impl <const C: UniFloatChoice, const D: UniFloatChoice> ops::Neg for &UniAudited<C, D> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+[f32; f32_parts_length(D)]: Sized,
+[f64; f64_parts_length(D)]: Sized,
+[TwoFloatPart; twofloat_parts_length(D)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(D)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(D)]: Sized,
+{
+    type Output = UniAudited<C, D>;
+    fn neg(self) -> Self::Output {
+        UniAudited { value: -&self.value, shadow: -&self.shadow }.released()
+    }
+}
+
+// Binary operators (+, -, *, /) on references, recorded for the call site of the operator. They
+// go through the assign operators, because the binary operators of `UniFloat` promote their output.
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl <const C: UniFloatChoice, const D: UniFloatChoice> ops::$trait<&UniAudited<C, D>> for &UniAudited<C, D> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        [f32; f32_parts_length(D)]: Sized,
+        [f64; f64_parts_length(D)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(D)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(D)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(D)]: Sized,
+        {
+            type Output = UniAudited<C, D>;
+            #[track_caller]
+            fn $method(self, other: &UniAudited<C, D>) -> Self::Output {
+                let (mut value, mut shadow) = (self.value, self.shadow);
+                ops::$assign_trait::$assign_method(&mut value, &other.value);
+                ops::$assign_trait::$assign_method(&mut shadow, &other.shadow);
+                UniAudited::recorded(value, shadow)
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, AddAssign, add_assign);
+impl_binary_op!(Sub, sub, SubAssign, sub_assign);
+impl_binary_op!(Mul, mul, MulAssign, mul_assign);
+impl_binary_op!(Div, div, DivAssign, div_assign);
@@ -0,0 +1,280 @@
+use core::{cmp::Ordering, ops};
+
+use crate::{MpfrBounds, RoundingMode, UniFloatChoice, UniFloat, UniInterval, UnsupportedRounding, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Choice of the radius of `UniBall`: 30 bits (as Arb's), with MPFR's wide exponent range, so it
+/// doesn't underflow even for tiny MPFR midpoints.
+pub const BALL_RADIUS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(30)
+};
+pub(crate) type Radius = UniFloat<{ BALL_RADIUS }>;
+
+/// A ball [`midpoint` - `radius`, `midpoint` + `radius`] (Arb-style midpoint-radius arithmetic):
+/// a rigorous enclosure of a real number. Arithmetic rounds the midpoint to nearest (as
+/// `UniFloat`), and adds bounds of the propagated errors and of its own rounding error to the
+/// radius (rounded up). That's cheaper than `UniInterval`, while the enclosure is about as tight.
+/// - TwoFloat isn't correctly rounded: its rounding error counts as 16 ulps (see `UniFloat::ulp()`).
+#[derive(Clone, Copy)]
+pub struct UniBall<const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    midpoint: UniFloat<C>,
+    radius: Radius,
+}
+
+/// A value with a running error bound: a value of `C`, along with a rigorous bound of its
+/// accumulated error, which every operation updates. That's a `UniBall` (whose radius is the
+/// bound), so it's the same type. `midpoint()` is the plain value, and `certified_digits()` tells
+/// how much of it holds.
+pub type UniFloatTracked<const C: UniFloatChoice> = UniBall<C>;
+
+impl <const C: UniFloatChoice> UniBall<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Panics if `radius` is negative.
+    pub fn new(midpoint: &UniFloat<C>, radius: &UniFloat<{ BALL_RADIUS }>) -> Self {
+        let mut zero = Radius::ZERO;
+        zero.copied();
+        assert!(!radius.lt(&zero), "UniBall needs a non-negative radius.");
+        Self { midpoint: *midpoint, radius: *radius }.released()
+    }
+
+    /// `value` with radius 0.
+    pub fn exact(value: &UniFloat<C>) -> Self {
+        let mut zero = Radius::ZERO;
+        zero.copied();
+        Self::new(value, &zero)
+    }
+
+    /// From parts returned by value.
+    fn from_parts(mut midpoint: UniFloat<C>, mut radius: Radius) -> Self {
+        midpoint.copied();
+        radius.copied();
+        Self::new(&midpoint, &radius)
+    }
+
+    /// As `UniFloat::copied()`, for both parts.
+    #[inline]
+    pub fn copied(&mut self) -> &mut Self {
+        self.midpoint.copied();
+        self.radius.copied();
+        self
+    }
+
+    /// As `UniFloat::released()`, for both parts.
+    fn released(self) -> Self {
+        Self { midpoint: self.midpoint.released(), radius: self.radius.released() }
+    }
+
+    pub fn midpoint(&self) -> &UniFloat<C> {
+        &self.midpoint
+    }
+
+    pub fn radius(&self) -> &UniFloat<{ BALL_RADIUS }> {
+        &self.radius
+    }
+
+    /// [`midpoint` - `radius`, `midpoint` + `radius`], rounded outward.
+    pub fn to_interval(&self) -> UniInterval<C> {
+        let mut radius = match self.radius.convert_round::<C>(RoundingMode::Up) {
+            Ok(radius) => radius,
+            // TwoFloat, beyond its exponent range. Its neighbour up (or Inf) is greater.
+            Err(_) => {
+                let mut nearest = self.radius.convert::<C>();
+                nearest.copied();
+                nearest.next_up()
+            }
+        };
+        radius.copied();
+        let mut spread = UniInterval::new(&-&radius, &radius);
+        spread.copied();
+        let mut midpoint = UniInterval::point(&self.midpoint);
+        midpoint.copied();
+        &midpoint + &spread
+    }
+
+    /// Whether `value` is in the ball (as per `to_interval()`, so it may include values slightly
+    /// beyond the radius). False if `value` is NaN.
+    pub fn contains(&self, value: &UniFloat<C>) -> bool {
+        let mut interval = self.to_interval();
+        interval.copied().contains(value)
+    }
+
+    /// The number of decimal digits of `midpoint` that are certain: -log10(`radius` / |`midpoint`|)
+    /// (rounded down), between 0 (if the ball contains zero, or `midpoint` is NaN) and all digits
+    /// of the precision of `C` (if `radius` is 0). Not an integer: 2.5 digits means that the
+    /// relative error is at most 10^-2.5.
+    pub fn certified_digits(&self) -> f64 {
+        let all_digits = C.precision_bits() as f64 * core::f64::consts::LOG10_2;
+        let mut zero = Radius::ZERO;
+        zero.copied();
+        if self.midpoint.is_nan() || self.radius.is_nan() {
+            return 0.0;
+        }
+        if self.radius == zero {
+            return all_digits;
+        }
+        let mut magnitude = self.magnitude(RoundingMode::Down);
+        magnitude.copied();
+        if magnitude.le(&self.radius) {
+            return 0.0;
+        }
+        let mut relative = radius_op(&self.radius, &magnitude, RoundingMode::Up, Radius::div_round);
+        relative.copied();
+        let mut log = relative.log10();
+        // The logarithm is rounded to nearest (to 30 bits), so it's lowered by an ulp (relative), to
+        // round it down.
+        let digits = -log.copied().to_f64_nearest() * (1.0 - 1.0 / (1u64 << 29) as f64);
+        digits.max(0.0).min(all_digits)
+    }
+
+    /// |`midpoint`| rounded in the direction of `rounding` for the radius.
+    fn magnitude(&self, rounding: RoundingMode) -> Radius {
+        let mut magnitude = self.midpoint.abs();
+        magnitude.copied();
+        match magnitude.convert_round::<{ BALL_RADIUS }>(rounding) {
+            Ok(magnitude) => magnitude,
+            Err(_) => unreachable!("MPFR supports directed rounding.")
+        }
+    }
+}
+
+/// log2 of the ulps that a rounding error of TwoFloat (or QuadFloat) arithmetic counts as.
+const TWOFLOAT_ERROR_ULPS_LOG2: i64 = 4;
+
+/// A bound of the rounding error of `rounded` (from a `*_with_ternary()` method, with `ternary`
+/// from it), rounded up for the radius.
+fn rounding_error<const C: UniFloatChoice>(rounded: &UniFloat<C>, ternary: Ordering) -> Radius where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    if ternary == Ordering::Equal {
+        Radius::ZERO
+    } else {
+        inexact_rounding_error(rounded)
+    }
+}
+
+/// A bound of the rounding error of `rounded`, from an inexact operation, rounded up for the
+/// radius.
+pub(crate) fn inexact_rounding_error<const C: UniFloatChoice>(rounded: &UniFloat<C>) -> Radius where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    // Correctly rounded results are within half an ulp.
+    let mut error = rounded.ulp();
+    error.copied();
+    if matches!(C, UniFloatChoice::TwoFloat | UniFloatChoice::QuadFloat) {
+        let scaled = error.ldexp(TWOFLOAT_ERROR_ULPS_LOG2);
+        error <<= scaled;
+    }
+    match error.convert_round::<{ BALL_RADIUS }>(RoundingMode::Up) {
+        Ok(error) => error,
+        Err(_) => unreachable!("MPFR supports directed rounding.")
+    }
+}
+
+/// `x op y` rounded in the direction of `rounding`, for radii.
+pub(crate) fn radius_op(x: &Radius, y: &Radius, rounding: RoundingMode,
+    op: fn(&Radius, &Radius, RoundingMode) -> Result<Radius, UnsupportedRounding>) -> Radius {
+    match op(x, y, rounding) {
+        Ok(result) => result,
+        Err(_) => unreachable!("MPFR supports directed rounding.")
+    }
+}
+
+/// `x * y + z`, rounded up (in two steps).
+pub(crate) fn mul_add_up(x: &Radius, y: &Radius, z: &Radius) -> Radius {
+    let mut product = radius_op(x, y, RoundingMode::Up, Radius::mul_round);
+    product.copied();
+    radius_op(&product, z, RoundingMode::Up, Radius::add_round)
+}
+
+/// [-`midpoint`, `radius`]. Exact.
+impl <const C: UniFloatChoice> ops::Neg for &UniBall<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Output = UniBall<C>;
+    fn neg(self) -> Self::Output {
+        UniBall::from_parts(-&self.midpoint, self.radius)
+    }
+}
+
+// Binary operators (+, -, *, /) on references.
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $with_ternary:ident, |$x:ident, $y:ident| $propagated:expr) => {
+        impl <const C: UniFloatChoice> ops::$trait<&UniBall<C>> for &UniBall<C> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        {
+            type Output = UniBall<C>;
+            fn $method(self, other: &UniBall<C>) -> Self::Output {
+                let ($x, $y) = (self, other);
+                let (mut midpoint, ternary) = $x.midpoint.$with_ternary(&$y.midpoint);
+                midpoint.copied();
+                let (mut propagated, mut error) = ($propagated, rounding_error(&midpoint, ternary));
+                propagated.copied();
+                error.copied();
+                UniBall::from_parts(midpoint.released(), radius_op(&propagated, &error, RoundingMode::Up, Radius::add_round))
+            }
+        }
+    };
+}
+
+// |(x + a) + (y + b) - (x + y)| <= |a| + |b|, and the same for subtraction.
+impl_binary_op!(Add, add, add_with_ternary, |x, y| radius_op(&x.radius, &y.radius, RoundingMode::Up, Radius::add_round));
+impl_binary_op!(Sub, sub, sub_with_ternary, |x, y| radius_op(&x.radius, &y.radius, RoundingMode::Up, Radius::add_round));
+// |(x + a)(y + b) - xy| <= |x| |b| + |y| |a| + |a| |b|.
+impl_binary_op!(Mul, mul, mul_with_ternary, |x, y| {
+    let (mut x_magnitude, mut y_magnitude) = (x.magnitude(RoundingMode::Up), y.magnitude(RoundingMode::Up));
+    x_magnitude.copied();
+    y_magnitude.copied();
+    let mut radii = radius_op(&x.radius, &y.radius, RoundingMode::Up, Radius::mul_round);
+    radii.copied();
+    let mut partial = mul_add_up(&x_magnitude, &y.radius, &radii);
+    partial.copied();
+    mul_add_up(&y_magnitude, &x.radius, &partial)
+});
+// |(x + a) / (y + b) - x / y| <= (|x| |b| + |y| |a|) / (|y| (|y| - |b|)), for |b| < |y|. Otherwise
+// the radius is infinite.
+impl_binary_op!(Div, div, div_with_ternary, |x, y| {
+    let (mut x_up, mut y_up, mut y_down) =
+        (x.magnitude(RoundingMode::Up), y.magnitude(RoundingMode::Up), y.magnitude(RoundingMode::Down));
+    x_up.copied();
+    y_up.copied();
+    y_down.copied();
+    if y_down.le(&y.radius) {
+        Radius::INFINITY
+    } else {
+        let mut partial = radius_op(&x_up, &y.radius, RoundingMode::Up, Radius::mul_round);
+        partial.copied();
+        let mut numerator = mul_add_up(&y_up, &x.radius, &partial);
+        numerator.copied();
+        let mut gap = radius_op(&y_down, &y.radius, RoundingMode::Down, Radius::sub_round);
+        gap.copied();
+        let mut denominator = radius_op(&y_down, &gap, RoundingMode::Down, Radius::mul_round);
+        denominator.copied();
+        radius_op(&numerator, &denominator, RoundingMode::Up, Radius::div_round)
+    }
+});
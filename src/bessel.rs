@@ -0,0 +1,57 @@
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use crate::mpfr;
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Bessel functions of the first kind (`j...`) and of the second kind (`y...`). They are correctly
+/// rounded for MPFR. The other choices delegate to `libm` in f64, with reduced accuracy: f32 is
+/// computed with f64, TwoFloat gets only f64 accuracy, and near the zeros of the functions (and
+/// for large `self`) even f64 loses relative accuracy.
+///
+/// The functions of the second kind are NaN for negative `self`, and -Inf for zero (for
+/// non-negative order).
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Bessel function of the first kind of order 0.
+    pub fn j0(&self) -> Self {
+        self.unary(|x| libm::j0(x as f64) as f32, libm::j0, twofloat_op!(|x| TwoFloat::from(libm::j0(x.hi()))),
+            mpfr_op!(|r, x| unsafe { mpfr::j0(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Bessel function of the first kind of order 1.
+    pub fn j1(&self) -> Self {
+        self.unary(|x| libm::j1(x as f64) as f32, libm::j1, twofloat_op!(|x| TwoFloat::from(libm::j1(x.hi()))),
+            mpfr_op!(|r, x| unsafe { mpfr::j1(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Bessel function of the first kind of order `n`.
+    pub fn jn(&self, n: i32) -> Self {
+        self.unary(|x| libm::jn(n, x as f64) as f32, |x| libm::jn(n, x), twofloat_op!(|x| TwoFloat::from(libm::jn(n, x.hi()))),
+            mpfr_op!(|r, x| unsafe { mpfr::jn(r, n.into(), x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Bessel function of the second kind of order 0.
+    pub fn y0(&self) -> Self {
+        self.unary(|x| libm::y0(x as f64) as f32, libm::y0, twofloat_op!(|x| TwoFloat::from(libm::y0(x.hi()))),
+            mpfr_op!(|r, x| unsafe { mpfr::y0(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Bessel function of the second kind of order 1.
+    pub fn y1(&self) -> Self {
+        self.unary(|x| libm::y1(x as f64) as f32, libm::y1, twofloat_op!(|x| TwoFloat::from(libm::y1(x.hi()))),
+            mpfr_op!(|r, x| unsafe { mpfr::y1(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Bessel function of the second kind of order `n`.
+    pub fn yn(&self, n: i32) -> Self {
+        self.unary(|x| libm::yn(n, x as f64) as f32, |x| libm::yn(n, x), twofloat_op!(|x| TwoFloat::from(libm::yn(n, x.hi()))),
+            mpfr_op!(|r, x| unsafe { mpfr::yn(r, n.into(), x, mpfr::rnd_t::RNDN) }))
+    }
+}
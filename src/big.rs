@@ -0,0 +1,190 @@
+use core::cmp::Ordering;
+
+/// Unsigned integer of `N * 32` bits (little endian), for exact conversions of f32 and f64 to and
+/// from other bases without MPFR, and for `UniDecimal` arithmetic. Results must fit: nothing here
+/// checks for overflows.
+#[derive(Clone, Copy)]
+pub(crate) struct Big<const N: usize> {
+    limbs: [u32; N],
+}
+
+impl <const N: usize> Big<N> {
+    pub(crate) const ZERO: Self = Big { limbs: [0; N] };
+
+    /// `value << shift`.
+    pub(crate) fn new(value: u64, shift: u32) -> Self {
+        let mut result = Self::ZERO;
+        let (index, wide) = ((shift / 32) as usize, (value as u128) << (shift % 32));
+        for (offset, limb) in result.limbs[index..].iter_mut().take(3).enumerate() {
+            *limb = (wide >> (32 * offset)) as u32;
+        }
+        result
+    }
+
+    pub(crate) fn from_u128(value: u128) -> Self {
+        let mut result = Self::ZERO;
+        for (offset, limb) in result.limbs.iter_mut().take(4).enumerate() {
+            *limb = (value >> (32 * offset)) as u32;
+        }
+        result
+    }
+
+    /// The lowest 128 bits.
+    pub(crate) fn to_u128(&self) -> u128 {
+        self.limbs.iter().take(4).enumerate().fold(0, |value, (offset, &limb)| value | (limb as u128) << (32 * offset))
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.limbs.iter().all(|&limb| limb == 0)
+    }
+
+    /// Number of bits, without leading zeros. Zero for zero.
+    pub(crate) fn bit_length(&self) -> u32 {
+        match self.limbs.iter().rposition(|&limb| limb != 0) {
+            Some(index) => 32 * index as u32 + 32 - self.limbs[index].leading_zeros(),
+            None => 0
+        }
+    }
+
+    pub(crate) fn compare(&self, other: &Self) -> Ordering {
+        self.limbs.iter().rev().cmp(other.limbs.iter().rev())
+    }
+
+    pub(crate) fn mul_small(&mut self, factor: u32) {
+        let mut carry = 0;
+        for limb in self.limbs.iter_mut() {
+            let product = *limb as u64 * factor as u64 + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+    }
+
+    pub(crate) fn add_small(&mut self, addend: u32) {
+        let mut carry = addend as u64;
+        for limb in self.limbs.iter_mut() {
+            if carry == 0 {
+                break;
+            }
+            let sum = *limb as u64 + carry;
+            *limb = sum as u32;
+            carry = sum >> 32;
+        }
+    }
+
+    /// Returns the remainder.
+    pub(crate) fn div_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0;
+        for limb in self.limbs.iter_mut().rev() {
+            let dividend = remainder << 32 | *limb as u64;
+            *limb = (dividend / divisor as u64) as u32;
+            remainder = dividend % divisor as u64;
+        }
+        remainder as u32
+    }
+
+    pub(crate) fn add(&mut self, other: &Self) {
+        let mut carry = 0;
+        for (limb, &addend) in self.limbs.iter_mut().zip(other.limbs.iter()) {
+            let sum = *limb as u64 + addend as u64 + carry;
+            *limb = sum as u32;
+            carry = sum >> 32;
+        }
+    }
+
+    /// Schoolbook multiplication.
+    pub(crate) fn mul(&self, other: &Self) -> Self {
+        let mut result = Self::ZERO;
+        for (index, &limb) in other.limbs.iter().enumerate().filter(|&(_, &limb)| limb != 0) {
+            let mut partial = *self;
+            partial.mul_small(limb);
+            result.add(&partial.shifted_left(32 * index as u32));
+        }
+        result
+    }
+
+    /// Long division, bit by bit: slow, but simple. Returns the remainder. `divisor` must not be
+    /// zero.
+    pub(crate) fn div_rem(&mut self, divisor: &Self) -> Self {
+        let mut remainder = Self::ZERO;
+        for bit in (0..self.bit_length()).rev() {
+            let (index, mask) = ((bit / 32) as usize, 1u32 << (bit % 32));
+            remainder = remainder.shifted_left(1);
+            remainder.limbs[0] |= (self.limbs[index] & mask != 0) as u32;
+            self.limbs[index] &= !mask;
+            if remainder.compare(divisor) != Ordering::Less {
+                remainder.sub(divisor);
+                self.limbs[index] |= mask;
+            }
+        }
+        remainder
+    }
+
+    /// `self - other`, where `other` must not be greater.
+    pub(crate) fn sub(&mut self, other: &Self) {
+        let mut borrow = 0;
+        for (limb, &subtrahend) in self.limbs.iter_mut().zip(other.limbs.iter()) {
+            let (difference, overflow) = limb.overflowing_sub(subtrahend);
+            let (difference, overflow_borrow) = difference.overflowing_sub(borrow);
+            *limb = difference;
+            borrow = (overflow || overflow_borrow) as u32;
+        }
+    }
+
+    pub(crate) fn shifted_left(&self, shift: u32) -> Self {
+        let mut result = Self::ZERO;
+        let (index, offset) = ((shift / 32) as usize, shift % 32);
+        for (target, &limb) in result.limbs[index..].iter_mut().zip(self.limbs.iter()) {
+            *target = limb << offset;
+        }
+        if offset > 0 {
+            for (target, &limb) in result.limbs[index + 1..].iter_mut().zip(self.limbs.iter()) {
+                *target |= limb >> (32 - offset);
+            }
+        }
+        result
+    }
+
+    /// `self >> shift`, with the bits below `shift` dropped.
+    pub(crate) fn shifted_right(&self, shift: u32) -> Self {
+        let mut result = Self::ZERO;
+        let (index, offset) = ((shift / 32) as usize, shift % 32);
+        for (target, &limb) in result.limbs.iter_mut().zip(self.limbs[index..].iter()) {
+            *target = limb >> offset;
+        }
+        if offset > 0 {
+            for (target, &limb) in result.limbs.iter_mut().zip(self.limbs[index + 1..].iter()) {
+                *target |= limb << (32 - offset);
+            }
+        }
+        result
+    }
+
+    /// 64 bits from bit `start` up.
+    pub(crate) fn bits_from(&self, start: u32) -> u64 {
+        let (index, offset) = ((start / 32) as usize, start % 32);
+        let mut wide = 0u128;
+        for (position, &limb) in self.limbs[index..].iter().take(3).enumerate() {
+            wide |= (limb as u128) << (32 * position);
+        }
+        (wide >> offset) as u64
+    }
+
+    /// Whether any bit below bit `end` is set.
+    #[cfg_attr(not(feature = "mpfr"), allow(dead_code))]
+    pub(crate) fn any_below(&self, end: u32) -> bool {
+        let (index, offset) = ((end / 32) as usize, end % 32);
+        self.limbs[..index].iter().any(|&limb| limb != 0)
+            || (offset > 0 && self.limbs[index] & ((1u32 << offset) - 1) != 0)
+    }
+
+    /// Remove the bits from bit `start` up, and return them. They must fit into 32 bits.
+    pub(crate) fn split_at(&mut self, start: u32) -> u32 {
+        let high = self.bits_from(start) as u32;
+        let (index, offset) = ((start / 32) as usize, start % 32);
+        self.limbs[index] &= ((1u64 << offset) - 1) as u32;
+        for limb in self.limbs[index + 1..].iter_mut() {
+            *limb = 0;
+        }
+        high
+    }
+}
@@ -0,0 +1,128 @@
+use core::convert::TryInto;
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Number of bytes of `UniFloat::to_be_bytes()` and `UniFloat::to_le_bytes()` for choice `c`:
+/// 4 for F32, 8 for F64, 16 for TwoFloat, 32 for QuadFloat, 24 for decimals, and for MPFR 9 plus 8
+/// for each (started) 64 bits of precision. It doesn't depend on the platform.
+pub const fn bytes_length(c: UniFloatChoice) -> usize {
+    match c {
+        UniFloatChoice::F32 => 4,
+        UniFloatChoice::F64 => 8,
+        UniFloatChoice::TwoFloat => 16,
+        UniFloatChoice::QuadFloat => 32,
+        UniFloatChoice::Decimal { .. } => 24,
+        UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+            9 + 8 * ((c.mpfr_bounds().precision_bits - 1) / 64 + 1),
+    }
+}
+
+/// Binary encoding that doesn't depend on the platform (neither on its endianness, nor on the
+/// width of GMP limbs), for storing values and reading them on another platform. The big endian
+/// layout is:
+/// - F32 and F64: the IEEE 754 bits, as `f32::to_be_bytes()` and `f64::to_be_bytes()`.
+/// - TwoFloat: the higher part, and then the lower part, each as `f64::to_be_bytes()`.
+/// - QuadFloat: the four components, from the highest, each as `f64::to_be_bytes()`.
+/// - Decimals: the coefficient (as `u128`, the lower 64 bits first), and then the exponent (as
+///   `i32`, in the lower 32 bits), the kind (0 for NaN, 1 for finite, 2 for infinities, in the
+///   next two bits) and the sign (in the next bit), each 64 bits as `u64::to_be_bytes()`.
+/// - MPFR: one byte for the sign (0 or 1), 8 bytes for the exponent (as `i64`), and then the
+///   significand as a big endian integer, aligned to the top of its 64-bit words. For NaN,
+///   infinities and zeros the exponent is `1 - i64::MAX`, `2 - i64::MAX` or `-i64::MAX`, and
+///   the significand is all zeros.
+///
+/// The little endian layout is the same, but with all bytes in reverse order.
+///
+/// The precision of MPFR isn't stored. Values can be read only with the same choice.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// The big endian layout (see above).
+    pub fn to_be_bytes(&self) -> [u8; bytes_length(C)] where [u8; bytes_length(C)]: Sized {
+        self.assert_copy_fixed();
+        let mut bytes = [0; bytes_length(C)];
+        #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(unused_mut))]
+        let mut start = 0;
+        #[cfg(not(feature = "f32_only"))]
+        #[cfg(feature = "mpfr")]
+        if C.in_mpfr() {
+            let (negative, exponent) = self.mpfr_sign_exponent();
+            bytes[0] = negative as u8;
+            bytes[1..9].copy_from_slice(&exponent.to_be_bytes());
+            start = 9;
+        }
+        let words = &mut bytes[start..];
+        if let UniFloatChoice::F32 = C {
+            words.copy_from_slice(&(self.word(0) as u32).to_be_bytes());
+        } else {
+            let word_count = Self::word_count();
+            for (position, chunk) in words.chunks_exact_mut(8).enumerate() {
+                // MPFR words are least significant first.
+                let index = if C.in_mpfr() { word_count - 1 - position } else { position };
+                chunk.copy_from_slice(&self.word(index).to_be_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// See `to_be_bytes()`.
+    pub fn to_le_bytes(&self) -> [u8; bytes_length(C)] where [u8; bytes_length(C)]: Sized {
+        let mut bytes = self.to_be_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    /// The counterpart of `to_be_bytes()`. It returns `None` if the bytes don't make a valid
+    /// value: if the parts of TwoFloat or QuadFloat aren't normalized, if a decimal has more digits
+    /// than its choice, or its exponent is beyond `DECIMAL_EXPONENT_LIMIT`, or for MPFR if the sign
+    /// byte isn't 0 or 1, if the significand isn't normalized or has bits below the precision, or
+    /// if the exponent is outside of the current exponent range.
+    pub fn from_be_bytes(bytes: [u8; bytes_length(C)]) -> Option<Self> where [u8; bytes_length(C)]: Sized {
+        let mut result = Self::NAN;
+        #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(unused_mut))]
+        let mut words = &bytes[..];
+        #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(unused_mut, unused_variables))]
+        let mut sign_exponent: Option<(bool, i64)> = None;
+        #[cfg(not(feature = "f32_only"))]
+        #[cfg(feature = "mpfr")]
+        if C.in_mpfr() {
+            let negative = match words[0] {
+                0 => false,
+                1 => true,
+                _ => return None
+            };
+            sign_exponent = Some((negative, i64::from_be_bytes(words[1..9].try_into().unwrap())));
+            words = &words[9..];
+        }
+        let mut valid = true;
+        if let UniFloatChoice::F32 = C {
+            valid &= result.set_word(0, u32::from_be_bytes(words.try_into().unwrap()) as u64);
+        } else {
+            let word_count = Self::word_count();
+            for (position, chunk) in words.chunks_exact(8).enumerate() {
+                let index = if C.in_mpfr() { word_count - 1 - position } else { position };
+                valid &= result.set_word(index, u64::from_be_bytes(chunk.try_into().unwrap()));
+            }
+        }
+        #[cfg(not(feature = "f32_only"))]
+        #[cfg(feature = "mpfr")]
+        if let Some((negative, exponent)) = sign_exponent {
+            valid &= result.set_mpfr_sign_exponent(negative, exponent);
+        }
+        if valid {
+            Some(result.released())
+        } else {
+            None
+        }
+    }
+
+    /// See `from_be_bytes()`.
+    pub fn from_le_bytes(mut bytes: [u8; bytes_length(C)]) -> Option<Self> where [u8; bytes_length(C)]: Sized {
+        bytes.reverse();
+        Self::from_be_bytes(bytes)
+    }
+}
@@ -0,0 +1,147 @@
+use core::num::FpCategory;
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(any(feature = "mpfr", all(feature = "softmpfr", not(feature = "f32_only"))))]
+use crate::mpfr;
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+
+/// Classification, as for Rust primitives.
+/// - TwoFloat: by the higher part, since the lower part is at most half an ulp of it. (Infinities
+///   may have NaN in the lower part, but they're still infinite.)
+/// - MPFR: by its special values (NaN, infinities and zeros have special exponents, rather than
+///   special bit patterns). MPFR has no subnormals, so its regular numbers are all normal, other
+///   than with `Underflow::Subnormal` (see `MpfrBounds::with_underflow()`).
+/// - Decimals: subnormal if non-zero, with fewer than all their digits at the least exponent.
+///   Their NaN has no sign.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub fn classify(&self) -> FpCategory {
+        self.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => self.f32s[0].classify(),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => self.f64s[0].classify(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat | UniFloatChoice::QuadFloat => self.twofloats[0].hi().classify(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => self.decimal().classify(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => unsafe {
+                let source = self.mpfr_src();
+                let x = source.as_ptr();
+                if mpfr::nan_p(x) != 0 {
+                    FpCategory::Nan
+                } else if mpfr::inf_p(x) != 0 {
+                    FpCategory::Infinite
+                } else if mpfr::zero_p(x) != 0 {
+                    FpCategory::Zero
+                } else if C.mpfr_bounds().is_subnormal_exponent(mpfr::get_exp(x)) {
+                    FpCategory::Subnormal
+                } else {
+                    FpCategory::Normal
+                }
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    #[inline]
+    pub fn is_nan(&self) -> bool {
+        self.classify() == FpCategory::Nan
+    }
+
+    #[inline]
+    pub fn is_infinite(&self) -> bool {
+        self.classify() == FpCategory::Infinite
+    }
+
+    /// Neither infinite nor NaN.
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        !matches!(self.classify(), FpCategory::Nan | FpCategory::Infinite)
+    }
+
+    /// Neither zero, subnormal, infinite nor NaN.
+    #[inline]
+    pub fn is_normal(&self) -> bool {
+        self.classify() == FpCategory::Normal
+    }
+
+    /// For MPFR only with `Underflow::Subnormal`.
+    #[inline]
+    pub fn is_subnormal(&self) -> bool {
+        self.classify() == FpCategory::Subnormal
+    }
+
+    /// `self`, or zero (with the sign of `self`) if `self` is subnormal. Call it on results of f32,
+    /// f64 and TwoFloat operations to emulate FPUs that flush subnormal results to zero. For MPFR
+    /// prefer `Underflow::FlushToZero`, which applies to all operations.
+    pub fn flushed_to_zero(&self) -> Self {
+        let subnormal = self.is_subnormal();
+        self.unary(|x| if subnormal { libm::copysignf(0.0, x) } else { x },
+            |x| if subnormal { libm::copysign(0.0, x) } else { x },
+            twofloat_op!(|x| if subnormal { TwoFloat::from(libm::copysign(0.0, x.hi())) } else { x }),
+            mpfr_op!(|r, x| unsafe {
+                if subnormal {
+                    mpfr::set_zero(r, if mpfr::signbit(x) != 0 { -1 } else { 1 });
+                } else {
+                    mpfr::set(r, x, mpfr::rnd_t::RNDN);
+                }
+                0
+            }))
+    }
+
+    /// Whether the sign bit is clear, including for 0 and NaN.
+    #[inline]
+    pub fn is_sign_positive(&self) -> bool {
+        !self.is_sign_negative()
+    }
+
+    /// Whether the sign bit is set, including for -0 and NaN.
+    pub fn is_sign_negative(&self) -> bool {
+        self.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => self.f32s[0].is_sign_negative(),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => self.f64s[0].is_sign_negative(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat | UniFloatChoice::QuadFloat => self.twofloats[0].hi().is_sign_negative(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => self.decimal().is_sign_negative(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+                unsafe { mpfr::signbit(self.mpfr_src().as_ptr()) != 0 },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use core::cmp::Ordering;
+#[cfg(any(feature = "mpfr", all(feature = "softmpfr", not(feature = "f32_only"))))]
+use crate::mpfr;
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+
+/// Comparison predicates. As per IEEE 754, NaN is unordered: all of `lt`, `le`, `gt`, `ge` are
+/// false if either side is NaN. Zeros compare equal, regardless of their sign.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// None if `self` or `other` is NaN.
+    fn compare(&self, other: &Self) -> Option<Ordering> {
+        self.assert_copy_fixed();
+        other.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => self.f32s[0].partial_cmp(&other.f32s[0]),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => self.f64s[0].partial_cmp(&other.f64s[0]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => self.twofloats[0].partial_cmp(&other.twofloats[0]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => self.quad().partial_cmp(&other.quad()),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => self.decimal().partial_cmp(&other.decimal()),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => unsafe {
+                let (x_source, y_source) = (self.mpfr_src(), other.mpfr_src());
+                let (x, y) = (x_source.as_ptr(), y_source.as_ptr());
+                if mpfr::unordered_p(x, y) != 0 {
+                    None
+                } else {
+                    Some(mpfr::cmp(x, y).cmp(&0))
+                }
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    #[inline]
+    pub fn lt(&self, other: &Self) -> bool {
+        self.compare(other) == Some(Ordering::Less)
+    }
+
+    #[inline]
+    pub fn le(&self, other: &Self) -> bool {
+        matches!(self.compare(other), Some(Ordering::Less) | Some(Ordering::Equal))
+    }
+
+    #[inline]
+    pub fn gt(&self, other: &Self) -> bool {
+        self.compare(other) == Some(Ordering::Greater)
+    }
+
+    #[inline]
+    pub fn ge(&self, other: &Self) -> bool {
+        matches!(self.compare(other), Some(Ordering::Greater) | Some(Ordering::Equal))
+    }
+
+    /// Whether `self` or `other` (or both) are NaN.
+    #[inline]
+    pub fn unordered(&self, other: &Self) -> bool {
+        self.compare(other).is_none()
+    }
+}
+
+/// Numeric equality (rather than a bitwise one): NaN is not equal to anything, and zeros are
+/// equal regardless of their sign. Both sides must be "copy fixed".
+impl <const C: UniFloatChoice> PartialEq for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.compare(other) == Some(Ordering::Equal)
+    }
+}
+
+/// Both sides must be "copy fixed".
+impl <const C: UniFloatChoice> PartialOrd for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.compare(other)
+    }
+
+    #[inline]
+    fn lt(&self, other: &Self) -> bool {
+        UniFloat::lt(self, other)
+    }
+
+    #[inline]
+    fn le(&self, other: &Self) -> bool {
+        UniFloat::le(self, other)
+    }
+
+    #[inline]
+    fn gt(&self, other: &Self) -> bool {
+        UniFloat::gt(self, other)
+    }
+
+    #[inline]
+    fn ge(&self, other: &Self) -> bool {
+        UniFloat::ge(self, other)
+    }
+}
+
+/// IEEE 754-2019 minimum and maximum. -0 is less than +0 here. `min()` and `max()` return NaN if
+/// either side is NaN (`minimum` and `maximum`), while `min_num()` and `max_num()` return the other
+/// side (`minimumNumber` and `maximumNumber`).
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn pick(&self, other: &Self, maximum: bool, propagate_nan: bool) -> Self {
+        self.binary(other,
+            |x, y| pick(x, y, maximum, propagate_nan, f32::is_nan, f32::is_sign_negative),
+            |x, y| pick(x, y, maximum, propagate_nan, f64::is_nan, f64::is_sign_negative),
+            twofloat_op!(|x, y| pick(x, y, maximum, propagate_nan, |x| x.hi().is_nan(), |x| x.hi().is_sign_negative())),
+            mpfr_op!(|r, x, y| unsafe {
+                if propagate_nan && (mpfr::nan_p(x) != 0 || mpfr::nan_p(y) != 0) {
+                    mpfr::set_nan(r);
+                    0
+                } else if maximum {
+                    // Both of them pick the non-NaN side, and -0 for `min()` (+0 for `max()`).
+                    mpfr::max(r, x, y, mpfr::rnd_t::RNDN)
+                } else {
+                    mpfr::min(r, x, y, mpfr::rnd_t::RNDN)
+                }
+            }))
+    }
+
+    pub fn min(&self, other: &Self) -> Self {
+        self.pick(other, false, true)
+    }
+
+    pub fn max(&self, other: &Self) -> Self {
+        self.pick(other, true, true)
+    }
+
+    pub fn min_num(&self, other: &Self) -> Self {
+        self.pick(other, false, false)
+    }
+
+    pub fn max_num(&self, other: &Self) -> Self {
+        self.pick(other, true, false)
+    }
+
+    /// `self` limited to `lower..=upper`, as `max(lower).min(upper)`: NaN if `self` is NaN.
+    ///
+    /// Panics if `lower > upper`, or if either of them is NaN (as `f64::clamp()`).
+    pub fn clamp(&self, lower: &Self, upper: &Self) -> Self {
+        assert!(lower.le(upper), "clamp() needs lower <= upper, and neither of them NaN.");
+        let mut bounded_below = self.max(lower);
+        bounded_below.copied();
+        bounded_below.min(upper)
+    }
+}
+
+/// The lesser (or with `maximum`, the greater) of `x` and `y`, as per `UniFloat::min()` and the
+/// others. A NaN comes out as it is.
+fn pick<T: Copy + PartialOrd>(x: T, y: T, maximum: bool, propagate_nan: bool, is_nan: impl Fn(T) -> bool,
+    is_negative: impl Fn(T) -> bool) -> T {
+    match (is_nan(x), is_nan(y)) {
+        (true, _) if propagate_nan => x,
+        (_, true) if propagate_nan => y,
+        (true, _) => y,
+        (_, true) => x,
+        // Zeros of either sign are equal, so only the sign is left to tell them apart.
+        _ if x == y => if is_negative(x) != maximum { x } else { y },
+        _ => if (x < y) != maximum { x } else { y }
+    }
+}
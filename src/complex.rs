@@ -0,0 +1,251 @@
+use core::{num::FpCategory, ops};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// A complex number `re` + `im` i, as a pair of `UniFloat<C>`. Each part is rounded to nearest
+/// (as `UniFloat`) at every step, so results aren't correctly rounded as complex numbers. Accuracy:
+/// - Multiplication uses fused multiply-add (`UniFloat::mul_add()`), and division uses Smith's
+///   algorithm, so neither overflows where the result doesn't.
+/// - `exp()` and `from_polar()` need sine and cosine of a real argument. For f32 and f64 they're
+///   as `libm`, and for MPFR they're correctly rounded. TwoFloat uses TwoFloat's own algorithms,
+///   which lose accuracy for large arguments (unlike `UniFloat::sin_pi()`).
+#[derive(Clone, Copy)]
+pub struct UniComplex<const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    re: UniFloat<C>,
+    im: UniFloat<C>,
+}
+
+impl <const C: UniFloatChoice> UniComplex<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub fn new(re: &UniFloat<C>, im: &UniFloat<C>) -> Self {
+        Self { re: *re, im: *im }.released()
+    }
+
+    /// `re` + 0i.
+    pub fn from_real(re: &UniFloat<C>) -> Self {
+        let mut zero = UniFloat::<C>::ZERO;
+        zero.copied();
+        Self::new(re, &zero)
+    }
+
+    /// `magnitude` (cos `angle` + i sin `angle`).
+    pub fn from_polar(magnitude: &UniFloat<C>, angle: &UniFloat<C>) -> Self {
+        let (mut sine, mut cosine) = (angle.sin(), angle.cos());
+        sine.copied();
+        cosine.copied();
+        sine *= magnitude;
+        cosine *= magnitude;
+        Self { re: cosine, im: sine }.released()
+    }
+
+    /// From parts returned by value.
+    fn from_parts(mut re: UniFloat<C>, mut im: UniFloat<C>) -> Self {
+        re.copied();
+        im.copied();
+        Self::new(&re, &im)
+    }
+
+    /// As `UniFloat::copied()`, for both parts.
+    #[inline]
+    pub fn copied(&mut self) -> &mut Self {
+        self.re.copied();
+        self.im.copied();
+        self
+    }
+
+    /// As `UniFloat::released()`, for both parts.
+    fn released(self) -> Self {
+        Self { re: self.re.released(), im: self.im.released() }
+    }
+
+    pub fn re(&self) -> &UniFloat<C> {
+        &self.re
+    }
+
+    pub fn im(&self) -> &UniFloat<C> {
+        &self.im
+    }
+
+    /// Complex conjugate `re` - `im` i. Exact.
+    pub fn conj(&self) -> Self {
+        Self::from_parts(self.re, -&self.im)
+    }
+
+    /// |`self`|, by `UniFloat::hypot()`, so it doesn't overflow where the result doesn't.
+    pub fn abs(&self) -> UniFloat<C> {
+        self.re.hypot(&self.im)
+    }
+
+    /// Argument (phase), in [-π, π], by `UniFloat::atan2()` (so with its signed zeros).
+    pub fn arg(&self) -> UniFloat<C> {
+        self.im.atan2(&self.re)
+    }
+
+    /// (`abs()`, `arg()`).
+    pub fn to_polar(&self) -> (UniFloat<C>, UniFloat<C>) {
+        (self.abs(), self.arg())
+    }
+
+    /// e^`self` = e^`re` (cos `im` + i sin `im`). For a real `self` (`im` being zero) it's
+    /// `UniFloat::exp()` of `re`, with `im` kept.
+    pub fn exp(&self) -> Self {
+        let mut magnitude = self.re.exp();
+        magnitude.copied();
+        if self.im.classify() == FpCategory::Zero {
+            Self::new(&magnitude, &self.im)
+        } else {
+            Self::from_polar(&magnitude, &self.im)
+        }
+    }
+
+    /// Principal natural logarithm: ln |`self`| + i `arg()`. ln 0 is -Inf + i `arg()`.
+    pub fn ln(&self) -> Self {
+        let mut magnitude = self.abs();
+        magnitude.copied();
+        Self::from_parts(magnitude.ln(), self.arg())
+    }
+
+    /// Principal square root, with `re` >= 0 and `im` of the same sign as `im` of `self`. It
+    /// avoids cancellation: for t = sqrt((|`re`| + |`self`|) / 2) it's t + i `im` / 2t for
+    /// non-negative `re`, and |`im`| / 2t ± i t otherwise. sqrt(x ± i Inf) is +Inf ± i Inf (even for
+    /// NaN x).
+    pub fn sqrt(&self) -> Self {
+        if self.im.is_infinite() {
+            return Self::from_parts(UniFloat::<C>::INFINITY, self.im);
+        }
+        if self.re.classify() == FpCategory::Zero && self.im.classify() == FpCategory::Zero {
+            return Self::from_parts(UniFloat::<C>::ZERO, self.im);
+        }
+        // Halving each side first, so the sum doesn't overflow.
+        let (mut magnitude, mut t) = (self.abs(), self.re.abs());
+        magnitude.copied();
+        t.copied();
+        let (mut half_magnitude, half_re) = (magnitude.ldexp(-1), t.ldexp(-1));
+        half_magnitude.copied();
+        t <<= half_re;
+        t += &half_magnitude;
+        let root = t.sqrt();
+        t <<= root;
+        let mut other = self.im.abs();
+        other.copied();
+        other /= &t;
+        let half_other = other.ldexp(-1);
+        other <<= half_other;
+        if self.re.is_sign_negative() {
+            Self::from_parts(other.released(), t.copysign(&self.im))
+        } else {
+            Self::from_parts(t.released(), other.copysign(&self.im))
+        }
+    }
+}
+
+/// `x` op `y`, by `assign` (one of the modify-and-assign operators).
+pub(crate) fn combined<const C: UniFloatChoice>(x: &UniFloat<C>, y: &UniFloat<C>, assign: impl FnOnce(&mut UniFloat<C>, &UniFloat<C>)) -> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut result = UniFloat::<C>::NAN;
+    result <<= x;
+    assign(&mut result, y);
+    result.released()
+}
+
+/// `x` * `y` + `z` * `w`, with the second product rounded first, and then fused into the first.
+pub(crate) fn sum_of_products<const C: UniFloatChoice>(x: &UniFloat<C>, y: &UniFloat<C>, z: &UniFloat<C>, w: &UniFloat<C>) -> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut product = combined(z, w, |r, w| *r *= w);
+    product.copied();
+    x.mul_add(y, &product)
+}
+
+/// -`re` - `im` i. Exact.
+impl <const C: UniFloatChoice> ops::Neg for &UniComplex<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Output = UniComplex<C>;
+    fn neg(self) -> Self::Output {
+        UniComplex::from_parts(-&self.re, -&self.im)
+    }
+}
+
+// Binary operators (+, -, *, /) on references.
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, |$x:ident, $y:ident| $op:expr) => {
+        impl <const C: UniFloatChoice> ops::$trait<&UniComplex<C>> for &UniComplex<C> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        {
+            type Output = UniComplex<C>;
+            fn $method(self, other: &UniComplex<C>) -> Self::Output {
+                let ($x, $y) = (self, other);
+                $op
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, |x, y| UniComplex::from_parts(
+    combined(&x.re, &y.re, |r, y| *r += y), combined(&x.im, &y.im, |r, y| *r += y)));
+impl_binary_op!(Sub, sub, |x, y| UniComplex::from_parts(
+    combined(&x.re, &y.re, |r, y| *r -= y), combined(&x.im, &y.im, |r, y| *r -= y)));
+// (a + bi)(c + di) = (ac - bd) + (ad + bc)i.
+impl_binary_op!(Mul, mul, |x, y| {
+    let mut negated = -&x.im;
+    negated.copied();
+    UniComplex::from_parts(sum_of_products(&x.re, &y.re, &negated, &y.im), sum_of_products(&x.re, &y.im, &x.im, &y.re))
+});
+// Smith's algorithm: for |c| >= |d| and r = d / c, (a + bi) / (c + di) = ((a + br) + (b - ar)i) /
+// (c + dr), and the other way around for |c| < |d|. Division by zero gives NaN parts.
+impl_binary_op!(Div, div, |x, y| {
+    let (mut c_magnitude, mut d_magnitude) = (y.re.abs(), y.im.abs());
+    c_magnitude.copied();
+    d_magnitude.copied();
+    let mut negated = -&x.re;
+    negated.copied();
+    let (mut re, mut im, mut denominator);
+    if c_magnitude.ge(&d_magnitude) {
+        let mut ratio = combined(&y.im, &y.re, |r, y| *r /= y);
+        ratio.copied();
+        denominator = y.im.mul_add(&ratio, &y.re);
+        re = x.im.mul_add(&ratio, &x.re);
+        im = negated.mul_add(&ratio, &x.im);
+    } else {
+        let mut ratio = combined(&y.re, &y.im, |r, y| *r /= y);
+        ratio.copied();
+        denominator = y.re.mul_add(&ratio, &y.im);
+        re = x.re.mul_add(&ratio, &x.im);
+        im = x.im.mul_add(&ratio, &negated);
+    }
+    denominator.copied();
+    re.copied();
+    im.copied();
+    re /= &denominator;
+    im /= &denominator;
+    UniComplex::from_parts(re.released(), im.released())
+});
@@ -0,0 +1,212 @@
+use crate::{mpfr, TwoFloat};
+#[cfg(feature = "mpfr")]
+use core::mem::MaybeUninit;
+
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::decimal::Dec;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::quadfloat::Quad;
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+
+/// Euler-Mascheroni constant γ as double-double: the nearest f64, and the rest.
+const EULER: (f64, f64) = (0.5772156649015329, -4.942915152430645e-18);
+/// Catalan's constant G as double-double: the nearest f64, and the rest.
+const CATALAN: (f64, f64) = (0.915965594177219, 3.747558421514984e-18);
+
+/// Mathematical constants, rounded to the precision of `C`: `core::f32::consts` and
+/// `core::f64::consts` for f32 and f64, `twofloat::consts` for TwoFloat, and correctly rounded
+/// ones from MPFR (`mpfr::const_pi()` and so on) at its precision (and at 212 bits for
+/// QuadFloat), rather than f64 constants converted. Decimals get them from MPFR at 192 bits,
+/// rounded to their digits.
+///
+/// They're functions rather than associated constants, because MPFR computes them at runtime
+/// (and it caches them).
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn constant(f32_value: f32, f64_value: f64, twofloat_value: impl FnOnce() -> TwoFloat,
+        mpfr_op: impl FnOnce(mpfr::mpfr_ptr) -> i32) -> Self {
+        let mut result = Self::NAN;
+        result.copied();
+        result.assign_constant(f32_value, f64_value, twofloat_value, mpfr_op);
+        result.released()
+    }
+
+    /// Sets `self` (which must be copy fixed) in place.
+    /// `twofloat_value` and `mpfr_op` are closures, as TwoFloat and MPFR may be left out.
+    #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr"), not(feature = "twofloat")), allow(unused_variables))]
+    fn assign_constant(&mut self, f32_value: f32, f64_value: f64, twofloat_value: impl FnOnce() -> TwoFloat,
+        mpfr_op: impl FnOnce(mpfr::mpfr_ptr) -> i32) {
+        self.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => self.f32s[0] = f32_value,
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => self.f64s[0] = f64_value,
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => self.twofloats[0] = twofloat_value(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(all(feature = "mpfr", feature = "twofloat"))]
+            UniFloatChoice::QuadFloat => self.set_quad(Quad::via_mpfr([], |r, _| mpfr_op(r))),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => self.set_decimal(Dec::via_mpfr(digits, [], |r, _| mpfr_op(r))),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                mpfr_op(self.mpfr_mut());
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    /// π.
+    pub fn pi() -> Self {
+        Self::constant(core::f32::consts::PI, core::f64::consts::PI, twofloat_op!(|| twofloat::consts::PI),
+            mpfr_op!(|r| unsafe { mpfr::const_pi(r, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// 2π. Scaling by 2 is exact.
+    pub fn tau() -> Self {
+        Self::constant(core::f32::consts::TAU, core::f64::consts::TAU, twofloat_op!(|| twofloat::consts::TAU),
+            mpfr_op!(|r| unsafe {
+                let ternary = mpfr::const_pi(r, mpfr::rnd_t::RNDN);
+                mpfr::mul_2ui(r, r, 1, mpfr::rnd_t::RNDN);
+                ternary
+            }))
+    }
+
+    /// π/2. Scaling by 1/2 is exact.
+    pub fn frac_pi_2() -> Self {
+        Self::constant(core::f32::consts::FRAC_PI_2, core::f64::consts::FRAC_PI_2, twofloat_op!(|| twofloat::consts::FRAC_PI_2),
+            mpfr_op!(|r| unsafe {
+                let ternary = mpfr::const_pi(r, mpfr::rnd_t::RNDN);
+                mpfr::div_2ui(r, r, 1, mpfr::rnd_t::RNDN);
+                ternary
+            }))
+    }
+
+    /// Euler's number e.
+    pub fn e() -> Self {
+        Self::constant(core::f32::consts::E, core::f64::consts::E, twofloat_op!(|| twofloat::consts::E),
+            mpfr_op!(|r| unsafe {
+                mpfr::set_ui(r, 1, mpfr::rnd_t::RNDN);
+                mpfr::exp(r, r, mpfr::rnd_t::RNDN)
+            }))
+    }
+
+    /// ln(2).
+    pub fn ln_2() -> Self {
+        Self::constant(core::f32::consts::LN_2, core::f64::consts::LN_2, twofloat_op!(|| twofloat::consts::LN_2),
+            mpfr_op!(|r| unsafe { mpfr::const_log2(r, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// ln(10).
+    pub fn ln_10() -> Self {
+        Self::constant(core::f32::consts::LN_10, core::f64::consts::LN_10, twofloat_op!(|| twofloat::consts::LN_10),
+            mpfr_op!(|r| unsafe { mpfr::log_ui(r, 10, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// log2(e) = 1 / ln(2).
+    pub fn log2_e() -> Self {
+        Self::constant(core::f32::consts::LOG2_E, core::f64::consts::LOG2_E, twofloat_op!(|| twofloat::consts::LOG2_E),
+            mpfr_op!(|r| unsafe { mpfr_reciprocal_ln(r, 2) }))
+    }
+
+    /// log10(e) = 1 / ln(10).
+    pub fn log10_e() -> Self {
+        Self::constant(core::f32::consts::LOG10_E, core::f64::consts::LOG10_E, twofloat_op!(|| twofloat::consts::LOG10_E),
+            mpfr_op!(|r| unsafe { mpfr_reciprocal_ln(r, 10) }))
+    }
+
+    /// sqrt(2).
+    pub fn sqrt_2() -> Self {
+        Self::constant(core::f32::consts::SQRT_2, core::f64::consts::SQRT_2, twofloat_op!(|| twofloat::consts::SQRT_2),
+            mpfr_op!(|r| unsafe { mpfr::sqrt_ui(r, 2, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// 1 / sqrt(2) = sqrt(2) / 2. Scaling by 1/2 is exact.
+    pub fn frac_1_sqrt_2() -> Self {
+        Self::constant(core::f32::consts::FRAC_1_SQRT_2, core::f64::consts::FRAC_1_SQRT_2,
+            twofloat_op!(|| twofloat::consts::FRAC_1_SQRT_2),
+            mpfr_op!(|r| unsafe {
+                let ternary = mpfr::sqrt_ui(r, 2, mpfr::rnd_t::RNDN);
+                mpfr::div_2ui(r, r, 1, mpfr::rnd_t::RNDN);
+                ternary
+            }))
+    }
+
+    /// Euler-Mascheroni constant γ. For MPFR that's slower than the constants above: see
+    /// `assign_const_euler()`.
+    pub fn const_euler() -> Self {
+        let mut result = Self::NAN;
+        result.copied();
+        result.assign_const_euler();
+        result.released()
+    }
+
+    /// Sets `self` to `const_euler()` in place. For MPFR, computing γ takes time that grows faster
+    /// than the precision. MPFR caches it (per thread), but it recomputes it whenever a higher
+    /// precision is asked for than the cached one, and `mpfr::free_cache()` drops it. So compute it
+    /// once into a variable, and reuse that.
+    pub fn assign_const_euler(&mut self) {
+        self.assign_constant(EULER.0 as f32, EULER.0, twofloat_op!(|| TwoFloat::new_add(EULER.0, EULER.1)),
+            mpfr_op!(|r| unsafe { mpfr::const_euler(r, mpfr::rnd_t::RNDN) }));
+    }
+
+    /// Catalan's constant G. As for `const_euler()`, see `assign_const_catalan()`.
+    pub fn const_catalan() -> Self {
+        let mut result = Self::NAN;
+        result.copied();
+        result.assign_const_catalan();
+        result.released()
+    }
+
+    /// Sets `self` to `const_catalan()` in place. As for `assign_const_euler()`, compute it once to
+    /// reuse it.
+    pub fn assign_const_catalan(&mut self) {
+        self.assign_constant(CATALAN.0 as f32, CATALAN.0, twofloat_op!(|| TwoFloat::new_add(CATALAN.0, CATALAN.1)),
+            mpfr_op!(|r| unsafe { mpfr::const_catalan(r, mpfr::rnd_t::RNDN) }));
+    }
+}
+
+/// Correctly rounded 1 / ln(n), by Ziv's strategy (as `mpfr_log()` in the logarithm module). It's
+/// irrational (for n > 1), so it can always be rounded eventually.
+#[cfg(feature = "mpfr")]
+unsafe fn mpfr_reciprocal_ln(r: mpfr::mpfr_ptr, n: u32) -> i32 {
+    let precision = mpfr::get_prec(r);
+    let mut working = precision + 32;
+    let mut reciprocal = MaybeUninit::uninit();
+    mpfr::init2(reciprocal.as_mut_ptr(), working);
+    let reciprocal = reciprocal.as_mut_ptr();
+    let ternary = loop {
+        mpfr::log_ui(reciprocal, n.into(), mpfr::rnd_t::RNDN);
+        mpfr::ui_div(reciprocal, 1, reciprocal, mpfr::rnd_t::RNDN);
+        // Two roundings, each with relative error up to 2^-working.
+        if mpfr::can_round(reciprocal, (working - 2) as mpfr::exp_t, mpfr::rnd_t::RNDN, mpfr::rnd_t::RNDZ, precision + 1) != 0
+            || working > 2 * precision + 256 {
+            break mpfr::set(r, reciprocal, mpfr::rnd_t::RNDN);
+        }
+        working += working / 2;
+        mpfr::set_prec(reciprocal, working);
+    };
+    mpfr::clear(reciprocal);
+    ternary
+}
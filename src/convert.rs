@@ -0,0 +1,1237 @@
+use {core::convert::TryFrom, core::fmt};
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use crate::gmp;
+#[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+use crate::mpfr;
+
+#[cfg(feature = "mpfr")]
+use crate::hex::{EXPONENT_LIMIT, HexLiteral};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::{decimal::Dec, parts::limit_exponent_range, rounding::mpfr_rounded};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::quadfloat::Quad;
+use crate::rounding::{UnsupportedRounding, next_down_f32, next_down_f64, next_up_f32, next_up_f64};
+use crate::{MpfrBounds, RoundingMode, UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+
+/// Enough to hold any u128 exactly.
+#[allow(dead_code)]
+pub(crate) const MPFR_128_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(128)
+};
+#[allow(dead_code)]
+pub(crate) type UniMpfr128bit = UniFloat<{ MPFR_128_BITS }>;
+
+/// Enough to hold any TwoFloat exactly: from 2^1023 down to 2^-1074 (the least subnormal f64).
+#[allow(dead_code)]
+pub(crate) const MPFR_TWOFLOAT_EXACT: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(1023 + 1074 + 1)
+};
+#[allow(dead_code)]
+pub(crate) type UniMpfrTwoFloatExact = UniFloat<{ MPFR_TWOFLOAT_EXACT }>;
+
+/// Exact. Both halves are exact in f64, since the lower half has 11 bits at most.
+#[cfg(feature = "twofloat")]
+fn twofloat_from_u64(value: u64) -> TwoFloat {
+    let high = value as f64;
+    let low = (value as i128 - high as i128) as f64;
+    TwoFloat::new_add(high, low)
+}
+
+#[cfg(feature = "twofloat")]
+fn twofloat_from_i64(value: i64) -> TwoFloat {
+    let magnitude = twofloat_from_u64(value.unsigned_abs());
+    if value < 0 { -magnitude } else { magnitude }
+}
+
+/// Exact if `value` fits into 64 bits. Otherwise rounded at double-double precision.
+#[cfg(feature = "twofloat")]
+fn twofloat_from_u128(value: u128) -> TwoFloat {
+    twofloat_from_u64((value >> 64) as u64) * 18_446_744_073_709_551_616.0 // 2^64
+        + twofloat_from_u64(value as u64)
+}
+
+/// Constructors from Rust primitives. They all round to nearest (ties to even). The result is
+/// exact if the value fits into the precision of `C`: 24 bits for F32, 53 bits for F64,
+/// `precision_bits` for MPFR. TwoFloat holds any f32, f64 and 64-bit integer exactly, and
+/// QuadFloat any 128-bit integer, too. Decimals hold integers of up to `digits` digits exactly
+/// (with exponent 0).
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn from_f32(value: f32) -> Self {
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = value,
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = value as f64,
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = TwoFloat::from(value as f64),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => result.set_quad(Quad::from_f64(value as f64)),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } =>
+                result.set_decimal(Dec::via_mpfr(digits, [], |r, _| unsafe { mpfr::set_flt(r, value, mpfr::rnd_t::RNDN) })),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                result.copied();
+                unsafe { mpfr::set_flt(result.mpfr_mut(), value, mpfr::rnd_t::RNDN) };
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+
+    fn from_i64(value: i64) -> Self {
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = value as f32,
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = value as f64,
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = twofloat_from_i64(value),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => result.set_quad(Quad::from_twofloat(twofloat_from_i64(value))),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => result.set_decimal(Dec::new(digits, value as i128, 0)),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                result.copied();
+                unsafe { mpfr::set_sj(result.mpfr_mut(), value, mpfr::rnd_t::RNDN) };
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+
+    fn from_u64(value: u64) -> Self {
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = value as f32,
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = value as f64,
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = twofloat_from_u64(value),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => result.set_quad(Quad::from_twofloat(twofloat_from_u64(value))),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => result.set_decimal(Dec::from_u128(digits, value as u128)),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                result.copied();
+                unsafe { mpfr::set_uj(result.mpfr_mut(), value, mpfr::rnd_t::RNDN) };
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+
+    fn from_i128(value: i128) -> Self {
+        let mut result = Self::from_u128(value.unsigned_abs());
+        if value < 0 {
+            result = -result;
+        }
+        result
+    }
+
+    fn from_u128(value: u128) -> Self {
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = value as f32,
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = value as f64,
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = twofloat_from_u128(value),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                // Both halves are exact, and so is their sum.
+                let high = Quad::from_twofloat(twofloat_from_u64((value >> 64) as u64)) * Quad::from_f64(18_446_744_073_709_551_616.0);
+                result.set_quad(high + Quad::from_twofloat(twofloat_from_u64(value as u64)));
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => result.set_decimal(Dec::from_u128(digits, value)),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                // Exact in 128 bits, and then rounded once only.
+                let (mut exact, mut low) = (UniMpfr128bit::NAN, UniMpfr128bit::NAN);
+                exact.copied();
+                low.copied();
+                result.copied();
+                unsafe {
+                    let (exact, low) = (exact.mpfr_mut(), low.mpfr_mut());
+                    mpfr::set_uj(exact, (value >> 64) as u64, mpfr::rnd_t::RNDN);
+                    mpfr::mul_2ui(exact, exact, 64, mpfr::rnd_t::RNDN);
+                    mpfr::set_uj(low, value as u64, mpfr::rnd_t::RNDN);
+                    mpfr::add(exact, exact, low, mpfr::rnd_t::RNDN);
+                    mpfr::set(result.mpfr_mut(), exact, mpfr::rnd_t::RNDN);
+                }
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+
+    /// Rounded to nearest (once only).
+    #[cfg(feature = "twofloat")]
+    fn from_twofloat(value: TwoFloat) -> Self {
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = round_to_f32(value.hi(), value.lo(), RoundingMode::Nearest).value(),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = value.hi(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = value,
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => result.set_quad(Quad::from_twofloat(value)),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } | UniFloatChoice::Decimal { .. } => {
+                let mut exact = UniMpfrTwoFloatExact::NAN;
+                exact.copied();
+                unsafe {
+                    let exact = exact.mpfr_mut();
+                    mpfr::set_d(exact, value.hi(), mpfr::rnd_t::RNDN);
+                    mpfr::add_d(exact, exact, value.lo(), mpfr::rnd_t::RNDN);
+                }
+                result = Self::from_mpfr(exact.mpfr_src().as_ptr());
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+
+    /// Rounded to nearest. For TwoFloat (and QuadFloat) the lower parts are rounded from the exact
+    /// difference, and decimals are rounded once, unless `value` has more than 2098 bits of
+    /// precision.
+    #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+    #[cfg_attr(all(feature = "f32_only", not(feature = "mpfr")), allow(dead_code))]
+    pub(crate) fn from_mpfr(value: mpfr::mpfr_srcptr) -> Self {
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = unsafe { mpfr::get_flt(value, mpfr::rnd_t::RNDN) },
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = unsafe { mpfr::get_d(value, mpfr::rnd_t::RNDN) },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => unsafe {
+                let high = mpfr::get_d(value, mpfr::rnd_t::RNDN);
+                result.twofloats[0] = if high.is_finite() && high != 0.0 {
+                    let mut low = UniMpfrTwoFloatExact::NAN;
+                    low.copied();
+                    let low = low.mpfr_mut();
+                    mpfr::sub_d(low, value, high, mpfr::rnd_t::RNDN);
+                    TwoFloat::new_add(high, mpfr::get_d(low, mpfr::rnd_t::RNDN))
+                } else {
+                    TwoFloat::from(high)
+                };
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => result.set_quad(unsafe { Quad::from_mpfr(value) }),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => result.set_decimal(unsafe { Dec::from_mpfr(digits, value, RoundingMode::Nearest) }),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                result.copied();
+                unsafe { mpfr::set(result.mpfr_mut(), value, mpfr::rnd_t::RNDN) };
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+}
+
+macro_rules! impl_from {
+    ($($primitive:ty => $constructor:ident as $via:ty),+) => {
+        $(
+            impl <const C: UniFloatChoice> From<$primitive> for UniFloat<C> where
+            [f32; f32_parts_length(C)]: Sized,
+            [f64; f64_parts_length(C)]: Sized,
+            [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+            [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+            [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+            {
+                #[inline]
+                fn from(value: $primitive) -> Self {
+                    Self::$constructor(value as $via)
+                }
+            }
+        )+
+    };
+}
+
+impl_from!(
+    f32 => from_f32 as f32, f64 => from_f64 as f64,
+    i8 => from_i64 as i64, i16 => from_i64 as i64, i32 => from_i64 as i64, i64 => from_i64 as i64,
+    isize => from_i64 as i64, i128 => from_i128 as i128,
+    u8 => from_u64 as u64, u16 => from_u64 as u64, u32 => from_u64 as u64, u64 => from_u64 as u64,
+    usize => from_u64 as u64, u128 => from_u128 as u128
+);
+
+/// Result of a conversion to a Rust primitive, which may lose information.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Conversion<T> {
+    /// The primitive holds exactly the same value.
+    Exact(T),
+    /// The value was rounded in the requested direction.
+    Rounded(T),
+    /// The value is out of range of the primitive. Integers saturate at MIN or MAX. Floats become
+    /// Inf, or MAX, if the rounding direction is toward zero (both with the sign of the value).
+    Overflow(T),
+    /// The value was NaN. Floats hold NaN, integers hold 0.
+    NaN(T),
+}
+
+impl <T> Conversion<T> {
+    /// The converted value, regardless of the loss.
+    pub fn value(self) -> T {
+        match self {
+            Conversion::Exact(value) | Conversion::Rounded(value) | Conversion::Overflow(value)
+            | Conversion::NaN(value) => value
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        matches!(self, Conversion::Exact(_))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToIntErrorKind {
+    /// The value was NaN.
+    NaN,
+    /// The rounded value is less than the integer's MIN (including -Inf).
+    NegativeOverflow,
+    /// The rounded value is greater than the integer's MAX (including Inf).
+    PositiveOverflow,
+}
+
+/// Error from a checked conversion to an integer, like `UniFloat::to_i64_round()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ToIntError {
+    kind: ToIntErrorKind,
+}
+
+impl ToIntError {
+    pub(crate) fn new(kind: ToIntErrorKind) -> Self {
+        Self { kind }
+    }
+
+    pub fn kind(&self) -> ToIntErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ToIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.kind {
+            ToIntErrorKind::NaN => "cannot convert NaN to integer",
+            ToIntErrorKind::NegativeOverflow => "value too small for the integer type",
+            ToIntErrorKind::PositiveOverflow => "value too large for the integer type",
+        })
+    }
+}
+
+/// For overflows in the direction of `rounding`: whether the result is MAX (rather than Inf).
+pub(crate) fn overflows_to_max(negative: bool, rounding: RoundingMode) -> bool {
+    match rounding {
+        RoundingMode::Nearest | RoundingMode::ToNearestAway | RoundingMode::Faithful => false,
+        RoundingMode::TowardZero => true,
+        RoundingMode::Up => negative,
+        RoundingMode::Down => !negative,
+    }
+}
+
+/// `high + low` rounded to f32. `low` is zero, or it's at most half an ulp of `high` (as in a
+/// normalized TwoFloat).
+pub(crate) fn round_to_f32(high: f64, low: f64, rounding: RoundingMode) -> Conversion<f32> {
+    if high.is_nan() {
+        return Conversion::NaN(f32::NAN);
+    } else if high.is_infinite() {
+        return Conversion::Exact(high as f32);
+    }
+    let mut result = high as f32;
+    if result.is_infinite() {
+        return Conversion::Overflow(
+            if overflows_to_max(high < 0.0, rounding) { libm::copysignf(f32::MAX, result) } else { result });
+    }
+    // (high + low) - result. `high - result` is exact. Adding `low` keeps the sign.
+    let difference = (high - result as f64) + low;
+    if difference == 0.0 {
+        return Conversion::Exact(result);
+    }
+    match rounding {
+        RoundingMode::Nearest | RoundingMode::ToNearestAway | RoundingMode::Faithful => {
+            let neighbour = if difference > 0.0 { next_up_f32(result) } else { next_down_f32(result) };
+            let (distance, result_distance) = (libm::fabs((high - neighbour as f64) + low), libm::fabs(difference));
+            // `high as f32` rounds ties to even already.
+            if distance < result_distance || (distance == result_distance && rounding == RoundingMode::ToNearestAway
+                && libm::fabsf(neighbour) > libm::fabsf(result)) {
+                result = neighbour;
+            }
+        },
+        RoundingMode::TowardZero => if (result > 0.0 && difference < 0.0) || (result < 0.0 && difference > 0.0) {
+            result = if result > 0.0 { next_down_f32(result) } else { next_up_f32(result) };
+        },
+        RoundingMode::Up => if difference > 0.0 {
+            result = next_up_f32(result);
+        },
+        RoundingMode::Down => if difference < 0.0 {
+            result = next_down_f32(result);
+        },
+    }
+    if result.is_infinite() { Conversion::Overflow(result) } else { Conversion::Rounded(result) }
+}
+
+/// `high + low` rounded to f64. `low` is at most half an ulp of `high` (as in a normalized
+/// TwoFloat), so `high` is already rounded to nearest (with ties to even). For
+/// `ToNearestAway`, a tie needs `low` to be exact.
+pub(crate) fn round_to_f64(high: f64, low: f64, rounding: RoundingMode) -> Conversion<f64> {
+    if high.is_nan() {
+        return Conversion::NaN(high);
+    } else if low == 0.0 || high.is_infinite() {
+        return Conversion::Exact(high);
+    }
+    let result = match rounding {
+        RoundingMode::Nearest | RoundingMode::Faithful => high,
+        RoundingMode::ToNearestAway => {
+            // A tie is halfway to the neighbour away from zero. That difference is exact.
+            let neighbour = if low > 0.0 { next_up_f64(high) } else { next_down_f64(high) };
+            if (low > 0.0) == (high > 0.0) && neighbour - high == 2.0 * low { neighbour } else { high }
+        },
+        RoundingMode::TowardZero => if (high > 0.0 && low < 0.0) || (high < 0.0 && low > 0.0) {
+            if high > 0.0 { next_down_f64(high) } else { next_up_f64(high) }
+        } else {
+            high
+        },
+        RoundingMode::Up => if low > 0.0 { next_up_f64(high) } else { high },
+        RoundingMode::Down => if low < 0.0 { next_down_f64(high) } else { high },
+    };
+    if result.is_infinite() { Conversion::Overflow(result) } else { Conversion::Rounded(result) }
+}
+
+/// `get(x, rnd)` (`mpfr::get_flt()` or `mpfr::get_d()`, as f64) as per `rounding`. MPFR has no
+/// ties away from zero, so for `ToNearestAway` this gets both neighbours, and it compares `x` with
+/// their midpoint (which is exact in 128 bits).
+#[cfg(not(feature = "f32_only"))]
+#[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+unsafe fn mpfr_get_rounded(x: mpfr::mpfr_srcptr, rounding: RoundingMode, get: impl Fn(mpfr::mpfr_srcptr, mpfr::rnd_t) -> f64) -> f64 {
+    if rounding != RoundingMode::ToNearestAway {
+        return get(x, rounding.to_mpfr());
+    }
+    let (down, up) = (get(x, mpfr::rnd_t::RNDD), get(x, mpfr::rnd_t::RNDU));
+    if down == up || !down.is_finite() || !up.is_finite() {
+        return get(x, mpfr::rnd_t::RNDN);
+    }
+    let mut midpoint = UniMpfr128bit::NAN;
+    midpoint.copied();
+    let midpoint = midpoint.mpfr_mut();
+    mpfr::set_d(midpoint, down, mpfr::rnd_t::RNDN);
+    mpfr::add_d(midpoint, midpoint, up, mpfr::rnd_t::RNDN);
+    mpfr::div_2ui(midpoint, midpoint, 1, mpfr::rnd_t::RNDN);
+    match mpfr::cmp(x, midpoint) {
+        0 => if mpfr::signbit(x) != 0 { down } else { up },
+        comparison if comparison > 0 => up,
+        _ => down
+    }
+}
+
+/// An integral value (or its absence), before it's converted to a specific integer type.
+enum Integral {
+    NaN,
+    /// At least 2^128 in magnitude (including infinity).
+    Overflow { negative: bool },
+    Value { negative: bool, magnitude: u128, exact: bool },
+}
+
+/// Conversions to Rust primitives, with rounding in the given direction. They report whether
+/// the result is exact, rounded, overflowed, or whether `self` was NaN.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    #[cfg_attr(feature = "f32_only", allow(unused_variables))]
+    pub fn to_f32(&self, rounding: RoundingMode) -> Conversion<f32> {
+        self.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => if self.f32s[0].is_nan() {
+                Conversion::NaN(self.f32s[0])
+            } else {
+                Conversion::Exact(self.f32s[0])
+            },
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => round_to_f32(self.f64s[0], 0.0, rounding),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => round_to_f32(self.twofloats[0].hi(), self.twofloats[0].lo(), rounding),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                let mut exact = self.convert::<{ MPFR_TWOFLOAT_EXACT }>();
+                exact.copied().to_f32(rounding)
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => self.decimal_rounded_to_odd().copied().to_f32(rounding),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => unsafe {
+                let source = self.mpfr_src();
+                let x = source.as_ptr();
+                let result = mpfr_get_rounded(x, rounding, |x, rnd| mpfr::get_flt(x, rnd) as f64) as f32;
+                if mpfr::nan_p(x) != 0 {
+                    Conversion::NaN(result)
+                } else if mpfr::inf_p(x) != 0 || mpfr::zero_p(x) != 0 {
+                    Conversion::Exact(result)
+                } else if result.is_infinite() || mpfr::get_exp(x) > f32::MAX_EXP as mpfr::exp_t {
+                    Conversion::Overflow(result)
+                } else if mpfr::cmp_d(x, result as f64) == 0 {
+                    Conversion::Exact(result)
+                } else {
+                    Conversion::Rounded(result)
+                }
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    pub fn to_f64(&self, rounding: RoundingMode) -> Conversion<f64> {
+        self.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => round_to_f64(self.f32s[0] as f64, 0.0, rounding),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => round_to_f64(self.f64s[0], 0.0, rounding),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => round_to_f64(self.twofloats[0].hi(), self.twofloats[0].lo(), rounding),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                let mut exact = self.convert::<{ MPFR_TWOFLOAT_EXACT }>();
+                exact.copied().to_f64(rounding)
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => self.decimal_rounded_to_odd().copied().to_f64(rounding),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => unsafe {
+                let source = self.mpfr_src();
+                let x = source.as_ptr();
+                let result = mpfr_get_rounded(x, rounding, |x, rnd| mpfr::get_d(x, rnd));
+                if mpfr::nan_p(x) != 0 {
+                    Conversion::NaN(result)
+                } else if mpfr::inf_p(x) != 0 || mpfr::zero_p(x) != 0 {
+                    Conversion::Exact(result)
+                } else if result.is_infinite() || mpfr::get_exp(x) > f64::MAX_EXP as mpfr::exp_t {
+                    Conversion::Overflow(result)
+                } else if mpfr::cmp_d(x, result) == 0 {
+                    Conversion::Exact(result)
+                } else {
+                    Conversion::Rounded(result)
+                }
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    /// `self` rounded to an integer, as a sign and magnitude.
+    fn to_integral(&self, rounding: RoundingMode) -> Integral {
+        let mut rounded = self.rint(rounding);
+        rounded.copied();
+        let exact = rounded == *self;
+        match C {
+            UniFloatChoice::F32 => float_to_integral(rounded.f32s[0] as f64, 0.0, exact),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => float_to_integral(rounded.f64s[0], 0.0, exact),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => float_to_integral(rounded.twofloats[0].hi(), rounded.twofloats[0].lo(), exact),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                let mut value = self.convert::<{ MPFR_TWOFLOAT_EXACT }>();
+                value.copied().to_integral(rounding)
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => self.decimal_rounded_to_odd().copied().to_integral(rounding),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => unsafe {
+                let source = rounded.mpfr_src();
+                let x = source.as_ptr();
+                let negative = mpfr::signbit(x) != 0;
+                if mpfr::nan_p(x) != 0 {
+                    Integral::NaN
+                } else if mpfr::zero_p(x) != 0 {
+                    Integral::Value { negative, magnitude: 0, exact }
+                } else if mpfr::inf_p(x) != 0 || mpfr::get_exp(x) > 128 {
+                    Integral::Overflow { negative }
+                } else {
+                    // |x| < 2^128 and it's an integer, so it's exact in 128 bits.
+                    let (mut magnitude, mut high) = (UniMpfr128bit::NAN, UniMpfr128bit::NAN);
+                    magnitude.copied();
+                    high.copied();
+                    let (magnitude, high) = (magnitude.mpfr_mut(), high.mpfr_mut());
+                    mpfr::abs(magnitude, x, mpfr::rnd_t::RNDN);
+                    mpfr::div_2ui(high, magnitude, 64, mpfr::rnd_t::RNDN);
+                    mpfr::trunc(high, high);
+                    let high_bits = mpfr::get_uj(high, mpfr::rnd_t::RNDZ);
+                    mpfr::mul_2ui(high, high, 64, mpfr::rnd_t::RNDN);
+                    mpfr::sub(magnitude, magnitude, high, mpfr::rnd_t::RNDN);
+                    let low_bits = mpfr::get_uj(magnitude, mpfr::rnd_t::RNDZ);
+                    Integral::Value { negative, magnitude: (high_bits as u128) << 64 | low_bits as u128, exact }
+                }
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+}
+
+/// `high + low` must be an integer (or NaN, or infinite).
+fn float_to_integral(high: f64, low: f64, exact: bool) -> Integral {
+    const TWO_POW_128: f64 = 340_282_366_920_938_463_463_374_607_431_768_211_456.0;
+    let negative = high.is_sign_negative();
+    if high.is_nan() {
+        Integral::NaN
+    } else if libm::fabs(high) >= TWO_POW_128 {
+        Integral::Overflow { negative }
+    } else {
+        // Both `high` and `low` are integers, because `low` is less than an ulp of `high`, and it
+        // can be fractional only if `high` is less than 2^53 - and then it's 0.
+        let high_magnitude = libm::fabs(high) as u128;
+        let low_magnitude = libm::fabs(low) as u128;
+        let magnitude = if (low < 0.0) == negative {
+            high_magnitude.checked_add(low_magnitude)
+        } else {
+            high_magnitude.checked_sub(low_magnitude)
+        };
+        match magnitude {
+            Some(magnitude) => Integral::Value { negative, magnitude, exact },
+            None => Integral::Overflow { negative }
+        }
+    }
+}
+
+macro_rules! to_signed {
+    ($($method:ident -> $integer:ty),+) => {
+        impl <const C: UniFloatChoice> UniFloat<C> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        {
+            $(
+                pub fn $method(&self, rounding: RoundingMode) -> Conversion<$integer> {
+                    match self.to_integral(rounding) {
+                        Integral::NaN => Conversion::NaN(0),
+                        Integral::Overflow { negative } =>
+                            Conversion::Overflow(if negative { <$integer>::MIN } else { <$integer>::MAX }),
+                        Integral::Value { negative, magnitude, exact } => {
+                            let value = if negative {
+                                // 2^127 wraps to i128::MIN, which negates to itself.
+                                if magnitude <= 1 << 127 { Some((magnitude as i128).wrapping_neg()) } else { None }
+                            } else {
+                                i128::try_from(magnitude).ok()
+                            };
+                            match value.and_then(|value| <$integer>::try_from(value).ok()) {
+                                Some(value) if exact => Conversion::Exact(value),
+                                Some(value) => Conversion::Rounded(value),
+                                None => Conversion::Overflow(if negative { <$integer>::MIN } else { <$integer>::MAX }),
+                            }
+                        }
+                    }
+                }
+            )+
+        }
+    };
+}
+
+macro_rules! to_unsigned {
+    ($($method:ident -> $integer:ty),+) => {
+        impl <const C: UniFloatChoice> UniFloat<C> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        {
+            $(
+                pub fn $method(&self, rounding: RoundingMode) -> Conversion<$integer> {
+                    match self.to_integral(rounding) {
+                        Integral::NaN => Conversion::NaN(0),
+                        Integral::Overflow { negative } =>
+                            Conversion::Overflow(if negative { 0 } else { <$integer>::MAX }),
+                        Integral::Value { negative, magnitude, exact } => {
+                            if negative && magnitude > 0 {
+                                Conversion::Overflow(0)
+                            } else {
+                                match <$integer>::try_from(magnitude) {
+                                    Ok(value) if exact => Conversion::Exact(value),
+                                    Ok(value) => Conversion::Rounded(value),
+                                    Err(_) => Conversion::Overflow(<$integer>::MAX),
+                                }
+                            }
+                        }
+                    }
+                }
+            )+
+        }
+    };
+}
+
+to_signed!(to_i8 -> i8, to_i16 -> i16, to_i32 -> i32, to_i64 -> i64, to_i128 -> i128, to_isize -> isize);
+to_unsigned!(to_u8 -> u8, to_u16 -> u16, to_u32 -> u32, to_u64 -> u64, to_u128 -> u128, to_usize -> usize);
+
+/// Checked conversions to integers: the value rounded in the direction of `rounding`, or an error
+/// if `self` is NaN, or if the rounded value doesn't fit. They round once, from the full
+/// precision of `self` (as `mpfr_get_si()` and `mpfr_get_z()` do for MPFR), never through f64.
+macro_rules! to_integer_round {
+    ($($round:ident => $method:ident -> $integer:ty),+) => {
+        impl <const C: UniFloatChoice> UniFloat<C> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        {
+            $(
+                pub fn $round(&self, rounding: RoundingMode) -> Result<$integer, ToIntError> {
+                    match self.$method(rounding) {
+                        Conversion::Exact(value) | Conversion::Rounded(value) => Ok(value),
+                        // Overflows saturate at MIN or MAX, by the sign.
+                        Conversion::Overflow(value) => Err(ToIntError::new(if value == <$integer>::MIN {
+                            ToIntErrorKind::NegativeOverflow
+                        } else {
+                            ToIntErrorKind::PositiveOverflow
+                        })),
+                        Conversion::NaN(_) => Err(ToIntError::new(ToIntErrorKind::NaN)),
+                    }
+                }
+            )+
+        }
+    };
+}
+
+to_integer_round!(
+    to_i8_round => to_i8 -> i8, to_i16_round => to_i16 -> i16, to_i32_round => to_i32 -> i32,
+    to_i64_round => to_i64 -> i64, to_i128_round => to_i128 -> i128, to_isize_round => to_isize -> isize,
+    to_u8_round => to_u8 -> u8, to_u16_round => to_u16 -> u16, to_u32_round => to_u32 -> u32,
+    to_u64_round => to_u64 -> u64, to_u128_round => to_u128 -> u128, to_usize_round => to_usize -> usize
+);
+
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// `self` rounded to nearest (ties to even) for choice `D`. Exact if `D` is at least as
+    /// precise as `C` (see `UniFloatChoice::promoted()`), with one exception: a TwoFloat (or
+    /// QuadFloat) whose lower parts are far below the higher part needs more bits than its nominal
+    /// 106 (or 212), so MPFR may round it. Conversions between binary and decimal choices round
+    /// (once), other than for values that both can hold.
+    pub fn convert<const D: UniFloatChoice>(&self) -> UniFloat<D> where
+    [f32; f32_parts_length(D)]: Sized,
+    [f64; f64_parts_length(D)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(D)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(D)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(D)]: Sized,
+    {
+        self.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => UniFloat::<D>::from_f32(self.f32s[0]),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => UniFloat::<D>::from_f64(self.f64s[0]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => UniFloat::<D>::from_twofloat(self.twofloats[0]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => match D {
+                UniFloatChoice::QuadFloat => {
+                    let mut result = UniFloat::<D>::NAN;
+                    result.set_quad(self.quad());
+                    result.released()
+                },
+                _ => {
+                    let mut exact = UniMpfrTwoFloatExact::NAN;
+                    exact.copied();
+                    unsafe { self.quad().to_mpfr(exact.mpfr_mut()) };
+                    UniFloat::<D>::from_mpfr(exact.mpfr_src().as_ptr())
+                }
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => match D {
+                UniFloatChoice::Decimal { digits } => {
+                    let mut result = UniFloat::<D>::NAN;
+                    result.set_decimal(self.decimal().with_digits(digits, RoundingMode::Nearest));
+                    result
+                },
+                _ => UniFloat::<D>::from_mpfr(self.decimal_rounded_to_odd().copied().mpfr_src().as_ptr())
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+                UniFloat::<D>::from_mpfr(self.mpfr_src().as_ptr()),
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    /// `self` rounded for choice `D` in the direction of `rounding` (while `convert()` rounds to
+    /// nearest). For F32 and F64 that's as `to_f32()` and `to_f64()`, and MPFR rounds as per
+    /// `rounding`, too, and so do decimals. TwoFloat and QuadFloat have no directed rounding: for
+    /// `D` being either, that's an error, unless the result is exact.
+    #[cfg_attr(feature = "f32_only", allow(unused_variables, unused_mut))]
+    pub fn convert_round<const D: UniFloatChoice>(&self, rounding: RoundingMode) -> Result<UniFloat<D>, UnsupportedRounding> where
+    [f32; f32_parts_length(D)]: Sized,
+    [f64; f64_parts_length(D)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(D)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(D)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(D)]: Sized,
+    {
+        self.assert_copy_fixed();
+        match D {
+            UniFloatChoice::F32 => Ok(UniFloat::<D>::from_f32(self.to_f32(rounding).value())),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => Ok(UniFloat::<D>::from_f64(self.to_f64(rounding).value())),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat | UniFloatChoice::QuadFloat => {
+                let mut result = self.convert::<D>();
+                result.copied();
+                // Only MPFR and QuadFloat may not fit.
+                let exact = match C {
+                    #[cfg(feature = "mpfr")]
+                    UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => unsafe {
+                        let mut converted = result.convert::<{ MPFR_TWOFLOAT_EXACT }>();
+                        converted.copied();
+                        let source = converted.mpfr_src();
+                        let converted = source.as_ptr();
+                        mpfr::equal_p(converted, self.mpfr_src().as_ptr()) != 0 || mpfr::nan_p(converted) != 0
+                    },
+                    // Rounded to odd, it's only exact if the decimal is.
+                    #[cfg(feature = "mpfr")]
+                    UniFloatChoice::Decimal { .. } => unsafe {
+                        let (mut odd, mut converted) = (self.decimal_rounded_to_odd(), result.convert::<{ MPFR_TWOFLOAT_EXACT }>());
+                        odd.copied();
+                        converted.copied();
+                        let source = converted.mpfr_src();
+                        let converted = source.as_ptr();
+                        mpfr::equal_p(converted, odd.mpfr_src().as_ptr()) != 0 || mpfr::nan_p(converted) != 0
+                    },
+                    // Back to QuadFloat is exact.
+                    #[cfg(feature = "mpfr")]
+                    #[cfg(feature = "twofloat")]
+                    UniFloatChoice::QuadFloat => {
+                        let mut back = result.convert::<C>();
+                        back.copied();
+                        back.eq(self) || self.is_nan()
+                    },
+                    _ => true
+                };
+                if exact || rounding.is_nearest_or_faithful() {
+                    Ok(result.released())
+                } else {
+                    Err(UnsupportedRounding::new(rounding))
+                }
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let mut result = UniFloat::<D>::NAN;
+                result.copied();
+                let r = result.mpfr_mut();
+                unsafe {
+                    match C {
+                        UniFloatChoice::F32 => mpfr_rounded(r, rounding, |rnd| mpfr::set_flt(r, self.f32s[0], rnd)),
+                        UniFloatChoice::F64 => mpfr_rounded(r, rounding, |rnd| mpfr::set_d(r, self.f64s[0], rnd)),
+                        #[cfg(feature = "twofloat")]
+                        UniFloatChoice::TwoFloat => {
+                            let mut exact = UniMpfrTwoFloatExact::NAN;
+                            exact.copied();
+                            let exact = exact.mpfr_mut();
+                            mpfr::set_d(exact, self.twofloats[0].hi(), mpfr::rnd_t::RNDN);
+                            mpfr::add_d(exact, exact, self.twofloats[0].lo(), mpfr::rnd_t::RNDN);
+                            mpfr_rounded(r, rounding, |rnd| mpfr::set(r, exact, rnd))
+                        },
+                        #[cfg(feature = "twofloat")]
+                        UniFloatChoice::QuadFloat => {
+                            let mut exact = self.convert::<{ MPFR_TWOFLOAT_EXACT }>();
+                            exact.copied();
+                            mpfr_rounded(r, rounding, |rnd| mpfr::set(r, exact.mpfr_src().as_ptr(), rnd))
+                        },
+                        #[cfg(not(feature = "twofloat"))]
+                        UniFloatChoice::TwoFloat | UniFloatChoice::QuadFloat =>
+                            unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+                        UniFloatChoice::Decimal { .. } => mpfr_rounded(r, rounding, |rnd| self.decimal().to_mpfr(r, rnd)),
+                        UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+                            mpfr_rounded(r, rounding, |rnd| mpfr::set(r, self.mpfr_src().as_ptr(), rnd)),
+                    };
+                }
+                Ok(result.released())
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => {
+                let mut result = UniFloat::<D>::NAN;
+                result.set_decimal(Dec::from_unifloat_round(digits, self, rounding));
+                Ok(result)
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+}
+
+/// A value that `set` stores into an MPFR variable (rounded in the direction of its `mpfr::rnd_t`
+/// argument, returning the ternary value), rounded once for choice `D` in the direction of
+/// `rounding`. For MPFR that's `set` itself (then limited to the exponent range of the bounds).
+/// Other choices round from 2098 bits rounded to odd (toward zero, with the lowest bit set if
+/// inexact), which rounds the same as the exact value for anything less precise. As for
+/// `UniFloat::convert_round()`, TwoFloat supports directed rounding only where the result is
+/// exact. Decimals need the exact value on their ties (as 0.15, which isn't binary), so
+/// `UniRational` and `UniDecimal` round to decimals by their own.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+pub(crate) fn round_exact_value<const D: UniFloatChoice>(set: impl Fn(mpfr::mpfr_ptr, mpfr::rnd_t) -> i32, rounding: RoundingMode)
+    -> Result<UniFloat<D>, UnsupportedRounding> where
+[f32; f32_parts_length(D)]: Sized,
+[f64; f64_parts_length(D)]: Sized,
+[TwoFloatPart; twofloat_parts_length(D)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(D)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(D)]: Sized,
+{
+    match D {
+        UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+            let mut result = UniFloat::<D>::NAN;
+            result.copied();
+            unsafe {
+                let r = result.mpfr_mut();
+                let ternary = mpfr_rounded(r, rounding, |rnd| set(r, rnd));
+                limit_exponent_range(D.mpfr_bounds(), r, ternary);
+            }
+            Ok(result.released())
+        },
+        _ => {
+            let mut odd = rounded_to_odd(set);
+            odd.copied().convert_round::<D>(rounding)
+        }
+    }
+}
+
+/// A value that `set` stores into an MPFR variable (as for `round_exact_value()`), rounded to odd
+/// at 2098 bits.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+pub(crate) fn rounded_to_odd(set: impl Fn(mpfr::mpfr_ptr, mpfr::rnd_t) -> i32) -> UniMpfrTwoFloatExact {
+    let mut odd = UniMpfrTwoFloatExact::NAN;
+    odd.copied();
+    unsafe {
+        let r = odd.mpfr_mut();
+        // An inexact result with its lowest bit clear is even. Then its neighbour away from
+        // zero is odd (and the exact value is between the two).
+        if set(r, mpfr::rnd_t::RNDZ) != 0 && mpfr::min_prec(r) < mpfr::get_prec(r) {
+            if mpfr::signbit(r) != 0 { mpfr::nextbelow(r) } else { mpfr::nextabove(r) }
+        }
+    }
+    odd.released()
+}
+
+/// Conversions from and to GMP integers, for integers beyond u128. The `gmp::mpz_t` values must be
+/// initialized (by `gmp::mpz_init()`, for example).
+#[cfg(feature = "mpfr")]
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Rounded to nearest (once only), as the constructors from Rust primitives. Integers too
+    /// large for the choice become infinities.
+    pub fn from_mpz(value: &gmp::mpz_t) -> Self {
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = mpz_literal(value).to_f32(),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = mpz_literal(value).to_f64(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat | UniFloatChoice::QuadFloat => {
+                // Integers below 2^1024 are exact here, and larger ones overflow anyway.
+                let mut exact = UniMpfrTwoFloatExact::NAN;
+                exact.copied();
+                unsafe { mpfr::set_z(exact.mpfr_mut(), value, mpfr::rnd_t::RNDN) };
+                result = Self::from_mpfr(exact.mpfr_src().as_ptr());
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => {
+                let mut odd = rounded_to_odd(|r, rnd| unsafe { mpfr::set_z(r, value, rnd) });
+                result = Self::from_mpfr(odd.copied().mpfr_src().as_ptr());
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                result.copied();
+                unsafe { mpfr::set_z(result.mpfr_mut(), value, mpfr::rnd_t::RNDN) };
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+
+    /// `self` rounded to an integer in the direction of `rounding`, stored in `result`. Rounded
+    /// once, from the full precision of `self` (as `mpfr_get_z()` does for MPFR). Decimals beyond
+    /// 2^2098 go through 2098 bits, so they may be rounded. Errors for NaN and infinities, which
+    /// leave `result` unchanged.
+    pub fn to_mpz_round(&self, rounding: RoundingMode, result: &mut gmp::mpz_t) -> Result<(), ToIntError> {
+        let mut rounded = self.rint(rounding);
+        rounded.copied();
+        match C {
+            UniFloatChoice::F32 => set_mpz_integral(result, rounded.f32s[0] as f64),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => set_mpz_integral(result, rounded.f64s[0]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => {
+                let value = rounded.twofloats[0];
+                check_finite(value.hi())?;
+                let mut exact = UniMpfrTwoFloatExact::NAN;
+                exact.copied();
+                unsafe {
+                    let exact = exact.mpfr_mut();
+                    mpfr::set_d(exact, value.hi(), mpfr::rnd_t::RNDN);
+                    mpfr::add_d(exact, exact, value.lo(), mpfr::rnd_t::RNDN);
+                    mpfr::get_z(result, exact, mpfr::rnd_t::RNDN);
+                }
+                Ok(())
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                let mut exact = rounded.convert::<{ MPFR_TWOFLOAT_EXACT }>();
+                exact.copied().to_mpz_round(rounding, result)
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => self.decimal_rounded_to_odd().copied().to_mpz_round(rounding, result),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => unsafe {
+                let source = rounded.mpfr_src();
+                let x = source.as_ptr();
+                if mpfr::nan_p(x) != 0 {
+                    Err(ToIntError::new(ToIntErrorKind::NaN))
+                } else if mpfr::inf_p(x) != 0 {
+                    check_finite(if mpfr::signbit(x) != 0 { f64::NEG_INFINITY } else { f64::INFINITY })
+                } else {
+                    mpfr::get_z(result, x, mpfr::rnd_t::RNDN);
+                    Ok(())
+                }
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+}
+
+/// The error for NaN and infinities, which have no integer value.
+#[cfg(feature = "mpfr")]
+fn check_finite(value: f64) -> Result<(), ToIntError> {
+    if value.is_nan() {
+        Err(ToIntError::new(ToIntErrorKind::NaN))
+    } else if value.is_infinite() {
+        Err(ToIntError::new(if value < 0.0 { ToIntErrorKind::NegativeOverflow } else { ToIntErrorKind::PositiveOverflow }))
+    } else {
+        Ok(())
+    }
+}
+
+/// `value` must be an integer (or NaN, or infinite). Exact, since it's integral.
+#[cfg(feature = "mpfr")]
+fn set_mpz_integral(result: &mut gmp::mpz_t, value: f64) -> Result<(), ToIntError> {
+    check_finite(value)?;
+    unsafe { gmp::mpz_set_d(result, value) };
+    Ok(())
+}
+
+/// The highest 64 bits of `value`, with the lower bits in `sticky`, for rounding to f32 or f64
+/// without MPFR.
+#[cfg(feature = "mpfr")]
+fn mpz_literal(value: &gmp::mpz_t) -> HexLiteral {
+    unsafe {
+        // The limbs hold the magnitude (unlike `mpz_tstbit()`, which sees negative values in
+        // two's complement).
+        let (limbs, count) = (gmp::mpz_limbs_read(value), gmp::mpz_size(value));
+        let limb_bits = gmp::NUMB_BITS as u64;
+        let bit = |index: u64| (*limbs.add((index / limb_bits) as usize) >> (index % limb_bits)) & 1 != 0;
+        let bits = if count == 0 { 0 } else { gmp::mpz_sizeinbase(value, 2) as u64 };
+        let shift = bits.saturating_sub(64);
+        let mut significand = 0;
+        for index in shift..bits {
+            significand |= (bit(index) as u64) << (index - shift);
+        }
+        let whole_limbs = (shift / limb_bits) as usize;
+        let sticky = (0..whole_limbs).any(|limb| *limbs.add(limb) != 0)
+            || (whole_limbs as u64 * limb_bits..shift).any(bit);
+        HexLiteral {
+            negative: gmp::mpz_sgn(value) < 0,
+            significand,
+            exponent: (shift as i64).min(EXPONENT_LIMIT),
+            sticky
+        }
+    }
+}
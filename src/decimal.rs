@@ -0,0 +1,1223 @@
+use core::{cmp::Ordering, convert::TryFrom, fmt, num::FpCategory, ops, str::FromStr};
+#[cfg(not(feature = "f32_only"))]
+use {core::mem, crate::gmp};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use {core::ptr, crate::mpfr};
+
+use crate::{big::Big, parse::validate, Assert, IsTrue, ParseUniFloatError, RoundingMode};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::{convert::{MPFR_TWOFLOAT_EXACT, UniMpfrTwoFloatExact, round_exact_value, rounded_to_odd}, format::{Digits, integer_text},
+    MpfrBounds, UnsupportedRounding};
+#[cfg(not(feature = "f32_only"))]
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, decimal_digits, f32_parts_length,
+    f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Limit of the exponent of `UniDecimal` (of its last digit, as `UniDecimal::exponent()`), both
+/// ways. Beyond it results overflow, or they lose digits (gradually) as they underflow.
+pub const DECIMAL_EXPONENT_LIMIT: i32 = 999_999_999;
+
+/// Most digits of `UniDecimal` (and of `UniFloatChoice::decimal()`), as decimal128.
+pub(crate) const MAX_DECIMAL_DIGITS: usize = 34;
+
+/// Whether `UniDecimal<DIGITS>` is supported: 1 to 34 digits (as decimal128).
+pub const fn valid_decimal_digits(digits: usize) -> bool {
+    digits >= 1 && digits <= MAX_DECIMAL_DIGITS
+}
+
+/// Precision of MPFR for what decimals have no arithmetic of their own for (see `Dec::via_mpfr()`):
+/// 78 bits more than any 34 digits need.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+const MPFR_DECIMAL: UniFloatChoice = UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(192) };
+
+/// Enough for a coefficient of 34 digits scaled by 10^68 (for division), with room to spare.
+type Wide = Big<12>;
+
+fn power_of_ten(exponent: u32) -> Wide {
+    let mut result = Wide::new(1, 0);
+    for _ in 0..exponent {
+        result.mul_small(10);
+    }
+    result
+}
+
+/// Number of decimal digits, without leading zeros. Zero for zero.
+fn digit_count(value: &Wide) -> u32 {
+    let (mut count, mut power) = (0, Wide::new(1, 0));
+    while power.compare(value) != Ordering::Greater {
+        count += 1;
+        power.mul_small(10);
+    }
+    count
+}
+
+fn scaled(coefficient: u128, exponent: u32) -> Wide {
+    Wide::from_u128(coefficient).mul(&power_of_ten(exponent))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Finite,
+    Infinite,
+    NaN
+}
+
+/// A decimal with `digits` significant digits, given at runtime: the value of `UniDecimal` (with
+/// `DIGITS` digits), and of `UniFloat` for `UniFloatChoice::Decimal`, which share the arithmetic
+/// here. The operators round to nearest (ties to even).
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Dec {
+    pub(crate) digits: usize,
+    negative: bool,
+    coefficient: u128,
+    exponent: i32,
+    kind: Kind,
+}
+
+// With f32_only (or without mpfr), only UniDecimal uses it (not UniFloatChoice::Decimal).
+#[cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(dead_code))]
+impl Dec {
+    pub(crate) const fn nan(digits: usize) -> Self {
+        Self { digits, negative: false, coefficient: 0, exponent: 0, kind: Kind::NaN }
+    }
+
+    pub(crate) const fn infinity(digits: usize, negative: bool) -> Self {
+        Self { digits, negative, coefficient: 0, exponent: 0, kind: Kind::Infinite }
+    }
+
+    /// `coefficient` * 10^`exponent`, which must fit into `digits` digits and the exponent range.
+    pub(crate) const fn finite(digits: usize, negative: bool, coefficient: u128, exponent: i32) -> Self {
+        Self { digits, negative, coefficient, exponent, kind: Kind::Finite }
+    }
+
+    /// `digits` nines, with the greatest exponent.
+    pub(crate) const fn max(digits: usize, negative: bool) -> Self {
+        Self::finite(digits, negative, 10u128.pow(digits as u32) - 1, DECIMAL_EXPONENT_LIMIT)
+    }
+
+    /// The least value with all `digits` digits: 10^(`digits` - 1) with the least exponent.
+    pub(crate) const fn min_positive(digits: usize) -> Self {
+        Self::finite(digits, false, 10u128.pow(digits as u32 - 1), -DECIMAL_EXPONENT_LIMIT)
+    }
+
+    /// The difference between 1 and the next larger value: 10^(1 - `digits`).
+    pub(crate) const fn epsilon(digits: usize) -> Self {
+        Self::finite(digits, false, 1, 1 - digits as i32)
+    }
+
+    /// `coefficient` * 10^`exponent`, rounded to nearest (ties to even) if `coefficient` has more
+    /// than `digits` digits, or if `exponent` is out of range.
+    pub(crate) fn new(digits: usize, coefficient: i128, exponent: i32) -> Self {
+        Self::rounded(digits, coefficient < 0, Wide::from_u128(coefficient.unsigned_abs()), exponent as i64, false,
+            RoundingMode::Nearest)
+    }
+
+    /// `value` (with exponent 0), rounded to nearest (ties to even).
+    pub(crate) fn from_u128(digits: usize, value: u128) -> Self {
+        Self::rounded(digits, false, Wide::from_u128(value), 0, false, RoundingMode::Nearest)
+    }
+
+    pub(crate) fn is_nan(&self) -> bool {
+        self.kind == Kind::NaN
+    }
+
+    pub(crate) fn is_infinite(&self) -> bool {
+        self.kind == Kind::Infinite
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.kind == Kind::Finite && self.coefficient == 0
+    }
+
+    pub(crate) fn is_sign_negative(&self) -> bool {
+        self.kind != Kind::NaN && self.negative
+    }
+
+    /// Whether `self` is non-zero, with fewer than all `digits` digits at the least exponent (so
+    /// it underflowed).
+    pub(crate) fn is_subnormal(&self) -> bool {
+        self.kind == Kind::Finite && self.coefficient != 0 && self.exponent == -DECIMAL_EXPONENT_LIMIT
+            && (digit_count(&Wide::from_u128(self.coefficient)) as usize) < self.digits
+    }
+
+    pub(crate) fn classify(&self) -> FpCategory {
+        match self.kind {
+            Kind::NaN => FpCategory::Nan,
+            Kind::Infinite => FpCategory::Infinite,
+            Kind::Finite if self.coefficient == 0 => FpCategory::Zero,
+            Kind::Finite if self.is_subnormal() => FpCategory::Subnormal,
+            Kind::Finite => FpCategory::Normal
+        }
+    }
+
+    /// Whether `self` is as arithmetic leaves it: finite ones within the digits and the exponent
+    /// range, and the rest without a coefficient or an exponent (and NaN without a sign).
+    pub(crate) fn is_canonical(&self) -> bool {
+        match self.kind {
+            Kind::Finite => self.coefficient < 10u128.pow(self.digits as u32) && self.exponent.abs() <= DECIMAL_EXPONENT_LIMIT,
+            Kind::Infinite => self.coefficient == 0 && self.exponent == 0,
+            Kind::NaN => self.coefficient == 0 && self.exponent == 0 && !self.negative
+        }
+    }
+
+    pub(crate) fn abs(&self) -> Self {
+        Self { negative: false, ..*self }
+    }
+
+    pub(crate) fn add_round(&self, other: &Self, rounding: RoundingMode) -> Self {
+        self.add_signed(other, other.negative, rounding)
+    }
+
+    pub(crate) fn sub_round(&self, other: &Self, rounding: RoundingMode) -> Self {
+        self.add_signed(other, !other.negative, rounding)
+    }
+
+    pub(crate) fn mul_round(&self, other: &Self, rounding: RoundingMode) -> Self {
+        let negative = self.negative != other.negative;
+        match (self.kind, other.kind) {
+            (Kind::NaN, _) | (_, Kind::NaN) => Self::nan(self.digits),
+            (Kind::Infinite, _) if other.is_zero() => Self::nan(self.digits),
+            (_, Kind::Infinite) if self.is_zero() => Self::nan(self.digits),
+            (Kind::Infinite, _) | (_, Kind::Infinite) => Self::infinity(self.digits, negative),
+            _ => Self::rounded(self.digits, negative,
+                Wide::from_u128(self.coefficient).mul(&Wide::from_u128(other.coefficient)),
+                self.exponent as i64 + other.exponent as i64, false, rounding)
+        }
+    }
+
+    /// NaN for 0/0 and Inf/Inf. Otherwise division by zero gives an infinity.
+    pub(crate) fn div_round(&self, other: &Self, rounding: RoundingMode) -> Self {
+        let (digits, negative) = (self.digits, self.negative != other.negative);
+        match (self.kind, other.kind) {
+            (Kind::NaN, _) | (_, Kind::NaN) | (Kind::Infinite, Kind::Infinite) => return Self::nan(digits),
+            (Kind::Infinite, _) => return Self::infinity(digits, negative),
+            (_, Kind::Infinite) => return Self::finite(digits, negative, 0, 0),
+            _ => {}
+        }
+        if other.is_zero() {
+            return if self.is_zero() { Self::nan(digits) } else { Self::infinity(digits, negative) };
+        }
+        let preferred = self.exponent as i64 - other.exponent as i64;
+        if self.is_zero() {
+            return Self::rounded(digits, negative, Wide::ZERO, preferred, false, rounding);
+        }
+        // Scaled so that the quotient has `digits` + 1 or `digits` + 2 digits. Then one digit (at
+        // least) and the remainder decide the rounding.
+        let (dividend_digits, divisor_digits) =
+            (digit_count(&Wide::from_u128(self.coefficient)), digit_count(&Wide::from_u128(other.coefficient)));
+        let scale = digits as u32 + divisor_digits + 1 - dividend_digits;
+        let mut quotient = scaled(self.coefficient, scale);
+        let remainder = quotient.div_rem(&Wide::from_u128(other.coefficient));
+        let mut exponent = preferred - scale as i64;
+        if remainder.is_zero() {
+            // Exact: as close to the preferred exponent as the digits allow.
+            while exponent < preferred {
+                let mut shorter = quotient;
+                if shorter.div_small(10) != 0 {
+                    break;
+                }
+                quotient = shorter;
+                exponent += 1;
+            }
+        }
+        Self::rounded(digits, negative, quotient, exponent, !remainder.is_zero(), rounding)
+    }
+
+    /// See `UniDecimal::quantize()`.
+    pub(crate) fn quantize(&self, exponent: i32, rounding: RoundingMode) -> Self {
+        let digits = self.digits;
+        if self.kind != Kind::Finite || exponent.abs() > DECIMAL_EXPONENT_LIMIT {
+            return Self::nan(digits);
+        }
+        if exponent < self.exponent {
+            // Exact: the coefficient gets more (trailing zero) digits.
+            let shift = (self.exponent as i64 - exponent as i64) as u32;
+            if self.coefficient != 0 && shift > digits as u32 {
+                return Self::nan(digits);
+            }
+            let coefficient = if self.coefficient == 0 { Wide::ZERO } else { scaled(self.coefficient, shift) };
+            return if digit_count(&coefficient) as usize > digits {
+                Self::nan(digits)
+            } else {
+                Self::finite(digits, self.negative, coefficient.to_u128(), exponent)
+            };
+        }
+        let result = Self::rounded_at(digits, self.negative, Wide::from_u128(self.coefficient), self.exponent as i64,
+            false, rounding, exponent as i64);
+        // Rounding up may have carried into one more digit than fits.
+        if result.exponent == exponent { result } else { Self::nan(digits) }
+    }
+
+    /// `self` rounded to `digits` digits. Exact (keeping the exponent) if it fits.
+    pub(crate) fn with_digits(&self, digits: usize, rounding: RoundingMode) -> Self {
+        match self.kind {
+            Kind::Finite => Self::rounded(digits, self.negative, Wide::from_u128(self.coefficient), self.exponent as i64,
+                false, rounding),
+            _ => Self { digits, ..*self }
+        }
+    }
+
+    /// The least value greater than `self`, as IEEE 754's `nextUp`, with all `digits` digits
+    /// (where the exponent range allows). Zeros step to the least subnormal, 10^-`DECIMAL_EXPONENT_LIMIT`,
+    /// and the largest finite value steps to infinity.
+    pub(crate) fn next_up(&self) -> Self {
+        let digits = self.digits;
+        match self.kind {
+            Kind::NaN => return *self,
+            Kind::Infinite => return if self.negative { Self::max(digits, true) } else { *self },
+            Kind::Finite if self.coefficient == 0 => return Self::finite(digits, false, 1, -DECIMAL_EXPONENT_LIMIT),
+            Kind::Finite => {}
+        }
+        let (mut coefficient, mut exponent) = self.normalized();
+        let least_normal = 10u128.pow(digits as u32 - 1);
+        if self.negative {
+            coefficient -= 1;
+            if coefficient < least_normal && coefficient != 0 && exponent > -DECIMAL_EXPONENT_LIMIT as i64 {
+                coefficient = coefficient * 10 + 9;
+                exponent -= 1;
+            }
+        } else {
+            coefficient += 1;
+            if coefficient == 10 * least_normal {
+                coefficient = least_normal;
+                exponent += 1;
+            }
+            if exponent > DECIMAL_EXPONENT_LIMIT as i64 {
+                return Self::infinity(digits, false);
+            }
+        }
+        Self::finite(digits, self.negative, coefficient, exponent as i32)
+    }
+
+    /// The greatest value less than `self`, as IEEE 754's `nextDown`.
+    pub(crate) fn next_down(&self) -> Self {
+        -(-*self).next_up()
+    }
+
+    /// 10^e for the last of all `digits` digits at the exponent of `self` (10^-`DECIMAL_EXPONENT_LIMIT`
+    /// for zeros and subnormals). Inf for infinities, NaN for NaN.
+    pub(crate) fn ulp(&self) -> Self {
+        match self.kind {
+            Kind::NaN => *self,
+            Kind::Infinite => Self::infinity(self.digits, false),
+            Kind::Finite if self.coefficient == 0 => Self::finite(self.digits, false, 1, -DECIMAL_EXPONENT_LIMIT),
+            Kind::Finite => Self::finite(self.digits, false, 1, self.normalized().1 as i32)
+        }
+    }
+
+    /// The number of `next_up()`s (or `next_down()`s) from `self` to `other`. None as for
+    /// `UniFloat::ulp_distance()`.
+    pub(crate) fn ulp_distance(&self, other: &Self) -> Option<u64> {
+        match (self.kind, other.kind) {
+            (Kind::NaN, _) | (_, Kind::NaN) => return None,
+            (Kind::Infinite, Kind::Infinite) if self.negative == other.negative => return Some(0),
+            (Kind::Infinite, _) | (_, Kind::Infinite) => return None,
+            _ => {}
+        }
+        // Steps from zero: 9 * 10^(digits - 1) for each exponent above the least one.
+        let steps_per_exponent = 9 * 10i128.pow(self.digits as u32 - 1);
+        let ordinal = |x: &Self, from_exponent: i64| {
+            let (coefficient, exponent) = x.normalized();
+            ((exponent - from_exponent) as i128).checked_mul(steps_per_exponent)?.checked_add(coefficient as i128)
+        };
+        let distance = if self.is_zero() || other.is_zero() || self.negative != other.negative {
+            let least = -DECIMAL_EXPONENT_LIMIT as i64;
+            ordinal(self, least)?.checked_add(ordinal(other, least)?)?
+        } else {
+            // The same sign: from the lesser exponent, so that far exponents don't overflow.
+            let from_exponent = self.normalized().1.min(other.normalized().1);
+            (ordinal(self, from_exponent)? - ordinal(other, from_exponent)?).abs()
+        };
+        u64::try_from(distance).ok()
+    }
+
+    /// The coefficient and exponent of a finite `self`, with all `digits` digits where the
+    /// exponent range allows (as `next_up()` steps).
+    fn normalized(&self) -> (u128, i64) {
+        let (mut coefficient, mut exponent) = (self.coefficient, self.exponent as i64);
+        if coefficient == 0 {
+            return (0, -DECIMAL_EXPONENT_LIMIT as i64);
+        }
+        while coefficient < 10u128.pow(self.digits as u32 - 1) && exponent > -DECIMAL_EXPONENT_LIMIT as i64 {
+            coefficient *= 10;
+            exponent -= 1;
+        }
+        (coefficient, exponent)
+    }
+
+    /// `self` + `other`, where `other_negative` replaces the sign of `other` (for subtraction).
+    fn add_signed(&self, other: &Self, other_negative: bool, rounding: RoundingMode) -> Self {
+        let digits = self.digits;
+        match (self.kind, other.kind) {
+            (Kind::NaN, _) | (_, Kind::NaN) => return Self::nan(digits),
+            (Kind::Infinite, Kind::Infinite) =>
+                return if self.negative == other_negative { *self } else { Self::nan(digits) },
+            (Kind::Infinite, _) => return *self,
+            (_, Kind::Infinite) => return Self::infinity(digits, other_negative),
+            _ => {}
+        }
+        // `x` has the greater exponent (or the same), and `y` the lesser one.
+        let ((x, x_negative), (y, y_negative)) = if self.exponent >= other.exponent {
+            ((self, self.negative), (other, other_negative))
+        } else {
+            ((other, other_negative), (self, self.negative))
+        };
+        let shift = (x.exponent as i64 - y.exponent as i64) as u32;
+        let (mut x_wide, mut y_wide, exponent, sticky) = if x.coefficient == 0 {
+            (Wide::ZERO, Wide::from_u128(y.coefficient), y.exponent as i64, false)
+        } else if y.coefficient == 0 {
+            // As many trailing zeros as fit, toward the exponent of `y`.
+            let pad = shift.min((digits as u32).saturating_sub(digit_count(&Wide::from_u128(x.coefficient))));
+            (scaled(x.coefficient, pad), Wide::ZERO, x.exponent as i64 - pad as i64, false)
+        } else if shift <= 2 * digits as u32 {
+            (scaled(x.coefficient, shift), Wide::from_u128(y.coefficient), y.exponent as i64, false)
+        } else {
+            // `y` is less than one unit of `x` scaled by `digits` + 1 digits, which is beyond the
+            // rounding digit. So it only makes the result inexact (and it borrows one unit, if
+            // it gets subtracted).
+            let mut x_wide = scaled(x.coefficient, digits as u32 + 1);
+            if x_negative != y_negative {
+                x_wide.sub(&Wide::new(1, 0));
+            }
+            (x_wide, Wide::ZERO, x.exponent as i64 - (digits as i64 + 1), true)
+        };
+        let negative = if x_negative == y_negative {
+            x_wide.add(&y_wide);
+            x_negative
+        } else {
+            match x_wide.compare(&y_wide) {
+                Ordering::Less => {
+                    y_wide.sub(&x_wide);
+                    x_wide = y_wide;
+                    y_negative
+                },
+                // An exact zero is +0, except when rounding down.
+                Ordering::Equal if !sticky => {
+                    x_wide = Wide::ZERO;
+                    rounding == RoundingMode::Down
+                },
+                _ => {
+                    x_wide.sub(&y_wide);
+                    x_negative
+                }
+            }
+        };
+        Self::rounded(digits, negative, x_wide, exponent, sticky, rounding)
+    }
+
+    /// `coefficient` * 10^`exponent` (plus a bit more if `sticky`: less than one unit of the
+    /// last digit of `coefficient`) rounded to `digits` digits, and to the exponent range.
+    fn rounded(digits: usize, negative: bool, coefficient: Wide, exponent: i64, sticky: bool, rounding: RoundingMode) -> Self {
+        Self::rounded_at(digits, negative, coefficient, exponent, sticky, rounding, -(DECIMAL_EXPONENT_LIMIT as i64))
+    }
+
+    /// As `rounded()`, but also to a multiple of 10^`least_exponent`.
+    fn rounded_at(digits: usize, negative: bool, mut coefficient: Wide, mut exponent: i64, mut sticky: bool,
+        rounding: RoundingMode, least_exponent: i64) -> Self {
+        let length = digit_count(&coefficient) as i64;
+        let dropped = (length - digits as i64).max(least_exponent - exponent).max(0);
+        let mut rounding_digit = 0;
+        if dropped > length {
+            // All of it is less than a tenth of the last digit kept.
+            sticky |= !coefficient.is_zero();
+            coefficient = Wide::ZERO;
+        } else {
+            for _ in 0..dropped {
+                sticky |= rounding_digit != 0;
+                rounding_digit = coefficient.div_small(10);
+            }
+        }
+        exponent += dropped;
+        let inexact = rounding_digit != 0 || sticky;
+        let increment = match rounding {
+            RoundingMode::Nearest | RoundingMode::Faithful =>
+                rounding_digit > 5 || (rounding_digit == 5 && (sticky || coefficient.bits_from(0) & 1 == 1)),
+            RoundingMode::ToNearestAway => rounding_digit >= 5,
+            RoundingMode::TowardZero => false,
+            RoundingMode::Up => inexact && !negative,
+            RoundingMode::Down => inexact && negative,
+        };
+        if increment {
+            coefficient.add_small(1);
+            if digit_count(&coefficient) as usize > digits {
+                coefficient.div_small(10);
+                exponent += 1;
+            }
+        }
+
+        let max_exponent = DECIMAL_EXPONENT_LIMIT as i64;
+        if coefficient.is_zero() {
+            exponent = exponent.min(max_exponent);
+        }
+        // Trailing zeros bring the exponent down, where they fit.
+        while exponent > max_exponent && (digit_count(&coefficient) as usize) < digits {
+            coefficient.mul_small(10);
+            exponent -= 1;
+        }
+        if exponent > max_exponent {
+            let to_infinity = match rounding {
+                RoundingMode::TowardZero => false,
+                RoundingMode::Up => !negative,
+                RoundingMode::Down => negative,
+                _ => true
+            };
+            return if to_infinity {
+                Self::infinity(digits, negative)
+            } else {
+                Self::max(digits, negative)
+            };
+        }
+        Self::finite(digits, negative, coefficient.to_u128(), exponent as i32)
+    }
+
+    /// Numeric comparison: -0 equals +0, and 1.0 equals 1.00. None if either side is NaN.
+    fn compare(&self, other: &Self) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        let sign = |x: &Self| if x.is_zero() { 0 } else if x.negative { -1 } else { 1 };
+        let (self_sign, other_sign) = (sign(self), sign(other));
+        if self_sign != other_sign || self_sign == 0 {
+            return Some(self_sign.cmp(&other_sign));
+        }
+        let magnitude = match (self.kind, other.kind) {
+            (Kind::Infinite, Kind::Infinite) => Ordering::Equal,
+            (Kind::Infinite, _) => Ordering::Greater,
+            (_, Kind::Infinite) => Ordering::Less,
+            _ => {
+                let (self_wide, other_wide) = (Wide::from_u128(self.coefficient), Wide::from_u128(other.coefficient));
+                // Exponents of the first digits.
+                let (self_adjusted, other_adjusted) = (self.exponent as i64 + digit_count(&self_wide) as i64,
+                    other.exponent as i64 + digit_count(&other_wide) as i64);
+                if self_adjusted != other_adjusted {
+                    self_adjusted.cmp(&other_adjusted)
+                } else if self.exponent >= other.exponent {
+                    scaled(self.coefficient, (self.exponent - other.exponent) as u32).compare(&other_wide)
+                } else {
+                    self_wide.compare(&scaled(other.coefficient, (other.exponent - self.exponent) as u32))
+                }
+            }
+        };
+        Some(if self_sign < 0 { magnitude.reverse() } else { magnitude })
+    }
+
+    /// The digits of the coefficient, written at the end of `text`.
+    fn coefficient_text<'a>(&self, text: &'a mut [u8; 40]) -> &'a str {
+        let (mut magnitude, mut start) = (self.coefficient, text.len());
+        loop {
+            start -= 1;
+            text[start] = b'0' + (magnitude % 10) as u8;
+            magnitude /= 10;
+            if magnitude == 0 {
+                break;
+            }
+        }
+        core::str::from_utf8(&text[start..]).unwrap_or("")
+    }
+
+    /// The same syntax as `UniFloat` (and Rust's `f64`), rounded to nearest (ties to even). The
+    /// exponent is as written: "1.50" has coefficient 150 and exponent -2.
+    pub(crate) fn parse(digits: usize, s: &str) -> Result<Self, ParseUniFloatError> {
+        validate(s)?;
+        let bytes = s.as_bytes();
+        let negative = bytes[0] == b'-';
+        let mut position = if negative || bytes[0] == b'+' { 1 } else { 0 };
+        let rest = &s[position..];
+        if rest.eq_ignore_ascii_case("nan") {
+            return Ok(Self::nan(digits));
+        }
+        if rest.eq_ignore_ascii_case("inf") || rest.eq_ignore_ascii_case("infinity") {
+            return Ok(Self::infinity(digits, negative));
+        }
+        // One digit more than kept, and the rest only for `sticky`.
+        let (mut coefficient, mut significant, mut exponent, mut sticky, mut dot) = (0u128, 0, 0i64, false, false);
+        while position < bytes.len() && bytes[position] != b'e' && bytes[position] != b'E' {
+            match bytes[position] {
+                b'.' => dot = true,
+                digit => {
+                    let digit = (digit - b'0') as u128;
+                    if dot {
+                        exponent -= 1;
+                    }
+                    if significant > digits {
+                        sticky |= digit != 0;
+                        exponent += 1;
+                    } else if coefficient != 0 || digit != 0 {
+                        // Leading zeros aren't significant.
+                        coefficient = coefficient * 10 + digit;
+                        significant += 1;
+                    }
+                }
+            }
+            position += 1;
+        }
+        if position < bytes.len() {
+            position += 1;
+            let exponent_negative = bytes[position] == b'-';
+            if exponent_negative || bytes[position] == b'+' {
+                position += 1;
+            }
+            // Far beyond the range anyway.
+            let written = bytes[position..].iter().fold(0i64, |value, &digit| (value * 10 + (digit - b'0') as i64).min(1 << 40));
+            exponent += if exponent_negative { -written } else { written };
+        }
+        Ok(Self::rounded(digits, negative, Wide::from_u128(coefficient), exponent, sticky, RoundingMode::Nearest))
+    }
+
+    /// The words that `UniFloat` keeps a decimal in: the coefficient (the lower 64 bits first),
+    /// and then the exponent (its lower 32 bits), the kind and the sign. All zeros are NaN.
+    pub(crate) const fn to_words(&self) -> [u64; 3] {
+        let kind = match self.kind {
+            Kind::NaN => 0,
+            Kind::Finite => 1,
+            Kind::Infinite => 2
+        };
+        [self.coefficient as u64, (self.coefficient >> 64) as u64,
+            self.exponent as u32 as u64 | kind << 32 | (self.negative as u64) << 34]
+    }
+
+    pub(crate) fn from_words(digits: usize, words: [u64; 3]) -> Self {
+        let kind = match (words[2] >> 32) & 3 {
+            1 => Kind::Finite,
+            2 => Kind::Infinite,
+            _ => Kind::NaN
+        };
+        Self {
+            digits,
+            negative: words[2] >> 34 & 1 == 1,
+            coefficient: words[0] as u128 | (words[1] as u128) << 64,
+            exponent: words[2] as u32 as i32,
+            kind
+        }
+    }
+}
+
+/// Conversions from and to MPFR (and to formatted digits), and operations that decimals run in
+/// MPFR.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+impl Dec {
+    /// The digits of a finite, non-zero `self`, as `Digits::from_mpfr()` in base 10: rounded to
+    /// `count` digits, to nearest (ties to even) or toward zero.
+    pub(crate) fn to_digits(&self, count: usize, rounding: RoundingMode) -> Digits {
+        let (mut coefficient, mut exponent) = (self.coefficient, self.exponent as isize);
+        let mut length = digit_count(&Wide::from_u128(coefficient)) as usize;
+        if length > count {
+            let divisor = 10u128.pow((length - count) as u32);
+            let (quotient, remainder) = (coefficient / divisor, coefficient % divisor);
+            let round_up = rounding == RoundingMode::Nearest
+                && (remainder > divisor / 2 || (remainder == divisor / 2 && quotient % 2 == 1));
+            coefficient = quotient + round_up as u128;
+            exponent += (length - count) as isize;
+            length = count;
+            if coefficient == 10u128.pow(count as u32) {
+                coefficient /= 10;
+                exponent += 1;
+            }
+        }
+        let mut result = Digits::ZERO;
+        for digit in result.digits[..length].iter_mut().rev() {
+            *digit = b'0' + (coefficient % 10) as u8;
+            coefficient /= 10;
+        }
+        result.length = length;
+        result.exponent = exponent + length as isize;
+        result.strip_zeros();
+        result
+    }
+
+    /// Sets `r` to `self`, rounded in the direction of `rnd`. Returns the ternary value.
+    pub(crate) unsafe fn to_mpfr(&self, r: mpfr::mpfr_ptr, rnd: mpfr::rnd_t) -> i32 {
+        let sign = if self.negative { -1 } else { 1 };
+        match self.kind {
+            Kind::NaN => {
+                mpfr::set_nan(r);
+                0
+            },
+            Kind::Infinite => {
+                mpfr::set_inf(r, sign);
+                0
+            },
+            Kind::Finite if self.coefficient == 0 => {
+                mpfr::set_zero(r, sign);
+                0
+            },
+            Kind::Finite => {
+                let mut text = [0u8; 80];
+                let length = self.write_exponential(&mut text);
+                text[length] = 0;
+                mpfr::strtofr(r, text.as_ptr() as *const _, ptr::null_mut(), 10, rnd)
+            }
+        }
+    }
+
+    /// Rounded to nearest.
+    pub(crate) fn to_f64(&self) -> f64 {
+        round_exact_value::<{ UniFloatChoice::F64 }>(|r, rnd| unsafe { self.to_mpfr(r, rnd) }, RoundingMode::Nearest)
+            .map_or(f64::NAN, |x| x.to_f64_nearest())
+    }
+
+    /// `x` rounded to `digits` digits, once if `x` has 2098 bits of precision at most (otherwise
+    /// it's first rounded to nearest to 2098 bits). If that's exact, it has no trailing zeros (so
+    /// 0.5 has coefficient 5 and exponent -1).
+    pub(crate) unsafe fn from_mpfr(digits: usize, x: mpfr::mpfr_srcptr, rounding: RoundingMode) -> Self {
+        let negative = mpfr::signbit(x) != 0;
+        if mpfr::nan_p(x) != 0 {
+            Self::nan(digits)
+        } else if mpfr::inf_p(x) != 0 {
+            Self::infinity(digits, negative)
+        } else if mpfr::zero_p(x) != 0 {
+            Self::finite(digits, negative, 0, 0)
+        } else {
+            let (mut exact, mut scratch) = (UniMpfrTwoFloatExact::NAN, UniMpfrTwoFloatExact::NAN);
+            exact.copied();
+            scratch.copied();
+            mpfr::set(exact.mpfr_mut(), x, mpfr::rnd_t::RNDN);
+            Self::from_mpfr_round(digits, exact.mpfr_src().as_ptr(), scratch.mpfr_mut(), rounding, 0)
+        }
+    }
+
+    /// The exponent of the last digit of a finite `self` (zeros included).
+    pub(crate) fn finite_exponent(&self) -> Option<i64> {
+        if self.kind == Kind::Finite { Some(self.exponent as i64) } else { None }
+    }
+
+    /// Sets `r` to the coefficient of `self`, with its sign, in a precision that holds it exactly.
+    /// NaN and infinities are as by `to_mpfr()`. With `finite_exponent()`, that's how decimals
+    /// add up exactly, as integers.
+    pub(crate) unsafe fn to_mpfr_integer(&self, r: mpfr::mpfr_ptr) {
+        mpfr::set_prec(r, 128);
+        Self { exponent: 0, ..*self }.to_mpfr(r, mpfr::rnd_t::RNDN);
+    }
+
+    /// The integer `x` * 10^`scale`, rounded to nearest (ties to even) to `digits` digits, as for
+    /// sums: exact results keep the exponent `scale` (the least one of the terms) where the digits
+    /// fit, as in IEEE 754, and zeros always do (within the exponent range).
+    pub(crate) unsafe fn from_mpfr_integer(digits: usize, x: mpfr::mpfr_srcptr, scale: i64) -> Self {
+        let limit = DECIMAL_EXPONENT_LIMIT as i64;
+        let negative = mpfr::signbit(x) != 0;
+        if mpfr::nan_p(x) != 0 {
+            return Self::nan(digits);
+        } else if mpfr::inf_p(x) != 0 {
+            return Self::infinity(digits, negative);
+        } else if mpfr::zero_p(x) != 0 {
+            return Self::finite(digits, negative, 0, scale.max(-limit).min(limit) as i32);
+        }
+        let mut scratch = mem::MaybeUninit::uninit();
+        mpfr::init2(scratch.as_mut_ptr(), mpfr::get_prec(x));
+        let result = Self::from_mpfr_round(digits, x, scratch.as_mut_ptr(), RoundingMode::Nearest, scale);
+        mpfr::clear(scratch.as_mut_ptr());
+        if scale.abs() <= limit {
+            let preferred = result.quantize(scale as i32, RoundingMode::Nearest);
+            if preferred == result {
+                return preferred;
+            }
+        }
+        result
+    }
+
+    /// The exact `value` (canonical, as `UniRational` keeps it) rounded to `digits` digits, once:
+    /// the integer quotient for a few digits more than kept, with the remainder as the sticky
+    /// part. If that's exact, it has no trailing zeros.
+    pub(crate) unsafe fn from_mpq(digits: usize, value: &gmp::mpq_t, rounding: RoundingMode) -> Self {
+        let sign = gmp::mpq_sgn(value);
+        if sign == 0 {
+            return Self::finite(digits, false, 0, 0);
+        }
+        let negative = sign < 0;
+        // |value| is within [2^(bits - 1), 2^(bits + 1)), so its leading digit is at 10^leading,
+        // give or take one.
+        let bits = gmp::mpz_sizeinbase(&value.num, 2) as i64 - gmp::mpz_sizeinbase(&value.den, 2) as i64;
+        let leading = (bits as i128 * 30_102_999_566).div_euclid(100_000_000_000) as i64;
+        let limit = DECIMAL_EXPONENT_LIMIT as i64;
+        if leading > limit + digits as i64 + 1 || leading < -limit - 2 {
+            // Beyond the greatest value, or less than half of the least one: no need for digits.
+            return Self::rounded(digits, negative, Wide::new(1, 0), leading, true, rounding);
+        }
+        // From `digits` + 1 to `digits` + 3 digits.
+        let shift = digits as i64 + 1 - leading;
+        let mut integers = [mem::MaybeUninit::<gmp::mpz_t>::uninit(); 4];
+        for integer in integers.iter_mut() {
+            gmp::mpz_init(integer.as_mut_ptr());
+        }
+        let (numerator, denominator) = (integers[0].as_mut_ptr(), integers[1].as_mut_ptr());
+        let (quotient, remainder) = (integers[2].as_mut_ptr(), integers[3].as_mut_ptr());
+        gmp::mpz_ui_pow_ui(quotient, 10, shift.unsigned_abs() as _);
+        if shift >= 0 {
+            gmp::mpz_mul(numerator, &value.num, quotient);
+            gmp::mpz_set(denominator, &value.den);
+        } else {
+            gmp::mpz_set(numerator, &value.num);
+            gmp::mpz_mul(denominator, &value.den, quotient);
+        }
+        gmp::mpz_abs(numerator, numerator);
+        gmp::mpz_tdiv_qr(quotient, remainder, numerator, denominator);
+        let limbs = gmp::mpz_limbs_read(quotient);
+        let coefficient = (0..gmp::mpz_size(quotient)).rev()
+            .fold(0u128, |value, index| value << gmp::NUMB_BITS | *limbs.add(index) as u128);
+        let exact = gmp::mpz_sgn(remainder) == 0;
+        for integer in integers.iter_mut() {
+            gmp::mpz_clear(integer.as_mut_ptr());
+        }
+        let result = Self::rounded(digits, negative, Wide::from_u128(coefficient), -shift, !exact, rounding);
+        if exact { result.without_trailing_zeros() } else { result }
+    }
+
+    /// Sets `result` (an initialized `gmp::mpq_t`) to a finite `self`, exactly.
+    pub(crate) unsafe fn to_mpq(&self, result: *mut gmp::mpq_t) {
+        let mut integer = mem::MaybeUninit::uninit();
+        mpfr::init2(integer.as_mut_ptr(), 128);
+        self.to_mpfr_integer(integer.as_mut_ptr());
+        let (numerator, denominator): (*mut gmp::mpz_t, *mut gmp::mpz_t) = (&mut (*result).num, &mut (*result).den);
+        mpfr::get_z(numerator, integer.as_ptr(), mpfr::rnd_t::RNDN);
+        mpfr::clear(integer.as_mut_ptr());
+        gmp::mpz_ui_pow_ui(denominator, 10, self.exponent.unsigned_abs() as _);
+        if self.exponent >= 0 {
+            gmp::mpz_mul(numerator, numerator, denominator);
+            gmp::mpz_set_ui(denominator, 1);
+        }
+        gmp::mpq_canonicalize(result);
+    }
+
+    /// `value` rounded to `digits` digits. If that's exact, it has no trailing zeros, unless
+    /// `value` is a decimal itself, which keeps its exponent where it fits.
+    pub(crate) fn from_unifloat_round<const C: UniFloatChoice>(digits: usize, value: &UniFloat<C>, rounding: RoundingMode) -> Self where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+    {
+        match value.classify() {
+            FpCategory::Nan => Self::nan(digits),
+            FpCategory::Infinite => Self::infinity(digits, value.is_sign_negative()),
+            FpCategory::Zero if !matches!(C, UniFloatChoice::Decimal { .. }) => Self::finite(digits, value.is_sign_negative(), 0, 0),
+            _ => match C {
+                UniFloatChoice::Decimal { .. } => value.decimal().with_digits(digits, rounding),
+                UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                    let mut scratch = UniFloat::<C>::NAN;
+                    scratch.copied();
+                    unsafe { Self::from_mpfr_round(digits, value.mpfr_src().as_ptr(), scratch.mpfr_mut(), rounding, 0) }
+                },
+                _ => {
+                    // Exact: it holds any f32, f64 or TwoFloat.
+                    let mut exact = value.convert::<{ MPFR_TWOFLOAT_EXACT }>();
+                    exact.copied();
+                    unsafe { Self::from_mpfr(digits, exact.mpfr_src().as_ptr(), rounding) }
+                }
+            }
+        }
+    }
+
+    /// `mpfr_op` (given the result first, then the operands, as in `UniFloat::unary()` and others)
+    /// on `operands`, which are rounded to nearest in MPFR at 192 bits. The result is rounded to
+    /// nearest (ties to even) to `digits` digits. That's how decimals do what they have no
+    /// arithmetic of their own for. Results are exact if they're exact in MPFR (like the square
+    /// root of 0.25), and otherwise they're within one unit of the last digit.
+    pub(crate) fn via_mpfr<const N: usize>(digits: usize, operands: [Dec; N],
+        mpfr_op: impl FnOnce(mpfr::mpfr_ptr, [mpfr::mpfr_srcptr; N]) -> i32) -> Self {
+        let mut rounded = [UniFloat::<{ MPFR_DECIMAL }>::NAN; N];
+        let mut pointers = [ptr::null(); N];
+        for index in 0..N {
+            rounded[index].copied();
+            let pointer = rounded[index].mpfr_mut();
+            unsafe { operands[index].to_mpfr(pointer, mpfr::rnd_t::RNDN) };
+            pointers[index] = pointer as mpfr::mpfr_srcptr;
+        }
+        let mut result = UniFloat::<{ MPFR_DECIMAL }>::NAN;
+        result.copied();
+        mpfr_op(result.mpfr_mut(), pointers);
+        unsafe { Self::from_mpfr(digits, result.mpfr_src().as_ptr(), RoundingMode::Nearest) }
+    }
+
+    /// `x` (finite and non-zero) * 10^`scale` rounded to `digits` digits. `scratch` must have the
+    /// precision of `x`.
+    unsafe fn from_mpfr_round(digits: usize, x: mpfr::mpfr_srcptr, scratch: mpfr::mpfr_ptr, rounding: RoundingMode,
+        scale: i64) -> Self {
+        // One digit more than kept, truncated. Whether that's exact decides the rest.
+        let mut text = [0u8; 80];
+        let mut exponent: mpfr::exp_t = 0;
+        mpfr::get_str(text.as_mut_ptr() as *mut _, &mut exponent, 10, digits + 1, x, mpfr::rnd_t::RNDZ);
+        let negative = text[0] == b'-';
+        let start = negative as usize;
+        let coefficient = text[start..start + digits + 1].iter().fold(0u128, |value, &digit| value * 10 + (digit - b'0') as u128);
+        let exponent = exponent as i64 - (digits as i64 + 1);
+        // The truncated digits are exact if they parse back to `x` exactly (at its precision).
+        let mut length = start + digits + 1;
+        let mut exponent_text = [0u8; 24];
+        for part in ["e", integer_text(&mut exponent_text, exponent)].iter() {
+            text[length..length + part.len()].copy_from_slice(part.as_bytes());
+            length += part.len();
+        }
+        text[length] = 0;
+        let exact = mpfr::strtofr(scratch, text.as_ptr() as *const _, ptr::null_mut(), 10, mpfr::rnd_t::RNDN) == 0
+            && mpfr::equal_p(scratch, x) != 0;
+        let result = Self::rounded(digits, negative, Wide::from_u128(coefficient), exponent + scale, !exact, rounding);
+        if exact { result.without_trailing_zeros() } else { result }
+    }
+
+    /// The same value, with its trailing zeros moved into the exponent (where it fits).
+    fn without_trailing_zeros(mut self) -> Self {
+        while self.coefficient != 0 && self.coefficient % 10 == 0 && self.exponent < DECIMAL_EXPONENT_LIMIT {
+            self.coefficient /= 10;
+            self.exponent += 1;
+        }
+        self
+    }
+
+    /// "-COEFFICIENTeEXPONENT" (finite only), for MPFR. Returns the length.
+    fn write_exponential(&self, text: &mut [u8; 80]) -> usize {
+        let (mut coefficient_text, mut exponent_text) = ([0u8; 40], [0u8; 24]);
+        let mut length = 0;
+        for part in [if self.negative { "-" } else { "" }, self.coefficient_text(&mut coefficient_text), "e",
+            integer_text(&mut exponent_text, self.exponent as i64)].iter() {
+            text[length..length + part.len()].copy_from_slice(part.as_bytes());
+            length += part.len();
+        }
+        length
+    }
+}
+
+impl PartialEq for Dec {
+    fn eq(&self, other: &Self) -> bool {
+        self.compare(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Dec {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.compare(other)
+    }
+}
+
+/// Exact.
+impl ops::Neg for Dec {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self { negative: !self.negative, ..self }
+    }
+}
+
+// Binary operators (+, -, *, /), rounded to nearest (ties to even), to the digits of the left side.
+macro_rules! impl_dec_op {
+    ($trait:ident, $method:ident, $round:ident) => {
+        impl ops::$trait for Dec {
+            type Output = Self;
+            fn $method(self, other: Self) -> Self {
+                self.$round(&other, RoundingMode::Nearest)
+            }
+        }
+    };
+}
+
+impl_dec_op!(Add, add, add_round);
+impl_dec_op!(Sub, sub, sub_round);
+impl_dec_op!(Mul, mul, mul_round);
+impl_dec_op!(Div, div, div_round);
+
+/// As the General Decimal Arithmetic's to-scientific-string: without an exponent if the
+/// exponent isn't positive and the value isn't tiny (like "-12.50" or "0.00012"), otherwise with
+/// one digit before the point (like "1.250E+5"). "Infinity", "-Infinity" or "NaN" for the rest.
+impl fmt::Display for Dec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_nan() {
+            return f.write_str("NaN");
+        }
+        if self.negative {
+            f.write_str("-")?;
+        }
+        if self.is_infinite() {
+            return f.write_str("Infinity");
+        }
+        let mut text = [0u8; 40];
+        let digits = self.coefficient_text(&mut text);
+        let adjusted = self.exponent as i64 + digits.len() as i64 - 1;
+        if self.exponent <= 0 && adjusted >= -6 {
+            let point = digits.len() as i64 + self.exponent as i64;
+            if self.exponent == 0 {
+                f.write_str(digits)
+            } else if point > 0 {
+                write!(f, "{}.{}", &digits[..point as usize], &digits[point as usize..])
+            } else {
+                f.write_str("0.")?;
+                for _ in point..0 {
+                    f.write_str("0")?;
+                }
+                f.write_str(digits)
+            }
+        } else {
+            f.write_str(&digits[..1])?;
+            if digits.len() > 1 {
+                write!(f, ".{}", &digits[1..])?;
+            }
+            write!(f, "E{}{}", if adjusted < 0 { "-" } else { "+" }, adjusted.abs())
+        }
+    }
+}
+
+/// `UniFloatChoice::Decimal` keeps `Dec::to_words()` in the limbs (the lowest limb first), which
+/// MPFR doesn't use then.
+#[cfg(not(feature = "f32_only"))]
+#[cfg_attr(not(feature = "mpfr"), allow(dead_code))]
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub(crate) fn decimal(&self) -> Dec {
+        let limbs_per_word = 64 / gmp::NUMB_BITS as usize;
+        let mut words = [0u64; 3];
+        for (index, limb) in self.mpfr_limbs.iter().enumerate() {
+            words[index / limbs_per_word] |= (unsafe { limb.assume_init() } as u64) << (index % limbs_per_word * gmp::NUMB_BITS as usize);
+        }
+        Dec::from_words(decimal_digits(C), words)
+    }
+
+    pub(crate) fn set_decimal(&mut self, value: Dec) {
+        self.mpfr_limbs = Self::decimal_limbs(value);
+    }
+
+    /// The limbs that hold `value` for decimals. For other choices they're left uninitialized.
+    pub(crate) const fn decimal_limbs(value: Dec) -> [MpfrLimbPart; mpfr_limb_parts_length(C)] {
+        let mut limbs: [MpfrLimbPart; mpfr_limb_parts_length(C)] = unsafe { mem::MaybeUninit::uninit().assume_init() };
+        if let UniFloatChoice::Decimal { .. } = C {
+            let (words, limbs_per_word) = (value.to_words(), 64 / gmp::NUMB_BITS as usize);
+            let mut index = 0;
+            while index < mpfr_limb_parts_length(C) {
+                let word = words[index / limbs_per_word];
+                limbs[index] = mem::MaybeUninit::new((word >> (index % limbs_per_word * gmp::NUMB_BITS as usize)) as gmp::limb_t);
+                index += 1;
+            }
+        }
+        limbs
+    }
+
+    /// The decimal of `self`, rounded to odd at 2098 bits, so that rounding it once more to
+    /// fewer bits is correct.
+    #[cfg(feature = "mpfr")]
+    pub(crate) fn decimal_rounded_to_odd(&self) -> UniMpfrTwoFloatExact {
+        let decimal = self.decimal();
+        rounded_to_odd(|r, rnd| unsafe { decimal.to_mpfr(r, rnd) })
+    }
+
+    /// `op` on the decimal of `self`, for what decimals don't run in MPFR.
+    pub(crate) fn decimal_unary(&self, op: impl FnOnce(Dec) -> Dec) -> Self {
+        let mut result = Self::NAN;
+        result.set_decimal(op(self.decimal()));
+        result
+    }
+}
+
+/// A decimal floating-point number with `DIGITS` significant digits: `coefficient` *
+/// 10^`exponent`, as in IEEE 754 decimal arithmetic (like decimal64 with 16 digits, and
+/// decimal128 with 34 digits). Decimal fractions like 0.1 are exact, and results round in decimal,
+/// so amounts of money round the way accountants expect (with `RoundingMode::ToNearestAway` for
+/// "round half up").
+///
+/// As in IEEE 754, values keep their quantum: 1.50 has coefficient 150 and exponent -2, so it
+/// formats as "1.50". Exact results get the exponent that IEEE 754 prefers (the lesser one of the
+/// operands for addition, the sum for multiplication, and the difference for division). Inexact
+/// ones get all `DIGITS` digits. `quantize()` rounds to a given exponent, like to cents.
+///
+/// The exponent range is +-`DECIMAL_EXPONENT_LIMIT`. Arithmetic is correctly rounded, with
+/// any `RoundingMode` (`Faithful` is the same as `Nearest`). Operators round to nearest, with
+/// ties to even. It's a plain value, without MPFR limbs.
+/// `UniFloatChoice::decimal()` is the same format as a choice of `UniFloat`.
+#[derive(Clone, Copy, Debug)]
+pub struct UniDecimal<const DIGITS: usize>(Dec) where
+Assert<{ valid_decimal_digits(DIGITS) }>: IsTrue;
+
+impl <const DIGITS: usize> UniDecimal<DIGITS> where
+Assert<{ valid_decimal_digits(DIGITS) }>: IsTrue,
+{
+    pub const NAN: Self = Self(Dec::nan(DIGITS));
+    pub const INFINITY: Self = Self(Dec::infinity(DIGITS, false));
+    pub const NEG_INFINITY: Self = Self(Dec::infinity(DIGITS, true));
+    pub const ZERO: Self = Self(Dec::finite(DIGITS, false, 0, 0));
+
+    /// `coefficient` * 10^`exponent`, rounded to nearest (ties to even) if `coefficient` has more
+    /// than `DIGITS` digits, or if `exponent` is out of range.
+    pub fn new(coefficient: i128, exponent: i32) -> Self {
+        Self(Dec::new(DIGITS, coefficient, exponent))
+    }
+
+    /// The significant digits, as an integer. Zero for NaN and infinities.
+    pub fn coefficient(&self) -> u128 {
+        self.0.coefficient
+    }
+
+    /// Exponent of the last digit of the coefficient. Zero for NaN and infinities.
+    pub fn exponent(&self) -> i32 {
+        self.0.exponent
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.0.is_nan()
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        self.0.is_infinite()
+    }
+
+    pub fn is_finite(&self) -> bool {
+        self.0.kind == Kind::Finite
+    }
+
+    /// Whether `self` is +0 or -0 (with any exponent).
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Whether the sign is negative, including -0 and -Inf. False for NaN.
+    pub fn is_sign_negative(&self) -> bool {
+        self.0.is_sign_negative()
+    }
+
+    /// Exact.
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs())
+    }
+
+    pub fn add_round(&self, other: &Self, rounding: RoundingMode) -> Self {
+        Self(self.0.add_round(&other.0, rounding))
+    }
+
+    pub fn sub_round(&self, other: &Self, rounding: RoundingMode) -> Self {
+        Self(self.0.sub_round(&other.0, rounding))
+    }
+
+    pub fn mul_round(&self, other: &Self, rounding: RoundingMode) -> Self {
+        Self(self.0.mul_round(&other.0, rounding))
+    }
+
+    /// NaN for 0/0 and Inf/Inf. Otherwise division by zero gives an infinity.
+    pub fn div_round(&self, other: &Self, rounding: RoundingMode) -> Self {
+        Self(self.0.div_round(&other.0, rounding))
+    }
+
+    /// `self` rounded to a multiple of 10^`exponent` (in the direction of `rounding`), with
+    /// that exponent. Like rounding an amount to cents with `exponent` -2. NaN if the result
+    /// doesn't fit into `DIGITS` digits, if `exponent` is out of range, or if `self` isn't
+    /// finite.
+    pub fn quantize(&self, exponent: i32, rounding: RoundingMode) -> Self {
+        Self(self.0.quantize(exponent, rounding))
+    }
+}
+
+/// Conversions from and to `UniFloat`, rounded once (in the direction of a `RoundingMode`). They
+/// go through MPFR.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+impl <const DIGITS: usize> UniDecimal<DIGITS> where
+Assert<{ valid_decimal_digits(DIGITS) }>: IsTrue,
+{
+    /// `value` rounded to `DIGITS` digits. If that's exact, it has no trailing zeros (so 0.5 has
+    /// coefficient 5 and exponent -1), unless `value` is a decimal itself, which keeps its
+    /// exponent where it fits.
+    pub fn from_unifloat_round<const C: UniFloatChoice>(value: &UniFloat<C>, rounding: RoundingMode) -> Self where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+    {
+        Self(Dec::from_unifloat_round(DIGITS, value, rounding))
+    }
+
+    /// `self` rounded for choice `C`. As for `UniFloat::convert_round()`, TwoFloat supports
+    /// directed rounding only where the result is exact.
+    pub fn to_unifloat_round<const C: UniFloatChoice>(&self, rounding: RoundingMode) -> Result<UniFloat<C>, UnsupportedRounding> where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+    {
+        match C {
+            UniFloatChoice::Decimal { digits } => {
+                let mut result = UniFloat::<C>::NAN;
+                result.set_decimal(self.0.with_digits(digits, rounding));
+                Ok(result)
+            },
+            _ => round_exact_value::<C>(|r, rnd| unsafe { self.0.to_mpfr(r, rnd) }, rounding)
+        }
+    }
+}
+
+impl <const DIGITS: usize> Default for UniDecimal<DIGITS> where
+Assert<{ valid_decimal_digits(DIGITS) }>: IsTrue,
+{
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl <const DIGITS: usize> PartialEq for UniDecimal<DIGITS> where
+Assert<{ valid_decimal_digits(DIGITS) }>: IsTrue,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl <const DIGITS: usize> PartialOrd for UniDecimal<DIGITS> where
+Assert<{ valid_decimal_digits(DIGITS) }>: IsTrue,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+/// Exact.
+impl <const DIGITS: usize> ops::Neg for UniDecimal<DIGITS> where
+Assert<{ valid_decimal_digits(DIGITS) }>: IsTrue,
+{
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+// Binary operators (+, -, *, /), rounded to nearest (ties to even).
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident) => {
+        impl <const DIGITS: usize> ops::$trait for UniDecimal<DIGITS> where
+        Assert<{ valid_decimal_digits(DIGITS) }>: IsTrue,
+        {
+            type Output = Self;
+            fn $method(self, other: Self) -> Self {
+                Self(ops::$trait::$method(self.0, other.0))
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add);
+impl_binary_op!(Sub, sub);
+impl_binary_op!(Mul, mul);
+impl_binary_op!(Div, div);
+
+/// See `Dec`'s: as the General Decimal Arithmetic's to-scientific-string.
+impl <const DIGITS: usize> fmt::Display for UniDecimal<DIGITS> where
+Assert<{ valid_decimal_digits(DIGITS) }>: IsTrue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// The same syntax as `UniFloat` (and Rust's `f64`), rounded to nearest (ties to even). The
+/// exponent is as written: "1.50" has coefficient 150 and exponent -2.
+impl <const DIGITS: usize> FromStr for UniDecimal<DIGITS> where
+Assert<{ valid_decimal_digits(DIGITS) }>: IsTrue,
+{
+    type Err = ParseUniFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Dec::parse(DIGITS, s).map(Self)
+    }
+}
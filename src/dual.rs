@@ -0,0 +1,393 @@
+use core::ops;
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+use crate::complex::{combined, sum_of_products};
+
+/// A dual number `value` + `derivative` ε (with ε² = 0), for forward-mode automatic
+/// differentiation: start from `variable()` x (derivative 1) and `constant()`s (derivative 0), and
+/// any computation of them carries the derivative along (by the chain rule), at the precision of
+/// `C`. That's what Newton's method, or gradient-based optimizers, need, with no symbolic
+/// differentiation, and no finite differences (which lose half of the precision).
+///
+/// Both parts are rounded to nearest (as `UniFloat`) at every step, so the derivative is accurate
+/// to a few ulps, where the computation is stable. Derivatives at points where a function isn't
+/// differentiable are what the formula gives there (like `abs()` at ±0: ±1, and `sqrt()` at 0:
+/// +Inf).
+#[derive(Clone, Copy)]
+pub struct UniDual<const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    value: UniFloat<C>,
+    derivative: UniFloat<C>,
+}
+
+impl <const C: UniFloatChoice> UniDual<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub fn new(value: &UniFloat<C>, derivative: &UniFloat<C>) -> Self {
+        Self { value: *value, derivative: *derivative }.released()
+    }
+
+    /// `value`, with derivative 0.
+    pub fn constant(value: &UniFloat<C>) -> Self {
+        Self { value: *value, derivative: UniFloat::ZERO }.released()
+    }
+
+    /// `value`, with derivative 1: the variable to differentiate by.
+    pub fn variable(value: &UniFloat<C>) -> Self {
+        Self { value: *value, derivative: UniFloat::ONE }.released()
+    }
+
+    /// From parts returned by value.
+    fn from_parts(mut value: UniFloat<C>, mut derivative: UniFloat<C>) -> Self {
+        value.copied();
+        derivative.copied();
+        Self::new(&value, &derivative)
+    }
+
+    /// f(`self`), from its value, and the derivative of f at `self.value` (both returned by value),
+    /// by the chain rule.
+    fn chained(&self, value: UniFloat<C>, mut slope: UniFloat<C>) -> Self {
+        slope.copied();
+        slope *= &self.derivative;
+        Self::from_parts(value, slope.released())
+    }
+
+    /// As `UniFloat::copied()`, for both parts.
+    #[inline]
+    pub fn copied(&mut self) -> &mut Self {
+        self.value.copied();
+        self.derivative.copied();
+        self
+    }
+
+    /// As `UniFloat::released()`, for both parts.
+    fn released(self) -> Self {
+        Self { value: self.value.released(), derivative: self.derivative.released() }
+    }
+
+    pub fn value(&self) -> &UniFloat<C> {
+        &self.value
+    }
+
+    pub fn derivative(&self) -> &UniFloat<C> {
+        &self.derivative
+    }
+
+    /// Derivative: sign(`self`) (so ±1 at ±0).
+    pub fn abs(&self) -> Self {
+        self.chained(self.value.abs(), self.value.signum())
+    }
+
+    /// Derivative: 1 / (2 sqrt(`self`)).
+    pub fn sqrt(&self) -> Self {
+        let mut root = self.value.sqrt();
+        root.copied();
+        let mut twice = root.ldexp(1);
+        twice.copied();
+        self.chained(root.released(), reciprocal(&twice))
+    }
+
+    /// Derivative: 1 / (3 cbrt(`self`)^2).
+    pub fn cbrt(&self) -> Self {
+        let mut root = self.value.cbrt();
+        root.copied();
+        let mut three = UniFloat::<C>::from(3u8);
+        three.copied();
+        let mut slope = combined(&root, &root, |r, y| *r *= y);
+        slope.copied();
+        slope *= &three;
+        self.chained(root.released(), reciprocal(&slope))
+    }
+
+    /// Derivative: e^`self`.
+    pub fn exp(&self) -> Self {
+        let mut power = self.value.exp();
+        power.copied();
+        self.chained(power.released(), power.released())
+    }
+
+    /// Derivative: 2^`self` ln 2.
+    pub fn exp2(&self) -> Self {
+        let (mut power, mut ln_2) = (self.value.exp2(), UniFloat::<C>::ln_2());
+        power.copied();
+        ln_2.copied();
+        self.chained(power.released(), combined(&power, &ln_2, |r, y| *r *= y))
+    }
+
+    /// Derivative: e^`self`.
+    pub fn exp_m1(&self) -> Self {
+        self.chained(self.value.exp_m1(), self.value.exp())
+    }
+
+    /// Derivative: 1 / `self`.
+    pub fn ln(&self) -> Self {
+        self.chained(self.value.ln(), reciprocal(&self.value))
+    }
+
+    /// Derivative: 1 / (`self` ln 2).
+    pub fn log2(&self) -> Self {
+        let mut ln_2 = UniFloat::<C>::ln_2();
+        ln_2.copied();
+        let mut product = combined(&self.value, &ln_2, |r, y| *r *= y);
+        product.copied();
+        self.chained(self.value.log2(), reciprocal(&product))
+    }
+
+    /// Derivative: 1 / (`self` ln 10).
+    pub fn log10(&self) -> Self {
+        let mut ln_10 = UniFloat::<C>::ln_10();
+        ln_10.copied();
+        let mut product = combined(&self.value, &ln_10, |r, y| *r *= y);
+        product.copied();
+        self.chained(self.value.log10(), reciprocal(&product))
+    }
+
+    /// Derivative: 1 / (1 + `self`).
+    pub fn ln_1p(&self) -> Self {
+        let mut one = UniFloat::<C>::ONE;
+        one.copied();
+        let mut successor = combined(&self.value, &one, |r, y| *r += y);
+        successor.copied();
+        self.chained(self.value.ln_1p(), reciprocal(&successor))
+    }
+
+    /// Derivative: cos(`self`).
+    pub fn sin(&self) -> Self {
+        self.chained(self.value.sin(), self.value.cos())
+    }
+
+    /// Derivative: -sin(`self`).
+    pub fn cos(&self) -> Self {
+        let mut sine = self.value.sin();
+        sine.copied();
+        self.chained(self.value.cos(), -&sine)
+    }
+
+    /// Derivative: 1 + tan(`self`)^2.
+    pub fn tan(&self) -> Self {
+        let mut tangent = self.value.tan();
+        tangent.copied();
+        self.chained(tangent.released(), square_plus(&tangent, 1, 1))
+    }
+
+    /// Derivative: 1 / sqrt(1 - `self`^2).
+    pub fn asin(&self) -> Self {
+        self.chained(self.value.asin(), reciprocal_root(&self.value, -1, 1))
+    }
+
+    /// Derivative: -1 / sqrt(1 - `self`^2).
+    pub fn acos(&self) -> Self {
+        let mut slope = reciprocal_root(&self.value, -1, 1);
+        slope.copied();
+        self.chained(self.value.acos(), -&slope)
+    }
+
+    /// Derivative: 1 / (1 + `self`^2).
+    pub fn atan(&self) -> Self {
+        let mut denominator = square_plus(&self.value, 1, 1);
+        denominator.copied();
+        self.chained(self.value.atan(), reciprocal(&denominator))
+    }
+
+    /// Derivative: cosh(`self`).
+    pub fn sinh(&self) -> Self {
+        self.chained(self.value.sinh(), self.value.cosh())
+    }
+
+    /// Derivative: sinh(`self`).
+    pub fn cosh(&self) -> Self {
+        self.chained(self.value.cosh(), self.value.sinh())
+    }
+
+    /// Derivative: 1 - tanh(`self`)^2.
+    pub fn tanh(&self) -> Self {
+        let mut tangent = self.value.tanh();
+        tangent.copied();
+        self.chained(tangent.released(), square_plus(&tangent, -1, 1))
+    }
+
+    /// Derivative: 1 / sqrt(`self`^2 + 1).
+    pub fn asinh(&self) -> Self {
+        self.chained(self.value.asinh(), reciprocal_root(&self.value, 1, 1))
+    }
+
+    /// Derivative: 1 / sqrt(`self`^2 - 1).
+    pub fn acosh(&self) -> Self {
+        self.chained(self.value.acosh(), reciprocal_root(&self.value, 1, -1))
+    }
+
+    /// Derivative: 1 / (1 - `self`^2).
+    pub fn atanh(&self) -> Self {
+        let mut denominator = square_plus(&self.value, -1, 1);
+        denominator.copied();
+        self.chained(self.value.atanh(), reciprocal(&denominator))
+    }
+
+    /// Derivative: `n` `self`^(`n` - 1).
+    pub fn powi(&self, n: i64) -> Self {
+        let (mut lower, mut factor) = (self.value.powi(n.saturating_sub(1)), UniFloat::<C>::from(n));
+        lower.copied();
+        factor.copied();
+        self.chained(self.value.powi(n), combined(&factor, &lower, |r, y| *r *= y))
+    }
+
+    /// `self` to the power of `exp`, where both may vary. Derivative: `exp` `self`^(`exp` - 1)
+    /// `self`' + `self`^`exp` ln(`self`) `exp`' (where the second term is left out for constant
+    /// `exp`, so that `self` may be zero, or negative, there).
+    pub fn pow(&self, exp: &Self) -> Self {
+        let (mut power, mut one) = (self.value.pow(&exp.value), UniFloat::<C>::ONE);
+        power.copied();
+        one.copied();
+        let mut lower_exp = combined(&exp.value, &one, |r, y| *r -= y);
+        lower_exp.copied();
+        let mut lower = self.value.pow(&lower_exp);
+        lower.copied();
+        let mut derivative = combined(&exp.value, &lower, |r, y| *r *= y);
+        derivative.copied();
+        derivative *= &self.derivative;
+        let mut zero = UniFloat::<C>::ZERO;
+        zero.copied();
+        if exp.derivative != zero {
+            let mut log = self.value.ln();
+            log.copied();
+            let mut term = combined(&power, &log, |r, y| *r *= y);
+            term.copied();
+            derivative <<= term.mul_add(&exp.derivative, &derivative);
+        }
+        Self::from_parts(power.released(), derivative.released())
+    }
+
+    /// sqrt(`self`^2 + `other`^2). Derivative: (`self` `self`' + `other` `other`') / hypot.
+    pub fn hypot(&self, other: &Self) -> Self {
+        let mut hypotenuse = self.value.hypot(&other.value);
+        hypotenuse.copied();
+        let mut derivative = sum_of_products(&self.value, &self.derivative, &other.value, &other.derivative);
+        derivative.copied();
+        derivative /= &hypotenuse;
+        Self::from_parts(hypotenuse.released(), derivative.released())
+    }
+
+    /// atan2(`self`, `x`), with `self` as y. Derivative: (`x` `self`' - `self` `x`') / (`x`^2 +
+    /// `self`^2).
+    pub fn atan2(&self, x: &Self) -> Self {
+        let mut negated = -&self.value;
+        negated.copied();
+        let (mut numerator, mut denominator) = (sum_of_products(&x.value, &self.derivative, &negated, &x.derivative),
+            sum_of_products(&x.value, &x.value, &self.value, &self.value));
+        numerator.copied();
+        denominator.copied();
+        numerator /= &denominator;
+        Self::from_parts(self.value.atan2(&x.value), numerator.released())
+    }
+
+    /// `self` * `a` + `b`, fused (for the value). Derivative: `self`' `a` + `self` `a`' + `b`'.
+    pub fn mul_add(&self, a: &Self, b: &Self) -> Self {
+        let mut partial = self.value.mul_add(&a.derivative, &b.derivative);
+        partial.copied();
+        Self::from_parts(self.value.mul_add(&a.value, &b.value), self.derivative.mul_add(&a.value, &partial))
+    }
+}
+
+/// 1 / `x`.
+fn reciprocal<const C: UniFloatChoice>(x: &UniFloat<C>) -> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut one = UniFloat::<C>::ONE;
+    one.copied();
+    combined(&one, x, |r, x| *r /= x)
+}
+
+/// `sign` `x`^2 + `constant` (fused), for `sign` and `constant` of ±1.
+fn square_plus<const C: UniFloatChoice>(x: &UniFloat<C>, sign: i8, constant: i8) -> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut signed, mut constant) = (UniFloat::<C>::from(sign), UniFloat::<C>::from(constant));
+    signed.copied();
+    constant.copied();
+    signed *= x;
+    signed.mul_add(x, &constant)
+}
+
+/// 1 / sqrt(`sign` `x`^2 + `constant`).
+fn reciprocal_root<const C: UniFloatChoice>(x: &UniFloat<C>, sign: i8, constant: i8) -> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut radicand = square_plus(x, sign, constant);
+    radicand.copied();
+    let mut root = radicand.sqrt();
+    root.copied();
+    reciprocal(&root)
+}
+
+/// -`value` - `derivative` ε. Exact.
+impl <const C: UniFloatChoice> ops::Neg for &UniDual<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Output = UniDual<C>;
+    fn neg(self) -> Self::Output {
+        UniDual::from_parts(-&self.value, -&self.derivative)
+    }
+}
+
+// Binary operators (+, -, *, /) on references.
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, |$x:ident, $y:ident| $op:expr) => {
+        impl <const C: UniFloatChoice> ops::$trait<&UniDual<C>> for &UniDual<C> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        {
+            type Output = UniDual<C>;
+            fn $method(self, other: &UniDual<C>) -> Self::Output {
+                let ($x, $y) = (self, other);
+                $op
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, |x, y| UniDual::from_parts(
+    combined(&x.value, &y.value, |r, y| *r += y), combined(&x.derivative, &y.derivative, |r, y| *r += y)));
+impl_binary_op!(Sub, sub, |x, y| UniDual::from_parts(
+    combined(&x.value, &y.value, |r, y| *r -= y), combined(&x.derivative, &y.derivative, |r, y| *r -= y)));
+// (a + a'ε)(b + b'ε) = ab + (a'b + ab')ε.
+impl_binary_op!(Mul, mul, |x, y| UniDual::from_parts(
+    combined(&x.value, &y.value, |r, y| *r *= y), sum_of_products(&x.derivative, &y.value, &x.value, &y.derivative)));
+// (a + a'ε) / (b + b'ε) = q + ((a' - q b') / b)ε, for q = a / b.
+impl_binary_op!(Div, div, |x, y| {
+    let mut quotient = combined(&x.value, &y.value, |r, y| *r /= y);
+    quotient.copied();
+    let mut negated = -&quotient;
+    negated.copied();
+    let mut derivative = negated.mul_add(&y.derivative, &x.derivative);
+    derivative.copied();
+    derivative /= &y.value;
+    UniDual::from_parts(quotient.released(), derivative.released())
+});
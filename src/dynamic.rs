@@ -0,0 +1,265 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use {core::{fmt, ops, ptr}, crate::{gmp, mpfr}, crate::TwoFloat};
+
+use crate::flags::{emulated_decimal, emulated_f32, emulated_f64, emulated_quadfloat};
+#[cfg(feature = "twofloat")]
+use crate::flags::emulated_twofloat;
+#[cfg(feature = "mpfr")]
+use crate::parts::limit_exponent_range;
+use crate::{decimal::Dec, quadfloat::Quad};
+#[cfg(feature = "twofloat")]
+use crate::roots::sqrt_twofloat;
+use crate::{MpfrLimbPart, NAN_MPFR_EXP, UniFloatChoice, mpfr_limb_parts_length};
+
+/// A floating-point number whose `UniFloatChoice` is decided at runtime (for example, from a
+/// configuration file), rather than by a const generic parameter as for `UniFloat`. Get the choice
+/// from `UniFloatBounds::to_choice()`, or from `MpfrBounds::for_bounds()`.
+///
+/// MPFR limbs are in a buffer given by the caller (see `new_in()`), or (with `alloc` feature) on
+/// the heap (see `new()`). Either way they don't move with the instance.
+///
+/// New instances are NaN, as for `UniFloat`. Both operands of an operation must have the same
+/// choice. Results are rounded to nearest, and they raise `Flags` as for `UniFloat`.
+pub struct UniFloatDyn<'a> {
+    choice: UniFloatChoice,
+    value: Value,
+    #[allow(dead_code)]
+    limbs: Limbs<'a>,
+}
+
+enum Value {
+    F32(f32),
+    F64(f64),
+    #[cfg_attr(not(feature = "twofloat"), allow(dead_code))]
+    TwoFloat(TwoFloat),
+    QuadFloat(Quad),
+    Decimal(Dec),
+    /// Its limbs are in `UniFloatDyn::limbs`.
+    #[cfg_attr(not(feature = "mpfr"), allow(dead_code))]
+    Mpfr(mpfr::mpfr_t),
+}
+
+/// Only keeps the limbs alive (and borrowed). They are accessed through `mpfr::mpfr_t::d`.
+#[allow(dead_code)]
+enum Limbs<'a> {
+    Borrowed(&'a mut [MpfrLimbPart]),
+    #[cfg(feature = "alloc")]
+    Owned(Vec<MpfrLimbPart>),
+}
+
+impl <'a> UniFloatDyn<'a> {
+    /// Length of the limb buffer that `new_in()` needs for `choice`. It's 0 for choices that don't
+    /// run in MPFR (decimals are held in the instance).
+    pub const fn limbs_needed(choice: UniFloatChoice) -> usize {
+        if choice.in_mpfr() { mpfr_limb_parts_length(choice) } else { 0 }
+    }
+
+    /// NaN, with its MPFR limbs (if any) in `limbs`. That needs to be at least
+    /// `limbs_needed(choice)` long.
+    pub fn new_in(choice: UniFloatChoice, limbs: &'a mut [MpfrLimbPart]) -> Self {
+        assert!(limbs.len() >= Self::limbs_needed(choice),
+            "The limb buffer is too short for the UniFloatChoice. See UniFloatDyn::limbs_needed().");
+        let limbs_ptr = limbs.as_mut_ptr() as *mut gmp::limb_t;
+        Self::with_limbs(choice, limbs_ptr, Limbs::Borrowed(limbs))
+    }
+
+    fn with_limbs(choice: UniFloatChoice, limbs_ptr: *mut gmp::limb_t, limbs: Limbs<'a>) -> Self {
+        #[cfg(feature = "f32_only")]
+        assert!(choice == UniFloatChoice::F32, "Only UniFloatChoice::F32 is supported with f32_only feature.");
+        #[cfg(not(feature = "mpfr"))]
+        assert!(matches!(choice, UniFloatChoice::F32 | UniFloatChoice::F64 | UniFloatChoice::TwoFloat),
+            "Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.");
+        #[cfg(not(feature = "twofloat"))]
+        assert!(choice != UniFloatChoice::TwoFloat, "UniFloatChoice::TwoFloat is supported with twofloat feature only.");
+        let value = match choice {
+            UniFloatChoice::F32 => Value::F32(f32::NAN),
+            UniFloatChoice::F64 => Value::F64(f64::NAN),
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => Value::TwoFloat(TwoFloat::from(f64::NAN)),
+            #[cfg(not(feature = "twofloat"))]
+            UniFloatChoice::TwoFloat => unreachable!("UniFloatChoice::TwoFloat is supported with twofloat feature only."),
+            UniFloatChoice::QuadFloat => Value::QuadFloat(Quad::from_f64(f64::NAN)),
+            UniFloatChoice::Decimal { digits } => Value::Decimal(Dec::nan(digits)),
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => Value::Mpfr(mpfr::mpfr_t {
+                prec: choice.mpfr_bounds().precision_bits as mpfr::prec_t,
+                sign: 1,
+                exp: NAN_MPFR_EXP,
+                // Slices (even empty ones) have non-null pointers.
+                d: unsafe { ptr::NonNull::new_unchecked(limbs_ptr) }
+            })
+        };
+        Self { choice, value, limbs }
+    }
+
+    pub fn choice(&self) -> UniFloatChoice {
+        self.choice
+    }
+
+    /// Sets `self` to `value`, rounded to nearest.
+    pub fn set_f64(&mut self, value: f64) {
+        match &mut self.value {
+            Value::F32(x) => *x = value as f32,
+            Value::F64(x) => *x = value,
+            #[cfg(feature = "twofloat")]
+            Value::TwoFloat(x) => *x = TwoFloat::from(value),
+            Value::QuadFloat(x) => *x = Quad::from_f64(value),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            Value::Decimal(x) => *x = Dec::via_mpfr(x.digits, [], |r, _| unsafe { mpfr::set_d(r, value, mpfr::rnd_t::RNDN) }),
+            #[cfg(feature = "f32_only")]
+            Value::Decimal(_) => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature."),
+            #[cfg(feature = "mpfr")]
+            Value::Mpfr(x) => unsafe {
+                let x: mpfr::mpfr_ptr = x;
+                let ternary = mpfr::set_d(x, value, mpfr::rnd_t::RNDN);
+                limit_mpfr(self.choice, x, ternary);
+            },
+            #[cfg(not(feature = "mpfr"))]
+            _ => unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature."),
+            #[cfg(all(feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat is supported with twofloat feature only.")
+        }
+    }
+
+    /// Rounded to nearest.
+    pub fn to_f64_nearest(&self) -> f64 {
+        match &self.value {
+            &Value::F32(x) => x as f64,
+            &Value::F64(x) => x,
+            #[cfg(feature = "twofloat")]
+            Value::TwoFloat(x) => x.hi(),
+            Value::QuadFloat(x) => x.0[0],
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            Value::Decimal(x) => x.to_f64(),
+            #[cfg(feature = "f32_only")]
+            Value::Decimal(_) => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature."),
+            #[cfg(feature = "mpfr")]
+            Value::Mpfr(x) => unsafe { mpfr::get_d(x, mpfr::rnd_t::RNDN) },
+            #[cfg(not(feature = "mpfr"))]
+            _ => unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature."),
+            #[cfg(all(feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat is supported with twofloat feature only.")
+        }
+    }
+
+    /// Sets `self` to the value of `other`.
+    pub fn assign(&mut self, other: &UniFloatDyn<'_>) {
+        self.assign_binary(other, |_, y| y, |_, y| y, twofloat_op!(|_, y| y), |_, y| y, |_, y| y,
+            mpfr_op!(dyn |r, _, y| unsafe { mpfr::set(r, y, mpfr::rnd_t::RNDN) }));
+    }
+
+    pub fn sqrt_assign(&mut self) {
+        self.assign_unary(libm::sqrtf, libm::sqrt, twofloat_op!(|x| sqrt_twofloat(x)),
+            mpfr_op!(dyn |r, x| unsafe { mpfr::sqrt(r, x, mpfr::rnd_t::RNDN) }));
+    }
+
+    #[cfg_attr(any(not(feature = "mpfr"), not(feature = "twofloat")), allow(unused_variables))]
+    fn assign_unary(&mut self,
+        f32_op: impl FnOnce(f32) -> f32,
+        f64_op: impl FnOnce(f64) -> f64,
+        twofloat_op: impl FnOnce(TwoFloat) -> TwoFloat,
+        mpfr_op: impl FnOnce(mpfr::mpfr_ptr, mpfr::mpfr_srcptr) -> i32
+    ) {
+        match &mut self.value {
+            Value::F32(x) => *x = emulated_f32(f32_op(*x), &[*x]),
+            Value::F64(x) => *x = emulated_f64(f64_op(*x), &[*x]),
+            #[cfg(feature = "twofloat")]
+            Value::TwoFloat(x) => *x = emulated_twofloat(twofloat_op(*x), &[*x]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            Value::QuadFloat(x) => *x = emulated_quadfloat(Quad::via_mpfr([*x], |r, x| mpfr_op(r, x[0])), &[*x]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            Value::Decimal(x) =>
+                *x = emulated_decimal(Dec::via_mpfr(x.digits, [*x], |r, x| mpfr_op(r, x[0])), &[*x]),
+            #[cfg(feature = "f32_only")]
+            Value::QuadFloat(_) | Value::Decimal(_) => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature."),
+            #[cfg(feature = "mpfr")]
+            Value::Mpfr(x) => {
+                let x: mpfr::mpfr_ptr = x;
+                let ternary = mpfr_op(x, x);
+                limit_mpfr(self.choice, x, ternary);
+            },
+            #[cfg(not(feature = "mpfr"))]
+            _ => unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature."),
+            #[cfg(all(feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat is supported with twofloat feature only.")
+        }
+    }
+
+    #[cfg_attr(any(not(feature = "mpfr"), not(feature = "twofloat")), allow(unused_variables))]
+    fn assign_binary(&mut self, other: &UniFloatDyn<'_>,
+        f32_op: impl FnOnce(f32, f32) -> f32,
+        f64_op: impl FnOnce(f64, f64) -> f64,
+        twofloat_op: impl FnOnce(TwoFloat, TwoFloat) -> TwoFloat,
+        quadfloat_op: impl FnOnce(Quad, Quad) -> Quad,
+        decimal_op: impl FnOnce(Dec, Dec) -> Dec,
+        mpfr_op: impl FnOnce(mpfr::mpfr_ptr, mpfr::mpfr_srcptr, mpfr::mpfr_srcptr) -> i32
+    ) {
+        assert!(self.choice == other.choice, "UniFloatDyn operands need the same UniFloatChoice.");
+        match (&mut self.value, &other.value) {
+            (Value::F32(x), &Value::F32(y)) => *x = emulated_f32(f32_op(*x, y), &[*x, y]),
+            (Value::F64(x), &Value::F64(y)) => *x = emulated_f64(f64_op(*x, y), &[*x, y]),
+            #[cfg(feature = "twofloat")]
+            (Value::TwoFloat(x), &Value::TwoFloat(y)) => *x = emulated_twofloat(twofloat_op(*x, y), &[*x, y]),
+            (Value::QuadFloat(x), &Value::QuadFloat(y)) => *x = emulated_quadfloat(quadfloat_op(*x, y), &[*x, y]),
+            (Value::Decimal(x), &Value::Decimal(y)) => *x = emulated_decimal(decimal_op(*x, y), &[*x, y]),
+            #[cfg(feature = "mpfr")]
+            (Value::Mpfr(x), Value::Mpfr(y)) => {
+                let x: mpfr::mpfr_ptr = x;
+                let ternary = mpfr_op(x, x, y);
+                limit_mpfr(self.choice, x, ternary);
+            },
+            _ => unreachable!("The value matches the choice.")
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl UniFloatDyn<'static> {
+    /// NaN, with its MPFR limbs (if any) on the heap.
+    pub fn new(choice: UniFloatChoice) -> Self {
+        let mut limbs = Vec::with_capacity(Self::limbs_needed(choice));
+        limbs.resize(Self::limbs_needed(choice), core::mem::MaybeUninit::uninit());
+        // The heap buffer stays in place when the Vec moves.
+        let limbs_ptr = limbs.as_mut_ptr() as *mut gmp::limb_t;
+        Self::with_limbs(choice, limbs_ptr, Limbs::Owned(limbs))
+    }
+}
+
+/// See `limit_exponent_range()`.
+#[cfg(feature = "mpfr")]
+fn limit_mpfr(choice: UniFloatChoice, x: mpfr::mpfr_ptr, ternary: i32) {
+    if choice.in_mpfr() {
+        limit_exponent_range(choice.mpfr_bounds(), x, ternary);
+    }
+}
+
+// Modify-and-assign operators (+=, -=, *=, /=), as for `UniFloat`.
+macro_rules! impl_assign_op {
+    ($trait:ident, $method:ident, $mpfr_op:path, |$x:ident, $y:ident| $native_op:expr) => {
+        impl ops::$trait<&UniFloatDyn<'_>> for UniFloatDyn<'_> {
+            #[inline]
+            fn $method(&mut self, rhs: &UniFloatDyn<'_>) {
+                self.assign_binary(rhs, |$x, $y| $native_op, |$x, $y| $native_op, twofloat_op!(|$x, $y| $native_op),
+                    |$x, $y| $native_op, |$x, $y| $native_op, mpfr_op!(dyn |r, x, y| unsafe { $mpfr_op(r, x, y, mpfr::rnd_t::RNDN) }));
+            }
+        }
+    };
+}
+
+impl_assign_op!(AddAssign, add_assign, mpfr::add, |x, y| x + y);
+impl_assign_op!(SubAssign, sub_assign, mpfr::sub, |x, y| x - y);
+impl_assign_op!(MulAssign, mul_assign, mpfr::mul, |x, y| x * y);
+impl_assign_op!(DivAssign, div_assign, mpfr::div, |x, y| x / y);
+
+impl fmt::Debug for UniFloatDyn<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UniFloatDyn")
+            .field("value", &self.to_f64_nearest())
+            .field("choice", &self.choice)
+            .finish()
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+use crate::quadfloat::{quick_two_sum, two_prod, two_sum};
+
+/// Error-free transformations: an operation rounded to nearest (as the operators), together with
+/// its rounding error, as `(result, error)`. The building blocks of compensated algorithms (like
+/// `UniFloat::sum_compensated()`), and of double-double arithmetic.
+/// - f32 and f64: the classic algorithms (TwoSum, Fast2Sum, and TwoProd by `fma()`), so `result +
+///   error` is exactly the value of the operation, apart from products whose error underflows.
+/// - TwoFloat, QuadFloat and MPFR: the error is the exact difference, rounded once (as by
+///   `UniFloat::exact_sum()`). For MPFR that's exact unless it underflows. TwoFloat and QuadFloat
+///   don't always hold it exactly, as their results have gaps between their parts.
+///
+/// For infinite or NaN results the error is 0.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// `self + other` and its rounding error (TwoSum, by Knuth).
+    pub fn two_sum(&self, other: &Self) -> (Self, Self) {
+        self.error_free(other, two_sum_f32, two_sum, |x, y| *x += y, mpfr_op!(|x, y, sum| Self::sum_error(x, y, sum)))
+    }
+
+    /// As `two_sum()`, with fewer operations (Fast2Sum, by Dekker), but only for |`self`| >=
+    /// |`other`| (or `self` being zero). Otherwise the error may be wrong for f32 and f64.
+    pub fn fast_two_sum(&self, other: &Self) -> (Self, Self) {
+        self.error_free(other, fast_two_sum_f32, quick_two_sum, |x, y| *x += y, mpfr_op!(|x, y, sum| Self::sum_error(x, y, sum)))
+    }
+
+    /// `self * other` and its rounding error.
+    pub fn two_prod(&self, other: &Self) -> (Self, Self) {
+        self.error_free(other, two_prod_f32, two_prod, |x, y| *x *= y,
+            mpfr_op!(|x, y, product| {
+                let (mut factors, mut others) = ([*x, -product], [*y, Self::ONE]);
+                for value in factors.iter_mut().chain(others.iter_mut()) {
+                    value.copied();
+                }
+                Self::exact_dot(&factors, &others)
+            }))
+    }
+
+    #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(unused_mut, unused_variables))]
+    fn error_free(&self, other: &Self, f32_op: fn(f32, f32) -> (f32, f32), f64_op: fn(f64, f64) -> (f64, f64),
+        op: impl FnOnce(&mut Self, &Self), exact_error: impl FnOnce(&Self, &Self, &Self) -> Self) -> (Self, Self) {
+        self.assert_copy_fixed();
+        other.assert_copy_fixed();
+        let (mut result, mut error) = (Self::NAN, Self::ZERO);
+        match C {
+            UniFloatChoice::F32 => {
+                let (value, value_error) = f32_op(self.f32s[0], other.f32s[0]);
+                result.f32s[0] = value;
+                if value.is_finite() {
+                    error.f32s[0] = value_error;
+                }
+            },
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => {
+                let (value, value_error) = f64_op(self.f64s[0], other.f64s[0]);
+                result.f64s[0] = value;
+                if value.is_finite() {
+                    error.f64s[0] = value_error;
+                }
+            },
+            // Without MPFR, TwoFloat has no exact error.
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => {
+                let () = Supported::<C>::TWOFLOAT_VIA_MPFR;
+                unreachable!("Error-free transformations of TwoFloat are supported with mpfr feature only.")
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            _ => {
+                result = *self;
+                result.copied();
+                op(&mut result, other);
+                if result.is_finite() {
+                    error = exact_error(self, other, &result);
+                    error.copied();
+                }
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        (result.released(), error.released())
+    }
+
+    #[cfg(feature = "mpfr")]
+    fn sum_error(x: &Self, y: &Self, sum: &Self) -> Self {
+        let mut terms = [*x, *y, -sum];
+        for term in terms.iter_mut() {
+            term.copied();
+        }
+        Self::exact_sum(&terms)
+    }
+}
+
+fn two_sum_f32(a: f32, b: f32) -> (f32, f32) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    (sum, (a - (sum - b_virtual)) + (b - b_virtual))
+}
+
+fn fast_two_sum_f32(a: f32, b: f32) -> (f32, f32) {
+    let sum = a + b;
+    (sum, b - (sum - a))
+}
+
+fn two_prod_f32(a: f32, b: f32) -> (f32, f32) {
+    let product = a * b;
+    (product, libm::fmaf(a, b, -product))
+}
@@ -0,0 +1,190 @@
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use {core::mem::MaybeUninit, crate::mpfr};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(feature = "twofloat")]
+use crate::trigonometry::PI_TWOFLOAT;
+
+/// Arithmetic-geometric mean, and the complete elliptic integrals that it gives. They are
+/// correctly rounded for MPFR (see `mpfr_elliptic()` for the integrals). f64 is computed with
+/// TwoFloat, so it's nearly always correctly rounded, and f32 with f64 (both need feature
+/// `twofloat`). TwoFloat iterates the mean
+/// in double-double, with relative error below 1e-26 (but the error of `elliptic_e()` grows near
+/// m = 1, and for large negative m).
+///
+/// The elliptic integrals take the parameter m (which is k^2 for modulus k), as MPFR and Abramowitz
+/// & Stegun. They're NaN for m > 1.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Arithmetic-geometric mean of `self` and `other`. As MPFR: NaN if either is negative, zero if
+    /// either is zero (but NaN for zero and +Inf), and +Inf if either is +Inf (and the other is
+    /// positive).
+    pub fn agm(&self, other: &Self) -> Self {
+        self.binary(other,
+            twofloat_op!(|x, y| agm_twofloat(TwoFloat::from(x as f64), TwoFloat::from(y as f64)).hi() as f32),
+            twofloat_op!(|x, y| agm_twofloat(TwoFloat::from(x), TwoFloat::from(y)).hi()),
+            twofloat_op!(|x, y| agm_twofloat(x, y)),
+            mpfr_op!(|r, x, y| unsafe { mpfr::agm(r, x, y, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Complete elliptic integral of the first kind, K(m) = π / (2 * agm(1, sqrt(1 - m))). +Inf
+    /// for m = 1, and +0 for m = -Inf.
+    pub fn elliptic_k(&self) -> Self {
+        self.unary(twofloat_op!(|m| elliptic_twofloat(TwoFloat::from(m as f64)).0.hi() as f32),
+            twofloat_op!(|m| elliptic_twofloat(TwoFloat::from(m)).0.hi()),
+            twofloat_op!(|m| elliptic_twofloat(m).0),
+            mpfr_op!(|r, m| unsafe { mpfr_elliptic(r, m, false) }))
+    }
+
+    /// Complete elliptic integral of the second kind, E(m). 1 for m = 1, and +Inf for m = -Inf.
+    pub fn elliptic_e(&self) -> Self {
+        self.unary(twofloat_op!(|m| elliptic_twofloat(TwoFloat::from(m as f64)).1.hi() as f32),
+            twofloat_op!(|m| elliptic_twofloat(TwoFloat::from(m)).1.hi()),
+            twofloat_op!(|m| elliptic_twofloat(m).1),
+            mpfr_op!(|r, m| unsafe { mpfr_elliptic(r, m, true) }))
+    }
+}
+
+#[cfg(feature = "twofloat")]
+fn agm_twofloat(x: TwoFloat, y: TwoFloat) -> TwoFloat {
+    let (x_hi, y_hi) = (x.hi(), y.hi());
+    if x_hi.is_nan() || y_hi.is_nan() || x_hi < 0.0 || y_hi < 0.0 {
+        TwoFloat::from(f64::NAN)
+    } else if x_hi == 0.0 || y_hi == 0.0 {
+        TwoFloat::from(if x_hi.is_infinite() || y_hi.is_infinite() { f64::NAN } else { 0.0 })
+    } else if x_hi.is_infinite() || y_hi.is_infinite() {
+        TwoFloat::from(f64::INFINITY)
+    } else {
+        let (mut a, mut b) = (x, y);
+        // It converges quadratically, once a and b are close. Until then, each step at least
+        // halves log(a / b).
+        for _ in 0..100 {
+            let next_a = (a + b) * 0.5;
+            // Then next_a is within (a - b)^2 / (8 * a) of the mean, less than 1e-34 relative.
+            if (a - b).abs().hi() <= 1e-17 * a.hi() {
+                return next_a;
+            }
+            // Not sqrt(a * b), which could overflow.
+            b = a.sqrt() * b.sqrt();
+            a = next_a;
+        }
+        a
+    }
+}
+
+/// (K(m), E(m)), by the arithmetic-geometric mean of a_0 = 1 and b_0 = sqrt(1 - m). With c_0^2 = m
+/// and c_n = (a_(n-1) - b_(n-1)) / 2, E(m) = K(m) * (1 - sum of 2^(n-1) * c_n^2, for n = 0...). The
+/// terms are computed as c_n^2 = c_(n-1)^4 / (16 * a_n^2), which doesn't cancel.
+#[cfg(feature = "twofloat")]
+fn elliptic_twofloat(m: TwoFloat) -> (TwoFloat, TwoFloat) {
+    let one = TwoFloat::from(1.0);
+    if m.hi().is_nan() || m > one {
+        return (TwoFloat::from(f64::NAN), TwoFloat::from(f64::NAN));
+    } else if m == one {
+        return (TwoFloat::from(f64::INFINITY), one);
+    } else if m.hi() == f64::NEG_INFINITY {
+        return (TwoFloat::from(0.0), TwoFloat::from(f64::INFINITY));
+    }
+    let (mut a, mut b) = (one, (one - m).sqrt());
+    let (mut c_square, mut weight) = (m, 0.5);
+    let mut sum = c_square * weight;
+    for _ in 0..100 {
+        let next_a = (a + b) * 0.5;
+        // Not c_square * c_square first, which could overflow for large negative m.
+        c_square = c_square * (c_square / next_a / next_a) / 16.0;
+        b = a.sqrt() * b.sqrt();
+        a = next_a;
+        weight *= 2.0;
+        sum = sum + c_square * weight;
+        if c_square.abs().hi() <= 1e-34 * (a * a).hi() {
+            break;
+        }
+    }
+    let k = TwoFloat::new_add(PI_TWOFLOAT.0, PI_TWOFLOAT.1) / (a * 2.0);
+    (k, k * (one - sum))
+}
+
+/// Correctly rounded K(m) (or E(m), if `second_kind`), as `elliptic_twofloat()`, by Ziv's strategy
+/// (as `mpfr_log()` in the logarithm module). The mean and the sum are iterated at a higher
+/// (working) precision, until the result can be rounded. The results are irrational (even for
+/// m = 0, they're π/2), but the working precision is limited all the same.
+#[cfg(feature = "mpfr")]
+unsafe fn mpfr_elliptic(r: mpfr::mpfr_ptr, m: mpfr::mpfr_srcptr, second_kind: bool) -> i32 {
+    if mpfr::nan_p(m) != 0 || mpfr::cmp_ui(m, 1) > 0 {
+        mpfr::set_nan(r);
+        return 0;
+    } else if mpfr::cmp_ui(m, 1) == 0 {
+        return if second_kind { mpfr::set_ui(r, 1, mpfr::rnd_t::RNDN) } else { mpfr::set_inf(r, 1); 0 };
+    } else if mpfr::inf_p(m) != 0 {
+        if second_kind { mpfr::set_inf(r, 1) } else { mpfr::set_zero(r, 1) };
+        return 0;
+    }
+    let precision = mpfr::get_prec(r);
+    let mut working = precision + 32;
+    let mut temporaries = [MaybeUninit::uninit(), MaybeUninit::uninit(), MaybeUninit::uninit(), MaybeUninit::uninit(),
+        MaybeUninit::uninit(), MaybeUninit::uninit()];
+    for temporary in temporaries.iter_mut() {
+        mpfr::init2(temporary.as_mut_ptr(), working);
+    }
+    let [a, b, next_a, c_square, sum, result] = {
+        let [a, b, next_a, c_square, sum, result] = &mut temporaries;
+        [a.as_mut_ptr(), b.as_mut_ptr(), next_a.as_mut_ptr(), c_square.as_mut_ptr(), sum.as_mut_ptr(), result.as_mut_ptr()]
+    };
+    let ternary = loop {
+        mpfr::set_ui(a, 1, mpfr::rnd_t::RNDN);
+        mpfr::ui_sub(b, 1, m, mpfr::rnd_t::RNDN);
+        mpfr::sqrt(b, b, mpfr::rnd_t::RNDN);
+        mpfr::set(c_square, m, mpfr::rnd_t::RNDN);
+        mpfr::div_2ui(sum, m, 1, mpfr::rnd_t::RNDN);
+        let mut steps = 0u32;
+        // `result` is a scratch variable here.
+        while mpfr::zero_p(c_square) == 0
+            && mpfr::get_exp(c_square) > 2 * mpfr::get_exp(a) - working as mpfr::exp_t - 8 && steps < 1000 {
+            steps += 1;
+            mpfr::add(next_a, a, b, mpfr::rnd_t::RNDN);
+            mpfr::div_2ui(next_a, next_a, 1, mpfr::rnd_t::RNDN);
+            mpfr::div(result, c_square, next_a, mpfr::rnd_t::RNDN);
+            mpfr::div(result, result, next_a, mpfr::rnd_t::RNDN);
+            mpfr::mul(c_square, c_square, result, mpfr::rnd_t::RNDN);
+            mpfr::div_2ui(c_square, c_square, 4, mpfr::rnd_t::RNDN);
+            mpfr::mul(b, a, b, mpfr::rnd_t::RNDN);
+            mpfr::sqrt(b, b, mpfr::rnd_t::RNDN);
+            mpfr::swap(a, next_a);
+            mpfr::mul_2ui(result, c_square, (steps - 1).into(), mpfr::rnd_t::RNDN);
+            mpfr::add(sum, sum, result, mpfr::rnd_t::RNDN);
+        }
+        mpfr::const_pi(result, mpfr::rnd_t::RNDN);
+        mpfr::div(result, result, a, mpfr::rnd_t::RNDN);
+        mpfr::div_2ui(result, result, 1, mpfr::rnd_t::RNDN);
+        // Each step rounds a few times, with relative error up to 2^-working each, and the mean
+        // doesn't amplify that. With fewer than 2^6 steps (each one at least halves log(a / b)
+        // until they're close), that's less than 2^(10 - working) relative.
+        let mut error_bits = working as mpfr::exp_t - 10;
+        if second_kind {
+            // 1 - sum cancels, up to the ratio of their magnitudes.
+            let sum_exponent = if mpfr::zero_p(sum) != 0 { mpfr::get_exp(a) } else { mpfr::get_exp(sum) };
+            mpfr::ui_sub(sum, 1, sum, mpfr::rnd_t::RNDN);
+            error_bits -= (sum_exponent - mpfr::get_exp(sum)).max(0) + 1;
+            mpfr::mul(result, result, sum, mpfr::rnd_t::RNDN);
+        }
+        if mpfr::can_round(result, error_bits, mpfr::rnd_t::RNDN, mpfr::rnd_t::RNDZ, precision + 1) != 0
+            || working > 2 * precision + 256 {
+            break mpfr::set(r, result, mpfr::rnd_t::RNDN);
+        }
+        working += working / 2;
+        for temporary in temporaries.iter_mut() {
+            mpfr::set_prec(temporary.as_mut_ptr(), working);
+        }
+    };
+    for temporary in temporaries.iter_mut() {
+        mpfr::clear(temporary.as_mut_ptr());
+    }
+    ternary
+}
@@ -0,0 +1,93 @@
+use core::fmt;
+
+use crate::{MpfrBounds, UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_128_BITS: UniFloatChoice = UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(128) };
+const MPFR_256_BITS: UniFloatChoice = UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(256) };
+const MPFR_512_BITS: UniFloatChoice = UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(512) };
+const MPFR_1024_BITS: UniFloatChoice = UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(1024) };
+const MPFR_2048_BITS: UniFloatChoice = UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(2048) };
+const MPFR_4096_BITS: UniFloatChoice = UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(4096) };
+
+/// Choices that `escalate()` tries, in this order.
+pub const ESCALATION_CHOICES: [UniFloatChoice; 8] = [
+    UniFloatChoice::F64, UniFloatChoice::TwoFloat, MPFR_128_BITS, MPFR_256_BITS, MPFR_512_BITS,
+    MPFR_1024_BITS, MPFR_2048_BITS, MPFR_4096_BITS
+];
+
+/// A computation for `escalate()`. Its methods are generic, since each choice is a different type.
+/// Any state between the attempts (like a result from a previous choice, to compare with) goes
+/// into `self`.
+pub trait Escalation {
+    /// The result at choice `C`.
+    fn compute<const C: UniFloatChoice>(&mut self) -> UniFloat<C> where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized;
+
+    /// Whether `result` (from `compute::<C>()`) is accurate enough.
+    /// Otherwise `escalate()` tries the next choice.
+    fn converged<const C: UniFloatChoice>(&mut self, result: &UniFloat<C>) -> bool where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized;
+}
+
+/// Error from `escalate()`: no choice up to the last one of `ESCALATION_CHOICES` converged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NotConverged;
+
+impl fmt::Display for NotConverged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no convergence up to {:?}", MPFR_4096_BITS)
+    }
+}
+
+/// Runs `escalation` over progressively more precise choices (see `ESCALATION_CHOICES`) until it
+/// converges (Ziv's strategy). Returns the converged result, rounded to nearest for choice `R` (as
+/// `UniFloat::convert()`).
+pub fn escalate<const R: UniFloatChoice, E: Escalation>(escalation: &mut E) -> Result<UniFloat<R>, NotConverged> where
+[f32; f32_parts_length(R)]: Sized,
+[f64; f64_parts_length(R)]: Sized,
+[TwoFloatPart; twofloat_parts_length(R)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(R)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(R)]: Sized,
+{
+    macro_rules! attempts {
+        ($($choice:expr),+) => {
+            $(
+                if let Some(result) = attempt::<{ $choice }, R, E>(escalation) {
+                    return Ok(result);
+                }
+            )+
+        };
+    }
+    attempts!(UniFloatChoice::F64, UniFloatChoice::TwoFloat, MPFR_128_BITS, MPFR_256_BITS, MPFR_512_BITS,
+        MPFR_1024_BITS, MPFR_2048_BITS, MPFR_4096_BITS);
+    Err(NotConverged)
+}
+
+fn attempt<const C: UniFloatChoice, const R: UniFloatChoice, E: Escalation>(escalation: &mut E) -> Option<UniFloat<R>> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+[f32; f32_parts_length(R)]: Sized,
+[f64; f64_parts_length(R)]: Sized,
+[TwoFloatPart; twofloat_parts_length(R)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(R)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(R)]: Sized,
+{
+    let mut result = escalation.compute::<C>();
+    result.copied();
+    if escalation.converged(&result) {
+        Some(result.convert::<R>())
+    } else {
+        None
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use crate::mpfr;
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(feature = "twofloat")]
+use crate::parts::twofloat_or_f64;
+
+/// ln(2) as double-double: the nearest f64, and the rest.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+pub(crate) const LN_2: (f64, f64) = (core::f64::consts::LN_2, 2.3190468138462996e-17);
+
+/// ln(10) as double-double: the nearest f64, and the rest.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+pub(crate) const LN_10: (f64, f64) = (2.302585092994046, -2.1707562233822494e-16);
+
+/// Exponential functions. Accuracy:
+/// - MPFR: correctly rounded.
+/// - f32: computed with f64, so nearly always correctly rounded.
+/// - f64: within 1 ulp (as `libm`).
+/// - TwoFloat: TwoFloat's own algorithms, with relative error below 1e-26. `exp10()` multiplies
+///   by ln(10) in double-double first, which adds relative error up to about |x| * 1e-32.
+///
+/// NaN, infinities, zeros, overflows and underflows are as in IEEE 754.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// e^self.
+    pub fn exp(&self) -> Self {
+        self.unary(|x| libm::exp(x as f64) as f32, libm::exp,
+            twofloat_op!(|x| twofloat_or_f64(x, libm::exp(x.hi()), |x| x.exp())),
+            mpfr_op!(|r, x| unsafe { mpfr::exp(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// 2^self.
+    pub fn exp2(&self) -> Self {
+        self.unary(|x| libm::exp2(x as f64) as f32, libm::exp2,
+            twofloat_op!(|x| twofloat_or_f64(x, libm::exp2(x.hi()), |x| x.exp2())),
+            mpfr_op!(|r, x| unsafe { mpfr::exp2(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// 10^self.
+    pub fn exp10(&self) -> Self {
+        self.unary(|x| libm::exp10(x as f64) as f32, libm::exp10,
+            twofloat_op!(|x| twofloat_or_f64(x, libm::exp10(x.hi()), |x| (x * TwoFloat::new_add(LN_10.0, LN_10.1)).exp())),
+            mpfr_op!(|r, x| unsafe { mpfr::exp10(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// e^self - 1, accurate for `self` near zero, too.
+    pub fn exp_m1(&self) -> Self {
+        self.unary(|x| libm::expm1(x as f64) as f32, libm::expm1,
+            twofloat_op!(|x| twofloat_or_f64(x, libm::expm1(x.hi()), |x| x.exp_m1())),
+            mpfr_op!(|r, x| unsafe { mpfr::expm1(r, x, mpfr::rnd_t::RNDN) }))
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{UniComplex, UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Discrete Fourier transforms of slices, in place: `fft()` replaces x[0..n] with X[k] = the sum
+/// of x[j] e^(-2πi jk/n), and `ifft()` replaces X[0..n] with x[j] = (the sum of X[k] e^(2πi jk/n))
+/// / n, so `ifft()` undoes `fft()` (apart from rounding).
+/// - `fft()` and `ifft()`: iterative radix-2 Cooley-Tukey, with no extra memory. They panic for
+///   lengths that aren't powers of two.
+/// - `fft_mixed()` and `ifft_mixed()`: any length, by mixed-radix Cooley-Tukey (splitting by the
+///   smallest prime factor first). They need `scratch`, at least as long as `values`, and overwrite
+///   it. A prime factor p costs O(p^2) operations, so for a prime length they're a plain DFT.
+///
+/// Twiddle factors are computed at the precision of the choice, by `UniFloat::cos_pi()` and
+/// `UniFloat::sin_pi()` of 2jk/n (which is exact for powers of two), rather than from a rounded π
+/// or by a recurrence, so they don't add up errors. Each one is computed once per stage.
+impl <const C: UniFloatChoice> UniComplex<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub fn fft(values: &mut [Self]) {
+        Self::radix_2(values, false);
+    }
+
+    pub fn ifft(values: &mut [Self]) {
+        Self::radix_2(values, true);
+        Self::scale_down(values);
+    }
+
+    pub fn fft_mixed(values: &mut [Self], scratch: &mut [Self]) {
+        Self::mixed(values, scratch, false);
+    }
+
+    pub fn ifft_mixed(values: &mut [Self], scratch: &mut [Self]) {
+        Self::mixed(values, scratch, true);
+        Self::scale_down(values);
+    }
+
+    fn radix_2(values: &mut [Self], inverse: bool) {
+        let n = values.len();
+        assert!(n == 0 || n.is_power_of_two(), "fft() and ifft() need a length that's a power of two.");
+        if n <= 1 {
+            return;
+        }
+        let bits = n.trailing_zeros();
+        for i in 0..n {
+            let j = i.reverse_bits() >> (usize::BITS - bits);
+            if i < j {
+                values.swap(i, j);
+                values[i].copied();
+                values[j].copied();
+            }
+        }
+        let mut half = 1;
+        while half < n {
+            let size = 2 * half;
+            for k in 0..half {
+                let mut twiddle = Self::twiddle(k, size, inverse);
+                twiddle.copied();
+                for start in (0..n).step_by(size) {
+                    let (i, j) = (start + k, start + k + half);
+                    let mut product = if k == 0 { values[j] } else { &values[j] * &twiddle };
+                    product.copied();
+                    values[j] = &values[i] - &product;
+                    values[j].copied();
+                    values[i] = &values[i] + &product;
+                    values[i].copied();
+                }
+            }
+            half = size;
+        }
+    }
+
+    fn mixed(values: &mut [Self], scratch: &mut [Self], inverse: bool) {
+        assert!(scratch.len() >= values.len(), "fft_mixed() and ifft_mixed() need scratch at least as long as values.");
+        if values.is_empty() {
+            return;
+        }
+        let scratch = &mut scratch[..values.len()];
+        for (slot, value) in scratch.iter_mut().zip(values.iter()) {
+            *slot = *value;
+            slot.copied();
+        }
+        Self::mixed_step(scratch, 0, 1, values, inverse);
+    }
+
+    /// Transforms `input[offset]`, `input[offset + stride]`, ... (as many as `output` is long) into
+    /// `output`, by decimation in time. Afterwards, those items of `input` are left undefined.
+    fn mixed_step(input: &mut [Self], offset: usize, stride: usize, output: &mut [Self], inverse: bool) {
+        let n = output.len();
+        if n == 1 {
+            output[0] = input[offset];
+            output[0].copied();
+            return;
+        }
+        let radix = smallest_factor(n);
+        let m = n / radix;
+        for (r, part) in output.chunks_mut(m).enumerate() {
+            Self::mixed_step(input, offset + r * stride, stride * radix, part, inverse);
+        }
+        // The input isn't needed anymore, so it holds the partial transforms, twiddled.
+        for (index, value) in output.iter().enumerate() {
+            let exponent = (index / m) * (index % m);
+            let slot = offset + index * stride;
+            input[slot] = if exponent == 0 {
+                *value
+            } else {
+                let mut twiddle = Self::twiddle(exponent, n, inverse);
+                twiddle.copied();
+                value * &twiddle
+            };
+            input[slot].copied();
+        }
+        // DFTs of length radix, across the partial transforms.
+        for q in 0..radix {
+            for r in 0..radix {
+                let exponent = r * q % radix;
+                let mut twiddle = Self::twiddle(exponent, radix, inverse);
+                twiddle.copied();
+                for k in 0..m {
+                    let term = &input[offset + (r * m + k) * stride];
+                    let mut product = if exponent == 0 { *term } else { term * &twiddle };
+                    product.copied();
+                    let target = &mut output[q * m + k];
+                    *target = if r == 0 { product } else { &*target + &product };
+                    target.copied();
+                }
+            }
+        }
+    }
+
+    /// e^(-2πi `exponent`/`n`), or e^(2πi `exponent`/`n`) for the inverse.
+    fn twiddle(exponent: usize, n: usize, inverse: bool) -> Self {
+        let mut angle = UniFloat::<C>::from((2 * (exponent % n)) as u64);
+        angle.copied();
+        let mut count = UniFloat::<C>::from(n as u64);
+        count.copied();
+        angle /= &count;
+        if !inverse {
+            let negated = -&angle;
+            angle <<= negated;
+        }
+        let (mut re, mut im) = (angle.cos_pi(), angle.sin_pi());
+        re.copied();
+        im.copied();
+        Self::new(&re, &im)
+    }
+
+    /// Divides each of `values` by their count.
+    fn scale_down(values: &mut [Self]) {
+        let mut count = UniFloat::<C>::from(values.len() as u64);
+        count.copied();
+        for value in values.iter_mut() {
+            let (mut re, mut im) = (*value.re(), *value.im());
+            re.copied();
+            im.copied();
+            re /= &count;
+            im /= &count;
+            *value = Self::new(&re, &im);
+            value.copied();
+        }
+    }
+}
+
+fn smallest_factor(n: usize) -> usize {
+    (2..).take_while(|factor| factor * factor <= n).find(|factor| n % factor == 0).unwrap_or(n)
+}
@@ -0,0 +1,171 @@
+use core::sync::atomic::{AtomicU8, Ordering};
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+
+use crate::{decimal::Dec, quadfloat::Quad};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::mpfr;
+
+const INEXACT: u8 = 1;
+const UNDERFLOW: u8 = 2;
+const OVERFLOW: u8 = 4;
+const INVALID: u8 = 8;
+const DIVIDE_BY_ZERO: u8 = 16;
+
+/// Flags raised by f32, f64, TwoFloat, QuadFloat and decimal operations. MPFR keeps its own.
+static EMULATED: AtomicU8 = AtomicU8::new(0);
+
+/// IEEE 754 exception flags. They are sticky: operations raise them, and only `Flags::clear()`
+/// lowers them. That lets long computations check once at the end, rather than after each step.
+/// - MPFR: MPFR's own flags. They are global (per thread, if MPFR was built with thread-local
+///   storage). MPFR raises them in intermediate steps, too (like in the Ziv loops of this crate),
+///   so `inexact()` may be raised for results that are exact. Underflow and overflow are relative
+///   to MPFR's exponent range, which is much wider than f64's.
+/// - f32, f64, TwoFloat, QuadFloat and decimals: emulated by operations of this crate (not by Rust's own
+///   operators on primitives), and shared by all threads. From the operands and the result only:
+///   NaN from non-NaN operands is `invalid()`; an infinity from finite operands is
+///   `divide_by_zero()` if any operand is zero, and `overflow()` (and `inexact()`) otherwise; a
+///   subnormal result from finite operands is `underflow()`. Hence poles at non-zero arguments
+///   (like `tan_pi(0.5)`) count as overflow, subnormal results count as underflow even if they're
+///   exact, results that underflow to zero don't count, and `inexact()` is raised by overflow
+///   only. QuadFloat and decimal operations other than `+`, `-`, `*` and `/` run in MPFR, so they
+///   raise MPFR's flags, too.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Flags {
+    bits: u8,
+}
+
+impl Flags {
+    /// Lowers all flags (MPFR's and the emulated ones).
+    pub fn clear() {
+        EMULATED.store(0, Ordering::Relaxed);
+        #[cfg(not(feature = "f32_only"))]
+        #[cfg(feature = "mpfr")]
+        unsafe { mpfr::clear_flags() };
+    }
+
+    /// The flags raised since the last `clear()`, by any choice.
+    pub fn read() -> Self {
+        #[allow(unused_mut)]
+        let mut bits = EMULATED.load(Ordering::Relaxed);
+        #[cfg(not(feature = "f32_only"))]
+        #[cfg(feature = "mpfr")]
+        unsafe {
+            for &(raised, bit) in [(mpfr::inexflag_p(), INEXACT), (mpfr::underflow_p(), UNDERFLOW),
+                (mpfr::overflow_p(), OVERFLOW), (mpfr::nanflag_p(), INVALID), (mpfr::divby0_p(), DIVIDE_BY_ZERO)].iter() {
+                if raised != 0 {
+                    bits |= bit;
+                }
+            }
+        }
+        Self { bits }
+    }
+
+    /// The result was rounded.
+    pub fn inexact(&self) -> bool {
+        self.bits & INEXACT != 0
+    }
+
+    /// A result was subnormal (tiny).
+    pub fn underflow(&self) -> bool {
+        self.bits & UNDERFLOW != 0
+    }
+
+    /// A finite result was too large, so it became an infinity.
+    pub fn overflow(&self) -> bool {
+        self.bits & OVERFLOW != 0
+    }
+
+    /// A result was NaN, from operands that weren't NaN (like 0 / 0, or `sqrt(-1)`).
+    pub fn invalid(&self) -> bool {
+        self.bits & INVALID != 0
+    }
+
+    /// A result was an exact infinity, from finite operands (like 1 / 0, or `ln(0)`).
+    pub fn divide_by_zero(&self) -> bool {
+        self.bits & DIVIDE_BY_ZERO != 0
+    }
+
+    /// Any flag was raised.
+    pub fn any(&self) -> bool {
+        self.bits != 0
+    }
+}
+
+/// Raises the flags for `result` (which is not finite, or tiny) from `operands`. See `Flags`.
+fn raise(result_nan: bool, result_infinite: bool, operands: impl Iterator<Item = f64>) {
+    let (mut any_nan, mut all_finite, mut any_zero) = (false, true, false);
+    for operand in operands {
+        any_nan |= operand.is_nan();
+        all_finite &= operand.is_finite();
+        any_zero |= operand == 0.0;
+    }
+    let bits = if result_nan {
+        if any_nan { 0 } else { INVALID }
+    } else if !all_finite {
+        0
+    } else if result_infinite {
+        if any_zero { DIVIDE_BY_ZERO } else { OVERFLOW | INEXACT }
+    } else {
+        UNDERFLOW
+    };
+    if bits != 0 {
+        EMULATED.fetch_or(bits, Ordering::Relaxed);
+    }
+}
+
+/// `result` of an f32 operation on `operands`, after it raised the flags (if any).
+pub(crate) fn emulated_f32(result: f32, operands: &[f32]) -> f32 {
+    if !result.is_finite() || (result != 0.0 && libm::fabsf(result) < f32::MIN_POSITIVE) {
+        raise(result.is_nan(), result.is_infinite(), operands.iter().map(|&x| x as f64));
+    }
+    result
+}
+
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+pub(crate) fn emulated_f64(result: f64, operands: &[f64]) -> f64 {
+    if !result.is_finite() || (result != 0.0 && libm::fabs(result) < f64::MIN_POSITIVE) {
+        raise(result.is_nan(), result.is_infinite(), operands.iter().copied());
+    }
+    result
+}
+
+/// By the higher parts only.
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+#[cfg(feature = "twofloat")]
+pub(crate) fn emulated_twofloat(result: TwoFloat, operands: &[TwoFloat]) -> TwoFloat {
+    let high = result.hi();
+    if !high.is_finite() || (high != 0.0 && libm::fabs(high) < f64::MIN_POSITIVE) {
+        raise(high.is_nan(), high.is_infinite(), operands.iter().map(|x| x.hi()));
+    }
+    result
+}
+
+/// By the first components only.
+#[cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(dead_code))]
+pub(crate) fn emulated_quadfloat(result: Quad, operands: &[Quad]) -> Quad {
+    let high = result.0[0];
+    if !high.is_finite() || (high != 0.0 && libm::fabs(high) < f64::MIN_POSITIVE) {
+        raise(high.is_nan(), high.is_infinite(), operands.iter().map(|x| x.0[0]));
+    }
+    result
+}
+
+/// Subnormal means with fewer than all digits at the least exponent (see `Dec::is_subnormal()`).
+#[cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(dead_code))]
+pub(crate) fn emulated_decimal(result: Dec, operands: &[Dec]) -> Dec {
+    if result.is_nan() || result.is_infinite() || result.is_subnormal() {
+        // Only the class of each operand matters.
+        raise(result.is_nan(), result.is_infinite(), operands.iter().map(|x| if x.is_nan() {
+            f64::NAN
+        } else if x.is_infinite() {
+            f64::INFINITY
+        } else if x.is_zero() {
+            0.0
+        } else {
+            1.0
+        }));
+    }
+    result
+}
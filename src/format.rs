@@ -0,0 +1,769 @@
+#![cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(dead_code))]
+
+use core::{cmp::Ordering, fmt::{self, Write}};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+use crate::mpfr;
+
+use crate::{big::Big, RoundingMode, UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::convert::MPFR_TWOFLOAT_EXACT;
+
+/// Most significant decimal digits that formatting of MPFR-based (and TwoFloat-based or
+/// QuadFloat-based) UniFloat produces. That's enough for the shortest round-trip representation
+/// of about 3300 bits. With a precision flag that asks for more digits, the rest are zeros.
+pub const MAX_FORMAT_DIGITS: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Notation {
+    /// As `Display`: no exponent, like "1234.5".
+    Fixed,
+    /// As `LowerExp`, like "1.2345e3".
+    LowerExp,
+    /// As `UpperExp`, like "1.2345E3".
+    UpperExp,
+}
+
+/// Options for `UniFloat::write_to()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    pub notation: Notation,
+    /// Number of digits after the decimal point. If None, use the shortest digits that parse back
+    /// to the same value (for the same choice).
+    pub precision: Option<usize>,
+    /// Whether to write '+' for positive values (including +0 and +Inf).
+    pub sign_plus: bool,
+}
+
+impl Default for FormatOptions {
+    /// Same as `Display`.
+    fn default() -> Self {
+        Self { notation: Notation::Fixed, precision: None, sign_plus: false }
+    }
+}
+
+/// Error from `UniFloat::write_to()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    required: usize,
+}
+
+impl BufferTooSmall {
+    /// Length (in bytes) that the buffer needs.
+    pub fn required(&self) -> usize {
+        self.required
+    }
+}
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "buffer too small, {} bytes required", self.required)
+    }
+}
+
+/// 67 * 32 = 2144 bits are enough for the exact sum of two f64 (like the parts of a TwoFloat)
+/// scaled by 2^1074 to an integer (of up to 2098 bits), and for its fraction part (of 1074 bits)
+/// multiplied by the base.
+pub(crate) type Big2144 = Big<67>;
+
+/// `|high + low|` scaled by 2^1074, exactly. Both must be finite, and `|low|` must not be greater
+/// than `|high|` (as for the parts of a TwoFloat).
+pub(crate) fn scaled_sum(high: f64, low: f64) -> Big2144 {
+    let scaled = |value: f64| {
+        let bits = value.to_bits();
+        let (biased_exponent, mantissa) = ((bits >> 52) & 0x7ff, bits & ((1 << 52) - 1));
+        // |value| = significand * 2^exponent
+        let (significand, exponent) = if biased_exponent == 0 {
+            (mantissa, -1074)
+        } else {
+            (mantissa | 1 << 52, biased_exponent as i32 - 1075)
+        };
+        Big2144::new(significand, (exponent + 1074) as u32)
+    };
+    let (mut sum, other) = (scaled(high), scaled(low));
+    if high.is_sign_negative() == low.is_sign_negative() {
+        sum.add(&other);
+    } else {
+        sum.sub(&other);
+    }
+    sum
+}
+
+/// Exact digits of an f64 (or of the sum of two) in the given base (as numbers, rather than
+/// characters), from the most significant one. Once it returns None, all the remaining digits are
+/// zeros. (With an odd base, a fraction never ends.)
+pub(crate) struct ExactDigits {
+    base: u32,
+    /// Least significant first. Up to 1024 for base 2.
+    integer: [u8; 1024],
+    integer_length: usize,
+    /// The fraction part, with `FRACTION_BITS` bits after the binary point.
+    fraction: Big2144,
+}
+
+const FRACTION_BITS: u32 = 1074;
+
+impl ExactDigits {
+    /// Digits of `|high + low|`, as for `scaled_sum()`.
+    pub(crate) fn new(high: f64, low: f64, base: u32) -> Self {
+        let mut fraction = scaled_sum(high, low);
+        let mut integer = fraction.shifted_right(FRACTION_BITS);
+        fraction.sub(&integer.shifted_left(FRACTION_BITS));
+        let mut result = ExactDigits { base, integer: [0; 1024], integer_length: 0, fraction };
+        while !integer.is_zero() {
+            result.integer[result.integer_length] = integer.div_small(base) as u8;
+            result.integer_length += 1;
+        }
+        result
+    }
+
+    /// Number of the integer digits not returned by `next()` yet.
+    pub(crate) fn integer_length(&self) -> usize {
+        self.integer_length
+    }
+
+    pub(crate) fn next(&mut self) -> Option<u8> {
+        if self.integer_length > 0 {
+            self.integer_length -= 1;
+            Some(self.integer[self.integer_length])
+        } else if self.fraction.is_zero() {
+            None
+        } else {
+            self.fraction.mul_small(self.base);
+            Some(self.fraction.split_at(FRACTION_BITS) as u8)
+        }
+    }
+
+    /// Compare the remaining digits (as a fraction of the last returned digit) with one half.
+    pub(crate) fn rest_vs_half(&self) -> Ordering {
+        // rest = (integer + fraction) / base^m, where `integer` consists of the remaining m
+        // integer digits, and 0 <= fraction < 1. So compare 2 * integer + 2 * fraction with
+        // base^m.
+        let (mut twice, mut power) = (Big2144::ZERO, Big2144::new(1, 0));
+        for &digit in self.integer[..self.integer_length].iter().rev() {
+            twice.mul_small(self.base);
+            twice.add_small(digit as u32);
+            power.mul_small(self.base);
+        }
+        twice.mul_small(2);
+        let fraction_vs_half = if self.fraction.is_zero() {
+            Ordering::Less
+        } else {
+            self.fraction.compare(&Big2144::new(1, FRACTION_BITS - 1))
+        };
+        match twice.compare(&power) {
+            Ordering::Equal if self.fraction.is_zero() => Ordering::Equal,
+            Ordering::Equal | Ordering::Greater => Ordering::Greater,
+            Ordering::Less => {
+                // 2 * fraction < 2, so only 2 * integer + 1 == base^m may get to (or over) it.
+                twice.add_small(1);
+                match twice.compare(&power) {
+                    Ordering::Equal => fraction_vs_half,
+                    _ => Ordering::Less
+                }
+            }
+        }
+    }
+}
+
+/// Significant digits (as ASCII) of a finite value: `0.DIGITS * base^exponent`. No trailing
+/// zeros, possibly no digits at all (for zero). The base is 10, except for `UniFloat::write_radix()`.
+pub(crate) struct Digits {
+    pub(crate) digits: [u8; MAX_FORMAT_DIGITS + 2],
+    pub(crate) length: usize,
+    pub(crate) exponent: isize,
+}
+
+impl Digits {
+    pub(crate) const ZERO: Self = Digits { digits: [0; MAX_FORMAT_DIGITS + 2], length: 0, exponent: 0 };
+
+    /// `|x|` rounded to `count` significant digits (at most MAX_FORMAT_DIGITS), either to
+    /// nearest, or toward zero. `x` must be finite and non-zero. For bases over 36, digits are
+    /// "0-9A-Za-z", otherwise "0-9a-z" (as in MPFR). Without feature `mpfr`, `SoftMpfr` writes
+    /// decimals (`base` 10) only.
+    #[cfg(not(feature = "f32_only"))]
+    #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+    pub(crate) fn from_mpfr(x: mpfr::mpfr_srcptr, base: u32, count: usize, rounding: RoundingMode) -> Self {
+        let mut result = Self::ZERO;
+        let count = count.min(MAX_FORMAT_DIGITS);
+        let mut exponent: mpfr::exp_t = 0;
+        unsafe {
+            mpfr::get_str(result.digits.as_mut_ptr() as *mut _, &mut exponent, base as i32, count, x, rounding.to_mpfr());
+        }
+        // Skip the minus sign, if any.
+        if result.digits[0] == b'-' {
+            result.digits.copy_within(1.., 0);
+        }
+        result.length = count;
+        result.exponent = exponent as isize;
+        result.strip_zeros();
+        result
+    }
+
+    /// Like `from_mpfr()`, but for an f64 (without MPFR).
+    pub(crate) fn from_f64(value: f64, base: u32, count: usize, rounding: RoundingMode) -> Self {
+        Self::from_sum(value, 0.0, base, count, rounding)
+    }
+
+    /// Like `from_f64()`, but for the exact sum of the parts of a TwoFloat.
+    pub(crate) fn from_sum(high: f64, low: f64, base: u32, count: usize, rounding: RoundingMode) -> Self {
+        let mut result = Self::ZERO;
+        let count = count.min(MAX_FORMAT_DIGITS);
+        let mut digits = ExactDigits::new(high, low, base);
+        // The first digit is the most significant integer digit, or else the first non-zero one
+        // of the fraction.
+        result.exponent = digits.integer_length() as isize;
+        let mut first = digits.next();
+        if result.exponent == 0 {
+            while first == Some(0) {
+                result.exponent -= 1;
+                first = digits.next();
+            }
+        }
+        result.digits[0] = first.unwrap_or(0);
+        for digit in result.digits[1..count].iter_mut() {
+            *digit = digits.next().unwrap_or(0);
+        }
+        let round_up = rounding == RoundingMode::Nearest && match digits.rest_vs_half() {
+            Ordering::Greater => true,
+            // Ties to even significand. In an odd base, that's an even sum of the digits.
+            Ordering::Equal if base % 2 == 0 => result.digits[count - 1] % 2 == 1,
+            Ordering::Equal => result.digits[..count].iter().map(|&digit| digit as u32).sum::<u32>() % 2 == 1,
+            Ordering::Less => false,
+        };
+        if round_up {
+            let mut index = count - 1;
+            loop {
+                if result.digits[index] as u32 != base - 1 {
+                    result.digits[index] += 1;
+                    break;
+                }
+                result.digits[index] = 0;
+                if index == 0 {
+                    result.digits[0] = 1;
+                    result.exponent += 1;
+                    break;
+                }
+                index -= 1;
+            }
+        }
+        for digit in result.digits[..count].iter_mut() {
+            *digit = digit_character(*digit as u32, base);
+        }
+        result.length = count;
+        result.strip_zeros();
+        result
+    }
+
+    pub(crate) fn strip_zeros(&mut self) {
+        while self.length > 0 && self.digits[self.length - 1] == b'0' {
+            self.length -= 1;
+        }
+    }
+
+    pub(crate) fn digits(&self, range: core::ops::Range<usize>) -> &str {
+        core::str::from_utf8(&self.digits[range]).unwrap_or("")
+    }
+
+    /// "-0.DIGITSe-EXPONENT", for parsing it back. Only for base 10.
+    fn to_text<'a>(&self, negative: bool, text: &'a mut [u8; MAX_FORMAT_DIGITS + 32]) -> &'a str {
+        let mut exponent_text = [0u8; 24];
+        let mut length = 0;
+        for part in [if negative { "-0." } else { "0." }, self.digits(0..self.length), "e",
+            integer_text(&mut exponent_text, self.exponent as i64)].iter() {
+            text[length..length + part.len()].copy_from_slice(part.as_bytes());
+            length += part.len();
+        }
+        core::str::from_utf8(&text[..length]).unwrap_or("")
+    }
+}
+
+/// As in MPFR: "0-9a-z" for bases up to 36, "0-9A-Za-z" for greater bases.
+pub(crate) fn digit_character(digit: u32, base: u32) -> u8 {
+    match digit {
+        0..=9 => b'0' + digit as u8,
+        10..=35 if base <= 36 => b'a' + (digit - 10) as u8,
+        10..=35 => b'A' + (digit - 10) as u8,
+        _ => b'a' + (digit - 36) as u8,
+    }
+}
+
+/// `value` in decimal, written at the end of `text`.
+pub(crate) fn integer_text(text: &mut [u8; 24], value: i64) -> &str {
+    let mut magnitude = value.unsigned_abs();
+    let mut start = text.len();
+    loop {
+        start -= 1;
+        text[start] = b'0' + (magnitude % 10) as u8;
+        magnitude /= 10;
+        if magnitude == 0 {
+            break;
+        }
+    }
+    if value < 0 {
+        start -= 1;
+        text[start] = b'-';
+    }
+    core::str::from_utf8(&text[start..]).unwrap_or("")
+}
+
+/// The digits with the shortest count (up to `max_count`) that `round_trips()`, or the digits
+/// rounded as per `precision` and `notation`.
+fn choose_decimal(precision: Option<usize>, notation: Notation, max_count: usize,
+    decimal: impl Fn(usize, RoundingMode) -> Digits, round_trips: impl Fn(&Digits) -> bool
+) -> Digits {
+    match (precision, notation) {
+        (None, _) => {
+            for count in 1..max_count {
+                let shortest = decimal(count, RoundingMode::Nearest);
+                if round_trips(&shortest) {
+                    return shortest;
+                }
+            }
+            decimal(max_count, RoundingMode::Nearest)
+        },
+        (Some(precision), Notation::Fixed) => {
+            // Toward zero, so that the exponent doesn't change because of rounding up.
+            let leading = decimal(1, RoundingMode::TowardZero);
+            let count = leading.exponent + precision as isize;
+            if count > 0 {
+                decimal(count as usize, RoundingMode::Nearest)
+            } else if count == 0 && leading.digits[0] == b'5' && decimal(max_count, RoundingMode::TowardZero).length == 1 {
+                // Exactly 0.5 * 10^-precision (as decimals, or 0.5 itself, can be) ties to zero.
+                Digits::ZERO
+            } else if count == 0 && leading.digits[0] >= b'5' {
+                // The value is in (0.5 * 10^-precision, 10^-precision), so it rounds up to
+                // 10^-precision.
+                let mut result = Digits::ZERO;
+                result.digits[0] = b'1';
+                result.length = 1;
+                result.exponent = leading.exponent + 1;
+                result
+            } else {
+                Digits::ZERO
+            }
+        },
+        (Some(precision), _) => decimal(precision + 1, RoundingMode::Nearest),
+    }
+}
+
+/// Part of a formatted number.
+#[derive(Clone, Copy)]
+enum Piece<'a> {
+    Text(&'a str),
+    Zeros(usize),
+}
+
+impl Piece<'_> {
+    fn len(&self) -> usize {
+        match *self {
+            Piece::Text(text) => text.len(),
+            Piece::Zeros(count) => count,
+        }
+    }
+}
+
+/// A formatted number (without its sign), the same way as Rust formats `f64`.
+struct Layout<'a> {
+    pieces: [Piece<'a>; 7],
+    count: usize,
+}
+
+impl<'a> Layout<'a> {
+    fn new(decimal: &'a Digits, notation: Notation, precision: Option<usize>, exponent_text: &'a mut [u8; 24]) -> Self {
+        let mut layout = Layout { pieces: [Piece::Text(""); 7], count: 0 };
+        let (length, exponent) = (decimal.length, decimal.exponent);
+        match notation {
+            Notation::Fixed => {
+                // The integer part.
+                if length == 0 || exponent <= 0 {
+                    layout.push(Piece::Text("0"));
+                } else if exponent as usize >= length {
+                    layout.push(Piece::Text(decimal.digits(0..length)));
+                    layout.push(Piece::Zeros(exponent as usize - length));
+                } else {
+                    layout.push(Piece::Text(decimal.digits(0..exponent as usize)));
+                }
+                // The fraction.
+                let (leading_zeros, fraction) = if length == 0 {
+                    (0, "")
+                } else if exponent <= 0 {
+                    (-exponent as usize, decimal.digits(0..length))
+                } else if (exponent as usize) < length {
+                    (0, decimal.digits(exponent as usize..length))
+                } else {
+                    (0, "")
+                };
+                let fraction_length = leading_zeros + fraction.len();
+                if precision.map_or(fraction_length > 0, |precision| precision > 0) {
+                    layout.push(Piece::Text("."));
+                    layout.push(Piece::Zeros(leading_zeros));
+                    layout.push(Piece::Text(fraction));
+                    layout.push(Piece::Zeros(precision.unwrap_or(0).saturating_sub(fraction_length)));
+                }
+            },
+            Notation::LowerExp | Notation::UpperExp => {
+                // Zero is "0e0".
+                layout.push(Piece::Text(if length == 0 { "0" } else { decimal.digits(0..1) }));
+                if length > 1 || precision.map_or(false, |precision| precision > 0) {
+                    layout.push(Piece::Text("."));
+                    layout.push(Piece::Text(decimal.digits(1..length.max(1))));
+                    layout.push(Piece::Zeros(precision.unwrap_or(0).saturating_sub(length.max(1) - 1)));
+                }
+                layout.push(Piece::Text(if notation == Notation::LowerExp { "e" } else { "E" }));
+                let exponent = if length == 0 { 0 } else { exponent - 1 };
+                layout.push(Piece::Text(integer_text(exponent_text, exponent as i64)));
+            },
+        }
+        layout
+    }
+
+    fn push(&mut self, piece: Piece<'a>) {
+        self.pieces[self.count] = piece;
+        self.count += 1;
+    }
+
+    fn pieces(&self) -> &[Piece<'a>] {
+        &self.pieces[..self.count]
+    }
+}
+
+fn write_repeated(f: &mut fmt::Formatter<'_>, c: char, count: usize) -> fmt::Result {
+    for _ in 0..count {
+        f.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// Like `Formatter::pad_integral()`: it honours the sign, fill, alignment, width and zero padding
+/// flags. But the number is given in pieces, so that long runs of zeros don't need a buffer.
+fn pad_pieces(f: &mut fmt::Formatter<'_>, negative: bool, pieces: &[Piece<'_>]) -> fmt::Result {
+    let sign = if negative { "-" } else if f.sign_plus() { "+" } else { "" };
+    let length = sign.len() + pieces.iter().map(Piece::len).sum::<usize>();
+    let padding = f.width().map_or(0, |width| width.saturating_sub(length));
+    let write_pieces = |f: &mut fmt::Formatter<'_>| -> fmt::Result {
+        for piece in pieces {
+            match *piece {
+                Piece::Text(text) => f.write_str(text)?,
+                Piece::Zeros(count) => write_repeated(f, '0', count)?,
+            }
+        }
+        Ok(())
+    };
+    if f.sign_aware_zero_pad() {
+        f.write_str(sign)?;
+        write_repeated(f, '0', padding)?;
+        write_pieces(f)
+    } else {
+        let (before, after) = match f.align() {
+            Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Center) => (padding / 2, (padding + 1) / 2),
+            Some(fmt::Alignment::Right) | None => (padding, 0),
+        };
+        let fill = f.fill();
+        write_repeated(f, fill, before)?;
+        f.write_str(sign)?;
+        write_pieces(f)?;
+        write_repeated(f, fill, after)
+    }
+}
+
+/// Formatting shared by `Display`, `LowerExp`, `UpperExp` and `write_to()`.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Whether `decimal` (with the given sign) parses back to `self`.
+    #[cfg(not(feature = "f32_only"))]
+    fn round_trips(&self, negative: bool, decimal: &Digits) -> bool {
+        let mut text = [0u8; MAX_FORMAT_DIGITS + 32];
+        match decimal.to_text(negative, &mut text).parse::<Self>() {
+            Ok(mut parsed) => {
+                parsed.copied();
+                parsed == *self
+            },
+            Err(_) => false
+        }
+    }
+
+    /// The sign and the decimal digits, or None for NaN and infinities.
+    fn to_decimal(&self, notation: Notation, precision: Option<usize>) -> Option<(bool, Digits)> {
+        self.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => {
+                let value = self.f32s[0];
+                if !value.is_finite() {
+                    return None;
+                } else if value == 0.0 {
+                    return Some((value.is_sign_negative(), Digits::ZERO));
+                }
+                Some((value < 0.0, choose_decimal(precision, notation, 9,
+                    |count, rounding| Digits::from_f64(value as f64, 10, count, rounding),
+                    |decimal| {
+                        let mut text = [0u8; MAX_FORMAT_DIGITS + 32];
+                        decimal.to_text(false, &mut text).parse::<f32>() == Ok(libm::fabsf(value))
+                    })))
+            },
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => {
+                let value = self.f64s[0];
+                if !value.is_finite() {
+                    return None;
+                } else if value == 0.0 {
+                    return Some((value.is_sign_negative(), Digits::ZERO));
+                }
+                Some((value < 0.0, choose_decimal(precision, notation, 17,
+                    |count, rounding| Digits::from_f64(value, 10, count, rounding),
+                    |decimal| {
+                        let mut text = [0u8; MAX_FORMAT_DIGITS + 32];
+                        decimal.to_text(false, &mut text).parse::<f64>() == Ok(libm::fabs(value))
+                    })))
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => {
+                let (high, low) = (self.twofloats[0].hi(), self.twofloats[0].lo());
+                if !high.is_finite() {
+                    return None;
+                } else if high == 0.0 {
+                    return Some((high.is_sign_negative(), Digits::ZERO));
+                }
+                Some((high < 0.0, choose_decimal(precision, notation, MAX_FORMAT_DIGITS,
+                    |count, rounding| Digits::from_sum(high, low, 10, count, rounding),
+                    |decimal| self.round_trips(high < 0.0, decimal))))
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                // The highest part.
+                let high = self.twofloats[0].hi();
+                if !high.is_finite() {
+                    return None;
+                } else if high == 0.0 {
+                    return Some((high.is_sign_negative(), Digits::ZERO));
+                }
+                let mut exact = self.convert::<{ MPFR_TWOFLOAT_EXACT }>();
+                exact.copied();
+                Some((high < 0.0, choose_decimal(precision, notation, MAX_FORMAT_DIGITS,
+                    |count, rounding| Digits::from_mpfr(exact.mpfr_src().as_ptr(), 10, count, rounding),
+                    |decimal| self.round_trips(high < 0.0, decimal))))
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let source = self.mpfr_src();
+                let x = source.as_ptr();
+                let negative = unsafe { mpfr::signbit(x) } != 0;
+                if unsafe { mpfr::nan_p(x) != 0 || mpfr::inf_p(x) != 0 } {
+                    return None;
+                } else if unsafe { mpfr::zero_p(x) } != 0 {
+                    return Some((negative, Digits::ZERO));
+                }
+                Some((negative, choose_decimal(precision, notation, MAX_FORMAT_DIGITS,
+                    |count, rounding| Digits::from_mpfr(x, 10, count, rounding),
+                    |decimal| self.round_trips(negative, decimal))))
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => {
+                let value = self.decimal();
+                let negative = value.is_sign_negative();
+                if value.is_nan() || value.is_infinite() {
+                    return None;
+                } else if value.is_zero() {
+                    return Some((negative, Digits::ZERO));
+                }
+                // All the digits are exact.
+                Some((negative, choose_decimal(precision, notation, digits,
+                    |count, rounding| value.to_digits(count, rounding),
+                    |decimal| self.round_trips(negative, decimal))))
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    fn format(&self, f: &mut fmt::Formatter<'_>, notation: Notation) -> fmt::Result {
+        self.assert_copy_fixed();
+        match C {
+            // Rust's own formatting is the reference for the others.
+            UniFloatChoice::F32 => match notation {
+                Notation::Fixed => fmt::Display::fmt(&self.f32s[0], f),
+                Notation::LowerExp => fmt::LowerExp::fmt(&self.f32s[0], f),
+                Notation::UpperExp => fmt::UpperExp::fmt(&self.f32s[0], f),
+            },
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => match notation {
+                Notation::Fixed => fmt::Display::fmt(&self.f64s[0], f),
+                Notation::LowerExp => fmt::LowerExp::fmt(&self.f64s[0], f),
+                Notation::UpperExp => fmt::UpperExp::fmt(&self.f64s[0], f),
+            },
+            _ => match self.to_decimal(notation, f.precision()) {
+                Some((negative, decimal)) => {
+                    let mut exponent_text = [0u8; 24];
+                    let layout = Layout::new(&decimal, notation, f.precision(), &mut exponent_text);
+                    pad_pieces(f, negative, layout.pieces())
+                },
+                // NaN and infinities, with all the flags as for f64.
+                None => match notation {
+                    Notation::Fixed => fmt::Display::fmt(&self.to_f64_nearest(), f),
+                    Notation::LowerExp => fmt::LowerExp::fmt(&self.to_f64_nearest(), f),
+                    Notation::UpperExp => fmt::UpperExp::fmt(&self.to_f64_nearest(), f),
+                }
+            }
+        }
+    }
+
+    /// Format into `buffer`, without `core::fmt` and without any allocation. The text is the
+    /// same as from `Display` (or `LowerExp` or `UpperExp`, as per `options.notation`), for
+    /// example "NaN", "-inf", "0", "1.5" or "1.5e-7". Returns the number of bytes written, or the
+    /// number of bytes that `buffer` would need.
+    pub fn write_to(&self, buffer: &mut [u8], options: FormatOptions) -> Result<usize, BufferTooSmall> {
+        let sign = |negative| if negative { "-" } else if options.sign_plus { "+" } else { "" };
+        match self.to_decimal(options.notation, options.precision) {
+            Some((negative, decimal)) => {
+                let mut exponent_text = [0u8; 24];
+                let layout = Layout::new(&decimal, options.notation, options.precision, &mut exponent_text);
+                copy_pieces(buffer, sign(negative), layout.pieces())
+            },
+            None => {
+                let value = self.to_f64_nearest();
+                if value.is_nan() {
+                    copy_pieces(buffer, "", &[Piece::Text("NaN")])
+                } else {
+                    copy_pieces(buffer, sign(value < 0.0), &[Piece::Text("inf")])
+                }
+            }
+        }
+    }
+}
+
+/// Write `sign` and `pieces` at the start of `buffer`.
+fn copy_pieces(buffer: &mut [u8], sign: &str, pieces: &[Piece<'_>]) -> Result<usize, BufferTooSmall> {
+    let required = sign.len() + pieces.iter().map(Piece::len).sum::<usize>();
+    if buffer.len() < required {
+        return Err(BufferTooSmall { required });
+    }
+    buffer[..sign.len()].copy_from_slice(sign.as_bytes());
+    let mut length = sign.len();
+    for piece in pieces {
+        let end = length + piece.len();
+        match *piece {
+            Piece::Text(text) => buffer[length..end].copy_from_slice(text.as_bytes()),
+            Piece::Zeros(_) => buffer[length..end].fill(b'0'),
+        }
+        length = end;
+    }
+    Ok(length)
+}
+
+/// Same as for `f64`: no exponent, and the shortest digits that parse back to the same value
+/// (for this choice). A precision flag sets the number of digits after the decimal point
+/// (rounded to nearest, ties to even). The sign, fill, alignment, width and zero padding flags
+/// are honoured, too.
+impl <const C: UniFloatChoice> fmt::Display for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.format(f, Notation::Fixed)
+    }
+}
+
+/// Same as for `f64`, like `1.5e-7`. A precision flag sets the number of digits after the
+/// decimal point. Otherwise see `Display`.
+impl <const C: UniFloatChoice> fmt::LowerExp for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.format(f, Notation::LowerExp)
+    }
+}
+
+/// Like `LowerExp`, but with `E`.
+impl <const C: UniFloatChoice> fmt::UpperExp for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.format(f, Notation::UpperExp)
+    }
+}
+
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Whether `self` has been used through `OperandMutated`, and not cleared (by .copied() or <<=)
+    /// since. Then other methods can't read it.
+    #[cfg(debug_assertions)]
+    fn mutated_state(&self) -> Option<bool> {
+        Some(self.used_as_operand_mutated)
+    }
+
+    /// Release builds don't track that.
+    #[cfg(not(debug_assertions))]
+    fn mutated_state(&self) -> Option<bool> {
+        None
+    }
+}
+
+/// Like "UniFloat { value: 1.5, choice: F64, precision_bits: 53, mutated: Some(false) }". The
+/// value is formatted as by `Display` (and with its flags). It works even if `self` has been used
+/// through `OperandMutated` (and not cleared since): it reads the value from a copy. `mutated` is
+/// None if that isn't tracked (see `mutated_state()`).
+impl <const C: UniFloatChoice> fmt::Debug for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut copy = *self;
+        copy.copied();
+        f.debug_struct("UniFloat")
+            .field("value", &DebugAsDisplay(&copy))
+            .field("choice", &C)
+            .field("precision_bits", &C.precision_bits())
+            .field("mutated", &self.mutated_state())
+            .finish()
+    }
+}
+
+/// Formats a field of `Debug` output with `Display` (and with the same flags).
+struct DebugAsDisplay<'a, T: fmt::Display>(&'a T);
+
+impl <T: fmt::Display> fmt::Debug for DebugAsDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.0, f)
+    }
+}
@@ -0,0 +1,401 @@
+use core::fmt;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::mpfr;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{ParseErrorKind, ParseUniFloatError, UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::{convert::UniMpfrTwoFloatExact, parse::parse_mpfr, parts::limit_exponent_range};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "twofloat")]
+use crate::{MAX_PARSE_LENGTH, TwoFloat, format::scaled_sum, radix::{parse_radix_literal, saturated_exponent}};
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Write the sign, "0x1", the fraction digits (if any) and the exponent. `fraction` holds the
+/// bits after the leading 1, aligned to the top.
+fn write_hex_parts(writer: &mut impl fmt::Write, negative: bool, mut fraction: u64, exponent: i64) -> fmt::Result {
+    writer.write_str(if negative { "-0x1" } else { "0x1" })?;
+    if fraction != 0 {
+        writer.write_char('.')?;
+        while fraction != 0 {
+            writer.write_char(HEX_DIGITS[(fraction >> 60) as usize] as char)?;
+            fraction <<= 4;
+        }
+    }
+    write!(writer, "p{:+}", exponent)
+}
+
+/// `x` must be finite and non-zero. Its precision can be anything. It gets overwritten.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+fn write_hex_mpfr(writer: &mut impl fmt::Write, x: mpfr::mpfr_ptr) -> fmt::Result {
+    unsafe {
+        let negative = mpfr::signbit(x) != 0;
+        // MPFR's exponent is for 0.1xxx (in binary), rather than for 1.xxx.
+        let exponent = mpfr::get_exp(x) as i64 - 1;
+        // All operations here are exact.
+        mpfr::abs(x, x, mpfr::rnd_t::RNDN);
+        mpfr::set_exp(x, 1);
+        mpfr::sub_ui(x, x, 1, mpfr::rnd_t::RNDN);
+        writer.write_str(if negative { "-0x1" } else { "0x1" })?;
+        if mpfr::zero_p(x) == 0 {
+            writer.write_char('.')?;
+            while mpfr::zero_p(x) == 0 {
+                mpfr::mul_2ui(x, x, 4, mpfr::rnd_t::RNDN);
+                let digit = mpfr::get_ui(x, mpfr::rnd_t::RNDZ);
+                mpfr::sub_ui(x, x, digit, mpfr::rnd_t::RNDN);
+                writer.write_char(HEX_DIGITS[digit as usize] as char)?;
+            }
+        }
+        write!(writer, "p{:+}", exponent)
+    }
+}
+
+fn write_hex_f64(writer: &mut impl fmt::Write, value: f64) -> fmt::Result {
+    if value.is_nan() {
+        return writer.write_str("nan");
+    }
+    let negative = value.is_sign_negative();
+    if value.is_infinite() {
+        return writer.write_str(if negative { "-inf" } else { "inf" });
+    } else if value == 0.0 {
+        return writer.write_str(if negative { "-0x0p+0" } else { "0x0p+0" });
+    }
+    let bits = value.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i64;
+    let mantissa = bits & ((1 << 52) - 1);
+    // value = significand * 2^exponent, with integer significand.
+    let (significand, exponent) = if biased_exponent == 0 {
+        (mantissa, -1074)
+    } else {
+        (mantissa | 1 << 52, biased_exponent - 1075)
+    };
+    let leading_zeros = significand.leading_zeros();
+    write_hex_parts(writer, negative, significand << leading_zeros << 1, exponent + 63 - leading_zeros as i64)
+}
+
+/// `high + low` (the parts of a TwoFloat), exactly. `high` must be finite and non-zero.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "twofloat")]
+fn write_hex_sum(writer: &mut impl fmt::Write, high: f64, low: f64) -> fmt::Result {
+    // Shifted by three more bits, so that the last digit is whole.
+    let mut fraction = scaled_sum(high, low).shifted_left(3);
+    let leading = fraction.bit_length() - 1;
+    // Leave the bits after the leading 1.
+    fraction.split_at(leading);
+    writer.write_str(if high < 0.0 { "-0x1" } else { "0x1" })?;
+    if !fraction.is_zero() {
+        writer.write_char('.')?;
+        let mut end = leading;
+        while !fraction.is_zero() {
+            end -= 4;
+            writer.write_char(HEX_DIGITS[fraction.split_at(end) as usize] as char)?;
+        }
+    }
+    write!(writer, "p{:+}", leading as i64 - 3 - 1074)
+}
+
+/// A hex float literal (or a literal in another base, converted to binary), with at most 64
+/// significant bits kept. value = significand * 2^exponent, plus something non-zero below the
+/// lowest bit of `significand` if `sticky`.
+pub(crate) struct HexLiteral {
+    pub(crate) negative: bool,
+    pub(crate) significand: u64,
+    pub(crate) exponent: i64,
+    pub(crate) sticky: bool,
+}
+
+/// Saturate the exponents far beyond any choice, so that they don't overflow.
+pub(crate) const EXPONENT_LIMIT: i64 = 1 << 40;
+
+impl HexLiteral {
+    /// Round to `precision` bits, with the least exponent of normal values being `min_exponent`
+    /// (as per `f64::MIN_EXP`). Returns (significand, exponent), where the value is
+    /// significand * 2^exponent.
+    fn round(&self, precision: u32, min_exponent: i64) -> (u64, i64) {
+        if self.significand == 0 {
+            return (0, 0);
+        }
+        let leading_zeros = self.significand.leading_zeros();
+        let significand = self.significand << leading_zeros;
+        let exponent = self.exponent - leading_zeros as i64;
+        // Exponent of the leading bit. Subnormals (below min_exponent - 1) keep fewer bits.
+        let leading = exponent + 63;
+        let kept_bits = precision as i64 - (min_exponent - 1 - leading).max(0);
+        if kept_bits < 0 {
+            return (0, 0);
+        }
+        let shift = 64 - kept_bits as u32;
+        let (kept, remainder) = if shift == 64 { (0, significand) } else { (significand >> shift, significand << (64 - shift)) };
+        // `remainder` holds the dropped bits, aligned to the top.
+        let half = 1 << 63;
+        let round_up = remainder > half || (remainder == half && (self.sticky || kept & 1 == 1));
+        (kept + round_up as u64, exponent + shift as i64)
+    }
+
+    #[cfg_attr(feature = "f32_only", allow(dead_code))]
+    pub(crate) fn to_f64(&self) -> f64 {
+        let (significand, exponent) = self.round(f64::MANTISSA_DIGITS, f64::MIN_EXP as i64);
+        let magnitude = libm::scalbn(significand as f64, exponent.max(-2000).min(2000) as i32);
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    pub(crate) fn to_f32(&self) -> f32 {
+        let (significand, exponent) = self.round(f32::MANTISSA_DIGITS, f32::MIN_EXP as i64);
+        let magnitude = libm::scalbnf(significand as f32, exponent.max(-400).min(400) as i32);
+        if self.negative { -magnitude } else { magnitude }
+    }
+}
+
+/// Hex float text: "inf", "-inf", "nan", or an optional sign, "0x" (or "0X"), hex digits with an
+/// optional '.', and an optional binary exponent ('p' or 'P', an optional sign and decimal
+/// digits). For example "0x1.8p+0" is 1.5. Same as C's "%a" and `strtod()`. Numbers are
+/// returned as `Ok(Some(..))`, and "inf", "infinity" and "nan" (case-insensitive) as `Ok(None)`.
+fn parse_hex_literal(s: &str) -> Result<Option<HexLiteral>, ParseUniFloatError> {
+    let error = |kind, position| Err(ParseUniFloatError::new(kind, position));
+    let bytes = s.as_bytes();
+    if bytes.is_empty() {
+        return error(ParseErrorKind::Empty, 0);
+    }
+    let negative = bytes[0] == b'-';
+    let mut position = if bytes[0] == b'+' || bytes[0] == b'-' { 1 } else { 0 };
+    let special = &s[position..];
+    if special.eq_ignore_ascii_case("inf") || special.eq_ignore_ascii_case("infinity")
+        || special.eq_ignore_ascii_case("nan") {
+        return Ok(None);
+    }
+    if position == bytes.len() {
+        return error(ParseErrorKind::MissingDigits, position);
+    } else if bytes[position] != b'0' {
+        return error(ParseErrorKind::InvalidCharacter, position);
+    } else if !matches!(bytes.get(position + 1), Some(b'x') | Some(b'X')) {
+        return error(ParseErrorKind::InvalidCharacter, position + 1);
+    }
+    position += 2;
+
+    let mut literal = HexLiteral { negative, significand: 0, exponent: 0, sticky: false };
+    let (mut digits, mut dot) = (0, false);
+    while position < bytes.len() {
+        let digit = match bytes[position] {
+            b'.' if !dot => {
+                dot = true;
+                position += 1;
+                continue;
+            },
+            digit => match (digit as char).to_digit(16) {
+                Some(digit) => digit as u64,
+                None => break
+            }
+        };
+        digits += 1;
+        if literal.significand >> 60 == 0 {
+            literal.significand = literal.significand << 4 | digit;
+            if dot {
+                literal.exponent -= 4;
+            }
+        } else {
+            literal.sticky |= digit != 0;
+            if !dot {
+                literal.exponent += 4;
+            }
+        }
+        position += 1;
+    }
+    if digits == 0 {
+        return error(ParseErrorKind::MissingDigits, position);
+    }
+
+    if position < bytes.len() && matches!(bytes[position], b'p' | b'P') {
+        position += 1;
+        let exponent_negative = bytes.get(position) == Some(&b'-');
+        if matches!(bytes.get(position), Some(b'+') | Some(b'-')) {
+            position += 1;
+        }
+        let exponent_start = position;
+        let mut exponent: i64 = 0;
+        while position < bytes.len() && bytes[position].is_ascii_digit() {
+            exponent = (exponent * 10 + (bytes[position] - b'0') as i64).min(EXPONENT_LIMIT);
+            position += 1;
+        }
+        if position == exponent_start {
+            return if position == bytes.len() {
+                error(ParseErrorKind::MissingDigits, position)
+            } else {
+                error(ParseErrorKind::InvalidCharacter, position)
+            };
+        }
+        literal.exponent += if exponent_negative { -exponent } else { exponent };
+    }
+    if position < bytes.len() {
+        return error(ParseErrorKind::InvalidCharacter, position);
+    }
+    Ok(Some(literal))
+}
+
+/// The nearest double-double to hex float text `s`, which `parse_hex_literal()` accepted as
+/// (finite) `literal`. Unless that's beyond the range of f64, it parses all the digits again, so
+/// then `s` can have at most `MAX_PARSE_LENGTH` bytes.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "twofloat")]
+fn twofloat_from_hex(s: &str, literal: &HexLiteral) -> Result<TwoFloat, ParseUniFloatError> {
+    let high = literal.to_f64();
+    if !high.is_finite() || high == 0.0 {
+        return Ok(TwoFloat::from(high));
+    } else if s.len() > MAX_PARSE_LENGTH {
+        return Err(ParseUniFloatError::new(ParseErrorKind::TooLong, MAX_PARSE_LENGTH));
+    }
+    // The digits between "0x" and 'p', in base 16.
+    let start = s.find(|c| c == 'x' || c == 'X').map_or(0, |index| index + 1);
+    let end = s.find(|c| c == 'p' || c == 'P').unwrap_or(s.len());
+    match parse_radix_literal(&s[start..end], 16)? {
+        Some(mut digits) => {
+            digits.negative = literal.negative;
+            digits.binary_exponent = saturated_exponent(s.get(end + 1..).unwrap_or(""));
+            Ok(digits.to_twofloat(16))
+        },
+        None => unreachable!("Hex digits are never \"@inf@\" or \"@nan@\".")
+    }
+}
+
+/// Hexadecimal floats, as C's "%a" format and `strtod()`. They are exact, so they're good for
+/// test fixtures and golden files.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Like "0x1.8p+0" for 1.5, "-0x1p-4" for -0.0625, "0x0p+0" for zero, and "inf", "-inf" or
+    /// "nan". The digits are exact, with no trailing zeros. For TwoFloat and QuadFloat they
+    /// represent the exact sum of the parts. Decimals (like 0.1) may have no finite binary
+    /// expansion: they're rounded to nearest at 2098 bits first.
+    pub fn write_hex(&self, writer: &mut impl fmt::Write) -> fmt::Result {
+        self.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => write_hex_f64(writer, self.f32s[0] as f64),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => write_hex_f64(writer, self.f64s[0]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => {
+                let (high, low) = (self.twofloats[0].hi(), self.twofloats[0].lo());
+                if !high.is_finite() || high == 0.0 {
+                    return write_hex_f64(writer, high);
+                }
+                write_hex_sum(writer, high, low)
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                let quad = self.quad();
+                if !quad.0[0].is_finite() || quad.0[0] == 0.0 {
+                    return write_hex_f64(writer, quad.0[0]);
+                }
+                let mut exact = UniMpfrTwoFloatExact::NAN;
+                exact.copied();
+                let exact = exact.mpfr_mut();
+                unsafe { quad.to_mpfr(exact) };
+                write_hex_mpfr(writer, exact)
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => {
+                let mut exact = UniMpfrTwoFloatExact::NAN;
+                exact.copied();
+                let exact = exact.mpfr_mut();
+                unsafe {
+                    self.decimal().to_mpfr(exact, mpfr::rnd_t::RNDN);
+                    if mpfr::regular_p(exact) == 0 {
+                        return write_hex_f64(writer, mpfr::get_d(exact, mpfr::rnd_t::RNDN));
+                    }
+                }
+                write_hex_mpfr(writer, exact)
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let source = self.mpfr_src();
+                let x = source.as_ptr();
+                if unsafe { mpfr::regular_p(x) } == 0 {
+                    return write_hex_f64(writer, unsafe { mpfr::get_d(x, mpfr::rnd_t::RNDN) });
+                }
+                let mut copy = *self;
+                copy.copied();
+                write_hex_mpfr(writer, copy.mpfr_mut())
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    /// See `write_hex()`.
+    #[cfg(feature = "alloc")]
+    pub fn to_hex_string(&self) -> String {
+        let mut result = String::new();
+        // Writing to a String doesn't fail.
+        let _ = self.write_hex(&mut result);
+        result
+    }
+
+    /// Parse the format of `write_hex()` (and C's "%a"). The result is correctly rounded (to
+    /// nearest, ties to even) for f32, f64 and MPFR. TwoFloat gets the nearest double-double (see
+    /// `FromStr`). QuadFloat is rounded to 2098 bits first (only for more than 524 hex digits),
+    /// and then to 212 bits, split into quad-double parts. Decimals are rounded once,
+    /// to nearest (but likewise for more than 524 hex digits). As with `FromStr`, MPFR results are
+    /// then limited to the bounds.
+    pub fn from_hex_str(s: &str) -> Result<Self, ParseUniFloatError> {
+        let literal = match parse_hex_literal(s)? {
+            Some(literal) => literal,
+            None => return Ok(s.parse()?)
+        };
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = literal.to_f32(),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = literal.to_f64(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = twofloat_from_hex(s, &literal)?,
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat | UniFloatChoice::Decimal { .. } => {
+                let mut exact = UniMpfrTwoFloatExact::NAN;
+                exact.copied();
+                parse_mpfr(exact.mpfr_mut(), s, 16)?;
+                result = Self::from_mpfr(exact.mpfr_src().as_ptr());
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let ternary = parse_mpfr(result.mpfr_mut(), s, 16)?;
+                limit_exponent_range(C.mpfr_bounds(), result.mpfr_mut(), ternary);
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        Ok(result.released())
+    }
+}
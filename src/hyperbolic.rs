@@ -0,0 +1,155 @@
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use crate::mpfr;
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(feature = "twofloat")]
+use crate::exponential::LN_2;
+#[cfg(feature = "twofloat")]
+use crate::parts::twofloat_or_f64;
+
+/// Below this magnitude, the odd functions here are `x` to double-double precision: their next
+/// term is x^3/6 or x^3/3, which is less than 1e-34 relative.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+const TWOFLOAT_LINEAR: f64 = 1e-17;
+/// Above this magnitude, `asinh(x)` and `acosh(x)` are ln(2 * x) to double-double precision.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+const TWOFLOAT_LOGARITHMIC: f64 = 1e17;
+
+/// Hyperbolic functions and their inverses. Accuracy:
+/// - MPFR: correctly rounded.
+/// - f32: computed with f64, so nearly always correctly rounded.
+/// - f64: within 1 ulp or so (as `libm`).
+/// - TwoFloat: formulations with `exp_m1()` and `ln_1p()` (rather than with `exp()` and `ln()`),
+///   so that they don't cancel near zero (or near 1 for `acosh()` and `atanh()`). That keeps
+///   relative error below 1e-26.
+///
+/// NaN, infinities, zeros, overflows and underflows are as in IEEE 754.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Hyperbolic sine.
+    pub fn sinh(&self) -> Self {
+        self.unary(|x| libm::sinh(x as f64) as f32, libm::sinh,
+            twofloat_op!(|x| twofloat_odd(x, libm::sinh(x.hi()), |x| if x.hi() > 709.0 {
+                // e^x would overflow, but e^x / 2 may not. e^-x is negligible.
+                (x - ln_2()).exp()
+            } else {
+                let e = x.exp_m1();
+                (e + e / (e + 1.0)) * 0.5
+            })),
+            mpfr_op!(|r, x| unsafe { mpfr::sinh(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Hyperbolic cosine.
+    pub fn cosh(&self) -> Self {
+        self.unary(|x| libm::cosh(x as f64) as f32, libm::cosh,
+            twofloat_op!(|x| twofloat_or_f64(x, libm::cosh(x.hi()), |x| {
+                let x = x.abs();
+                if x.hi() > 709.0 {
+                    (x - ln_2()).exp()
+                } else {
+                    let e = x.exp();
+                    (e + TwoFloat::from(1.0) / e) * 0.5
+                }
+            })),
+            mpfr_op!(|r, x| unsafe { mpfr::cosh(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Hyperbolic tangent.
+    pub fn tanh(&self) -> Self {
+        self.unary(|x| libm::tanh(x as f64) as f32, libm::tanh,
+            twofloat_op!(|x| twofloat_odd(x, libm::tanh(x.hi()), |x| if x.hi() > 40.0 {
+                // 1 - tanh(x) is about 2 * e^(-2 * x), which is less than 2^-110 here.
+                TwoFloat::from(1.0)
+            } else {
+                let e = (x * 2.0).exp_m1();
+                e / (e + 2.0)
+            })),
+            mpfr_op!(|r, x| unsafe { mpfr::tanh(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Inverse hyperbolic sine.
+    pub fn asinh(&self) -> Self {
+        self.unary(|x| libm::asinh(x as f64) as f32, libm::asinh,
+            twofloat_op!(|x| twofloat_odd(x, libm::asinh(x.hi()), |x| if x.hi() > TWOFLOAT_LOGARITHMIC {
+                x.ln() + ln_2()
+            } else {
+                let square = x * x;
+                (x + square / ((square + 1.0).sqrt() + 1.0)).ln_1p()
+            })),
+            mpfr_op!(|r, x| unsafe { mpfr::asinh(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Inverse hyperbolic cosine. NaN for `self < 1`.
+    pub fn acosh(&self) -> Self {
+        self.unary(|x| libm::acosh(x as f64) as f32, libm::acosh, twofloat_op!(|x| acosh_twofloat(x)),
+            mpfr_op!(|r, x| unsafe { mpfr::acosh(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Inverse hyperbolic tangent. NaN for |self| > 1, and +-Inf for +-1.
+    pub fn atanh(&self) -> Self {
+        self.unary(|x| libm::atanh(x as f64) as f32, libm::atanh, twofloat_op!(|x| atanh_twofloat(x)),
+            mpfr_op!(|r, x| unsafe { mpfr::atanh(r, x, mpfr::rnd_t::RNDN) }))
+    }
+}
+
+#[cfg(feature = "twofloat")]
+fn ln_2() -> TwoFloat {
+    TwoFloat::new_add(LN_2.0, LN_2.1)
+}
+
+/// For odd functions that are `x` near zero: `x` itself (for zeros and NaN, too) below
+/// `TWOFLOAT_LINEAR`. Otherwise `twofloat_op(|x|)` with the sign of `x`, or `approximation` (the
+/// result in f64) where `twofloat_or_f64()` takes that.
+#[cfg(feature = "twofloat")]
+fn twofloat_odd(x: TwoFloat, approximation: f64, twofloat_op: impl FnOnce(TwoFloat) -> TwoFloat) -> TwoFloat {
+    if !(libm::fabs(x.hi()) >= TWOFLOAT_LINEAR) {
+        return x;
+    }
+    twofloat_or_f64(x, approximation, |x| {
+        let result = twofloat_op(x.abs());
+        if x.hi() < 0.0 { -result } else { result }
+    })
+}
+
+/// acosh(x) = ln(x + sqrt(x^2 - 1)) = ln_1p(t + sqrt(t * (t + 2))) for t = x - 1, which is exact.
+/// The f64 result isn't a good guard here, since it's 0 or NaN for any `x` whose `hi()` is 1.
+#[cfg(feature = "twofloat")]
+fn acosh_twofloat(x: TwoFloat) -> TwoFloat {
+    let one = TwoFloat::from(1.0);
+    if x > one && x.hi().is_finite() {
+        if x.hi() > TWOFLOAT_LOGARITHMIC {
+            x.ln() + ln_2()
+        } else {
+            let t = x - one;
+            (t + (t * (t + 2.0)).sqrt()).ln_1p()
+        }
+    } else if x < one {
+        TwoFloat::from(f64::NAN)
+    } else {
+        TwoFloat::from(libm::acosh(x.hi()))
+    }
+}
+
+/// atanh(x) = ln_1p(2 * x / (1 - x)) / 2 for positive `x`. As for `acosh_twofloat()`, the f64
+/// result isn't a good guard, since it's infinite for any `x` whose `hi()` is +-1.
+#[cfg(feature = "twofloat")]
+fn atanh_twofloat(x: TwoFloat) -> TwoFloat {
+    let (one, magnitude) = (TwoFloat::from(1.0), x.abs());
+    if !(libm::fabs(x.hi()) >= TWOFLOAT_LINEAR) {
+        x
+    } else if magnitude < one {
+        let result = (magnitude * 2.0 / (one - magnitude)).ln_1p() * 0.5;
+        if x.hi() < 0.0 { -result } else { result }
+    } else if magnitude > one {
+        TwoFloat::from(f64::NAN)
+    } else {
+        TwoFloat::from(libm::atanh(x.hi()))
+    }
+}
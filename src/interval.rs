@@ -0,0 +1,268 @@
+use {core::ops, crate::mpfr};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+
+use crate::{RoundingMode, UniFloatChoice, UniFloat, UnsupportedRounding, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+#[cfg(feature = "twofloat")]
+use crate::Supported;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::ternary::{UniMpfrTwoFloatProductExact, mpfr_set_twofloat};
+
+/// A closed interval [`lower`, `upper`] of `UniFloat<C>` values: a rigorous enclosure of a real
+/// number (or of a set of them). Arithmetic rounds outward: the lower bound down and the upper one
+/// up, so the result encloses every exact result for operands from the intervals.
+/// - TwoFloat has no directed rounding, so its bounds are computed in MPFR (exactly, apart from
+///   division), and then rounded outward to double-double.
+///
+/// An interval with a NaN bound is invalid: its results are invalid, too, and it contains nothing.
+#[derive(Clone, Copy)]
+pub struct UniInterval<const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    lower: UniFloat<C>,
+    upper: UniFloat<C>,
+}
+
+impl <const C: UniFloatChoice> UniInterval<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// [`lower`, `upper`]. Panics if `lower` > `upper`.
+    pub fn new(lower: &UniFloat<C>, upper: &UniFloat<C>) -> Self {
+        assert!(!lower.gt(upper), "UniInterval needs lower <= upper.");
+        Self { lower: *lower, upper: *upper }.released()
+    }
+
+    /// [`value`, `value`].
+    pub fn point(value: &UniFloat<C>) -> Self {
+        Self::new(value, value)
+    }
+
+    /// [`value` - ulp, `value` + ulp] (see `UniFloat::ulp()`), rounded outward. That encloses the
+    /// exact value of a result with an error of up to one ulp.
+    pub fn with_ulp_error(value: &UniFloat<C>) -> Self {
+        let mut ulp = value.ulp();
+        ulp.copied();
+        Self::from_bounds(
+            directed(value, &ulp, RoundingMode::Down, UniFloat::<C>::sub_round, mpfr_op!(|r, x, y, rnd| unsafe { mpfr::sub(r, x, y, rnd) })),
+            directed(value, &ulp, RoundingMode::Up, UniFloat::<C>::add_round, mpfr_op!(|r, x, y, rnd| unsafe { mpfr::add(r, x, y, rnd) })))
+    }
+
+    /// From bounds returned by value.
+    fn from_bounds(mut lower: UniFloat<C>, mut upper: UniFloat<C>) -> Self {
+        lower.copied();
+        upper.copied();
+        Self::new(&lower, &upper)
+    }
+
+    /// As `UniFloat::copied()`, for both bounds.
+    #[inline]
+    pub fn copied(&mut self) -> &mut Self {
+        self.lower.copied();
+        self.upper.copied();
+        self
+    }
+
+    /// As `UniFloat::released()`, for both bounds.
+    fn released(self) -> Self {
+        Self { lower: self.lower.released(), upper: self.upper.released() }
+    }
+
+    pub fn lower(&self) -> &UniFloat<C> {
+        &self.lower
+    }
+
+    pub fn upper(&self) -> &UniFloat<C> {
+        &self.upper
+    }
+
+    /// Whether `lower` <= `value` <= `upper`. False if `value` is NaN.
+    pub fn contains(&self, value: &UniFloat<C>) -> bool {
+        self.lower.le(value) && value.le(&self.upper)
+    }
+
+    /// Whether `other` is a subset of `self`.
+    pub fn encloses(&self, other: &Self) -> bool {
+        self.lower.le(&other.lower) && other.upper.le(&self.upper)
+    }
+
+    /// Whether `self` and `other` have any value in common.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.lower.le(&other.upper) && other.lower.le(&self.upper)
+    }
+
+    /// (`lower` + `upper`) / 2, rounded to nearest. It's in the interval. NaN if both bounds are
+    /// infinite.
+    pub fn midpoint(&self) -> UniFloat<C> {
+        let mut sum = UniFloat::<C>::NAN;
+        sum <<= &self.lower;
+        sum += &self.upper;
+        if sum.is_infinite() && self.lower.is_finite() && self.upper.is_finite() {
+            // Overflow. Then halving each bound is exact.
+            let mut half_upper = self.upper.ldexp(-1);
+            half_upper.copied();
+            sum <<= self.lower.ldexp(-1);
+            sum += &half_upper;
+            sum.released()
+        } else {
+            sum.ldexp(-1)
+        }
+    }
+
+    /// `upper` - `lower`, rounded up.
+    pub fn width(&self) -> UniFloat<C> {
+        directed(&self.upper, &self.lower, RoundingMode::Up, UniFloat::<C>::sub_round, mpfr_op!(|r, x, y, rnd| unsafe { mpfr::sub(r, x, y, rnd) }))
+    }
+
+    /// The least and the greatest of `op` over the pairs of bounds of `self` and `other`, rounded
+    /// outward. For multiplication and division.
+    fn extremes(&self, other: &Self,
+        round: impl Fn(&UniFloat<C>, &UniFloat<C>, RoundingMode) -> Result<UniFloat<C>, UnsupportedRounding>,
+        mpfr_op: impl Fn(mpfr::mpfr_ptr, mpfr::mpfr_srcptr, mpfr::mpfr_srcptr, mpfr::rnd_t) -> i32
+    ) -> Self {
+        let pairs = [(&self.lower, &other.lower), (&self.lower, &other.upper), (&self.upper, &other.lower),
+            (&self.upper, &other.upper)];
+        let (mut lower, mut upper) = (UniFloat::<C>::INFINITY, UniFloat::<C>::NEG_INFINITY);
+        lower.copied();
+        upper.copied();
+        for &(x, y) in pairs.iter() {
+            let mut down = directed(x, y, RoundingMode::Down, &round, &mpfr_op);
+            down.copied();
+            let least = lower.min(&down);
+            lower <<= least;
+            let mut up = directed(x, y, RoundingMode::Up, &round, &mpfr_op);
+            up.copied();
+            let greatest = upper.max(&up);
+            upper <<= greatest;
+        }
+        Self::new(&lower, &upper)
+    }
+}
+
+/// `x op y` rounded in the direction of `rounding` (`Up` or `Down`): by `round` (one of the
+/// `UniFloat::*_round()` methods), or for TwoFloat by `mpfr_op` in MPFR.
+#[cfg_attr(any(not(feature = "mpfr"), not(feature = "twofloat")), allow(unused_variables))]
+fn directed<const C: UniFloatChoice>(x: &UniFloat<C>, y: &UniFloat<C>, rounding: RoundingMode,
+    round: impl Fn(&UniFloat<C>, &UniFloat<C>, RoundingMode) -> Result<UniFloat<C>, UnsupportedRounding>,
+    mpfr_op: impl Fn(mpfr::mpfr_ptr, mpfr::mpfr_srcptr, mpfr::mpfr_srcptr, mpfr::rnd_t) -> i32
+) -> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    match C {
+        #[cfg(not(feature = "f32_only"))]
+        #[cfg(feature = "mpfr")]
+        #[cfg(feature = "twofloat")]
+        UniFloatChoice::TwoFloat => unsafe {
+            let (mut exact, mut operand) = (UniMpfrTwoFloatProductExact::NAN, UniMpfrTwoFloatProductExact::NAN);
+            exact.copied();
+            operand.copied();
+            let (exact, operand) = (exact.mpfr_mut(), operand.mpfr_mut());
+            mpfr_set_twofloat(exact, x.twofloats[0]);
+            mpfr_set_twofloat(operand, y.twofloats[0]);
+            let rnd = rounding.to_mpfr();
+            // Exact for addition, subtraction and multiplication. Division rounds in the same
+            // direction as `twofloat_rounded()`.
+            mpfr_op(exact, exact, operand, rnd);
+            let mut result = UniFloat::<C>::NAN;
+            result.twofloats[0] = twofloat_rounded(exact, rnd);
+            result
+        },
+        #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+        #[cfg(feature = "twofloat")]
+        UniFloatChoice::TwoFloat => {
+            let () = Supported::<C>::TWOFLOAT_VIA_MPFR;
+            unreachable!("Intervals of TwoFloat are supported with mpfr feature only.")
+        },
+        _ => match round(x, y, rounding) {
+            Ok(result) => result,
+            Err(_) => unreachable!("Only TwoFloat lacks directed rounding.")
+        }
+    }
+}
+
+/// `x` (from `UniMpfrTwoFloatProductExact`) rounded to double-double in the direction of `rnd`
+/// (RNDU or RNDD). The higher part rounds in that direction, and so does the rest.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+unsafe fn twofloat_rounded(x: mpfr::mpfr_srcptr, rnd: mpfr::rnd_t) -> TwoFloat {
+    let high = mpfr::get_d(x, rnd);
+    if !high.is_finite() {
+        return TwoFloat::from(high);
+    }
+    let mut rest = UniMpfrTwoFloatProductExact::NAN;
+    rest.copied();
+    let rest = rest.mpfr_mut();
+    mpfr::sub_d(rest, x, high, rnd);
+    // Exact: `high + low` is a TwoSum.
+    TwoFloat::new_add(high, mpfr::get_d(rest, rnd))
+}
+
+/// [-`upper`, -`lower`]. Exact.
+impl <const C: UniFloatChoice> ops::Neg for &UniInterval<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Output = UniInterval<C>;
+    fn neg(self) -> Self::Output {
+        UniInterval::from_bounds(-&self.upper, -&self.lower)
+    }
+}
+
+// Binary operators (+, -, *, /) on references, rounded outward.
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, |$x:ident, $y:ident| $op:expr) => {
+        impl <const C: UniFloatChoice> ops::$trait<&UniInterval<C>> for &UniInterval<C> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        {
+            type Output = UniInterval<C>;
+            fn $method(self, other: &UniInterval<C>) -> Self::Output {
+                let ($x, $y) = (self, other);
+                $op
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, |x, y| UniInterval::from_bounds(
+    directed(&x.lower, &y.lower, RoundingMode::Down, UniFloat::<C>::add_round, mpfr_op!(|r, a, b, rnd| unsafe { mpfr::add(r, a, b, rnd) })),
+    directed(&x.upper, &y.upper, RoundingMode::Up, UniFloat::<C>::add_round, mpfr_op!(|r, a, b, rnd| unsafe { mpfr::add(r, a, b, rnd) }))
+));
+impl_binary_op!(Sub, sub, |x, y| UniInterval::from_bounds(
+    directed(&x.lower, &y.upper, RoundingMode::Down, UniFloat::<C>::sub_round, mpfr_op!(|r, a, b, rnd| unsafe { mpfr::sub(r, a, b, rnd) })),
+    directed(&x.upper, &y.lower, RoundingMode::Up, UniFloat::<C>::sub_round, mpfr_op!(|r, a, b, rnd| unsafe { mpfr::sub(r, a, b, rnd) }))
+));
+impl_binary_op!(Mul, mul, |x, y| x.extremes(y, UniFloat::<C>::mul_round, mpfr_op!(|r, a, b, rnd| unsafe { mpfr::mul(r, a, b, rnd) })));
+// A divisor that contains zero gives [-Inf, +Inf].
+impl_binary_op!(Div, div, |x, y| {
+    let mut zero = UniFloat::<C>::ZERO;
+    zero.copied();
+    if y.contains(&zero) {
+        UniInterval::from_bounds(UniFloat::NEG_INFINITY, UniFloat::INFINITY)
+    } else {
+        x.extremes(y, UniFloat::<C>::div_round, mpfr_op!(|r, a, b, rnd| unsafe { mpfr::div(r, a, b, rnd) }))
+    }
+});
@@ -0,0 +1,255 @@
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use {core::mem::MaybeUninit, crate::mpfr};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// e as double-double: the nearest f64, and the rest.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+const E_TWOFLOAT: (f64, f64) = (core::f64::consts::E, 1.4456468917292502e-16);
+
+/// Lambert W function: the inverse of w * e^w. It's real for x >= -1/e, where it has two branches:
+/// W0 (from -1 up) and, for x < 0, W-1 (from -1 down). Both start from an approximation (a series
+/// at the branch point -1/e, or an asymptotic one elsewhere), and improve it by Halley's method for
+/// w * e^w = x, or by Newton's method for w + ln|w| = ln|x| (which doesn't overflow or underflow)
+/// where |w| >= 2. Accuracy:
+/// - MPFR: correctly rounded. The iterations run at a wider working precision, which increases
+///   until the result can be rounded (see `mpfr_lambert_w()`).
+/// - f32: computed with f64, so nearly always correctly rounded.
+/// - f64: computed with TwoFloat, so nearly always correctly rounded. f32 and f64 need feature
+///   `twofloat`.
+/// - TwoFloat: iterated in double-double, with relative error below 1e-26 (but W is
+///   ill-conditioned near -1/e, so there the error grows).
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Principal branch W0. NaN for `self < -1/e`, +-0 for +-0, and +Inf for +Inf.
+    pub fn lambert_w0(&self) -> Self {
+        self.unary(twofloat_op!(|x| lambert_w_twofloat(TwoFloat::from(x as f64), false).hi() as f32),
+            twofloat_op!(|x| lambert_w_twofloat(TwoFloat::from(x), false).hi()),
+            twofloat_op!(|x| lambert_w_twofloat(x, false)),
+            mpfr_op!(|r, x| unsafe { mpfr_lambert_w(r, x, false) }))
+    }
+
+    /// Lower branch W-1. NaN for `self < -1/e` and for `self > 0`, and -Inf for +-0.
+    pub fn lambert_wm1(&self) -> Self {
+        self.unary(twofloat_op!(|x| lambert_w_twofloat(TwoFloat::from(x as f64), true).hi() as f32),
+            twofloat_op!(|x| lambert_w_twofloat(TwoFloat::from(x), true).hi()),
+            twofloat_op!(|x| lambert_w_twofloat(x, true)),
+            mpfr_op!(|r, x| unsafe { mpfr_lambert_w(r, x, true) }))
+    }
+}
+
+/// W0(x), or W-1(x) if `lower`.
+#[cfg(feature = "twofloat")]
+fn lambert_w_twofloat(x: TwoFloat, lower: bool) -> TwoFloat {
+    let hi = x.hi();
+    if hi.is_nan() || (lower && hi > 0.0) || hi == f64::INFINITY {
+        return TwoFloat::from(if lower { f64::NAN } else { hi });
+    } else if hi == 0.0 {
+        return if lower { TwoFloat::from(f64::NEG_INFINITY) } else { x };
+    }
+    // t = 1 + e * x is zero at the branch point. The product rounds, so tiny t counts as zero.
+    let t = x * TwoFloat::new_add(E_TWOFLOAT.0, E_TWOFLOAT.1) + 1.0;
+    if !(t.hi() >= -1e-30) {
+        return TwoFloat::from(f64::NAN);
+    } else if t.hi() <= 1e-30 {
+        return TwoFloat::from(-1.0);
+    }
+    let mut w = TwoFloat::from(lambert_w_approximation(hi, t.hi(), lower));
+    for _ in 0..20 {
+        let step = if libm::fabs(w.hi()) >= 2.0 {
+            (w + w.abs().ln() - x.abs().ln()) * w / (w + 1.0)
+        } else {
+            let (e, w_1) = (w.exp(), w + 1.0);
+            let f = w * e - x;
+            f / (e * w_1 - (w + 2.0) * f / (w_1 * 2.0))
+        };
+        w = w - step;
+        if !(libm::fabs(step.hi()) > 1e-30 * libm::fabs(w.hi())) {
+            break;
+        }
+    }
+    w
+}
+
+/// A starting point for the iterations, within a few percent, for x (with t = 1 + e * x) in the
+/// domain of the branch.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+fn lambert_w_approximation(x: f64, t: f64, lower: bool) -> f64 {
+    if t < 0.25 {
+        // The series at the branch point, in p = +-sqrt(2 * t).
+        let p = if lower { -libm::sqrt(2.0 * t) } else { libm::sqrt(2.0 * t) };
+        ((11.0 / 72.0 * p - 1.0 / 3.0) * p + 1.0) * p - 1.0
+    } else if lower {
+        // Asymptotic, for x towards -0.
+        let l1 = libm::log(-x);
+        let l2 = libm::log(-l1);
+        l1 - l2 + l2 / l1
+    } else {
+        // Winitzki's approximation.
+        let l = libm::log1p(x);
+        l * (1.0 - libm::log1p(l) / (2.0 + l))
+    }
+}
+
+/// Correctly rounded W0(x) (or W-1(x), if `lower`), by Ziv's strategy (as `mpfr_log()` in the
+/// logarithm module). The iterations of `lambert_w_twofloat()` run at a higher (working)
+/// precision, until the result can be rounded. W is irrational except at zero, but the working
+/// precision is limited all the same.
+///
+/// Each iteration at a higher precision starts from the result at the previous one.
+#[cfg(feature = "mpfr")]
+unsafe fn mpfr_lambert_w(r: mpfr::mpfr_ptr, x: mpfr::mpfr_srcptr, lower: bool) -> i32 {
+    if mpfr::nan_p(x) != 0 || (lower && mpfr::sgn(x) > 0) || (mpfr::inf_p(x) != 0 && (lower || mpfr::sgn(x) < 0)) {
+        mpfr::set_nan(r);
+        return 0;
+    } else if mpfr::inf_p(x) != 0 || (mpfr::zero_p(x) != 0 && !lower) {
+        return mpfr::set(r, x, mpfr::rnd_t::RNDN);
+    } else if mpfr::zero_p(x) != 0 {
+        mpfr::set_inf(r, -1);
+        return 0;
+    }
+    let precision = mpfr::get_prec(r);
+    let mut working = precision + 32;
+    let mut temporaries = [MaybeUninit::uninit(), MaybeUninit::uninit(), MaybeUninit::uninit(), MaybeUninit::uninit(),
+        MaybeUninit::uninit()];
+    for temporary in temporaries.iter_mut() {
+        mpfr::init2(temporary.as_mut_ptr(), working);
+    }
+    let [w, e, f, s, d] = {
+        let [w, e, f, s, d] = &mut temporaries;
+        [w.as_mut_ptr(), e.as_mut_ptr(), f.as_mut_ptr(), s.as_mut_ptr(), d.as_mut_ptr()]
+    };
+    let mut approximated = false;
+    let ternary = loop {
+        // t = 1 + e * x, with absolute error below 2^(2 - working).
+        mpfr::set_ui(s, 1, mpfr::rnd_t::RNDN);
+        mpfr::exp(s, s, mpfr::rnd_t::RNDN);
+        mpfr::mul(s, s, x, mpfr::rnd_t::RNDN);
+        mpfr::add_ui(s, s, 1, mpfr::rnd_t::RNDN);
+        let capped = working > 2 * precision + 256;
+        if mpfr::zero_p(s) != 0 || mpfr::get_exp(s) <= 2 - working as mpfr::exp_t {
+            // The branch point, or too close to tell yet.
+            if capped {
+                break if mpfr::sgn(s) < 0 { mpfr::set_nan(r); 0 } else { mpfr::set_si(r, -1, mpfr::rnd_t::RNDN) };
+            }
+        } else if mpfr::sgn(s) < 0 {
+            mpfr::set_nan(r);
+            break 0;
+        } else {
+            if !approximated || mpfr::number_p(w) == 0 {
+                mpfr_lambert_w_approximation(w, x, s, e, lower);
+                approximated = true;
+            }
+            let mut lost_bits = 0;
+            for _ in 0..64 {
+                if mpfr::regular_p(w) != 0 && mpfr::get_exp(w) > 1 {
+                    // Newton's method for w + ln|w| = ln|x|: the step is
+                    // (w + ln|w| - ln|x|) * w / (w + 1).
+                    mpfr::abs(f, w, mpfr::rnd_t::RNDN);
+                    mpfr::log(f, f, mpfr::rnd_t::RNDN);
+                    mpfr::add(f, f, w, mpfr::rnd_t::RNDN);
+                    mpfr::abs(s, x, mpfr::rnd_t::RNDN);
+                    mpfr::log(s, s, mpfr::rnd_t::RNDN);
+                    mpfr::sub(f, f, s, mpfr::rnd_t::RNDN);
+                    mpfr::add_ui(s, w, 1, mpfr::rnd_t::RNDN);
+                    mpfr::div(s, w, s, mpfr::rnd_t::RNDN);
+                    mpfr::mul(f, f, s, mpfr::rnd_t::RNDN);
+                    lost_bits = 0;
+                } else {
+                    // Halley's method for w * e^w = x: with f = w * e^w - x, the step is
+                    // f / (e^w * (w + 1) - (w + 2) * f / (2 * w + 2)).
+                    mpfr::exp(e, w, mpfr::rnd_t::RNDN);
+                    mpfr::mul(f, w, e, mpfr::rnd_t::RNDN);
+                    mpfr::sub(f, f, x, mpfr::rnd_t::RNDN);
+                    mpfr::add_ui(s, w, 1, mpfr::rnd_t::RNDN);
+                    if mpfr::zero_p(s) != 0 {
+                        // Exactly at the branch point, at this precision: too close to tell.
+                        lost_bits = working as mpfr::exp_t;
+                        break;
+                    }
+                    // Near the branch point, w + 1 is small, and W is ill-conditioned by about
+                    // 1 / (w + 1).
+                    lost_bits = (-mpfr::get_exp(s)).max(0);
+                    mpfr::mul(e, e, s, mpfr::rnd_t::RNDN);
+                    mpfr::mul_2ui(s, s, 1, mpfr::rnd_t::RNDN);
+                    mpfr::add_ui(d, w, 2, mpfr::rnd_t::RNDN);
+                    mpfr::mul(d, d, f, mpfr::rnd_t::RNDN);
+                    mpfr::div(d, d, s, mpfr::rnd_t::RNDN);
+                    mpfr::sub(d, e, d, mpfr::rnd_t::RNDN);
+                    mpfr::div(f, f, d, mpfr::rnd_t::RNDN);
+                }
+                mpfr::sub(w, w, f, mpfr::rnd_t::RNDN);
+                // Once the step is down to the rounding errors, the iterations have converged
+                // (quadratically or better).
+                if mpfr::regular_p(f) == 0
+                    || mpfr::get_exp(f) < mpfr::get_exp(w) - working as mpfr::exp_t + 8 + lost_bits {
+                    break;
+                }
+            }
+            // The rounding errors of evaluating the step at the last iteration (a few ulps of
+            // working precision, scaled up by the conditioning of W), and that step's own error.
+            let error_bits = working as mpfr::exp_t - 12 - lost_bits;
+            if (mpfr::number_p(w) != 0
+                && mpfr::can_round(w, error_bits, mpfr::rnd_t::RNDN, mpfr::rnd_t::RNDZ, precision + 1) != 0) || capped {
+                break mpfr::set(r, w, mpfr::rnd_t::RNDN);
+            }
+        }
+        working += working / 2;
+        mpfr::prec_round(w, working, mpfr::rnd_t::RNDN);
+        for &temporary in [e, f, s, d].iter() {
+            mpfr::set_prec(temporary, working);
+        }
+    };
+    for temporary in temporaries.iter_mut() {
+        mpfr::clear(temporary.as_mut_ptr());
+    }
+    ternary
+}
+
+/// `lambert_w_approximation()` in MPFR (whose exponents may be out of f64's range), with
+/// t = 1 + e * x in `t`. It sets `w`, and it overwrites `t` and `scratch`.
+#[cfg(feature = "mpfr")]
+unsafe fn mpfr_lambert_w_approximation(w: mpfr::mpfr_ptr, x: mpfr::mpfr_srcptr, t: mpfr::mpfr_ptr,
+    scratch: mpfr::mpfr_ptr, lower: bool) {
+    if mpfr::cmp_d(t, 0.25) < 0 {
+        let p = t;
+        mpfr::mul_2ui(p, p, 1, mpfr::rnd_t::RNDN);
+        mpfr::sqrt(p, p, mpfr::rnd_t::RNDN);
+        if lower {
+            mpfr::neg(p, p, mpfr::rnd_t::RNDN);
+        }
+        mpfr::mul_ui(w, p, 11, mpfr::rnd_t::RNDN);
+        mpfr::div_ui(w, w, 72, mpfr::rnd_t::RNDN);
+        mpfr::set_ui(scratch, 1, mpfr::rnd_t::RNDN);
+        mpfr::div_ui(scratch, scratch, 3, mpfr::rnd_t::RNDN);
+        mpfr::sub(w, w, scratch, mpfr::rnd_t::RNDN);
+        mpfr::mul(w, w, p, mpfr::rnd_t::RNDN);
+        mpfr::add_ui(w, w, 1, mpfr::rnd_t::RNDN);
+        mpfr::mul(w, w, p, mpfr::rnd_t::RNDN);
+        mpfr::sub_ui(w, w, 1, mpfr::rnd_t::RNDN);
+    } else if lower {
+        let (l1, l2) = (t, scratch);
+        mpfr::neg(l1, x, mpfr::rnd_t::RNDN);
+        mpfr::log(l1, l1, mpfr::rnd_t::RNDN);
+        mpfr::neg(l2, l1, mpfr::rnd_t::RNDN);
+        mpfr::log(l2, l2, mpfr::rnd_t::RNDN);
+        mpfr::sub(w, l1, l2, mpfr::rnd_t::RNDN);
+        mpfr::div(l2, l2, l1, mpfr::rnd_t::RNDN);
+        mpfr::add(w, w, l2, mpfr::rnd_t::RNDN);
+    } else {
+        let l = t;
+        mpfr::log1p(l, x, mpfr::rnd_t::RNDN);
+        mpfr::log1p(scratch, l, mpfr::rnd_t::RNDN);
+        mpfr::add_ui(w, l, 2, mpfr::rnd_t::RNDN);
+        mpfr::div(scratch, scratch, w, mpfr::rnd_t::RNDN);
+        mpfr::ui_sub(scratch, 1, scratch, mpfr::rnd_t::RNDN);
+        mpfr::mul(w, l, scratch, mpfr::rnd_t::RNDN);
+    }
+}
@@ -1,13 +1,245 @@
 #![allow(incomplete_features)]
-#![feature(const_generics, const_evaluatable_checked, const_panic, int_bits_const, const_maybe_uninit_assume_init, const_fn_floating_point_arithmetic)]
+#![feature(const_generics, const_evaluatable_checked, const_panic, int_bits_const, const_maybe_uninit_assume_init, const_fn_floating_point_arithmetic, const_fn_transmute)]
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+/// A closure of an operation that calls MPFR: the MPFR one (for `UniFloat::unary()` and the like),
+/// or one for another choice that computes in MPFR. Without feature `mpfr` it's a closure with the
+/// same parameters that fails to compile once it's instantiated for an MPFR choice (see
+/// `Supported`): the MPFR choices are left out, and so are such operations. For TwoFloat
+/// (`mpfr_op!(twofloat_op!(..))`) it fails to compile, too. `UniFloatDyn` has no choice at compile
+/// time: there (`mpfr_op!(dyn ..)`) the closure panics instead.
+#[cfg(feature = "mpfr")]
+macro_rules! mpfr_op {
+    (dyn $closure:expr) => { $closure };
+    ($closure:expr) => { $closure };
+}
+#[cfg(not(feature = "mpfr"))]
+macro_rules! mpfr_op {
+    (dyn |$($param:tt),*| $body:expr) => {
+        |$(mpfr_op!(@ignored $param)),*| unreachable!("MPFR is available with mpfr feature only.")
+    };
+    (|$($param:tt),*| $body:expr) => {
+        |$(mpfr_op!(@ignored $param)),*| {
+            let () = crate::Supported::<C>::WITHOUT_MPFR;
+            unreachable!("MPFR is available with mpfr feature only.")
+        }
+    };
+    (twofloat_op!(|$($param:tt),*| $body:expr)) => {
+        |$(mpfr_op!(@ignored $param)),*| {
+            let () = crate::Supported::<C>::TWOFLOAT_VIA_MPFR;
+            unreachable!("TwoFloat computes this through MPFR, with mpfr feature only.")
+        }
+    };
+    (@ignored $param:tt) => { _ };
+}
+
+/// As `mpfr_op!`, for the basic operations that `SoftMpfr` has, too: with feature `softmpfr` (and
+/// without `mpfr`) the closure stays, and the MPFR functions it calls are SoftMpfr's (see
+/// `softmpfr::mpfr_functions`).
+#[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+macro_rules! softmpfr_op {
+    ($closure:expr) => { $closure };
+}
+#[cfg(not(any(feature = "mpfr", feature = "softmpfr")))]
+macro_rules! softmpfr_op {
+    ($($closure:tt)*) => { mpfr_op!($($closure)*) };
+}
+
+/// As `mpfr_op!`, a closure of an operation that computes in TwoFloat: the TwoFloat (or QuadFloat)
+/// one, or one for another choice that goes through TwoFloat. Without feature `twofloat` it's a
+/// closure with the same parameters that panics.
+#[cfg(feature = "twofloat")]
+macro_rules! twofloat_op {
+    ($closure:expr) => { $closure };
+}
+#[cfg(not(feature = "twofloat"))]
+macro_rules! twofloat_op {
+    (|| $body:expr) => {
+        || unreachable!("TwoFloat is available with twofloat feature only.")
+    };
+    (|$($param:tt),*| $body:expr) => {
+        |$(twofloat_op!(@ignored $param)),*| unreachable!("TwoFloat is available with twofloat feature only.")
+    };
+    (@ignored $param:tt) => { _ };
+}
+
+#[cfg(feature = "approx")]
+mod approximating;
+#[cfg(any(feature = "quickcheck", feature = "proptest"))]
+mod arbitrary;
+mod arith;
+#[cfg(not(feature = "f32_only"))]
+mod auditing;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+mod ball;
+mod bessel;
+mod big;
+mod bytes;
+mod classify;
+mod cmp;
+mod complex;
+mod consts;
+mod convert;
+mod decimal;
+mod dual;
+mod dynamic;
+mod eft;
+mod elliptic;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+mod escalation;
+mod exponential;
+mod fft;
+mod flags;
+mod format;
+mod hex;
+mod hyperbolic;
+mod interval;
+mod lambert;
+mod literal;
+mod logarithm;
+#[cfg(feature = "alloc")]
+mod matrix;
+mod neighbours;
+#[cfg(feature = "num-traits")]
+mod num;
 mod operands;
+mod parse;
+mod parts;
+mod polynomial;
+mod posit;
+mod power;
+mod quadfloat;
+mod radix;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+mod rational;
+#[cfg(feature = "real_field")]
+mod real_field;
+mod roots;
+mod rounded;
+mod rounding;
+#[cfg(feature = "rand")]
+mod sampling;
+mod scaling;
+#[cfg(feature = "serde")]
+mod serializing;
+#[cfg(feature = "softmpfr")]
+mod softmpfr;
+mod special;
+mod statistics;
+mod stochastic;
+mod summation;
+mod ternary;
+// The tests check against MPFR, and they cover TwoFloat.
+#[cfg(all(feature = "mpfr", feature = "twofloat"))]
 mod tests;
+pub mod testing;
+mod trigonometry;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+mod view;
+#[cfg(feature = "zeroize")]
+mod zeroizing;
 
-use {core::ops, core::ptr, core::mem, core::num, gmp_mpfr_sys::{mpfr, gmp}};
+use {core::ops, core::ptr, core::mem, core::num};
+#[cfg(feature = "mpfr")]
+use gmp_mpfr_sys::{mpfr, gmp};
+#[cfg(not(feature = "mpfr"))]
+use no_mpfr::{mpfr, gmp};
+#[cfg(feature = "twofloat")]
+use twofloat::TwoFloat;
+#[cfg(not(feature = "twofloat"))]
+use no_twofloat::TwoFloat;
 
+/// Without feature `twofloat`: a stand-in for `twofloat::TwoFloat`, with no values, for the
+/// where-clause of `UniFloat` and the TwoFloat closures of operations.
+#[cfg(not(feature = "twofloat"))]
+mod no_twofloat {
+    #[derive(Clone, Copy, Debug)]
+    pub enum TwoFloat {}
+}
+
+/// Without feature `mpfr`: the types of gmp-mpfr-sys that `UniFloat` and the choices are made of
+/// (and that the MPFR closures of operations take), so that they stay the same. There are no
+/// functions, other than the basic ones (and decimal text) that feature `softmpfr` computes by
+/// `SoftMpfr`: code that calls MPFR needs feature `mpfr`.
+#[cfg(not(feature = "mpfr"))]
+#[allow(non_camel_case_types)]
+mod no_mpfr {
+    pub mod gmp {
+        pub type limb_t = u64;
+        pub const NUMB_BITS: i32 = 64;
+    }
+
+    pub mod mpfr {
+        pub type prec_t = i64;
+        pub type exp_t = i64;
+
+        #[allow(dead_code)] // only the MPFR closures of operations use them.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        #[repr(C)]
+        pub enum rnd_t { RNDN = 0, RNDZ = 1, RNDU = 2, RNDD = 3, RNDA = 4, RNDF = 5, RNDNA = -1 }
+
+        #[derive(Clone, Copy, Debug)]
+        #[repr(C)]
+        pub struct mpfr_t {
+            pub prec: prec_t,
+            pub sign: i32,
+            pub exp: exp_t,
+            pub d: core::ptr::NonNull<super::gmp::limb_t>
+        }
+        pub type mpfr_ptr = *mut mpfr_t;
+        pub type mpfr_srcptr = *const mpfr_t;
+
+        #[cfg(feature = "softmpfr")]
+        pub use crate::softmpfr::mpfr_functions::*;
+    }
+}
+
+#[cfg(not(feature = "f32_only"))]
+pub use auditing::{AUDIT_SITES, AuditSite, UniAudited};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+pub use ball::{BALL_RADIUS, UniBall, UniFloatTracked};
+pub use bytes::bytes_length;
+pub use complex::UniComplex;
+pub use convert::{Conversion, ToIntError, ToIntErrorKind};
+pub use decimal::{DECIMAL_EXPONENT_LIMIT, UniDecimal, valid_decimal_digits};
+use decimal::{Dec, MAX_DECIMAL_DIGITS};
+pub use dual::UniDual;
+pub use dynamic::UniFloatDyn;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+pub use escalation::{ESCALATION_CHOICES, Escalation, NotConverged, escalate};
+pub use flags::Flags;
+pub use format::{BufferTooSmall, FormatOptions, MAX_FORMAT_DIGITS, Notation};
+pub use interval::UniInterval;
+pub use literal::LITERAL_BITS;
+#[cfg(feature = "num-traits")]
+pub use num::UniNum;
+#[cfg(feature = "alloc")]
+pub use matrix::{UniMat, UniVec};
 pub use operands::{OperandMutated, OperandOwned};
+pub use parse::{MAX_PARSE_LENGTH, ParseErrorKind, ParseUniFloatError};
+pub use posit::{UniPosit, valid_posit};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+pub use rational::UniRational;
+pub use rounding::{RoundingMode, UnsupportedRounding};
+#[cfg(feature = "rand")]
+pub use sampling::{UniExponential, UniNormal};
+#[cfg(feature = "softmpfr")]
+pub use softmpfr::{SOFTMPFR_MAX_LIMBS, SoftMpfr};
+pub use stochastic::{UniStochastic, cestac};
+pub use summation::{PAIRWISE_BLOCK_SIZE, SumCompensated};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+pub use view::{UniFloatMut, UniFloatRef};
 
 /// Across this crate: Const generic parameter S is NOT necessarily a number of
 /// 64bit extras, but a number of any and all 64-bit
@@ -25,11 +257,58 @@ pub use operands::{OperandMutated, OperandOwned};
 /// That is compatible with MPFR. It saves extra steps
 /// and prevents mistakes with uninitialized values.
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct MpfrBounds {
     /// Intentionally private, to guard integrity.
     precision_bits: usize,
-    limb_parts: usize
+    limb_parts: usize,
+    /// Exponent range (as per `f64::MIN_EXP` and `f64::MAX_EXP`, which is the same as MPFR's emin
+    /// and emax). `isize::MIN` and `isize::MAX` mean MPFR's default range.
+    min_exponent: isize,
+    max_exponent: isize,
+    underflow: Underflow,
+    /// For `UniFloatChoice::fixed()`.
+    fixed: Option<FixedFormat>,
+    /// For `UniFloatChoice::posit()`.
+    posit: Option<PositFormat>
+}
+
+/// What MPFR results below the normal range (less than 2^(`min_exponent` - 1) in magnitude) become,
+/// with `MpfrBounds::for_bounds()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Underflow {
+    /// MPFR's own: rounded to zero or to the least normal value. There are no subnormals.
+    Rounded,
+    /// Subnormal, with precision reduced as in IEEE 754 (gradual underflow), with
+    /// `mpfr::subnormalize()`.
+    Subnormal,
+    /// Zero (with the sign of the result), like FPUs that flush subnormal results to zero.
+    FlushToZero
+}
+
+/// What results beyond the range of a fixed-point format become (see `UniFloatChoice::fixed()`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixedOverflow {
+    /// The largest or the least value, as DSPs with saturating arithmetic.
+    Saturating,
+    /// Modulo 2^`int_bits`, as two's complement integers.
+    Wrapping
+}
+
+/// Binary fixed-point format: `int_bits` (including the sign) before the binary point, and
+/// `frac_bits` after it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FixedFormat {
+    int_bits: usize,
+    frac_bits: usize,
+    overflow: FixedOverflow
+}
+
+/// Posit format: `nbits` bits, with up to `es` exponent bits (see `UniPosit`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PositFormat {
+    nbits: u32,
+    es: u32
 }
 
 impl MpfrBounds {
@@ -38,41 +317,232 @@ impl MpfrBounds {
             precision_bits,
             /// Based on mfpr::MPFR_DECL_INIT
             limb_parts: (precision_bits - 1) / gmp::NUMB_BITS 
-                as usize + 1
+                as usize + 1,
+            min_exponent: isize::MIN,
+            max_exponent: isize::MAX,
+            underflow: Underflow::Rounded,
+            fixed: None,
+            posit: None
+        }
+    }
+
+    /// MPFR with the precision and the exponent range of `bounds` exactly, rather than MPFR's wide
+    /// default range. That emulates a custom format: results of operations overflow to infinity
+    /// beyond 2^`bounds.max_exponent`, and underflow to zero below 2^(`bounds.min_exponent` - 1).
+    /// (MPFR has no subnormals.) Operations set MPFR's emin and emax for their duration.
+    ///
+    /// Values from other sources (like parsing, or conversions from other choices) are not limited
+    /// to the range until the next arithmetic operation.
+    pub const fn for_bounds(bounds: &UniFloatBounds<{ UniFloatBoundsBase::BINARY }>) -> Self {
+        assert!(bounds.min_exponent <= bounds.max_exponent, "MpfrBounds need min_exponent <= max_exponent.");
+        Self {
+            min_exponent: bounds.min_exponent,
+            max_exponent: bounds.max_exponent,
+            ..Self::for_precision_binary(bounds.precision)
+        }
+    }
+
+    /// `self` with results below the normal range handled as per `underflow`. It has an effect only
+    /// with an exponent range from `for_bounds()`. The default is `Underflow::Rounded`.
+    pub const fn with_underflow(self, underflow: Underflow) -> Self {
+        Self { underflow, ..self }
+    }
+
+    /// Whether MPFR values with exponent `exponent` are subnormal for these bounds. (MPFR's
+    /// exponent is one more than the one of IEEE 754, as its significands are in [0.5, 1).)
+    #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(dead_code))]
+    const fn is_subnormal_exponent(&self, exponent: mpfr::exp_t) -> bool {
+        match (self.exponent_range(), self.underflow) {
+            (Some((min_exponent, _)), Underflow::Subnormal) => exponent < min_exponent,
+            _ => false
         }
     }
-    // Once https://github.com/rust-lang/rust/pull/80918 is merged, consider #![feature(int_log)] instead. Then see if you can make this function `const`.
-    fn for_precision_decimal(precision_decimal: usize) -> Self {
-        let precision_bits = (precision_decimal as f32 * core::f32::consts::LOG10_2).ceil() as usize;
-        Self::for_precision_binary(precision_bits)
+
+    /// MPFR's emin and emax for these bounds, or None for MPFR's default range.
+    const fn exponent_range(&self) -> Option<(mpfr::exp_t, mpfr::exp_t)> {
+        if self.min_exponent == isize::MIN && self.max_exponent == isize::MAX {
+            None
+        } else {
+            Some((self.min_exponent as mpfr::exp_t, self.max_exponent as mpfr::exp_t))
+        }
+    }
+    #[allow(dead_code)]
+    const fn for_precision_decimal(precision_decimal: usize) -> Self {
+        Self::for_precision_binary(decimal_to_binary_precision(precision_decimal))
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum UniFloatChoice {
     F32, F64, TwoFloat,
+    /// Quad-double: four f64 components, for 212 bits of significand with the exponent range of
+    /// f64. `+`, `-`, `*` and `/` are native (error-free transformations of f64, as in the QD
+    /// library), so they're much faster than MPFR at this precision, but not correctly rounded.
+    /// Other operations round the result of MPFR (from the exact value of the operands) to 212
+    /// bits.
+    QuadFloat,
     Mpfr {
         bounds: MpfrBounds
+    },
+    /// Binary fixed point, emulated by MPFR (see `UniFloatChoice::fixed()`, which checks the
+    /// format).
+    Fixed {
+        int_bits: usize,
+        frac_bits: usize,
+        overflow: FixedOverflow
+    },
+    /// Posit, emulated by MPFR (see `UniFloatChoice::posit()`, which checks the format).
+    Posit {
+        nbits: u32,
+        es: u32
+    },
+    /// Decimal floating point with `digits` significant digits, as `UniDecimal` (see
+    /// `UniFloatChoice::decimal()`, which checks the digits).
+    Decimal {
+        digits: usize
+    }
+}
+
+/// 16-bit formats, emulated by MPFR with their exact precision, exponent range and subnormals. So
+/// every operation rounds (and overflows and underflows) as on hardware with these formats, and
+/// the same generic code runs on them as on any other choice, like for studies of rounding errors
+/// in machine learning inference. They're not fast, and they take as much space as any one-limb MPFR
+/// choice.
+#[cfg(not(feature = "f32_only"))]
+impl UniFloatChoice {
+    /// IEEE 754 binary16 (half precision): 11 bits, from 2^-24 (subnormal) to 65504.
+    pub const F16: Self = UniFloatChoice::Mpfr {
+        bounds: MpfrBounds::for_bounds(&UniFloatBounds::new(11, -13, 16)).with_underflow(Underflow::Subnormal)
+    };
+    /// bfloat16 (brain floating point): 8 bits, with the exponent range of f32.
+    pub const BF16: Self = UniFloatChoice::Mpfr {
+        bounds: MpfrBounds::for_bounds(&UniFloatBounds::new(8, f32::MIN_EXP as isize, f32::MAX_EXP as isize))
+            .with_underflow(Underflow::Subnormal)
+    };
+}
+
+/// The 80-bit extended format of x87 FPUs, emulated by MPFR (on any target) as the 16-bit formats
+/// above, for reproducing results of legacy numerical code that ran on x87 in extended precision.
+/// (Code that stored intermediate results to f64 rounded them twice; so does `to_f64_nearest()`
+/// here.)
+#[cfg(not(feature = "f32_only"))]
+impl UniFloatChoice {
+    /// 64 bits (the explicit integer bit included), from 2^-16445 (subnormal) to about
+    /// 1.19 * 10^4932.
+    pub const F80: Self = UniFloatChoice::Mpfr {
+        bounds: MpfrBounds::for_bounds(&UniFloatBounds::new(64, -16381, 16384)).with_underflow(Underflow::Subnormal)
+    };
+}
+
+/// Binary fixed-point formats, emulated by MPFR: signed values with `int_bits` (including the sign)
+/// before the binary point and `frac_bits` after it, so the multiples of 2^-`frac_bits` from
+/// -2^(`int_bits` - 1) to 2^(`int_bits` - 1) - 2^-`frac_bits`, like the Q formats of DSPs. The
+/// same generic code can run in high precision MPFR, and then in the fixed-point format of the
+/// target hardware.
+///
+/// Operations run in MPFR with `int_bits` + `frac_bits` bits of precision, and then the result is
+/// rounded to nearest on the grid (with MPFR's ternary value, so without double rounding), and
+/// it saturates, or wraps, as per `overflow`. Zeros are +0. Wrapping is exact for sums and
+/// differences; results of other operations beyond 2^`int_bits` have already been rounded to
+/// `int_bits` + `frac_bits` significant bits before they wrap, unlike on hardware with double-width
+/// products. Infinities (from division by zero) wrap to NaN.
+///
+/// As with `MpfrBounds::for_bounds()`, values from other sources (like parsing) are not limited
+/// to the format until the next arithmetic operation.
+#[cfg(not(feature = "f32_only"))]
+impl UniFloatChoice {
+    pub const fn fixed(int_bits: usize, frac_bits: usize, overflow: FixedOverflow) -> Self {
+        MpfrBounds::for_fixed(FixedFormat { int_bits, frac_bits, overflow });
+        UniFloatChoice::Fixed { int_bits, frac_bits, overflow }
+    }
+}
+
+impl MpfrBounds {
+    /// The bounds that emulate `fixed`: its precision, and the exponent range of its grid.
+    const fn for_fixed(fixed: FixedFormat) -> Self {
+        assert!(fixed.int_bits >= 1 && fixed.int_bits + fixed.frac_bits >= 2,
+            "Fixed-point formats need the sign bit before the binary point, and at least one more bit.");
+        MpfrBounds {
+            min_exponent: 1 - fixed.frac_bits as isize,
+            max_exponent: fixed.int_bits as isize,
+            fixed: Some(fixed),
+            ..MpfrBounds::for_precision_binary(fixed.int_bits + fixed.frac_bits)
+        }
+    }
+}
+
+/// Posits of `nbits` bits with up to `es` exponent bits (see `UniPosit`, and `valid_posit()` for
+/// the supported formats), emulated by MPFR, so that the same generic code runs in posits, with
+/// all the functions of `UniFloat`.
+///
+/// Operations run in MPFR with `nbits` bits of precision (at least two more than any posit of the
+/// format has), and then the result is rounded as by `UniPosit` (with MPFR's ternary value, so
+/// without double rounding): to nearest, and saturated at `MAX` and `MIN_POSITIVE`. NaN and
+/// infinities become NaN, for NaR, and zeros +0.
+///
+/// As with `MpfrBounds::for_bounds()`, values from other sources (like parsing) are not limited
+/// to the format until the next arithmetic operation.
+#[cfg(not(feature = "f32_only"))]
+impl UniFloatChoice {
+    pub const fn posit(nbits: u32, es: u32) -> Self {
+        MpfrBounds::for_posit(PositFormat { nbits, es });
+        UniFloatChoice::Posit { nbits, es }
+    }
+}
+
+/// Decimal floating point with `digits` significant digits (1 to 34, see `valid_decimal_digits()`),
+/// with the arithmetic of `UniDecimal<digits>`, so that the same generic code runs in decimal, with
+/// all the functions of `UniFloat`. Decimal fractions like 0.1 are exact, so 0.1 + 0.2 == 0.3.
+///
+/// `+`, `-`, `*` and `/` are native and correctly rounded (to nearest, ties to even, or in the
+/// direction of a `RoundingMode`), and they keep the quantum as `UniDecimal` does. Other
+/// operations run in MPFR at 192 bits, and then the result is rounded to `digits` digits, so it's
+/// faithful (within one unit of the last digit) rather than correctly rounded. MPFR's exponent
+/// range (about 10^-323228496 to 10^323228496) is narrower than the decimal one (up to
+/// 10^`DECIMAL_EXPONENT_LIMIT`), so those operations overflow and underflow sooner.
+///
+/// Values take three 64-bit words, whatever `digits`.
+#[cfg(not(feature = "f32_only"))]
+impl UniFloatChoice {
+    pub const fn decimal(digits: usize) -> Self {
+        assert!(valid_decimal_digits(digits), "Decimals need 1 to 34 digits.");
+        UniFloatChoice::Decimal { digits }
     }
 }
 
+impl MpfrBounds {
+    /// The bounds that emulate `posit`: `nbits` bits of precision, and the exponent range from
+    /// `MIN_POSITIVE` to `MAX`.
+    const fn for_posit(posit: PositFormat) -> Self {
+        assert!(valid_posit(posit.nbits, posit.es), "Posits need 2 to 32 bits, with up to 4 exponent bits.");
+        let max_scale = posit_max_scale(posit) as isize;
+        MpfrBounds {
+            min_exponent: 1 - max_scale,
+            max_exponent: max_scale + 1,
+            posit: Some(posit),
+            ..MpfrBounds::for_precision_binary(posit.nbits as usize)
+        }
+    }
+}
+
+/// The scale of `MAX` of `posit`: 2^`es` * (`nbits` - 2).
+const fn posit_max_scale(posit: PositFormat) -> mpfr::exp_t {
+    (posit.nbits as mpfr::exp_t - 2) << posit.es
+}
+
 // Not public. Let's promote as generic solutions as possible.
 type UniF32 = UniFloat<{ UniFloatChoice::F32 }>;
 type UniF64 = UniFloat<{ UniFloatChoice::F64 }>;
 type UniTwoFloat = UniFloat<{ UniFloatChoice::TwoFloat }>;
+type UniQuadFloat = UniFloat<{ UniFloatChoice::QuadFloat }>;
 // Types with names starting with `UniMpfrLimbX` use `X` number of limbs.
-type UniMpfrLimb1Prec1 = UniFloat<{ UniFloatChoice::Mpfr { bounds: MpfrBounds {
-    limb_parts: 1,
-    precision_bits: 1,
-}}}>;
+type UniMpfrLimb1Prec1 = UniFloat<{ UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(1) }}>;
 
 const ONE_LIMB_PRECISION: usize = gmp::limb_t::BITS as usize;
 // Types with names like UniMpfrLimbxPrecAll use all the precision available
 // for their number of limbs.
-type UniMpfrLimb2PrecAll = UniFloat<{ UniFloatChoice::Mpfr { bounds: MpfrBounds {
-    limb_parts: 2,
-    precision_bits: 2 * ONE_LIMB_PRECISION,
-}}}>;
+type UniMpfrLimb2PrecAll = UniFloat<{ UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(2 * ONE_LIMB_PRECISION) }}>;
+type UniDecimalFloat = UniFloat<{ UniFloatChoice::Decimal { digits: MAX_DECIMAL_DIGITS } }>;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum UniFloatBoundsBase { DECIMAL, BINARY }
@@ -100,28 +570,163 @@ pub trait UniFloatBoundsToChoice {
 
 impl UniFloatBoundsToChoice for UniFloatBounds<{ UniFloatBoundsBase::BINARY }> {
     fn to_choice(&self) -> UniFloatChoice {
+        self.covering_choice()
+    }
+}
+
+impl UniFloatBoundsToChoice for UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> {
+    fn to_choice(&self) -> UniFloatChoice {
+        self.covering_choice()
+    }
+}
+
+impl UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> {
+    /// `UniFloatBoundsToChoice::to_choice()`, but `const`, for const generic arguments (as in
+    /// `unifloat_type!`).
+    pub const fn covering_choice(&self) -> UniFloatChoice {
+        self.to_binary().covering_choice()
+    }
+
+    /// Binary bounds that accommodate all needs of `self`. Both precision and exponents are rounded
+    /// in the conservative direction:
+    /// - precision: enough bits so that any decimal number with `self.precision` significant
+    ///   digits survives a round trip to binary and back (as per `f64::DIGITS`), and
+    /// - exponents: as per `f64::MIN_EXP` and `f64::MAX_EXP`, such that 10^`self.min_exponent`
+    ///   is normal and 10^`self.max_exponent` is finite (as per `f64::MIN_10_EXP` and
+    ///   `f64::MAX_10_EXP`).
+    ///
+    /// `self.to_binary().to_decimal()` covers `self`, unless the result saturates at usize or isize
+    /// limits.
+    pub const fn to_binary(&self) -> UniFloatBounds<{ UniFloatBoundsBase::BINARY }> {
+        UniFloatBounds::<{ UniFloatBoundsBase::BINARY }> {
+            precision: decimal_to_binary_precision(self.precision),
+            // floor(e * log2(10)) + 1 is both the greatest `min_exponent` with
+            // 2^(min_exponent - 1) <= 10^e, and the least `max_exponent` with 10^e < 2^max_exponent.
+            min_exponent: saturating_isize(floor_div_log10_2(self.min_exponent as i128, Approximation::Below) + 1),
+            max_exponent: saturating_isize(floor_div_log10_2(self.max_exponent as i128, Approximation::Above) + 1)
+        }
+    }
+}
+
+impl UniFloatBounds<{ UniFloatBoundsBase::BINARY }> {
+    /// `UniFloatBoundsToChoice::to_choice()`, but `const`, for const generic arguments.
+    pub const fn covering_choice(&self) -> UniFloatChoice {
         if F32_BOUNDS_BINARY.covers(self) {
             UniFloatChoice::F32
         } else if F64_BOUNDS_BINARY.covers(self) {
             UniFloatChoice::F64
         } else if TWOFLOAT_BOUNDS_BINARY.covers(self) {
             UniFloatChoice::TwoFloat
+        } else if QUADFLOAT_BOUNDS_BINARY.covers(self) {
+            UniFloatChoice::QuadFloat
         } else {
             UniFloatChoice::Mpfr {
                 bounds: MpfrBounds::for_precision_binary(self.precision)
             }
         }
     }
+
+    /// Decimal bounds guaranteed by `self`, rounded in the conservative direction. The inverse of
+    /// `UniFloatBounds::<DECIMAL>::to_binary()`: for `F32_BOUNDS_BINARY` and `F64_BOUNDS_BINARY`
+    /// this gives `f32::DIGITS`, `f32::MIN_10_EXP`, `f32::MAX_10_EXP` and the same for f64.
+    ///
+    /// `self` covers `self.to_decimal().to_binary()`.
+    pub const fn to_decimal(&self) -> UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> {
+        UniFloatBounds::<{ UniFloatBoundsBase::DECIMAL }> {
+            // floor((precision - 1) * log10(2))
+            precision: floor_mul_log10_2(self.precision.saturating_sub(1) as i128, Approximation::Below) as usize,
+            // ceil((min_exponent - 1) * log10(2))
+            min_exponent: saturating_isize(-floor_mul_log10_2(-(self.min_exponent as i128 - 1), Approximation::Below)),
+            // ceil(max_exponent * log10(2)) - 1
+            max_exponent: saturating_isize(-floor_mul_log10_2(-(self.max_exponent as i128), Approximation::Above) - 1)
+        }
+    }
 }
 
-impl UniFloatBoundsToChoice for UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> {
-    fn to_choice(&self) -> UniFloatChoice {
-        panic!()
+/// log10(2) = 0.30102999566398119521... is approximated by `LOG10_2_BELOW / LOG10_2_DENOMINATOR`
+/// and `LOG10_2_ABOVE / LOG10_2_DENOMINATOR`. Integer (rather than floating-point) arithmetic keeps
+/// bounds conversions `const` and the rounding direction certain. The products fit i128 for all
+/// usize and isize values.
+const LOG10_2_DENOMINATOR: i128 = 1_000_000_000_000_000_000;
+const LOG10_2_BELOW: i128 = 301_029_995_663_981_195;
+const LOG10_2_ABOVE: i128 = 301_029_995_663_981_196;
+
+/// Direction of error of results that use an approximation of log10(2), so that bounds round in
+/// the conservative direction: `Below` gives results less than or equal to the exact ones, `Above`
+/// greater or equal.
+#[derive(Clone, Copy)]
+enum Approximation { Below, Above }
+
+/// floor(`value` * log10(2)), with the error in the direction of `approximation`.
+const fn floor_mul_log10_2(value: i128, approximation: Approximation) -> i128 {
+    let log10_2 = match approximation {
+        Approximation::Below => if value >= 0 { LOG10_2_BELOW } else { LOG10_2_ABOVE },
+        Approximation::Above => if value >= 0 { LOG10_2_ABOVE } else { LOG10_2_BELOW }
+    };
+    floor_div(value * log10_2, LOG10_2_DENOMINATOR)
+}
+
+/// floor(`value` / log10(2)), that is floor(`value` * log2(10)), with the error in the direction of
+/// `approximation`.
+const fn floor_div_log10_2(value: i128, approximation: Approximation) -> i128 {
+    let log10_2 = match approximation {
+        Approximation::Below => if value >= 0 { LOG10_2_ABOVE } else { LOG10_2_BELOW },
+        Approximation::Above => if value >= 0 { LOG10_2_BELOW } else { LOG10_2_ABOVE }
+    };
+    floor_div(value * LOG10_2_DENOMINATOR, log10_2)
+}
+
+/// Integer division rounded down (towards negative infinity), for positive `divisor`.
+const fn floor_div(dividend: i128, divisor: i128) -> i128 {
+    let quotient = dividend / divisor;
+    if dividend % divisor < 0 { quotient - 1 } else { quotient }
+}
+
+const fn saturating_isize(value: i128) -> isize {
+    if value < isize::MIN as i128 {
+        isize::MIN
+    } else if value > isize::MAX as i128 {
+        isize::MAX
+    } else {
+        value as isize
+    }
+}
+
+/// Number of bits needed so that any decimal number with `precision_decimal` significant digits
+/// survives a round trip to binary and back: ceil(digits * log2(10)) + 1.
+const fn decimal_to_binary_precision(precision_decimal: usize) -> usize {
+    let bits = -floor_div_log10_2(-(precision_decimal as i128), Approximation::Below) + 1;
+    if bits > usize::MAX as i128 { usize::MAX } else { bits as usize }
+}
+
+/// Binary bounds guaranteed by decimals of `digits` digits, rounded in the conservative direction:
+/// - precision: floor((digits - 1) * log2(10)) + 1 bits, whose relative spacing isn't finer than
+///   that of the decimal digits,
+/// - min_exponent: such that 2^(min_exponent - 1) is at least the least decimal with all `digits`
+///   digits, 10^(digits - 1 - `DECIMAL_EXPONENT_LIMIT`), and
+/// - max_exponent: such that 2^max_exponent is at most 10^(`DECIMAL_EXPONENT_LIMIT` + digits - 1).
+const fn decimal_bounds_binary(digits: usize) -> UniFloatBounds<{ UniFloatBoundsBase::BINARY }> {
+    let limit = DECIMAL_EXPONENT_LIMIT as i128;
+    UniFloatBounds::<{ UniFloatBoundsBase::BINARY }> {
+        precision: floor_div_log10_2(digits as i128 - 1, Approximation::Below) as usize + 1,
+        min_exponent: saturating_isize(-floor_div_log10_2(limit + 1 - digits as i128, Approximation::Below) + 1),
+        max_exponent: saturating_isize(floor_div_log10_2(limit + digits as i128 - 1, Approximation::Below))
+    }
+}
+
+/// Decimal bounds of decimals of `digits` digits, which are exact: 10^(digits - 1 -
+/// `DECIMAL_EXPONENT_LIMIT`) has all `digits` digits, and 10^(`DECIMAL_EXPONENT_LIMIT` + digits - 1)
+/// is finite.
+const fn decimal_bounds_decimal(digits: usize) -> UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> {
+    UniFloatBounds::<{ UniFloatBoundsBase::DECIMAL }> {
+        precision: digits,
+        min_exponent: digits as isize - 1 - DECIMAL_EXPONENT_LIMIT as isize,
+        max_exponent: DECIMAL_EXPONENT_LIMIT as isize + digits as isize - 1
     }
 }
 
 impl <const BASE: UniFloatBoundsBase> UniFloatBounds<BASE> {
-    pub fn new(precision: usize, min_exponent: isize, max_exponent: isize) -> Self {
+    pub const fn new(precision: usize, min_exponent: isize, max_exponent: isize) -> Self {
         Self { precision, min_exponent, max_exponent}
     }
 
@@ -129,6 +734,36 @@ impl <const BASE: UniFloatBoundsBase> UniFloatBounds<BASE> {
     pub const fn covers(&self, other: &Self) -> bool {
         self.precision >= other.precision && self.min_exponent <= other.min_exponent && self.max_exponent >= other.max_exponent
     }
+
+    /// Bounds that fulfill the needs of both `self` and `other`: the higher precision and the wider
+    /// exponent range of the two. A choice that covers the result covers both.
+    pub const fn union(&self, other: &Self) -> Self {
+        Self {
+            precision: if self.precision >= other.precision { self.precision } else { other.precision },
+            min_exponent: if self.min_exponent <= other.min_exponent { self.min_exponent } else { other.min_exponent },
+            max_exponent: if self.max_exponent >= other.max_exponent { self.max_exponent } else { other.max_exponent }
+        }
+    }
+
+    /// Bounds that both `self` and `other` guarantee: the lower precision and the narrower exponent
+    /// range of the two. If their exponent ranges don't overlap, the result has `min_exponent`
+    /// greater than `max_exponent`.
+    pub const fn intersect(&self, other: &Self) -> Self {
+        Self {
+            precision: if self.precision <= other.precision { self.precision } else { other.precision },
+            min_exponent: if self.min_exponent >= other.min_exponent { self.min_exponent } else { other.min_exponent },
+            max_exponent: if self.max_exponent <= other.max_exponent { self.max_exponent } else { other.max_exponent }
+        }
+    }
+
+    /// `self` with `extra` more digits (bits for BINARY base) of precision, saturating. For
+    /// example, for guard bits of an algorithm that loses some precision.
+    pub const fn widen_precision(&self, extra: usize) -> Self {
+        Self {
+            precision: self.precision.saturating_add(extra),
+            ..*self
+        }
+    }
 }
 
 const F32_BOUNDS_BINARY: UniFloatBounds<{ UniFloatBoundsBase::BINARY }> = UniFloatBounds::<{ UniFloatBoundsBase::BINARY }> {
@@ -136,31 +771,99 @@ const F32_BOUNDS_BINARY: UniFloatBounds<{ UniFloatBoundsBase::BINARY }> = UniFlo
     min_exponent: f32::MIN_EXP as isize,
     max_exponent: f32::MAX_EXP as isize
 };
-const F32_BOUNDS_DECIMAL: UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> = UniFloatBounds::<{ UniFloatBoundsBase::DECIMAL }> {
-    precision: f32::DIGITS as usize,
-    min_exponent: f32::MIN_10_EXP as isize,
-    max_exponent: f32::MAX_10_EXP as isize
-};
+const F32_BOUNDS_DECIMAL: UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> = F32_BOUNDS_BINARY.to_decimal();
 const F64_BOUNDS_BINARY: UniFloatBounds<{ UniFloatBoundsBase::BINARY }> = UniFloatBounds::<{ UniFloatBoundsBase::BINARY }> {
     precision: f64::MANTISSA_DIGITS as usize,
     min_exponent: f64::MIN_EXP as isize,
     max_exponent: f64::MAX_EXP as isize
 };
-const F64_BOUNDS_DECIMAL: UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> = UniFloatBounds::<{ UniFloatBoundsBase::DECIMAL }> {
-    precision: f64::DIGITS as usize,
-    min_exponent: f64::MIN_10_EXP as isize,
-    max_exponent: f64::MAX_10_EXP as isize
-};
+const F64_BOUNDS_DECIMAL: UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> = F64_BOUNDS_BINARY.to_decimal();
 const TWOFLOAT_BOUNDS_BINARY: UniFloatBounds<{ UniFloatBoundsBase::BINARY }> = UniFloatBounds::<{ UniFloatBoundsBase::BINARY }> {
     precision: 2* f64::MANTISSA_DIGITS as usize,
     min_exponent: f64::MIN_EXP as isize,
     max_exponent: f64::MAX_EXP as isize
 };
-const TWOFLOAT_BOUNDS_DECIMAL: UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> = UniFloatBounds::<{ UniFloatBoundsBase::DECIMAL }> {
-    precision: 2 * f64::DIGITS as usize,
-    min_exponent: f64::MIN_10_EXP as isize,
-    max_exponent: f64::MAX_10_EXP as isize
+const TWOFLOAT_BOUNDS_DECIMAL: UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> = TWOFLOAT_BOUNDS_BINARY.to_decimal();
+const QUADFLOAT_BOUNDS_BINARY: UniFloatBounds<{ UniFloatBoundsBase::BINARY }> = UniFloatBounds::<{ UniFloatBoundsBase::BINARY }> {
+    precision: 4 * f64::MANTISSA_DIGITS as usize,
+    min_exponent: f64::MIN_EXP as isize,
+    max_exponent: f64::MAX_EXP as isize
 };
+const QUADFLOAT_BOUNDS_DECIMAL: UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> = QUADFLOAT_BOUNDS_BINARY.to_decimal();
+
+/// Helper for compile time checks in `where` clauses: `Assert<{ ...condition... }>: IsTrue`.
+/// Not a part of public API. It's public only because of Rust requirements.
+pub struct Assert<const CHECK: bool>;
+/// Not a part of public API. It's public only because of Rust requirements.
+pub trait IsTrue {}
+impl IsTrue for Assert<true> {}
+
+/// Helper for compile time checks of what the enabled features support for a choice, in code that
+/// `C` can't run without them: `let () = Supported::<C>::TWOFLOAT_VIA_MPFR;`. The constant fails
+/// to evaluate (so the build fails) once that code gets instantiated for such a `C`, rather than
+/// the code panicking at run time.
+#[cfg_attr(feature = "mpfr", allow(dead_code))]
+pub(crate) struct Supported<const C: UniFloatChoice>;
+
+#[cfg_attr(any(feature = "mpfr", feature = "f32_only"), allow(dead_code))]
+impl <const C: UniFloatChoice> Supported<C> {
+    /// For what TwoFloat computes through MPFR (like intervals and ternary values).
+    pub(crate) const TWOFLOAT_VIA_MPFR: () = if !cfg!(feature = "mpfr") && matches!(C, UniFloatChoice::TwoFloat) {
+        panic!("This operation of UniFloatChoice::TwoFloat needs feature mpfr.")
+    };
+
+    /// For what only F32, F64 and TwoFloat compute without MPFR.
+    pub(crate) const WITHOUT_MPFR: () = if !cfg!(feature = "mpfr") && !Self::native() {
+        panic!("This operation needs feature mpfr for choices other than UniFloatChoice::F32, F64 and TwoFloat.")
+    };
+
+    /// For the basic operations (see `softmpfr_op!`), formatting and parsing, which `SoftMpfr`
+    /// computes for `UniFloatChoice::Mpfr` in MPFR's default exponent range, too.
+    pub(crate) const BY_SOFTMPFR: () = if !cfg!(feature = "mpfr") && !Self::native()
+        && !(cfg!(feature = "softmpfr") && Self::default_mpfr()) {
+        panic!("Without feature mpfr, this operation needs feature softmpfr and UniFloatChoice::Mpfr with MPFR's default exponent range (or F32, F64 or TwoFloat).")
+    };
+
+    const fn native() -> bool {
+        matches!(C, UniFloatChoice::F32 | UniFloatChoice::F64 | UniFloatChoice::TwoFloat)
+    }
+
+    const fn default_mpfr() -> bool {
+        match C {
+            UniFloatChoice::Mpfr { bounds } =>
+                matches!((bounds.fixed, bounds.posit, bounds.exponent_range()), (None, None, None)),
+            _ => false
+        }
+    }
+}
+
+/// Fails to compile if a `UniFloatChoice` doesn't cover binary `UniFloatBounds` (see
+/// `UniFloatChoice::covers_bounds()`). Use it in a `const` item, so that shrinking the choice
+/// can't silently break the precision (or exponent range) that other code relies on:
+/// `const _: () = assert_choice_covers!(MY_CHOICE, REQUIRED_BOUNDS);`
+/// Both arguments have to be constant expressions. Build the bounds with `UniFloatBounds::new()`.
+#[macro_export]
+macro_rules! assert_choice_covers {
+    ($choice:expr, $bounds:expr) => {
+        if !$crate::UniFloatChoice::covers_bounds(&$choice, &$bounds) {
+            panic!("The UniFloatChoice doesn't cover the required UniFloatBounds.")
+        }
+    };
+}
+
+/// Declares `type $name = UniFloat<C>` for the choice `C` that covers `digits` significant
+/// decimal digits, from 10^`min` to 10^`max` (see `UniFloatBounds::<DECIMAL>::to_binary()`), as
+/// `UniFloatBoundsToChoice::to_choice()` picks it, at compile time:
+/// `unifloat_type!(pub MyFloat, digits = 40, exp10 = -100..100);` (which is QuadFloat). The
+/// exponents have to be literals. A visibility before the name is optional.
+#[macro_export]
+macro_rules! unifloat_type {
+    ($visibility:vis $name:ident, digits = $digits:expr, exp10 = $min:literal..$max:literal) => {
+        $visibility type $name = $crate::UniFloat<{
+            $crate::UniFloatBounds::<{ $crate::UniFloatBoundsBase::DECIMAL }>::new($digits, $min, $max).covering_choice()
+        }>;
+    };
+}
 
 /// Helper so we can return constants from const-generic UniFloatChoice::bounds().
 /// Not a part of public API. It's public only because of Rust requirements.
@@ -175,11 +878,16 @@ impl UniFloatChoiceToBounds for UniFloatBounds<{ UniFloatBoundsBase::BINARY }> {
             UniFloatChoice::F32 => F32_BOUNDS_BINARY,
             UniFloatChoice::F64 => F64_BOUNDS_BINARY,
             UniFloatChoice::TwoFloat => TWOFLOAT_BOUNDS_BINARY,
-            UniFloatChoice::Mpfr { bounds: MpfrBounds { precision_bits, ..}} => UniFloatBounds::<{ UniFloatBoundsBase::BINARY }> {
-                precision: precision_bits,
-                min_exponent: isize::MIN,
-                max_exponent: isize::MAX
-            }
+            UniFloatChoice::QuadFloat => QUADFLOAT_BOUNDS_BINARY,
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let MpfrBounds { precision_bits, min_exponent, max_exponent, .. } = choice.mpfr_bounds();
+                UniFloatBounds::<{ UniFloatBoundsBase::BINARY }> {
+                    precision: precision_bits,
+                    min_exponent,
+                    max_exponent
+                }
+            },
+            UniFloatChoice::Decimal { digits } => decimal_bounds_binary(digits)
         }
     }
 }
@@ -189,11 +897,9 @@ impl UniFloatChoiceToBounds for UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }>
             UniFloatChoice::F32 => F32_BOUNDS_DECIMAL,
             UniFloatChoice::F64 => F64_BOUNDS_DECIMAL,
             UniFloatChoice::TwoFloat => TWOFLOAT_BOUNDS_DECIMAL,
-            UniFloatChoice::Mpfr { bounds: MpfrBounds { precision_bits, ..}} => UniFloatBounds::<{ UniFloatBoundsBase::DECIMAL }> {
-                precision: (precision_bits as f32 * core::f32::consts::LOG10_2).floor() as usize,
-                min_exponent: (isize::MIN as f32 * core::f32::consts::LOG10_2).ceil() as isize,
-                max_exponent: (isize::MAX as f32 * core::f32::consts::LOG10_2).floor() as isize
-            }
+            UniFloatChoice::QuadFloat => QUADFLOAT_BOUNDS_DECIMAL,
+            UniFloatChoice::Decimal { digits } => decimal_bounds_decimal(digits),
+            mpfr => mpfr.bounds::<{ UniFloatBoundsBase::BINARY }>().to_decimal()
         }
     }
 }
@@ -227,13 +933,17 @@ impl UniFloatChoice {
     /// return (a copy of) self, or a new instance.
     pub const fn most_precise_for_same_space(&self) -> Self {
         match *self {
-            UniFloatChoice::Mpfr { bounds: MpfrBounds { limb_parts: limb_parts_length, .. }} =>
+            // More precision would change the grid of fixed-point formats, so they stay.
+            UniFloatChoice::Mpfr { bounds } =>
                 // Based on reverse of mfpr::MPFR_DECL_INIT
                 UniFloatChoice::Mpfr {
-                    bounds: MpfrBounds::for_precision_binary(
-                        limb_parts_length * gmp::NUMB_BITS as usize
-                    )
+                    bounds: MpfrBounds {
+                        precision_bits: bounds.limb_parts * gmp::NUMB_BITS as usize,
+                        ..bounds
+                    }
                 },
+            // Any digits take the same three words.
+            UniFloatChoice::Decimal { .. } => UniFloatChoice::Decimal { digits: MAX_DECIMAL_DIGITS },
             other => other
         }
     }
@@ -247,13 +957,102 @@ impl UniFloatChoice {
             UniFloatChoice::F32 => mem::size_of::<UniF32>(),
             UniFloatChoice::F64 => mem::size_of::<UniF64>(),
             UniFloatChoice::TwoFloat => mem::size_of::<UniTwoFloat>(),
-            UniFloatChoice::Mpfr { bounds: MpfrBounds {limb_parts: limb_parts_length, ..}} => {
+            UniFloatChoice::QuadFloat => mem::size_of::<UniQuadFloat>(),
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
                 mem::size_of::<UniMpfrLimb1Prec1>()
-                    + (limb_parts_length - 1)
+                    + (self.mpfr_bounds().limb_parts - 1)
                       * (   mem::size_of::<UniMpfrLimb2PrecAll>()
                           - mem::size_of::<UniMpfrLimb1Prec1>())
 
-            }
+            },
+            UniFloatChoice::Decimal { .. } => mem::size_of::<UniDecimalFloat>()
+        }
+    }
+
+    /// Whether UniFloat instances for `self` can hold values with all (binary) exponents
+    /// between `min_exponent` and `max_exponent`, as per `f64::MIN_EXP` and `f64::MAX_EXP`.
+    pub const fn covers_exponents(&self, min_exponent: isize, max_exponent: isize) -> bool {
+        let (mine_min, mine_max) = match *self {
+            UniFloatChoice::F32 => (F32_BOUNDS_BINARY.min_exponent, F32_BOUNDS_BINARY.max_exponent),
+            UniFloatChoice::F64 => (F64_BOUNDS_BINARY.min_exponent, F64_BOUNDS_BINARY.max_exponent),
+            UniFloatChoice::TwoFloat => (TWOFLOAT_BOUNDS_BINARY.min_exponent, TWOFLOAT_BOUNDS_BINARY.max_exponent),
+            UniFloatChoice::QuadFloat => (QUADFLOAT_BOUNDS_BINARY.min_exponent, QUADFLOAT_BOUNDS_BINARY.max_exponent),
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+                (self.mpfr_bounds().min_exponent, self.mpfr_bounds().max_exponent),
+            UniFloatChoice::Decimal { digits } =>
+                (decimal_bounds_binary(digits).min_exponent, decimal_bounds_binary(digits).max_exponent)
+        };
+        mine_min <= min_exponent && mine_max >= max_exponent
+    }
+
+    /// Whether UniFloat instances for `self` fulfill (binary) `bounds`: both the precision and the
+    /// exponent range. The same as `covers()`, but against bounds rather than another choice, and
+    /// usable at compile time (see `assert_choice_covers!`).
+    pub const fn covers_bounds(&self, bounds: &UniFloatBounds<{ UniFloatBoundsBase::BINARY }>) -> bool {
+        self.precision_bits() >= bounds.precision && self.covers_exponents(bounds.min_exponent, bounds.max_exponent)
+    }
+
+    /// Number of bits of the significand (for TwoFloat and QuadFloat: of all parts together).
+    const fn precision_bits(&self) -> usize {
+        match *self {
+            UniFloatChoice::F32 => F32_BOUNDS_BINARY.precision,
+            UniFloatChoice::F64 => F64_BOUNDS_BINARY.precision,
+            UniFloatChoice::TwoFloat => TWOFLOAT_BOUNDS_BINARY.precision,
+            UniFloatChoice::QuadFloat => QUADFLOAT_BOUNDS_BINARY.precision,
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+                self.mpfr_bounds().precision_bits,
+            UniFloatChoice::Decimal { digits } => decimal_bounds_binary(digits).precision
+        }
+    }
+
+    /// Whether UniFloat instances for `self` hold an `mpfr::mpfr_t`: for MPFR itself, and for the
+    /// formats that it emulates.
+    pub(crate) const fn in_mpfr(&self) -> bool {
+        match *self {
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => true,
+            _ => false
+        }
+    }
+
+    /// The `MpfrBounds` of MPFR, or the ones that emulate a format. For choices `in_mpfr()` only.
+    pub(crate) const fn mpfr_bounds(&self) -> MpfrBounds {
+        match *self {
+            UniFloatChoice::Mpfr { bounds } => bounds,
+            UniFloatChoice::Fixed { int_bits, frac_bits, overflow } =>
+                MpfrBounds::for_fixed(FixedFormat { int_bits, frac_bits, overflow }),
+            UniFloatChoice::Posit { nbits, es } => MpfrBounds::for_posit(PositFormat { nbits, es }),
+            _ => panic!("Only MPFR choices have MpfrBounds.")
+        }
+    }
+
+    /// The choice for results of operations that mix UniFloat instances based on `self` and
+    /// `other`. It's the more precise of the two. If either is MPFR, so is the result (because
+    /// of its exponent range), with the higher precision of the two. Promoting a value to the
+    /// result choice is exact (except for TwoFloat with a big gap between its parts, see
+    /// `UniFloat::convert()`).
+    ///
+    /// If either is decimal, so is the result (the one with more digits, if both are), as mixing
+    /// in a decimal means decimal arithmetic. Promoting a binary value to it rounds (to nearest).
+    ///
+    /// For the same choice (`a.promoted(&a)`) this returns `a` itself.
+    pub const fn promoted(&self, other: &Self) -> Self {
+        match (*self, *other) {
+            (UniFloatChoice::Decimal { digits }, UniFloatChoice::Decimal { digits: other_digits }) =>
+                return if digits >= other_digits { *self } else { *other },
+            (UniFloatChoice::Decimal { .. }, _) => return *self,
+            (_, UniFloatChoice::Decimal { .. }) => return *other,
+            _ => {}
+        }
+        match (self.in_mpfr(), other.in_mpfr()) {
+            (true, false) | (false, true) => {
+                let (mpfr, native) = if self.in_mpfr() { (*self, *other) } else { (*other, *self) };
+                if mpfr.precision_bits() >= native.precision_bits() {
+                    mpfr
+                } else {
+                    UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(native.precision_bits()) }
+                }
+            },
+            _ => if self.precision_bits() >= other.precision_bits() { *self } else { *other }
         }
     }
     
@@ -285,47 +1084,158 @@ pub const fn f64_parts_length(c: UniFloatChoice) -> usize {
 #[allow(dead_code)] // not used with f32_only feature.
 type F64Parts<const C: UniFloatChoice> = [f64; f64_parts_length(C)];
 
-/// Number of `twofloat::TwoFloat` parts in UniFloat. Either 0 or 1.
+/// Number of `twofloat::TwoFloat` parts in UniFloat: 0, 1, or 2 (for QuadFloat).
 /// Not a part of public API. It's public only because of Rust requirements.
 pub const fn twofloat_parts_length(c: UniFloatChoice) -> usize {
     match c {
         UniFloatChoice::TwoFloat => 1,
+        UniFloatChoice::QuadFloat => 2,
         _ => 0
     }
 }
+/// The element type of the TwoFloat parts, for the where-clause of generic code:
+/// `[TwoFloatPart; twofloat_parts_length(C)]: Sized`.
+pub type TwoFloatPart = TwoFloat;
 #[allow(dead_code)]
-type TwoFloatParts<const C: UniFloatChoice> = [twofloat::TwoFloat; twofloat_parts_length(C)];
+type TwoFloatParts<const C: UniFloatChoice> = [TwoFloatPart; twofloat_parts_length(C)];
 
 /// Number of `gmp::limb_t` parts in UniFloat. Either 0 or a positive number, depending on precision indicated by `c`.
 /// Not a part of public API. It's public only because of Rust requirements.
 pub const fn mpfr_limb_parts_length(c: UniFloatChoice) -> usize {
     match c {
-        UniFloatChoice::Mpfr { bounds: MpfrBounds {limb_parts: limb_parts_length, ..} } => limb_parts_length,
-        _ => 0
+        // For `Dec::to_words()`.
+        UniFloatChoice::Decimal { .. } => 3 * 64 / gmp::NUMB_BITS as usize,
+        _ => if c.in_mpfr() { c.mpfr_bounds().limb_parts } else { 0 }
     }
 }
-type MpfrLimbPart = mem::MaybeUninit<gmp::limb_t>;
+/// The element type of the limb parts, for the where-clause of generic code:
+/// `[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized`.
+pub type MpfrLimbPart = mem::MaybeUninit<gmp::limb_t>;
 #[allow(dead_code)]
 type MpfrLimbParts<const C: UniFloatChoice> = [MpfrLimbPart; mpfr_limb_parts_length(C)];
 
 /// Number of `mpfr::mpfr_t` parts in UniFloat. Either 0 or 1.
 /// Not a part of public API. It's public only because of Rust requirements.
 pub const fn mpfr_fixed_parts_length(c: UniFloatChoice) -> usize {
+    if c.in_mpfr() { 1 } else { 0 }
+}
+/// The element type of the `mpfr::mpfr_t` parts, for the where-clause of generic code:
+/// `[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized`.
+pub type MpfrFixedPart = mpfr::mpfr_t;
+#[allow(dead_code)]
+type MpfrFixedParts<const C: UniFloatChoice> = [MpfrFixedPart; mpfr_fixed_parts_length(C)];
+
+/// The `mpfr::mpfr_t` part of a `UniFloat`, with `d` pointing to its limbs, for MPFR to read
+/// while the `UniFloat` is borrowed. See `UniFloat::mpfr_src()`.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+pub(crate) struct MpfrSrc<'a> {
+    fixed: mpfr::mpfr_t,
+    limbs: core::marker::PhantomData<&'a [MpfrLimbPart]>
+}
+
+#[cfg(not(feature = "f32_only"))]
+#[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+impl MpfrSrc<'_> {
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> mpfr::mpfr_srcptr {
+        &self.fixed
+    }
+}
+
+/// Precision of the `mpfr::mpfr_t` part, in bits. For non-MPFR choices this is 1 (the minimum
+/// accepted by MPFR), but then it's not used.
+const fn mpfr_precision_bits(c: UniFloatChoice) -> mpfr::prec_t {
+    if c.in_mpfr() { c.mpfr_bounds().precision_bits as mpfr::prec_t } else { 1 }
+}
+
+/// Number of digits of decimals. For other choices this is 1, but then it's not used.
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+const fn decimal_digits(c: UniFloatChoice) -> usize {
     match c {
-        UniFloatChoice::Mpfr{ .. } => 1,
-        _ => 0
+        UniFloatChoice::Decimal { digits } => digits,
+        _ => 1
+    }
+}
+
+/// Maximum exponent of the `mpfr::mpfr_t` part: the one from `MpfrBounds::for_bounds()`, or MPFR's
+/// default. For fixed-point formats: that of their largest value, and for posits: that of `MAX`.
+const fn mpfr_max_exponent(c: UniFloatChoice) -> mpfr::exp_t {
+    match c {
+        UniFloatChoice::Fixed { int_bits, .. } => int_bits as mpfr::exp_t - 1,
+        UniFloatChoice::Posit { nbits, es } => posit_max_scale(PositFormat { nbits, es }) + 1,
+        UniFloatChoice::Mpfr { bounds } => match bounds.exponent_range() {
+            Some((_, max_exponent)) => max_exponent,
+            None => MAX_MPFR_EXP
+        },
+        _ => MAX_MPFR_EXP
+    }
+}
+
+/// Minimum exponent of the `mpfr::mpfr_t` part: the one from `MpfrBounds::for_bounds()`, or MPFR's
+/// default. For fixed-point formats: that of their step, and for posits: that of `MIN_POSITIVE`.
+const fn mpfr_min_exponent(c: UniFloatChoice) -> mpfr::exp_t {
+    match c {
+        UniFloatChoice::Fixed { frac_bits, .. } => 1 - frac_bits as mpfr::exp_t,
+        UniFloatChoice::Posit { nbits, es } => 1 - posit_max_scale(PositFormat { nbits, es }),
+        UniFloatChoice::Mpfr { bounds } => match bounds.exponent_range() {
+            Some((min_exponent, _)) => min_exponent,
+            None => MIN_MPFR_EXP
+        },
+        _ => MIN_MPFR_EXP
     }
 }
-#[allow(dead_code)]
-type MpfrFixedParts<const C: UniFloatChoice> = [mpfr::mpfr_t;mpfr_fixed_parts_length(C)];
 
+/// Number of the highest bits set in the significand of `UniFloat::MAX`: the precision, but one
+/// less for fixed-point formats, for 2^(int_bits - 1) - 2^-frac_bits, and one for posits, whose
+/// `MAX` is a power of two.
+const fn mpfr_max_bits(c: UniFloatChoice) -> usize {
+    match c {
+        UniFloatChoice::Fixed { int_bits, frac_bits, .. } => int_bits + frac_bits - 1,
+        UniFloatChoice::Posit { .. } => 1,
+        _ => mpfr_precision_bits(c) as usize
+    }
+}
+
+/// MPFR's exponent of `UniFloat::EPSILON`: that of 2^(1 - precision), of 2^-frac_bits for
+/// fixed-point formats, or of 2^(3 + es - nbits) for posits.
+const fn mpfr_epsilon_exponent(c: UniFloatChoice) -> mpfr::exp_t {
+    match c {
+        UniFloatChoice::Fixed { frac_bits, .. } => 1 - frac_bits as mpfr::exp_t,
+        UniFloatChoice::Posit { nbits, es } => 4 + es as mpfr::exp_t - nbits as mpfr::exp_t,
+        _ => 2 - mpfr_precision_bits(c) as mpfr::exp_t
+    }
+}
+
+/// The fixed-point format of `c`, if any (see `UniFloatChoice::fixed()`).
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+const fn fixed_format(c: UniFloatChoice) -> Option<FixedFormat> {
+    match c {
+        UniFloatChoice::Fixed { int_bits, frac_bits, overflow } => Some(FixedFormat { int_bits, frac_bits, overflow }),
+        _ => None
+    }
+}
+
+/// The posit format of `c`, if any (see `UniFloatChoice::posit()`).
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+const fn posit_format(c: UniFloatChoice) -> Option<PositFormat> {
+    match c {
+        UniFloatChoice::Posit { nbits, es } => Some(PositFormat { nbits, es }),
+        _ => None
+    }
+}
+
+/// A value of choice `C`. It's plain `Copy` data, and it can move freely: MPFR's limb pointer is
+/// pointed at the limbs of the value wherever it is, on each access (see `mpfr_src()` and
+/// `mpfr_mut()`), rather than being fixed once. So it needs no pinning, and there's no pinned
+/// variant: that would only restrict where values can live, and save nothing.
 #[repr(C)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy)]
 pub struct UniFloat<const C: UniFloatChoice> where
 [f32; f32_parts_length(C)]: Sized,
 [f64; f64_parts_length(C)]: Sized,
-[twofloat::TwoFloat; twofloat_parts_length(C)]: Sized,
-[mpfr::mpfr_t; mpfr_fixed_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
 [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
 {
     // When you initialize the arrays with `[item; array_length]`, `item` gets evaluated, even if
@@ -334,6 +1244,7 @@ pub struct UniFloat<const C: UniFloatChoice> where
     #[cfg(not(feature = "f32_only"))]
     f64s: F64Parts<C>,
     #[cfg(not(feature = "f32_only"))]
+    #[cfg(feature = "twofloat")]
     twofloats: TwoFloatParts<C>,
 
     #[cfg(not(feature = "f32_only"))]
@@ -341,10 +1252,6 @@ pub struct UniFloat<const C: UniFloatChoice> where
     #[cfg(not(feature = "f32_only"))]
     mpfr_limbs: MpfrLimbParts<C>,
 
-    #[cfg(debug_assertions)]
-    /// A pointer to UniFloat instance itself. Used for extra .copied() check.
-    /// Beneficial for testing the right usage of the .copied() and <<= API even without UniFloatChoice::Mpfr.
-    unifloat_self: * const UniFloat<C>,
     #[cfg(debug_assertions)]
     /// A (limited) safeguard for confirming that we've applied .mutate() on the same instance only once - until it's cleared with .copied() or <<=.
     /// Or that it's owned by OperandOwned.
@@ -362,12 +1269,37 @@ const DUMMY_MPFR_LIMB_PTR: ptr::NonNull<gmp::limb_t> = unsafe {
 /// Based on gmp_mpfr_sys::MPFR_DECL_INIT
 #[allow(dead_code)]
 const INITIAL_MPFR_EXP: mpfr::exp_t = 1-mpfr::exp_t::max_value();
+/// Exponent of MPFR zero. Based on `__MPFR_EXP_ZERO` in mpfr.h.
+#[allow(dead_code)]
+const ZERO_MPFR_EXP: mpfr::exp_t = 0-mpfr::exp_t::max_value();
+/// Exponent of MPFR NaN (the same as `INITIAL_MPFR_EXP`). Based on `__MPFR_EXP_NAN` in mpfr.h.
+#[allow(dead_code)]
+const NAN_MPFR_EXP: mpfr::exp_t = 1-mpfr::exp_t::max_value();
+/// Exponent of MPFR infinities. Based on `__MPFR_EXP_INF` in mpfr.h.
+#[allow(dead_code)]
+const INF_MPFR_EXP: mpfr::exp_t = 2-mpfr::exp_t::max_value();
+/// MPFR's default maximum exponent (`MPFR_EMAX_DEFAULT` in mpfr.h), which this crate keeps (other
+/// than for `MpfrBounds::for_bounds()`). For `UniFloat::MAX` of MPFR.
+const MAX_MPFR_EXP: mpfr::exp_t = (1 << 30) - 1;
+/// MPFR's default minimum exponent (`MPFR_EMIN_DEFAULT`). For `UniFloat::MIN_POSITIVE` of MPFR.
+const MIN_MPFR_EXP: mpfr::exp_t = 1 - (1 << 30);
+
+/// Significand of MPFR constants.
+#[derive(Clone, Copy)]
+enum MpfrSignificand {
+    /// For special values (zeros, infinities and NaN), which have no significand.
+    Unused,
+    /// For powers of two.
+    HighestBit,
+    /// All bits of the precision (and none beyond), for the largest values.
+    AllBits,
+}
 
 impl <const C: UniFloatChoice> Default for UniFloat<C> where
 [f32; f32_parts_length(C)]: Sized,
 [f64; f64_parts_length(C)]: Sized,
-[twofloat::TwoFloat; twofloat_parts_length(C)]: Sized,
-[mpfr::mpfr_t; mpfr_fixed_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
 [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
 {
     fn default() -> Self {
@@ -378,8 +1310,8 @@ impl <const C: UniFloatChoice> Default for UniFloat<C> where
 impl <const C: UniFloatChoice> UniFloat<C> where
     [f32; f32_parts_length(C)]: Sized,
     [f64; f64_parts_length(C)]: Sized,
-    [twofloat::TwoFloat; twofloat_parts_length(C)]: Sized,
-    [mpfr::mpfr_t; mpfr_fixed_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
     [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
 {
     /// Not-a-Number.
@@ -388,27 +1320,126 @@ impl <const C: UniFloatChoice> UniFloat<C> where
         #[cfg(not(feature = "f32_only"))]
         f64s: [f64::NAN; f64_parts_length(C)],
         #[cfg(not(feature = "f32_only"))]
-        twofloats: [twofloat::TwoFloat::NAN; twofloat_parts_length(C)],
+        #[cfg(feature = "twofloat")]
+        twofloats: [TwoFloat::NAN; twofloat_parts_length(C)],
 
+        // Uninitialized, but for decimals.
         #[cfg(not(feature = "f32_only"))]
-        mpfr_limbs: unsafe { core::mem::MaybeUninit::uninit().assume_init() },
+        mpfr_limbs: Self::decimal_limbs(Dec::nan(decimal_digits(C))),
 
         #[cfg(not(feature = "f32_only"))]
         mpfr_fixeds: [mpfr::mpfr_t {
-            prec: 1,
+            prec: mpfr_precision_bits(C),
             sign: 1,
             exp: INITIAL_MPFR_EXP,
             d: DUMMY_MPFR_LIMB_PTR
         }; mpfr_fixed_parts_length(C)],
 
-        #[cfg(debug_assertions)]
-        unifloat_self: ptr::null(),
         #[cfg(debug_assertions)]
         used_as_operand_mutated: false
     };
 
+    pub const INFINITY: Self = Self::exact_constant(f32::INFINITY, f64::INFINITY, 1, INF_MPFR_EXP, MpfrSignificand::Unused,
+        Dec::infinity(decimal_digits(C), false));
+    pub const NEG_INFINITY: Self = Self::exact_constant(f32::NEG_INFINITY, f64::NEG_INFINITY, -1, INF_MPFR_EXP,
+        MpfrSignificand::Unused, Dec::infinity(decimal_digits(C), true));
+    pub const ZERO: Self = Self::exact_constant(0.0, 0.0, 1, ZERO_MPFR_EXP, MpfrSignificand::Unused,
+        Dec::finite(decimal_digits(C), false, 0, 0));
+    pub const NEG_ZERO: Self = Self::exact_constant(-0.0, -0.0, -1, ZERO_MPFR_EXP, MpfrSignificand::Unused,
+        Dec::finite(decimal_digits(C), true, 0, 0));
+    pub const ONE: Self = Self::exact_constant(1.0, 1.0, 1, 1, MpfrSignificand::HighestBit, Dec::finite(decimal_digits(C), false, 1, 0));
+
+    /// The largest finite value. For TwoFloat and QuadFloat it's `f64::MAX` (with no lower parts),
+    /// since their arithmetic overflows beyond that. For MPFR it's (1 - 2^-precision) * 2^emax,
+    /// where emax is MPFR's default maximum exponent, 2^30 - 1 (which this crate doesn't change),
+    /// or the maximum exponent given to `MpfrBounds::for_bounds()`. For fixed-point formats (see
+    /// `UniFloatChoice::fixed()`) it's 2^(int_bits - 1) - 2^-frac_bits. For decimals it's `digits`
+    /// nines * 10^`DECIMAL_EXPONENT_LIMIT`.
+    pub const MAX: Self = Self::exact_constant(f32::MAX, f64::MAX, 1, mpfr_max_exponent(C), MpfrSignificand::AllBits,
+        Dec::max(decimal_digits(C), false));
+
+    /// The least finite value, `-MAX`. (For fixed-point formats that's one step above their least
+    /// value, -2^(int_bits - 1).)
+    pub const MIN: Self = Self::exact_constant(f32::MIN, f64::MIN, -1, mpfr_max_exponent(C), MpfrSignificand::AllBits,
+        Dec::max(decimal_digits(C), true));
+
+    /// The least positive normal value. For TwoFloat and QuadFloat it's `f64::MIN_POSITIVE` (their
+    /// lower parts can be less). For MPFR it's 2^(emin - 1), where emin is MPFR's default minimum
+    /// exponent, 1 - 2^30, or the minimum exponent given to `MpfrBounds::for_bounds()`. For
+    /// fixed-point formats it's their step, 2^-frac_bits. For decimals it's the least value with all
+    /// `digits` digits, 10^(`digits` - 1 - `DECIMAL_EXPONENT_LIMIT`).
+    pub const MIN_POSITIVE: Self = Self::exact_constant(f32::MIN_POSITIVE, f64::MIN_POSITIVE, 1, mpfr_min_exponent(C),
+        MpfrSignificand::HighestBit, Dec::min_positive(decimal_digits(C)));
+
+    /// The difference between 1 and the next larger value: 2^(1 - precision). For TwoFloat it's
+    /// 2^-105 (as its precision is 106 bits, see `UniFloatChoice::bounds()`), and for QuadFloat
+    /// it's 2^-211, though they can hold 1 + less than that. For fixed-point formats it's their
+    /// step, 2^-frac_bits. For decimals it's 10^(1 - `digits`).
+    pub const EPSILON: Self = Self::exact_constant_with_twofloat(f32::EPSILON, f64::EPSILON, f64::EPSILON * f64::EPSILON / 2.0,
+        f64::EPSILON * f64::EPSILON * f64::EPSILON * f64::EPSILON / 8.0,
+        1, mpfr_epsilon_exponent(C), MpfrSignificand::HighestBit, Dec::epsilon(decimal_digits(C)));
+
+    /// A constant with the same value for f32, f64, TwoFloat and QuadFloat (whose lower parts are
+    /// zero). For MPFR: `sign` and `exp` as in `mpfr::mpfr_t`. For decimals: `decimal`.
+    const fn exact_constant(f32_value: f32, f64_value: f64, sign: i32, exp: mpfr::exp_t, significand: MpfrSignificand,
+        decimal: Dec) -> Self {
+        Self::exact_constant_with_twofloat(f32_value, f64_value, f64_value, f64_value, sign, exp, significand, decimal)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(any(feature = "f32_only", not(feature = "twofloat")), allow(unused_variables, unused_mut))]
+    const fn exact_constant_with_twofloat(f32_value: f32, f64_value: f64, twofloat_value: f64, quadfloat_value: f64, sign: i32,
+        exp: mpfr::exp_t, significand: MpfrSignificand, decimal: Dec) -> Self {
+        let mut result = Self {
+            f32s: [f32_value; f32_parts_length(C)],
+            #[cfg(not(feature = "f32_only"))]
+            f64s: [f64_value; f64_parts_length(C)],
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            twofloats: [TwoFloat::from_f64(twofloat_value); twofloat_parts_length(C)],
+            ..Self::NAN
+        };
+        #[cfg(not(feature = "f32_only"))]
+        #[cfg(feature = "twofloat")]
+        if let UniFloatChoice::QuadFloat = C {
+            result.twofloats[0] = TwoFloat::from_f64(quadfloat_value);
+            result.twofloats[1] = TwoFloat::from_f64(0.0);
+        }
+        #[cfg(not(feature = "f32_only"))]
+        if let UniFloatChoice::Decimal { .. } = C {
+            result.mpfr_limbs = Self::decimal_limbs(decimal);
+        }
+        #[cfg(not(feature = "f32_only"))]
+        if C.in_mpfr() {
+            result.mpfr_fixeds[0].sign = sign;
+            result.mpfr_fixeds[0].exp = exp;
+            // Limbs are little endian: the highest bits are in the last limb. Bits below the
+            // precision (in the first limb, or in the first two for fixed-point MAX) must be zero.
+            let length = mpfr_limb_parts_length(C);
+            let all_bits: gmp::limb_t = !0;
+            let unused_bits = length * gmp::NUMB_BITS as usize - mpfr_max_bits(C);
+            let mut index = 0;
+            while index < length {
+                let limb_start = index * gmp::NUMB_BITS as usize;
+                result.mpfr_limbs[index] = mem::MaybeUninit::new(match significand {
+                    MpfrSignificand::Unused => 0,
+                    MpfrSignificand::HighestBit => if index == length - 1 { 1 << (gmp::NUMB_BITS - 1) } else { 0 },
+                    MpfrSignificand::AllBits => if limb_start >= unused_bits {
+                        all_bits
+                    } else if unused_bits - limb_start >= gmp::NUMB_BITS as usize {
+                        0
+                    } else {
+                        all_bits << (unused_bits - limb_start)
+                    }
+                });
+                index += 1;
+            }
+        }
+        result
+    }
+
     // Based on `gmp_mpfr_sys::MPFR_DECL_INIT`, but here we accept non-mutable
-    // &self, because we use this in read-only asserts, too.
+    // &self, because we use this in read-only accessors, too.
     #[cfg(not(feature = "f32_only"))]
     fn mpfr_limps_ptr(&self) -> ptr::NonNull<gmp::limb_t> {
         unsafe {
@@ -416,55 +1447,65 @@ impl <const C: UniFloatChoice> UniFloat<C> where
         }
     }
 
-    /// Assert that an instance is "copy fixed". If it has been used through `OperandMututated`, then it must have been "cleared," too.
+    /// The `mpfr::mpfr_t` part, for MPFR to read. It's a copy whose `d` points to the limbs of
+    /// `self` wherever `self` is now, so it doesn't matter whether `self` has moved since its `d`
+    /// was last set. Keep the result for as long as its pointer is used (temporaries in a call's
+    /// arguments live long enough).
+    #[cfg(not(feature = "f32_only"))]
+    #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
     #[inline]
-    fn assert_copy_fixed(&self) {
-        #[cfg(debug_assertions)] {
-            assert!(self.unifloat_self == self,
-                "Must call .copied() first, or assign with <<= instead of =. (unifloat_self hasn't been fixed.)");
-            assert!(self.used_as_operand_mutated,
-                 "Must call .copied() first, or assign with <<= instead of =. (used_as_mut_ref_operand hasn't been cleared.)" );
+    pub(crate) fn mpfr_src(&self) -> MpfrSrc<'_> {
+        #[cfg(all(feature = "softmpfr", not(feature = "mpfr")))]
+        let () = Supported::<C>::BY_SOFTMPFR;
+        MpfrSrc {
+            fixed: mpfr::mpfr_t { d: self.mpfr_limps_ptr(), ..self.mpfr_fixeds[0] },
+            limbs: core::marker::PhantomData
         }
-        #[cfg(not(feature = "f32_only"))]
-        assert!(
-            if let UniFloatChoice::Mpfr { .. } = C {
-                self.mpfr_fixeds[0].d == self.mpfr_limps_ptr()
-            } else {
-                true
-            },
-            "MPFR fields indicate that the instance was copied without having called .copied() afterwards, or it was assign to with = instead of <<=. However, unifloat_self guard didn't catch this. Please report this to UniFloat along with how to reproduce it in debug mode.");
     }
 
+    /// The `mpfr::mpfr_t` part, for MPFR to write (or read). It first points `d` to the limbs of
+    /// `self` wherever `self` is now. The pointer is valid until `self` moves.
+    #[cfg(not(feature = "f32_only"))]
+    #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
     #[inline]
-    fn assert_copy_not_fixed(&self) {
+    pub(crate) fn mpfr_mut(&mut self) -> mpfr::mpfr_ptr {
+        #[cfg(all(feature = "softmpfr", not(feature = "mpfr")))]
+        let () = Supported::<C>::BY_SOFTMPFR;
+        self.mpfr_fixeds[0].d = self.mpfr_limps_ptr();
+        self.mpfr_fixeds.as_mut_ptr()
+    }
+
+    /// Assert that an instance, if it has been used through `OperandMututated`, has been "cleared"
+    /// since (by .copied() or <<=). Nothing else needs .copied(): MPFR's limbs are addressed by
+    /// `mpfr_src()` and `mpfr_mut()`, wherever the instance is.
+    #[inline]
+    fn assert_copy_fixed(&self) {
         #[cfg(debug_assertions)]
-        assert!(self.unifloat_self != self,
-            "Have already called .copied(), or assigned with <<= instead of =. Do not call .copied() again.");
-        #[cfg(not(feature = "f32_only"))]
-        assert!(
-            if let UniFloatChoice::Mpfr { .. } = C {
-                self.mpfr_fixeds[0].d != self.mpfr_limps_ptr()
-            } else {
-                true
-            },
-            "MPFR fields indicate that the code had already called .copied(), or assigned with <<= instead of =. However, unifloat_self guard didn't catch this. Please report this to UniFloat along with how to reproduce it in debug mode.");
+        assert!(!self.used_as_operand_mutated,
+             "Must call .copied() first, or assign with <<= instead of =. (used_as_mut_ref_operand hasn't been cleared.)" );
     }
 
-    /// Call this exactly one after a UniFloat value is copied to:
-    /// Assigned to, received as a 
-    /// parameter, received from a result of a called function, copied from a
-    /// struct/tuple/array. Also if was assigned from a result of an expression.
-    /// However, do not call this if the value was assigned to with <<= 
-    /// operator.
+    /// Not needed (anymore) after a UniFloat value is copied, moved or returned: any copy is
+    /// usable as it is, as MPFR's limbs are addressed wherever the value is. It's kept, and
+    /// harmless (any number of times), so that code written for the earlier contract (which
+    /// required it after every copy) works unchanged. It still clears `.mutate()` (see
+    /// `OperandMutated`), as <<= does. So no code needs rewriting to insert it after assignments,
+    /// returns or pattern bindings, and there's no attribute macro that would: what it inserted
+    /// would do nothing.
     #[inline]
     pub fn copied(&mut self) -> &mut Self {
-        self.assert_copy_not_fixed();
+        self.refixed()
+    }
+
+    /// As `copied()`. For containers that may (or may not) move their items, like `Vec` when it
+    /// grows.
+    #[inline]
+    pub(crate) fn refixed(&mut self) -> &mut Self {
         #[cfg(not(feature = "f32_only"))]
-        if let UniFloatChoice::Mpfr { .. } = C {
+        if C.in_mpfr() {
             self.mpfr_fixeds[0].d = self.mpfr_limps_ptr();
         }
         #[cfg(debug_assertions)] {
-            self.unifloat_self = self as *const _ as *const UniFloat<C>;
             self.used_as_operand_mutated = false;
         }
         self
@@ -481,19 +1522,27 @@ impl <const C: UniFloatChoice> UniFloat<C> where
     pub fn mutate(&mut self) -> OperandMutated<C> {
         OperandMutated::new(self)
     }
+
+    /// Same as NAN, but it fails to compile if `C` can't hold values with all (binary) exponents
+    /// between `MIN_EXPONENT` and `MAX_EXPONENT` (as per `f64::MIN_EXP` and `f64::MAX_EXP`).
+    /// That catches, for example, "F32 can't hold values this large" at build time, rather than
+    /// getting infinities at runtime. Use it to initialize variables that will hold such values.
+    pub const fn nan_covering_exponents<const MIN_EXPONENT: isize, const MAX_EXPONENT: isize>() -> Self
+    where Assert<{ UniFloatChoice::covers_exponents(&C, MIN_EXPONENT, MAX_EXPONENT) }>: IsTrue
+    {
+        Self::NAN
+    }
 }
 
 impl <const C: UniFloatChoice> ops::ShlAssign for UniFloat<C> where
 [f32; f32_parts_length(C)]: Sized,
 [f64; f64_parts_length(C)]: Sized,
-[twofloat::TwoFloat; twofloat_parts_length(C)]: Sized,
-[mpfr::mpfr_t; mpfr_fixed_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
 [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
 {
     #[inline]
     fn shl_assign(&mut self, rhs: Self) {
-        // DO NOT call rhs.assert_copy_fixed() here, because it's passed by value (rather than
-        // by reference). So it should have been copy-fixed already.
         *self = rhs;
         self.copied();
     }
@@ -502,8 +1551,8 @@ impl <const C: UniFloatChoice> ops::ShlAssign for UniFloat<C> where
 impl <const C: UniFloatChoice> ops::ShlAssign<&Self> for UniFloat<C> where
 [f32; f32_parts_length(C)]: Sized,
 [f64; f64_parts_length(C)]: Sized,
-[twofloat::TwoFloat; twofloat_parts_length(C)]: Sized,
-[mpfr::mpfr_t; mpfr_fixed_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
 [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
 {
     fn shl_assign(&mut self, rhs: &Self) {
@@ -0,0 +1,954 @@
+#![cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(dead_code))]
+
+use core::cmp::Ordering;
+#[cfg(any(feature = "mpfr", feature = "twofloat"))]
+use core::mem;
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::{gmp, mpfr};
+
+use crate::decimal::{DECIMAL_EXPONENT_LIMIT, Dec};
+use crate::hex::EXPONENT_LIMIT;
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+use crate::posit::{posit_from_parts, posit_parts};
+use crate::{FixedFormat, FixedOverflow, PositFormat, Underflow};
+use crate::{MAX_MPFR_EXP, MIN_MPFR_EXP, fixed_format, mpfr_max_exponent, mpfr_min_exponent,
+    mpfr_precision_bits, posit_format, posit_max_scale};
+#[cfg(feature = "mpfr")]
+use crate::{INF_MPFR_EXP, ZERO_MPFR_EXP};
+
+/// Bits of the integers that `UniFloat::from_literal()` (and `unifloat!`) computes with. Enough
+/// for several hundred digits with the exponents of f64 (and TwoFloat and QuadFloat), or for MPFR
+/// precisions of a few thousand bits. Literals that need more fail to compile.
+pub const LITERAL_BITS: usize = 8192;
+
+const WIDE_LIMBS: usize = LITERAL_BITS / 32;
+
+/// 5^13, the greatest power of 5 in a u32.
+const FIVE_TO_13: u32 = 1_220_703_125;
+
+/// Unsigned integer of `LITERAL_BITS` bits, little endian, in 32-bit limbs. Like `Big`, but its
+/// arithmetic is `const` (so its methods take and return values, rather than `&mut`), and it
+/// keeps track of its `length`: limbs from there on are zero, and the one below isn't.
+#[derive(Clone, Copy)]
+struct Wide {
+    limbs: [u32; WIDE_LIMBS],
+    length: usize,
+}
+
+impl Wide {
+    const ZERO: Self = Self { limbs: [0; WIDE_LIMBS], length: 0 };
+    const ONE: Self = Self::from_u64(1);
+
+    const fn from_u64(value: u64) -> Self {
+        let mut result = Self::ZERO;
+        result.limbs[0] = value as u32;
+        result.limbs[1] = (value >> 32) as u32;
+        result.length = 2;
+        result.trimmed()
+    }
+
+    /// The lowest 64 bits.
+    const fn low_u64(&self) -> u64 {
+        self.limbs[0] as u64 | (self.limbs[1] as u64) << 32
+    }
+
+    /// The lowest 128 bits.
+    const fn low_u128(&self) -> u128 {
+        self.low_u64() as u128 | (self.shifted_right(64).low_u64() as u128) << 64
+    }
+
+    /// `10^exponent`.
+    const fn power_of_10(exponent: i64) -> Self {
+        Self::ONE.mul_power_of_5(exponent).shifted_left(exponent)
+    }
+
+    /// Number of decimal digits, without leading zeros. Zero for zero.
+    const fn digit_count(&self) -> i64 {
+        if self.is_zero() {
+            return 0;
+        }
+        // 10^estimate <= 2^bit_length < 10^(estimate + 1), as log10(2) to 11 decimals is off by
+        // far less than the distance of bit_length * log10(2) to an integer (for up to
+        // LITERAL_BITS bits). Then the value (at least 2^(bit_length - 1)) has estimate or
+        // estimate + 1 digits.
+        let estimate = self.bit_length() as i64 * 30_102_999_566 / 100_000_000_000;
+        if matches!(self.compare(&Self::power_of_10(estimate)), Ordering::Less) { estimate } else { estimate + 1 }
+    }
+
+    const fn trimmed(mut self) -> Self {
+        while self.length > 0 && self.limbs[self.length - 1] == 0 {
+            self.length -= 1;
+        }
+        self
+    }
+
+    const fn is_zero(&self) -> bool {
+        self.length == 0
+    }
+
+    const fn bit_length(&self) -> u32 {
+        if self.length == 0 { 0 } else { 32 * self.length as u32 - self.limbs[self.length - 1].leading_zeros() }
+    }
+
+    const fn bit(&self, index: u32) -> bool {
+        let limb = (index / 32) as usize;
+        limb < self.length && (self.limbs[limb] >> (index % 32)) & 1 == 1
+    }
+
+    /// Whether any bit below bit `end` is set.
+    const fn any_below(&self, end: u32) -> bool {
+        let (limb, offset) = ((end / 32) as usize, end % 32);
+        let mut index = 0;
+        while index < limb && index < self.length {
+            if self.limbs[index] != 0 {
+                return true;
+            }
+            index += 1;
+        }
+        limb < self.length && offset > 0 && self.limbs[limb] & ((1 << offset) - 1) != 0
+    }
+
+    const fn compare(&self, other: &Self) -> Ordering {
+        if self.length != other.length {
+            return if self.length > other.length { Ordering::Greater } else { Ordering::Less };
+        }
+        let mut index = self.length;
+        while index > 0 {
+            index -= 1;
+            if self.limbs[index] != other.limbs[index] {
+                return if self.limbs[index] > other.limbs[index] { Ordering::Greater } else { Ordering::Less };
+            }
+        }
+        Ordering::Equal
+    }
+
+    /// `self * factor + addend`.
+    const fn mul_add_small(self, factor: u32, addend: u32) -> Self {
+        let mut result = self;
+        let mut carry = addend as u64;
+        let mut index = 0;
+        while index < self.length {
+            let product = self.limbs[index] as u64 * factor as u64 + carry;
+            result.limbs[index] = product as u32;
+            carry = product >> 32;
+            index += 1;
+        }
+        if carry != 0 {
+            assert!(self.length < WIDE_LIMBS, "The literal needs more than LITERAL_BITS bits.");
+            result.limbs[self.length] = carry as u32;
+            result.length = self.length + 1;
+        }
+        result.trimmed()
+    }
+
+    /// `self * 5^count`.
+    const fn mul_power_of_5(self, count: i64) -> Self {
+        let (mut result, mut count) = (self, count);
+        while count >= 13 {
+            result = result.mul_add_small(FIVE_TO_13, 0);
+            count -= 13;
+        }
+        while count > 0 {
+            result = result.mul_add_small(5, 0);
+            count -= 1;
+        }
+        result
+    }
+
+    /// `self * factor`.
+    const fn mul_u64(self, factor: u64) -> Self {
+        self.mul_add_small(factor as u32, 0).add(&self.mul_add_small((factor >> 32) as u32, 0).shifted_left(32))
+    }
+
+    const fn add(self, other: &Self) -> Self {
+        let length = if self.length > other.length { self.length } else { other.length };
+        let mut result = self;
+        let mut carry = 0;
+        let mut index = 0;
+        while index < length {
+            let sum = self.limbs[index] as u64 + other.limbs[index] as u64 + carry;
+            result.limbs[index] = sum as u32;
+            carry = sum >> 32;
+            index += 1;
+        }
+        result.length = length;
+        if carry != 0 {
+            assert!(length < WIDE_LIMBS, "The literal needs more than LITERAL_BITS bits.");
+            result.limbs[length] = 1;
+            result.length = length + 1;
+        }
+        result
+    }
+
+    /// `self - other`, for `other` not greater than `self`.
+    const fn sub(self, other: &Self) -> Self {
+        let mut result = self;
+        let mut borrow = 0;
+        let mut index = 0;
+        while index < self.length {
+            let difference = (self.limbs[index] as u64).wrapping_sub(other.limbs[index] as u64).wrapping_sub(borrow);
+            result.limbs[index] = difference as u32;
+            borrow = difference >> 63;
+            index += 1;
+        }
+        result.trimmed()
+    }
+
+    /// `self * 2 + bit`.
+    const fn doubled_plus(self, bit: bool) -> Self {
+        let mut result = self;
+        let mut carry = bit as u32;
+        let mut index = 0;
+        while index < self.length {
+            result.limbs[index] = self.limbs[index] << 1 | carry;
+            carry = self.limbs[index] >> 31;
+            index += 1;
+        }
+        if carry != 0 {
+            assert!(self.length < WIDE_LIMBS, "The literal needs more than LITERAL_BITS bits.");
+            result.limbs[self.length] = carry;
+            result.length = self.length + 1;
+        }
+        result
+    }
+
+    const fn shifted_left(self, shift: i64) -> Self {
+        if self.length == 0 || shift == 0 {
+            return self;
+        }
+        assert!(self.bit_length() as i64 + shift <= LITERAL_BITS as i64, "The literal needs more than LITERAL_BITS bits.");
+        let (limbs, offset) = ((shift / 32) as usize, (shift % 32) as u32);
+        let mut result = Self::ZERO;
+        let mut index = 0;
+        while index < self.length {
+            let wide = (self.limbs[index] as u64) << offset;
+            result.limbs[index + limbs] |= wide as u32;
+            if index + limbs + 1 < WIDE_LIMBS {
+                result.limbs[index + limbs + 1] |= (wide >> 32) as u32;
+            }
+            index += 1;
+        }
+        let length = self.length + limbs + 1;
+        result.length = if length < WIDE_LIMBS { length } else { WIDE_LIMBS };
+        result.trimmed()
+    }
+
+    const fn shifted_right(self, shift: u32) -> Self {
+        let (limbs, offset) = ((shift / 32) as usize, shift % 32);
+        if limbs >= self.length {
+            return Self::ZERO;
+        }
+        let mut result = Self::ZERO;
+        let mut index = limbs;
+        while index < self.length {
+            let high = if index + 1 < self.length { (self.limbs[index + 1] as u64) << 32 } else { 0 };
+            result.limbs[index - limbs] = ((self.limbs[index] as u64 | high) >> offset) as u32;
+            index += 1;
+        }
+        result.length = self.length - limbs;
+        result.trimmed()
+    }
+
+    /// Quotient and remainder, bit by bit (it's only for compile time). `divisor` must not be zero.
+    const fn div_rem(self, divisor: &Self) -> (Self, Self) {
+        let (mut quotient, mut remainder) = (Self::ZERO, Self::ZERO);
+        let mut bit = self.bit_length();
+        while bit > 0 {
+            bit -= 1;
+            remainder = remainder.doubled_plus(self.bit(bit));
+            if !matches!(remainder.compare(divisor), Ordering::Less) {
+                remainder = remainder.sub(divisor);
+                quotient.limbs[(bit / 32) as usize] |= 1 << (bit % 32);
+            }
+        }
+        quotient.length = self.length;
+        (quotient.trimmed(), remainder)
+    }
+}
+
+/// A binary floating-point format to round to: `precision` bits, with the exponent of the lowest
+/// bit at least `min_exponent` (for subnormals), and the exponent of the highest bit from
+/// `min_top` (below that it's zero) to `max_top` (above that it's infinite).
+#[derive(Clone, Copy)]
+struct Format {
+    precision: u32,
+    min_exponent: i64,
+    min_top: i64,
+    max_top: i64,
+}
+
+const F32_FORMAT: Format = Format { precision: 24, min_exponent: -149, min_top: -150, max_top: 127 };
+const F64_FORMAT: Format = Format { precision: 53, min_exponent: -1074, min_top: -1075, max_top: 1023 };
+
+/// The format of MPFR choice `c` (not a fixed-point one): its precision, in its exponent range
+/// (within MPFR's default range, where operations run). With `Underflow::Subnormal` it has
+/// subnormals, and with `Underflow::FlushToZero` values below the least normal one are zero. With
+/// `Underflow::Rounded` (MPFR's own, also for its default range), `min_top` is two below that of
+/// the least normal value: values below it are surely zero, but see `mpfr_outcome()`.
+const fn mpfr_format(c: UniFloatChoice) -> Format {
+    let precision = mpfr_precision_bits(c) as u32;
+    let emin = (if mpfr_min_exponent(c) > MIN_MPFR_EXP { mpfr_min_exponent(c) } else { MIN_MPFR_EXP }) as i64;
+    let emax = (if mpfr_max_exponent(c) < MAX_MPFR_EXP { mpfr_max_exponent(c) } else { MAX_MPFR_EXP }) as i64;
+    let max_top = emax - 1;
+    match mpfr_underflow(c) {
+        Underflow::Subnormal => {
+            let min_exponent = emin - precision as i64;
+            Format { precision, min_exponent, min_top: min_exponent - 1, max_top }
+        },
+        Underflow::FlushToZero => Format { precision, min_exponent: i64::MIN / 4, min_top: emin - 1, max_top },
+        Underflow::Rounded => Format { precision, min_exponent: i64::MIN / 4, min_top: emin - 3, max_top }
+    }
+}
+
+/// How MPFR choice `c` underflows: as per its bounds, but `Underflow::Rounded` (MPFR's own) in
+/// MPFR's default range.
+const fn mpfr_underflow(c: UniFloatChoice) -> Underflow {
+    match c {
+        UniFloatChoice::Mpfr { bounds } => match bounds.exponent_range() {
+            Some(_) => bounds.underflow,
+            None => Underflow::Rounded
+        },
+        _ => panic!("Not an MPFR choice.")
+    }
+}
+
+/// `literal` (finite, or infinite for fixed-point formats) rounded for MPFR choice `c`, as
+/// `FromStr` rounds it: to the precision, and then limited to the bounds (see
+/// `limit_exponent_range()`). Returns the sign too, as wrapping fixed-point values may change it,
+/// or None for NaN (a wrapped infinity, as in MPFR).
+const fn mpfr_outcome(literal: &Literal, c: UniFloatChoice) -> Option<(bool, Outcome)> {
+    if let Some(fixed) = fixed_format(c) {
+        return fixed_outcome(literal, mpfr_precision_bits(c) as u32, fixed);
+    }
+    if let Some(posit) = posit_format(c) {
+        return Some(posit_outcome(literal, posit));
+    }
+    let format = mpfr_format(c);
+    let exact = match literal.exact_in(format) {
+        Ok(exact) => exact,
+        Err(outcome) => return Some((literal.negative, outcome))
+    };
+    let outcome = exact.rounded(format);
+    let least_top = format.min_top + 2;
+    match (outcome, mpfr_underflow(c)) {
+        // Values below the least normal one round to it or to zero, as on a grid of its step
+        // (from the exact value, as MPFR does with the ternary value).
+        (Outcome::Finite(rounded), Underflow::Rounded) if rounded.top() < least_top => {
+            let grid = Format { precision: format.precision, min_exponent: least_top, min_top: least_top, max_top: format.max_top };
+            Some((literal.negative, exact.rounded(grid)))
+        },
+        (outcome, _) => Some((literal.negative, outcome))
+    }
+}
+
+/// `literal` (finite or infinite) on the grid of fixed-point format `fixed`, with `precision`
+/// bits, and then saturated or wrapped, as by `limit_fixed()`. There is no -0.
+const fn fixed_outcome(literal: &Literal, precision: u32, fixed: FixedFormat) -> Option<(bool, Outcome)> {
+    let (int_bits, frac_bits) = (fixed.int_bits as i64, fixed.frac_bits as i64);
+    let format = Format { precision, min_exponent: -frac_bits, min_top: -frac_bits - 1, max_top: MAX_MPFR_EXP as i64 - 1 };
+    let outcome = if let Kind::Infinite = literal.kind {
+        Outcome::Infinite
+    } else {
+        match literal.exact_in(format) {
+            Ok(exact) => exact.rounded(format),
+            Err(outcome) => outcome
+        }
+    };
+    let (negative, infinite) = (literal.negative, matches!(outcome, Outcome::Infinite));
+    let top = int_bits - 1;
+    let rounded = match outcome {
+        Outcome::Zero => return Some((false, Outcome::Zero)),
+        Outcome::Infinite => Rounded { significand: Wide::ONE, exponent: MAX_MPFR_EXP as i64 },
+        Outcome::Finite(rounded) => rounded
+    };
+    // -2^top is the least value, while 2^top is beyond the largest.
+    let is_power_of_two = !rounded.significand.any_below(rounded.significand.bit_length() - 1);
+    if rounded.top() < top || (negative && rounded.top() == top && is_power_of_two) {
+        return Some((negative, Outcome::Finite(rounded)));
+    }
+    match fixed.overflow {
+        FixedOverflow::Saturating => if negative {
+            Some((true, Outcome::Finite(Rounded { significand: Wide::ONE, exponent: top })))
+        } else {
+            // That's zero for 1 integer bit and no fractional ones.
+            let significand = Wide::ONE.shifted_left(top + frac_bits).sub(&Wide::ONE);
+            Some((false, if significand.is_zero() { Outcome::Zero } else { Outcome::Finite(Rounded { significand, exponent: -frac_bits }) }))
+        },
+        FixedOverflow::Wrapping => {
+            if infinite {
+                return None;
+            }
+            // The steps modulo 2^(int_bits + frac_bits), in two's complement.
+            let bits = (int_bits + frac_bits) as u32;
+            let steps = rounded.significand.shifted_left(rounded.exponent + frac_bits);
+            let low = steps.sub(&steps.shifted_right(bits).shifted_left(bits as i64));
+            let modulus = Wide::ONE.shifted_left(bits as i64);
+            let low = if negative && !low.is_zero() { modulus.sub(&low) } else { low };
+            let (negative, magnitude) = if low.bit(bits - 1) { (true, modulus.sub(&low)) } else { (false, low) };
+            if magnitude.is_zero() {
+                Some((false, Outcome::Zero))
+            } else {
+                Some((negative, Outcome::Finite(Rounded { significand: magnitude, exponent: -frac_bits })))
+            }
+        }
+    }
+}
+
+/// `literal` (finite) rounded to posit format `posit`, as by `limit_posit()`: on the bit patterns,
+/// from the exact value, and saturated. There is no -0.
+const fn posit_outcome(literal: &Literal, posit: PositFormat) -> (bool, Outcome) {
+    // Beyond `MAX` or below `MIN_POSITIVE`, `exact_in()` gives up early, and posits saturate.
+    let max_scale = posit_max_scale(posit) as i64;
+    let format = Format { precision: 64, min_exponent: i64::MIN / 4, min_top: -max_scale - 1, max_top: max_scale };
+    let (scale, significand, sticky) = match literal.exact_in(format) {
+        Ok(exact) => exact.truncated(),
+        Err(Outcome::Zero) if literal.digits.is_zero() => return (false, Outcome::Zero),
+        Err(Outcome::Zero) => (i64::MIN / 2, 1 << 63, false),
+        Err(_) => (i64::MAX / 2, 1 << 63, false)
+    };
+    let bits = posit_from_parts(posit, literal.negative, scale, significand, sticky);
+    let (negative, scale, significand) = posit_parts(posit, bits);
+    (negative, Outcome::Finite(Rounded { significand: Wide::from_u64(significand), exponent: scale - 63 }))
+}
+
+/// A non-zero value rounded to a `Format`: `significand * 2^exponent`.
+#[derive(Clone, Copy)]
+struct Rounded {
+    significand: Wide,
+    exponent: i64,
+}
+
+impl Rounded {
+    /// Exponent of the highest bit.
+    const fn top(&self) -> i64 {
+        self.exponent + self.significand.bit_length() as i64 - 1
+    }
+}
+
+enum Outcome {
+    Zero,
+    Infinite,
+    Finite(Rounded),
+}
+
+/// An exact value: `numerator / denominator * 2^exponent`, with a sign.
+#[derive(Clone, Copy)]
+struct Exact {
+    negative: bool,
+    numerator: Wide,
+    denominator: Wide,
+    exponent: i64,
+}
+
+impl Exact {
+    /// Rounded to nearest, ties to even. It divides with two more bits than the precision, and a
+    /// sticky bit for the remainder.
+    const fn rounded(&self, format: Format) -> Outcome {
+        if self.numerator.is_zero() {
+            return Outcome::Zero;
+        }
+        let is_integer = matches!(self.denominator.compare(&Wide::ONE), Ordering::Equal);
+        let shift = format.precision as i64 + 2 + self.denominator.bit_length() as i64 - self.numerator.bit_length() as i64;
+        let shift = if shift > 0 { shift } else { 0 };
+        let (quotient, remainder) = if is_integer {
+            (self.numerator.shifted_left(shift), Wide::ZERO)
+        } else {
+            self.numerator.shifted_left(shift).div_rem(&self.denominator)
+        };
+        let exponent = self.exponent - shift;
+        let top = exponent + quotient.bit_length() as i64 - 1;
+        let lowest = top - format.precision as i64 + 1;
+        let lowest = if lowest > format.min_exponent { lowest } else { format.min_exponent };
+        let dropped = lowest - exponent;
+        if dropped > quotient.bit_length() as i64 {
+            return Outcome::Zero;
+        }
+        let dropped = dropped as u32;
+        let kept = quotient.shifted_right(dropped);
+        let sticky = !remainder.is_zero() || quotient.any_below(dropped - 1);
+        let kept = if quotient.bit(dropped - 1) && (sticky || kept.bit(0)) { kept.mul_add_small(1, 1) } else { kept };
+        // Rounding up to 2^precision leaves the lowest bit zero.
+        let rounded = if kept.bit_length() > format.precision {
+            Rounded { significand: kept.shifted_right(1), exponent: lowest + 1 }
+        } else {
+            Rounded { significand: kept, exponent: lowest }
+        };
+        if rounded.significand.is_zero() || rounded.top() < format.min_top {
+            Outcome::Zero
+        } else if rounded.top() > format.max_top {
+            Outcome::Infinite
+        } else {
+            Outcome::Finite(rounded)
+        }
+    }
+
+    /// The exponent of the highest bit of the magnitude, its highest 64 bits, and whether any bit
+    /// below them is set. For a non-zero value.
+    const fn truncated(&self) -> (i64, u64, bool) {
+        let is_integer = matches!(self.denominator.compare(&Wide::ONE), Ordering::Equal);
+        let shift = 64 + self.denominator.bit_length() as i64 - self.numerator.bit_length() as i64;
+        let shift = if shift > 0 { shift } else { 0 };
+        let (quotient, remainder) = if is_integer {
+            (self.numerator.shifted_left(shift), Wide::ZERO)
+        } else {
+            self.numerator.shifted_left(shift).div_rem(&self.denominator)
+        };
+        let dropped = quotient.bit_length() - 64;
+        let sticky = !remainder.is_zero() || quotient.any_below(dropped);
+        (self.exponent - shift + quotient.bit_length() as i64 - 1, quotient.shifted_right(dropped).low_u64(), sticky)
+    }
+
+    /// `self - rounded`, exactly, for `rounded` of the same sign (as `self.rounded()` is).
+    const fn minus(&self, rounded: &Rounded) -> Self {
+        let exponent = if self.exponent < rounded.exponent { self.exponent } else { rounded.exponent };
+        let minuend = self.numerator.shifted_left(self.exponent - exponent);
+        let subtrahend = self.denominator.mul_u64(rounded.significand.low_u64()).shifted_left(rounded.exponent - exponent);
+        let (negative, numerator) = match minuend.compare(&subtrahend) {
+            Ordering::Less => (!self.negative, subtrahend.sub(&minuend)),
+            _ => (self.negative, minuend.sub(&subtrahend))
+        };
+        Self { negative, numerator, denominator: self.denominator, exponent }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Finite,
+    Infinite,
+    Nan,
+}
+
+/// A parsed literal: `digits * 10^exp10 * 2^exp2` (decimal literals have no `exp2`, and hex ones
+/// no `exp10`), with a sign.
+#[derive(Clone, Copy)]
+struct Literal {
+    negative: bool,
+    kind: Kind,
+    digits: Wide,
+    exp10: i64,
+    exp2: i64,
+}
+
+const INF: &[u8] = b"inf";
+const INFINITY: &[u8] = b"infinity";
+const NAN: &[u8] = b"nan";
+
+/// Whether `bytes` from `start` on are `keyword` (in lowercase), ignoring ASCII case.
+const fn is_keyword(bytes: &[u8], start: usize, keyword: &[u8]) -> bool {
+    if bytes.len() - start != keyword.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < keyword.len() {
+        if bytes[start + index] | 0x20 != keyword[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+const fn digit_value(byte: u8, radix: u32) -> Option<u32> {
+    let value = match byte {
+        b'0'..=b'9' => byte - b'0',
+        b'a'..=b'f' => byte - b'a' + 10,
+        b'A'..=b'F' => byte - b'A' + 10,
+        _ => return None
+    };
+    if (value as u32) < radix { Some(value as u32) } else { None }
+}
+
+impl Literal {
+    /// `digits` (from 0 to 9 each, the most significant first) * 10^`exp10`.
+    const fn from_decimal_parts(negative: bool, digits: &[u8], exp10: i32) -> Self {
+        let mut result = Self { negative, kind: Kind::Finite, digits: Wide::ZERO, exp10: exp10 as i64, exp2: 0 };
+        let mut index = 0;
+        while index < digits.len() {
+            assert!(digits[index] < 10, "Decimal digits are from 0 to 9.");
+            result.digits = result.digits.mul_add_small(10, digits[index] as u32);
+            index += 1;
+        }
+        result
+    }
+
+    /// Parses the syntax of `FromStr` (see `parse::validate()`), or that of `from_hex_str()` (with
+    /// "0x"). Panics for anything else. Exponents saturate at `EXPONENT_LIMIT`, like those of
+    /// `from_hex_str()`.
+    const fn parse(s: &str) -> Self {
+        let bytes = s.as_bytes();
+        assert!(!bytes.is_empty(), "Empty UniFloat literal.");
+        let negative = bytes[0] == b'-';
+        let mut position = if bytes[0] == b'+' || bytes[0] == b'-' { 1 } else { 0 };
+        let mut result = Self { negative, kind: Kind::Finite, digits: Wide::ZERO, exp10: 0, exp2: 0 };
+        if is_keyword(bytes, position, INF) || is_keyword(bytes, position, INFINITY) {
+            result.kind = Kind::Infinite;
+            return result;
+        }
+        if is_keyword(bytes, position, NAN) {
+            result.kind = Kind::Nan;
+            return result;
+        }
+
+        let hex = position + 1 < bytes.len() && bytes[position] == b'0' && bytes[position + 1] | 0x20 == b'x';
+        let radix = if hex { 16 } else { 10 };
+        if hex {
+            position += 2;
+        }
+        let (mut digits, mut dot, mut fraction_digits) = (0, false, 0);
+        while position < bytes.len() {
+            if bytes[position] == b'.' && !dot {
+                dot = true;
+            } else if let Some(digit) = digit_value(bytes[position], radix) {
+                result.digits = result.digits.mul_add_small(radix, digit);
+                digits += 1;
+                if dot {
+                    fraction_digits += 1;
+                }
+            } else {
+                break;
+            }
+            position += 1;
+        }
+        assert!(digits > 0, "A UniFloat literal needs digits.");
+
+        let (mut exponent, marker) = (0, if hex { b'p' } else { b'e' });
+        if position < bytes.len() && bytes[position] | 0x20 == marker {
+            position += 1;
+            let exponent_negative = position < bytes.len() && bytes[position] == b'-';
+            if position < bytes.len() && (bytes[position] == b'+' || bytes[position] == b'-') {
+                position += 1;
+            }
+            let exponent_start = position;
+            while position < bytes.len() && bytes[position].is_ascii_digit() {
+                exponent = exponent * 10 + (bytes[position] - b'0') as i64;
+                if exponent > EXPONENT_LIMIT {
+                    exponent = EXPONENT_LIMIT;
+                }
+                position += 1;
+            }
+            assert!(position > exponent_start, "A UniFloat literal's exponent needs digits.");
+            if exponent_negative {
+                exponent = -exponent;
+            }
+        }
+        assert!(position == bytes.len(), "Invalid character in a UniFloat literal.");
+        if hex {
+            result.exp2 = exponent - 4 * fraction_digits;
+        } else {
+            result.exp10 = exponent - fraction_digits;
+        }
+        result
+    }
+
+    /// Bounds of the exponent of the highest bit of the value (which isn't zero), without
+    /// computing it.
+    const fn top_range(&self) -> (i64, i64) {
+        let top = self.digits.bit_length() as i64 - 1 + self.exp2;
+        // log2(10) to 11 decimals, off by less than 2 in all for exponents up to EXPONENT_LIMIT,
+        // and by less than 1 for rounding down (toward zero), and for the digits.
+        let scaled = (self.exp10 as i128 * 332_192_809_489 / 100_000_000_000) as i64;
+        (top + scaled - 4, top + scaled + 4)
+    }
+
+    const fn exact(&self) -> Exact {
+        let (mut numerator, mut denominator) = (self.digits, Wide::ONE);
+        // 10^exp10 = 5^exp10 * 2^exp10.
+        if self.exp10 >= 0 {
+            numerator = numerator.mul_power_of_5(self.exp10);
+        } else {
+            denominator = denominator.mul_power_of_5(-self.exp10);
+        }
+        Exact { negative: self.negative, numerator, denominator, exponent: self.exp2 + self.exp10 }
+    }
+
+    /// The exact value, or `Zero` or `Infinite` if it's far beyond the range of `format` (so that
+    /// such exponents don't need big powers of 5).
+    const fn exact_in(&self, format: Format) -> Result<Exact, Outcome> {
+        if self.digits.is_zero() {
+            return Err(Outcome::Zero);
+        }
+        let (low, high) = self.top_range();
+        if high < format.min_top {
+            Err(Outcome::Zero)
+        } else if low > format.max_top {
+            Err(Outcome::Infinite)
+        } else {
+            Ok(self.exact())
+        }
+    }
+
+    /// Rounded to nearest (ties to even) to `digits` digits, and to the exponent range, as
+    /// `Dec::parse()` does. The exponent is as written, where the digits fit.
+    const fn to_decimal(&self, digits: usize) -> Dec {
+        let limit = DECIMAL_EXPONENT_LIMIT as i64;
+        // digits * 2^-n = (digits * 5^n) * 10^-n.
+        let (value, mut exponent) = if self.exp2 >= 0 {
+            (self.digits.shifted_left(self.exp2), self.exp10)
+        } else {
+            (self.digits.mul_power_of_5(-self.exp2), self.exp10 + self.exp2)
+        };
+        let length = value.digit_count();
+        let mut dropped = length - digits as i64;
+        if -limit - exponent > dropped {
+            dropped = -limit - exponent;
+        }
+        let mut coefficient = 0;
+        if dropped <= 0 {
+            coefficient = value.low_u128();
+        } else if dropped <= length {
+            let divisor = Wide::power_of_10(dropped);
+            let (quotient, remainder) = value.div_rem(&divisor);
+            coefficient = quotient.low_u128();
+            let increment = match remainder.doubled_plus(false).compare(&divisor) {
+                Ordering::Greater => true,
+                Ordering::Equal => coefficient & 1 == 1,
+                Ordering::Less => false
+            };
+            if increment {
+                coefficient += 1;
+                if coefficient == 10u128.pow(digits as u32) {
+                    coefficient /= 10;
+                    exponent += 1;
+                }
+            }
+        }
+        // Otherwise all of it is less than half of the last digit kept.
+        if dropped > 0 {
+            exponent += dropped;
+        }
+        if coefficient == 0 && exponent > limit {
+            exponent = limit;
+        }
+        // Trailing zeros bring the exponent down, where they fit.
+        while exponent > limit && coefficient < 10u128.pow(digits as u32 - 1) {
+            coefficient *= 10;
+            exponent -= 1;
+        }
+        if exponent > limit {
+            Dec::infinity(digits, self.negative)
+        } else {
+            Dec::finite(digits, self.negative, coefficient, exponent as i32)
+        }
+    }
+
+    const fn to_f32(&self) -> f32 {
+        let magnitude = match self.exact_in(F32_FORMAT) {
+            Ok(exact) => match exact.rounded(F32_FORMAT) {
+                Outcome::Finite(rounded) => scaled_f32(rounded.significand.low_u64() as u32, rounded.exponent),
+                Outcome::Zero => 0.0,
+                Outcome::Infinite => f32::INFINITY
+            },
+            Err(Outcome::Infinite) => f32::INFINITY,
+            Err(_) => 0.0
+        };
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    /// The first `N` f64 components, each rounded to nearest from the exact difference of the
+    /// value and the components before it (as `UniFloat::from_mpfr()` and `Quad::from_mpfr()` do).
+    /// So `[f64; 1]` is the nearest f64.
+    const fn components<const N: usize>(&self) -> [f64; N] {
+        let mut result = [0.0; N];
+        let mut exact = match self.exact_in(F64_FORMAT) {
+            Ok(exact) => exact,
+            Err(outcome) => {
+                let magnitude = if let Outcome::Infinite = outcome { f64::INFINITY } else { 0.0 };
+                result[0] = if self.negative { -magnitude } else { magnitude };
+                return result;
+            }
+        };
+        let mut index = 0;
+        while index < N {
+            // The rest can be of either sign.
+            let negative = exact.negative;
+            let magnitude = match exact.rounded(F64_FORMAT) {
+                Outcome::Finite(rounded) => {
+                    exact = exact.minus(&rounded);
+                    scaled_f64(rounded.significand.low_u64(), rounded.exponent)
+                },
+                Outcome::Zero => 0.0,
+                Outcome::Infinite => f64::INFINITY
+            };
+            result[index] = if negative { -magnitude } else { magnitude };
+            if magnitude == 0.0 || magnitude == f64::INFINITY || exact.numerator.is_zero() {
+                break;
+            }
+            index += 1;
+        }
+        result
+    }
+}
+
+/// `significand * 2^exponent`, which f32 must hold exactly (as a normal or a subnormal value).
+const fn scaled_f32(significand: u32, exponent: i64) -> f32 {
+    let (mut value, mut exponent) = (significand as f32, exponent);
+    while exponent > 0 {
+        value *= 2.0;
+        exponent -= 1;
+    }
+    while exponent < 0 {
+        value *= 0.5;
+        exponent += 1;
+    }
+    value
+}
+
+/// `significand * 2^exponent`, which f64 must hold exactly (as a normal or a subnormal value).
+const fn scaled_f64(significand: u64, exponent: i64) -> f64 {
+    let (mut value, mut exponent) = (significand as f64, exponent);
+    while exponent > 0 {
+        value *= 2.0;
+        exponent -= 1;
+    }
+    while exponent < 0 {
+        value *= 0.5;
+        exponent += 1;
+    }
+    value
+}
+
+/// `high + low` (normalized, as from `Literal::components()`) as a `TwoFloat`. It's what
+/// `TwoFloat::new_add()` gives, but that isn't `const`. Like `set_quad()`, it keeps -0.
+#[cfg(feature = "twofloat")]
+const fn twofloat_pair(high: f64, low: f64) -> TwoFloat {
+    if low == 0.0 {
+        return TwoFloat::from_f64(high);
+    }
+    // TwoFloat is two f64 (transmute wouldn't compile otherwise). Which one is the high one,
+    // `from_f64()` tells.
+    let probe: [f64; 2] = unsafe { mem::transmute(TwoFloat::from_f64(1.0)) };
+    let parts = if probe[0] == 1.0 { [high, low] } else { [low, high] };
+    unsafe { mem::transmute(parts) }
+}
+
+impl <const C: UniFloatChoice> UniFloat<C> where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Parses a literal at compile time (in a `const` item, or with `unifloat!`): decimal, in the
+    /// syntax of `FromStr`, or hex, in that of `from_hex_str()`. The result is the same as theirs:
+    /// correctly rounded for all choices, and for MPFR limited to the bounds (the exponent range,
+    /// as per `Underflow`, or the fixed-point format, saturated or wrapped). Unlike them, it
+    /// raises no `Flags`.
+    ///
+    /// An invalid literal, or one that needs more than `LITERAL_BITS` bits (for very many digits,
+    /// a big decimal exponent with MPFR, or a big binary exponent with decimals), panics, which fails to compile in a const context.
+    /// Long literals (or high MPFR precisions) may also reach the compiler's limit of const
+    /// evaluation steps.
+    pub const fn from_literal(literal: &str) -> Self {
+        Self::from_literal_value(Literal::parse(literal))
+    }
+
+    /// `digits` * 10^`exp10`, negated if `negative`, at compile time (in a `const` item), so that
+    /// tables of constants need no parsing (and can be in read-only memory). `digits` are from 0
+    /// to 9 each (not ASCII), the most significant first: (false, &[1, 2, 5], -2) is 1.25. No
+    /// digits are zero (-0 if `negative`). The result is as by `from_literal()` (correctly rounded,
+    /// and limited to the bounds), with the same limits.
+    ///
+    /// Panics for a digit above 9.
+    pub const fn from_decimal_parts(negative: bool, digits: &[u8], exp10: i32) -> Self {
+        Self::from_literal_value(Literal::from_decimal_parts(negative, digits, exp10))
+    }
+
+    #[cfg_attr(feature = "f32_only", allow(unused_mut))]
+    const fn from_literal_value(literal: Literal) -> Self {
+        match literal.kind {
+            Kind::Nan => return Self::NAN,
+            // Posits have NaR instead.
+            Kind::Infinite if posit_format(C).is_some() => return Self::NAN,
+            // Fixed-point formats saturate (or wrap) infinities, too.
+            Kind::Infinite if fixed_format(C).is_none() => return if literal.negative { Self::NEG_INFINITY } else { Self::INFINITY },
+            Kind::Infinite | Kind::Finite => {}
+        }
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = literal.to_f32(),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = literal.components::<1>()[0],
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => {
+                let [high, low] = literal.components::<2>();
+                result.twofloats[0] = twofloat_pair(high, low);
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                let [c0, c1, c2, c3] = literal.components::<4>();
+                result.twofloats[0] = twofloat_pair(c0, c1);
+                result.twofloats[1] = twofloat_pair(c2, c3);
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => result.mpfr_limbs = Self::decimal_limbs(literal.to_decimal(digits)),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let (negative, outcome) = match mpfr_outcome(&literal, C) {
+                    Some(outcome) => outcome,
+                    None => return Self::NAN
+                };
+                let length = mpfr_limb_parts_length(C);
+                let mut index = 0;
+                while index < length {
+                    result.mpfr_limbs[index] = mem::MaybeUninit::new(0);
+                    index += 1;
+                }
+                result.mpfr_fixeds[0].sign = if negative { -1 } else { 1 };
+                result.mpfr_fixeds[0].exp = match outcome {
+                    Outcome::Zero => ZERO_MPFR_EXP,
+                    Outcome::Infinite => INF_MPFR_EXP,
+                    Outcome::Finite(rounded) => {
+                        // Limbs are little endian, with the highest bit at the top of the last.
+                        let total_bits = length as i64 * gmp::NUMB_BITS as i64;
+                        let aligned = rounded.significand.shifted_left(total_bits - rounded.significand.bit_length() as i64);
+                        let mut index = 0;
+                        while index < length {
+                            let limb = if gmp::NUMB_BITS == 64 {
+                                aligned.limbs[2 * index] as u64 | (aligned.limbs[2 * index + 1] as u64) << 32
+                            } else {
+                                aligned.limbs[index] as u64
+                            };
+                            result.mpfr_limbs[index] = mem::MaybeUninit::new(limb as gmp::limb_t);
+                            index += 1;
+                        }
+                        (rounded.top() + 1) as mpfr::exp_t
+                    }
+                };
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => panic!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature."),
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => panic!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => panic!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result
+    }
+}
+
+/// A `UniFloat` from a literal (see `UniFloat::from_literal()`), rounded at compile time:
+/// `unifloat!(UniFloat<{ UniFloatChoice::F64 }>, "3.14159")` evaluates in a `const` item of its
+/// own, so it's a constant even in run-time code. Without the type, `unifloat!("0x1.8p-3")` is
+/// `UniFloat::from_literal()`, for a `const` initializer whose type it infers:
+/// `const TENTH: UniFloat<{ UniFloatChoice::TwoFloat }> = unifloat!("0.1");`
+///
+/// It's `macro_rules!` (over a `const fn`) rather than a procedural macro, so that it needs no
+/// extra crate.
+#[macro_export]
+macro_rules! unifloat {
+    ($literal:literal) => {
+        $crate::UniFloat::from_literal($literal)
+    };
+    ($type:ty, $literal:literal) => {{
+        const VALUE: $type = <$type>::from_literal($literal);
+        VALUE
+    }};
+}
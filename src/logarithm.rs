@@ -0,0 +1,141 @@
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use {core::mem::MaybeUninit, crate::mpfr};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Logarithms. Accuracy:
+/// - MPFR: correctly rounded (for `log()`, see there).
+/// - f32: computed with f64, so nearly always correctly rounded.
+/// - f64: within 1 ulp (as `libm`), except for `log()`.
+/// - TwoFloat: TwoFloat's own algorithms, with relative error below 1e-26.
+///
+/// NaN, infinities and zeros are as in IEEE 754: NaN for negative `self`, -Inf for zero.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Natural logarithm.
+    pub fn ln(&self) -> Self {
+        self.unary(|x| libm::log(x as f64) as f32, libm::log,
+            twofloat_op!(|x| twofloat_log(x, libm::log, |x| x.ln())),
+            mpfr_op!(|r, x| unsafe { mpfr::log(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Base 2 logarithm. Exact for powers of two.
+    pub fn log2(&self) -> Self {
+        self.unary(|x| libm::log2(x as f64) as f32, libm::log2,
+            twofloat_op!(|x| twofloat_log(x, libm::log2, |x| x.log2())),
+            mpfr_op!(|r, x| unsafe { mpfr::log2(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Base 10 logarithm.
+    pub fn log10(&self) -> Self {
+        self.unary(|x| libm::log10(x as f64) as f32, libm::log10,
+            twofloat_op!(|x| twofloat_log(x, libm::log10, |x| x.log10())),
+            mpfr_op!(|r, x| unsafe { mpfr::log10(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// ln(1 + self), accurate for `self` near zero, too.
+    pub fn ln_1p(&self) -> Self {
+        self.unary(|x| libm::log1p(x as f64) as f32, libm::log1p,
+            twofloat_op!(|x| if x > TwoFloat::from(-1.0) && x.hi().is_finite() && x.hi() != 0.0 {
+                x.ln_1p()
+            } else {
+                TwoFloat::from(libm::log1p(x.hi()))
+            }),
+            mpfr_op!(|r, x| unsafe { mpfr::log1p(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Logarithm to base `base`: ln(self) / ln(base), but rounded once only. For MPFR that's
+    /// correctly rounded, except when the exact result is halfway between two neighbors (then
+    /// it may round either way). For f32 and f64 the quotient is computed in double-double, so
+    /// exact results (like log(1000) to base 10) come out exact (they need feature `twofloat`). TwoFloat computes the quotient
+    /// in double-double, too, with relative error below 1e-26.
+    ///
+    /// Specials follow from the quotient: NaN if either side is NaN or negative, or if `base` is 1
+    /// and `self` is 1, too; +-0 if `self` is 1; +-Inf if `base` is 1 or `self` is 0.
+    pub fn log(&self, base: &Self) -> Self {
+        self.binary(base, twofloat_op!(|x, base| log_f64(x as f64, base as f64) as f32),
+            twofloat_op!(|x, base| log_f64(x, base)),
+            twofloat_op!(|x, base| {
+                let quotient = libm::log(x.hi()) / libm::log(base.hi());
+                if is_log_regular(x.hi(), base.hi(), quotient) {
+                    twofloat_log(x, libm::log, |x| x.ln()) / twofloat_log(base, libm::log, |x| x.ln())
+                } else {
+                    TwoFloat::from(quotient)
+                }
+            }),
+            mpfr_op!(|r, x, base| unsafe { mpfr_log(r, x, base) }))
+    }
+}
+
+/// TwoFloat's `twofloat_op(x)` for finite positive `x` other than 1. Otherwise `log_f64(x)`
+/// (for NaN, infinities, zeros and 1, where f64 is exact).
+#[cfg(feature = "twofloat")]
+fn twofloat_log(x: TwoFloat, log_f64: fn(f64) -> f64, twofloat_op: impl FnOnce(TwoFloat) -> TwoFloat) -> TwoFloat {
+    if x.hi() > 0.0 && x.hi().is_finite() && x != TwoFloat::from(1.0) {
+        twofloat_op(x)
+    } else {
+        TwoFloat::from(log_f64(x.hi()))
+    }
+}
+
+/// Whether the logarithm of `x` to base `base` needs to be computed, rather than taking
+/// `quotient` of the f64 logarithms as it is (for special values, and for `x` being 1).
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+fn is_log_regular(x: f64, base: f64, quotient: f64) -> bool {
+    x.is_finite() && base.is_finite() && quotient.is_finite() && quotient != 0.0
+}
+
+#[cfg(feature = "twofloat")]
+fn log_f64(x: f64, base: f64) -> f64 {
+    let quotient = libm::log(x) / libm::log(base);
+    if is_log_regular(x, base, quotient) {
+        (TwoFloat::from(x).ln() / TwoFloat::from(base).ln()).hi()
+    } else {
+        quotient
+    }
+}
+
+/// Correctly rounded ln(x) / ln(base), by Ziv's strategy: compute it at a higher (working)
+/// precision, and increase that until the result can be rounded. If the exact result can be
+/// represented in the precision of `r` (or it's halfway between two such numbers), that never
+/// happens. So the working precision is limited, and then the result is rounded as it is. That
+/// is correct for representable results, and it may be off by 1 ulp for halfway ones.
+///
+/// The temporaries are allocated by MPFR (on the heap).
+#[cfg(feature = "mpfr")]
+unsafe fn mpfr_log(r: mpfr::mpfr_ptr, x: mpfr::mpfr_srcptr, base: mpfr::mpfr_srcptr) -> i32 {
+    let precision = mpfr::get_prec(r);
+    let mut working = precision + 32;
+    let (mut numerator, mut denominator) = (MaybeUninit::uninit(), MaybeUninit::uninit());
+    mpfr::init2(numerator.as_mut_ptr(), working);
+    mpfr::init2(denominator.as_mut_ptr(), working);
+    let (numerator, denominator) = (numerator.as_mut_ptr(), denominator.as_mut_ptr());
+    let ternary = loop {
+        mpfr::log(numerator, x, mpfr::rnd_t::RNDN);
+        mpfr::log(denominator, base, mpfr::rnd_t::RNDN);
+        mpfr::div(numerator, numerator, denominator, mpfr::rnd_t::RNDN);
+        // Specials (including ln(1) = 0) are exact.
+        if mpfr::regular_p(numerator) == 0 {
+            break mpfr::set(r, numerator, mpfr::rnd_t::RNDN);
+        }
+        // Three roundings, each with relative error up to 2^-working. That's less than
+        // 2^(2 - working) relative, hence less than 2^(exponent + 2 - working) absolute.
+        if mpfr::can_round(numerator, (working - 2) as mpfr::exp_t, mpfr::rnd_t::RNDN, mpfr::rnd_t::RNDZ, precision + 1) != 0
+            || working > 2 * precision + 256 {
+            break mpfr::set(r, numerator, mpfr::rnd_t::RNDN);
+        }
+        working += working / 2;
+        mpfr::set_prec(numerator, working);
+        mpfr::set_prec(denominator, working);
+    };
+    mpfr::clear(numerator);
+    mpfr::clear(denominator);
+    ternary
+}
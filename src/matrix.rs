@@ -0,0 +1,318 @@
+use alloc::vec::Vec;
+use core::{num::FpCategory, ops, slice};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// A vector of `UniFloat<C>`, stored contiguously on the heap (with MPFR limbs inline, as in
+/// `UniFloat`). Items can be used by reference right away (also after it grows). Items change
+/// through `set()` and the bulk operations, rather than through `IndexMut` (which would allow to
+/// assign with =).
+pub struct UniVec<const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    items: Vec<UniFloat<C>>,
+}
+
+/// A matrix of `UniFloat<C>`, stored contiguously on the heap in row-major order, and kept "copy
+/// fixed" as `UniVec`.
+pub struct UniMat<const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    rows: usize,
+    cols: usize,
+    items: Vec<UniFloat<C>>,
+}
+
+impl <const C: UniFloatChoice> UniVec<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// `len` zeros.
+    pub fn zeros(len: usize) -> Self {
+        Self { items: zeros(len) }
+    }
+
+    /// Copies of `values`.
+    pub fn from_slice(values: &[UniFloat<C>]) -> Self {
+        Self { items: fixed(values.to_vec()) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The items, ready to be used by reference.
+    pub fn as_slice(&self) -> &[UniFloat<C>] {
+        &self.items
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, UniFloat<C>> {
+        self.items.iter()
+    }
+
+    pub fn set(&mut self, index: usize, value: &UniFloat<C>) {
+        self.items[index] <<= value;
+    }
+
+    pub fn push(&mut self, value: &UniFloat<C>) {
+        let buffer = self.items.as_ptr();
+        self.items.push(*value);
+        // If the buffer has grown elsewhere, all items have moved.
+        let moved_from = if self.items.as_ptr() == buffer { self.items.len() - 1 } else { 0 };
+        refix(&mut self.items[moved_from..]);
+    }
+
+    /// `self` = `a` * `x` + `self` (as BLAS axpy), each item by `UniFloat::mul_add()`. Panics if
+    /// `x` has another length.
+    pub fn axpy(&mut self, a: &UniFloat<C>, x: &Self) {
+        assert!(x.len() == self.len(), "axpy() needs vectors of the same length.");
+        for (item, x) in self.items.iter_mut().zip(x.iter()) {
+            let next = a.mul_add(x, item);
+            *item <<= next;
+        }
+    }
+
+    /// Dot product, accumulated by `UniFloat::mul_add()`. (For a correctly rounded one, use
+    /// `UniFloat::exact_dot()` of `as_slice()`.) Panics if `other` has another length.
+    pub fn dot(&self, other: &Self) -> UniFloat<C> {
+        assert!(other.len() == self.len(), "dot() needs vectors of the same length.");
+        dot(self.iter(), other.iter())
+    }
+}
+
+impl <const C: UniFloatChoice> UniMat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// `rows` x `cols` zeros.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, items: zeros(rows * cols) }
+    }
+
+    /// Copies of `values`, row after row. Panics unless there are `rows` * `cols` of them.
+    pub fn from_row_major(rows: usize, cols: usize, values: &[UniFloat<C>]) -> Self {
+        assert!(values.len() == rows * cols, "UniMat needs rows * cols values.");
+        Self { rows, cols, items: fixed(values.to_vec()) }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The items, row after row, ready to be used by reference.
+    pub fn as_slice(&self) -> &[UniFloat<C>] {
+        &self.items
+    }
+
+    pub fn row(&self, row: usize) -> &[UniFloat<C>] {
+        assert!(row < self.rows, "UniMat has no row {}.", row);
+        &self.items[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: &UniFloat<C>) {
+        let index = self.index_of(row, col);
+        self.items[index] <<= value;
+    }
+
+    fn index_of(&self, row: usize, col: usize) -> usize {
+        assert!(row < self.rows && col < self.cols, "UniMat has no item ({}, {}).", row, col);
+        row * self.cols + col
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut items = Vec::with_capacity(self.items.len());
+        for col in 0..self.cols {
+            items.extend(self.items[col..].iter().step_by(self.cols));
+        }
+        Self { rows: self.cols, cols: self.rows, items: fixed(items) }
+    }
+
+    /// `y` = `alpha` * `self` * `x` + `beta` * `y` (as BLAS gemv). Each item of `self` * `x` is
+    /// accumulated by `UniFloat::mul_add()`, as by `UniVec::dot()`. For a zero `beta`, `y` is only
+    /// written to (so NaN in it doesn't propagate), as in BLAS. Panics if `x` isn't as long as a
+    /// row, or `y` as a column.
+    pub fn gemv(&self, alpha: &UniFloat<C>, x: &UniVec<C>, beta: &UniFloat<C>, y: &mut UniVec<C>) {
+        assert!(x.len() == self.cols && y.len() == self.rows, "gemv() needs x as long as a row, and y as long as a column.");
+        for (row, item) in y.items.iter_mut().enumerate() {
+            let mut product = dot(self.row(row).iter(), x.iter());
+            product.copied();
+            update(item, alpha, &product, beta);
+        }
+    }
+
+    /// `c` = `alpha` * `self` * `b` + `beta` * `c` (as BLAS gemm), accumulated and with zero `beta`
+    /// as by `gemv()`. Panics if `b` has other rows than `self` has columns, or `c` isn't rows of
+    /// `self` x columns of `b`.
+    pub fn gemm(&self, alpha: &UniFloat<C>, b: &Self, beta: &UniFloat<C>, c: &mut Self) {
+        assert!(b.rows == self.cols && c.rows == self.rows && c.cols == b.cols,
+            "gemm() needs b with as many rows as self has columns, and c with the rows of self and the columns of b.");
+        for row in 0..c.rows {
+            for col in 0..c.cols {
+                let mut product = dot(self.row(row).iter(), b.items[col..].iter().step_by(b.cols));
+                product.copied();
+                update(&mut c.items[row * c.cols + col], alpha, &product, beta);
+            }
+        }
+    }
+}
+
+impl <const C: UniFloatChoice> Default for UniVec<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl <const C: UniFloatChoice> Clone for UniVec<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn clone(&self) -> Self {
+        Self { items: fixed(self.items.clone()) }
+    }
+}
+
+impl <const C: UniFloatChoice> Clone for UniMat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn clone(&self) -> Self {
+        Self { rows: self.rows, cols: self.cols, items: fixed(self.items.clone()) }
+    }
+}
+
+impl <const C: UniFloatChoice> ops::Index<usize> for UniVec<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Output = UniFloat<C>;
+    fn index(&self, index: usize) -> &UniFloat<C> {
+        &self.items[index]
+    }
+}
+
+/// `matrix[(row, col)]`.
+impl <const C: UniFloatChoice> ops::Index<(usize, usize)> for UniMat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Output = UniFloat<C>;
+    fn index(&self, (row, col): (usize, usize)) -> &UniFloat<C> {
+        &self.items[self.index_of(row, col)]
+    }
+}
+
+/// Fixes `items` where they are now, whether they have moved or not.
+fn refix<const C: UniFloatChoice>(items: &mut [UniFloat<C>]) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for item in items.iter_mut() {
+        item.refixed();
+    }
+}
+
+/// `items`, fixed in their buffer (which stays in place when the `Vec` moves).
+fn fixed<const C: UniFloatChoice>(mut items: Vec<UniFloat<C>>) -> Vec<UniFloat<C>> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    refix(&mut items);
+    items
+}
+
+fn zeros<const C: UniFloatChoice>(len: usize) -> Vec<UniFloat<C>> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut items = Vec::with_capacity(len);
+    items.resize(len, UniFloat::<C>::ZERO);
+    fixed(items)
+}
+
+/// The sum of products of `x` and `y`, by `UniFloat::mul_add()`.
+fn dot<'a, const C: UniFloatChoice>(x: impl Iterator<Item = &'a UniFloat<C>>, y: impl Iterator<Item = &'a UniFloat<C>>) -> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut sum = UniFloat::<C>::ZERO;
+    sum.copied();
+    for (x, y) in x.zip(y) {
+        let next = x.mul_add(y, &sum);
+        sum <<= next;
+    }
+    sum.released()
+}
+
+/// `item` = `alpha` * `product` + `beta` * `item`, or `alpha` * `product` for a zero `beta`.
+fn update<const C: UniFloatChoice>(item: &mut UniFloat<C>, alpha: &UniFloat<C>, product: &UniFloat<C>, beta: &UniFloat<C>) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut scaled = UniFloat::<C>::ZERO;
+    scaled.copied();
+    if beta.classify() != FpCategory::Zero {
+        scaled <<= &*item;
+        scaled *= beta;
+    }
+    let next = alpha.mul_add(product, &scaled);
+    *item <<= next;
+}
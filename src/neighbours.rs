@@ -0,0 +1,467 @@
+use core::cmp::Ordering;
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use {core::mem::MaybeUninit, crate::mpfr};
+
+#[cfg(feature = "mpfr")]
+use crate::posit::{posit_bits, set_posit_bits};
+use crate::quadfloat::Quad;
+use crate::rounding::{next_down_f32, next_down_f64, next_up_f32, next_up_f64};
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+#[cfg(feature = "mpfr")]
+use crate::{PositFormat, fixed_format, posit_format};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::convert::UniMpfrTwoFloatExact;
+
+/// Neighbouring values, as IEEE 754's `nextUp` and `nextDown`, and C's `nextafter()`. NaN stays
+/// NaN, and infinities step to the largest finite values (but not beyond). Zeros of either sign
+/// step to the least subnormal (for MPFR: the least positive value in its exponent range).
+/// - TwoFloat: double-double values have no fixed grid (the lower part can hold bits far below the
+///   precision of the higher one), so the lower part steps to its neighbouring f64. For 1 that's
+///   1 + 2^-1074.
+/// - QuadFloat: likewise, the last component steps to its neighbouring f64.
+/// - Fixed-point formats (see `UniFloatChoice::fixed()`): by 2^-frac_bits, so beyond the largest
+///   value they saturate, or wrap.
+/// - Posits (see `UniFloatChoice::posit()`): to the next bit pattern, as `UniPosit` orders them, so
+///   zeros step to `MIN_POSITIVE`, and `MAX` (or `-MAX`) steps to NaN, for NaR.
+/// - Decimals (see `UniFloatChoice::decimal()`): by one unit of the last of all their digits, as
+///   IEEE 754 does, so 1 steps to 1 + 10^(1 - digits). Zeros step to 10^-`DECIMAL_EXPONENT_LIMIT`.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// The least value greater than `self`.
+    pub fn next_up(&self) -> Self {
+        #[cfg(not(feature = "f32_only"))]
+        #[cfg(feature = "twofloat")]
+        if let UniFloatChoice::QuadFloat = C {
+            return self.quad_unary(next_up_quad);
+        }
+        #[cfg(not(feature = "f32_only"))]
+        if let UniFloatChoice::Decimal { .. } = C {
+            return self.decimal_unary(|x| x.next_up());
+        }
+        self.unary(next_up_f32, next_up_f64, twofloat_op!(|x| next_up_twofloat(x)),
+            mpfr_op!(|r, x| unsafe {
+                mpfr::set(r, x, mpfr::rnd_t::RNDN);
+                next_mpfr(r, C, true);
+                0
+            }))
+    }
+
+    /// The greatest value less than `self`.
+    pub fn next_down(&self) -> Self {
+        #[cfg(not(feature = "f32_only"))]
+        #[cfg(feature = "twofloat")]
+        if let UniFloatChoice::QuadFloat = C {
+            return self.quad_unary(|x| -next_up_quad(-x));
+        }
+        #[cfg(not(feature = "f32_only"))]
+        if let UniFloatChoice::Decimal { .. } = C {
+            return self.decimal_unary(|x| x.next_down());
+        }
+        self.unary(next_down_f32, next_down_f64, twofloat_op!(|x| -next_up_twofloat(-x)),
+            mpfr_op!(|r, x| unsafe {
+                mpfr::set(r, x, mpfr::rnd_t::RNDN);
+                next_mpfr(r, C, false);
+                0
+            }))
+    }
+
+    /// The neighbour of `self` in the direction of `other`: `next_up()` or `next_down()`. NaN if
+    /// either of them is NaN, and `other` if they're equal (so `next_toward(0, -0)` is -0).
+    pub fn next_toward(&self, other: &Self) -> Self {
+        #[cfg(not(feature = "f32_only"))]
+        #[cfg(feature = "twofloat")]
+        if let UniFloatChoice::QuadFloat = C {
+            let y = other.quad();
+            return self.quad_unary(|x| next_toward(x, y, |x| x.0[0].is_nan(), next_up_quad, |x| -next_up_quad(-x)));
+        }
+        #[cfg(not(feature = "f32_only"))]
+        if let UniFloatChoice::Decimal { .. } = C {
+            let y = other.decimal();
+            return self.decimal_unary(|x| next_toward(x, y, |x| x.is_nan(), |x| x.next_up(), |x| x.next_down()));
+        }
+        self.binary(other,
+            |x, y| next_toward(x, y, f32::is_nan, next_up_f32, next_down_f32),
+            |x, y| next_toward(x, y, f64::is_nan, next_up_f64, next_down_f64),
+            twofloat_op!(|x, y| next_toward(x, y, |x| x.hi().is_nan(), next_up_twofloat, |x| -next_up_twofloat(-x))),
+            mpfr_op!(|r, x, y| unsafe {
+                // MPFR's nexttoward() keeps `r` for equal values, so that takes `y`.
+                if mpfr::equal_p(x, y) != 0 {
+                    mpfr::set(r, y, mpfr::rnd_t::RNDN);
+                } else if mpfr::unordered_p(x, y) == 0 && (fixed_format(C).is_some() || posit_format(C).is_some()) {
+                    mpfr::set(r, x, mpfr::rnd_t::RNDN);
+                    next_mpfr(r, C, mpfr::less_p(x, y) != 0);
+                } else {
+                    mpfr::set(r, x, mpfr::rnd_t::RNDN);
+                    mpfr::nexttoward(r, y);
+                }
+                0
+            }))
+    }
+}
+
+/// Units in the last place. The grid is that of the choice's precision: 24 bits for F32, 53 bits
+/// for F64, `precision_bits` for MPFR, 106 bits for TwoFloat and 212 bits for QuadFloat (with the
+/// exponent range of f64, so below 2^-969, or 2^-863, the grid is that of f64 subnormals,
+/// 2^-1074). Fixed-point formats have one step, 2^-frac_bits. Posits have the steps between their
+/// neighbouring values (see `next_up()`). Decimals have the unit of the last of all their digits,
+/// 10^(e + 1 - digits) for 10^e <= |self| < 10^(e + 1).
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// The distance between neighbouring values at the exponent of `self`: 2^(e + 1 - precision)
+    /// for 2^e <= |self| < 2^(e + 1). Always positive. The least positive value for zeros, Inf for
+    /// infinities, NaN for NaN. (For MPFR, ulps of values near the bottom of MPFR's exponent
+    /// range are less than its least value, so they underflow.)
+    pub fn ulp(&self) -> Self {
+        #[cfg(not(feature = "f32_only"))]
+        #[cfg(feature = "twofloat")]
+        if let UniFloatChoice::QuadFloat = C {
+            return self.quad_unary(|x| Quad::from_f64(ulp_quad(x)));
+        }
+        #[cfg(not(feature = "f32_only"))]
+        if let UniFloatChoice::Decimal { .. } = C {
+            return self.decimal_unary(|x| x.ulp());
+        }
+        self.unary(
+            |x| if x.is_nan() { x } else if x.is_infinite() { f32::INFINITY } else {
+                let magnitude = libm::fabsf(x);
+                if magnitude == f32::MAX { magnitude - next_down_f32(magnitude) } else { next_up_f32(magnitude) - magnitude }
+            },
+            |x| if x.is_nan() { x } else if x.is_infinite() { f64::INFINITY } else {
+                let magnitude = libm::fabs(x);
+                if magnitude == f64::MAX { magnitude - next_down_f64(magnitude) } else { next_up_f64(magnitude) - magnitude }
+            },
+            twofloat_op!(|x| TwoFloat::from(ulp_twofloat(x))),
+            mpfr_op!(|r, x| unsafe {
+                if mpfr::nan_p(x) != 0 {
+                    mpfr::set_nan(r);
+                } else if mpfr::inf_p(x) != 0 {
+                    mpfr::set_inf(r, 1);
+                } else if let Some(fixed) = fixed_format(C) {
+                    mpfr::set_ui_2exp(r, 1, -(fixed.frac_bits as mpfr::exp_t), mpfr::rnd_t::RNDN);
+                } else if let Some(posit) = posit_format(C) {
+                    return ulp_posit(posit, r, x);
+                } else if mpfr::zero_p(x) != 0 {
+                    mpfr::set_ui_2exp(r, 1, mpfr::get_emin() - 1, mpfr::rnd_t::RNDN);
+                } else {
+                    // MPFR's exponent is that of 0.5 <= |x| / 2^exponent < 1.
+                    mpfr::set_ui_2exp(r, 1, mpfr::get_exp(x) - mpfr::get_prec(x) as mpfr::exp_t, mpfr::rnd_t::RNDN);
+                }
+                0
+            }))
+    }
+
+    /// The number of steps on the grid from `self` to `other` (how many `next_up()`s, or
+    /// `next_down()`s, for F32, F64 and MPFR), for accuracy testing. -0 and +0 are the same
+    /// value. None if either side is NaN, if one side is infinite and the other isn't the same
+    /// infinity, or if the distance doesn't fit into u64. TwoFloat values off the 106-bit grid
+    /// (with bits in the lower part far below the higher one) count fractional steps, rounded
+    /// toward zero.
+    pub fn ulp_distance(&self, other: &Self) -> Option<u64> {
+        self.assert_copy_fixed();
+        other.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => {
+                let (x, y) = (self.f32s[0], other.f32s[0]);
+                if x.is_nan() || y.is_nan() || ((x.is_infinite() || y.is_infinite()) && x != y) {
+                    None
+                } else {
+                    Some(ordinal_distance(x < 0.0, (x.to_bits() & !(1 << 31)) as u64, y < 0.0, (y.to_bits() & !(1 << 31)) as u64))
+                }
+            },
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => {
+                let (x, y) = (self.f64s[0], other.f64s[0]);
+                if x.is_nan() || y.is_nan() || ((x.is_infinite() || y.is_infinite()) && x != y) {
+                    None
+                } else {
+                    Some(ordinal_distance(x < 0.0, x.to_bits() & !(1 << 63), y < 0.0, y.to_bits() & !(1 << 63)))
+                }
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => unsafe {
+                let (mut x, mut y) = (UniMpfrTwoFloatExact::NAN, UniMpfrTwoFloatExact::NAN);
+                x.copied();
+                y.copied();
+                let (x, y) = (x.mpfr_mut(), y.mpfr_mut());
+                for &(exact, value) in [(x, self.twofloats[0]), (y, other.twofloats[0])].iter() {
+                    mpfr::set_d(exact, value.hi(), mpfr::rnd_t::RNDN);
+                    if value.hi().is_finite() {
+                        mpfr::add_d(exact, exact, value.lo(), mpfr::rnd_t::RNDN);
+                    }
+                }
+                grid_distance(x, y, 2 * f64::MANTISSA_DIGITS as mpfr::prec_t, Some(f64::MIN_EXP as mpfr::exp_t - 53))
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => unsafe {
+                let (mut x, mut y) = (UniMpfrTwoFloatExact::NAN, UniMpfrTwoFloatExact::NAN);
+                x.copied();
+                y.copied();
+                let (x, y) = (x.mpfr_mut(), y.mpfr_mut());
+                self.quad().to_mpfr(x);
+                other.quad().to_mpfr(y);
+                grid_distance(x, y, 4 * f64::MANTISSA_DIGITS as mpfr::prec_t, Some(f64::MIN_EXP as mpfr::exp_t - 53))
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => self.decimal().ulp_distance(&other.decimal()),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => unsafe {
+                let source = self.mpfr_src();
+                let x = source.as_ptr();
+                if let Some(posit) = posit_format(C) {
+                    return posit_distance(posit, x, other.mpfr_src().as_ptr());
+                }
+                // Fixed-point values are all on the grid of the least one.
+                let least = fixed_format(C).map(|fixed| -(fixed.frac_bits as mpfr::exp_t));
+                grid_distance(x, other.mpfr_src().as_ptr(), mpfr::get_prec(x), least)
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => {
+                let () = Supported::<C>::TWOFLOAT_VIA_MPFR;
+                unreachable!("The ULP distance of TwoFloat is supported with mpfr feature only.")
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+}
+
+/// The distance between two values given by their signs and the bits of their magnitudes (of
+/// f32 or f64, where the bits count the values from zero up).
+fn ordinal_distance(x_negative: bool, x_magnitude: u64, y_negative: bool, y_magnitude: u64) -> u64 {
+    let ordinal = |negative, magnitude| if negative { -(magnitude as i128) } else { magnitude as i128 };
+    (ordinal(x_negative, x_magnitude) - ordinal(y_negative, y_magnitude)).unsigned_abs() as u64
+}
+
+/// 2^(e - 105) for the exponent e of `x.hi() + x.lo()`, but at least 2^-1074.
+#[cfg(feature = "twofloat")]
+fn ulp_twofloat(x: TwoFloat) -> f64 {
+    let (high, low) = (x.hi(), x.lo());
+    if high.is_nan() {
+        return high;
+    } else if high.is_infinite() {
+        return f64::INFINITY;
+    } else if high == 0.0 {
+        return f64::from_bits(1);
+    }
+    let (fraction, exponent) = libm::frexp(high);
+    // frexp() gives 0.5 <= |fraction| < 1. If |high| is a power of two and `low` goes toward
+    // zero, the sum is in the binade below.
+    let exponent = if libm::fabs(fraction) == 0.5 && low != 0.0 && (low < 0.0) != (high < 0.0) {
+        exponent - 2
+    } else {
+        exponent - 1
+    };
+    libm::ldexp(1.0, (exponent - 105).max(-1074))
+}
+
+/// As `ulp_twofloat()`, with the first non-zero lower component in place of `x.lo()`.
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+#[cfg(feature = "twofloat")]
+fn ulp_quad(x: Quad) -> f64 {
+    let high = x.0[0];
+    if !high.is_finite() || high == 0.0 {
+        return ulp_twofloat(TwoFloat::from(high));
+    }
+    let low = x.0[1..].iter().copied().find(|&component| component != 0.0).unwrap_or(0.0);
+    let (fraction, exponent) = libm::frexp(high);
+    let exponent = if libm::fabs(fraction) == 0.5 && low != 0.0 && (low < 0.0) != (high < 0.0) {
+        exponent - 2
+    } else {
+        exponent - 1
+    };
+    libm::ldexp(1.0, (exponent - 211).max(-1074))
+}
+
+/// Sets `n` to the number of values on the grid of `precision` bits from 0 up to |v| (finite), as
+/// for the bits of f64: 2^(precision - 1) values per binade. With `subnormal_exponent` q, values
+/// below 2^(q + precision - 1) are the multiples of 2^q (as IEEE subnormals). Without it (for
+/// MPFR), there are no values below MPFR's least one, 2^(emin - 1). The count is exact for values
+/// on the grid, if `n` has 64 bits more than `v` and `precision`.
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+#[cfg(feature = "mpfr")]
+unsafe fn grid_count(n: mpfr::mpfr_ptr, v: mpfr::mpfr_srcptr, precision: mpfr::prec_t, subnormal_exponent: Option<mpfr::exp_t>) {
+    if mpfr::zero_p(v) != 0 {
+        mpfr::set_zero(n, 1);
+        return;
+    }
+    // 2^exponent <= |v| < 2^(exponent + 1).
+    let exponent = mpfr::get_exp(v) - 1;
+    let precision = precision as mpfr::exp_t;
+    match subnormal_exponent {
+        Some(least) if exponent < least + precision - 1 => {
+            mpfr::abs(n, v, mpfr::rnd_t::RNDN);
+            mpfr::mul_2si(n, n, -least, mpfr::rnd_t::RNDN);
+        },
+        // (exponent - lowest binade + |v| / 2^exponent) * 2^(precision - 1), where |v| / 2^exponent
+        // is in [1, 2), and the binades start at 2^(precision - 1) values with subnormals, and at
+        // one value without them (MPFR's least one).
+        Some(least) => {
+            mpfr::abs(n, v, mpfr::rnd_t::RNDN);
+            mpfr::mul_2si(n, n, -exponent, mpfr::rnd_t::RNDN);
+            mpfr::add_si(n, n, exponent - (least + precision - 1), mpfr::rnd_t::RNDN);
+            mpfr::mul_2si(n, n, precision - 1, mpfr::rnd_t::RNDN);
+        },
+        None => {
+            mpfr::abs(n, v, mpfr::rnd_t::RNDN);
+            mpfr::mul_2si(n, n, -exponent, mpfr::rnd_t::RNDN);
+            mpfr::add_si(n, n, exponent - (mpfr::get_emin() - 1) - 1, mpfr::rnd_t::RNDN);
+            mpfr::mul_2si(n, n, precision - 1, mpfr::rnd_t::RNDN);
+            mpfr::add_ui(n, n, 1, mpfr::rnd_t::RNDN);
+        }
+    }
+}
+
+/// See `UniFloat::ulp_distance()` and `grid_count()`.
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+#[cfg(feature = "mpfr")]
+unsafe fn grid_distance(x: mpfr::mpfr_srcptr, y: mpfr::mpfr_srcptr, precision: mpfr::prec_t,
+    subnormal_exponent: Option<mpfr::exp_t>) -> Option<u64> {
+    if mpfr::nan_p(x) != 0 || mpfr::nan_p(y) != 0 {
+        return None;
+    } else if mpfr::inf_p(x) != 0 || mpfr::inf_p(y) != 0 {
+        return if mpfr::equal_p(x, y) != 0 { Some(0) } else { None };
+    }
+    let working = mpfr::get_prec(x).max(mpfr::get_prec(y)).max(precision) + 64;
+    let (mut count_x, mut count_y) = (MaybeUninit::uninit(), MaybeUninit::uninit());
+    mpfr::init2(count_x.as_mut_ptr(), working);
+    mpfr::init2(count_y.as_mut_ptr(), working);
+    let (count_x, count_y) = (count_x.as_mut_ptr(), count_y.as_mut_ptr());
+    grid_count(count_x, x, precision, subnormal_exponent);
+    grid_count(count_y, y, precision, subnormal_exponent);
+    // Zeros count as zero either way.
+    if (mpfr::signbit(x) != 0) == (mpfr::signbit(y) != 0) {
+        mpfr::sub(count_x, count_x, count_y, mpfr::rnd_t::RNDN);
+        mpfr::abs(count_x, count_x, mpfr::rnd_t::RNDN);
+    } else {
+        mpfr::add(count_x, count_x, count_y, mpfr::rnd_t::RNDN);
+    }
+    let distance = if mpfr::fits_uintmax_p(count_x, mpfr::rnd_t::RNDZ) != 0 {
+        Some(mpfr::get_uj(count_x, mpfr::rnd_t::RNDZ))
+    } else {
+        None
+    };
+    mpfr::clear(count_x);
+    mpfr::clear(count_y);
+    distance
+}
+
+/// MPFR's `nextabove()` (for `up`) or `nextbelow()`, but for fixed-point formats a step of
+/// 2^-frac_bits (exact, as `r` is on their grid), and for posits a step of their bit pattern.
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+#[cfg(feature = "mpfr")]
+unsafe fn next_mpfr(r: mpfr::mpfr_ptr, c: UniFloatChoice, up: bool) {
+    match (fixed_format(c), posit_format(c)) {
+        (Some(fixed), _) if mpfr::number_p(r) != 0 => {
+            let frac_bits = fixed.frac_bits as mpfr::exp_t;
+            mpfr::mul_2si(r, r, frac_bits, mpfr::rnd_t::RNDN);
+            mpfr::add_si(r, r, if up { 1 } else { -1 }, mpfr::rnd_t::RNDN);
+            mpfr::mul_2si(r, r, -frac_bits, mpfr::rnd_t::RNDN);
+        },
+        (_, Some(posit)) if mpfr::number_p(r) != 0 => {
+            let bits = posit_bits(posit, r).wrapping_add(if up { 1 } else { u32::MAX });
+            set_posit_bits(posit, r, bits & u32::MAX >> (32 - posit.nbits));
+        },
+        _ if up => mpfr::nextabove(r),
+        _ => mpfr::nextbelow(r)
+    }
+}
+
+/// Sets `r` to the step from |`x`| (a real posit) to its neighbour away from zero, or toward zero
+/// from `MAX`, rounded to the precision of `r`. Returns the ternary value.
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+#[cfg(feature = "mpfr")]
+unsafe fn ulp_posit(posit: PositFormat, r: mpfr::mpfr_ptr, x: mpfr::mpfr_srcptr) -> i32 {
+    let max = (1 << (posit.nbits - 1)) - 1;
+    let (mut magnitude, mut neighbour) = (MaybeUninit::uninit(), MaybeUninit::uninit());
+    mpfr::init2(magnitude.as_mut_ptr(), posit.nbits as mpfr::prec_t);
+    mpfr::init2(neighbour.as_mut_ptr(), posit.nbits as mpfr::prec_t);
+    let (magnitude, neighbour) = (magnitude.as_mut_ptr(), neighbour.as_mut_ptr());
+    mpfr::abs(magnitude, x, mpfr::rnd_t::RNDN);
+    let bits = posit_bits(posit, magnitude);
+    set_posit_bits(posit, neighbour, if bits == max { max - 1 } else { bits + 1 });
+    let ternary = if bits == max {
+        mpfr::sub(r, magnitude, neighbour, mpfr::rnd_t::RNDN)
+    } else {
+        mpfr::sub(r, neighbour, magnitude, mpfr::rnd_t::RNDN)
+    };
+    mpfr::clear(magnitude);
+    mpfr::clear(neighbour);
+    ternary
+}
+
+/// The number of posits from `x` to `y` (the distance of their bit patterns as signed integers),
+/// as `grid_distance()` counts.
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+#[cfg(feature = "mpfr")]
+unsafe fn posit_distance(posit: PositFormat, x: mpfr::mpfr_srcptr, y: mpfr::mpfr_srcptr) -> Option<u64> {
+    if mpfr::number_p(x) == 0 || mpfr::number_p(y) == 0 {
+        return if mpfr::inf_p(x) != 0 && mpfr::equal_p(x, y) != 0 { Some(0) } else { None };
+    }
+    let unused = 32 - posit.nbits;
+    let (x_bits, y_bits) = ((posit_bits(posit, x) << unused) as i32 >> unused, (posit_bits(posit, y) << unused) as i32 >> unused);
+    Some((x_bits as i64 - y_bits as i64).unsigned_abs())
+}
+
+fn next_toward<T: Copy + PartialOrd>(x: T, y: T, is_nan: impl Fn(T) -> bool, up: impl FnOnce(T) -> T,
+    down: impl FnOnce(T) -> T) -> T {
+    match x.partial_cmp(&y) {
+        Some(Ordering::Less) => up(x),
+        Some(Ordering::Greater) => down(x),
+        Some(Ordering::Equal) => y,
+        None => if is_nan(x) { x } else { y }
+    }
+}
+
+/// The lower part steps up to its neighbouring f64. `new_add()` renormalizes exactly (by TwoSum),
+/// so the result is greater than `x`.
+#[cfg(feature = "twofloat")]
+fn next_up_twofloat(x: TwoFloat) -> TwoFloat {
+    let (high, low) = (x.hi(), x.lo());
+    if !high.is_finite() {
+        TwoFloat::from(next_up_f64(high))
+    } else {
+        TwoFloat::new_add(high, next_up_f64(low))
+    }
+}
+
+/// As `next_up_twofloat()`: the last component steps up to its neighbouring f64.
+#[cfg_attr(any(feature = "f32_only", all(not(feature = "mpfr"), not(feature = "twofloat"))), allow(dead_code))]
+fn next_up_quad(x: Quad) -> Quad {
+    if !x.0[0].is_finite() || x.0[0] == 0.0 {
+        return Quad::from_f64(next_up_f64(x.0[0]));
+    }
+    let mut result = x;
+    result.0[3] = next_up_f64(x.0[3]);
+    result
+}
@@ -0,0 +1,728 @@
+use core::{cmp::Ordering, fmt, num::FpCategory, ops};
+use num_traits::{Float, FromPrimitive, Num, NumCast, One, Signed, ToPrimitive, Zero, float::FloatCore};
+
+use crate::{Conversion, ParseUniFloatError, RoundingMode, UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// A `UniFloat<C>` for generic code written against `num_traits` (like `num_traits::Float`), with
+/// optional feature `num-traits`. Such code passes values around by value and compares them by
+/// reference, without ever calling .copied(). That's fine, as `UniFloat` doesn't need it either.
+///
+/// Arithmetic, comparisons and functions are those of `UniFloat`, so they round (and raise
+/// `Flags`) the same way. Conversions from primitives (`NumCast` and `FromPrimitive`) are exact
+/// for integers and f32 (unless the choice is narrower), and give None for finite values beyond
+/// `UniFloat::MAX` or `UniFloat::MIN` (rather than Inf, or a saturated or wrapped fixed-point
+/// value). Conversions to primitives (`ToPrimitive`) truncate toward zero for integers, and round
+/// to nearest for floats, and give None for values out of the primitive's range (NaN converts to
+/// NaN floats, but no integer).
+/// `integer_decode()` decodes the value rounded to f64, for all choices.
+#[derive(Clone, Copy)]
+pub struct UniNum<const C: UniFloatChoice>(UniFloat<C>) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized;
+
+impl <const C: UniFloatChoice> UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub fn new(value: &UniFloat<C>) -> Self {
+        Self(*value)
+    }
+
+    /// The value.
+    pub fn get(self) -> UniFloat<C> {
+        self.0
+    }
+
+    /// The value, passed to `op` by reference.
+    #[inline]
+    fn with<T>(self, op: impl FnOnce(&UniFloat<C>) -> T) -> T {
+        op(&self.0)
+    }
+
+    #[inline]
+    fn map(self, op: impl FnOnce(&UniFloat<C>) -> UniFloat<C>) -> Self {
+        Self(self.with(op))
+    }
+
+    #[inline]
+    fn map2(self, other: Self, op: impl FnOnce(&UniFloat<C>, &UniFloat<C>) -> UniFloat<C>) -> Self {
+        Self(self.with(|x| other.with(|y| op(x, y))))
+    }
+
+    /// `value` rounded to nearest (for integers that's exact where `C` holds them), or None if
+    /// `in_range` says it's beyond `UniFloat::MAX` or `UniFloat::MIN`, given them fixed.
+    fn from_value(value: impl Into<UniFloat<C>>, in_range: impl FnOnce(&UniFloat<C>, &UniFloat<C>) -> bool) -> Option<Self> {
+        let (mut max, mut min) = (UniFloat::<C>::MAX, UniFloat::<C>::MIN);
+        max.copied();
+        min.copied();
+        if in_range(&max, &min) { Some(Self(value.into())) } else { None }
+    }
+
+    /// `n`, or None if it's beyond `MAX` or `MIN` (which are exact as integers where they fit).
+    fn from_signed(n: i128) -> Option<Self> {
+        Self::from_value(n, |max, min| {
+            // A limit beyond i128 is no limit.
+            max.to_i128_round(RoundingMode::Down).map_or(true, |max| n <= max)
+                && min.to_i128_round(RoundingMode::Up).map_or(true, |min| n >= min)
+        })
+    }
+
+    /// `n`, or None if it's beyond `MAX`. (`MIN` is negative.)
+    fn from_unsigned(n: u128) -> Option<Self> {
+        Self::from_value(n, |max, _| max.to_u128_round(RoundingMode::Down).map_or(true, |max| n <= max))
+    }
+
+    /// `n` rounded to nearest, or None if it's finite, but beyond `MAX` or `MIN`. Infinities and
+    /// NaN convert as they are.
+    fn from_float(n: f64) -> Option<Self> {
+        Self::from_value(n, |max, min| {
+            !n.is_finite() || (n <= max.to_f64(RoundingMode::Up).value() && n >= min.to_f64(RoundingMode::Down).value())
+        })
+    }
+}
+
+// `UniFloat` holds raw pointers (for MPFR, to its own limbs), so it's neither `Send` nor `Sync`.
+// `UniNum` never reads through them: every operation reads from a local copy, which it fixes
+// first. So it can be moved to, and shared with, other threads (as `num_traits` users, like
+// nalgebra, expect).
+unsafe impl <const C: UniFloatChoice> Send for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{}
+
+unsafe impl <const C: UniFloatChoice> Sync for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{}
+
+impl <const C: UniFloatChoice> PartialEq for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.with(|x| other.with(|y| x == y))
+    }
+}
+
+impl <const C: UniFloatChoice> PartialOrd for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.with(|x| other.with(|y| x.partial_cmp(y)))
+    }
+}
+
+impl <const C: UniFloatChoice> fmt::Debug for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.with(|x| fmt::Debug::fmt(x, f))
+    }
+}
+
+impl <const C: UniFloatChoice> fmt::Display for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.with(|x| fmt::Display::fmt(x, f))
+    }
+}
+
+impl <const C: UniFloatChoice> ops::Neg for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Output = Self;
+    fn neg(self) -> Self {
+        // This only flips the sign, so it doesn't need `self` to be fixed.
+        Self(-self.0)
+    }
+}
+
+// Binary operators (+, -, *, /, %) and their modify-and-assign counterparts, by value, as
+// `num_traits::NumOps` and `num_traits::NumAssignOps` need them.
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, |$x:ident, $y:ident| $op:expr) => {
+        impl <const C: UniFloatChoice> ops::$trait for UniNum<C> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        {
+            type Output = Self;
+            fn $method(self, other: Self) -> Self {
+                self.map2(other, |$x, $y| $op)
+            }
+        }
+
+        impl <const C: UniFloatChoice> ops::$assign_trait for UniNum<C> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        {
+            fn $assign_method(&mut self, other: Self) {
+                *self = ops::$trait::$method(*self, other);
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, AddAssign, add_assign, |x, y| assigned(x, y, |r, y| *r += y));
+impl_binary_op!(Sub, sub, SubAssign, sub_assign, |x, y| assigned(x, y, |r, y| *r -= y));
+impl_binary_op!(Mul, mul, MulAssign, mul_assign, |x, y| assigned(x, y, |r, y| *r *= y));
+impl_binary_op!(Div, div, DivAssign, div_assign, |x, y| assigned(x, y, |r, y| *r /= y));
+impl_binary_op!(Rem, rem, RemAssign, rem_assign, |x, y| x.fmod(y));
+
+/// `x` op `y`, by `assign` (one of the modify-and-assign operators of `UniFloat`).
+fn assigned<const C: UniFloatChoice>(x: &UniFloat<C>, y: &UniFloat<C>, assign: impl FnOnce(&mut UniFloat<C>, &UniFloat<C>)) -> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut result = *x;
+    result.copied();
+    assign(&mut result, y);
+    result.released()
+}
+
+impl <const C: UniFloatChoice> Zero for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn zero() -> Self {
+        Self(UniFloat::ZERO)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.with(|x| x.classify() == FpCategory::Zero)
+    }
+}
+
+impl <const C: UniFloatChoice> One for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn one() -> Self {
+        Self(UniFloat::ONE)
+    }
+}
+
+/// By `UniFloat::from_str_radix()`, so it panics if `radix` is not between 2 and 62.
+impl <const C: UniFloatChoice> Num for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type FromStrRadixErr = ParseUniFloatError;
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseUniFloatError> {
+        UniFloat::from_str_radix(s, radix).map(Self)
+    }
+}
+
+/// As `Signed` for f32 and f64: `is_positive()` and `is_negative()` go by the sign bit, so they
+/// hold for zeros (and NaN) of that sign.
+impl <const C: UniFloatChoice> Signed for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn abs(&self) -> Self {
+        Float::abs(*self)
+    }
+
+    /// max(`self` - `other`, 0).
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self <= other { Self::zero() } else { *self - *other }
+    }
+
+    fn signum(&self) -> Self {
+        Float::signum(*self)
+    }
+
+    fn is_positive(&self) -> bool {
+        Float::is_sign_positive(*self)
+    }
+
+    fn is_negative(&self) -> bool {
+        Float::is_sign_negative(*self)
+    }
+}
+
+macro_rules! to_integer {
+    ($($method:ident => $round:ident -> $integer:ty),+) => {
+        $(
+            fn $method(&self) -> Option<$integer> {
+                self.with(|x| x.$round(RoundingMode::TowardZero).ok())
+            }
+        )+
+    };
+}
+
+impl <const C: UniFloatChoice> ToPrimitive for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    to_integer!(to_i64 => to_i64_round -> i64, to_i128 => to_i128_round -> i128,
+        to_u64 => to_u64_round -> u64, to_u128 => to_u128_round -> u128);
+
+    /// None if the value is finite, but beyond `f32::MAX` (after rounding).
+    fn to_f32(&self) -> Option<f32> {
+        self.with(|x| match x.to_f32(RoundingMode::Nearest) {
+            Conversion::Overflow(_) => None,
+            conversion => Some(conversion.value())
+        })
+    }
+
+    /// None if the value is finite, but beyond `f64::MAX` (after rounding).
+    fn to_f64(&self) -> Option<f64> {
+        self.with(|x| match x.to_f64(RoundingMode::Nearest) {
+            Conversion::Overflow(_) => None,
+            conversion => Some(conversion.value())
+        })
+    }
+}
+
+impl <const C: UniFloatChoice> FromPrimitive for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn from_i64(n: i64) -> Option<Self> {
+        Self::from_signed(n.into())
+    }
+
+    fn from_i128(n: i128) -> Option<Self> {
+        Self::from_signed(n)
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Self::from_unsigned(n.into())
+    }
+
+    fn from_u128(n: u128) -> Option<Self> {
+        Self::from_unsigned(n)
+    }
+
+    fn from_f32(n: f32) -> Option<Self> {
+        Self::from_float(n.into())
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        Self::from_float(n)
+    }
+}
+
+/// Integers go through i128 or u128 where they fit (so they're exact where `C` holds them), and
+/// other values through f64.
+impl <const C: UniFloatChoice> NumCast for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+        let float = n.to_f64();
+        // Floats convert to integers by truncation, so only those that round back are integers.
+        match (n.to_i128(), n.to_u128()) {
+            (Some(integer), _) if Some(integer as f64) == float => Self::from_signed(integer),
+            (_, Some(integer)) if Some(integer as f64) == float => Self::from_unsigned(integer),
+            _ => float.and_then(Self::from_float)
+        }
+    }
+}
+
+/// NaN, infinities and zeros are as for `UniFloat`, and so are the constants: `min_value()` is
+/// `UniFloat::MIN`, `max_value()` is `UniFloat::MAX`, `min_positive_value()` is
+/// `UniFloat::MIN_POSITIVE` and `epsilon()` is `UniFloat::EPSILON`.
+impl <const C: UniFloatChoice> Float for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn nan() -> Self {
+        Self(UniFloat::NAN)
+    }
+
+    fn infinity() -> Self {
+        Self(UniFloat::INFINITY)
+    }
+
+    fn neg_infinity() -> Self {
+        Self(UniFloat::NEG_INFINITY)
+    }
+
+    fn neg_zero() -> Self {
+        Self(UniFloat::NEG_ZERO)
+    }
+
+    fn min_value() -> Self {
+        Self(UniFloat::MIN)
+    }
+
+    fn min_positive_value() -> Self {
+        Self(UniFloat::MIN_POSITIVE)
+    }
+
+    fn epsilon() -> Self {
+        Self(UniFloat::EPSILON)
+    }
+
+    fn max_value() -> Self {
+        Self(UniFloat::MAX)
+    }
+
+    fn is_nan(self) -> bool {
+        self.with(UniFloat::is_nan)
+    }
+
+    fn is_infinite(self) -> bool {
+        self.with(UniFloat::is_infinite)
+    }
+
+    fn is_finite(self) -> bool {
+        self.with(UniFloat::is_finite)
+    }
+
+    fn is_normal(self) -> bool {
+        self.with(UniFloat::is_normal)
+    }
+
+    fn classify(self) -> FpCategory {
+        self.with(UniFloat::classify)
+    }
+
+    fn floor(self) -> Self {
+        self.map(UniFloat::floor)
+    }
+
+    fn ceil(self) -> Self {
+        self.map(UniFloat::ceil)
+    }
+
+    fn round(self) -> Self {
+        self.map(UniFloat::round)
+    }
+
+    fn trunc(self) -> Self {
+        self.map(UniFloat::trunc)
+    }
+
+    fn fract(self) -> Self {
+        self.map(UniFloat::fract)
+    }
+
+    fn abs(self) -> Self {
+        self.map(UniFloat::abs)
+    }
+
+    fn signum(self) -> Self {
+        self.map(UniFloat::signum)
+    }
+
+    fn is_sign_positive(self) -> bool {
+        self.with(UniFloat::is_sign_positive)
+    }
+
+    fn is_sign_negative(self) -> bool {
+        self.with(UniFloat::is_sign_negative)
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        Self(self.with(|x| a.with(|a| b.with(|b| x.mul_add(a, b)))))
+    }
+
+    fn recip(self) -> Self {
+        Self::one() / self
+    }
+
+    fn powi(self, n: i32) -> Self {
+        self.map(|x| x.powi(i64::from(n)))
+    }
+
+    fn powf(self, n: Self) -> Self {
+        self.map2(n, UniFloat::pow)
+    }
+
+    fn sqrt(self) -> Self {
+        self.map(UniFloat::sqrt)
+    }
+
+    fn exp(self) -> Self {
+        self.map(UniFloat::exp)
+    }
+
+    fn exp2(self) -> Self {
+        self.map(UniFloat::exp2)
+    }
+
+    fn ln(self) -> Self {
+        self.map(UniFloat::ln)
+    }
+
+    fn log(self, base: Self) -> Self {
+        self.map2(base, UniFloat::log)
+    }
+
+    fn log2(self) -> Self {
+        self.map(UniFloat::log2)
+    }
+
+    fn log10(self) -> Self {
+        self.map(UniFloat::log10)
+    }
+
+    fn max(self, other: Self) -> Self {
+        self.map2(other, UniFloat::max)
+    }
+
+    fn min(self, other: Self) -> Self {
+        self.map2(other, UniFloat::min)
+    }
+
+    /// max(`self` - `other`, 0), as `f64::abs_sub()` was.
+    fn abs_sub(self, other: Self) -> Self {
+        if self <= other { Self::zero() } else { self - other }
+    }
+
+    fn cbrt(self) -> Self {
+        self.map(UniFloat::cbrt)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        self.map2(other, UniFloat::hypot)
+    }
+
+    fn sin(self) -> Self {
+        self.map(UniFloat::sin)
+    }
+
+    fn cos(self) -> Self {
+        self.map(UniFloat::cos)
+    }
+
+    fn tan(self) -> Self {
+        self.map(UniFloat::tan)
+    }
+
+    fn asin(self) -> Self {
+        self.map(UniFloat::asin)
+    }
+
+    fn acos(self) -> Self {
+        self.map(UniFloat::acos)
+    }
+
+    fn atan(self) -> Self {
+        self.map(UniFloat::atan)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        self.map2(other, UniFloat::atan2)
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+
+    fn exp_m1(self) -> Self {
+        self.map(UniFloat::exp_m1)
+    }
+
+    fn ln_1p(self) -> Self {
+        self.map(UniFloat::ln_1p)
+    }
+
+    fn sinh(self) -> Self {
+        self.map(UniFloat::sinh)
+    }
+
+    fn cosh(self) -> Self {
+        self.map(UniFloat::cosh)
+    }
+
+    fn tanh(self) -> Self {
+        self.map(UniFloat::tanh)
+    }
+
+    fn asinh(self) -> Self {
+        self.map(UniFloat::asinh)
+    }
+
+    fn acosh(self) -> Self {
+        self.map(UniFloat::acosh)
+    }
+
+    fn atanh(self) -> Self {
+        self.map(UniFloat::atanh)
+    }
+
+    fn integer_decode(self) -> (u64, i16, i8) {
+        FloatCore::integer_decode(self.with(|x| x.to_f64(RoundingMode::Nearest).value()))
+    }
+
+    fn copysign(self, sign: Self) -> Self {
+        self.map2(sign, UniFloat::copysign)
+    }
+}
+
+/// As `Float`.
+impl <const C: UniFloatChoice> FloatCore for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn infinity() -> Self {
+        Float::infinity()
+    }
+
+    fn neg_infinity() -> Self {
+        Float::neg_infinity()
+    }
+
+    fn nan() -> Self {
+        Float::nan()
+    }
+
+    fn neg_zero() -> Self {
+        Float::neg_zero()
+    }
+
+    fn min_value() -> Self {
+        Float::min_value()
+    }
+
+    fn min_positive_value() -> Self {
+        Float::min_positive_value()
+    }
+
+    fn epsilon() -> Self {
+        Float::epsilon()
+    }
+
+    fn max_value() -> Self {
+        Float::max_value()
+    }
+
+    fn classify(self) -> FpCategory {
+        Float::classify(self)
+    }
+
+    fn to_degrees(self) -> Self {
+        Float::to_degrees(self)
+    }
+
+    fn to_radians(self) -> Self {
+        Float::to_radians(self)
+    }
+
+    fn integer_decode(self) -> (u64, i16, i8) {
+        Float::integer_decode(self)
+    }
+
+    fn floor(self) -> Self {
+        Float::floor(self)
+    }
+
+    fn ceil(self) -> Self {
+        Float::ceil(self)
+    }
+
+    fn round(self) -> Self {
+        Float::round(self)
+    }
+
+    fn trunc(self) -> Self {
+        Float::trunc(self)
+    }
+
+    fn fract(self) -> Self {
+        Float::fract(self)
+    }
+
+    fn abs(self) -> Self {
+        Float::abs(self)
+    }
+
+    fn signum(self) -> Self {
+        Float::signum(self)
+    }
+
+    fn is_sign_positive(self) -> bool {
+        Float::is_sign_positive(self)
+    }
+
+    fn is_sign_negative(self) -> bool {
+        Float::is_sign_negative(self)
+    }
+
+    fn min(self, other: Self) -> Self {
+        Float::min(self, other)
+    }
+
+    fn max(self, other: Self) -> Self {
+        Float::max(self, other)
+    }
+
+    fn recip(self) -> Self {
+        Float::recip(self)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        Float::powi(self, n)
+    }
+}
@@ -1,15 +1,13 @@
-use gmp_mpfr_sys::mpfr;
-
 use core::ops;
-use crate::{UniFloatChoice, UniFloat, MpfrLimbPart, MpfrBounds, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, MpfrBounds, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
 
 /// Not a part of public API. Used only for intermediate results.
 #[derive(Clone, Copy, Debug)]
 pub struct OperandOwned<const C: UniFloatChoice> where
 [f32; f32_parts_length(C)]: Sized,
 [f64; f64_parts_length(C)]: Sized,
-[twofloat::TwoFloat; twofloat_parts_length(C)]: Sized,
-[mpfr::mpfr_t; mpfr_fixed_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
 [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
 {
     float: UniFloat<C>
@@ -18,8 +16,8 @@ pub struct OperandOwned<const C: UniFloatChoice> where
 impl <'a, const C: UniFloatChoice> OperandOwned<C> where
 [f32; f32_parts_length(C)]: Sized,
 [f64; f64_parts_length(C)]: Sized,
-[twofloat::TwoFloat; twofloat_parts_length(C)]: Sized,
-[mpfr::mpfr_t; mpfr_fixed_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
 [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
 {
     pub(crate) fn new(float: &UniFloat<C>) -> Self {
@@ -37,8 +35,8 @@ impl <'a, const C: UniFloatChoice> OperandOwned<C> where
 pub struct OperandMutated<'a, const C: UniFloatChoice> where
 [f32; f32_parts_length(C)]: Sized,
 [f64; f64_parts_length(C)]: Sized,
-[twofloat::TwoFloat; twofloat_parts_length(C)]: Sized,
-[mpfr::mpfr_t; mpfr_fixed_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
 [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
 {
     float: &'a mut UniFloat<C>
@@ -47,8 +45,8 @@ pub struct OperandMutated<'a, const C: UniFloatChoice> where
 impl <'a, const C: UniFloatChoice> OperandMutated<'a, C> where
 [f32; f32_parts_length(C)]: Sized,
 [f64; f64_parts_length(C)]: Sized,
-[twofloat::TwoFloat; twofloat_parts_length(C)]: Sized,
-[mpfr::mpfr_t; mpfr_fixed_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
 [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
 {
     pub(crate) fn new(float: &'a mut UniFloat<C>) -> Self {
@@ -64,8 +62,8 @@ impl <'a, const C: UniFloatChoice>
 ops::Add<&UniFloat<C>> for OperandMutated<'a, C> where
 [f32; f32_parts_length(C)]: Sized,
 [f64; f64_parts_length(C)]: Sized,
-[twofloat::TwoFloat; twofloat_parts_length(C)]: Sized,
-[mpfr::mpfr_t; mpfr_fixed_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
 [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
 {
     type Output = Self;
@@ -75,23 +73,6 @@ ops::Add<&UniFloat<C>> for OperandMutated<'a, C> where
     }
 }
 
-// TODO use "existential" impl UniFloatable<C>, so that this works with
-// right hand: &UniFloat, OperandMutated, OperandOwned.
-impl <const C: UniFloatChoice>
-ops::Add<&UniFloat<C>> for &UniFloat<C> where
-[f32; f32_parts_length(C)]: Sized,
-[f64; f64_parts_length(C)]: Sized,
-[twofloat::TwoFloat; twofloat_parts_length(C)]: Sized,
-[mpfr::mpfr_t; mpfr_fixed_parts_length(C)]: Sized,
-[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
-{
-    type Output = OperandOwned<C>;
-    fn add(self, right: &UniFloat<C>) -> Self::Output {
-        //TODO
-        panic!()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use crate::{MpfrBounds, UniFloat, UniFloatBounds, UniFloatBoundsBase, UniFloatBoundsToChoice, UniFloatChoice};
@@ -0,0 +1,202 @@
+use core::{fmt, str::FromStr};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+use crate::{mpfr, parts::limit_exponent_range};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::decimal::Dec;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::convert::UniMpfrTwoFloatExact;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "twofloat")]
+use crate::{radix::parse_decimal_literal, TwoFloat};
+
+/// The longest string that MPFR-based (and TwoFloat-based) UniFloat can parse. MPFR needs a
+/// NUL-terminated copy, and TwoFloat keeps all the digits, both (since this crate is no_std) on
+/// the stack.
+pub const MAX_PARSE_LENGTH: usize = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The string is empty.
+    Empty,
+    /// A character that doesn't belong there.
+    InvalidCharacter,
+    /// The significand or the exponent has no digits (for example "+", "." or "1e").
+    MissingDigits,
+    /// Longer than `MAX_PARSE_LENGTH` (only for TwoFloat and MPFR, and for `from_str_radix()`).
+    TooLong,
+}
+
+/// Error from parsing a string into UniFloat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseUniFloatError {
+    kind: ParseErrorKind,
+    position: usize,
+}
+
+impl ParseUniFloatError {
+    pub(crate) fn new(kind: ParseErrorKind, position: usize) -> Self {
+        Self { kind, position }
+    }
+
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Byte offset of the offending character (or of the end, if a part is missing).
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ParseUniFloatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self.kind {
+            ParseErrorKind::Empty => "cannot parse float from empty string",
+            ParseErrorKind::InvalidCharacter => "invalid character in float literal",
+            ParseErrorKind::MissingDigits => "missing digits in float literal",
+            ParseErrorKind::TooLong => "float literal too long",
+        };
+        write!(f, "{} at position {}", description, self.position)
+    }
+}
+
+/// Check the syntax: an optional sign, then either "inf", "infinity" or "nan" (case-insensitive),
+/// or decimal digits with an optional '.' and an optional exponent ('e' or 'E', an optional sign
+/// and digits). That's the same as Rust's `f64::from_str()`.
+pub(crate) fn validate(s: &str) -> Result<(), ParseUniFloatError> {
+    let error = |kind, position| Err(ParseUniFloatError { kind, position });
+    let bytes = s.as_bytes();
+    if bytes.is_empty() {
+        return error(ParseErrorKind::Empty, 0);
+    }
+    let mut position = if bytes[0] == b'+' || bytes[0] == b'-' { 1 } else { 0 };
+    let special = &s[position..];
+    if special.eq_ignore_ascii_case("inf") || special.eq_ignore_ascii_case("infinity")
+        || special.eq_ignore_ascii_case("nan") {
+        return Ok(());
+    }
+
+    let (mut digits, mut dot) = (0, false);
+    while position < bytes.len() {
+        match bytes[position] {
+            b'0'..=b'9' => digits += 1,
+            b'.' if !dot => dot = true,
+            _ => break
+        }
+        position += 1;
+    }
+    if digits == 0 {
+        return if position < bytes.len() && !matches!(bytes[position], b'e' | b'E') {
+            error(ParseErrorKind::InvalidCharacter, position)
+        } else {
+            error(ParseErrorKind::MissingDigits, position)
+        };
+    }
+
+    if position < bytes.len() && matches!(bytes[position], b'e' | b'E') {
+        position += 1;
+        if position < bytes.len() && matches!(bytes[position], b'+' | b'-') {
+            position += 1;
+        }
+        let exponent_start = position;
+        while position < bytes.len() && bytes[position].is_ascii_digit() {
+            position += 1;
+        }
+        if position == exponent_start && position == bytes.len() {
+            return error(ParseErrorKind::MissingDigits, position);
+        }
+    }
+    if position < bytes.len() {
+        error(ParseErrorKind::InvalidCharacter, position)
+    } else {
+        Ok(())
+    }
+}
+
+/// Parse with MPFR in the given `base`, correctly rounded to the precision of `target`. `s` must be
+/// valid already. Returns MPFR's ternary value, for `limit_exponent_range()`. Without feature
+/// `mpfr`, `SoftMpfr` parses decimals (`base` 10) only.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+pub(crate) fn parse_mpfr(target: mpfr::mpfr_ptr, s: &str, base: i32) -> Result<i32, ParseUniFloatError> {
+    if s.len() > MAX_PARSE_LENGTH {
+        return Err(ParseUniFloatError { kind: ParseErrorKind::TooLong, position: MAX_PARSE_LENGTH });
+    }
+    let mut nul_terminated = [0u8; MAX_PARSE_LENGTH + 1];
+    nul_terminated[..s.len()].copy_from_slice(s.as_bytes());
+    let ternary = unsafe {
+        mpfr::strtofr(target, nul_terminated.as_ptr() as *const _, core::ptr::null_mut(), base,
+            mpfr::rnd_t::RNDN)
+    };
+    Ok(ternary)
+}
+
+/// Decimal strings, as accepted by Rust's `f64::from_str()`. The result is correctly rounded
+/// (to nearest, ties to even) for f32, f64 and MPFR. For TwoFloat it's the nearest double-double:
+/// the higher part is rounded from the exact value, and so is the lower part from the rest. For
+/// QuadFloat it's rounded to 2098 bits first, and then to the nearest quad-double. Decimals round
+/// once, in decimal, and keep the exponent as written where the digits fit ("1.50" has exponent
+/// -2), as `UniDecimal` does. For MPFR it's then limited to the exponent range (or the
+/// fixed-point format) of the bounds.
+impl <const C: UniFloatChoice> FromStr for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Err = ParseUniFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        validate(s)?;
+        let mut result = Self::NAN;
+        match C {
+            // Rust's own parsing is correctly rounded. It can't fail once `s` is valid.
+            UniFloatChoice::F32 => result.f32s[0] = s.parse().unwrap_or(f32::NAN),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = s.parse().unwrap_or(f64::NAN),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = match parse_decimal_literal(s)? {
+                Some(literal) => literal.to_twofloat(10),
+                None => TwoFloat::from(s.parse::<f64>().unwrap_or(f64::NAN))
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                let mut exact = UniMpfrTwoFloatExact::NAN;
+                exact.copied();
+                parse_mpfr(exact.mpfr_mut(), s, 10)?;
+                result = Self::from_mpfr(exact.mpfr_src().as_ptr());
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => result.set_decimal(Dec::parse(digits, s)?),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let ternary = parse_mpfr(result.mpfr_mut(), s, 10)?;
+                limit_exponent_range(C.mpfr_bounds(), result.mpfr_mut(), ternary);
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        Ok(result.released())
+    }
+}
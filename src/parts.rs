@@ -0,0 +1,680 @@
+use {crate::mpfr, crate::TwoFloat};
+
+use crate::flags::emulated_f32;
+#[cfg(feature = "mpfr")]
+use crate::posit::limit_posit;
+#[cfg(feature = "mpfr")]
+use crate::{FixedFormat, FixedOverflow, Underflow};
+#[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+use crate::MpfrBounds;
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+#[cfg(not(feature = "f32_only"))]
+use crate::DUMMY_MPFR_LIMB_PTR;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::{gmp, INF_MPFR_EXP, NAN_MPFR_EXP, ZERO_MPFR_EXP};
+#[cfg(not(feature = "f32_only"))]
+use crate::flags::emulated_f64;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "twofloat")]
+use crate::flags::emulated_twofloat;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::flags::emulated_decimal;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::flags::emulated_quadfloat;
+use crate::{decimal::Dec, quadfloat::Quad};
+
+/// Helpers that apply an operation to whichever part (f32, f64, TwoFloat or MPFR) is in use by `C`.
+/// Operations are given one closure per part. Only the closure for `C` gets called, so the others
+/// can be "anything" that type checks.
+/// MPFR closures receive pointers to `mpfr::mpfr_t` (the result first, then the operands), and
+/// they return MPFR's ternary value. QuadFloat and decimals run the MPFR closures, too (see
+/// `Quad::via_mpfr()` and `Dec::via_mpfr()`), other than in `assign_binary()`, which takes a
+/// closure for each.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr"), not(feature = "twofloat")), allow(unused_variables))]
+    pub(crate) fn unary(&self,
+        f32_op: impl FnOnce(f32) -> f32,
+        f64_op: impl FnOnce(f64) -> f64,
+        twofloat_op: impl FnOnce(TwoFloat) -> TwoFloat,
+        mpfr_op: impl FnOnce(mpfr::mpfr_ptr, mpfr::mpfr_srcptr) -> i32
+    ) -> Self {
+        self.assert_copy_fixed();
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = emulated_f32(f32_op(self.f32s[0]), &[self.f32s[0]]),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = emulated_f64(f64_op(self.f64s[0]), &[self.f64s[0]]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = emulated_twofloat(twofloat_op(self.twofloats[0]), &[self.twofloats[0]]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => result.set_quad(emulated_quadfloat(
+                Quad::via_mpfr([self.quad()], |r, x| mpfr_op(r, x[0])), &[self.quad()])),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => result.set_decimal(emulated_decimal(
+                Dec::via_mpfr(digits, [self.decimal()], |r, x| mpfr_op(r, x[0])), &[self.decimal()])),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                result.copied();
+                let ternary = mpfr_op(result.mpfr_mut(), self.mpfr_src().as_ptr());
+                limit_exponent_range(C.mpfr_bounds(), result.mpfr_mut(), ternary);
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+
+    #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr"), not(feature = "twofloat")), allow(unused_variables))]
+    pub(crate) fn binary(&self, other: &Self,
+        f32_op: impl FnOnce(f32, f32) -> f32,
+        f64_op: impl FnOnce(f64, f64) -> f64,
+        twofloat_op: impl FnOnce(TwoFloat, TwoFloat) -> TwoFloat,
+        mpfr_op: impl FnOnce(mpfr::mpfr_ptr, mpfr::mpfr_srcptr, mpfr::mpfr_srcptr) -> i32
+    ) -> Self {
+        self.assert_copy_fixed();
+        other.assert_copy_fixed();
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = emulated_f32(f32_op(self.f32s[0], other.f32s[0]), &[self.f32s[0], other.f32s[0]]),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = emulated_f64(f64_op(self.f64s[0], other.f64s[0]), &[self.f64s[0], other.f64s[0]]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = emulated_twofloat(twofloat_op(self.twofloats[0], other.twofloats[0]),
+                &[self.twofloats[0], other.twofloats[0]]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => result.set_quad(emulated_quadfloat(
+                Quad::via_mpfr([self.quad(), other.quad()], |r, x| mpfr_op(r, x[0], x[1])), &[self.quad(), other.quad()])),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => result.set_decimal(emulated_decimal(
+                Dec::via_mpfr(digits, [self.decimal(), other.decimal()], |r, x| mpfr_op(r, x[0], x[1])),
+                &[self.decimal(), other.decimal()])),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                result.copied();
+                let ternary = mpfr_op(result.mpfr_mut(), self.mpfr_src().as_ptr(), other.mpfr_src().as_ptr());
+                limit_exponent_range(C.mpfr_bounds(), result.mpfr_mut(), ternary);
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+
+    #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr"), not(feature = "twofloat")), allow(unused_variables))]
+    pub(crate) fn ternary(&self, second: &Self, third: &Self,
+        f32_op: impl FnOnce(f32, f32, f32) -> f32,
+        f64_op: impl FnOnce(f64, f64, f64) -> f64,
+        twofloat_op: impl FnOnce(TwoFloat, TwoFloat, TwoFloat) -> TwoFloat,
+        mpfr_op: impl FnOnce(mpfr::mpfr_ptr, mpfr::mpfr_srcptr, mpfr::mpfr_srcptr, mpfr::mpfr_srcptr) -> i32
+    ) -> Self {
+        self.assert_copy_fixed();
+        second.assert_copy_fixed();
+        third.assert_copy_fixed();
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = emulated_f32(f32_op(self.f32s[0], second.f32s[0], third.f32s[0]),
+                &[self.f32s[0], second.f32s[0], third.f32s[0]]),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = emulated_f64(f64_op(self.f64s[0], second.f64s[0], third.f64s[0]),
+                &[self.f64s[0], second.f64s[0], third.f64s[0]]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = emulated_twofloat(
+                twofloat_op(self.twofloats[0], second.twofloats[0], third.twofloats[0]),
+                &[self.twofloats[0], second.twofloats[0], third.twofloats[0]]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => result.set_quad(emulated_quadfloat(
+                Quad::via_mpfr([self.quad(), second.quad(), third.quad()], |r, x| mpfr_op(r, x[0], x[1], x[2])),
+                &[self.quad(), second.quad(), third.quad()])),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => result.set_decimal(emulated_decimal(
+                Dec::via_mpfr(digits, [self.decimal(), second.decimal(), third.decimal()], |r, x| mpfr_op(r, x[0], x[1], x[2])),
+                &[self.decimal(), second.decimal(), third.decimal()])),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                result.copied();
+                let ternary = mpfr_op(result.mpfr_mut(), self.mpfr_src().as_ptr(),
+                    second.mpfr_src().as_ptr(), third.mpfr_src().as_ptr());
+                limit_exponent_range(C.mpfr_bounds(), result.mpfr_mut(), ternary);
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+
+    /// Like `binary()`, but it stores the result in `self`. For MPFR that reuses the limbs of `self`.
+    /// QuadFloat runs `quadfloat_op` and decimals `decimal_op` (rather than MPFR), for native
+    /// arithmetic.
+    #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr"), not(feature = "twofloat")), allow(unused_variables))]
+    pub(crate) fn assign_binary(&mut self, other: &Self,
+        f32_op: impl FnOnce(f32, f32) -> f32,
+        f64_op: impl FnOnce(f64, f64) -> f64,
+        twofloat_op: impl FnOnce(TwoFloat, TwoFloat) -> TwoFloat,
+        quadfloat_op: impl FnOnce(Quad, Quad) -> Quad,
+        decimal_op: impl FnOnce(Dec, Dec) -> Dec,
+        mpfr_op: impl FnOnce(mpfr::mpfr_ptr, mpfr::mpfr_srcptr, mpfr::mpfr_srcptr) -> i32
+    ) {
+        self.assert_copy_fixed();
+        other.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => self.f32s[0] = emulated_f32(f32_op(self.f32s[0], other.f32s[0]), &[self.f32s[0], other.f32s[0]]),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => self.f64s[0] = emulated_f64(f64_op(self.f64s[0], other.f64s[0]), &[self.f64s[0], other.f64s[0]]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => self.twofloats[0] = emulated_twofloat(twofloat_op(self.twofloats[0], other.twofloats[0]),
+                &[self.twofloats[0], other.twofloats[0]]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                let (x, y) = (self.quad(), other.quad());
+                self.set_quad(emulated_quadfloat(quadfloat_op(x, y), &[x, y]));
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => {
+                let (x, y) = (self.decimal(), other.decimal());
+                self.set_decimal(emulated_decimal(decimal_op(x, y), &[x, y]));
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let this = self.mpfr_mut();
+                let ternary = mpfr_op(this, this, other.mpfr_src().as_ptr());
+                limit_exponent_range(C.mpfr_bounds(), this, ternary);
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    /// Point MPFR's `d` of a local result back to the dummy, before it's returned by value, so that
+    /// it's like any other moved value (or a constant). MPFR's `d` is re-pointed on each access
+    /// anyway (see `mpfr_src()` and `mpfr_mut()`).
+    #[inline]
+    pub(crate) fn released(mut self) -> Self {
+        #[cfg(not(feature = "f32_only"))]
+        if C.in_mpfr() {
+            self.mpfr_fixeds[0].d = DUMMY_MPFR_LIMB_PTR;
+        }
+        #[cfg(debug_assertions)] {
+            self.used_as_operand_mutated = false;
+        }
+        self
+    }
+
+    /// Rounded to nearest.
+    pub(crate) fn from_f64(value: f64) -> Self {
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = value as f32,
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = value,
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = TwoFloat::from(value),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => result.set_quad(Quad::from_f64(value)),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } =>
+                result.set_decimal(Dec::via_mpfr(digits, [], |r, _| unsafe { mpfr::set_d(r, value, mpfr::rnd_t::RNDN) })),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                result.copied();
+                let ternary = unsafe { mpfr::set_d(result.mpfr_mut(), value, mpfr::rnd_t::RNDN) };
+                limit_exponent_range(C.mpfr_bounds(), result.mpfr_mut(), ternary);
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+
+    /// Rounded to nearest.
+    pub(crate) fn to_f64_nearest(&self) -> f64 {
+        self.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => self.f32s[0] as f64,
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => self.f64s[0],
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => self.twofloats[0].hi(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => self.quad().0[0],
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => self.decimal().to_f64(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => unsafe {
+                mpfr::get_d(self.mpfr_src().as_ptr(), mpfr::rnd_t::RNDN)
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::BY_SOFTMPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+}
+
+/// `ZERO_MPFR_EXP` with a 64-bit `mpfr::exp_t`. NaN and infinities follow (as in mpfr.h).
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+const PORTABLE_ZERO_EXP: i64 = 0 - i64::MAX;
+
+/// The raw contents as 64-bit words, for lossless serialization. For F32, F64, TwoFloat and
+/// QuadFloat the words are the IEEE 754 bits (of each part, the highest part first). For MPFR they
+/// hold the significand, aligned to the top of `word_count()` words, least significant word
+/// first, regardless of the width of GMP limbs. They're all zero for MPFR NaN, infinities and
+/// zeros. The sign and the exponent of MPFR are separate. For decimals they're `Dec::to_words()`.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub(crate) const fn word_count() -> usize {
+        match C {
+            UniFloatChoice::F32 | UniFloatChoice::F64 => 1,
+            UniFloatChoice::TwoFloat => 2,
+            UniFloatChoice::QuadFloat => 4,
+            UniFloatChoice::Decimal { .. } => 3,
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+                (C.mpfr_bounds().precision_bits - 1) / 64 + 1,
+        }
+    }
+
+    /// Number of limbs per word (1 or 2), and number of the (non-existing) lowest limbs below
+    /// the top-aligned limbs.
+    #[cfg(not(feature = "f32_only"))]
+    #[cfg(feature = "mpfr")]
+    fn limb_layout() -> (usize, usize) {
+        let limbs_per_word = 64 / gmp::NUMB_BITS as usize;
+        (limbs_per_word, Self::word_count() * limbs_per_word - mpfr_limb_parts_length(C))
+    }
+
+    #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(unused_variables))]
+    pub(crate) fn word(&self, index: usize) -> u64 {
+        self.assert_copy_fixed();
+        match C {
+            UniFloatChoice::F32 => self.f32s[0].to_bits() as u64,
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => self.f64s[0].to_bits(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => if index == 0 {
+                self.twofloats[0].hi().to_bits()
+            } else {
+                self.twofloats[0].lo().to_bits()
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => self.quad().0[index].to_bits(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => self.decimal().to_words()[index],
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                if unsafe { mpfr::regular_p(self.mpfr_src().as_ptr()) } == 0 {
+                    return 0;
+                }
+                let (limbs_per_word, skipped) = Self::limb_layout();
+                let mut word = 0;
+                for part in 0..limbs_per_word {
+                    if let Some(limb) = (index * limbs_per_word + part).checked_sub(skipped) {
+                        word |= (unsafe { self.mpfr_limbs[limb].assume_init() } as u64) << (part * gmp::NUMB_BITS as usize);
+                    }
+                }
+                word
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    /// Set the words in order (from index 0). Returns false if `word` can't be there: if it
+    /// doesn't fit F32, if the parts of TwoFloat or QuadFloat aren't normalized, if it has bits
+    /// below the limbs of MPFR, or if the words of a decimal aren't `Dec::to_words()` of a valid
+    /// one (checked at the last word). Then the value is unspecified.
+    #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(unused_variables))]
+    pub(crate) fn set_word(&mut self, index: usize, word: u64) -> bool {
+        match C {
+            UniFloatChoice::F32 => {
+                self.f32s[0] = f32::from_bits(word as u32);
+                word <= u32::MAX as u64
+            },
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => {
+                self.f64s[0] = f64::from_bits(word);
+                true
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => {
+                let value = f64::from_bits(word);
+                if index == 0 {
+                    self.twofloats[0] = TwoFloat::from(value);
+                    true
+                } else {
+                    let high = self.twofloats[0].hi();
+                    if value == 0.0 || !high.is_finite() {
+                        // That keeps negative zero.
+                        value.to_bits() == 0
+                    } else {
+                        self.twofloats[0] = TwoFloat::new_add(high, value);
+                        self.twofloats[0].hi().to_bits() == high.to_bits() && self.twofloats[0].lo().to_bits() == word
+                    }
+                }
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                let value = f64::from_bits(word);
+                if index == 0 {
+                    self.set_quad(Quad::from_f64(value));
+                    return true;
+                }
+                // As for TwoFloat, each part against the one above it.
+                let mut quad = self.quad();
+                let high = quad.0[index - 1];
+                quad.0[index] = value;
+                let valid = if value == 0.0 || high == 0.0 || !high.is_finite() {
+                    value.to_bits() == 0
+                } else {
+                    let pair = TwoFloat::new_add(high, value);
+                    pair.hi().to_bits() == high.to_bits() && pair.lo().to_bits() == word
+                };
+                self.set_quad(quad);
+                valid
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => {
+                let limbs_per_word = 64 / gmp::NUMB_BITS as usize;
+                for part in 0..limbs_per_word {
+                    self.mpfr_limbs[index * limbs_per_word + part] =
+                        MpfrLimbPart::new((word >> (part * gmp::NUMB_BITS as usize)) as gmp::limb_t);
+                }
+                let words = [self.word(0), self.word(1), word];
+                index < 2 || (Dec::from_words(digits, words).to_words() == words && self.decimal().is_canonical())
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let (limbs_per_word, skipped) = Self::limb_layout();
+                let mut valid = true;
+                for part in 0..limbs_per_word {
+                    let limb = (word >> (part * gmp::NUMB_BITS as usize)) as gmp::limb_t;
+                    match (index * limbs_per_word + part).checked_sub(skipped) {
+                        Some(index) => self.mpfr_limbs[index] = MpfrLimbPart::new(limb),
+                        None => valid &= limb == 0
+                    }
+                }
+                valid
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    /// The sign and the (raw) exponent of MPFR, including the special exponents for NaN,
+    /// infinities and zeros. Those are as with a 64-bit `mpfr::exp_t`, whatever its width on this
+    /// platform, so that they're portable.
+    #[cfg(not(feature = "f32_only"))]
+    #[cfg(feature = "mpfr")]
+    pub(crate) fn mpfr_sign_exponent(&self) -> (bool, i64) {
+        self.assert_copy_fixed();
+        let exponent = self.mpfr_fixeds[0].exp;
+        let exponent = if exponent == ZERO_MPFR_EXP || exponent == NAN_MPFR_EXP || exponent == INF_MPFR_EXP {
+            PORTABLE_ZERO_EXP + (exponent - ZERO_MPFR_EXP) as i64
+        } else {
+            exponent as i64
+        };
+        (self.mpfr_fixeds[0].sign < 0, exponent)
+    }
+
+    /// Set the sign and the (raw) exponent of MPFR, after all words have been set. Returns whether
+    /// that makes a valid value: either a special exponent, or an exponent in the current range
+    /// with a normalized significand (the highest bit set, and no bits below the precision).
+    #[cfg(not(feature = "f32_only"))]
+    #[cfg(feature = "mpfr")]
+    pub(crate) fn set_mpfr_sign_exponent(&mut self, negative: bool, exponent: i64) -> bool {
+        self.mpfr_fixeds[0].sign = if negative { -1 } else { 1 };
+        if (PORTABLE_ZERO_EXP..=PORTABLE_ZERO_EXP + 2).contains(&exponent) {
+            // The limbs don't matter.
+            self.mpfr_fixeds[0].exp = ZERO_MPFR_EXP + (exponent - PORTABLE_ZERO_EXP) as mpfr::exp_t;
+            return true;
+        }
+        self.mpfr_fixeds[0].exp = exponent as mpfr::exp_t;
+        let exponent_valid = exponent == self.mpfr_fixeds[0].exp as i64;
+        let exponent = self.mpfr_fixeds[0].exp;
+        let limb_count = mpfr_limb_parts_length(C);
+        let (highest, lowest) = unsafe {
+            (self.mpfr_limbs[limb_count - 1].assume_init(), self.mpfr_limbs[0].assume_init())
+        };
+        let unused_bits = limb_count * gmp::NUMB_BITS as usize - C.precision_bits();
+        exponent_valid && unsafe { mpfr::get_emin() <= exponent && exponent <= mpfr::get_emax() }
+            && highest >> (gmp::NUMB_BITS - 1) == 1
+            && (unused_bits == 0 || lowest & ((1 << unused_bits) - 1) == 0)
+    }
+}
+
+/// Limits `result` (of an MPFR operation, whose ternary value was `ternary`) to the exponent range
+/// of `bounds` (see `MpfrBounds::for_bounds()`), if any: it overflows to an infinity, or it
+/// underflows as per `Underflow`, as if rounded to nearest. Operations themselves run in MPFR's
+/// default range, so that intermediate steps don't overflow.
+/// Returns the new ternary value. Fixed-point formats are limited by `limit_fixed()` instead, and
+/// posits by `limit_posit()`.
+#[cfg(feature = "mpfr")]
+pub(crate) fn limit_exponent_range(bounds: MpfrBounds, result: mpfr::mpfr_ptr, ternary: i32) -> i32 {
+    if let Some(fixed) = bounds.fixed {
+        return unsafe { limit_fixed(fixed, result, ternary) };
+    }
+    if let Some(posit) = bounds.posit {
+        return unsafe { limit_posit(posit, result, ternary) };
+    }
+    match bounds.exponent_range() {
+        None => ternary,
+        Some((min_exponent, max_exponent)) => unsafe {
+            let (previous_min, previous_max) = (mpfr::get_emin(), mpfr::get_emax());
+            // For subnormals, MPFR's emin is the exponent of the least subnormal (as with IEEE
+            // 754 emulation in MPFR's documentation of `mpfr_subnormalize`).
+            let least_exponent = match bounds.underflow {
+                Underflow::Subnormal => min_exponent.saturating_sub(bounds.precision_bits as mpfr::exp_t - 1),
+                _ => min_exponent
+            };
+            mpfr::set_emin(least_exponent.max(mpfr::get_emin_min()));
+            mpfr::set_emax(max_exponent.min(mpfr::get_emax_max()));
+            let mut ternary = ternary;
+            if bounds.underflow == Underflow::FlushToZero && mpfr::regular_p(result) != 0
+                && mpfr::get_exp(result) < min_exponent {
+                let negative = mpfr::signbit(result) != 0;
+                mpfr::set_zero(result, if negative { -1 } else { 1 });
+                // Zero is above a negative result, and below a positive one.
+                ternary = if negative { 1 } else { -1 };
+                mpfr::set_underflow();
+                mpfr::set_inexflag();
+            }
+            ternary = mpfr::check_range(result, ternary, mpfr::rnd_t::RNDN);
+            if bounds.underflow == Underflow::Subnormal {
+                ternary = mpfr::subnormalize(result, ternary, mpfr::rnd_t::RNDN);
+            }
+            mpfr::set_emin(previous_min);
+            mpfr::set_emax(previous_max);
+            ternary
+        }
+    }
+}
+
+/// With feature `softmpfr` (and without `mpfr`), SoftMpfr has MPFR's default exponent range only,
+/// so there's nothing to limit: bounded choices (and fixed-point formats and posits) need feature
+/// `mpfr`. They fail to compile already (see `Supported::BY_SOFTMPFR`), so the assertion is a
+/// backstop.
+#[cfg(all(feature = "softmpfr", not(feature = "mpfr")))]
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+pub(crate) fn limit_exponent_range(bounds: MpfrBounds, _result: mpfr::mpfr_ptr, ternary: i32) -> i32 {
+    assert!(matches!((bounds.fixed, bounds.posit, bounds.exponent_range()), (None, None, None)),
+        "Only UniFloatChoice::Mpfr with MPFR's default exponent range is supported with softmpfr feature (without mpfr).");
+    ternary
+}
+
+/// Rounds `result` (of an MPFR operation, whose ternary value was `ternary`) to nearest multiple of
+/// 2^-frac_bits, and then saturates or wraps it as per `fixed` (see `UniFloatChoice::fixed()`).
+/// The grid is that of subnormals with MPFR's emin of 1 - frac_bits, so `mpfr::subnormalize()`
+/// rounds without double rounding. Returns the new ternary value (for wrapped results: that of the
+/// rounding).
+#[cfg(feature = "mpfr")]
+unsafe fn limit_fixed(fixed: FixedFormat, result: mpfr::mpfr_ptr, ternary: i32) -> i32 {
+    if mpfr::nan_p(result) != 0 {
+        return ternary;
+    }
+    let previous_min = mpfr::get_emin();
+    mpfr::set_emin((1 - fixed.frac_bits as mpfr::exp_t).max(mpfr::get_emin_min()));
+    let mut ternary = mpfr::check_range(result, ternary, mpfr::rnd_t::RNDN);
+    ternary = mpfr::subnormalize(result, ternary, mpfr::rnd_t::RNDN);
+    mpfr::set_emin(previous_min);
+    let top = fixed.int_bits as mpfr::exp_t - 1;
+    let above = mpfr::cmp_si_2exp(result, 1, top) >= 0;
+    let below = mpfr::cmp_si_2exp(result, -1, top) < 0;
+    if above || below {
+        mpfr::set_overflow();
+        match fixed.overflow {
+            FixedOverflow::Saturating => {
+                mpfr::set_inexflag();
+                if above {
+                    // 2^(int_bits - 1) - 2^-frac_bits is two steps below 2^(int_bits - 1), as
+                    // the precision has one bit more than the grid there.
+                    mpfr::set_ui_2exp(result, 1, top, mpfr::rnd_t::RNDN);
+                    mpfr::nextbelow(result);
+                    mpfr::nextbelow(result);
+                    ternary = -1;
+                } else {
+                    mpfr::set_si_2exp(result, -1, top, mpfr::rnd_t::RNDN);
+                    ternary = 1;
+                }
+            },
+            FixedOverflow::Wrapping => {
+                // The remainder modulo 2^int_bits, in [-2^(int_bits - 1), 2^(int_bits - 1)).
+                // Exact, as `result` is on the grid.
+                let int_bits = fixed.int_bits as mpfr::exp_t;
+                mpfr::mul_2si(result, result, -int_bits, mpfr::rnd_t::RNDN);
+                mpfr::frac(result, result, mpfr::rnd_t::RNDN);
+                if mpfr::cmp_d(result, 0.5) >= 0 {
+                    mpfr::sub_ui(result, result, 1, mpfr::rnd_t::RNDN);
+                } else if mpfr::cmp_d(result, -0.5) < 0 {
+                    mpfr::add_ui(result, result, 1, mpfr::rnd_t::RNDN);
+                }
+                mpfr::mul_2si(result, result, int_bits, mpfr::rnd_t::RNDN);
+            }
+        }
+    }
+    if mpfr::zero_p(result) != 0 {
+        mpfr::set_zero(result, 1);
+    }
+    ternary
+}
+
+/// `twofloat_op(x)` if both `x` and `approximation` (the result in f64) are finite and non-zero.
+/// Otherwise (for NaN, infinities, zeros, overflows and underflows) `approximation` itself. That
+/// keeps TwoFloat's algorithms away from values that they don't handle.
+#[cfg(feature = "twofloat")]
+pub(crate) fn twofloat_or_f64(x: TwoFloat, approximation: f64, twofloat_op: impl FnOnce(TwoFloat) -> TwoFloat) -> TwoFloat {
+    if x.hi().is_finite() && x.hi() != 0.0 && approximation.is_finite() && approximation != 0.0 {
+        twofloat_op(x)
+    } else {
+        TwoFloat::from(approximation)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::{BALL_RADIUS, RoundingMode, ball::{Radius, inexact_rounding_error, mul_add_up, radius_op}};
+
+/// Polynomials by Horner's scheme, one `UniFloat::mul_add()` for each coefficient after the
+/// first. `coefficients[i]` is the coefficient of x^i (so the constant term comes first). The
+/// polynomial of no coefficients is +0.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// The polynomial of `coefficients` at `x`.
+    pub fn eval_polynomial(coefficients: &[Self], x: &Self) -> Self {
+        let (last, rest) = match coefficients.split_last() {
+            Some(split) => split,
+            None => return Self::ZERO
+        };
+        let mut result = *last;
+        result.copied();
+        for coefficient in rest.iter().rev() {
+            let next = result.mul_add(x, coefficient);
+            result <<= next;
+        }
+        result.released()
+    }
+
+    /// As `eval_polynomial()`, together with a rigorous bound of the error: the result is within
+    /// that of the exact value of the polynomial (of `coefficients` as they are) at `x`. The bound
+    /// is a running error bound (as in Higham's "Accuracy and Stability of Numerical Algorithms",
+    /// 5.1): the rounding error of each step counts as an ulp of its result (16 of them for
+    /// TwoFloat and QuadFloat, as for `UniBall`, and for TwoFloat the product rounds separately),
+    /// and the errors so far get multiplied by |`x`|. It's rounded up, in the choice of the
+    /// radius of `UniBall`. Needs feature `mpfr`.
+    #[cfg(not(feature = "f32_only"))]
+    #[cfg(feature = "mpfr")]
+    pub fn eval_polynomial_with_error_bound(coefficients: &[Self], x: &Self) -> (Self, UniFloat<{ BALL_RADIUS }>) {
+        let (last, rest) = match coefficients.split_last() {
+            Some(split) => split,
+            None => return (Self::ZERO, Radius::ZERO)
+        };
+        let mut result = *last;
+        result.copied();
+        let mut bound = Radius::ZERO;
+        bound.copied();
+        let mut magnitude = x.abs();
+        magnitude.copied();
+        let mut magnitude = match magnitude.convert_round::<{ BALL_RADIUS }>(RoundingMode::Up) {
+            Ok(magnitude) => magnitude,
+            Err(_) => unreachable!("MPFR supports directed rounding.")
+        };
+        magnitude.copied();
+        for coefficient in rest.iter().rev() {
+            let mut next = result.mul_add(x, coefficient);
+            next.copied();
+            let mut error = inexact_rounding_error(&next);
+            error.copied();
+            if matches!(C, UniFloatChoice::TwoFloat) {
+                let mut product = result;
+                product.copied();
+                product *= x;
+                let mut product_error = inexact_rounding_error(&product);
+                product_error.copied();
+                let sum = radius_op(&error, &product_error, RoundingMode::Up, Radius::add_round);
+                error <<= sum;
+            }
+            let propagated = mul_add_up(&bound, &magnitude, &error);
+            bound <<= propagated;
+            result <<= &next;
+        }
+        (result.released(), bound.released())
+    }
+}
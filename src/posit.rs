@@ -0,0 +1,529 @@
+use core::{cmp::Ordering, fmt, ops};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::MpfrBounds;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::convert::MPFR_TWOFLOAT_EXACT;
+#[cfg(feature = "mpfr")]
+use crate::mpfr;
+use crate::{Assert, IsTrue, PositFormat, UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length,
+    f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+
+/// Whether `UniPosit<NBITS, ES>` is supported: 2 to 32 bits, with up to 4 exponent bits. Then every
+/// posit is exact as f64 (with scales up to +-2^480).
+pub const fn valid_posit(nbits: u32, es: u32) -> bool {
+    nbits >= 2 && nbits <= 32 && es <= 4
+}
+
+/// Enough for `valid_posit()` values, and the rounding of any f64 or MPFR to them.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+const MPFR_53_BITS: UniFloatChoice = UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(53) };
+
+/// A posit (type III unum) of `NBITS` bits, with up to `ES` exponent bits, in software. Posits
+/// have tapered accuracy: more fraction bits near 1, and fewer toward `MAX` and `MIN_POSITIVE`.
+/// So `UniPosit<32, 2>` is more accurate than f32 around 1, and it spans a wider range.
+///
+/// Arithmetic and conversions round to nearest (ties to even), once. As the posit standard
+/// requires, results don't overflow to infinity or underflow to zero: they saturate at `MAX` and
+/// `MIN_POSITIVE` (with the sign). There's one zero, and one NaR ("not a real") for both NaN and
+/// infinities. NaR equals itself, and it's less than any other posit, so the order is total.
+///
+/// Like `UniFloat`, it has constants `ZERO`, `ONE`, `MAX` and so on, and the arithmetic operators.
+/// Converting to and from `UniFloat` lets the same computation run in posits and in any
+/// `UniFloatChoice`, for comparing accuracy.
+/// `UniFloatChoice::posit()` is the same format as a choice of `UniFloat`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UniPosit<const NBITS: u32, const ES: u32> where
+Assert<{ valid_posit(NBITS, ES) }>: IsTrue,
+{
+    /// The lowest `NBITS` bits only.
+    bits: u32,
+}
+
+impl <const NBITS: u32, const ES: u32> UniPosit<NBITS, ES> where
+Assert<{ valid_posit(NBITS, ES) }>: IsTrue,
+{
+    const MASK: u32 = u32::MAX >> (32 - NBITS);
+    const FORMAT: PositFormat = PositFormat { nbits: NBITS, es: ES };
+
+    pub const ZERO: Self = Self { bits: 0 };
+    pub const ONE: Self = Self { bits: 1 << (NBITS - 2) };
+    /// Not a real: the result of invalid operations (like 0/0 or the square root of -1), and of
+    /// conversions from NaN and infinities.
+    pub const NAR: Self = Self { bits: 1 << (NBITS - 1) };
+    /// The greatest posit: 2^(2^`ES` * (`NBITS` - 2)).
+    pub const MAX: Self = Self { bits: (1 << (NBITS - 1)) - 1 };
+    /// The least positive posit: 2^-(2^`ES` * (`NBITS` - 2)).
+    pub const MIN_POSITIVE: Self = Self { bits: 1 };
+
+    /// The posit with bit pattern `bits` (in two's complement, as in the posit standard). Bits above
+    /// `NBITS` are ignored.
+    pub const fn from_bits(bits: u32) -> Self {
+        Self { bits: bits & Self::MASK }
+    }
+
+    pub const fn to_bits(&self) -> u32 {
+        self.bits
+    }
+
+    pub fn is_nar(&self) -> bool {
+        *self == Self::NAR
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    /// False for NaR.
+    pub fn is_sign_negative(&self) -> bool {
+        !self.is_nar() && self.bits >> (NBITS - 1) == 1
+    }
+
+    /// Exact. NaR stays NaR.
+    pub fn abs(&self) -> Self {
+        if self.is_sign_negative() { -*self } else { *self }
+    }
+
+    /// NaR for negative numbers.
+    pub fn sqrt(&self) -> Self {
+        if self.is_nar() || self.is_sign_negative() {
+            return Self::NAR;
+        }
+        if self.is_zero() {
+            return Self::ZERO;
+        }
+        let (_, scale, significand) = self.parts();
+        // With an even scale, the root of the radicand has 64 bits.
+        let radicand = (significand as u128) << (63 + (scale & 1));
+        let (mut root, mut remainder, mut bit) = (0u128, radicand, 1u128 << 126);
+        while bit != 0 {
+            if remainder >= root + bit {
+                remainder -= root + bit;
+                root = (root >> 1) + bit;
+            } else {
+                root >>= 1;
+            }
+            bit >>= 2;
+        }
+        Self::from_parts(false, scale >> 1, root as u64, remainder != 0)
+    }
+
+    /// `value` rounded to nearest (ties to even). NaR for NaN and infinities.
+    pub fn from_f64(value: f64) -> Self {
+        if !value.is_finite() {
+            return Self::NAR;
+        }
+        if value == 0.0 {
+            return Self::ZERO;
+        }
+        let bits = value.to_bits();
+        let (biased, mantissa) = ((bits >> 52 & 0x7ff) as i64, bits & ((1 << 52) - 1));
+        let (scale, significand) = if biased == 0 {
+            // Subnormal.
+            let leading_zeros = mantissa.leading_zeros();
+            (63 - 1074 - leading_zeros as i64, mantissa << leading_zeros)
+        } else {
+            (biased - 1023, 1 << 63 | mantissa << 11)
+        };
+        Self::from_parts(bits >> 63 == 1, scale, significand, false)
+    }
+
+    /// Exact. NaN for NaR.
+    pub fn to_f64(&self) -> f64 {
+        if self.is_nar() {
+            return f64::NAN;
+        }
+        if self.is_zero() {
+            return 0.0;
+        }
+        // The significand has at most 31 bits, and the scale is well within the normal range.
+        let (negative, scale, significand) = self.parts();
+        f64::from_bits((negative as u64) << 63 | ((scale + 1023) as u64) << 52 | (significand << 1) >> 12)
+    }
+
+    /// `value` rounded to nearest (ties to even), once. NaR for NaN and infinities.
+    pub fn from_unifloat<const C: UniFloatChoice>(value: &UniFloat<C>) -> Self where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+    {
+        match C {
+            // Exact as f64.
+            UniFloatChoice::F32 => Self::from_f64(value.to_f64_nearest()),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => Self::from_f64(value.to_f64_nearest()),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat | UniFloatChoice::QuadFloat => {
+                // Exact: it holds any TwoFloat (and QuadFloat).
+                let mut exact = value.convert::<{ MPFR_TWOFLOAT_EXACT }>();
+                exact.copied();
+                unsafe { Self::from_mpfr(exact.mpfr_src().as_ptr()) }
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => {
+                // Rounded to odd, which rounds the same as the exact value for posits.
+                let mut odd = value.decimal_rounded_to_odd();
+                unsafe { Self::from_mpfr(odd.copied().mpfr_src().as_ptr()) }
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+                unsafe { Self::from_mpfr(value.mpfr_src().as_ptr()) },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => {
+                let () = Supported::<C>::TWOFLOAT_VIA_MPFR;
+                unreachable!("Posits from TwoFloat are supported with mpfr feature only.")
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    /// `self` rounded to nearest (ties to even) for choice `C`, once. NaN for NaR.
+    pub fn to_unifloat<const C: UniFloatChoice>(&self) -> UniFloat<C> where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+    {
+        UniFloat::<C>::from_f64(self.to_f64())
+    }
+
+    /// Truncated to 53 bits, which leaves plenty for rounding to `NBITS` correctly. The 53 bits are
+    /// exact as f64 (with a separate exponent).
+    #[cfg(not(feature = "f32_only"))]
+    #[cfg(feature = "mpfr")]
+    unsafe fn from_mpfr(x: mpfr::mpfr_srcptr) -> Self {
+        if mpfr::number_p(x) == 0 {
+            return Self::NAR;
+        }
+        let mut truncated = UniFloat::<{ MPFR_53_BITS }>::NAN;
+        truncated.copied();
+        let mut exponent = 0;
+        let r = truncated.mpfr_mut();
+        let inexact = mpfr::set(r, x, mpfr::rnd_t::RNDZ) != 0;
+        let fraction = mpfr::get_d_2exp(&mut exponent, r, mpfr::rnd_t::RNDN);
+        if fraction == 0.0 {
+            return Self::ZERO;
+        }
+        // `fraction` is in [0.5, 1).
+        let bits = fraction.to_bits();
+        Self::from_parts(bits >> 63 == 1, exponent as i64 - 1, 1 << 63 | bits << 11, inexact)
+    }
+
+    fn parts(&self) -> (bool, i64, u64) {
+        posit_parts(Self::FORMAT, self.bits)
+    }
+
+    fn from_parts(negative: bool, scale: i64, significand: u64, sticky: bool) -> Self {
+        Self { bits: posit_from_parts(Self::FORMAT, negative, scale, significand, sticky) }
+    }
+
+    /// `self` + `other`, or `self` - `other` if `subtract`.
+    fn add_signed(self, other: Self, subtract: bool) -> Self {
+        if self.is_nar() || other.is_nar() {
+            return Self::NAR;
+        }
+        let other = if subtract { -other } else { other };
+        if self.is_zero() {
+            return other;
+        }
+        if other.is_zero() {
+            return self;
+        }
+        // `x` has the greater magnitude (or the same).
+        let (self_parts, other_parts) = (self.parts(), other.parts());
+        let ((x_negative, x_scale, x_significand), (y_negative, y_scale, y_significand)) =
+            if (self_parts.1, self_parts.2) >= (other_parts.1, other_parts.2) {
+                (self_parts, other_parts)
+            } else {
+                (other_parts, self_parts)
+            };
+        // One bit of headroom for a carry. The bits of `y` beyond the lowest one are sticky.
+        let x = (x_significand as u128) << 63;
+        let (y, shift) = ((y_significand as u128) << 63, (x_scale - y_scale) as u64);
+        let y = if shift >= 127 { 1 } else { y >> shift | (y & ((1 << shift) - 1) != 0) as u128 };
+        let sum = if x_negative == y_negative { x + y } else { x - y };
+        if sum == 0 {
+            return Self::ZERO;
+        }
+        let leading_zeros = sum.leading_zeros();
+        let normalized = sum << leading_zeros;
+        Self::from_parts(x_negative, x_scale + 1 - leading_zeros as i64, (normalized >> 64) as u64, normalized as u64 != 0)
+    }
+}
+
+/// Sign, scale and significand (with its highest bit set) of the posit with bit pattern `bits` in
+/// `format`, such that the absolute value is `significand` * 2^(`scale` - 63). For a non-zero
+/// real posit only.
+pub(crate) const fn posit_parts(format: PositFormat, bits: u32) -> (bool, i64, u64) {
+    let (nbits, es) = (format.nbits, format.es);
+    let mask = u32::MAX >> (32 - nbits);
+    let negative = bits >> (nbits - 1) == 1;
+    let magnitude = if negative { bits.wrapping_neg() & mask } else { bits };
+    // Aligned to the top, without the sign bit.
+    let aligned = (magnitude as u64) << (64 - nbits + 1);
+    // The regime: a run of ones (for k >= 0) or zeros, ended by the opposite bit (if any).
+    let (run, k) = if aligned >> 63 == 1 {
+        let run = (!aligned).leading_zeros();
+        (run, run as i64 - 1)
+    } else {
+        let run = aligned.leading_zeros();
+        (run, -(run as i64))
+    };
+    let rest = if run + 1 < 64 { aligned << (run + 1) } else { 0 };
+    let exponent = if es == 0 { 0 } else { rest >> (64 - es) };
+    (negative, (k << es) + exponent as i64, 1 << 63 | (rest << es) >> 1)
+}
+
+/// The bit pattern in `format` of `significand` * 2^(`scale` - 63) (plus a bit more if `sticky`:
+/// less than one unit of the lowest bit of `significand`), with its highest bit set, rounded to
+/// nearest (ties to even), and saturated.
+pub(crate) const fn posit_from_parts(format: PositFormat, negative: bool, scale: i64, significand: u64, sticky: bool) -> u32 {
+    let (nbits, es) = (format.nbits, format.es);
+    let mask = u32::MAX >> (32 - nbits);
+    let max_k = nbits as i64 - 2;
+    let k = scale >> es;
+    let magnitude = if k >= max_k {
+        (1 << (nbits - 1)) - 1
+    } else if k < -max_k {
+        1
+    } else {
+        // The regime, the exponent and the fraction (without the hidden bit), then rounded to
+        // the `nbits` - 1 bits after the sign.
+        let (regime, regime_length) = if k >= 0 {
+            (((1u128 << (k + 1)) - 1) << 1, k as u32 + 2)
+        } else {
+            (1, -k as u32 + 1)
+        };
+        let exponent = (scale & ((1 << es) - 1)) as u128;
+        let body = (regime << es | exponent) << 63 | (significand & !(1 << 63)) as u128;
+        let shift = regime_length + es + 63 - (nbits - 1);
+        let (mut rounded, remainder, half) = ((body >> shift) as u32, body & ((1 << shift) - 1), 1u128 << (shift - 1));
+        if remainder > half || (remainder == half && (sticky || rounded & 1 == 1)) {
+            rounded += 1;
+        }
+        rounded
+    };
+    if negative { magnitude.wrapping_neg() & mask } else { magnitude }
+}
+
+/// The bit pattern in `format` of `x`, a real on its grid (see `limit_posit()`), or NaR.
+#[cfg(feature = "mpfr")]
+pub(crate) unsafe fn posit_bits(format: PositFormat, x: mpfr::mpfr_srcptr) -> u32 {
+    if mpfr::number_p(x) == 0 {
+        1 << (format.nbits - 1)
+    } else if mpfr::zero_p(x) != 0 {
+        0
+    } else {
+        let (negative, scale, significand) = mpfr_parts(x);
+        posit_from_parts(format, negative, scale, significand, false)
+    }
+}
+
+/// Sets `r` to the posit with bit pattern `bits` in `format`: exact, as posits have less
+/// precision than `r` (see `UniFloatChoice::posit()`). NaN for NaR.
+#[cfg(feature = "mpfr")]
+pub(crate) unsafe fn set_posit_bits(format: PositFormat, r: mpfr::mpfr_ptr, bits: u32) {
+    if bits == 0 {
+        mpfr::set_zero(r, 1);
+    } else if bits == 1 << (format.nbits - 1) {
+        mpfr::set_nan(r);
+    } else {
+        let (negative, scale, significand) = posit_parts(format, bits);
+        mpfr::set_uj_2exp(r, significand as _, scale - 63, mpfr::rnd_t::RNDN);
+        if negative {
+            mpfr::neg(r, r, mpfr::rnd_t::RNDN);
+        }
+    }
+}
+
+/// Sign, scale and significand of non-zero real `x`, as in `posit_parts()`. Exact for up to 53
+/// bits of precision.
+#[cfg(feature = "mpfr")]
+unsafe fn mpfr_parts(x: mpfr::mpfr_srcptr) -> (bool, i64, u64) {
+    let mut exponent = 0;
+    // In [0.5, 1).
+    let fraction = mpfr::get_d_2exp(&mut exponent, x, mpfr::rnd_t::RNDN);
+    let bits = fraction.to_bits();
+    (bits >> 63 == 1, exponent as i64 - 1, 1 << 63 | bits << 11)
+}
+
+/// Rounds `result` (of an MPFR operation, whose ternary value was `ternary`) to posit format
+/// `format` (see `UniFloatChoice::posit()`), as `UniPosit` rounds. `result` has at least two bits
+/// more than any posit of the format, so there are no posits (nor midpoints between them)
+/// strictly between `result` and the exact value: a step below or above `result` in the lowest
+/// bits, as per `ternary`, rounds as the exact value. NaN and infinities become NaN (NaR), and
+/// zeros +0, but results that overflowed (or underflowed) MPFR's range saturate. Returns the new
+/// ternary value.
+#[cfg(feature = "mpfr")]
+pub(crate) unsafe fn limit_posit(format: PositFormat, result: mpfr::mpfr_ptr, ternary: i32) -> i32 {
+    if mpfr::nan_p(result) != 0 || (mpfr::inf_p(result) != 0 && ternary == 0) {
+        if mpfr::inf_p(result) != 0 {
+            mpfr::set_nanflag();
+        }
+        mpfr::set_nan(result);
+        return 0;
+    }
+    if mpfr::zero_p(result) != 0 && ternary == 0 {
+        mpfr::set_zero(result, 1);
+        return 0;
+    }
+    let negative = mpfr::signbit(result) != 0;
+    let (scale, significand) = if mpfr::regular_p(result) == 0 {
+        // Overflowed or underflowed MPFR's exponent range: posits saturate instead.
+        (if mpfr::zero_p(result) != 0 { i64::MIN / 2 } else { i64::MAX / 2 }, 1 << 63)
+    } else {
+        let (_, scale, significand) = mpfr_parts(result);
+        // Whether `result` is beyond the exact value (in magnitude).
+        let beyond = if negative { ternary < 0 } else { ternary > 0 };
+        match (beyond, significand == 1 << 63) {
+            // A power of two: the step below is in the binade below.
+            (true, true) => (scale - 1, u64::MAX),
+            (true, false) => (scale, significand - 1),
+            (false, _) => (scale, significand)
+        }
+    };
+    let bits = posit_from_parts(format, negative, scale, significand, ternary != 0);
+    let rounded = posit_parts(format, bits);
+    let outward = (rounded.1, rounded.2) > (scale, significand);
+    set_posit_bits(format, result, bits);
+    if ternary == 0 && (rounded.1, rounded.2) == (scale, significand) {
+        return 0;
+    }
+    mpfr::set_inexflag();
+    if outward == negative { -1 } else { 1 }
+}
+
+impl <const NBITS: u32, const ES: u32> Default for UniPosit<NBITS, ES> where
+Assert<{ valid_posit(NBITS, ES) }>: IsTrue,
+{
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+/// The order of the bit patterns as signed integers, as in the posit standard: NaR first.
+impl <const NBITS: u32, const ES: u32> Ord for UniPosit<NBITS, ES> where
+Assert<{ valid_posit(NBITS, ES) }>: IsTrue,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        let signed = |x: &Self| (x.bits << (32 - NBITS)) as i32;
+        signed(self).cmp(&signed(other))
+    }
+}
+
+impl <const NBITS: u32, const ES: u32> PartialOrd for UniPosit<NBITS, ES> where
+Assert<{ valid_posit(NBITS, ES) }>: IsTrue,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Exact. NaR stays NaR.
+impl <const NBITS: u32, const ES: u32> ops::Neg for UniPosit<NBITS, ES> where
+Assert<{ valid_posit(NBITS, ES) }>: IsTrue,
+{
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self { bits: self.bits.wrapping_neg() & Self::MASK }
+    }
+}
+
+impl <const NBITS: u32, const ES: u32> ops::Add for UniPosit<NBITS, ES> where
+Assert<{ valid_posit(NBITS, ES) }>: IsTrue,
+{
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        self.add_signed(other, false)
+    }
+}
+
+impl <const NBITS: u32, const ES: u32> ops::Sub for UniPosit<NBITS, ES> where
+Assert<{ valid_posit(NBITS, ES) }>: IsTrue,
+{
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self.add_signed(other, true)
+    }
+}
+
+impl <const NBITS: u32, const ES: u32> ops::Mul for UniPosit<NBITS, ES> where
+Assert<{ valid_posit(NBITS, ES) }>: IsTrue,
+{
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        if self.is_nar() || other.is_nar() {
+            return Self::NAR;
+        }
+        if self.is_zero() || other.is_zero() {
+            return Self::ZERO;
+        }
+        let ((x_negative, x_scale, x_significand), (y_negative, y_scale, y_significand)) = (self.parts(), other.parts());
+        let product = x_significand as u128 * y_significand as u128;
+        let (scale, normalized) = if product >> 127 == 1 {
+            (x_scale + y_scale + 1, product)
+        } else {
+            (x_scale + y_scale, product << 1)
+        };
+        Self::from_parts(x_negative != y_negative, scale, (normalized >> 64) as u64, normalized as u64 != 0)
+    }
+}
+
+/// Division by zero gives NaR.
+impl <const NBITS: u32, const ES: u32> ops::Div for UniPosit<NBITS, ES> where
+Assert<{ valid_posit(NBITS, ES) }>: IsTrue,
+{
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        if self.is_nar() || other.is_nar() || other.is_zero() {
+            return Self::NAR;
+        }
+        if self.is_zero() {
+            return Self::ZERO;
+        }
+        let ((x_negative, x_scale, x_significand), (y_negative, y_scale, y_significand)) = (self.parts(), other.parts());
+        let (dividend, divisor) = ((x_significand as u128) << 64, y_significand as u128);
+        let (quotient, remainder) = (dividend / divisor, dividend % divisor);
+        // The quotient is in (2^63, 2^65).
+        let (scale, significand, sticky) = if quotient >> 64 != 0 {
+            (x_scale - y_scale, (quotient >> 1) as u64, quotient & 1 == 1 || remainder != 0)
+        } else {
+            (x_scale - y_scale - 1, quotient as u64, remainder != 0)
+        };
+        Self::from_parts(x_negative != y_negative, scale, significand, sticky)
+    }
+}
+
+/// As f64 (which is exact), or "NaR".
+impl <const NBITS: u32, const ES: u32> fmt::Display for UniPosit<NBITS, ES> where
+Assert<{ valid_posit(NBITS, ES) }>: IsTrue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_nar() {
+            f.pad("NaR")
+        } else {
+            fmt::Display::fmt(&self.to_f64(), f)
+        }
+    }
+}
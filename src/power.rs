@@ -0,0 +1,157 @@
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use {core::{convert::TryFrom, mem::MaybeUninit}, crate::mpfr};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+#[cfg(feature = "twofloat")]
+use crate::parts::twofloat_or_f64;
+
+/// Powers. Accuracy:
+/// - MPFR: correctly rounded.
+/// - f32: computed with f64, so nearly always correctly rounded.
+/// - f64: within 1 ulp (as `libm`).
+/// - TwoFloat: exponentiation by squaring for integer exponents, with relative error up to about
+///   2 * log2(|n|) * 2^-104. Otherwise e^(exp * ln(self)), which loses about log2(|exp * ln(self)|)
+///   more bits.
+/// - Decimals: with MPFR at 192 bits, rounded once more to their digits (see
+///   `UniFloatChoice::decimal()`).
+///
+/// Special cases are as in IEEE 754 (and C's `pow()`): anything to the power of zero is 1, even
+/// NaN; 1 to any power is 1; a negative base with a non-integer exponent gives NaN.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// `self` to the power of `exp`.
+    pub fn pow(&self, exp: &Self) -> Self {
+        self.binary(exp, |x, y| libm::pow(x as f64, y as f64) as f32, libm::pow, twofloat_op!(|x, y| pow_twofloat(x, y)),
+            mpfr_op!(|r, x, y| unsafe { mpfr::pow(r, x, y, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// `self` to the power of an integer `n`.
+    pub fn powi(&self, n: i64) -> Self {
+        self.unary(|x| powi_f64(x as f64, n) as f32, |x| powi_f64(x, n), twofloat_op!(|x| powi_twofloat(x, n)),
+            mpfr_op!(|r, x| unsafe {
+                // `mpfr::exp_t` is `c_long`, the same as the exponent of `mpfr_pow_si()`.
+                match mpfr::exp_t::try_from(n) {
+                    Ok(n) => mpfr::pow_si(r, x, n, mpfr::rnd_t::RNDN),
+                    Err(_) => {
+                        // 64 bits hold any `i64` exactly.
+                        let mut exponent = MaybeUninit::uninit();
+                        mpfr::init2(exponent.as_mut_ptr(), 64);
+                        let exponent = exponent.as_mut_ptr();
+                        mpfr::set_sj(exponent, n, mpfr::rnd_t::RNDN);
+                        let ternary = mpfr::pow(r, x, exponent, mpfr::rnd_t::RNDN);
+                        mpfr::clear(exponent);
+                        ternary
+                    }
+                }
+            }))
+    }
+
+    /// Like `pow()`, but `None` if the result overflows: if it's infinite, while `self` is
+    /// finite and non-zero and `exp` is finite. (Zero to a negative power is an exact infinity,
+    /// so that gives `Some`.)
+    pub fn checked_pow(&self, exp: &Self) -> Option<Self> {
+        let mut result = self.pow(exp);
+        result.copied();
+        let ((finite, _, zero), (exp_finite, _, _), (_, result_infinite, _)) = (self.classified(), exp.classified(),
+            result.classified());
+        if finite && !zero && exp_finite && result_infinite {
+            None
+        } else {
+            Some(result.released())
+        }
+    }
+
+    /// Whether finite, whether infinite, and whether zero.
+    fn classified(&self) -> (bool, bool, bool) {
+        self.assert_copy_fixed();
+        let classify = |x: f64| (x.is_finite(), x.is_infinite(), x == 0.0);
+        match C {
+            UniFloatChoice::F32 => classify(self.f32s[0] as f64),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => classify(self.f64s[0]),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat | UniFloatChoice::QuadFloat => classify(self.twofloats[0].hi()),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => {
+                let decimal = self.decimal();
+                (!decimal.is_nan() && !decimal.is_infinite(), decimal.is_infinite(), decimal.is_zero())
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => unsafe {
+                let source = self.mpfr_src();
+                let x = source.as_ptr();
+                (mpfr::number_p(x) != 0, mpfr::inf_p(x) != 0, mpfr::zero_p(x) != 0)
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+}
+
+/// The sign comes from the parity of `n`, so it's right even where `n as f64` isn't exact.
+fn powi_f64(x: f64, n: i64) -> f64 {
+    let magnitude = libm::pow(libm::fabs(x), n as f64);
+    if x.is_sign_negative() && n % 2 != 0 { -magnitude } else { magnitude }
+}
+
+/// Exponentiation by squaring. For a negative `n` it inverts the result at the end, or (if
+/// the positive power would overflow) it inverts `x` first.
+#[cfg(feature = "twofloat")]
+fn powi_twofloat(x: TwoFloat, n: i64) -> TwoFloat {
+    let approximation = powi_f64(x.hi(), n);
+    if !(x.hi().is_finite() && x.hi() != 0.0 && approximation.is_finite() && approximation != 0.0) {
+        return TwoFloat::from(approximation);
+    }
+    let invert_first = n < 0 && !libm::pow(libm::fabs(x.hi()), n.unsigned_abs() as f64).is_finite();
+    let mut base = if invert_first { TwoFloat::from(1.0) / x } else { x };
+    let (mut result, mut remaining) = (TwoFloat::from(1.0), n.unsigned_abs());
+    while remaining > 0 {
+        if remaining & 1 == 1 {
+            result = result * base;
+        }
+        remaining >>= 1;
+        if remaining > 0 {
+            base = base * base;
+        }
+    }
+    if n < 0 && !invert_first { TwoFloat::from(1.0) / result } else { result }
+}
+
+#[cfg(feature = "twofloat")]
+fn pow_twofloat(x: TwoFloat, y: TwoFloat) -> TwoFloat {
+    const TWO_POW_63: f64 = 9_223_372_036_854_775_808.0;
+    let is_integer = y.hi().is_finite() && y.trunc() == y;
+    if is_integer && libm::fabs(y.hi()) < TWO_POW_63 {
+        // Both parts are integers.
+        return powi_twofloat(x, y.hi() as i64 + y.lo() as i64);
+    }
+    let approximation = libm::pow(x.hi(), y.hi());
+    if !y.hi().is_finite() {
+        return TwoFloat::from(approximation);
+    }
+    // A negative `x` has a (huge) integer `y` here, otherwise `approximation` is NaN.
+    twofloat_or_f64(x, approximation, |x| {
+        let magnitude = (y * x.abs().ln()).exp();
+        let half = y * 0.5;
+        if x.hi() < 0.0 && half.trunc() != half { -magnitude } else { magnitude }
+    })
+}
@@ -0,0 +1,360 @@
+#![cfg_attr(any(feature = "f32_only", not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+
+use core::ops;
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use crate::mpfr;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use core::ptr;
+
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::convert::UniMpfrTwoFloatExact;
+#[cfg(not(feature = "f32_only"))]
+use crate::{MpfrBounds, UniFloatChoice};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(any(feature = "mpfr", feature = "twofloat"))]
+use crate::UniFloat;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "twofloat")]
+use crate::{TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Precision of MPFR results that become a `Quad`: 4 * 53 bits.
+#[cfg(not(feature = "f32_only"))]
+const MPFR_QUADFLOAT: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(4 * f64::MANTISSA_DIGITS as usize)
+};
+
+/// A quad-double (as in the QD library by Hida, Li and Bailey): the exact sum of four f64
+/// components, in decreasing magnitude, each at most half an ulp of the one before. That's 212
+/// bits of significand, with the exponent range of f64. NaN and infinities are in the first
+/// component, and the others are zero then.
+///
+/// The arithmetic operators use error-free transformations of f64 (with `libm::fma()` for
+/// products), so they're much faster than MPFR at this precision. They're not correctly rounded:
+/// the relative error is within a few units of 2^-208. Components below f64's subnormal range get
+/// lost, as for TwoFloat. Comparisons are lexicographic, which orders normalized components by
+/// value.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub(crate) struct Quad(pub(crate) [f64; 4]);
+
+/// `a + b` as `(sum, error)`, exactly, where `sum` is `a + b` rounded to nearest.
+pub(crate) fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    (sum, (a - (sum - b_virtual)) + (b - b_virtual))
+}
+
+/// As `two_sum()`, but only for |a| >= |b| (or a being zero).
+pub(crate) fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    (sum, b - (sum - a))
+}
+
+/// `a * b` as `(product, error)`, exactly (unless the error underflows).
+pub(crate) fn two_prod(a: f64, b: f64) -> (f64, f64) {
+    let product = a * b;
+    (product, libm::fma(a, b, -product))
+}
+
+/// `a + b + c` as three terms, the first one being the sum rounded to nearest.
+fn three_sum(a: f64, b: f64, c: f64) -> (f64, f64, f64) {
+    let (t1, t2) = two_sum(a, b);
+    let (a, t3) = two_sum(c, t1);
+    let (b, c) = two_sum(t2, t3);
+    (a, b, c)
+}
+
+/// Adds `c` to the accumulator `(a, b)`. Returns what doesn't fit into the accumulator any more, or
+/// 0 if it all fits.
+fn quick_three_accum(a: &mut f64, b: &mut f64, c: f64) -> f64 {
+    let (sum, low) = two_sum(*b, c);
+    let (sum, high) = two_sum(*a, sum);
+    if high != 0.0 && low != 0.0 {
+        *a = high;
+        *b = low;
+        return sum;
+    }
+    if low == 0.0 {
+        *b = high;
+    } else {
+        *b = low;
+    }
+    *a = sum;
+    0.0
+}
+
+impl Quad {
+    pub(crate) fn from_f64(value: f64) -> Self {
+        Quad([value, 0.0, 0.0, 0.0])
+    }
+
+    /// Exact.
+    #[cfg(feature = "twofloat")]
+    pub(crate) fn from_twofloat(value: TwoFloat) -> Self {
+        if value.hi().is_finite() {
+            Quad([value.hi(), value.lo(), 0.0, 0.0])
+        } else {
+            Self::from_f64(value.hi())
+        }
+    }
+
+    /// The sum of `terms` (up to five, in roughly decreasing magnitude), as normalized components:
+    /// sums from the bottom up, and then from the top down (skipping zeros). NaN, infinities and
+    /// overflows are as for f64.
+    fn renormalized(terms: &[f64]) -> Self {
+        let mut terms_padded = [0.0; 5];
+        terms_padded[..terms.len()].copy_from_slice(terms);
+        let mut sum = terms_padded[4];
+        for index in (0..4).rev() {
+            let (high, low) = quick_two_sum(terms_padded[index], sum);
+            sum = high;
+            terms_padded[index + 1] = low;
+        }
+        let mut components = [0.0; 4];
+        let mut count = 0;
+        for &term in terms_padded[1..].iter() {
+            if count == 3 {
+                sum += term;
+            } else {
+                let (high, low) = quick_two_sum(sum, term);
+                if low == 0.0 {
+                    sum = high;
+                } else {
+                    components[count] = high;
+                    count += 1;
+                    sum = low;
+                }
+            }
+        }
+        components[count] = sum;
+        if components.iter().all(|component| component.is_finite()) {
+            Quad(components)
+        } else {
+            Self::from_f64(terms.iter().sum::<f64>())
+        }
+    }
+
+    /// Sets `r` to `self`, exactly if `r` has 2098 bits of precision (see `MPFR_TWOFLOAT_EXACT`).
+    #[cfg(feature = "mpfr")]
+    pub(crate) unsafe fn to_mpfr(self, r: mpfr::mpfr_ptr) {
+        mpfr::set_d(r, self.0[0], mpfr::rnd_t::RNDN);
+        // Adding zeros would lose the sign of -0.
+        for &component in self.0[1..].iter().filter(|&&component| component != 0.0) {
+            mpfr::add_d(r, r, component, mpfr::rnd_t::RNDN);
+        }
+    }
+
+    /// `value` rounded to nearest, one component after another, each from the exact difference (as
+    /// for TwoFloat in `UniFloat::from_mpfr()`), unless `value` has more than 2098 bits of
+    /// precision.
+    #[cfg(not(feature = "f32_only"))]
+    #[cfg(feature = "mpfr")]
+    pub(crate) unsafe fn from_mpfr(value: mpfr::mpfr_srcptr) -> Self {
+        let mut result = Self::from_f64(mpfr::get_d(value, mpfr::rnd_t::RNDN));
+        if result.0[0].is_finite() && result.0[0] != 0.0 {
+            let mut rest = UniMpfrTwoFloatExact::NAN;
+            rest.copied();
+            let rest = rest.mpfr_mut();
+            mpfr::sub_d(rest, value, result.0[0], mpfr::rnd_t::RNDN);
+            for index in 1..4 {
+                result.0[index] = mpfr::get_d(rest, mpfr::rnd_t::RNDN);
+                mpfr::sub_d(rest, rest, result.0[index], mpfr::rnd_t::RNDN);
+            }
+        }
+        result
+    }
+
+    /// `mpfr_op` (given the result first, then the operands, as in `UniFloat::unary()` and
+    /// others) on `operands`, which are exact in MPFR. The result is rounded to 212 bits, and then
+    /// as by `from_mpfr()`. That's how QuadFloat does what it has no native arithmetic for.
+    #[cfg(not(feature = "f32_only"))]
+    #[cfg(feature = "mpfr")]
+    pub(crate) fn via_mpfr<const N: usize>(operands: [Quad; N],
+        mpfr_op: impl FnOnce(mpfr::mpfr_ptr, [mpfr::mpfr_srcptr; N]) -> i32) -> Self {
+        let mut exact = [UniMpfrTwoFloatExact::NAN; N];
+        let mut pointers = [ptr::null(); N];
+        for index in 0..N {
+            exact[index].copied();
+            let pointer = exact[index].mpfr_mut();
+            unsafe { operands[index].to_mpfr(pointer) };
+            pointers[index] = pointer as mpfr::mpfr_srcptr;
+        }
+        let mut result = UniFloat::<{ MPFR_QUADFLOAT }>::NAN;
+        result.copied();
+        mpfr_op(result.mpfr_mut(), pointers);
+        unsafe { Self::from_mpfr(result.mpfr_src().as_ptr()) }
+    }
+}
+
+impl ops::Neg for Quad {
+    type Output = Self;
+    fn neg(self) -> Self {
+        let [c0, c1, c2, c3] = self.0;
+        Quad([-c0, -c1, -c2, -c3])
+    }
+}
+
+/// As `ieee_add()` of the QD library: the components of both sides merged by magnitude, and added
+/// into a two-term accumulator.
+impl ops::Add for Quad {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let (a, b) = (self.0, other.0);
+        if !a[0].is_finite() || !b[0].is_finite() || (a[0] == 0.0 && b[0] == 0.0) {
+            return Self::from_f64(a[0] + b[0]);
+        }
+        let (mut i, mut j) = (0, 0);
+        let mut next = || if j >= 4 || (i < 4 && libm::fabs(a[i]) > libm::fabs(b[j])) {
+            i += 1;
+            a[i - 1]
+        } else {
+            j += 1;
+            b[j - 1]
+        };
+        let (first, second) = (next(), next());
+        let (mut u, mut v) = quick_two_sum(first, second);
+        let mut taken = 2;
+        let mut components = [0.0; 4];
+        let mut count = 0;
+        while count < 4 {
+            if taken == 8 {
+                components[count] = u;
+                if count < 3 {
+                    components[count + 1] = v;
+                }
+                break;
+            }
+            let spilled = quick_three_accum(&mut u, &mut v, next());
+            taken += 1;
+            if spilled != 0.0 {
+                components[count] = spilled;
+                count += 1;
+            }
+        }
+        // The rest is below all four components.
+        for _ in taken..8 {
+            components[3] += next();
+        }
+        Self::renormalized(&components)
+    }
+}
+
+impl ops::Sub for Quad {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+/// As `sloppy_mul()` of the QD library: the products of components down to the order of 2^-159
+/// relative are exact, and the rest is summed in f64.
+impl ops::Mul for Quad {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let (a, b) = (self.0, other.0);
+        let (p0, q0) = two_prod(a[0], b[0]);
+        if !p0.is_finite() || p0 == 0.0 {
+            return Self::from_f64(p0);
+        }
+        let (p1, q1) = two_prod(a[0], b[1]);
+        let (p2, q2) = two_prod(a[1], b[0]);
+        let (p3, q3) = two_prod(a[0], b[2]);
+        let (p4, q4) = two_prod(a[1], b[1]);
+        let (p5, q5) = two_prod(a[2], b[0]);
+
+        let (p1, p2, q0) = three_sum(p1, p2, q0);
+
+        // (p2, q1, q2) + (p3, p4, p5) as (s0, s1, s2).
+        let (p2, q1, q2) = three_sum(p2, q1, q2);
+        let (p3, p4, p5) = three_sum(p3, p4, p5);
+        let (s0, t0) = two_sum(p2, p3);
+        let (s1, t1) = two_sum(q1, p4);
+        let s2 = q2 + p5;
+        let (s1, t0) = two_sum(s1, t0);
+        let s2 = s2 + (t0 + t1);
+
+        // Terms of the order of 2^-159.
+        let (p6, q6) = two_prod(a[0], b[3]);
+        let (p7, q7) = two_prod(a[1], b[2]);
+        let (p8, q8) = two_prod(a[2], b[1]);
+        let (p9, q9) = two_prod(a[3], b[0]);
+
+        // q0, s1, q3, q4, q5, p6, p7, p8 and p9 as two terms (t0, t1).
+        let (q0, q3) = two_sum(q0, q3);
+        let (q4, q5) = two_sum(q4, q5);
+        let (p6, p7) = two_sum(p6, p7);
+        let (p8, p9) = two_sum(p8, p9);
+        let (t0, t1) = two_sum(q0, q4);
+        let t1 = t1 + (q3 + q5);
+        let (r0, r1) = two_sum(p6, p8);
+        let r1 = r1 + (p7 + p9);
+        let (q3, q4) = two_sum(t0, r0);
+        let q4 = q4 + (t1 + r1);
+        let (t0, t1) = two_sum(q3, s1);
+        let t1 = t1 + q4;
+
+        // Terms of the order of 2^-212.
+        let t1 = t1 + (a[1] * b[3] + a[2] * b[2] + a[3] * b[1] + q6 + q7 + q8 + q9 + s2);
+
+        Self::renormalized(&[p0, p1, s0, t0, t1])
+    }
+}
+
+/// As `accurate_div()` of the QD library: long division, with five quotient digits.
+impl ops::Div for Quad {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        let divisor = other.0[0];
+        let mut quotient = [self.0[0] / divisor, 0.0, 0.0, 0.0, 0.0];
+        if !quotient[0].is_finite() || quotient[0] == 0.0 {
+            return Self::from_f64(quotient[0]);
+        }
+        let mut remainder = self - other * Self::from_f64(quotient[0]);
+        for index in 1..5 {
+            quotient[index] = remainder.0[0] / divisor;
+            if index < 4 {
+                remainder = remainder - other * Self::from_f64(quotient[index]);
+            }
+        }
+        Self::renormalized(&quotient)
+    }
+}
+
+/// QuadFloat keeps its components in both TwoFloat parts: the first two in `twofloats[0]`, the
+/// other two in `twofloats[1]`.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "twofloat")]
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub(crate) fn quad(&self) -> Quad {
+        let (high, low) = (self.twofloats[0], self.twofloats[1]);
+        if high.hi().is_finite() {
+            Quad([high.hi(), high.lo(), low.hi(), low.lo()])
+        } else {
+            Quad::from_f64(high.hi())
+        }
+    }
+
+    /// `op` on the components of `self`, for what QuadFloat can't run in MPFR.
+    pub(crate) fn quad_unary(&self, op: impl FnOnce(Quad) -> Quad) -> Self {
+        let mut result = Self::NAN;
+        result.set_quad(op(self.quad()));
+        result
+    }
+
+    pub(crate) fn set_quad(&mut self, value: Quad) {
+        let value = if value.0[0].is_finite() { value } else { Quad::from_f64(value.0[0]) };
+        // TwoFloat::new_add() is exact, but it would turn -0 into +0.
+        let pair = |high: f64, low: f64| if low == 0.0 { TwoFloat::from(high) } else { TwoFloat::new_add(high, low) };
+        self.twofloats[0] = pair(value.0[0], value.0[1]);
+        self.twofloats[1] = pair(value.0[2], value.0[3]);
+    }
+}
@@ -0,0 +1,499 @@
+use core::{cmp::Ordering, fmt};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::mpfr;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{MAX_FORMAT_DIGITS, MAX_PARSE_LENGTH, ParseErrorKind, ParseUniFloatError, RoundingMode, UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+use crate::{big::Big, format::Digits, hex::{EXPONENT_LIMIT, HexLiteral}};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::{parse::parse_mpfr, parts::limit_exponent_range};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::convert::UniMpfrTwoFloatExact;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+
+/// 256 * 32 = 8192 bits are enough for `MAX_PARSE_LENGTH` digits in base 62 (6100 bits), divided
+/// by a power of the base that keeps the result within (far) f64 subnormals, and for what's left
+/// of that value past the nearest f64, scaled by up to 2^1074 (for the lower part of a TwoFloat).
+type Big8192 = Big<256>;
+
+fn assert_base(base: u32) {
+    assert!((2..=62).contains(&base), "The base must be between 2 and 62, but it's {}.", base);
+}
+
+/// Number of significant digits in `base` that are enough for any value with `precision_bits` to
+/// parse back to itself. The same as MPFR's `mpfr_get_str_ndigits()`.
+fn round_trip_digits(precision_bits: usize, base: u32) -> usize {
+    if base.is_power_of_two() {
+        let bits_per_digit = base.trailing_zeros() as usize;
+        1 + (precision_bits - 1 + bits_per_digit - 1) / bits_per_digit
+    } else {
+        1 + libm::ceil(precision_bits as f64 / libm::log2(base as f64)) as usize
+    }
+}
+
+/// Write `digits` (of a non-zero value), rounded to `count` digits. If the integer part fits into
+/// `count` digits, then it's positional, like "zz.i". Otherwise it's with one digit before '.' and
+/// an exponent, like "1.i@-3" (for 1.5 * 36^-3). The exponent is in decimal.
+fn write_digits(writer: &mut impl fmt::Write, negative: bool, digits: &Digits, count: usize) -> fmt::Result {
+    if negative {
+        writer.write_char('-')?;
+    }
+    let (length, exponent) = (digits.length, digits.exponent);
+    if exponent >= 1 && exponent as usize <= count {
+        let integer = exponent as usize;
+        writer.write_str(digits.digits(0..integer.min(length)))?;
+        for _ in length..integer {
+            writer.write_char('0')?;
+        }
+        if length > integer {
+            writer.write_char('.')?;
+            writer.write_str(digits.digits(integer..length))?;
+        }
+        Ok(())
+    } else {
+        writer.write_str(digits.digits(0..1))?;
+        if length > 1 {
+            writer.write_char('.')?;
+            writer.write_str(digits.digits(1..length))?;
+        }
+        write!(writer, "@{}", exponent - 1)
+    }
+}
+
+fn write_radix_f64(writer: &mut impl fmt::Write, value: f64, base: u32, count: usize) -> fmt::Result {
+    if value.is_nan() {
+        writer.write_str("@nan@")
+    } else if value.is_infinite() {
+        writer.write_str(if value < 0.0 { "-@inf@" } else { "@inf@" })
+    } else if value == 0.0 {
+        writer.write_str(if value.is_sign_negative() { "-0" } else { "0" })
+    } else {
+        write_digits(writer, value < 0.0, &Digits::from_f64(value, base, count, RoundingMode::Nearest), count)
+    }
+}
+
+/// A literal in any base: value = significand * base^exponent * 2^binary_exponent, where `digits`
+/// make up the significand (with no leading zeros). The binary exponent is for hex float text.
+pub(crate) struct RadixLiteral {
+    pub(crate) negative: bool,
+    digits: [u8; MAX_PARSE_LENGTH],
+    length: usize,
+    pub(crate) exponent: i64,
+    pub(crate) binary_exponent: i64,
+}
+
+impl RadixLiteral {
+    /// The digit values of the significand (not characters).
+    #[cfg(all(feature = "softmpfr", not(feature = "mpfr"), not(feature = "f32_only")))]
+    pub(crate) fn digits(&self) -> &[u8] {
+        &self.digits[..self.length]
+    }
+
+    /// The value as numerator / denominator, or `Err` with the value as `HexLiteral`, if it's zero
+    /// or far beyond the range of f64 (with a huge or a tiny exponent), where the digits don't
+    /// matter.
+    fn to_fraction(&self, base: u32) -> Result<(Big8192, Big8192), HexLiteral> {
+        let mut far = HexLiteral { negative: self.negative, significand: 0, exponent: 0, sticky: false };
+        if self.length == 0 {
+            return Err(far);
+        }
+        // base^(length - 1 + exponent) <= value / 2^binary_exponent < base^(length + exponent)
+        let log2_base = libm::log2(base as f64);
+        let (length, binary_exponent) = (self.length as i64, self.binary_exponent as f64);
+        if (length - 1 + self.exponent) as f64 * log2_base + binary_exponent > 1100.0 {
+            far.significand = 1;
+            far.exponent = EXPONENT_LIMIT;
+            return Err(far);
+        } else if (length + self.exponent) as f64 * log2_base + binary_exponent < -1200.0 {
+            far.significand = 1;
+            far.exponent = -EXPONENT_LIMIT;
+            return Err(far);
+        }
+        let (mut numerator, mut denominator) = (Big8192::ZERO, Big8192::new(1, 0));
+        for &digit in self.digits[..self.length].iter() {
+            numerator.mul_small(base);
+            numerator.add_small(digit as u32);
+        }
+        for _ in 0..self.exponent.abs() {
+            if self.exponent >= 0 {
+                numerator.mul_small(base);
+            } else {
+                denominator.mul_small(base);
+            }
+        }
+        if self.binary_exponent >= 0 {
+            numerator = numerator.shifted_left(self.binary_exponent as u32);
+        } else {
+            denominator = denominator.shifted_left(-self.binary_exponent as u32);
+        }
+        Ok((numerator, denominator))
+    }
+
+    /// Exact (or with `sticky` set), for rounding to f32 or f64.
+    fn to_binary(&self, base: u32) -> HexLiteral {
+        match self.to_fraction(base) {
+            Ok((numerator, denominator)) => divide(self.negative, &numerator, &denominator),
+            Err(far) => far
+        }
+    }
+
+    /// Rounded to the nearest double-double: the higher part is the nearest f64, and the lower
+    /// part is the nearest f64 to the exact rest.
+    #[cfg(not(feature = "f32_only"))]
+    #[cfg(feature = "twofloat")]
+    pub(crate) fn to_twofloat(&self, base: u32) -> TwoFloat {
+        let (numerator, denominator) = match self.to_fraction(base) {
+            Ok(fraction) => fraction,
+            Err(far) => return TwoFloat::from(far.to_f64())
+        };
+        let high = divide(self.negative, &numerator, &denominator).to_f64();
+        if !high.is_finite() || high == 0.0 {
+            return TwoFloat::from(high);
+        }
+        let bits = high.to_bits();
+        let (biased_exponent, mantissa) = ((bits >> 52) & 0x7ff, bits & ((1 << 52) - 1));
+        // |high| = significand * 2^exponent
+        let (significand, exponent) = if biased_exponent == 0 {
+            (mantissa, -1074)
+        } else {
+            (mantissa | 1 << 52, biased_exponent as i64 - 1075)
+        };
+        // |value| - |high| = (scaled - product) / denominator * 2^min(exponent, 0)
+        let (scaled, product) = if exponent >= 0 {
+            (numerator, denominator.mul(&Big8192::new(significand, exponent as u32)))
+        } else {
+            (numerator.shifted_left(-exponent as u32), denominator.mul(&Big8192::new(significand, 0)))
+        };
+        let mut low = if scaled.compare(&product) == Ordering::Less {
+            let mut rest = product;
+            rest.sub(&scaled);
+            divide(!self.negative, &rest, &denominator)
+        } else {
+            let mut rest = scaled;
+            rest.sub(&product);
+            divide(self.negative, &rest, &denominator)
+        };
+        low.exponent += exponent.min(0);
+        TwoFloat::new_add(high, low.to_f64())
+    }
+}
+
+/// `numerator / denominator` (with the sign), with 58 or 59 significant bits, and with `sticky` set
+/// if that's not exact. `denominator` must not be zero.
+fn divide(negative: bool, numerator: &Big8192, denominator: &Big8192) -> HexLiteral {
+    let mut result = HexLiteral { negative, significand: 0, exponent: 0, sticky: false };
+    if numerator.is_zero() {
+        return result;
+    }
+    // Scale so that the quotient has 58 or 59 bits, which is more than f64 needs.
+    let shift = denominator.bit_length() as i64 - numerator.bit_length() as i64 + 58;
+    let (mut dividend, divisor) = if shift >= 0 {
+        (numerator.shifted_left(shift as u32), *denominator)
+    } else {
+        (*numerator, denominator.shifted_left(-shift as u32))
+    };
+    for bit in (0..60).rev() {
+        let shifted = divisor.shifted_left(bit);
+        if dividend.compare(&shifted) != Ordering::Less {
+            dividend.sub(&shifted);
+            result.significand |= 1 << bit;
+        }
+    }
+    result.exponent = -shift;
+    result.sticky = !dividend.is_zero();
+    result
+}
+
+/// The value of `character` as a digit in `base`, if any. Letters are case-insensitive for bases
+/// up to 36. For greater bases "A-Z" are 10 to 35, and "a-z" are 36 to 61 (as in MPFR).
+fn digit_value(character: u8, base: u32) -> Option<u32> {
+    let value = match character {
+        b'0'..=b'9' => (character - b'0') as u32,
+        b'a'..=b'z' if base <= 36 => (character - b'a') as u32 + 10,
+        b'a'..=b'z' => (character - b'a') as u32 + 36,
+        b'A'..=b'Z' => (character - b'A') as u32 + 10,
+        _ => return None
+    };
+    if value < base { Some(value) } else { None }
+}
+
+/// Text in `base`: an optional sign, then either "@inf@" or "@nan@", or digits with an optional
+/// '.', and an optional exponent ('@', an optional sign and decimal digits) of the base. For
+/// example "zz.i@-2" in base 36. The same syntax as of MPFR's `mpfr_strtofr()`, except for its
+/// base-specific prefixes and exponents. Numbers are returned as `Ok(Some(..))`, and the special
+/// values as `Ok(None)`.
+pub(crate) fn parse_radix_literal(s: &str, base: u32) -> Result<Option<RadixLiteral>, ParseUniFloatError> {
+    let error = |kind, position| Err(ParseUniFloatError::new(kind, position));
+    let bytes = s.as_bytes();
+    if bytes.is_empty() {
+        return error(ParseErrorKind::Empty, 0);
+    } else if bytes.len() > MAX_PARSE_LENGTH {
+        return error(ParseErrorKind::TooLong, MAX_PARSE_LENGTH);
+    }
+    let negative = bytes[0] == b'-';
+    let mut position = if bytes[0] == b'+' || bytes[0] == b'-' { 1 } else { 0 };
+    if matches!(&s[position..], "@inf@" | "@nan@") {
+        return Ok(None);
+    }
+
+    let mut literal = RadixLiteral { negative, digits: [0; MAX_PARSE_LENGTH], length: 0, exponent: 0, binary_exponent: 0 };
+    let (mut digits, mut dot) = (0, false);
+    while position < bytes.len() {
+        match bytes[position] {
+            b'.' if !dot => dot = true,
+            character => match digit_value(character, base) {
+                Some(digit) => {
+                    digits += 1;
+                    // Skip leading zeros.
+                    if digit != 0 || literal.length > 0 {
+                        literal.digits[literal.length] = digit as u8;
+                        literal.length += 1;
+                    }
+                    if dot {
+                        literal.exponent -= 1;
+                    }
+                },
+                None => break
+            }
+        }
+        position += 1;
+    }
+    if digits == 0 {
+        return if position < bytes.len() && bytes[position] != b'@' {
+            error(ParseErrorKind::InvalidCharacter, position)
+        } else {
+            error(ParseErrorKind::MissingDigits, position)
+        };
+    }
+
+    if position < bytes.len() && bytes[position] == b'@' {
+        position += 1;
+        let exponent_negative = bytes.get(position) == Some(&b'-');
+        if matches!(bytes.get(position), Some(b'+') | Some(b'-')) {
+            position += 1;
+        }
+        let exponent_start = position;
+        let mut exponent: i64 = 0;
+        while position < bytes.len() && bytes[position].is_ascii_digit() {
+            exponent = (exponent * 10 + (bytes[position] - b'0') as i64).min(EXPONENT_LIMIT);
+            position += 1;
+        }
+        if position == exponent_start {
+            return if position == bytes.len() {
+                error(ParseErrorKind::MissingDigits, position)
+            } else {
+                error(ParseErrorKind::InvalidCharacter, position)
+            };
+        }
+        literal.exponent += if exponent_negative { -exponent } else { exponent };
+    }
+    if position < bytes.len() {
+        return error(ParseErrorKind::InvalidCharacter, position);
+    }
+    // Trailing zeros only make the numbers bigger.
+    while literal.length > 0 && literal.digits[literal.length - 1] == 0 {
+        literal.length -= 1;
+        literal.exponent += 1;
+    }
+    Ok(Some(literal))
+}
+
+/// Decimal text that `parse::validate()` accepted, with all its digits, or `Ok(None)` for
+/// infinities and NaN. The text can have at most `MAX_PARSE_LENGTH` bytes.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(any(feature = "twofloat", all(feature = "softmpfr", not(feature = "mpfr"))))]
+pub(crate) fn parse_decimal_literal(s: &str) -> Result<Option<RadixLiteral>, ParseUniFloatError> {
+    if s.len() > MAX_PARSE_LENGTH {
+        return Err(ParseUniFloatError::new(ParseErrorKind::TooLong, MAX_PARSE_LENGTH));
+    }
+    let (significand, exponent) = match s.find(|c| c == 'e' || c == 'E') {
+        Some(index) => (&s[..index], &s[index + 1..]),
+        None => (s, "")
+    };
+    if !significand.trim_start_matches(|c| c == '+' || c == '-').starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return Ok(None);
+    }
+    Ok(parse_radix_literal(significand, 10)?.map(|mut literal| {
+        literal.exponent += saturated_exponent(exponent);
+        literal
+    }))
+}
+
+/// The value of an exponent that has been validated already: an optional sign and decimal digits
+/// (or nothing, for 0). Saturated at `EXPONENT_LIMIT`.
+#[cfg(not(feature = "f32_only"))]
+#[cfg(any(feature = "twofloat", all(feature = "softmpfr", not(feature = "mpfr"))))]
+pub(crate) fn saturated_exponent(text: &str) -> i64 {
+    let magnitude = text.trim_start_matches(|c| c == '+' || c == '-').bytes()
+        .fold(0, |exponent: i64, digit| (exponent * 10 + (digit - b'0') as i64).min(EXPONENT_LIMIT));
+    if text.starts_with('-') { -magnitude } else { magnitude }
+}
+
+/// Text in any base from 2 to 62, as in MPFR's `mpfr_get_str()` and `mpfr_strtofr()`. For
+/// example, base 36 for data exchange, or base 3 for exact thirds.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Write the value in `base`, rounded (to nearest, ties to even) to `digits` significant
+    /// digits. If `digits` is 0, use enough digits for the value to parse back to itself (for
+    /// TwoFloat and QuadFloat as if they had 106 or 212 bits). At most `MAX_FORMAT_DIGITS` digits
+    /// are used. Decimals round once in base 10, and otherwise from 2098 bits. Digits are "0-9a-z" for bases up to 36, and "0-9A-Za-z" for greater bases.
+    /// Trailing zeros after the point are skipped. See `from_str_radix()` for the syntax, for
+    /// example "-zz.i" or "1.i@-3" in base 36 (the exponent is of the base, in decimal), "0",
+    /// "@inf@" or "@nan@".
+    ///
+    /// Panics if `base` is not between 2 and 62.
+    pub fn write_radix(&self, writer: &mut impl fmt::Write, base: u32, digits: usize) -> fmt::Result {
+        self.assert_copy_fixed();
+        assert_base(base);
+        let count = if digits == 0 { round_trip_digits(C.precision_bits(), base) } else { digits };
+        let count = count.min(MAX_FORMAT_DIGITS);
+        match C {
+            UniFloatChoice::F32 => write_radix_f64(writer, self.f32s[0] as f64, base, count),
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => write_radix_f64(writer, self.f64s[0], base, count),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => {
+                let (high, low) = (self.twofloats[0].hi(), self.twofloats[0].lo());
+                if !high.is_finite() || high == 0.0 {
+                    return write_radix_f64(writer, high, base, count);
+                }
+                write_digits(writer, high < 0.0, &Digits::from_sum(high, low, base, count, RoundingMode::Nearest), count)
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                let quad = self.quad();
+                if !quad.0[0].is_finite() || quad.0[0] == 0.0 {
+                    return write_radix_f64(writer, quad.0[0], base, count);
+                }
+                let mut exact = UniMpfrTwoFloatExact::NAN;
+                exact.copied();
+                let exact = exact.mpfr_mut();
+                unsafe { quad.to_mpfr(exact) };
+                write_digits(writer, quad.0[0] < 0.0, &Digits::from_mpfr(exact, base, count, RoundingMode::Nearest), count)
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => {
+                let value = self.decimal();
+                if value.is_nan() || value.is_infinite() || value.is_zero() {
+                    return write_radix_f64(writer, value.to_f64(), base, count);
+                }
+                let digits = if base == 10 {
+                    value.to_digits(count, RoundingMode::Nearest)
+                } else {
+                    let mut odd = self.decimal_rounded_to_odd();
+                    Digits::from_mpfr(odd.copied().mpfr_src().as_ptr(), base, count, RoundingMode::Nearest)
+                };
+                write_digits(writer, value.is_sign_negative(), &digits, count)
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let source = self.mpfr_src();
+                let x = source.as_ptr();
+                if unsafe { mpfr::regular_p(x) } == 0 {
+                    return write_radix_f64(writer, unsafe { mpfr::get_d(x, mpfr::rnd_t::RNDN) }, base, count);
+                }
+                let negative = unsafe { mpfr::signbit(x) } != 0;
+                write_digits(writer, negative, &Digits::from_mpfr(x, base, count, RoundingMode::Nearest), count)
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+    }
+
+    /// See `write_radix()`.
+    #[cfg(feature = "alloc")]
+    pub fn to_string_radix(&self, base: u32, digits: usize) -> String {
+        let mut result = String::new();
+        // Writing to a String doesn't fail.
+        let _ = self.write_radix(&mut result, base, digits);
+        result
+    }
+
+    /// Parse text in `base`, as written by `write_radix()`: an optional sign, then digits with an
+    /// optional '.', and an optional exponent ('@', an optional sign and decimal digits) of the
+    /// base. Or "@inf@" or "@nan@" (with an optional sign). Letters are case-insensitive for bases
+    /// up to 36. The result is correctly rounded (to nearest, ties to even) for f32, f64 and
+    /// MPFR. TwoFloat gets the nearest double-double (see `FromStr`). QuadFloat is rounded to 2098
+    /// bits first, and then to the nearest quad-double, and likewise for decimals. As with
+    /// `FromStr`, MPFR results are then limited to the bounds. The text can have at most `MAX_PARSE_LENGTH` bytes.
+    ///
+    /// Panics if `base` is not between 2 and 62.
+    pub fn from_str_radix(s: &str, base: u32) -> Result<Self, ParseUniFloatError> {
+        assert_base(base);
+        let literal = parse_radix_literal(s, base)?;
+        let special = |negative| match (s.ends_with("@nan@"), negative) {
+            (true, _) => f64::NAN,
+            (false, true) => f64::NEG_INFINITY,
+            (false, false) => f64::INFINITY,
+        };
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = match literal {
+                Some(literal) => literal.to_binary(base).to_f32(),
+                None => special(s.starts_with('-')) as f32
+            },
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = match literal {
+                Some(literal) => literal.to_binary(base).to_f64(),
+                None => special(s.starts_with('-'))
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = match literal {
+                Some(literal) => literal.to_twofloat(base),
+                None => TwoFloat::from(special(s.starts_with('-')))
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat | UniFloatChoice::Decimal { .. } => {
+                let mut exact = UniMpfrTwoFloatExact::NAN;
+                exact.copied();
+                parse_mpfr(exact.mpfr_mut(), s, base as i32)?;
+                result = Self::from_mpfr(exact.mpfr_src().as_ptr());
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let ternary = parse_mpfr(result.mpfr_mut(), s, base as i32)?;
+                limit_exponent_range(C.mpfr_bounds(), result.mpfr_mut(), ternary);
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        Ok(result.released())
+    }
+}
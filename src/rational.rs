@@ -0,0 +1,221 @@
+use {core::{cmp::Ordering, mem::MaybeUninit, ops}, crate::{gmp, mpfr}};
+
+use crate::convert::{MPFR_TWOFLOAT_EXACT, round_exact_value};
+use crate::decimal::Dec;
+use crate::{RoundingMode, UniFloatChoice, UniFloat, UnsupportedRounding, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// An exact rational number, backed by GMP's `mpq_t` (always in canonical form: the numerator and
+/// the denominator have no common factor, and the denominator is positive). Arithmetic is exact,
+/// so a computation can run on rationals, and round once at the end (by `to_unifloat_round()`),
+/// as for predicates of computational geometry.
+///
+/// Unlike `UniFloat`, it lives on the heap (GMP allocates the limbs), so it isn't `Copy`.
+pub struct UniRational {
+    inner: gmp::mpq_t,
+}
+
+impl UniRational {
+    /// Zero.
+    pub fn new() -> Self {
+        let mut inner = MaybeUninit::uninit();
+        unsafe {
+            gmp::mpq_init(inner.as_mut_ptr());
+            Self { inner: inner.assume_init() }
+        }
+    }
+
+    /// `numerator` / `denominator`. Panics if `denominator` is zero.
+    pub fn from_integers(numerator: i32, denominator: u32) -> Self {
+        assert!(denominator != 0, "UniRational needs a non-zero denominator.");
+        let mut result = Self::new();
+        unsafe {
+            gmp::mpq_set_si(&mut result.inner, numerator.into(), denominator.into());
+            gmp::mpq_canonicalize(&mut result.inner);
+        }
+        result
+    }
+
+    /// A copy of `value`, which must be initialized (by `gmp::mpq_init()`, for example) and
+    /// canonical (see `gmp::mpq_canonicalize()`).
+    pub fn from_mpq(value: &gmp::mpq_t) -> Self {
+        let mut result = Self::new();
+        unsafe { gmp::mpq_set(&mut result.inner, value) };
+        result
+    }
+
+    pub fn as_mpq(&self) -> &gmp::mpq_t {
+        &self.inner
+    }
+
+    /// The exact value of `value` (every finite float is a rational). `None` for NaN and
+    /// infinities.
+    pub fn from_unifloat<const C: UniFloatChoice>(value: &UniFloat<C>) -> Option<Self> where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+    {
+        if !value.is_finite() {
+            return None;
+        }
+        let mut result = Self::new();
+        match C {
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+                unsafe { mpfr::get_q(&mut result.inner, value.mpfr_src().as_ptr()) },
+            UniFloatChoice::Decimal { .. } => unsafe { value.decimal().to_mpq(&mut result.inner) },
+            _ => {
+                // Exact: it holds any f32, f64 or TwoFloat.
+                let mut exact = value.convert::<{ MPFR_TWOFLOAT_EXACT }>();
+                exact.copied();
+                unsafe { mpfr::get_q(&mut result.inner, exact.mpfr_src().as_ptr()) };
+            }
+        }
+        Some(result)
+    }
+
+    /// `self` rounded to nearest (ties to even) for choice `C`, once only.
+    pub fn to_unifloat<const C: UniFloatChoice>(&self) -> UniFloat<C> where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+    {
+        match self.to_unifloat_round(RoundingMode::Nearest) {
+            Ok(result) => result,
+            Err(_) => unreachable!("Every choice supports rounding to nearest.")
+        }
+    }
+
+    /// `self` rounded for choice `C` in the direction of `rounding`, once only. As for
+    /// `UniFloat::convert_round()`, TwoFloat supports directed rounding only where the result is
+    /// exact.
+    pub fn to_unifloat_round<const C: UniFloatChoice>(&self, rounding: RoundingMode) -> Result<UniFloat<C>, UnsupportedRounding> where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+    {
+        match C {
+            // Straight from the fraction, since binary can't hold decimal ties (as 0.15).
+            UniFloatChoice::Decimal { digits } => {
+                let mut result = UniFloat::<C>::NAN;
+                result.set_decimal(unsafe { Dec::from_mpq(digits, &self.inner, rounding) });
+                Ok(result)
+            },
+            _ => round_exact_value::<C>(|r, rnd| unsafe { mpfr::set_q(r, &self.inner, rnd) }, rounding)
+        }
+    }
+
+    /// -1, 0 or 1, as the sign of `self`.
+    pub fn signum(&self) -> i32 {
+        unsafe { gmp::mpq_sgn(&self.inner) }
+    }
+
+    pub fn abs(&self) -> Self {
+        let mut result = Self::new();
+        unsafe { gmp::mpq_abs(&mut result.inner, &self.inner) };
+        result
+    }
+
+    /// 1 / `self`. Panics if `self` is zero.
+    pub fn recip(&self) -> Self {
+        assert!(self.signum() != 0, "UniRational division by zero.");
+        let mut result = Self::new();
+        unsafe { gmp::mpq_inv(&mut result.inner, &self.inner) };
+        result
+    }
+}
+
+impl Default for UniRational {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for UniRational {
+    fn clone(&self) -> Self {
+        Self::from_mpq(&self.inner)
+    }
+}
+
+impl Drop for UniRational {
+    fn drop(&mut self) {
+        unsafe { gmp::mpq_clear(&mut self.inner) };
+    }
+}
+
+impl PartialEq for UniRational {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { gmp::mpq_equal(&self.inner, &other.inner) != 0 }
+    }
+}
+
+impl Eq for UniRational {}
+
+impl PartialOrd for UniRational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for UniRational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        unsafe { gmp::mpq_cmp(&self.inner, &other.inner) }.cmp(&0)
+    }
+}
+
+impl ops::Neg for &UniRational {
+    type Output = UniRational;
+    fn neg(self) -> Self::Output {
+        let mut result = UniRational::new();
+        unsafe { gmp::mpq_neg(&mut result.inner, &self.inner) };
+        result
+    }
+}
+
+// Exact arithmetic in place (+=, -=, *=).
+macro_rules! impl_assign_op {
+    ($trait:ident, $method:ident, $gmp_op:path) => {
+        impl ops::$trait<&UniRational> for UniRational {
+            fn $method(&mut self, rhs: &UniRational) {
+                let inner: *mut gmp::mpq_t = &mut self.inner;
+                unsafe { $gmp_op(inner, inner, &rhs.inner) };
+            }
+        }
+    };
+}
+
+impl_assign_op!(AddAssign, add_assign, gmp::mpq_add);
+impl_assign_op!(SubAssign, sub_assign, gmp::mpq_sub);
+impl_assign_op!(MulAssign, mul_assign, gmp::mpq_mul);
+
+/// Panics if `rhs` is zero.
+impl ops::DivAssign<&UniRational> for UniRational {
+    fn div_assign(&mut self, rhs: &UniRational) {
+        assert!(rhs.signum() != 0, "UniRational division by zero.");
+        let inner: *mut gmp::mpq_t = &mut self.inner;
+        unsafe { gmp::mpq_div(inner, inner, &rhs.inner) };
+    }
+}
+
+// Exact binary operators (+, -, *, /) on references. Division by zero panics.
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl ops::$trait<&UniRational> for &UniRational {
+            type Output = UniRational;
+            fn $method(self, rhs: &UniRational) -> Self::Output {
+                let mut result = self.clone();
+                ops::$assign_trait::$assign_method(&mut result, rhs);
+                result
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, AddAssign, add_assign);
+impl_binary_op!(Sub, sub, SubAssign, sub_assign);
+impl_binary_op!(Mul, mul, MulAssign, mul_assign);
+impl_binary_op!(Div, div, DivAssign, div_assign);
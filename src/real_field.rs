@@ -0,0 +1,432 @@
+use num_traits::Float;
+use simba::{scalar::{ComplexField, Field, RealField, SubsetOf}, simd::SimdValue};
+
+use crate::{RoundingMode, UniFloatChoice, UniFloat, UniNum, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+// What nalgebra needs of its scalars (`RealField`, and through it `ComplexField` and `Field`),
+// with optional feature `real_field`, for `UniNum` (so that nalgebra can pass values around by
+// value, as it does). Functions are those of `num_traits::Float`, so they round as for
+// `UniFloat`. Matrices of `UniNum<C>` then have nalgebra's decompositions (like QR, or SVD) at
+// the precision of `C`.
+
+/// A single lane: `UniNum` is a scalar.
+impl <const C: UniFloatChoice> SimdValue for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Element = Self;
+    type SimdBool = bool;
+
+    fn lanes() -> usize {
+        1
+    }
+
+    fn splat(value: Self) -> Self {
+        value
+    }
+
+    fn extract(&self, _: usize) -> Self {
+        *self
+    }
+
+    unsafe fn extract_unchecked(&self, _: usize) -> Self {
+        *self
+    }
+
+    fn replace(&mut self, _: usize, value: Self) {
+        *self = value;
+    }
+
+    unsafe fn replace_unchecked(&mut self, _: usize, value: Self) {
+        *self = value;
+    }
+
+    fn select(self, condition: bool, other: Self) -> Self {
+        if condition { self } else { other }
+    }
+}
+
+impl <const C: UniFloatChoice> Field for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{}
+
+impl <const C: UniFloatChoice> SubsetOf<UniNum<C>> for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn to_superset(&self) -> Self {
+        *self
+    }
+
+    fn from_superset_unchecked(element: &Self) -> Self {
+        *element
+    }
+
+    fn is_in_subset(_: &Self) -> bool {
+        true
+    }
+}
+
+// f32 and f64 (so that nalgebra can convert its constants, and matrices of them). As for
+// f32 and f64 themselves, every value is in the subset: values beyond the range of the
+// primitive round to infinities.
+macro_rules! impl_subset_of {
+    ($($primitive:ident => $to_primitive:ident),+) => {
+        $(
+            impl <const C: UniFloatChoice> SubsetOf<UniNum<C>> for $primitive where
+            [f32; f32_parts_length(C)]: Sized,
+            [f64; f64_parts_length(C)]: Sized,
+            [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+            [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+            [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+            {
+                fn to_superset(&self) -> UniNum<C> {
+                    UniNum::new(&UniFloat::from(*self))
+                }
+
+                fn from_superset_unchecked(element: &UniNum<C>) -> Self {
+                    let mut value = element.get();
+                    value.copied();
+                    value.$to_primitive(RoundingMode::Nearest).value()
+                }
+
+                fn is_in_subset(_: &UniNum<C>) -> bool {
+                    true
+                }
+            }
+        )+
+    };
+}
+
+impl_subset_of!(f32 => to_f32, f64 => to_f64);
+
+/// Real: the imaginary part is zero, and `modulus()` and `norm1()` are `abs()`.
+impl <const C: UniFloatChoice> ComplexField for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type RealField = Self;
+
+    fn from_real(re: Self) -> Self {
+        re
+    }
+
+    fn real(self) -> Self {
+        self
+    }
+
+    fn imaginary(self) -> Self {
+        Self::new(&UniFloat::ZERO)
+    }
+
+    fn modulus(self) -> Self {
+        Float::abs(self)
+    }
+
+    fn modulus_squared(self) -> Self {
+        self * self
+    }
+
+    /// 0 for positive values (including +0), π for negative ones (including -0), NaN for NaN.
+    fn argument(self) -> Self {
+        if Float::is_nan(self) {
+            self
+        } else if Float::is_sign_negative(self) {
+            <Self as RealField>::pi()
+        } else {
+            Self::new(&UniFloat::ZERO)
+        }
+    }
+
+    fn norm1(self) -> Self {
+        Float::abs(self)
+    }
+
+    fn scale(self, factor: Self) -> Self {
+        self * factor
+    }
+
+    fn unscale(self, factor: Self) -> Self {
+        self / factor
+    }
+
+    fn floor(self) -> Self {
+        Float::floor(self)
+    }
+
+    fn ceil(self) -> Self {
+        Float::ceil(self)
+    }
+
+    fn round(self) -> Self {
+        Float::round(self)
+    }
+
+    fn trunc(self) -> Self {
+        Float::trunc(self)
+    }
+
+    fn fract(self) -> Self {
+        Float::fract(self)
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        Float::mul_add(self, a, b)
+    }
+
+    fn abs(self) -> Self {
+        Float::abs(self)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        Float::hypot(self, other)
+    }
+
+    fn recip(self) -> Self {
+        Float::recip(self)
+    }
+
+    fn conjugate(self) -> Self {
+        self
+    }
+
+    fn signum(self) -> Self {
+        Float::signum(self)
+    }
+
+    fn sin(self) -> Self {
+        Float::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        Float::cos(self)
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        Float::sin_cos(self)
+    }
+
+    fn tan(self) -> Self {
+        Float::tan(self)
+    }
+
+    fn asin(self) -> Self {
+        Float::asin(self)
+    }
+
+    fn acos(self) -> Self {
+        Float::acos(self)
+    }
+
+    fn atan(self) -> Self {
+        Float::atan(self)
+    }
+
+    fn sinh(self) -> Self {
+        Float::sinh(self)
+    }
+
+    fn cosh(self) -> Self {
+        Float::cosh(self)
+    }
+
+    fn tanh(self) -> Self {
+        Float::tanh(self)
+    }
+
+    fn asinh(self) -> Self {
+        Float::asinh(self)
+    }
+
+    fn acosh(self) -> Self {
+        Float::acosh(self)
+    }
+
+    fn atanh(self) -> Self {
+        Float::atanh(self)
+    }
+
+    fn log(self, base: Self) -> Self {
+        Float::log(self, base)
+    }
+
+    fn log2(self) -> Self {
+        Float::log2(self)
+    }
+
+    fn log10(self) -> Self {
+        Float::log10(self)
+    }
+
+    fn ln(self) -> Self {
+        Float::ln(self)
+    }
+
+    fn ln_1p(self) -> Self {
+        Float::ln_1p(self)
+    }
+
+    fn sqrt(self) -> Self {
+        Float::sqrt(self)
+    }
+
+    fn exp(self) -> Self {
+        Float::exp(self)
+    }
+
+    fn exp2(self) -> Self {
+        Float::exp2(self)
+    }
+
+    fn exp_m1(self) -> Self {
+        Float::exp_m1(self)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        Float::powi(self, n)
+    }
+
+    fn powf(self, n: Self) -> Self {
+        Float::powf(self, n)
+    }
+
+    fn powc(self, n: Self) -> Self {
+        Float::powf(self, n)
+    }
+
+    fn cbrt(self) -> Self {
+        Float::cbrt(self)
+    }
+
+    fn is_finite(&self) -> bool {
+        Float::is_finite(*self)
+    }
+
+    /// None for negative values (other than -0) and NaN.
+    fn try_sqrt(self) -> Option<Self> {
+        let root = Float::sqrt(self);
+        if Float::is_nan(root) { None } else { Some(root) }
+    }
+}
+
+/// `min_value()` and `max_value()` are `UniFloat::MIN` and `UniFloat::MAX`. The constants are those
+/// of `UniFloat` (like `UniFloat::pi()`), and those that `UniFloat` doesn't have are divided or
+/// inverted from them (so they round twice).
+impl <const C: UniFloatChoice> RealField for UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn is_sign_positive(&self) -> bool {
+        Float::is_sign_positive(*self)
+    }
+
+    fn is_sign_negative(&self) -> bool {
+        Float::is_sign_negative(*self)
+    }
+
+    fn copysign(self, sign: Self) -> Self {
+        Float::copysign(self, sign)
+    }
+
+    fn max(self, other: Self) -> Self {
+        Float::max(self, other)
+    }
+
+    fn min(self, other: Self) -> Self {
+        Float::min(self, other)
+    }
+
+    /// Panics if `min` > `max`, as `f64::clamp()`.
+    fn clamp(self, min: Self, max: Self) -> Self {
+        assert!(min <= max, "clamp() needs min <= max.");
+        Float::min(Float::max(self, min), max)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        Float::atan2(self, other)
+    }
+
+    fn min_value() -> Option<Self> {
+        Some(Float::min_value())
+    }
+
+    fn max_value() -> Option<Self> {
+        Some(Float::max_value())
+    }
+
+    fn pi() -> Self {
+        Self::new(&UniFloat::pi())
+    }
+
+    fn two_pi() -> Self {
+        Self::new(&UniFloat::tau())
+    }
+
+    fn frac_pi_2() -> Self {
+        Self::new(&UniFloat::frac_pi_2())
+    }
+
+    fn frac_pi_3() -> Self {
+        Self::pi() / Self::new(&UniFloat::from(3u8))
+    }
+
+    fn frac_pi_4() -> Self {
+        Self::pi() / Self::new(&UniFloat::from(4u8))
+    }
+
+    fn frac_pi_6() -> Self {
+        Self::pi() / Self::new(&UniFloat::from(6u8))
+    }
+
+    fn frac_pi_8() -> Self {
+        Self::pi() / Self::new(&UniFloat::from(8u8))
+    }
+
+    fn frac_1_pi() -> Self {
+        Float::recip(Self::pi())
+    }
+
+    fn frac_2_pi() -> Self {
+        Float::recip(Self::frac_pi_2())
+    }
+
+    fn frac_2_sqrt_pi() -> Self {
+        Self::new(&UniFloat::from(2u8)) / Float::sqrt(Self::pi())
+    }
+
+    fn e() -> Self {
+        Self::new(&UniFloat::e())
+    }
+
+    fn log2_e() -> Self {
+        Self::new(&UniFloat::log2_e())
+    }
+
+    fn log10_e() -> Self {
+        Self::new(&UniFloat::log10_e())
+    }
+
+    fn ln_2() -> Self {
+        Self::new(&UniFloat::ln_2())
+    }
+
+    fn ln_10() -> Self {
+        Self::new(&UniFloat::ln_10())
+    }
+}
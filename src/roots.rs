@@ -0,0 +1,96 @@
+#[cfg(feature = "twofloat")]
+use core::convert::TryFrom;
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+use crate::mpfr;
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Roots. They are correctly rounded for MPFR, and `sqrt()` is correctly rounded for f32 and f64,
+/// too. Otherwise f32 and f64 are within an ulp or so (f32 is computed with f64), and TwoFloat is
+/// within a few ulps (of double-double).
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Square root. NaN for negative `self`, but -0 for -0 (as IEEE 754).
+    pub fn sqrt(&self) -> Self {
+        self.unary(libm::sqrtf, libm::sqrt, twofloat_op!(|x| sqrt_twofloat(x)),
+            softmpfr_op!(|r, x| unsafe { mpfr::sqrt(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Cube root. It keeps the sign (for zeros and infinities, too).
+    pub fn cbrt(&self) -> Self {
+        self.unary(|x| libm::cbrt(x as f64) as f32, libm::cbrt, twofloat_op!(|x| root_n_twofloat(x, 3)),
+            mpfr_op!(|r, x| unsafe { mpfr::cbrt(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// The `k`-th root, as MPFR's `mpfr_rootn_ui()`: NaN for `k` zero, and for negative `self`
+    /// (including -Inf) when `k` is even. The root of -0 is +0 for even `k`, and -0 for odd `k`.
+    pub fn root_n(&self, k: u32) -> Self {
+        self.unary(|x| root_n_f64(x as f64, k) as f32, |x| root_n_f64(x, k), twofloat_op!(|x| root_n_twofloat(x, k)),
+            mpfr_op!(|r, x| unsafe { mpfr::rootn_ui(r, x, k.into(), mpfr::rnd_t::RNDN) }))
+    }
+}
+
+/// Square root, but -0 for -0 (as IEEE 754).
+#[cfg(feature = "twofloat")]
+pub(crate) fn sqrt_twofloat(x: TwoFloat) -> TwoFloat {
+    if x.hi() == 0.0 { x } else { root_n_twofloat(x, 2) }
+}
+
+/// Special cases of `UniFloat::root_n()` for NaN, zeros and infinities (and for `k` being 0 or
+/// 1). Otherwise `None`.
+fn root_n_special(x: f64, k: u32) -> Option<f64> {
+    if k == 0 || x.is_nan() || (k % 2 == 0 && x < 0.0) {
+        Some(f64::NAN)
+    } else if k == 1 || x.is_infinite() {
+        Some(x)
+    } else if x == 0.0 {
+        Some(if k % 2 == 0 { 0.0 } else { x })
+    } else {
+        None
+    }
+}
+
+fn root_n_f64(x: f64, k: u32) -> f64 {
+    if let Some(special) = root_n_special(x, k) {
+        return special;
+    }
+    match k {
+        2 => libm::sqrt(x),
+        3 => libm::cbrt(x),
+        _ => {
+            let magnitude = libm::fabs(x);
+            let root = libm::pow(magnitude, 1.0 / k as f64);
+            // 1/k isn't exact, which makes an error up to about |ln(x)| / k ulps. One Newton step
+            // fixes that.
+            let root = root + (magnitude / libm::pow(root, (k - 1) as f64) - root) / k as f64;
+            libm::copysign(root, x)
+        }
+    }
+}
+
+/// Newton's method, starting from f64. One step doubles the number of correct bits.
+#[cfg(feature = "twofloat")]
+fn root_n_twofloat(x: TwoFloat, k: u32) -> TwoFloat {
+    if let Some(special) = root_n_special(x.hi(), k) {
+        return if k == 1 { x } else { TwoFloat::from(special) };
+    }
+    match (k, i32::try_from(k - 1)) {
+        (2, _) => x.sqrt(),
+        (3, _) => x.cbrt(),
+        (_, Ok(power)) => {
+            let magnitude = x.abs();
+            let root = TwoFloat::from(root_n_f64(magnitude.hi(), k));
+            let root = root + (magnitude / root.powi(power) - root) / k as f64;
+            if x.hi() < 0.0 { -root } else { root }
+        },
+        // The root is 1 (to nearly double-double precision), or it's in the subnormal range.
+        (_, Err(_)) => TwoFloat::from(root_n_f64(x.hi(), k))
+    }
+}
@@ -0,0 +1,183 @@
+#[cfg(feature = "mpfr")]
+use crate::mpfr;
+
+use crate::{RoundingMode, UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+use crate::convert::{overflows_to_max, round_to_f32, round_to_f64};
+use crate::rounding::UnsupportedRounding;
+#[cfg(feature = "mpfr")]
+use crate::rounding::mpfr_rounded;
+#[cfg(feature = "twofloat")]
+use crate::roots::sqrt_twofloat;
+
+/// The basic arithmetic, rounded in the direction of `rounding` (while the operators round to
+/// nearest, ties to even).
+/// - MPFR: correctly rounded, in any direction.
+/// - f32: computed in f64, with the rounding error, so correctly rounded, too.
+/// - f64: the result rounded to nearest, and its error (exact, or of the right sign), so
+///   correctly rounded, too, apart from results that are subnormal (or that underflow to zero):
+///   there the error itself may underflow, so directed rounding may be off by 1 ulp.
+/// - TwoFloat: it has no directed rounding, so anything other than `Nearest` and `Faithful` is an
+///   `UnsupportedRounding` error. Those two are the same as the operators.
+/// - QuadFloat: in MPFR, rounded to 212 bits in any direction, and then split into its parts.
+///
+/// NaN, infinities and zeros are as in IEEE 754 (exact zero sums are -0 for `Down`). Overflows
+/// are Inf, or MAX, if the rounding direction is toward zero.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub fn add_round(&self, other: &Self, rounding: RoundingMode) -> Result<Self, UnsupportedRounding> {
+        Self::supported(rounding)?;
+        Ok(self.binary(other,
+            |x, y| {
+                let (sum, error) = sum_f64(x as f64, y as f64, rounding);
+                round_to_f32(sum, error, rounding).value()
+            },
+            |x, y| {
+                let (sum, error) = sum_f64(x, y, rounding);
+                round_f64(sum, error, x.is_finite() && y.is_finite(), rounding)
+            },
+            twofloat_op!(|x, y| x + y),
+            mpfr_op!(|r, x, y| unsafe { mpfr_rounded(r, rounding, |rnd| mpfr::add(r, x, y, rnd)) })))
+    }
+
+    pub fn sub_round(&self, other: &Self, rounding: RoundingMode) -> Result<Self, UnsupportedRounding> {
+        Self::supported(rounding)?;
+        Ok(self.binary(other,
+            |x, y| {
+                let (sum, error) = sum_f64(x as f64, -y as f64, rounding);
+                round_to_f32(sum, error, rounding).value()
+            },
+            |x, y| {
+                let (sum, error) = sum_f64(x, -y, rounding);
+                round_f64(sum, error, x.is_finite() && y.is_finite(), rounding)
+            },
+            twofloat_op!(|x, y| x - y),
+            mpfr_op!(|r, x, y| unsafe { mpfr_rounded(r, rounding, |rnd| mpfr::sub(r, x, y, rnd)) })))
+    }
+
+    pub fn mul_round(&self, other: &Self, rounding: RoundingMode) -> Result<Self, UnsupportedRounding> {
+        Self::supported(rounding)?;
+        Ok(self.binary(other,
+            // Exact in f64: 48 bits at most, and no underflow.
+            |x, y| round_to_f32(x as f64 * y as f64, 0.0, rounding).value(),
+            |x, y| {
+                let product = x * y;
+                round_f64(product, product_error(x, y, product), x.is_finite() && y.is_finite(), rounding)
+            },
+            twofloat_op!(|x, y| x * y),
+            mpfr_op!(|r, x, y| unsafe { mpfr_rounded(r, rounding, |rnd| mpfr::mul(r, x, y, rnd)) })))
+    }
+
+    /// Quotients are never halfway between two neighbours, so the error of the remainder divided
+    /// by `other` doesn't matter, as long as it has the right sign.
+    pub fn div_round(&self, other: &Self, rounding: RoundingMode) -> Result<Self, UnsupportedRounding> {
+        Self::supported(rounding)?;
+        Ok(self.binary(other,
+            |x, y| {
+                let (x, y) = (x as f64, y as f64);
+                let quotient = x / y;
+                round_to_f32(quotient, quotient_error(x, y, quotient), rounding).value()
+            },
+            |x, y| {
+                let quotient = x / y;
+                round_f64(quotient, quotient_error(x, y, quotient), x.is_finite() && y != 0.0, rounding)
+            },
+            twofloat_op!(|x, y| x / y),
+            mpfr_op!(|r, x, y| unsafe { mpfr_rounded(r, rounding, |rnd| mpfr::div(r, x, y, rnd)) })))
+    }
+
+    /// NaN for negative `self`, but -0 for -0, as `sqrt()`. Square roots are never halfway
+    /// between two neighbours either.
+    pub fn sqrt_round(&self, rounding: RoundingMode) -> Result<Self, UnsupportedRounding> {
+        Self::supported(rounding)?;
+        Ok(self.unary(
+            |x| {
+                let x = x as f64;
+                let root = libm::sqrt(x);
+                round_to_f32(root, root_error(x, root), rounding).value()
+            },
+            |x| {
+                let root = libm::sqrt(x);
+                round_to_f64(root, root_error(x, root), rounding).value()
+            },
+            twofloat_op!(|x| sqrt_twofloat(x)),
+            mpfr_op!(|r, x| unsafe { mpfr_rounded(r, rounding, |rnd| mpfr::sqrt(r, x, rnd)) })))
+    }
+
+    fn supported(rounding: RoundingMode) -> Result<(), UnsupportedRounding> {
+        if matches!(C, UniFloatChoice::TwoFloat) && !rounding.is_nearest_or_faithful() {
+            Err(UnsupportedRounding::new(rounding))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// `x + y` rounded to nearest, and its error (exact, by TwoSum). Exact zero sums are -0 for
+/// `Down`, unless both `x` and `y` are +0.
+pub(crate) fn sum_f64(x: f64, y: f64, rounding: RoundingMode) -> (f64, f64) {
+    let sum = x + y;
+    if !sum.is_finite() {
+        (sum, 0.0)
+    } else if sum == 0.0 {
+        let negative = rounding == RoundingMode::Down && (x.is_sign_negative() || y.is_sign_negative());
+        (if negative { -0.0 } else { sum }, 0.0)
+    } else {
+        let y_part = sum - x;
+        (sum, (x - (sum - y_part)) + (y - y_part))
+    }
+}
+
+/// `x * y - product` (exact by `fma()`, unless it underflows). Zero for infinite or NaN products.
+pub(crate) fn product_error(x: f64, y: f64, product: f64) -> f64 {
+    if !product.is_finite() {
+        0.0
+    } else if product == 0.0 {
+        underflow_error(product, x != 0.0 && y != 0.0)
+    } else {
+        libm::fma(x, y, -product)
+    }
+}
+
+/// The remainder `x - quotient * y` (exact by `fma()`, unless it underflows), divided by `y`.
+/// Zero for infinite or NaN quotients, and for infinite `y`.
+pub(crate) fn quotient_error(x: f64, y: f64, quotient: f64) -> f64 {
+    if !quotient.is_finite() || !y.is_finite() {
+        0.0
+    } else if quotient == 0.0 {
+        underflow_error(quotient, x != 0.0)
+    } else {
+        libm::fma(-quotient, y, x) / y
+    }
+}
+
+/// `x - root^2` (exact by `fma()`, unless it underflows), divided by the derivative `2 * root`.
+pub(crate) fn root_error(x: f64, root: f64) -> f64 {
+    if !root.is_finite() || root == 0.0 {
+        0.0
+    } else {
+        libm::fma(-root, root, x) / (2.0 * root)
+    }
+}
+
+/// For a result that's rounded to zero: the least subnormal with its sign, if it underflowed
+/// (that is, if the exact result isn't zero). The error is less than that, but it has its sign.
+fn underflow_error(zero: f64, underflowed: bool) -> f64 {
+    if underflowed { libm::copysign(f64::from_bits(1), zero) } else { 0.0 }
+}
+
+/// `nearest + error` rounded in the direction of `rounding`, where `nearest` is rounded to
+/// nearest already. If that overflowed from finite operands, it's Inf or MAX, as per
+/// `overflows_to_max()`.
+fn round_f64(nearest: f64, error: f64, finite_operands: bool, rounding: RoundingMode) -> f64 {
+    if nearest.is_infinite() && finite_operands {
+        if overflows_to_max(nearest < 0.0, rounding) { libm::copysign(f64::MAX, nearest) } else { nearest }
+    } else {
+        round_to_f64(nearest, error, rounding).value()
+    }
+}
+
@@ -0,0 +1,374 @@
+use core::fmt;
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+use crate::mpfr;
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::decimal::Dec;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::{convert::UniMpfrTwoFloatExact, quadfloat::Quad};
+
+/// Direction of rounding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// To nearest, with ties (half-way cases) to even.
+    Nearest,
+    TowardZero,
+    /// Toward +Inf.
+    Up,
+    /// Toward -Inf.
+    Down,
+    /// To nearest, with ties away from zero (IEEE 754 roundTiesToAway).
+    ToNearestAway,
+    /// Either neighbour: the nearest one, or the other one. That's all that some algorithms need,
+    /// and MPFR may be faster with it (for the basic arithmetic). Elsewhere it's the same as
+    /// `Nearest`.
+    Faithful,
+}
+
+impl RoundingMode {
+    /// MPFR has no mode for `ToNearestAway`: it maps to RNDN, so callers have to handle its ties
+    /// (as `mpfr_rounded()` does). `Faithful` maps to RNDN, too: MPFR supports RNDF for some
+    /// functions only (see `mpfr_rounded()`).
+    #[cfg(any(feature = "mpfr", feature = "softmpfr"))]
+    #[cfg_attr(all(feature = "f32_only", not(feature = "mpfr")), allow(dead_code))]
+    pub(crate) const fn to_mpfr(self) -> mpfr::rnd_t {
+        match self {
+            RoundingMode::Nearest | RoundingMode::ToNearestAway | RoundingMode::Faithful => mpfr::rnd_t::RNDN,
+            RoundingMode::TowardZero => mpfr::rnd_t::RNDZ,
+            RoundingMode::Up => mpfr::rnd_t::RNDU,
+            RoundingMode::Down => mpfr::rnd_t::RNDD,
+        }
+    }
+
+    /// Whether TwoFloat supports it: its arithmetic rounds (nearly) to nearest, so it's faithful,
+    /// but it has no directed rounding.
+    pub(crate) const fn is_nearest_or_faithful(self) -> bool {
+        matches!(self, RoundingMode::Nearest | RoundingMode::Faithful)
+    }
+}
+
+/// Error from functions that take a `RoundingMode` (like `UniFloat::add_round()`), for rounding
+/// modes that TwoFloat doesn't support: anything other than `Nearest` and `Faithful`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnsupportedRounding {
+    rounding: RoundingMode,
+}
+
+impl UnsupportedRounding {
+    pub(crate) fn new(rounding: RoundingMode) -> Self {
+        Self { rounding }
+    }
+
+    pub fn rounding(&self) -> RoundingMode {
+        self.rounding
+    }
+}
+
+impl fmt::Display for UnsupportedRounding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rounding mode {:?} is not supported by TwoFloat", self.rounding)
+    }
+}
+
+/// `op(rnd)` as per `rounding`, where `op` sets `r` (which must not be one of its operands).
+/// `ToNearestAway` is MPFR's `mpfr_round_nearest_away()`: it rounds to nearest at one more bit,
+/// and then fixes the ties. `Faithful` is RNDF, which MPFR supports for the basic arithmetic
+/// (addition, subtraction, multiplication, division and square root).
+#[cfg(feature = "mpfr")]
+pub(crate) unsafe fn mpfr_rounded(r: mpfr::mpfr_ptr, rounding: RoundingMode, op: impl FnOnce(mpfr::rnd_t) -> i32) -> i32 {
+    match rounding {
+        RoundingMode::ToNearestAway => {
+            mpfr::round_nearest_away_begin(r);
+            let ternary = op(mpfr::rnd_t::RNDN);
+            mpfr::round_nearest_away_end(r, ternary)
+        },
+        RoundingMode::Faithful => op(mpfr::rnd_t::RNDF),
+        _ => op(rounding.to_mpfr()),
+    }
+}
+
+/// `x` rounded to an integer in the direction of `rounding`, by MPFR's `rint_*()` family. Those
+/// round to an integer first, and then to the precision of `r`, which is exact here (the integer
+/// has no more bits than `x`).
+#[cfg(feature = "mpfr")]
+unsafe fn mpfr_rint(r: mpfr::mpfr_ptr, x: mpfr::mpfr_srcptr, rounding: RoundingMode) -> i32 {
+    match rounding {
+        RoundingMode::Nearest | RoundingMode::Faithful => mpfr::rint(r, x, mpfr::rnd_t::RNDN),
+        RoundingMode::ToNearestAway => mpfr::rint_round(r, x, mpfr::rnd_t::RNDN),
+        RoundingMode::TowardZero => mpfr::rint_trunc(r, x, mpfr::rnd_t::RNDN),
+        RoundingMode::Up => mpfr::rint_ceil(r, x, mpfr::rnd_t::RNDN),
+        RoundingMode::Down => mpfr::rint_floor(r, x, mpfr::rnd_t::RNDN),
+    }
+}
+
+/// Rounding to integers.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// The greatest integer not greater than `self`. Same as Rust's `f64::floor()`.
+    pub fn floor(&self) -> Self {
+        self.rint(RoundingMode::Down)
+    }
+
+    /// The least integer not less than `self`. Same as Rust's `f64::ceil()`.
+    pub fn ceil(&self) -> Self {
+        self.rint(RoundingMode::Up)
+    }
+
+    /// The integer part of `self`: rounded toward zero. Same as Rust's `f64::trunc()`.
+    pub fn trunc(&self) -> Self {
+        self.rint(RoundingMode::TowardZero)
+    }
+
+    /// Round to the nearest integer, with ties (half-way cases) away from zero. Same as Rust's
+    /// `f64::round()`.
+    pub fn round(&self) -> Self {
+        self.unary(libm::roundf, libm::round, twofloat_op!(|x| round_twofloat(x, RoundingMode::ToNearestAway)),
+            mpfr_op!(|r, x| unsafe { mpfr::round(r, x) }))
+    }
+
+    /// Round to the nearest integer, with ties (half-way cases) to even. This is the
+    /// "banker's rounding", used by financial calculations.
+    pub fn round_ties_even(&self) -> Self {
+        self.unary(round_ties_even_f32, round_ties_even_f64, twofloat_op!(|x| round_twofloat(x, RoundingMode::Nearest)),
+            mpfr_op!(|r, x| unsafe { mpfr::rint(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Round to an integer in the direction of `rounding` (as MPFR's `mpfr_rint()`, and its
+    /// `mpfr_rint_*()` family). `Nearest` (and `Faithful`) is `round_ties_even()`, and
+    /// `ToNearestAway` is `round()`. The result is exact (an integer has no more bits than `self`).
+    pub fn rint(&self, rounding: RoundingMode) -> Self {
+        self.unary(|x| round_f32(x, rounding), |x| round_f64(x, rounding), twofloat_op!(|x| round_twofloat(x, rounding)),
+            mpfr_op!(|r, x| unsafe { mpfr_rint(r, x, rounding) }))
+    }
+
+    /// The nearest multiple of `step` in the direction of `rounding`. That is
+    /// `round(self / step) * step`, where `round` is the integer rounding in the direction of
+    /// `rounding`. The division and the multiplication themselves round to nearest (for MPFR
+    /// at the type precision). If `step` is zero, the result is NaN.
+    pub fn quantize(&self, step: &Self, rounding: RoundingMode) -> Self {
+        self.binary(step,
+            |x, step| round_f32(x / step, rounding) * step,
+            |x, step| round_f64(x / step, rounding) * step,
+            twofloat_op!(|x, step| round_twofloat(x / step, rounding) * step),
+            mpfr_op!(|r, x, step| unsafe {
+                mpfr::div(r, x, step, mpfr::rnd_t::RNDN);
+                mpfr_rint(r, r, rounding);
+                mpfr::mul(r, r, step, mpfr::rnd_t::RNDN)
+            }))
+    }
+
+    /// The integer part (rounded toward zero) and the fractional part of `self`, in one pass (as
+    /// MPFR's `mpfr_modf()`, and C's `modf()`). Both are exact for f32, f64, decimals and MPFR (for
+    /// TwoFloat the fractional part is subtracted at double-double precision, for QuadFloat both
+    /// parts are split from exact MPFR values). The fractional part has the
+    /// sign of `self` (-0 for negative integers), and it's 0 with the sign of `self` for infinities
+    /// (unlike Rust's `f64::fract()`, which is NaN for them).
+    #[cfg_attr(feature = "f32_only", allow(unused_mut))]
+    pub fn trunc_fract(&self) -> (Self, Self) {
+        self.assert_copy_fixed();
+        let (mut integer, mut fraction) = (Self::NAN, Self::NAN);
+        match C {
+            UniFloatChoice::F32 => {
+                let x = self.f32s[0];
+                integer.f32s[0] = libm::truncf(x);
+                fraction.f32s[0] = libm::copysignf(if x.is_infinite() { 0.0 } else { x - integer.f32s[0] }, x);
+            },
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => {
+                let x = self.f64s[0];
+                integer.f64s[0] = libm::trunc(x);
+                fraction.f64s[0] = libm::copysign(if x.is_infinite() { 0.0 } else { x - integer.f64s[0] }, x);
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => {
+                let x = self.twofloats[0];
+                integer.twofloats[0] = round_twofloat(x, RoundingMode::TowardZero);
+                let difference = x - integer.twofloats[0];
+                fraction.twofloats[0] = if x.hi().is_nan() {
+                    x
+                } else if x.hi().is_infinite() || difference.hi() == 0.0 {
+                    TwoFloat::from(libm::copysign(0.0, x.hi()))
+                } else {
+                    difference
+                };
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => {
+                let (mut exact, mut exact_integer, mut exact_fraction) =
+                    (UniMpfrTwoFloatExact::NAN, UniMpfrTwoFloatExact::NAN, UniMpfrTwoFloatExact::NAN);
+                exact.copied();
+                exact_integer.copied();
+                exact_fraction.copied();
+                unsafe {
+                    self.quad().to_mpfr(exact.mpfr_mut());
+                    mpfr::modf(exact_integer.mpfr_mut(), exact_fraction.mpfr_mut(),
+                        exact.mpfr_src().as_ptr(), mpfr::rnd_t::RNDN);
+                    integer.set_quad(Quad::from_mpfr(exact_integer.mpfr_src().as_ptr()));
+                    fraction.set_quad(Quad::from_mpfr(exact_fraction.mpfr_src().as_ptr()));
+                }
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => {
+                let (x, whole) = (self.decimal(), self.trunc().decimal());
+                integer.set_decimal(whole);
+                fraction.set_decimal(if x.is_nan() {
+                    x
+                } else if x.is_infinite() || x == whole {
+                    Dec::finite(digits, x.is_sign_negative(), 0, 0)
+                } else {
+                    // Exact: the fraction has no more digits than `x`.
+                    x - whole
+                });
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                integer.copied();
+                fraction.copied();
+                unsafe {
+                    mpfr::modf(integer.mpfr_mut(), fraction.mpfr_mut(),
+                        self.mpfr_src().as_ptr(), mpfr::rnd_t::RNDN);
+                }
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        (integer.released(), fraction.released())
+    }
+
+    /// The fractional part of `self`, as from `trunc_fract()`.
+    pub fn fract(&self) -> Self {
+        self.trunc_fract().1
+    }
+
+    /// The integer part of `self`, as from `trunc_fract()`. Same as `trunc()`.
+    pub fn int_part(&self) -> Self {
+        self.trunc()
+    }
+
+    /// The nearest multiple of `step`, with ties to even multiples. Same as
+    /// `quantize(step, RoundingMode::Nearest)`.
+    pub fn round_to_multiple(&self, step: &Self) -> Self {
+        self.quantize(step, RoundingMode::Nearest)
+    }
+}
+
+/// Round to an integer in the direction of `rounding`.
+fn round_f32(x: f32, rounding: RoundingMode) -> f32 {
+    match rounding {
+        RoundingMode::Nearest | RoundingMode::Faithful => round_ties_even_f32(x),
+        RoundingMode::ToNearestAway => libm::roundf(x),
+        RoundingMode::TowardZero => libm::truncf(x),
+        RoundingMode::Up => libm::ceilf(x),
+        RoundingMode::Down => libm::floorf(x),
+    }
+}
+
+fn round_f64(x: f64, rounding: RoundingMode) -> f64 {
+    match rounding {
+        RoundingMode::Nearest | RoundingMode::Faithful => round_ties_even_f64(x),
+        RoundingMode::ToNearestAway => libm::round(x),
+        RoundingMode::TowardZero => libm::trunc(x),
+        RoundingMode::Up => libm::ceil(x),
+        RoundingMode::Down => libm::floor(x),
+    }
+}
+
+/// Infinities and NaN stay as they are, rather than going through TwoFloat's arithmetic.
+#[cfg(feature = "twofloat")]
+fn round_twofloat(x: TwoFloat, rounding: RoundingMode) -> TwoFloat {
+    if !x.hi().is_finite() {
+        return x;
+    }
+    match rounding {
+        RoundingMode::Nearest | RoundingMode::Faithful => round_ties_even_twofloat(x),
+        RoundingMode::ToNearestAway => x.round(),
+        RoundingMode::TowardZero => x.trunc(),
+        RoundingMode::Up => x.ceil(),
+        RoundingMode::Down => x.floor(),
+    }
+}
+
+/// The least f32 greater than `x`.
+pub(crate) fn next_up_f32(x: f32) -> f32 {
+    if x.is_nan() || x == f32::INFINITY {
+        x
+    } else if x == 0.0 {
+        f32::from_bits(1)
+    } else if x > 0.0 {
+        f32::from_bits(x.to_bits() + 1)
+    } else {
+        f32::from_bits(x.to_bits() - 1)
+    }
+}
+
+pub(crate) fn next_down_f32(x: f32) -> f32 {
+    -next_up_f32(-x)
+}
+
+/// The least f64 greater than `x`.
+pub(crate) fn next_up_f64(x: f64) -> f64 {
+    if x.is_nan() || x == f64::INFINITY {
+        x
+    } else if x == 0.0 {
+        f64::from_bits(1)
+    } else if x > 0.0 {
+        f64::from_bits(x.to_bits() + 1)
+    } else {
+        f64::from_bits(x.to_bits() - 1)
+    }
+}
+
+pub(crate) fn next_down_f64(x: f64) -> f64 {
+    -next_up_f64(-x)
+}
+
+// For ties, x/2 is exact and it's not a tie anymore. Then round(x/2)*2 is the even neighbour.
+fn round_ties_even_f32(x: f32) -> f32 {
+    if libm::fabsf(x - libm::truncf(x)) == 0.5 {
+        2.0 * libm::roundf(x / 2.0)
+    } else {
+        libm::roundf(x)
+    }
+}
+
+fn round_ties_even_f64(x: f64) -> f64 {
+    if libm::fabs(x - libm::trunc(x)) == 0.5 {
+        2.0 * libm::round(x / 2.0)
+    } else {
+        libm::round(x)
+    }
+}
+
+#[cfg(feature = "twofloat")]
+fn round_ties_even_twofloat(x: TwoFloat) -> TwoFloat {
+    if (x - x.trunc()).abs() == TwoFloat::from(0.5) {
+        (x / 2.0).round() * 2.0
+    } else {
+        x.round()
+    }
+}
@@ -0,0 +1,226 @@
+use rand::{Rng, distributions::{Distribution, Open01, Standard}};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, fixed_format, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Random bits per chunk. Any chunk (as an integer) is exact for all choices, even F32.
+const CHUNK_BITS: usize = 24;
+
+/// Uniform in [0, 1), with all bits of the precision random (like `mpfr_urandomb()`): k / 2^p for
+/// a uniformly random integer 0 <= k < 2^p, where p is the precision of `C` (for TwoFloat and
+/// QuadFloat: of all parts together), or `frac_bits` for fixed-point formats. So, unlike an f64
+/// sample widened to `C`, there are no missing low bits.
+impl <const C: UniFloatChoice> Distribution<UniFloat<C>> for Standard where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> UniFloat<C> {
+        UniFloat::random_bits(rng, sample_bits(C))
+    }
+}
+
+/// Uniform in (0, 1), with all bits of the precision random but the last one, which is set (as
+/// `Open01` for f32 and f64): (2k + 1) / 2^p for a uniformly random integer 0 <= k < 2^(p - 1),
+/// with p as for `Standard`. Panics for fixed-point formats without fractional bits.
+impl <const C: UniFloatChoice> Distribution<UniFloat<C>> for Open01 where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> UniFloat<C> {
+        let bits = sample_bits(C);
+        assert!(bits > 0, "Open01 needs a fixed-point format with frac_bits > 0.");
+        let mut result = UniFloat::random_bits(rng, bits - 1);
+        result.copied();
+        let mut one = UniFloat::<C>::ONE;
+        one.copied();
+        let mut last = one.ldexp(-(bits as i64));
+        last.copied();
+        result += &last;
+        result.released()
+    }
+}
+
+/// The normal (Gaussian) distribution with `mean` and standard deviation `sigma`, sampled at the
+/// precision of `C`: by Box-Muller, from uniform samples with all bits random (see `Standard` and
+/// `Open01` for `UniFloat`), with the logarithm, square root and cosine of `C`. (So the result
+/// is accurate to a few ulps, not correctly rounded.)
+///
+/// It holds its parameters by value, and samples from local copies of them.
+#[derive(Clone, Copy)]
+pub struct UniNormal<const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    mean: UniFloat<C>,
+    sigma: UniFloat<C>,
+}
+
+impl <const C: UniFloatChoice> UniNormal<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Panics unless `mean` is finite, and `sigma` is finite and non-negative.
+    pub fn new(mean: &UniFloat<C>, sigma: &UniFloat<C>) -> Self {
+        let mut zero = UniFloat::<C>::ZERO;
+        zero.copied();
+        assert!(mean.is_finite() && sigma.is_finite() && sigma.ge(&zero),
+            "UniNormal needs a finite mean, and a finite sigma >= 0.");
+        Self { mean: *mean, sigma: *sigma }
+    }
+
+    /// Mean 0, sigma 1.
+    pub fn standard() -> Self {
+        Self { mean: UniFloat::ZERO, sigma: UniFloat::ONE }
+    }
+
+    pub fn mean(&self) -> UniFloat<C> {
+        self.mean
+    }
+
+    pub fn sigma(&self) -> UniFloat<C> {
+        self.sigma
+    }
+}
+
+impl <const C: UniFloatChoice> Distribution<UniFloat<C>> for UniNormal<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> UniFloat<C> {
+        let (mut mean, mut sigma) = (self.mean, self.sigma);
+        mean.refixed();
+        sigma.refixed();
+        let mut result = UniFloat::standard_normal(rng);
+        result.copied();
+        result *= &sigma;
+        result += &mean;
+        result.released()
+    }
+}
+
+/// The exponential distribution with rate `lambda` (so with mean 1/`lambda`), sampled at the
+/// precision of `C`: -ln(u)/`lambda`, for u of `Open01` (with all bits random). (So the result
+/// is accurate to a few ulps, not correctly rounded.)
+///
+/// As `UniNormal`, it holds its parameter by value.
+#[derive(Clone, Copy)]
+pub struct UniExponential<const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    lambda: UniFloat<C>,
+}
+
+impl <const C: UniFloatChoice> UniExponential<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Panics unless `lambda` is finite and positive.
+    pub fn new(lambda: &UniFloat<C>) -> Self {
+        let mut zero = UniFloat::<C>::ZERO;
+        zero.copied();
+        assert!(lambda.is_finite() && lambda.gt(&zero), "UniExponential needs a finite lambda > 0.");
+        Self { lambda: *lambda }
+    }
+
+    pub fn lambda(&self) -> UniFloat<C> {
+        self.lambda
+    }
+}
+
+impl <const C: UniFloatChoice> Distribution<UniFloat<C>> for UniExponential<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> UniFloat<C> {
+        let mut lambda = self.lambda;
+        lambda.refixed();
+        let mut uniform: UniFloat<C> = Open01.sample(rng);
+        uniform.copied();
+        let mut result = -uniform.ln();
+        result.copied();
+        result /= &lambda;
+        result.released()
+    }
+}
+
+/// The number of random bits of a sample in [0, 1): the precision, or `frac_bits` for fixed-point
+/// formats (so that samples are on their grid).
+fn sample_bits(c: UniFloatChoice) -> usize {
+    match fixed_format(c) {
+        Some(fixed) => fixed.frac_bits,
+        None => c.precision_bits()
+    }
+}
+
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Standard normal, by Box-Muller: sqrt(-2 ln(u)) * cos(2πv) for u of `Open01` (so that the
+    /// logarithm is finite) and v of `Standard`. The angle is in half-revolutions (`cos_pi()`), so
+    /// it's exact.
+    fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let (mut u, mut v): (Self, Self) = (Open01.sample(rng), Standard.sample(rng));
+        u.copied();
+        v.copied();
+        let (mut minus_two, mut two) = (Self::from_f64(-2.0), Self::from_f64(2.0));
+        minus_two.copied();
+        two.copied();
+        let mut log = u.ln();
+        log.copied();
+        log *= &minus_two;
+        let mut radius = log.sqrt();
+        radius.copied();
+        v *= &two;
+        let mut cosine = v.cos_pi();
+        cosine.copied();
+        radius *= &cosine;
+        radius.released()
+    }
+
+    /// k / 2^`bits` for a uniformly random integer 0 <= k < 2^`bits`, by chunks of `CHUNK_BITS`,
+    /// each scaled to its place. Every step is exact, as long as `bits` fit into the precision.
+    fn random_bits<R: Rng + ?Sized>(rng: &mut R, bits: usize) -> Self {
+        let mut result = Self::ZERO;
+        result.copied();
+        let mut done = 0;
+        while done < bits {
+            let chunk_bits = (bits - done).min(CHUNK_BITS);
+            done += chunk_bits;
+            let chunk = rng.next_u32() >> (32 - chunk_bits);
+            let mut chunk = Self::from_f64(f64::from(chunk));
+            chunk.copied();
+            let mut part = chunk.ldexp(-(done as i64));
+            part.copied();
+            result += &part;
+        }
+        result.released()
+    }
+}
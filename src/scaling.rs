@@ -0,0 +1,100 @@
+use core::cell::Cell;
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use {core::convert::TryFrom, crate::mpfr};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Scaling by powers of two. That's exact, unless the result overflows or underflows (for f32,
+/// f64 and TwoFloat: becomes subnormal, or zero), and then it's rounded to nearest as any other
+/// result. For TwoFloat both parts are scaled, so the lower part may underflow (and lose bits)
+/// well before the higher one.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// (mantissa, exponent), such that `self` = mantissa * 2^exponent, with 0.5 <= |mantissa| < 1
+    /// (as C's `frexp()` and MPFR's `mpfr_frexp()`). Zeros, infinities and NaN are returned as they
+    /// are, with exponent 0.
+    pub fn frexp(&self) -> (Self, i64) {
+        let exponent = Cell::new(0);
+        let mantissa = self.unary(
+            |x| {
+                let (mantissa, power) = libm::frexpf(x);
+                exponent.set(i64::from(power));
+                mantissa
+            },
+            |x| {
+                let (mantissa, power) = libm::frexp(x);
+                exponent.set(i64::from(power));
+                mantissa
+            },
+            twofloat_op!(|x| {
+                let (mantissa, power) = frexp_twofloat(x);
+                exponent.set(power);
+                mantissa
+            }),
+            mpfr_op!(|r, x| unsafe {
+                let mut power: mpfr::exp_t = 0;
+                let ternary = mpfr::frexp(&mut power, r, x, mpfr::rnd_t::RNDN);
+                // Unspecified for zeros, infinities and NaN.
+                exponent.set(if mpfr::regular_p(x) != 0 { i64::from(power) } else { 0 });
+                ternary
+            }));
+        (mantissa, exponent.get())
+    }
+
+    /// `self` * 2^`exponent`.
+    pub fn ldexp(&self, exponent: i64) -> Self {
+        self.unary(
+            |x| libm::scalbnf(x, clamp_exponent(exponent)),
+            |x| libm::scalbn(x, clamp_exponent(exponent)),
+            twofloat_op!(|x| ldexp_twofloat(x, clamp_exponent(exponent))),
+            mpfr_op!(|r, x| unsafe {
+                let exponent = mpfr::exp_t::try_from(exponent).unwrap_or(if exponent < 0 { mpfr::exp_t::MIN } else { mpfr::exp_t::MAX });
+                mpfr::mul_2si(r, x, exponent, mpfr::rnd_t::RNDN)
+            }))
+    }
+
+    /// Same as `ldexp()` (by MPFR's name).
+    #[inline]
+    pub fn mul_2i(&self, exponent: i64) -> Self {
+        self.ldexp(exponent)
+    }
+}
+
+/// Beyond +-2^12, f32, f64 and TwoFloat overflow (or underflow) anyway.
+fn clamp_exponent(exponent: i64) -> i32 {
+    exponent.max(-(1 << 12)).min(1 << 12) as i32
+}
+
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+#[cfg(feature = "twofloat")]
+fn ldexp_twofloat(x: TwoFloat, exponent: i32) -> TwoFloat {
+    let high = libm::scalbn(x.hi(), exponent);
+    if !high.is_finite() || high == 0.0 {
+        TwoFloat::from(high)
+    } else {
+        TwoFloat::new_add(high, libm::scalbn(x.lo(), exponent))
+    }
+}
+
+/// By the higher part, but one binade lower if it's a power of two and the lower part goes toward
+/// zero (then the sum is less than the power of two).
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+#[cfg(feature = "twofloat")]
+fn frexp_twofloat(x: TwoFloat) -> (TwoFloat, i64) {
+    let (high, low) = (x.hi(), x.lo());
+    if !high.is_finite() || high == 0.0 {
+        return (x, 0);
+    }
+    let (fraction, mut exponent) = libm::frexp(high);
+    if libm::fabs(fraction) == 0.5 && low != 0.0 && (low < 0.0) != (high < 0.0) {
+        exponent -= 1;
+    }
+    (ldexp_twofloat(x, -exponent), i64::from(exponent))
+}
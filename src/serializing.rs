@@ -0,0 +1,429 @@
+use core::{fmt, fmt::Write};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de, ser::{SerializeStruct, SerializeTuple}};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const NAME: &str = "UniFloat";
+const HUMAN_READABLE_FIELDS: &[&str] = &["choice", "value"];
+const BINARY_FIELDS: &[&str] = &["choice", "negative", "exponent", "limbs"];
+
+/// Like "F32", "F64", "TwoFloat", "QuadFloat", "Mpfr(100)" (with the precision in bits),
+/// "Fixed(16, 16)", "Posit(32, 2)" or "Decimal(16)" (with the digits). The limb parts don't
+/// matter, since they follow from the precision.
+struct ChoiceTag(UniFloatChoice);
+
+impl fmt::Display for ChoiceTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            UniFloatChoice::F32 => f.write_str("F32"),
+            UniFloatChoice::F64 => f.write_str("F64"),
+            UniFloatChoice::TwoFloat => f.write_str("TwoFloat"),
+            UniFloatChoice::QuadFloat => f.write_str("QuadFloat"),
+            UniFloatChoice::Mpfr { bounds } => write!(f, "Mpfr({})", bounds.precision_bits),
+            UniFloatChoice::Fixed { int_bits, frac_bits, .. } => write!(f, "Fixed({}, {})", int_bits, frac_bits),
+            UniFloatChoice::Posit { nbits, es } => write!(f, "Posit({}, {})", nbits, es),
+            UniFloatChoice::Decimal { digits } => write!(f, "Decimal({})", digits),
+        }
+    }
+}
+
+impl Serialize for ChoiceTag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Compares what gets written with the rest of a given string, so that a tag can be checked
+/// without allocating.
+struct Matcher<'a> {
+    rest: &'a str,
+}
+
+impl fmt::Write for Matcher<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.rest.strip_prefix(s) {
+            Some(rest) => {
+                self.rest = rest;
+                Ok(())
+            },
+            None => Err(fmt::Error)
+        }
+    }
+}
+
+/// Accepts only the tag of the given choice.
+struct ChoiceSeed(UniFloatChoice);
+
+impl <'de> de::DeserializeSeed<'de> for ChoiceSeed {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl <'de> de::Visitor<'de> for ChoiceSeed {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "choice \"{}\"", ChoiceTag(self.0))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<(), E> {
+        let mut matcher = Matcher { rest: v };
+        if write!(matcher, "{}", ChoiceTag(self.0)).is_ok() && matcher.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(E::invalid_value(de::Unexpected::Str(v), &self))
+        }
+    }
+}
+
+/// Field names (or their indexes), deserialized as indexes to the given names.
+struct FieldSeed(&'static [&'static str]);
+
+impl <'de> de::DeserializeSeed<'de> for FieldSeed {
+    type Value = usize;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl <'de> de::Visitor<'de> for FieldSeed {
+    type Value = usize;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a field identifier")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<usize, E> {
+        if v < self.0.len() as u64 {
+            Ok(v as usize)
+        } else {
+            Err(E::invalid_value(de::Unexpected::Unsigned(v), &self))
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<usize, E> {
+        self.0.iter().position(|&name| name == v).ok_or_else(|| E::unknown_field(v, self.0))
+    }
+}
+
+/// The value in the human-readable form. See `UniFloat::write_hex()`.
+struct Hex<'a, const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    float: &'a UniFloat<C>
+}
+
+impl <const C: UniFloatChoice> fmt::Display for Hex<'_, C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.float.write_hex(f)
+    }
+}
+
+impl <const C: UniFloatChoice> Serialize for Hex<'_, C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+struct HexSeed<const C: UniFloatChoice>;
+
+impl <'de, const C: UniFloatChoice> de::DeserializeSeed<'de> for HexSeed<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Value = UniFloat<C>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<UniFloat<C>, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl <'de, const C: UniFloatChoice> de::Visitor<'de> for HexSeed<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Value = UniFloat<C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a hexadecimal float")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<UniFloat<C>, E> {
+        UniFloat::<C>::from_hex_str(v).map_err(E::custom)
+    }
+}
+
+/// The limbs in the binary form: a tuple of `UniFloat::word_count()` words. See `UniFloat::word()`.
+struct Words<'a, const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    float: &'a UniFloat<C>
+}
+
+impl <const C: UniFloatChoice> Serialize for Words<'_, C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(UniFloat::<C>::word_count())?;
+        for index in 0..UniFloat::<C>::word_count() {
+            tuple.serialize_element(&self.float.word(index))?;
+        }
+        tuple.end()
+    }
+}
+
+/// Sets the words of a given (not copy fixed) instance.
+struct WordsSeed<'a, const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    float: &'a mut UniFloat<C>
+}
+
+impl <'de, const C: UniFloatChoice> de::DeserializeSeed<'de> for WordsSeed<'_, C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_tuple(UniFloat::<C>::word_count(), self)
+    }
+}
+
+impl <'de, const C: UniFloatChoice> de::Visitor<'de> for WordsSeed<'_, C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a tuple of {} limbs", UniFloat::<C>::word_count())
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        for index in 0..UniFloat::<C>::word_count() {
+            let word: u64 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(index, &self))?;
+            if !self.float.set_word(index, word) {
+                return Err(de::Error::invalid_value(de::Unexpected::Unsigned(word), &self));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lossless: the choice, and then either (for human-readable formats) a hexadecimal float as
+/// per `write_hex()`, or the sign, the exponent and the limbs. The sign and the exponent are
+/// only used by MPFR (they're `false` and 0 otherwise). The limbs are the IEEE 754 bits of f32,
+/// f64 or the parts of TwoFloat, or the significand of MPFR in 64-bit words (least significant
+/// first), so that the result doesn't depend on the platform's limb size.
+///
+/// The hexadecimal float doesn't keep NaN payloads, nor the sign of NaN.
+impl <const C: UniFloatChoice> Serialize for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.assert_copy_fixed();
+        if serializer.is_human_readable() {
+            let mut state = serializer.serialize_struct(NAME, HUMAN_READABLE_FIELDS.len())?;
+            state.serialize_field("choice", &ChoiceTag(C))?;
+            state.serialize_field("value", &Hex { float: self })?;
+            state.end()
+        } else {
+            let (negative, exponent) = match C {
+                #[cfg(not(feature = "f32_only"))]
+                #[cfg(feature = "mpfr")]
+                UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+                    self.mpfr_sign_exponent(),
+                _ => (false, 0)
+            };
+            let mut state = serializer.serialize_struct(NAME, BINARY_FIELDS.len())?;
+            state.serialize_field("choice", &ChoiceTag(C))?;
+            state.serialize_field("negative", &negative)?;
+            state.serialize_field("exponent", &exponent)?;
+            state.serialize_field("limbs", &Words { float: self })?;
+            state.end()
+        }
+    }
+}
+
+struct HumanReadableVisitor<const C: UniFloatChoice>;
+
+impl <'de, const C: UniFloatChoice> de::Visitor<'de> for HumanReadableVisitor<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Value = UniFloat<C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("struct UniFloat")
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<UniFloat<C>, A::Error> {
+        seq.next_element_seed(ChoiceSeed(C))?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        seq.next_element_seed(HexSeed::<C>)?.ok_or_else(|| de::Error::invalid_length(1, &self))
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<UniFloat<C>, A::Error> {
+        let (mut choice, mut value) = (false, None);
+        while let Some(field) = map.next_key_seed(FieldSeed(HUMAN_READABLE_FIELDS))? {
+            if field == 0 {
+                map.next_value_seed(ChoiceSeed(C))?;
+                choice = true;
+            } else {
+                value = Some(map.next_value_seed(HexSeed::<C>)?);
+            }
+        }
+        if !choice {
+            return Err(de::Error::missing_field("choice"));
+        }
+        value.ok_or_else(|| de::Error::missing_field("value"))
+    }
+}
+
+struct BinaryVisitor<const C: UniFloatChoice>;
+
+impl <const C: UniFloatChoice> BinaryVisitor<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Validate the sign and the exponent, once the limbs have been set.
+    #[cfg_attr(feature = "f32_only", allow(unused_mut))]
+    fn finish<E: de::Error>(mut result: UniFloat<C>, negative: bool, exponent: i64) -> Result<UniFloat<C>, E> {
+        let valid = match C {
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+                result.set_mpfr_sign_exponent(negative, exponent),
+            _ => !negative && exponent == 0
+        };
+        if valid {
+            Ok(result.released())
+        } else {
+            Err(E::custom("invalid sign, exponent or limbs"))
+        }
+    }
+}
+
+impl <'de, const C: UniFloatChoice> de::Visitor<'de> for BinaryVisitor<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Value = UniFloat<C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("struct UniFloat")
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<UniFloat<C>, A::Error> {
+        seq.next_element_seed(ChoiceSeed(C))?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let negative = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let exponent = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        let mut result = UniFloat::<C>::NAN;
+        seq.next_element_seed(WordsSeed { float: &mut result })?.ok_or_else(|| de::Error::invalid_length(3, &self))?;
+        Self::finish(result, negative, exponent)
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<UniFloat<C>, A::Error> {
+        let (mut choice, mut negative, mut exponent, mut limbs) = (false, None, None, false);
+        let mut result = UniFloat::<C>::NAN;
+        while let Some(field) = map.next_key_seed(FieldSeed(BINARY_FIELDS))? {
+            match field {
+                0 => {
+                    map.next_value_seed(ChoiceSeed(C))?;
+                    choice = true;
+                },
+                1 => negative = Some(map.next_value()?),
+                2 => exponent = Some(map.next_value()?),
+                _ => {
+                    map.next_value_seed(WordsSeed { float: &mut result })?;
+                    limbs = true;
+                }
+            }
+        }
+        if !choice {
+            return Err(de::Error::missing_field("choice"));
+        }
+        if !limbs {
+            return Err(de::Error::missing_field("limbs"));
+        }
+        Self::finish(result,
+            negative.ok_or_else(|| de::Error::missing_field("negative"))?,
+            exponent.ok_or_else(|| de::Error::missing_field("exponent"))?)
+    }
+}
+
+/// The counterpart of `Serialize`. It fails if the serialized choice (including the precision
+/// of MPFR) differs from `C`, or if the limbs don't make a valid value. MPFR exponents must be in
+/// the current exponent range.
+impl <'de, const C: UniFloatChoice> Deserialize<'de> for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_struct(NAME, HUMAN_READABLE_FIELDS, HumanReadableVisitor::<C>)
+        } else {
+            deserializer.deserialize_struct(NAME, BINARY_FIELDS, BinaryVisitor::<C>)
+        }
+    }
+}
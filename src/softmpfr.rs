@@ -0,0 +1,1043 @@
+use core::cmp::Ordering;
+
+use crate::RoundingMode;
+
+/// The most limbs of `SoftMpfr`, for 4096 bits of precision. Intermediate results live on the
+/// stack, in arrays of this size (doubled).
+pub const SOFTMPFR_MAX_LIMBS: usize = 64;
+/// Limbs of intermediate results: a product of two significands, or the radicand of a square
+/// root (with a guard limb for the root).
+const SCRATCH_LIMBS: usize = 2 * SOFTMPFR_MAX_LIMBS + 2;
+/// MPFR's default exponent range (which this crate doesn't change): 2^(EMIN - 1) <= |value| <
+/// 2^EMAX.
+const EMAX: i64 = (1 << 30) - 1;
+const EMIN: i64 = 1 - (1 << 30);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Nan,
+    Infinite,
+    Zero,
+    Regular,
+}
+
+/// A pure Rust counterpart of MPFR's `mpfr_t`, in `LIMBS` 64-bit limbs (at most
+/// `SOFTMPFR_MAX_LIMBS`), with no C dependency. It's the core of the `softmpfr` feature: the
+/// basic arithmetic (`add()`, `sub()`, `mul()`, `div()` and `sqrt()`) and conversions from and to
+/// f64, correctly rounded in any direction of `RoundingMode`, with MPFR's ternary values. The
+/// layout is as MPFR's: a sign, an exponent e for 2^(e - 1) <= |value| < 2^e, and a normalized
+/// significand, with the bits below the precision clear.
+///
+/// The exponent range is MPFR's default. Overflows are Inf (or the largest value, if rounding
+/// toward zero). Underflows are zero, or the least positive value 2^(emin - 1) if rounding away
+/// from zero (`Up` for positive values, `Down` for negative ones), unlike MPFR's rounding to
+/// nearest there.
+///
+/// With feature `softmpfr` and without feature `mpfr`, `UniFloatChoice::Mpfr` computes the basic
+/// arithmetic, conversions from and to f32 and f64, comparisons, and decimal formatting and parsing
+/// by `SoftMpfr` (see `mpfr_functions`), with no C dependency. Other functions of the MPFR choices
+/// need feature `mpfr` (they fail to compile without it), and so do choices with bounds from
+/// `MpfrBounds::for_bounds()`. With both features, MPFR itself backs them.
+#[derive(Clone, Copy, Debug)]
+pub struct SoftMpfr<const LIMBS: usize> {
+    precision: usize,
+    kind: Kind,
+    negative: bool,
+    exponent: i64,
+    /// Little endian, as MPFR's limbs.
+    limbs: [u64; LIMBS],
+}
+
+impl <const LIMBS: usize> SoftMpfr<LIMBS> {
+    /// NaN (as `mpfr_init2()`) with `precision` bits. Panics unless `precision` is between 1 and
+    /// 64 * `LIMBS`, or if `LIMBS` is more than `SOFTMPFR_MAX_LIMBS`.
+    pub fn new(precision: usize) -> Self {
+        assert!(LIMBS <= SOFTMPFR_MAX_LIMBS, "SoftMpfr supports at most SOFTMPFR_MAX_LIMBS limbs.");
+        assert!(precision >= 1 && precision <= 64 * LIMBS, "SoftMpfr precision must be between 1 and 64 * LIMBS.");
+        Self { precision, kind: Kind::Nan, negative: false, exponent: 0, limbs: [0; LIMBS] }
+    }
+
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    pub fn is_nan(&self) -> bool {
+        self.kind == Kind::Nan
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        self.kind == Kind::Infinite
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.kind == Kind::Zero
+    }
+
+    /// Whether the sign bit is set, including for -0 (but not for NaN).
+    pub fn is_sign_negative(&self) -> bool {
+        self.kind != Kind::Nan && self.negative
+    }
+
+    fn set_special(&mut self, kind: Kind, negative: bool) -> Ordering {
+        self.kind = kind;
+        self.negative = negative;
+        self.limbs = [0; LIMBS];
+        Ordering::Equal
+    }
+
+    /// `x` rounded to the precision of `self`.
+    pub fn set<const X_LIMBS: usize>(&mut self, x: &SoftMpfr<X_LIMBS>, rounding: RoundingMode) -> Ordering {
+        self.set_signed(x, x.negative, rounding)
+    }
+
+    fn set_signed<const X_LIMBS: usize>(&mut self, x: &SoftMpfr<X_LIMBS>, negative: bool, rounding: RoundingMode) -> Ordering {
+        match x.kind {
+            Kind::Regular => self.round_from(negative, x.exponent, &x.limbs, false, rounding),
+            kind => self.set_special(kind, negative)
+        }
+    }
+
+    /// `value` rounded to the precision of `self` (exact for 53 bits or more).
+    pub fn set_f64(&mut self, value: f64, rounding: RoundingMode) -> Ordering {
+        let negative = value.is_sign_negative();
+        if value.is_nan() {
+            return self.set_special(Kind::Nan, false);
+        } else if value.is_infinite() {
+            return self.set_special(Kind::Infinite, negative);
+        } else if value == 0.0 {
+            return self.set_special(Kind::Zero, negative);
+        }
+        let bits = value.to_bits();
+        let (biased, fraction) = ((bits >> 52) & 0x7ff, bits & ((1 << 52) - 1));
+        // |value| = significand * 2^exponent.
+        let (significand, exponent) = if biased == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), biased as i64 - 1075)
+        };
+        let shift = significand.leading_zeros();
+        self.round_from(negative, exponent + 64 - shift as i64, &[significand << shift], false, rounding)
+    }
+
+    /// `self` rounded to f64 in the direction of `rounding`, with f64's subnormals and overflows.
+    pub fn to_f64(&self, rounding: RoundingMode) -> f64 {
+        self.to_binary(rounding, f64::MANTISSA_DIGITS, f64::MIN_EXP, f64::MAX_EXP, f64::MAX)
+    }
+
+    /// `self` rounded to f32 in the direction of `rounding`, with f32's subnormals and overflows.
+    pub fn to_f32(&self, rounding: RoundingMode) -> f32 {
+        // Exact: it's on f32's grid (or Inf).
+        self.to_binary(rounding, f32::MANTISSA_DIGITS, f32::MIN_EXP, f32::MAX_EXP, f32::MAX as f64) as f32
+    }
+
+    /// `self` rounded to the binary format of `digits` bits, exponents `min_exp` to `max_exp` (as
+    /// `f64::MIN_EXP` and `f64::MAX_EXP`) and the largest value `max`, with its subnormals and
+    /// overflows. The format must fit into f64.
+    fn to_binary(&self, rounding: RoundingMode, digits: u32, min_exp: i32, max_exp: i32, max: f64) -> f64 {
+        let sign = if self.negative { -1.0 } else { 1.0 };
+        match self.kind {
+            Kind::Nan => return f64::NAN,
+            Kind::Infinite => return sign * f64::INFINITY,
+            Kind::Zero => return sign * 0.0,
+            Kind::Regular => {}
+        }
+        let exponent = self.exponent;
+        // The least subnormal is 2^least_exponent: 2^-1074 for f64.
+        let least_exponent = min_exp as i64 - digits as i64;
+        if exponent > max_exp as i64 {
+            return sign * if rounds_away(rounding, self.negative, true) { f64::INFINITY } else { max };
+        } else if exponent < least_exponent {
+            // Below half of the least subnormal.
+            return sign * if rounds_away(rounding, self.negative, false) {
+                libm::ldexp(1.0, least_exponent as i32)
+            } else {
+                0.0
+            };
+        }
+        // Subnormals have fewer bits (none for half of the least subnormal <= |self| < the least
+        // subnormal).
+        let precision = if exponent >= min_exp as i64 { digits as usize } else { (exponent - least_exponent) as usize };
+        let mut word = [0];
+        let (carry, _) = round_words(&mut word, precision, &self.limbs, false, self.negative, rounding);
+        let exponent = exponent + carry as i64;
+        // Exact: the significand has `digits` bits at most, and the result is on the grid of the
+        // format (or Inf).
+        sign * libm::ldexp((word[0] >> (64 - digits)) as f64, (exponent - digits as i64) as i32)
+    }
+
+    /// `x + y`, rounded to the precision of `self`. Returns the ternary value (as MPFR's: how the
+    /// result compares to the exact value).
+    pub fn add(&mut self, x: &Self, y: &Self, rounding: RoundingMode) -> Ordering {
+        self.sum(x, y, false, rounding)
+    }
+
+    /// `x - y`, rounded to the precision of `self`. Returns the ternary value.
+    pub fn sub(&mut self, x: &Self, y: &Self, rounding: RoundingMode) -> Ordering {
+        self.sum(x, y, true, rounding)
+    }
+
+    fn sum(&mut self, x: &Self, y: &Self, negate_y: bool, rounding: RoundingMode) -> Ordering {
+        let y_negative = y.negative != negate_y;
+        match (x.kind, y.kind) {
+            (Kind::Nan, _) | (_, Kind::Nan) => return self.set_special(Kind::Nan, false),
+            (Kind::Infinite, Kind::Infinite) => return if x.negative == y_negative {
+                self.set_special(Kind::Infinite, x.negative)
+            } else {
+                self.set_special(Kind::Nan, false)
+            },
+            (Kind::Infinite, _) => return self.set_special(Kind::Infinite, x.negative),
+            (_, Kind::Infinite) => return self.set_special(Kind::Infinite, y_negative),
+            // Exact zero sums are -0 for `Down`, as in IEEE 754.
+            (Kind::Zero, Kind::Zero) => return self.set_special(Kind::Zero,
+                if x.negative == y_negative { x.negative } else { rounding == RoundingMode::Down }),
+            (Kind::Zero, _) => return self.set_signed(y, y_negative, rounding),
+            (_, Kind::Zero) => return self.set_signed(x, x.negative, rounding),
+            (Kind::Regular, Kind::Regular) => {}
+        }
+        let ((big, big_negative), (small, small_negative)) = if compare_magnitudes(x, y) == Ordering::Less {
+            ((y, y_negative), (x, x.negative))
+        } else {
+            ((x, x.negative), (y, y_negative))
+        };
+        // Two guard limbs. Once `small` is shifted beyond them, there's at most one bit of
+        // cancellation, so what's shifted out counts only as a sticky bit.
+        let width = LIMBS + 2;
+        let (mut a, mut b) = ([0; SCRATCH_LIMBS], [0; SCRATCH_LIMBS]);
+        let (a, b) = (&mut a[..width], &mut b[..width]);
+        a[2..].copy_from_slice(&big.limbs);
+        b[2..].copy_from_slice(&small.limbs);
+        let shift = (big.exponent - small.exponent).min(64 * width as i64) as usize;
+        let mut sticky = shift_right(b, shift);
+        let mut exponent = big.exponent;
+        if big_negative == small_negative {
+            if add_words(a, b) {
+                sticky |= shift_right(a, 1);
+                a[width - 1] |= 1 << 63;
+                exponent += 1;
+            }
+        } else {
+            sub_words(a, b);
+            // The exact `small` is a little more than `b`: `a` - `b` - 1, plus something in (0, 1).
+            if sticky {
+                sub_words(a, &[1]);
+            }
+            let zeros = leading_zeros(a);
+            if zeros == 64 * width {
+                return self.set_special(Kind::Zero, rounding == RoundingMode::Down);
+            }
+            shift_left(a, zeros);
+            exponent -= zeros as i64;
+        }
+        self.round_from(big_negative, exponent, a, sticky, rounding)
+    }
+
+    /// `x * y`, rounded to the precision of `self`. Returns the ternary value.
+    pub fn mul(&mut self, x: &Self, y: &Self, rounding: RoundingMode) -> Ordering {
+        let negative = x.negative != y.negative;
+        match (x.kind, y.kind) {
+            (Kind::Nan, _) | (_, Kind::Nan) | (Kind::Infinite, Kind::Zero) | (Kind::Zero, Kind::Infinite) =>
+                return self.set_special(Kind::Nan, false),
+            (Kind::Infinite, _) | (_, Kind::Infinite) => return self.set_special(Kind::Infinite, negative),
+            (Kind::Zero, _) | (_, Kind::Zero) => return self.set_special(Kind::Zero, negative),
+            (Kind::Regular, Kind::Regular) => {}
+        }
+        let width = 2 * LIMBS;
+        let mut product = [0; SCRATCH_LIMBS];
+        let product = &mut product[..width];
+        for (i, &x_limb) in x.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &y_limb) in y.limbs.iter().enumerate() {
+                let partial = x_limb as u128 * y_limb as u128 + product[i + j] as u128 + carry;
+                product[i + j] = partial as u64;
+                carry = partial >> 64;
+            }
+            product[i + LIMBS] = carry as u64;
+        }
+        // Both significands are in [1/2, 1), so the product is in [1/4, 1).
+        let mut exponent = x.exponent + y.exponent;
+        if product[width - 1] >> 63 == 0 {
+            shift_left(product, 1);
+            exponent -= 1;
+        }
+        self.round_from(negative, exponent, product, false, rounding)
+    }
+
+    /// `x / y`, rounded to the precision of `self`. Returns the ternary value. Division of
+    /// non-zero values by zero is Inf.
+    pub fn div(&mut self, x: &Self, y: &Self, rounding: RoundingMode) -> Ordering {
+        let negative = x.negative != y.negative;
+        match (x.kind, y.kind) {
+            (Kind::Nan, _) | (_, Kind::Nan) | (Kind::Infinite, Kind::Infinite) | (Kind::Zero, Kind::Zero) =>
+                return self.set_special(Kind::Nan, false),
+            (Kind::Infinite, _) | (_, Kind::Zero) => return self.set_special(Kind::Infinite, negative),
+            (Kind::Zero, _) | (_, Kind::Infinite) => return self.set_special(Kind::Zero, negative),
+            (Kind::Regular, Kind::Regular) => {}
+        }
+        // Long division, one bit at a time: 64 bits more than the precision can need. The
+        // remainder stays below twice the divisor, so one more limb holds it.
+        let width = LIMBS + 1;
+        let (mut remainder, mut divisor, mut quotient) = ([0; SCRATCH_LIMBS], [0; SCRATCH_LIMBS], [0; SCRATCH_LIMBS]);
+        let (remainder, divisor, quotient) = (&mut remainder[..width], &mut divisor[..width], &mut quotient[..width]);
+        remainder[..LIMBS].copy_from_slice(&x.limbs);
+        divisor[..LIMBS].copy_from_slice(&y.limbs);
+        for index in 0..64 * width {
+            if cmp_words(remainder, divisor) != Ordering::Less {
+                sub_words(remainder, divisor);
+                let position = 64 * width - 1 - index;
+                quotient[position / 64] |= 1 << (position % 64);
+            }
+            shift_left(remainder, 1);
+        }
+        // The first quotient bit is of 2^0, as the significands are in [1/2, 1).
+        let mut exponent = x.exponent - y.exponent + 1;
+        if quotient[width - 1] >> 63 == 0 {
+            shift_left(quotient, 1);
+            exponent -= 1;
+        }
+        let sticky = remainder.iter().any(|&limb| limb != 0);
+        self.round_from(negative, exponent, quotient, sticky, rounding)
+    }
+
+    /// The square root of `x`, rounded to the precision of `self`. Returns the ternary value. NaN
+    /// for negative `x`, but -0 for -0.
+    pub fn sqrt(&mut self, x: &Self, rounding: RoundingMode) -> Ordering {
+        match x.kind {
+            Kind::Nan => return self.set_special(Kind::Nan, false),
+            Kind::Zero => return self.set_special(Kind::Zero, x.negative),
+            _ if x.negative => return self.set_special(Kind::Nan, false),
+            Kind::Infinite => return self.set_special(Kind::Infinite, false),
+            Kind::Regular => {}
+        }
+        // The root of 0.significand (or of half of it, for odd exponents), as an integer of
+        // `root_width` limbs from the radicand of twice as many, two bits at a time.
+        let root_width = LIMBS + 1;
+        let radicand_width = 2 * root_width;
+        let remainder_width = root_width + 1;
+        let mut radicand = [0; SCRATCH_LIMBS];
+        let radicand = &mut radicand[..radicand_width];
+        radicand[radicand_width - LIMBS..].copy_from_slice(&x.limbs);
+        let odd = x.exponent & 1 != 0;
+        if odd {
+            shift_right(radicand, 1);
+        }
+        let exponent = if odd { (x.exponent + 1) / 2 } else { x.exponent / 2 };
+        let (mut remainder, mut root, mut trial) = ([0; SCRATCH_LIMBS], [0; SCRATCH_LIMBS], [0; SCRATCH_LIMBS]);
+        let (remainder, root, trial) =
+            (&mut remainder[..remainder_width], &mut root[..remainder_width], &mut trial[..remainder_width]);
+        for index in 0..64 * root_width {
+            shift_left(remainder, 2);
+            remainder[0] |= (bit_from_top(radicand, 2 * index) as u64) << 1 | bit_from_top(radicand, 2 * index + 1) as u64;
+            // 4 * root + 1 is the difference of the squares of 2 * root + 1 and 2 * root.
+            trial.copy_from_slice(root);
+            shift_left(trial, 2);
+            trial[0] |= 1;
+            shift_left(root, 1);
+            if cmp_words(remainder, trial) != Ordering::Less {
+                sub_words(remainder, trial);
+                root[0] |= 1;
+            }
+        }
+        let sticky = remainder.iter().any(|&limb| limb != 0);
+        self.round_from(false, exponent, &root[..root_width], sticky, rounding)
+    }
+
+    /// Sets `self` to 0.`words` * 2^`exponent` (followed by more non-zero bits if `sticky`),
+    /// rounded to its precision, and limited to the exponent range. The highest bit of `words`
+    /// (little endian) must be set. Returns the ternary value.
+    fn round_from(&mut self, negative: bool, exponent: i64, words: &[u64], sticky: bool, rounding: RoundingMode) -> Ordering {
+        let (carry, inexact) = round_words(&mut self.limbs, self.precision, words, sticky, negative, rounding);
+        let exponent = exponent + carry as i64;
+        self.kind = Kind::Regular;
+        self.negative = negative;
+        self.exponent = exponent;
+        let away = if exponent > EMAX {
+            // Inf, or the largest value.
+            if rounds_away(rounding, negative, true) {
+                self.set_special(Kind::Infinite, negative);
+            } else {
+                self.limbs = [!0; LIMBS];
+                clear_below(&mut self.limbs, 64 * LIMBS - self.precision);
+                self.exponent = EMAX;
+            }
+            rounds_away(rounding, negative, true)
+        } else if exponent < EMIN {
+            // Zero, or the least positive value.
+            if rounds_away(rounding, negative, false) {
+                self.limbs = [0; LIMBS];
+                self.limbs[LIMBS - 1] = 1 << 63;
+                self.exponent = EMIN;
+            } else {
+                self.set_special(Kind::Zero, negative);
+            }
+            rounds_away(rounding, negative, false)
+        } else if let Some(away) = inexact {
+            away
+        } else {
+            return Ordering::Equal;
+        };
+        // Away from zero is up for positive values.
+        if away != negative { Ordering::Greater } else { Ordering::Less }
+    }
+}
+
+/// Whether an inexact value that's out of range rounds away from zero: to Inf for overflows
+/// (`overflow`), or to the least value for underflows.
+fn rounds_away(rounding: RoundingMode, negative: bool, overflow: bool) -> bool {
+    match rounding {
+        RoundingMode::Nearest | RoundingMode::ToNearestAway | RoundingMode::Faithful => overflow,
+        RoundingMode::TowardZero => false,
+        RoundingMode::Up => !negative,
+        RoundingMode::Down => negative,
+    }
+}
+
+/// Sets `target` to the highest `precision` bits of `words` (both little endian, and aligned at
+/// their highest bits), rounded in the direction of `rounding` for the sign of `negative`, where
+/// `sticky` stands for non-zero bits below `words`. With `precision` 0, the result is 0 or the
+/// carry. Returns the carry (then `target` is the highest bit, and the exponent goes up by one),
+/// and, if inexact, whether it rounded away from zero.
+fn round_words(target: &mut [u64], precision: usize, words: &[u64], sticky: bool, negative: bool,
+    rounding: RoundingMode) -> (bool, Option<bool>) {
+    let (target_length, length) = (target.len(), words.len());
+    for index in 0..target_length {
+        target[target_length - 1 - index] = if index < length { words[length - 1 - index] } else { 0 };
+    }
+    let low_bits = 64 * target_length - precision;
+    clear_below(target, low_bits);
+    let round_bit = precision < 64 * length && bit_from_top(words, precision);
+    let sticky = sticky || any_below(words, (64 * length).saturating_sub(precision + 1));
+    if !round_bit && !sticky {
+        return (false, None);
+    }
+    // Ties go to even: precision 0 counts as even.
+    let odd = precision > 0 && (target[low_bits / 64] >> (low_bits % 64)) & 1 != 0;
+    let away = match rounding {
+        RoundingMode::Nearest | RoundingMode::Faithful => round_bit && (sticky || odd),
+        RoundingMode::ToNearestAway => round_bit,
+        RoundingMode::TowardZero => false,
+        RoundingMode::Up => !negative,
+        RoundingMode::Down => negative,
+    };
+    let carry = away && (low_bits == 64 * target_length || add_bit(target, low_bits));
+    if carry {
+        target[target_length - 1] = 1 << 63;
+    }
+    (carry, Some(away))
+}
+
+fn compare_magnitudes<const LIMBS: usize>(x: &SoftMpfr<LIMBS>, y: &SoftMpfr<LIMBS>) -> Ordering {
+    x.exponent.cmp(&y.exponent).then_with(|| cmp_words(&x.limbs, &y.limbs))
+}
+
+/// NaN is unordered, and -0 equals +0.
+impl <const LIMBS: usize> PartialOrd for SoftMpfr<LIMBS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let signed = |ordering: Ordering, negative: bool| if negative { ordering.reverse() } else { ordering };
+        match (self.kind, other.kind) {
+            (Kind::Nan, _) | (_, Kind::Nan) => None,
+            (Kind::Zero, Kind::Zero) => Some(Ordering::Equal),
+            (Kind::Zero, _) => Some(signed(Ordering::Less, other.negative)),
+            (_, Kind::Zero) => Some(signed(Ordering::Greater, self.negative)),
+            _ if self.negative != other.negative => Some(signed(Ordering::Greater, self.negative)),
+            (Kind::Infinite, Kind::Infinite) => Some(Ordering::Equal),
+            (Kind::Infinite, _) => Some(signed(Ordering::Greater, self.negative)),
+            (_, Kind::Infinite) => Some(signed(Ordering::Less, self.negative)),
+            (Kind::Regular, Kind::Regular) => Some(signed(compare_magnitudes(self, other), self.negative)),
+        }
+    }
+}
+
+impl <const LIMBS: usize> PartialEq for SoftMpfr<LIMBS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+// Little endian multi-limb helpers. Slices of the same operation have the same length.
+
+/// Bit `index` of `words`, counted from the highest one (0).
+fn bit_from_top(words: &[u64], index: usize) -> bool {
+    let position = 64 * words.len() - 1 - index;
+    (words[position / 64] >> (position % 64)) & 1 != 0
+}
+
+/// Whether any of the lowest `count` bits of `words` is set.
+fn any_below(words: &[u64], count: usize) -> bool {
+    let (whole, rest) = (count / 64, count % 64);
+    words[..whole].iter().any(|&word| word != 0) || (rest > 0 && words[whole] & ((1 << rest) - 1) != 0)
+}
+
+/// Clears the lowest `count` bits of `words`.
+fn clear_below(words: &mut [u64], count: usize) {
+    let (whole, rest) = (count / 64, count % 64);
+    words[..whole].iter_mut().for_each(|word| *word = 0);
+    if rest > 0 {
+        words[whole] &= !((1 << rest) - 1);
+    }
+}
+
+fn leading_zeros(words: &[u64]) -> usize {
+    let mut zeros = 0;
+    for &word in words.iter().rev() {
+        if word != 0 {
+            return zeros + word.leading_zeros() as usize;
+        }
+        zeros += 64;
+    }
+    zeros
+}
+
+fn cmp_words(x: &[u64], y: &[u64]) -> Ordering {
+    x.iter().rev().cmp(y.iter().rev())
+}
+
+/// Returns whether any of the bits shifted out was set.
+fn shift_right(words: &mut [u64], bits: usize) -> bool {
+    let length = words.len();
+    if bits >= 64 * length {
+        let lost = words.iter().any(|&word| word != 0);
+        words.iter_mut().for_each(|word| *word = 0);
+        return lost;
+    }
+    let (word_shift, bit_shift) = (bits / 64, bits % 64);
+    let lost = any_below(words, bits);
+    for index in 0..length {
+        let source = index + word_shift;
+        let low = if source < length { words[source] >> bit_shift } else { 0 };
+        let high = if bit_shift > 0 && source + 1 < length { words[source + 1] << (64 - bit_shift) } else { 0 };
+        words[index] = low | high;
+    }
+    lost
+}
+
+/// Shifts by fewer bits than `words` has.
+fn shift_left(words: &mut [u64], bits: usize) {
+    let (word_shift, bit_shift) = (bits / 64, bits % 64);
+    for index in (0..words.len()).rev() {
+        words[index] = if index < word_shift {
+            0
+        } else {
+            let source = index - word_shift;
+            let low = if bit_shift > 0 && source > 0 { words[source - 1] >> (64 - bit_shift) } else { 0 };
+            words[source] << bit_shift | low
+        };
+    }
+}
+
+/// Returns the carry.
+fn add_words(x: &mut [u64], y: &[u64]) -> bool {
+    let mut carry = false;
+    for (x_word, &y_word) in x.iter_mut().zip(y) {
+        let (sum, first) = x_word.overflowing_add(y_word);
+        let (sum, second) = sum.overflowing_add(carry as u64);
+        *x_word = sum;
+        carry = first || second;
+    }
+    carry
+}
+
+/// Subtracts `y`, which may be shorter than `x`. Returns the borrow.
+fn sub_words(x: &mut [u64], y: &[u64]) -> bool {
+    let mut borrow = false;
+    for (index, x_word) in x.iter_mut().enumerate() {
+        let y_word = y.get(index).copied().unwrap_or(0);
+        let (difference, first) = x_word.overflowing_sub(y_word);
+        let (difference, second) = difference.overflowing_sub(borrow as u64);
+        *x_word = difference;
+        borrow = first || second;
+    }
+    borrow
+}
+
+/// Adds 2^`position`. Returns the carry.
+fn add_bit(words: &mut [u64], position: usize) -> bool {
+    let mut addend = 1 << (position % 64);
+    for word in words[position / 64..].iter_mut() {
+        let (sum, carry) = word.overflowing_add(addend);
+        *word = sum;
+        if !carry {
+            return false;
+        }
+        addend = 1;
+    }
+    true
+}
+
+/// Without feature `mpfr`: the functions of MPFR that the basic operations (see `softmpfr_op!`),
+/// decimal formatting and parsing of the MPFR choices call, with MPFR's signatures, on
+/// `mpfr::mpfr_t` as MPFR lays it out. They're computed by `SoftMpfr`, of the fewest limbs (of a
+/// few sizes) that hold the precisions of their operands and result.
+#[cfg(not(feature = "mpfr"))]
+#[cfg_attr(feature = "f32_only", allow(dead_code))]
+pub(crate) mod mpfr_functions {
+    use core::{cmp::Ordering, slice};
+
+    use super::{EMAX, EMIN, Kind, SOFTMPFR_MAX_LIMBS, SoftMpfr, compare_magnitudes, leading_zeros, round_words,
+        shift_left, shift_right};
+    use crate::mpfr::{exp_t, mpfr_ptr, mpfr_srcptr, prec_t, rnd_t};
+    use crate::{RoundingMode, INF_MPFR_EXP, NAN_MPFR_EXP, ZERO_MPFR_EXP};
+    #[cfg(not(feature = "f32_only"))]
+    use crate::radix::{RadixLiteral, parse_decimal_literal};
+
+    /// Calls `$function::<LIMBS>($arg, ...)` with `LIMBS` of at least `$precision` bits.
+    macro_rules! with_limbs {
+        ($precision:expr, $function:ident($($arg:expr),*)) => {
+            match ($precision as usize + 63) / 64 {
+                0..=1 => $function::<1>($($arg),*),
+                2 => $function::<2>($($arg),*),
+                3..=4 => $function::<4>($($arg),*),
+                5..=16 => $function::<16>($($arg),*),
+                _ => $function::<SOFTMPFR_MAX_LIMBS>($($arg),*),
+            }
+        };
+    }
+
+    fn rounding(rnd: rnd_t) -> RoundingMode {
+        match rnd {
+            rnd_t::RNDN => RoundingMode::Nearest,
+            rnd_t::RNDZ => RoundingMode::TowardZero,
+            rnd_t::RNDU => RoundingMode::Up,
+            rnd_t::RNDD => RoundingMode::Down,
+            rnd_t::RNDF => RoundingMode::Faithful,
+            rnd_t::RNDNA => RoundingMode::ToNearestAway,
+            rnd_t::RNDA => unreachable!("SoftMpfr doesn't round away from zero (RNDA).")
+        }
+    }
+
+    unsafe fn max_precision(values: &[mpfr_srcptr]) -> prec_t {
+        values.iter().map(|&x| (*x).prec).max().unwrap_or(1)
+    }
+
+    unsafe fn load<const LIMBS: usize>(x: mpfr_srcptr) -> SoftMpfr<LIMBS> {
+        let x = &*x;
+        let mut soft = SoftMpfr::new(x.prec as usize);
+        soft.negative = x.sign < 0;
+        soft.kind = match x.exp {
+            NAN_MPFR_EXP => Kind::Nan,
+            INF_MPFR_EXP => Kind::Infinite,
+            ZERO_MPFR_EXP => Kind::Zero,
+            exponent => {
+                let length = (x.prec as usize + 63) / 64;
+                soft.limbs[LIMBS - length..].copy_from_slice(slice::from_raw_parts(x.d.as_ptr(), length));
+                soft.exponent = exponent;
+                Kind::Regular
+            }
+        };
+        soft
+    }
+
+    /// `soft` must have the precision of `r`.
+    unsafe fn store<const LIMBS: usize>(r: mpfr_ptr, soft: &SoftMpfr<LIMBS>) {
+        let r = &mut *r;
+        r.sign = if soft.negative { -1 } else { 1 };
+        r.exp = match soft.kind {
+            Kind::Nan => NAN_MPFR_EXP,
+            Kind::Infinite => INF_MPFR_EXP,
+            Kind::Zero => ZERO_MPFR_EXP,
+            Kind::Regular => {
+                let length = (r.prec as usize + 63) / 64;
+                slice::from_raw_parts_mut(r.d.as_ptr(), length).copy_from_slice(&soft.limbs[LIMBS - length..]);
+                soft.exponent
+            }
+        };
+    }
+
+    unsafe fn unary<const LIMBS: usize>(r: mpfr_ptr, x: mpfr_srcptr, rnd: rnd_t,
+        op: fn(&mut SoftMpfr<LIMBS>, &SoftMpfr<LIMBS>, RoundingMode) -> Ordering) -> i32 {
+        let x = load::<LIMBS>(x);
+        let mut result = SoftMpfr::new((*r).prec as usize);
+        let ternary = op(&mut result, &x, rounding(rnd));
+        store(r, &result);
+        ternary as i32
+    }
+
+    unsafe fn binary<const LIMBS: usize>(r: mpfr_ptr, x: mpfr_srcptr, y: mpfr_srcptr, rnd: rnd_t,
+        op: fn(&mut SoftMpfr<LIMBS>, &SoftMpfr<LIMBS>, &SoftMpfr<LIMBS>, RoundingMode) -> Ordering) -> i32 {
+        let (x, y) = (load::<LIMBS>(x), load::<LIMBS>(y));
+        let mut result = SoftMpfr::new((*r).prec as usize);
+        let ternary = op(&mut result, &x, &y, rounding(rnd));
+        store(r, &result);
+        ternary as i32
+    }
+
+    pub unsafe fn add(r: mpfr_ptr, x: mpfr_srcptr, y: mpfr_srcptr, rnd: rnd_t) -> i32 {
+        with_limbs!(max_precision(&[r as mpfr_srcptr, x, y]), binary(r, x, y, rnd, SoftMpfr::add))
+    }
+
+    pub unsafe fn sub(r: mpfr_ptr, x: mpfr_srcptr, y: mpfr_srcptr, rnd: rnd_t) -> i32 {
+        with_limbs!(max_precision(&[r as mpfr_srcptr, x, y]), binary(r, x, y, rnd, SoftMpfr::sub))
+    }
+
+    pub unsafe fn mul(r: mpfr_ptr, x: mpfr_srcptr, y: mpfr_srcptr, rnd: rnd_t) -> i32 {
+        with_limbs!(max_precision(&[r as mpfr_srcptr, x, y]), binary(r, x, y, rnd, SoftMpfr::mul))
+    }
+
+    pub unsafe fn div(r: mpfr_ptr, x: mpfr_srcptr, y: mpfr_srcptr, rnd: rnd_t) -> i32 {
+        with_limbs!(max_precision(&[r as mpfr_srcptr, x, y]), binary(r, x, y, rnd, SoftMpfr::div))
+    }
+
+    pub unsafe fn sqrt(r: mpfr_ptr, x: mpfr_srcptr, rnd: rnd_t) -> i32 {
+        with_limbs!(max_precision(&[r as mpfr_srcptr, x]), unary(r, x, rnd, SoftMpfr::sqrt))
+    }
+
+    pub unsafe fn set(r: mpfr_ptr, x: mpfr_srcptr, rnd: rnd_t) -> i32 {
+        with_limbs!(max_precision(&[r as mpfr_srcptr, x]), unary(r, x, rnd, |result, x, rounding| result.set(x, rounding)))
+    }
+
+    pub unsafe fn neg(r: mpfr_ptr, x: mpfr_srcptr, rnd: rnd_t) -> i32 {
+        with_limbs!(max_precision(&[r as mpfr_srcptr, x]), unary(r, x, rnd, |result, x, rounding| result.set_signed(x, !x.negative, rounding)))
+    }
+
+    unsafe fn set_d_in<const LIMBS: usize>(r: mpfr_ptr, value: f64, rnd: rnd_t) -> i32 {
+        let mut result = SoftMpfr::<LIMBS>::new((*r).prec as usize);
+        let ternary = result.set_f64(value, rounding(rnd));
+        store(r, &result);
+        ternary as i32
+    }
+
+    pub unsafe fn set_d(r: mpfr_ptr, value: f64, rnd: rnd_t) -> i32 {
+        with_limbs!((*r).prec, set_d_in(r, value, rnd))
+    }
+
+    /// f32 values are exact in f64.
+    pub unsafe fn set_flt(r: mpfr_ptr, value: f32, rnd: rnd_t) -> i32 {
+        set_d(r, value as f64, rnd)
+    }
+
+    /// f64 values have 53 bits.
+    fn from_f64<const LIMBS: usize>(value: f64) -> SoftMpfr<LIMBS> {
+        let mut soft = SoftMpfr::new(f64::MANTISSA_DIGITS as usize);
+        soft.set_f64(value, RoundingMode::Nearest);
+        soft
+    }
+
+    unsafe fn add_d_in<const LIMBS: usize>(r: mpfr_ptr, x: mpfr_srcptr, value: f64, rnd: rnd_t) -> i32 {
+        let (x, y) = (load::<LIMBS>(x), from_f64::<LIMBS>(value));
+        let mut result = SoftMpfr::new((*r).prec as usize);
+        let ternary = result.add(&x, &y, rounding(rnd));
+        store(r, &result);
+        ternary as i32
+    }
+
+    pub unsafe fn add_d(r: mpfr_ptr, x: mpfr_srcptr, value: f64, rnd: rnd_t) -> i32 {
+        with_limbs!(max_precision(&[r as mpfr_srcptr, x]).max(f64::MANTISSA_DIGITS as prec_t), add_d_in(r, x, value, rnd))
+    }
+
+    /// As `add_d()` of `-value` (negating is exact).
+    #[cfg_attr(not(feature = "twofloat"), allow(dead_code))]
+    pub unsafe fn sub_d(r: mpfr_ptr, x: mpfr_srcptr, value: f64, rnd: rnd_t) -> i32 {
+        add_d(r, x, -value, rnd)
+    }
+
+    unsafe fn div_2ui_in<const LIMBS: usize>(r: mpfr_ptr, x: mpfr_srcptr, n: u64, rnd: rnd_t) -> i32 {
+        let mut x = load::<LIMBS>(x);
+        if x.kind == Kind::Regular {
+            x.exponent = x.exponent.saturating_sub(n.min(i64::MAX as u64) as i64);
+        }
+        let mut result = SoftMpfr::<LIMBS>::new((*r).prec as usize);
+        // Rounding limits it to the exponent range, too.
+        let ternary = match x.kind {
+            Kind::Regular => result.round_from(x.negative, x.exponent, &x.limbs, false, rounding(rnd)),
+            _ => result.set(&x, rounding(rnd))
+        };
+        store(r, &result);
+        ternary as i32
+    }
+
+    pub unsafe fn div_2ui(r: mpfr_ptr, x: mpfr_srcptr, n: u64, rnd: rnd_t) -> i32 {
+        with_limbs!(max_precision(&[r as mpfr_srcptr, x]), div_2ui_in(r, x, n, rnd))
+    }
+
+    unsafe fn get_d_in<const LIMBS: usize>(x: mpfr_srcptr, rnd: rnd_t) -> f64 {
+        load::<LIMBS>(x).to_f64(rounding(rnd))
+    }
+
+    pub unsafe fn get_d(x: mpfr_srcptr, rnd: rnd_t) -> f64 {
+        with_limbs!((*x).prec, get_d_in(x, rnd))
+    }
+
+    unsafe fn get_flt_in<const LIMBS: usize>(x: mpfr_srcptr, rnd: rnd_t) -> f32 {
+        load::<LIMBS>(x).to_f32(rounding(rnd))
+    }
+
+    pub unsafe fn get_flt(x: mpfr_srcptr, rnd: rnd_t) -> f32 {
+        with_limbs!((*x).prec, get_flt_in(x, rnd))
+    }
+
+    /// As MPFR's, 0 if either is NaN.
+    unsafe fn cmp_in<const LIMBS: usize>(x: mpfr_srcptr, y: mpfr_srcptr) -> i32 {
+        load::<LIMBS>(x).partial_cmp(&load::<LIMBS>(y)).map_or(0, |ordering| ordering as i32)
+    }
+
+    pub unsafe fn cmp(x: mpfr_srcptr, y: mpfr_srcptr) -> i32 {
+        with_limbs!(max_precision(&[x, y]), cmp_in(x, y))
+    }
+
+    unsafe fn cmp_d_in<const LIMBS: usize>(x: mpfr_srcptr, value: f64) -> i32 {
+        load::<LIMBS>(x).partial_cmp(&from_f64::<LIMBS>(value)).map_or(0, |ordering| ordering as i32)
+    }
+
+    pub unsafe fn cmp_d(x: mpfr_srcptr, value: f64) -> i32 {
+        with_limbs!(max_precision(&[x]).max(f64::MANTISSA_DIGITS as prec_t), cmp_d_in(x, value))
+    }
+
+    pub unsafe fn nan_p(x: mpfr_srcptr) -> i32 {
+        ((*x).exp == NAN_MPFR_EXP) as i32
+    }
+
+    pub unsafe fn inf_p(x: mpfr_srcptr) -> i32 {
+        ((*x).exp == INF_MPFR_EXP) as i32
+    }
+
+    pub unsafe fn zero_p(x: mpfr_srcptr) -> i32 {
+        ((*x).exp == ZERO_MPFR_EXP) as i32
+    }
+
+    pub unsafe fn unordered_p(x: mpfr_srcptr, y: mpfr_srcptr) -> i32 {
+        (nan_p(x) != 0 || nan_p(y) != 0) as i32
+    }
+
+    pub unsafe fn signbit(x: mpfr_srcptr) -> i32 {
+        ((*x).sign < 0) as i32
+    }
+
+    /// Of regular values only, as MPFR's.
+    pub unsafe fn get_exp(x: mpfr_srcptr) -> exp_t {
+        (*x).exp
+    }
+
+    // Decimal text, for `Display` and `FromStr` of the MPFR choices. Both scale by a power of 10
+    // and round once, by Ziv's strategy: they take bounds of the scaled value at more and more
+    // precision, until both bounds round the same way. Values that are on the rounding boundary
+    // (exactly) have exact bounds by then. Only if the bounds still round apart at
+    // `MAX_PRECISION` (for a value that's closer to the boundary than that), the result is from
+    // the lower bound, so it's faithful.
+
+    /// SoftMpfr for the intermediate results of decimal text.
+    type Widest = SoftMpfr<SOFTMPFR_MAX_LIMBS>;
+    const MAX_PRECISION: usize = 64 * SOFTMPFR_MAX_LIMBS;
+    /// The most significant digits of decimal text (`MAX_PARSE_LENGTH` and `MAX_FORMAT_DIGITS`
+    /// are less): their integers fit into `MAX_PRECISION` bits, with some left for the rounding.
+    const MAX_TEXT_DIGITS: usize = 1200;
+
+    /// `words * factor + addend`, which must fit.
+    fn mul_small(words: &mut [u64], factor: u64, addend: u64) {
+        let mut carry = addend as u128;
+        for word in words.iter_mut() {
+            let product = *word as u128 * factor as u128 + carry;
+            *word = product as u64;
+            carry = product >> 64;
+        }
+    }
+
+    /// Returns the remainder.
+    fn div_small(words: &mut [u64], divisor: u64) -> u64 {
+        let mut remainder = 0u128;
+        for word in words.iter_mut().rev() {
+            let dividend = remainder << 64 | *word as u128;
+            *word = (dividend / divisor as u128) as u64;
+            remainder = dividend % divisor as u128;
+        }
+        remainder as u64
+    }
+
+    fn power_of_ten_words(n: usize) -> [u64; SOFTMPFR_MAX_LIMBS] {
+        let mut words = [0; SOFTMPFR_MAX_LIMBS];
+        words[0] = 1;
+        for _ in 0..n {
+            mul_small(&mut words, 10, 0);
+        }
+        words
+    }
+
+    /// The integer of `words` (non-zero), exactly.
+    fn integer_value(mut words: [u64; SOFTMPFR_MAX_LIMBS]) -> Widest {
+        let zeros = leading_zeros(&words);
+        shift_left(&mut words, zeros);
+        let mut value = Widest::new(MAX_PRECISION);
+        value.round_from(false, (MAX_PRECISION - zeros) as i64, &words, false, RoundingMode::Nearest);
+        value
+    }
+
+    /// 5^`n`, rounded to `precision` bits in the direction of `rounding`, and whether that's
+    /// exact.
+    fn power_of_five(n: u64, precision: usize, rounding: RoundingMode) -> (Widest, bool) {
+        let (mut five, mut power) = (Widest::new(precision), Widest::new(precision));
+        five.set_f64(5.0, rounding);
+        power.set_f64(1.0, rounding);
+        let mut exact = true;
+        for bit in (0..64 - n.leading_zeros()).rev() {
+            let square = power;
+            exact &= power.mul(&square, &square, rounding) == Ordering::Equal;
+            if (n >> bit) & 1 == 1 {
+                let previous = power;
+                exact &= power.mul(&previous, &five, rounding) == Ordering::Equal;
+            }
+        }
+        (power, exact)
+    }
+
+    /// A lower and an upper bound of `x` * 10^`k` (for positive, regular `x`) at `precision`
+    /// bits, and whether they're exact. That's `x` * 5^`k` with `k` added to the exponent, so
+    /// that only 5^`k` has to be in the exponent range.
+    fn scaled_bounds(x: &Widest, k: i64, precision: usize) -> (Widest, Widest, bool) {
+        let (low_power, low_exact) = power_of_five(k.unsigned_abs(), precision, RoundingMode::Down);
+        let (high_power, high_exact) = power_of_five(k.unsigned_abs(), precision, RoundingMode::Up);
+        let (mut low, mut high) = (Widest::new(precision), Widest::new(precision));
+        let ternaries = if k >= 0 {
+            (low.mul(x, &low_power, RoundingMode::Down), high.mul(x, &high_power, RoundingMode::Up))
+        } else {
+            (low.div(x, &high_power, RoundingMode::Down), high.div(x, &low_power, RoundingMode::Up))
+        };
+        low.exponent += k;
+        high.exponent += k;
+        (low, high, low_exact && high_exact && ternaries == (Ordering::Equal, Ordering::Equal))
+    }
+
+    /// `y` (at least 1) rounded to an integer in the direction of `rounding` for the sign of
+    /// `negative`.
+    fn rounded_integer(y: &Widest, negative: bool, rounding: RoundingMode) -> [u64; SOFTMPFR_MAX_LIMBS] {
+        let mut integer = [0; SOFTMPFR_MAX_LIMBS];
+        let bits = y.exponent as usize;
+        let (carry, _) = round_words(&mut integer, bits, &y.limbs, false, negative, rounding);
+        shift_right(&mut integer, MAX_PRECISION - bits - carry as usize);
+        integer
+    }
+
+    /// `|x|` (regular) rounded to `text.len()` significant decimal digits in the direction of
+    /// `rounding` (for the sign of `x`), into `text` as ASCII. Returns the exponent e of
+    /// 0.DIGITS * 10^e.
+    fn to_decimal<const LIMBS: usize>(x: &SoftMpfr<LIMBS>, text: &mut [u8], rounding: RoundingMode) -> i64 {
+        let count = text.len();
+        assert!(count >= 1 && count <= MAX_TEXT_DIGITS, "SoftMpfr writes 1 to 1200 decimal digits.");
+        let mut magnitude = Widest::new(MAX_PRECISION);
+        magnitude.set_signed(x, false, RoundingMode::Nearest);
+        let (lowest, highest) = (integer_value(power_of_ten_words(count - 1)), power_of_ten_words(count));
+        let highest_value = integer_value(highest);
+        // 10^(exponent - 1) <= |x| < 10^exponent, give or take one: the bounds tell.
+        let mut exponent = libm::floor((x.exponent - 1) as f64 * core::f64::consts::LOG10_2) as i64 + 1;
+        let mut precision = (libm::ceil(count as f64 * core::f64::consts::LOG2_10) as usize + 64).min(MAX_PRECISION);
+        loop {
+            let (low, high, exact) = scaled_bounds(&magnitude, count as i64 - exponent, precision);
+            if compare_magnitudes(&high, &lowest) == Ordering::Less {
+                exponent -= 1;
+                continue;
+            } else if compare_magnitudes(&low, &highest_value) != Ordering::Less {
+                exponent += 1;
+                continue;
+            }
+            let last = exact || precision == MAX_PRECISION;
+            let below_lowest = compare_magnitudes(&low, &lowest) == Ordering::Less;
+            if last || (!below_lowest && compare_magnitudes(&high, &highest_value) == Ordering::Less) {
+                let mut integer = rounded_integer(if below_lowest { &high } else { &low }, x.negative, rounding);
+                if last || integer == rounded_integer(&high, x.negative, rounding) {
+                    // Rounded up to 10^count.
+                    if integer == highest {
+                        text[0] = b'1';
+                        text[1..].iter_mut().for_each(|digit| *digit = b'0');
+                        return exponent + 1;
+                    }
+                    for digit in text.iter_mut().rev() {
+                        *digit = b'0' + div_small(&mut integer, 10) as u8;
+                    }
+                    return exponent;
+                }
+            }
+            precision = (2 * precision).min(MAX_PRECISION);
+        }
+    }
+
+    /// Sets `result` to `digits` (an integer, as decimal digits rather than characters) times
+    /// 10^`exponent`, with the sign of `negative`, rounded to its precision. Returns the ternary
+    /// value.
+    fn set_decimal<const LIMBS: usize>(result: &mut SoftMpfr<LIMBS>, negative: bool, digits: &[u8], exponent: i64,
+        rounding: RoundingMode) -> Ordering {
+        assert!(digits.len() <= MAX_TEXT_DIGITS, "SoftMpfr reads at most 1200 decimal digits.");
+        let mut words = [0; SOFTMPFR_MAX_LIMBS];
+        for &digit in digits.iter() {
+            mul_small(&mut words, 10, digit as u64);
+        }
+        let bits = MAX_PRECISION - leading_zeros(&words);
+        if bits == 0 {
+            return result.set_special(Kind::Zero, negative);
+        }
+        // 2^(bits - 1) * 10^exponent <= |value| < 2^bits * 10^exponent. Far out of the exponent
+        // range, the digits don't matter.
+        let log2_power = exponent as f64 * core::f64::consts::LOG2_10;
+        if log2_power + (bits - 1) as f64 > (EMAX + 1) as f64 {
+            return result.round_from(negative, EMAX + 1, &[1 << 63], false, rounding);
+        } else if (EMIN - 2) as f64 > log2_power + bits as f64 {
+            return result.round_from(negative, EMIN - 1, &[1 << 63], false, rounding);
+        }
+        let integer = integer_value(words);
+        let mut precision = (result.precision + 64).min(MAX_PRECISION);
+        loop {
+            let (low, high, exact) = scaled_bounds(&integer, exponent, precision);
+            let (mut rounded, mut other) = (*result, *result);
+            let ternary = rounded.set_signed(&low, negative, rounding);
+            let other_ternary = other.set_signed(&high, negative, rounding);
+            // The value is between the bounds. If both round to the same, and `high` rounds away
+            // from zero (or `low` toward zero), so does the value.
+            let away = |ternary: Ordering| ternary != Ordering::Equal && (ternary == Ordering::Greater) != negative;
+            let ternary = if exact || precision == MAX_PRECISION {
+                Some(ternary)
+            } else if rounded == other && away(other_ternary) {
+                Some(other_ternary)
+            } else if rounded == other && ternary != Ordering::Equal && !away(ternary) {
+                Some(ternary)
+            } else {
+                None
+            };
+            if let Some(ternary) = ternary {
+                *result = rounded;
+                return ternary;
+            }
+            precision = (2 * precision).min(MAX_PRECISION);
+        }
+    }
+
+    /// Decimal only (`base` 10), as `Digits::from_mpfr()` calls it for formatting: text in other
+    /// bases needs feature `mpfr`. `x` must be regular. Writes the sign (if negative), `n` digits
+    /// (at most 1200) and a NUL, as MPFR's.
+    #[cfg(not(feature = "f32_only"))]
+    pub unsafe fn get_str(s: *mut u8, exponent: *mut exp_t, base: i32, n: usize, x: mpfr_srcptr, rnd: rnd_t) -> *mut u8 {
+        debug_assert!(base == 10, "SoftMpfr writes decimal text only.");
+        let sign = signbit(x) as usize;
+        if sign != 0 {
+            *s = b'-';
+        }
+        *exponent = with_limbs!((*x).prec, get_str_in(slice::from_raw_parts_mut(s.add(sign), n), x, rnd));
+        *s.add(sign + n) = 0;
+        s
+    }
+
+    unsafe fn get_str_in<const LIMBS: usize>(text: &mut [u8], x: mpfr_srcptr, rnd: rnd_t) -> exp_t {
+        to_decimal(&load::<LIMBS>(x), text, rounding(rnd))
+    }
+
+    /// Decimal only (`base` 10), as `parse::parse_mpfr()` calls it for `FromStr`: text in other
+    /// bases needs feature `mpfr`. `s` must be valid already (see `parse::validate()`), and
+    /// NUL-terminated. `end` is ignored.
+    #[cfg(not(feature = "f32_only"))]
+    pub unsafe fn strtofr(r: mpfr_ptr, s: *const u8, _end: *mut *mut u8, base: i32, rnd: rnd_t) -> i32 {
+        debug_assert!(base == 10, "SoftMpfr reads decimal text only.");
+        let mut length = 0;
+        while *s.add(length) != 0 {
+            length += 1;
+        }
+        let text = core::str::from_utf8(slice::from_raw_parts(s, length)).unwrap_or("");
+        match parse_decimal_literal(text) {
+            Ok(Some(literal)) => with_limbs!((*r).prec, strtofr_in(r, &literal, rnd)),
+            // Infinities and NaN.
+            _ => set_d(r, text.parse().unwrap_or(f64::NAN), rnd)
+        }
+    }
+
+    #[cfg(not(feature = "f32_only"))]
+    unsafe fn strtofr_in<const LIMBS: usize>(r: mpfr_ptr, literal: &RadixLiteral, rnd: rnd_t) -> i32 {
+        let mut result = SoftMpfr::<LIMBS>::new((*r).prec as usize);
+        let ternary = set_decimal(&mut result, literal.negative, literal.digits(), literal.exponent, rounding(rnd));
+        store(r, &result);
+        ternary as i32
+    }
+}
@@ -0,0 +1,374 @@
+use {core::cell::Cell, core::f64::consts::PI};
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use crate::mpfr;
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(feature = "twofloat")]
+use crate::parts::twofloat_or_f64;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::decimal::Dec;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::quadfloat::Quad;
+#[cfg(feature = "twofloat")]
+use crate::trigonometry::{PI_TWOFLOAT, TrigPi, trig_pi_twofloat};
+
+/// ln(2 * π) / 2 as double-double: the nearest f64, and the rest.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+const HALF_LN_2_PI: (f64, f64) = (0.9189385332046728, -3.8782941580672414e-17);
+/// ln(π) as double-double: the nearest f64, and the rest.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+const LN_PI: (f64, f64) = (1.1447298858494002, 1.0265951162707826e-17);
+/// 2 / sqrt(π) as double-double: the nearest f64, and the rest.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+const FRAC_2_SQRT_PI: (f64, f64) = (1.1283791670955126, 1.533545961316588e-17);
+
+/// Special functions. They are correctly rounded for MPFR. For f32 and f64 they use `libm` or
+/// f64-based series, with lower accuracy. For TwoFloat, `li2()` gets only f64 accuracy, but the
+/// Gamma family and the error functions use double-double (see there). QuadFloat runs them all
+/// in MPFR, at 212 bits.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Dilogarithm. For `self > 1` this is the real part of it (same as MPFR).
+    pub fn li2(&self) -> Self {
+        self.unary(|x| li2_f64(x as f64) as f32, li2_f64, twofloat_op!(|x| TwoFloat::from(li2_f64(x.hi()))),
+            mpfr_op!(|r, x| unsafe { mpfr::li2(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Digamma function (logarithmic derivative of Gamma). At its poles (non-positive integers)
+    /// this is NaN, except for zero: +0 gives -Inf, and -0 gives +Inf (same as MPFR).
+    ///
+    /// TwoFloat uses the recurrence and the asymptotic series in double-double, with absolute
+    /// error up to about 1e-30 (so near its positive root, 1.4616..., relative error is larger).
+    pub fn digamma(&self) -> Self {
+        self.unary(|x| digamma_f64(x as f64) as f32, digamma_f64,
+            twofloat_op!(|x| twofloat_or_f64(x, digamma_f64(x.hi()), digamma_twofloat)),
+            mpfr_op!(|r, x| unsafe { mpfr::digamma(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Gamma function. At its poles (non-positive integers) this is NaN, except for zero: +-0
+    /// gives +-Inf (as IEEE 754 and MPFR).
+    ///
+    /// TwoFloat exponentiates `ln_gamma()` in double-double. Its absolute error becomes relative
+    /// error, so that's up to about 1e-30 * (1 + |ln(gamma(self))|).
+    pub fn gamma(&self) -> Self {
+        self.unary(|x| libm::tgamma(x as f64) as f32, libm::tgamma,
+            twofloat_op!(|x| twofloat_or_f64(x, libm::tgamma(x.hi()), |x| {
+                let (ln, sign) = ln_gamma_twofloat(x);
+                ln.exp() * sign as f64
+            })),
+            mpfr_op!(|r, x| unsafe { mpfr::gamma(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Natural logarithm of the absolute value of Gamma, and the sign of Gamma (1 or -1, as
+    /// `libm::lgamma_r()` and `mpfr_lgamma()`). At the poles of Gamma this is +Inf.
+    ///
+    /// TwoFloat uses Stirling's series in double-double (after shifting `self` up with the
+    /// recurrence, and after reflecting negative `self`), with absolute error up to about 1e-30.
+    /// So near the roots (1 and 2) relative error is larger, but 1 and 2 themselves give exact 0.
+    pub fn ln_gamma(&self) -> (Self, i32) {
+        let sign = Cell::new(1);
+        let ln = self.unary(
+            |x| {
+                let (ln, s) = libm::lgamma_r(x as f64);
+                sign.set(s);
+                ln as f32
+            },
+            |x| {
+                let (ln, s) = libm::lgamma_r(x);
+                sign.set(s);
+                ln
+            },
+            twofloat_op!(|x| {
+                let (ln, s) = ln_gamma_twofloat(x);
+                sign.set(s);
+                ln
+            }),
+            mpfr_op!(|r, x| unsafe {
+                let mut s = 1;
+                let ternary = mpfr::lgamma(r, &mut s, x, mpfr::rnd_t::RNDN);
+                sign.set(s);
+                ternary
+            }));
+        (ln, sign.get())
+    }
+
+    /// Error function.
+    ///
+    /// TwoFloat uses a series (with positive terms only) for |self| < 2, and 1 - `erfc()`
+    /// otherwise, with relative error below 1e-28.
+    pub fn erf(&self) -> Self {
+        self.unary(|x| libm::erf(x as f64) as f32, libm::erf, twofloat_op!(|x| erf_twofloat(x)),
+            mpfr_op!(|r, x| unsafe { mpfr::erf(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Complementary error function: 1 - erf(self), accurate for large `self`, too.
+    ///
+    /// TwoFloat uses 1 - `erf()` for |self| < 2, and a continued fraction otherwise, with relative
+    /// error below 1e-28 (but up to about 1e-30 * (1 + self^2) where it exponentiates -self^2).
+    /// Results below 1e-290 (for `self` above 26 or so) come from f64, since double-double has
+    /// no more precision than f64 there.
+    pub fn erfc(&self) -> Self {
+        self.unary(|x| libm::erfc(x as f64) as f32, libm::erfc, twofloat_op!(|x| erfc_twofloat(x)),
+            mpfr_op!(|r, x| unsafe { mpfr::erfc(r, x, mpfr::rnd_t::RNDN) }))
+    }
+}
+
+/// Special functions that only MPFR (and QuadFloat and decimals, in MPFR) supports at full
+/// precision: for f32, f64 and TwoFloat they return NaN (for any arguments), rather than a result
+/// of lower accuracy. See also `li2()` (above), which has an f64-based fallback.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Riemann zeta function. +Inf for 1.
+    pub fn zeta(&self) -> Self {
+        self.unary(|_| f32::NAN, |_| f64::NAN, twofloat_op!(|_| TwoFloat::from(f64::NAN)),
+            mpfr_op!(|r, x| unsafe { mpfr::zeta(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Riemann zeta function of an integer `n`. +Inf for 1, and -0.5 for 0.
+    #[cfg_attr(any(feature = "f32_only", not(feature = "mpfr")), allow(unused_variables, unused_mut))]
+    pub fn zeta_ui(n: u32) -> Self {
+        let mut result = Self::NAN;
+        #[cfg(not(feature = "f32_only"))]
+        #[cfg(feature = "mpfr")]
+        match C {
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                result.copied();
+                unsafe { mpfr::zeta_ui(result.mpfr_mut(), n.into(), mpfr::rnd_t::RNDN) };
+            },
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat =>
+                result.set_quad(Quad::via_mpfr([], |r, _| unsafe { mpfr::zeta_ui(r, n.into(), mpfr::rnd_t::RNDN) })),
+            UniFloatChoice::Decimal { digits } =>
+                result.set_decimal(Dec::via_mpfr(digits, [], |r, _| unsafe { mpfr::zeta_ui(r, n.into(), mpfr::rnd_t::RNDN) })),
+            _ => {}
+        }
+        result.released()
+    }
+
+    /// Airy function Ai. For large negative `self` MPFR may take long (its accuracy is kept).
+    pub fn ai(&self) -> Self {
+        self.unary(|_| f32::NAN, |_| f64::NAN, twofloat_op!(|_| TwoFloat::from(f64::NAN)),
+            mpfr_op!(|r, x| unsafe { mpfr::ai(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Exponential integral Ei. For negative `self` this is -E1(-self) (same as MPFR). -Inf for
+    /// zero.
+    pub fn eint(&self) -> Self {
+        self.unary(|_| f32::NAN, |_| f64::NAN, twofloat_op!(|_| TwoFloat::from(f64::NAN)),
+            mpfr_op!(|r, x| unsafe { mpfr::eint(r, x, mpfr::rnd_t::RNDN) }))
+    }
+}
+
+/// Stirling's series for ln(Gamma(x)): each pair is a numerator and a denominator of
+/// B_2k / (2k * (2k - 1)), for k = 1... (B_2k are Bernoulli numbers).
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+const LN_GAMMA_SERIES: [(f64, f64); 13] = [(1.0, 12.0), (-1.0, 360.0), (1.0, 1260.0), (-1.0, 1680.0), (1.0, 1188.0),
+    (-691.0, 360360.0), (1.0, 156.0), (-3617.0, 122400.0), (43867.0, 244188.0), (-174611.0, 125400.0),
+    (77683.0, 5796.0), (-236364091.0, 1506960.0), (657931.0, 300.0)];
+/// The asymptotic series for digamma: each pair is a numerator and a denominator of B_2k / 2k.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+const DIGAMMA_SERIES: [(f64, f64); 13] = [(1.0, 12.0), (-1.0, 120.0), (1.0, 252.0), (-1.0, 240.0), (1.0, 132.0),
+    (-691.0, 32760.0), (1.0, 12.0), (-3617.0, 8160.0), (43867.0, 14364.0), (-174611.0, 6600.0),
+    (77683.0, 276.0), (-236364091.0, 65520.0), (657931.0, 12.0)];
+/// Where the series above are used from. There, their last terms are below 1e-34.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+const TWOFLOAT_ASYMPTOTIC: f64 = 30.0;
+
+/// Sum of `series[k] * inverse_square^k`, with each coefficient divided in double-double.
+#[cfg(feature = "twofloat")]
+fn twofloat_series(series: &[(f64, f64)], inverse_square: TwoFloat) -> TwoFloat {
+    series.iter().rev().fold(TwoFloat::from(0.0), |sum, &(numerator, denominator)|
+        sum * inverse_square + TwoFloat::from(numerator) / denominator)
+}
+
+/// ln(|Gamma(x)|) and the sign of Gamma(x). Negative `x` uses the reflection formula
+/// Gamma(x) * Gamma(1 - x) = π / sin(π * x).
+#[cfg(feature = "twofloat")]
+fn ln_gamma_twofloat(x: TwoFloat) -> (TwoFloat, i32) {
+    let (approximation, sign) = libm::lgamma_r(x.hi());
+    if !(x.hi().is_finite() && approximation.is_finite()) {
+        return (TwoFloat::from(approximation), sign);
+    }
+    let one = TwoFloat::from(1.0);
+    if x == one || x == TwoFloat::from(2.0) {
+        return (TwoFloat::from(0.0), 1);
+    }
+    if x.hi() > 0.0 {
+        return (ln_gamma_positive_twofloat(x), 1);
+    }
+    let sine = trig_pi_twofloat(x, TrigPi::Sin);
+    let ln = TwoFloat::new_add(LN_PI.0, LN_PI.1) - sine.abs().ln() - ln_gamma_positive_twofloat(one - x);
+    (ln, if sine.hi() < 0.0 { -1 } else { 1 })
+}
+
+#[cfg(feature = "twofloat")]
+fn ln_gamma_positive_twofloat(x: TwoFloat) -> TwoFloat {
+    let (mut x, mut product) = (x, TwoFloat::from(1.0));
+    while x.hi() < TWOFLOAT_ASYMPTOTIC {
+        product = product * x;
+        x = x + 1.0;
+    }
+    let inverse = TwoFloat::from(1.0) / x;
+    (x - 0.5) * x.ln() - x + TwoFloat::new_add(HALF_LN_2_PI.0, HALF_LN_2_PI.1)
+        + inverse * twofloat_series(&LN_GAMMA_SERIES, inverse * inverse) - product.ln()
+}
+
+/// Like `digamma_f64()`, in double-double. Only for finite `x` other than poles.
+#[cfg(feature = "twofloat")]
+fn digamma_twofloat(x: TwoFloat) -> TwoFloat {
+    if x.hi() < 0.0 {
+        let pi = TwoFloat::new_add(PI_TWOFLOAT.0, PI_TWOFLOAT.1);
+        return digamma_twofloat(TwoFloat::from(1.0) - x) - pi / trig_pi_twofloat(x, TrigPi::Tan);
+    }
+    let (mut x, mut result) = (x, TwoFloat::from(0.0));
+    while x.hi() < TWOFLOAT_ASYMPTOTIC {
+        result = result - TwoFloat::from(1.0) / x;
+        x = x + 1.0;
+    }
+    let inverse = TwoFloat::from(1.0) / x;
+    let inverse_square = inverse * inverse;
+    result + x.ln() - inverse * 0.5 - inverse_square * twofloat_series(&DIGAMMA_SERIES, inverse_square)
+}
+
+const PI_SQUARED_OVER_6: f64 = PI * PI / 6.0;
+
+/// Real part of the dilogarithm. It transforms `x` to [-0.5, 0.5], where the power series
+/// converges fast.
+fn li2_f64(x: f64) -> f64 {
+    if x.is_nan() {
+        f64::NAN
+    } else if x.is_infinite() {
+        f64::NEG_INFINITY
+    } else if x > 1.0 {
+        let ln_x = libm::log(x);
+        2.0 * PI_SQUARED_OVER_6 - ln_x * ln_x / 2.0 - li2_f64(1.0 / x)
+    } else if x == 1.0 {
+        PI_SQUARED_OVER_6
+    } else if x > 0.5 {
+        PI_SQUARED_OVER_6 - libm::log(x) * libm::log1p(-x) - li2_series(1.0 - x)
+    } else if x >= -0.5 {
+        li2_series(x)
+    } else if x >= -1.0 {
+        // Landen's identity.
+        let ln_1_minus_x = libm::log1p(-x);
+        -li2_series(-x / (1.0 - x)) - ln_1_minus_x * ln_1_minus_x / 2.0
+    } else {
+        let ln_minus_x = libm::log(-x);
+        -PI_SQUARED_OVER_6 - ln_minus_x * ln_minus_x / 2.0 - li2_f64(1.0 / x)
+    }
+}
+
+/// Sum of x^k/k^2 for k=1... Only for |x| <= 0.5.
+fn li2_series(x: f64) -> f64 {
+    let (mut sum, mut power) = (0.0, 1.0);
+    for k in 1..100 {
+        power *= x;
+        let term = power / (k * k) as f64;
+        sum += term;
+        if libm::fabs(term) <= f64::EPSILON * libm::fabs(sum) {
+            break;
+        }
+    }
+    sum
+}
+
+/// It shifts `x` up with the recurrence, and then it uses the asymptotic series. Negative `x`
+/// uses the reflection formula.
+fn digamma_f64(x: f64) -> f64 {
+    if x.is_nan() || x == f64::NEG_INFINITY {
+        f64::NAN
+    } else if x == 0.0 {
+        if x.is_sign_negative() { f64::INFINITY } else { f64::NEG_INFINITY }
+    } else if x < 0.0 {
+        if x == libm::floor(x) {
+            f64::NAN
+        } else {
+            digamma_f64(1.0 - x) - PI / libm::tan(PI * x)
+        }
+    } else {
+        let (mut x, mut result) = (x, 0.0);
+        while x < 6.0 {
+            result -= 1.0 / x;
+            x += 1.0;
+        }
+        let inverse = 1.0 / x;
+        let inverse_squared = inverse * inverse;
+        result + libm::log(x) - 0.5 * inverse
+            - inverse_squared * (1.0 / 12.0 - inverse_squared * (1.0 / 120.0
+            - inverse_squared * (1.0 / 252.0 - inverse_squared * (1.0 / 240.0
+            - inverse_squared / 132.0))))
+    }
+}
+
+/// erf(x) = 2 / sqrt(π) * e^(-x^2) * sum of 2^k * x^(2k + 1) / (1 * 3 * ... * (2k + 1)), for k = 0...
+/// It doesn't cancel, and for |x| < 2 it needs fewer than 70 terms.
+#[cfg(feature = "twofloat")]
+fn erf_series(x: TwoFloat) -> TwoFloat {
+    let square = x * x;
+    let (mut term, mut sum) = (x, x);
+    for k in 1..200 {
+        term = term * square * 2.0 / (2 * k + 1) as f64;
+        sum = sum + term;
+        if libm::fabs(term.hi()) <= 1e-34 * libm::fabs(sum.hi()) {
+            break;
+        }
+    }
+    TwoFloat::new_add(FRAC_2_SQRT_PI.0, FRAC_2_SQRT_PI.1) * (-square).exp() * sum
+}
+
+/// erfc(x) = e^(-x^2) / sqrt(π) / (x + (1/2) / (x + (2/2) / (x + (3/2) / (x + ...)))), for x >= 2.
+/// It's evaluated from the back. The number of terms is more than twice what double-double needs.
+#[cfg(feature = "twofloat")]
+fn erfc_fraction(x: TwoFloat) -> TwoFloat {
+    let terms = 20 + (1200.0 / (x.hi() * x.hi())) as usize;
+    let denominator = (1..=terms).rev().fold(x, |denominator, k| x + TwoFloat::from(k as f64 * 0.5) / denominator);
+    TwoFloat::new_add(FRAC_2_SQRT_PI.0, FRAC_2_SQRT_PI.1) * 0.5 * (-(x * x)).exp() / denominator
+}
+
+#[cfg(feature = "twofloat")]
+fn erf_twofloat(x: TwoFloat) -> TwoFloat {
+    if x.hi() == 0.0 || !x.hi().is_finite() {
+        return if x.hi() == 0.0 { x } else { TwoFloat::from(libm::erf(x.hi())) };
+    }
+    let magnitude = x.abs();
+    let result = if magnitude.hi() < 2.0 {
+        erf_series(magnitude)
+    } else if magnitude.hi() < 10.0 {
+        TwoFloat::from(1.0) - erfc_fraction(magnitude)
+    } else {
+        // erfc(10) is below 1e-44.
+        TwoFloat::from(1.0)
+    };
+    if x.hi() < 0.0 { -result } else { result }
+}
+
+#[cfg(feature = "twofloat")]
+fn erfc_twofloat(x: TwoFloat) -> TwoFloat {
+    let approximation = libm::erfc(x.hi());
+    if !x.hi().is_finite() || approximation < 1e-290 {
+        TwoFloat::from(approximation)
+    } else if x.hi() >= 2.0 {
+        erfc_fraction(x)
+    } else if x.hi() > -2.0 {
+        TwoFloat::from(1.0) - erf_series(x)
+    } else if x.hi() > -10.0 {
+        TwoFloat::from(2.0) - erfc_fraction(-x)
+    } else {
+        TwoFloat::from(2.0)
+    }
+}
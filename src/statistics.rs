@@ -0,0 +1,126 @@
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Statistics of slices, accurate to about the precision of the choice (unlike the naive loops):
+/// - `mean()`: the sum by `sum_compensated()`, divided by the count.
+/// - `variance()` and the others: the corrected two-pass algorithm (by Chan, Golub and LeVeque).
+///   The deviations from the mean, and their squares, add up with compensation (by
+///   `two_sum()` and `two_prod()`), and the sum of the deviations corrects the error of the mean.
+///
+/// `variance()` and `std_dev()` are of a sample (divided by the count - 1), `population_variance()`
+/// and `population_std_dev()` are of a whole population (divided by the count). Results are NaN
+/// for too few values (none, or one for a sample), and if any value is NaN.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub fn mean(values: &[Self]) -> Self {
+        let mut result = Self::sum_compensated(values);
+        result.copied();
+        let mut count = Self::from(values.len() as u64);
+        count.copied();
+        result /= &count;
+        result.released()
+    }
+
+    pub fn variance(values: &[Self]) -> Self {
+        Self::squared_deviations(values, (values.len() as u64).saturating_sub(1))
+    }
+
+    pub fn population_variance(values: &[Self]) -> Self {
+        Self::squared_deviations(values, values.len() as u64)
+    }
+
+    pub fn std_dev(values: &[Self]) -> Self {
+        let mut variance = Self::variance(values);
+        variance.copied();
+        variance.sqrt()
+    }
+
+    pub fn population_std_dev(values: &[Self]) -> Self {
+        let mut variance = Self::population_variance(values);
+        variance.copied();
+        variance.sqrt()
+    }
+
+    /// The least of `values`, as by `min()`: NaN if any of them is NaN, and -0 is less than +0.
+    /// NaN for no values.
+    pub fn min_of(values: &[Self]) -> Self {
+        Self::fold_pick(values, Self::min)
+    }
+
+    /// The greatest of `values`, as by `max()`. NaN for no values.
+    pub fn max_of(values: &[Self]) -> Self {
+        Self::fold_pick(values, Self::max)
+    }
+
+    fn fold_pick(values: &[Self], pick: fn(&Self, &Self) -> Self) -> Self {
+        let (first, rest) = match values.split_first() {
+            Some(split) => split,
+            None => return Self::NAN
+        };
+        let mut result = *first;
+        result.copied();
+        for x in rest {
+            let picked = pick(&result, x);
+            result <<= picked;
+        }
+        result.released()
+    }
+
+    /// The sum of the squared deviations from the mean (corrected by the sum of the deviations),
+    /// divided by `divisor`.
+    fn squared_deviations(values: &[Self], divisor: u64) -> Self {
+        let mut mean = Self::mean(values);
+        mean.copied();
+        let (mut deviations, mut deviations_error) = (Self::ZERO, Self::ZERO);
+        let (mut squares, mut squares_error) = (Self::ZERO, Self::ZERO);
+        for value in [&mut deviations, &mut deviations_error, &mut squares, &mut squares_error].iter_mut() {
+            value.copied();
+        }
+        for x in values {
+            let mut deviation = *x;
+            deviation.copied();
+            deviation -= &mean;
+            add_compensated(&mut deviations, &mut deviations_error, &deviation);
+            let (mut square, mut square_error) = deviation.two_prod(&deviation);
+            square.copied();
+            square_error.copied();
+            squares_error += &square_error;
+            add_compensated(&mut squares, &mut squares_error, &square);
+        }
+        deviations += &deviations_error;
+        squares += &squares_error;
+        // The sum of the deviations is 0 for the exact mean. Otherwise it's n times the error of
+        // the mean, which makes the squares (deviations^2 / n) too large.
+        let mut correction = deviations;
+        correction.copied();
+        correction *= &deviations;
+        let mut count = Self::from(values.len() as u64);
+        count.copied();
+        correction /= &count;
+        squares -= &correction;
+        let mut divisor = Self::from(divisor);
+        divisor.copied();
+        squares /= &divisor;
+        squares.released()
+    }
+}
+
+/// Adds `x` to `sum`, and its rounding error to `compensation` (Neumaier's summation, one term at
+/// a time).
+fn add_compensated<const C: UniFloatChoice>(sum: &mut UniFloat<C>, compensation: &mut UniFloat<C>, x: &UniFloat<C>) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut next, mut error) = sum.two_sum(x);
+    next.copied();
+    error.copied();
+    *compensation += &error;
+    *sum <<= &next;
+}
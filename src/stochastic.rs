@@ -0,0 +1,179 @@
+use core::{ops, sync::atomic::{AtomicU64, Ordering}};
+
+use crate::{RoundingMode, UniFloatChoice, UniFloat, UnsupportedRounding, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Two-sided 95% quantiles of Student's t distribution, for 1 to 30 degrees of freedom. Beyond
+/// that, `cestac()` uses the normal distribution's (1.96).
+const STUDENT_T_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228,
+    2.201, 2.179, 2.160, 2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086,
+    2.080, 2.074, 2.069, 2.064, 2.060, 2.056, 2.052, 2.048, 2.045, 2.042,
+];
+
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// State of the SplitMix64 sequence that picks rounding directions. Shared by all threads (like
+/// the emulated `Flags`), and by all choices.
+static STATE: AtomicU64 = AtomicU64::new(GOLDEN_GAMMA);
+
+/// `Up` or `Down`, each with probability 1/2.
+fn random_rounding() -> RoundingMode {
+    let mut z = STATE.fetch_add(GOLDEN_GAMMA, Ordering::Relaxed).wrapping_add(GOLDEN_GAMMA);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    if (z ^ (z >> 31)) >> 63 == 1 { RoundingMode::Up } else { RoundingMode::Down }
+}
+
+/// Stochastic rounding (the random rounding of CESTAC, by Vignes): a value whose arithmetic
+/// rounds each result up or down, at random, with probability 1/2 each (by the `*_round()`
+/// methods of `UniFloat`). Exact results stay exact. Running a computation several times then
+/// spreads its results about as far as its rounding errors reach, which `cestac()` turns into an
+/// estimate of its significant digits.
+///
+/// It has the basic arithmetic (and square root) only, as those are what `UniFloat` rounds in any
+/// direction. TwoFloat has no directed rounding, so `new()` rejects it.
+#[derive(Clone, Copy)]
+pub struct UniStochastic<const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    value: UniFloat<C>,
+}
+
+impl <const C: UniFloatChoice> UniStochastic<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Panics for TwoFloat.
+    pub fn new(value: &UniFloat<C>) -> Self {
+        assert!(!matches!(C, UniFloatChoice::TwoFloat), "UniStochastic needs directed rounding, which TwoFloat doesn't have.");
+        Self { value: *value }.released()
+    }
+
+    /// Restarts the random sequence of rounding directions (for all choices, and all threads), so
+    /// that a (single-threaded) computation repeats its results.
+    pub fn seed(seed: u64) {
+        STATE.store(seed, Ordering::Relaxed);
+    }
+
+    /// From a `*_round()` result.
+    fn rounded(result: Result<UniFloat<C>, UnsupportedRounding>) -> Self {
+        match result {
+            Ok(value) => Self { value },
+            Err(_) => unreachable!("new() rejects TwoFloat.")
+        }
+    }
+
+    /// As `UniFloat::copied()`.
+    #[inline]
+    pub fn copied(&mut self) -> &mut Self {
+        self.value.copied();
+        self
+    }
+
+    /// As `UniFloat::released()`.
+    fn released(self) -> Self {
+        Self { value: self.value.released() }
+    }
+
+    pub fn value(&self) -> &UniFloat<C> {
+        &self.value
+    }
+
+    pub fn sqrt(&self) -> Self {
+        Self::rounded(self.value.sqrt_round(random_rounding()))
+    }
+}
+
+/// Exact.
+impl <const C: UniFloatChoice> ops::Neg for &UniStochastic<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    type Output = UniStochastic<C>;
+    fn neg(self) -> Self::Output {
+        UniStochastic { value: -&self.value }
+    }
+}
+
+// Binary operators (+, -, *, /) on references, each rounded in a random direction.
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $round:ident) => {
+        impl <const C: UniFloatChoice> ops::$trait<&UniStochastic<C>> for &UniStochastic<C> where
+        [f32; f32_parts_length(C)]: Sized,
+        [f64; f64_parts_length(C)]: Sized,
+        [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+        [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+        [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+        {
+            type Output = UniStochastic<C>;
+            fn $method(self, other: &UniStochastic<C>) -> Self::Output {
+                UniStochastic::rounded(self.value.$round(&other.value, random_rounding()))
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, add_round);
+impl_binary_op!(Sub, sub, sub_round);
+impl_binary_op!(Mul, mul, mul_round);
+impl_binary_op!(Div, div, div_round);
+
+/// CESTAC: runs `computation` (with `UniStochastic` arithmetic) `K` times, and estimates how many
+/// decimal digits of the mean of its results are significant, from their spread: log10(sqrt(K)
+/// |mean| / (t sigma)), with sigma their standard deviation, and t the 95% quantile of Student's
+/// t distribution (so it holds with 95% confidence). Returns the mean and the digits, between 0
+/// (no significant digits, like a result that is zero up to rounding errors) and all digits of the precision of `C` (if all results are the same).
+/// NaN results have no significant digits.
+///
+/// Vignes recommends `K` = 3: more runs narrow the estimate only a little. Panics for `K` < 2.
+pub fn cestac<const C: UniFloatChoice, const K: usize>(mut computation: impl FnMut() -> UniStochastic<C>) -> (UniFloat<C>, f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    assert!(K >= 2, "cestac() needs at least 2 runs.");
+    let mut results = [UniFloat::<C>::NAN; K];
+    for result in results.iter_mut() {
+        let mut run = computation();
+        run.copied();
+        *result = run.value;
+        result.copied();
+    }
+    let (mut mean, mut sigma) = (UniFloat::mean(&results), UniFloat::std_dev(&results));
+    mean.copied();
+    sigma.copied();
+    let mut zero = UniFloat::<C>::ZERO;
+    zero.copied();
+    let all_digits = C.precision_bits() as f64 * core::f64::consts::LOG10_2;
+    let digits = if mean.is_nan() || sigma.is_nan() {
+        0.0
+    } else if sigma == zero {
+        all_digits
+    } else if mean == zero {
+        0.0
+    } else {
+        let t = STUDENT_T_95.get(K - 2).copied().unwrap_or(1.96);
+        let mut factor = UniFloat::<C>::from_f64(libm::sqrt(K as f64) / t);
+        factor.copied();
+        let mut ratio = mean.abs();
+        ratio.copied();
+        ratio *= &factor;
+        ratio /= &sigma;
+        let mut log = ratio.log10();
+        let digits = log.copied().to_f64_nearest();
+        if digits.is_nan() { 0.0 } else { digits.max(0.0).min(all_digits) }
+    };
+    (mean.released(), digits)
+}
@@ -0,0 +1,540 @@
+use core::ops;
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use {core::mem::MaybeUninit, crate::mpfr};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+use crate::Supported;
+#[cfg(feature = "twofloat")]
+use crate::arith::hypot_twofloat;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::{convert::UniMpfrTwoFloatExact, decimal::Dec, parts::limit_exponent_range};
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::quadfloat::Quad;
+
+/// Summation that doesn't lose digits to cancellation, as the naive loop of `+=` does (like for
+/// 1 + 10^100 + 1 - 10^100, which is 0 that way).
+/// - f32: Neumaier's summation in f64, rounded to f32 at the end.
+/// - f64, TwoFloat and QuadFloat: Neumaier's summation (improved Kahan summation): the rounding
+///   error of each addition goes into a separate compensation, added at the end. The result is
+///   as accurate as summation in twice the precision, and then rounded.
+/// - MPFR: the sum is exact (in a precision that grows as needed), rounded once at the end.
+/// - Decimals: likewise, as integers in units of the least exponent, so terms of far apart
+///   exponents take much memory.
+///
+/// Sums of infinities (or NaN) are as with `+`. Sums of no values are +0.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// The sum of `values`. For iterators, see `SumCompensated`.
+    pub fn sum_compensated(values: &[Self]) -> Self {
+        Self::sum_compensated_iter(values.iter())
+    }
+
+    fn sum_compensated_iter<'a>(values: impl Iterator<Item = &'a Self>) -> Self {
+        let mut result = Self::NAN;
+        match C {
+            UniFloatChoice::F32 => result.f32s[0] = neumaier(values.map(|x| {
+                x.assert_copy_fixed();
+                x.f32s[0] as f64
+            }), 0.0, libm::fabs) as f32,
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => result.f64s[0] = neumaier(values.map(|x| {
+                x.assert_copy_fixed();
+                x.f64s[0]
+            }), 0.0, libm::fabs),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = neumaier(values.map(|x| {
+                x.assert_copy_fixed();
+                x.twofloats[0]
+            }), TwoFloat::from(0.0), |x| libm::fabs(x.hi())),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => result.set_quad(neumaier(values.map(|x| {
+                x.assert_copy_fixed();
+                x.quad()
+            }), Quad::from_f64(0.0), |x| libm::fabs(x.0[0]))),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let mut sum = ExactSum::new();
+                for x in values {
+                    x.assert_copy_fixed();
+                    unsafe { sum.add(x.mpfr_src().as_ptr()) };
+                }
+                result = Self::from_exact_sum(&sum);
+                result.copied();
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => {
+                let mut sum = ExactSum::new();
+                for x in values {
+                    unsafe { sum.add_decimal(x.decimal()) };
+                }
+                result = Self::from_exact_sum(&sum);
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+
+    /// The sum of `values` by pairwise summation: halves add up recursively, and blocks of up to
+    /// `PAIRWISE_BLOCK_SIZE` values (the leaves) by the naive loop. The error grows with log2 of
+    /// the count of values (rather than with the count, as of the naive loop), at the cost of
+    /// the naive loop, and with no extra operations: much faster than `sum_compensated()` for huge
+    /// slices of MPFR values. Sums of no values are +0.
+    pub fn sum_pairwise(values: &[Self]) -> Self {
+        Self::sum_pairwise_blocks(values, PAIRWISE_BLOCK_SIZE)
+    }
+
+    /// As `sum_pairwise()`, with leaves of up to `block_size` values. 1 is the plain pairwise
+    /// summation (the most accurate), larger blocks recurse less. Panics if `block_size` is 0.
+    pub fn sum_pairwise_blocks(values: &[Self], block_size: usize) -> Self {
+        assert!(block_size > 0, "sum_pairwise_blocks() needs a positive block_size.");
+        if values.len() > block_size {
+            let (left, right) = values.split_at(values.len() / 2);
+            let mut sum = Self::sum_pairwise_blocks(left, block_size);
+            sum.copied();
+            let mut rest = Self::sum_pairwise_blocks(right, block_size);
+            rest.copied();
+            sum += &rest;
+            return sum.released();
+        }
+        // The first value starts the sum, so that sums of -0 are -0.
+        let (first, rest) = match values.split_first() {
+            Some(split) => split,
+            None => return Self::ZERO
+        };
+        let mut sum = *first;
+        sum.copied();
+        for x in rest {
+            sum += x;
+        }
+        sum.released()
+    }
+
+    /// The sum of `values`, rounded (to nearest) once, as if it were exact: 10^100 + 1 - 10^100 is
+    /// 1 for any choice, whatever the order of the values. For MPFR the values add up exactly, and
+    /// then round to the bounds. Other choices add up their parts (as f64) exactly, in MPFR: a long
+    /// accumulator. TwoFloat and QuadFloat round that as conversions from MPFR do. Decimals add up
+    /// exactly as integers (see `sum_compensated()`). Sums of infinities (or NaN) are as with `+`.
+    /// Sums of no values are +0. Needs feature `mpfr`.
+    #[cfg(feature = "mpfr")]
+    pub fn exact_sum(values: &[Self]) -> Self {
+        let mut sum = ExactSum::new();
+        for x in values {
+            match C {
+                #[cfg(not(feature = "f32_only"))]
+                #[cfg(feature = "mpfr")]
+                UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                    x.assert_copy_fixed();
+                    unsafe { sum.add(x.mpfr_src().as_ptr()) };
+                },
+                #[cfg(not(feature = "f32_only"))]
+                #[cfg(feature = "mpfr")]
+                UniFloatChoice::Decimal { .. } => unsafe { sum.add_decimal(x.decimal()) },
+                _ => {
+                    let (parts, count) = x.exact_parts();
+                    for &part in parts[..count].iter() {
+                        sum.add_product_f64(part, 1.0);
+                    }
+                }
+            }
+        }
+        Self::from_exact_sum(&sum)
+    }
+
+    /// The dot product of `x` and `y` (the sum of their products, pair by pair), rounded once as
+    /// by `exact_sum()`: each product is exact before it adds up. Panics if `x` and `y` differ in
+    /// length. Needs feature `mpfr`.
+    #[cfg(feature = "mpfr")]
+    pub fn exact_dot(x: &[Self], y: &[Self]) -> Self {
+        assert!(x.len() == y.len(), "exact_dot() needs slices of the same length.");
+        let mut sum = ExactSum::new();
+        for (x, y) in x.iter().zip(y) {
+            match C {
+                #[cfg(not(feature = "f32_only"))]
+                #[cfg(feature = "mpfr")]
+                UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                    x.assert_copy_fixed();
+                    y.assert_copy_fixed();
+                    unsafe { sum.add_product(x.mpfr_src().as_ptr(), y.mpfr_src().as_ptr()) };
+                },
+                #[cfg(not(feature = "f32_only"))]
+                #[cfg(feature = "mpfr")]
+                UniFloatChoice::Decimal { .. } => unsafe { sum.add_decimal_product(x.decimal(), y.decimal()) },
+                _ => {
+                    let ((x_parts, x_count), (y_parts, y_count)) = (x.exact_parts(), y.exact_parts());
+                    for &x_part in x_parts[..x_count].iter() {
+                        for &y_part in y_parts[..y_count].iter() {
+                            sum.add_product_f64(x_part, y_part);
+                        }
+                    }
+                }
+            }
+        }
+        Self::from_exact_sum(&sum)
+    }
+
+    /// The Euclidean norm of `values`: sqrt(x0^2 + x1^2 + ...), without overflow or underflow in
+    /// between (as `hypot()`, which it is for two values). f32, f64 and TwoFloat scale the values
+    /// by a power of two near the norm, and add up their squares in TwoFloat (as `hypot()` of
+    /// TwoFloat), so it's nearly always correctly rounded for f32 and f64 (which need feature
+    /// `twofloat`). QuadFloat, decimals and
+    /// MPFR add up the squares exactly in MPFR, whose exponent range is practically unlimited, and
+    /// round the root once (QuadFloat as conversions from MPFR do, decimals from 2098 bits). +Inf if any value is infinite (even
+    /// if another one is NaN). +0 for no values.
+    #[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(unreachable_code, unused_variables, unused_mut))]
+    pub fn norm2(values: &[Self]) -> Self {
+        #[cfg(feature = "twofloat")]
+        let twofloats = values.iter().map(|x| {
+            x.assert_copy_fixed();
+            match C {
+                UniFloatChoice::F32 => TwoFloat::from(x.f32s[0] as f64),
+                #[cfg(not(feature = "f32_only"))]
+                UniFloatChoice::F64 => TwoFloat::from(x.f64s[0]),
+                #[cfg(not(feature = "f32_only"))]
+                #[cfg(feature = "twofloat")]
+                UniFloatChoice::TwoFloat => x.twofloats[0],
+                _ => unreachable!("Only f32, f64 and TwoFloat scale in TwoFloat.")
+            }
+        });
+        let mut result = Self::NAN;
+        match C {
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::F32 => result.f32s[0] = hypot_twofloat(twofloats).hi() as f32,
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::F64 => result.f64s[0] = hypot_twofloat(twofloats).hi(),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => result.twofloats[0] = hypot_twofloat(twofloats),
+            #[cfg(not(feature = "twofloat"))]
+            UniFloatChoice::F32 | UniFloatChoice::F64 => panic!("The norm of f32 and f64 is supported with twofloat feature only."),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            _ => {
+                if values.iter().any(|x| x.is_infinite()) {
+                    return Self::INFINITY;
+                }
+                let mut sum = ExactSum::new();
+                for x in values {
+                    if C.in_mpfr() {
+                        x.assert_copy_fixed();
+                        unsafe { sum.add_product(x.mpfr_src().as_ptr(), x.mpfr_src().as_ptr()) };
+                    } else if let UniFloatChoice::Decimal { .. } = C {
+                        unsafe { sum.add_decimal_product(x.decimal(), x.decimal()) };
+                    } else {
+                        let (parts, count) = x.exact_parts();
+                        for &x_part in parts[..count].iter() {
+                            for &y_part in parts[..count].iter() {
+                                sum.add_product_f64(x_part, y_part);
+                            }
+                        }
+                    }
+                }
+                unsafe {
+                    if C.in_mpfr() {
+                        result.copied();
+                        let r = result.mpfr_mut();
+                        let ternary = mpfr::sqrt(r, &sum.sum, mpfr::rnd_t::RNDN);
+                        limit_exponent_range(C.mpfr_bounds(), r, ternary);
+                    } else if let UniFloatChoice::Decimal { digits } = C {
+                        // sqrt(sum * 10^least), with an even exponent.
+                        let mut least = sum.decimal_least.unwrap_or(0);
+                        let sum_ptr = &mut sum.sum as mpfr::mpfr_ptr;
+                        if least % 2 != 0 {
+                            sum.scale_up(sum_ptr, 1);
+                            least -= 1;
+                        }
+                        let mut root = UniMpfrTwoFloatExact::NAN;
+                        root.copied();
+                        let root = root.mpfr_mut();
+                        mpfr::sqrt(root, sum_ptr, mpfr::rnd_t::RNDN);
+                        result.set_decimal(Dec::from_mpfr_integer(digits, root, least / 2));
+                    } else {
+                        #[cfg(feature = "twofloat")] {
+                            let mut root = UniMpfrTwoFloatExact::NAN;
+                            root.copied();
+                            let root = root.mpfr_mut();
+                            mpfr::sqrt(root, &sum.sum, mpfr::rnd_t::RNDN);
+                            result.set_quad(Quad::from_mpfr(root));
+                        }
+                        #[cfg(not(feature = "twofloat"))]
+                        unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only.");
+                    }
+                }
+            },
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        }
+        result.released()
+    }
+
+    /// The parts of a value of a choice other than MPFR, as f64s that add up to it exactly, and
+    /// how many of them there are: all but the zeros after the first (which would lose the sign of
+    /// -0), and but the first of infinities and NaN.
+    #[cfg(feature = "mpfr")]
+    fn exact_parts(&self) -> ([f64; 4], usize) {
+        self.assert_copy_fixed();
+        let parts = match C {
+            UniFloatChoice::F32 => [self.f32s[0] as f64, 0.0, 0.0, 0.0],
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::F64 => [self.f64s[0], 0.0, 0.0, 0.0],
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::TwoFloat => [self.twofloats[0].hi(), self.twofloats[0].lo(), 0.0, 0.0],
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            #[cfg(feature = "twofloat")]
+            UniFloatChoice::QuadFloat => self.quad().0,
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+                unreachable!("MPFR values add up as they are."),
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { .. } => unreachable!("Decimals add up as integers."),
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            _ => {
+                let () = Supported::<C>::WITHOUT_MPFR;
+                unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+            },
+            #[cfg(all(not(feature = "f32_only"), feature = "mpfr", not(feature = "twofloat")))]
+            _ => unreachable!("UniFloatChoice::TwoFloat and QuadFloat are supported with twofloat feature only."),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        };
+        let count = if parts[0].is_finite() { parts.iter().rposition(|&part| part != 0.0).unwrap_or(0) + 1 } else { 1 };
+        (parts, count)
+    }
+
+    /// `sum` rounded to nearest once (for MPFR, then limited to the bounds).
+    #[cfg(feature = "mpfr")]
+    fn from_exact_sum(sum: &ExactSum) -> Self {
+        match C {
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Decimal { digits } => {
+                let mut result = Self::NAN;
+                result.set_decimal(unsafe { Dec::from_mpfr_integer(digits, &sum.sum, sum.decimal_least.unwrap_or(0)) });
+                result
+            },
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => {
+                let mut result = Self::NAN;
+                result.copied();
+                unsafe {
+                    let r = result.mpfr_mut();
+                    let ternary = mpfr::set(r, &sum.sum, mpfr::rnd_t::RNDN);
+                    limit_exponent_range(C.mpfr_bounds(), r, ternary);
+                }
+                result.released()
+            },
+            _ => Self::from_mpfr(&sum.sum)
+        }
+    }
+}
+
+/// The default size of blocks of `UniFloat::sum_pairwise()` (as NumPy's).
+pub const PAIRWISE_BLOCK_SIZE: usize = 128;
+
+/// `UniFloat::sum_compensated()` for iterators of references, like `values.iter().filter(...)`.
+pub trait SumCompensated<const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn sum_compensated(self) -> UniFloat<C>;
+}
+
+impl <'a, I: Iterator<Item = &'a UniFloat<C>>, const C: UniFloatChoice> SumCompensated<C> for I where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn sum_compensated(self) -> UniFloat<C> {
+        UniFloat::sum_compensated_iter(self)
+    }
+}
+
+/// Neumaier's summation of `values`, where `magnitude` is the absolute value (of the highest part)
+/// as f64. The error of each addition is exact for f32 (in f64) and f64 (by Fast2Sum, with the
+/// operands ordered by magnitude), and nearly so for TwoFloat and QuadFloat. The first value
+/// starts the sum, so that sums of -0 are -0.
+fn neumaier<T: Copy + ops::Add<Output = T> + ops::Sub<Output = T>>(mut values: impl Iterator<Item = T>, zero: T,
+    magnitude: impl Fn(T) -> f64) -> T {
+    let (mut sum, mut compensation) = (values.next().unwrap_or(zero), zero);
+    for x in values {
+        let next = sum + x;
+        compensation = compensation + if magnitude(sum) >= magnitude(x) { (sum - next) + x } else { (x - next) + sum };
+        sum = next;
+    }
+    // With infinities the compensation is NaN.
+    if magnitude(sum).is_finite() && magnitude(compensation) != 0.0 { sum + compensation } else { sum }
+}
+
+/// An exact sum in MPFR: its precision grows to cover the bits of all terms so far (and one more,
+/// for a carry). Products are exact too, in the precisions of their factors together, before they
+/// add up.
+#[cfg(feature = "mpfr")]
+struct ExactSum {
+    sum: mpfr::mpfr_t,
+    product: mpfr::mpfr_t,
+    /// Powers of 10, which scale decimals.
+    power: mpfr::mpfr_t,
+    /// All terms so far, and so their sum, are multiples of 2^least.
+    least: mpfr::exp_t,
+    /// For decimals, the sum is an integer in units of 10^decimal_least, the least exponent of the
+    /// terms so far (if any are finite).
+    #[cfg_attr(feature = "f32_only", allow(dead_code))]
+    decimal_least: Option<i64>,
+    first: bool
+}
+
+#[cfg(feature = "mpfr")]
+impl ExactSum {
+    fn new() -> Self {
+        let (mut sum, mut product, mut power) = (MaybeUninit::uninit(), MaybeUninit::uninit(), MaybeUninit::uninit());
+        unsafe {
+            mpfr::init2(sum.as_mut_ptr(), mpfr::PREC_MIN);
+            mpfr::set_zero(sum.as_mut_ptr(), 1);
+            mpfr::init2(product.as_mut_ptr(), 2 * f64::MANTISSA_DIGITS as mpfr::prec_t);
+            mpfr::init2(power.as_mut_ptr(), mpfr::PREC_MIN);
+            ExactSum { sum: sum.assume_init(), product: product.assume_init(), power: power.assume_init(),
+                least: mpfr::exp_t::MAX, decimal_least: None, first: true }
+        }
+    }
+
+    unsafe fn add(&mut self, x: mpfr::mpfr_srcptr) {
+        let sum = &mut self.sum as mpfr::mpfr_ptr;
+        if mpfr::regular_p(x) != 0 {
+            self.least = self.least.min(mpfr::get_exp(x) - mpfr::get_prec(x) as mpfr::exp_t);
+            let highest = if mpfr::regular_p(sum) != 0 { mpfr::get_exp(sum).max(mpfr::get_exp(x)) } else { mpfr::get_exp(x) };
+            let needed = (highest + 1 - self.least) as mpfr::prec_t;
+            if needed > mpfr::get_prec(sum) {
+                // Exact, as the precision grows.
+                mpfr::prec_round(sum, needed, mpfr::rnd_t::RNDN);
+            }
+        }
+        // The first term starts the sum, so that sums of -0 are -0.
+        if self.first {
+            mpfr::set(sum, x, mpfr::rnd_t::RNDN);
+            self.first = false;
+        } else {
+            mpfr::add(sum, sum, x, mpfr::rnd_t::RNDN);
+        }
+    }
+
+    unsafe fn add_product(&mut self, x: mpfr::mpfr_srcptr, y: mpfr::mpfr_srcptr) {
+        let product = &mut self.product as mpfr::mpfr_ptr;
+        mpfr::set_prec(product, mpfr::get_prec(x) + mpfr::get_prec(y));
+        mpfr::mul(product, x, y, mpfr::rnd_t::RNDN);
+        self.add(product);
+    }
+
+    /// Sums of f64s add products with 1.
+    fn add_product_f64(&mut self, x: f64, y: f64) {
+        unsafe {
+            let product = &mut self.product as mpfr::mpfr_ptr;
+            mpfr::set_prec(product, 2 * f64::MANTISSA_DIGITS as mpfr::prec_t);
+            mpfr::set_d(product, x, mpfr::rnd_t::RNDN);
+            mpfr::mul_d(product, product, y, mpfr::rnd_t::RNDN);
+            self.add(product);
+        }
+    }
+
+    #[cfg(not(feature = "f32_only"))]
+    unsafe fn add_decimal(&mut self, x: Dec) {
+        let product = &mut self.product as mpfr::mpfr_ptr;
+        x.to_mpfr_integer(product);
+        self.add_scaled(product, x.finite_exponent());
+    }
+
+    #[cfg(not(feature = "f32_only"))]
+    unsafe fn add_decimal_product(&mut self, x: Dec, y: Dec) {
+        let (product, power) = (&mut self.product as mpfr::mpfr_ptr, &mut self.power as mpfr::mpfr_ptr);
+        x.to_mpfr_integer(product);
+        y.to_mpfr_integer(power);
+        mpfr::prec_round(product, 256, mpfr::rnd_t::RNDN);
+        mpfr::mul(product, product, power, mpfr::rnd_t::RNDN);
+        let exponent = match (x.finite_exponent(), y.finite_exponent()) {
+            (Some(x_exponent), Some(y_exponent)) => Some(x_exponent + y_exponent),
+            _ => None
+        };
+        self.add_scaled(product, exponent);
+    }
+
+    /// Adds the integer `x` * 10^`exponent` (None for NaN and infinities), scaling `x` or the sum
+    /// up to the lesser exponent.
+    #[cfg(not(feature = "f32_only"))]
+    unsafe fn add_scaled(&mut self, x: mpfr::mpfr_ptr, exponent: Option<i64>) {
+        if let Some(exponent) = exponent {
+            let least = self.decimal_least.unwrap_or(exponent);
+            if exponent < least {
+                let sum = &mut self.sum as mpfr::mpfr_ptr;
+                self.scale_up(sum, least - exponent);
+            } else if exponent > least {
+                self.scale_up(x, exponent - least);
+            }
+            self.decimal_least = Some(least.min(exponent));
+        }
+        self.add(x);
+    }
+
+    /// `x` * 10^`count`, exactly (in a precision that grows as needed).
+    #[cfg(not(feature = "f32_only"))]
+    unsafe fn scale_up(&mut self, x: mpfr::mpfr_ptr, count: i64) {
+        if mpfr::regular_p(x) == 0 {
+            return;
+        }
+        let power = &mut self.power as mpfr::mpfr_ptr;
+        // 10^count has fewer than 3.33 * count bits.
+        mpfr::set_prec(power, (count * 333 / 100 + 2) as mpfr::prec_t);
+        mpfr::set_ui(power, 10, mpfr::rnd_t::RNDN);
+        mpfr::pow_si(power, power, count as mpfr::exp_t, mpfr::rnd_t::RNDN);
+        mpfr::prec_round(x, mpfr::get_prec(x) + mpfr::get_prec(power), mpfr::rnd_t::RNDN);
+        mpfr::mul(x, x, power, mpfr::rnd_t::RNDN);
+    }
+}
+
+#[cfg(feature = "mpfr")]
+impl Drop for ExactSum {
+    fn drop(&mut self) {
+        unsafe {
+            mpfr::clear(&mut self.sum);
+            mpfr::clear(&mut self.product);
+            mpfr::clear(&mut self.power);
+        }
+    }
+}
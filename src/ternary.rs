@@ -0,0 +1,346 @@
+use {core::cell::Cell, core::cmp::Ordering};
+#[cfg(any(not(feature = "f32_only"), feature = "mpfr"))]
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use crate::mpfr;
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use core::mem::MaybeUninit;
+
+use crate::{RoundingMode, UniFloatChoice, UniFloat, UnsupportedRounding, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+#[cfg(feature = "twofloat")]
+use crate::Supported;
+use crate::convert::round_to_f32;
+#[cfg(not(feature = "f32_only"))]
+#[cfg(feature = "mpfr")]
+use crate::convert::{MPFR_TWOFLOAT_EXACT, UniMpfrTwoFloatExact};
+#[cfg(feature = "mpfr")]
+use crate::MpfrBounds;
+use crate::rounded::{product_error, quotient_error, root_error, sum_f64};
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+use crate::roots::sqrt_twofloat;
+
+/// Enough to hold the product of any two TwoFloats exactly (each of them holds 2098 bits at most,
+/// see `MPFR_TWOFLOAT_EXACT`), and hence their sum, too.
+#[cfg(feature = "mpfr")]
+pub(crate) const MPFR_TWOFLOAT_PRODUCT_EXACT: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(TWOFLOAT_PRODUCT_EXACT_BITS)
+};
+#[cfg(feature = "mpfr")]
+const TWOFLOAT_PRODUCT_EXACT_BITS: usize = 2 * (1023 + 1074 + 1);
+#[cfg(feature = "mpfr")]
+pub(crate) type UniMpfrTwoFloatProductExact = UniFloat<{ MPFR_TWOFLOAT_PRODUCT_EXACT }>;
+
+/// The basic arithmetic, rounded to nearest (as the operators), together with a ternary value as
+/// MPFR's: how the returned result compares to the exact one. `Less` if it was rounded down,
+/// `Greater` if it was rounded up, and `Equal` if it's exact (and for NaN). Overflows to Inf
+/// are rounded up, and overflows to -Inf are rounded down.
+/// - MPFR: MPFR's own ternary value.
+/// - f32: computed in f64, with the rounding error, so the ternary value is exact.
+/// - f64: from the rounding error (by `fma()`, or by TwoSum for addition), so it's exact, apart
+///   from subnormal results: there the error itself may underflow, so an inexact result may be
+///   reported as `Equal`.
+/// - TwoFloat: the result is compared with the exact one in MPFR (at 4196 bits, which holds any
+///   sum or product of TwoFloats exactly). That's slow, but it's exact.
+/// - QuadFloat: MPFR's ternary value at 212 bits. The split into components is exact, apart
+///   from results outside the (normal) range of f64.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub fn add_with_ternary(&self, other: &Self) -> (Self, Ordering) {
+        let ternary = Cell::new(Ordering::Equal);
+        let result = self.binary(other,
+            |x, y| {
+                let (sum, error) = sum_f64(x as f64, y as f64, RoundingMode::Nearest);
+                rounded_f32(sum, error, &ternary)
+            },
+            |x, y| {
+                let (sum, error) = sum_f64(x, y, RoundingMode::Nearest);
+                ternary.set(ternary_f64(sum, error, x.is_finite() && y.is_finite()));
+                sum
+            },
+            mpfr_op!(twofloat_op!(|x, y| {
+                let sum = x + y;
+                ternary.set(unsafe { twofloat_ternary(sum, |exact, term| {
+                    mpfr_set_twofloat(exact, x);
+                    mpfr_set_twofloat(term, y);
+                    mpfr::add(exact, exact, term, mpfr::rnd_t::RNDN);
+                    exact
+                }) });
+                sum
+            })),
+            mpfr_op!(|r, x, y| unsafe { mpfr_ternary(mpfr::add(r, x, y, mpfr::rnd_t::RNDN), &ternary) }));
+        (result, ternary.get())
+    }
+
+    pub fn sub_with_ternary(&self, other: &Self) -> (Self, Ordering) {
+        let ternary = Cell::new(Ordering::Equal);
+        let result = self.binary(other,
+            |x, y| {
+                let (sum, error) = sum_f64(x as f64, -y as f64, RoundingMode::Nearest);
+                rounded_f32(sum, error, &ternary)
+            },
+            |x, y| {
+                let (sum, error) = sum_f64(x, -y, RoundingMode::Nearest);
+                ternary.set(ternary_f64(sum, error, x.is_finite() && y.is_finite()));
+                sum
+            },
+            mpfr_op!(twofloat_op!(|x, y| {
+                let difference = x - y;
+                ternary.set(unsafe { twofloat_ternary(difference, |exact, term| {
+                    mpfr_set_twofloat(exact, x);
+                    mpfr_set_twofloat(term, y);
+                    mpfr::sub(exact, exact, term, mpfr::rnd_t::RNDN);
+                    exact
+                }) });
+                difference
+            })),
+            mpfr_op!(|r, x, y| unsafe { mpfr_ternary(mpfr::sub(r, x, y, mpfr::rnd_t::RNDN), &ternary) }));
+        (result, ternary.get())
+    }
+
+    pub fn mul_with_ternary(&self, other: &Self) -> (Self, Ordering) {
+        let ternary = Cell::new(Ordering::Equal);
+        let result = self.binary(other,
+            |x, y| rounded_f32(x as f64 * y as f64, 0.0, &ternary),
+            |x, y| {
+                let product = x * y;
+                ternary.set(ternary_f64(product, product_error(x, y, product), x.is_finite() && y.is_finite()));
+                product
+            },
+            mpfr_op!(twofloat_op!(|x, y| {
+                let product = x * y;
+                ternary.set(unsafe { twofloat_ternary(product, |exact, factor| {
+                    mpfr_set_twofloat(exact, x);
+                    mpfr_set_twofloat(factor, y);
+                    mpfr::mul(exact, exact, factor, mpfr::rnd_t::RNDN);
+                    exact
+                }) });
+                product
+            })),
+            mpfr_op!(|r, x, y| unsafe { mpfr_ternary(mpfr::mul(r, x, y, mpfr::rnd_t::RNDN), &ternary) }));
+        (result, ternary.get())
+    }
+
+    pub fn div_with_ternary(&self, other: &Self) -> (Self, Ordering) {
+        let ternary = Cell::new(Ordering::Equal);
+        let result = self.binary(other,
+            |x, y| {
+                let (x, y) = (x as f64, y as f64);
+                let quotient = x / y;
+                rounded_f32(quotient, quotient_error(x, y, quotient), &ternary)
+            },
+            |x, y| {
+                let quotient = x / y;
+                ternary.set(ternary_f64(quotient, quotient_error(x, y, quotient), x.is_finite() && y != 0.0));
+                quotient
+            },
+            mpfr_op!(twofloat_op!(|x, y| {
+                let quotient = x / y;
+                // quotient > x / y exactly when quotient * y > x, for positive y. That compares x
+                // with quotient * y, so it's reversed.
+                if y.hi().is_finite() && y.hi() != 0.0 {
+                    let ternary_of_product = unsafe { twofloat_ternary(x, |exact, factor| {
+                        mpfr_set_twofloat(exact, quotient);
+                        mpfr_set_twofloat(factor, y);
+                        mpfr::mul(exact, exact, factor, mpfr::rnd_t::RNDN);
+                        exact
+                    }) };
+                    ternary.set(if y.hi() > 0.0 { ternary_of_product.reverse() } else { ternary_of_product });
+                }
+                quotient
+            })),
+            mpfr_op!(|r, x, y| unsafe { mpfr_ternary(mpfr::div(r, x, y, mpfr::rnd_t::RNDN), &ternary) }));
+        (result, ternary.get())
+    }
+
+    /// NaN for negative `self`, but -0 for -0, as `sqrt()`.
+    pub fn sqrt_with_ternary(&self) -> (Self, Ordering) {
+        let ternary = Cell::new(Ordering::Equal);
+        let result = self.unary(
+            |x| {
+                let x = x as f64;
+                let root = libm::sqrt(x);
+                rounded_f32(root, root_error(x, root), &ternary)
+            },
+            |x| {
+                let root = libm::sqrt(x);
+                ternary.set(ternary_f64(root, root_error(x, root), true));
+                root
+            },
+            mpfr_op!(twofloat_op!(|x| {
+                let root = sqrt_twofloat(x);
+                // root > sqrt(x) exactly when root^2 > x. That compares x with root^2, so it's
+                // reversed.
+                ternary.set(unsafe { twofloat_ternary(x, |exact, factor| {
+                    mpfr_set_twofloat(exact, root);
+                    mpfr_set_twofloat(factor, root);
+                    mpfr::mul(exact, exact, factor, mpfr::rnd_t::RNDN);
+                    exact
+                }) }.reverse());
+                root
+            })),
+            mpfr_op!(|r, x| unsafe { mpfr_ternary(mpfr::sqrt(r, x, mpfr::rnd_t::RNDN), &ternary) }));
+        (result, ternary.get())
+    }
+}
+
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// `self` rounded to the precision (and the choice) `D` in the direction of `rounding`, as
+    /// `convert_round()`, together with a ternary value as for the basic arithmetic above. Between
+    /// MPFR choices that's MPFR's `mpfr_prec_round()` (into the limbs of the result, as they can't
+    /// grow). Errors are as for `convert_round()`.
+    pub fn prec_round<const D: UniFloatChoice>(&self, rounding: RoundingMode) -> Result<(UniFloat<D>, Ordering), UnsupportedRounding> where
+    [f32; f32_parts_length(D)]: Sized,
+    [f64; f64_parts_length(D)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(D)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(D)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(D)]: Sized,
+    {
+        let mut result = self.convert_round::<D>(rounding)?;
+        result.copied();
+        let ternary = match (C, D) {
+            // f32 into f32.
+            (UniFloatChoice::F32, UniFloatChoice::F32) => Ordering::Equal,
+            // Compared in MPFR. Values other than MPFR fit into `UniMpfrTwoFloatExact` exactly.
+            #[cfg(not(feature = "f32_only"))]
+            #[cfg(feature = "mpfr")]
+            _ => unsafe {
+                let (mut result_exact, mut self_exact) = (UniMpfrTwoFloatExact::NAN, UniMpfrTwoFloatExact::NAN);
+                let result_source = match D {
+                    UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } =>
+                        result.mpfr_src(),
+                    _ => {
+                        result_exact <<= result.convert::<{ MPFR_TWOFLOAT_EXACT }>();
+                        result_exact.mpfr_src()
+                    }
+                };
+                let self_source = match C {
+                    UniFloatChoice::Mpfr { .. } | UniFloatChoice::Fixed { .. } | UniFloatChoice::Posit { .. } => self.mpfr_src(),
+                    _ => {
+                        self_exact <<= self.convert::<{ MPFR_TWOFLOAT_EXACT }>();
+                        self_exact.mpfr_src()
+                    }
+                };
+                let (result_mpfr, self_mpfr) = (result_source.as_ptr(), self_source.as_ptr());
+                if mpfr::nan_p(result_mpfr) != 0 || mpfr::nan_p(self_mpfr) != 0 {
+                    Ordering::Equal
+                } else {
+                    mpfr::cmp(result_mpfr, self_mpfr).cmp(&0)
+                }
+            },
+            // Without MPFR, the choices (F32, F64 and TwoFloat) fit into TwoFloat exactly.
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+            #[cfg(feature = "twofloat")]
+            _ => exact_twofloat(&result).partial_cmp(&exact_twofloat(self)).unwrap_or(Ordering::Equal),
+            // Without TwoFloat, too, they (F32 and F64) fit into f64 exactly.
+            #[cfg(all(not(feature = "f32_only"), not(feature = "mpfr"), not(feature = "twofloat")))]
+            _ => result.to_f64_nearest().partial_cmp(&self.to_f64_nearest()).unwrap_or(Ordering::Equal),
+            #[cfg(feature = "f32_only")]
+            _ => unreachable!("Only UniFloatChoice::F32 is supported with f32_only feature.")
+        };
+        Ok((result.released(), ternary))
+    }
+}
+
+/// `high + low` rounded to nearest f32, with its ternary value in `ternary`. `low` is as for
+/// `round_to_f32()`.
+fn rounded_f32(high: f64, low: f64, ternary: &Cell<Ordering>) -> f32 {
+    let result = round_to_f32(high, low, RoundingMode::Nearest).value();
+    ternary.set(if !high.is_finite() {
+        Ordering::Equal
+    } else if result.is_infinite() {
+        if result > 0.0 { Ordering::Greater } else { Ordering::Less }
+    } else {
+        // result - (high + low). `high - result` is exact.
+        (result as f64 - high).partial_cmp(&low).unwrap_or(Ordering::Equal)
+    });
+    result
+}
+
+/// The ternary value of `nearest`, whose exact value is `nearest + error`. If `nearest`
+/// overflowed from finite operands, it was rounded away from zero.
+fn ternary_f64(nearest: f64, error: f64, finite_operands: bool) -> Ordering {
+    if nearest.is_infinite() && finite_operands {
+        if nearest > 0.0 { Ordering::Greater } else { Ordering::Less }
+    } else {
+        0.0.partial_cmp(&error).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// The value of `x` (F32, F64 or TwoFloat) as TwoFloat, exactly.
+#[cfg(all(not(feature = "f32_only"), not(feature = "mpfr")))]
+#[cfg(feature = "twofloat")]
+fn exact_twofloat<const C: UniFloatChoice>(x: &UniFloat<C>) -> TwoFloat where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    x.assert_copy_fixed();
+    match C {
+        UniFloatChoice::F32 => TwoFloat::from(x.f32s[0] as f64),
+        UniFloatChoice::F64 => TwoFloat::from(x.f64s[0]),
+        UniFloatChoice::TwoFloat => x.twofloats[0],
+        _ => {
+            let () = Supported::<C>::WITHOUT_MPFR;
+            unreachable!("Only UniFloatChoice::F32, F64 and TwoFloat are supported without mpfr feature.")
+        }
+    }
+}
+
+/// Sets `ternary` from MPFR's ternary value, and returns that.
+#[cfg(feature = "mpfr")]
+fn mpfr_ternary(value: i32, ternary: &Cell<Ordering>) -> i32 {
+    ternary.set(value.cmp(&0));
+    value
+}
+
+/// Sets `r` to the exact value of `x` (`r` has 2098 bits at least). The lower part of infinities
+/// and NaN doesn't count.
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+pub(crate) unsafe fn mpfr_set_twofloat(r: mpfr::mpfr_ptr, x: TwoFloat) {
+    mpfr::set_d(r, x.hi(), mpfr::rnd_t::RNDN);
+    if x.hi().is_finite() {
+        mpfr::add_d(r, r, x.lo(), mpfr::rnd_t::RNDN);
+    }
+}
+
+/// How `result` compares to the exact value, which `exact(exact, temporary)` sets in `exact` (and
+/// returns). Both of them have 4196 bits (as `MPFR_TWOFLOAT_PRODUCT_EXACT`). NaN compares as
+/// `Equal`.
+#[cfg(feature = "mpfr")]
+#[cfg(feature = "twofloat")]
+unsafe fn twofloat_ternary(result: TwoFloat, exact: impl FnOnce(mpfr::mpfr_ptr, mpfr::mpfr_ptr) -> mpfr::mpfr_ptr) -> Ordering {
+    let mut values = [MaybeUninit::uninit(); 3];
+    for value in values.iter_mut() {
+        mpfr::init2(value.as_mut_ptr(), TWOFLOAT_PRODUCT_EXACT_BITS as mpfr::prec_t);
+    }
+    let (value, temporary, rounded) = (values[0].as_mut_ptr(), values[1].as_mut_ptr(), values[2].as_mut_ptr());
+    let value = exact(value, temporary);
+    mpfr_set_twofloat(rounded, result);
+    let ternary = if mpfr::nan_p(value) != 0 || mpfr::nan_p(rounded) != 0 {
+        Ordering::Equal
+    } else {
+        mpfr::cmp(rounded, value).cmp(&0)
+    };
+    for value in values.iter_mut() {
+        mpfr::clear(value.as_mut_ptr());
+    }
+    ternary
+}
@@ -0,0 +1,74 @@
+//! Helpers for testing numeric code built on `UniFloat`: `assert_uni_ulps_eq!` (exported at the
+//! crate root), and `oracle()`, which re-computes an expression at twice the precision (in MPFR),
+//! to compare results against.
+//!
+//! ```ignore
+//! let expected = oracle::<C, { oracle_choice(C) }, 1>([&x], |[x]| x.exp());
+//! assert_uni_ulps_eq!(x.exp(), expected, 1);
+//! ```
+
+#[cfg(not(feature = "f32_only"))]
+use crate::{MpfrBounds, UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Fails unless `left` and `right` (both `&UniFloat<C>`-like, "copy fixed") are at most `ulps`
+/// steps apart on the grid of `C` (see `UniFloat::ulp_distance()`). -0 and +0 are equal, and so
+/// are two NaNs (so that it can check NaN results, too). On failure it shows both values, and
+/// their distance.
+#[macro_export]
+macro_rules! assert_uni_ulps_eq {
+    ($left:expr, $right:expr, $ulps:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let ulps: u64 = $ulps;
+                if !(left.is_nan() && right.is_nan()) {
+                    match left.ulp_distance(right) {
+                        Some(distance) if distance <= ulps => {},
+                        distance => panic!("assertion failed: `(left == right within {} ulps)`\n    left: `{:?}`,\n   right: `{:?}`,\ndistance: `{:?}` ulps",
+                            ulps, left, right, distance)
+                    }
+                }
+            }
+        }
+    };
+}
+
+/// The choice for `oracle()` to compute at for `c`: MPFR, with twice the precision of `c` (for
+/// TwoFloat and QuadFloat: of all parts together; for fixed-point formats: of `int_bits` +
+/// `frac_bits`), and MPFR's default exponent range.
+#[cfg(not(feature = "f32_only"))]
+pub const fn oracle_choice(c: UniFloatChoice) -> UniFloatChoice {
+    UniFloatChoice::Mpfr {
+        bounds: MpfrBounds::for_precision_binary(2 * c.precision_bits())
+    }
+}
+
+/// The result of `f` for `inputs` (which need to be "copy fixed"), computed at choice `D`, and
+/// rounded (to nearest) back to `C`. With `D` from `oracle_choice()`, that's a reference result
+/// for `C`: correctly rounded, unless `f` loses more than half of `D`'s precision. (For
+/// fixed-point formats, it's not limited to the format, as conversions aren't.)
+///
+/// `f` gets the inputs converted to `D` (exactly), "copy fixed". Panics if `D` is less than twice
+/// as precise as `C`.
+#[cfg(not(feature = "f32_only"))]
+pub fn oracle<const C: UniFloatChoice, const D: UniFloatChoice, const N: usize>(inputs: [&UniFloat<C>; N], f: impl FnOnce(&[UniFloat<D>; N]) -> UniFloat<D>) -> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+[f32; f32_parts_length(D)]: Sized,
+[f64; f64_parts_length(D)]: Sized,
+[TwoFloatPart; twofloat_parts_length(D)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(D)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(D)]: Sized,
+{
+    assert!(D.precision_bits() >= 2 * C.precision_bits(), "oracle() needs D at least twice as precise as C (see oracle_choice()).");
+    let mut converted = [UniFloat::<D>::ZERO; N];
+    for (target, input) in converted.iter_mut().zip(inputs.iter()) {
+        *target = input.convert();
+        target.copied();
+    }
+    let mut result = f(&converted);
+    result.refixed();
+    result.convert()
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "approx")]
+
+use {approx::{AbsDiffEq, RelativeEq, UlpsEq, assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, assert_ulps_eq},
+    gmp_mpfr_sys::mpfr};
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// `value` + `steps` * EPSILON. The result needs .copied() (or <<=), as usual.
+fn above<const C: UniFloatChoice>(value: f64, steps: f64) -> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut result, mut steps, mut value) = (UniFloat::<C>::EPSILON, UniFloat::<C>::from_f64(steps), UniFloat::<C>::from_f64(value));
+    result.copied();
+    steps.copied();
+    value.copied();
+    result *= &steps;
+    result += &value;
+    result.released()
+}
+
+fn assert_approx<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut one, mut thousand) = (UniFloat::<C>::ONE, UniFloat::<C>::from_f64(1000.0));
+    one.copied();
+    thousand.copied();
+    // One, two and five steps above one, and one step above a thousand (2^9 steps above one).
+    let (mut one_up, mut two_up, mut five_up) = (above::<C>(1.0, 1.0), above::<C>(1.0, 2.0), above::<C>(1.0, 5.0));
+    one_up.copied();
+    two_up.copied();
+    five_up.copied();
+    let mut thousand_up = above::<C>(1000.0, 512.0);
+    thousand_up.copied();
+
+    // Default epsilons follow the precision of the choice.
+    assert_abs_diff_eq!(one, one_up);
+    assert!(!one.abs_diff_eq(&two_up, UniFloat::default_epsilon()));
+    assert!(one.abs_diff_eq(&two_up, UniFloat::from_f64(0.5)));
+    assert_relative_eq!(thousand, thousand_up);
+    assert!(!thousand.abs_diff_eq(&thousand_up, UniFloat::default_epsilon()));
+    assert_relative_ne!(one, two_up);
+    assert_relative_eq!(one, two_up, max_relative = UniFloat::from_f64(1e-3));
+    assert_ulps_eq!(one, two_up);
+    assert!(!one.ulps_eq(&five_up, UniFloat::default_epsilon(), UniFloat::<C>::default_max_ulps()));
+    assert_ulps_eq!(one, five_up, max_ulps = 5);
+
+    // Infinities, NaN and signs, as for f32 and f64.
+    let (mut inf, mut nan, mut zero, mut neg_zero, mut minus_one) = (UniFloat::<C>::INFINITY, UniFloat::<C>::NAN,
+        UniFloat::<C>::ZERO, UniFloat::<C>::NEG_ZERO, UniFloat::<C>::from_f64(-1.0));
+    inf.copied();
+    nan.copied();
+    zero.copied();
+    neg_zero.copied();
+    minus_one.copied();
+    assert_relative_eq!(inf, inf);
+    assert!(!inf.abs_diff_eq(&inf, UniFloat::default_epsilon()));
+    assert!(!inf.relative_eq(&thousand, UniFloat::from_f64(1e300), UniFloat::from_f64(1e300)));
+    assert_relative_ne!(nan, nan);
+    assert!(!nan.ulps_eq(&nan, UniFloat::default_epsilon(), u32::MAX));
+    assert_ulps_eq!(zero, neg_zero);
+    assert!(!one.ulps_eq(&minus_one, UniFloat::default_epsilon(), u32::MAX));
+    assert!(one.ulps_eq(&minus_one, UniFloat::from_f64(2.0), 0));
+}
+
+#[test]
+fn approx_traits() {
+    assert_approx::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_approx::<{ UniFloatChoice::F64 }>();
+        assert_approx::<{ UniFloatChoice::TwoFloat }>();
+        assert_approx::<{ MPFR_100_BITS }>();
+    }
+}
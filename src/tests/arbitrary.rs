@@ -0,0 +1,121 @@
+#![cfg(any(feature = "quickcheck", feature = "proptest"))]
+
+use {core::num::FpCategory, gmp_mpfr_sys::mpfr};
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(not(feature = "f32_only"))]
+use crate::FixedOverflow;
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+const SEEDS: u64 = 2000;
+
+/// Every kind of value comes up among the first `SEEDS` seeds.
+fn assert_spread<const C: UniFloatChoice>(subnormals: bool) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut max, mut min_positive) = (UniFloat::<C>::MAX, UniFloat::<C>::MIN_POSITIVE);
+    max.copied();
+    min_positive.copied();
+    let mut tiny = min_positive.ldexp(11);
+    tiny.copied();
+    let mut counts = [0; 8];
+    for seed in 0..SEEDS {
+        let mut x = UniFloat::<C>::arbitrary_from_seed(seed);
+        x.copied();
+        let mut magnitude = x.abs();
+        magnitude.copied();
+        let (mut mantissa, _) = magnitude.frexp();
+        mantissa.copied();
+        let kind = match x.classify() {
+            FpCategory::Nan => 0,
+            FpCategory::Infinite => 1,
+            FpCategory::Zero => 2,
+            FpCategory::Subnormal => 3,
+            _ if magnitude == max => 4,
+            _ if magnitude.lt(&tiny) => 5,
+            _ if mantissa.to_f64_nearest() == 0.5 => 6,
+            _ => 7
+        };
+        counts[kind] += 1;
+    }
+    for (kind, count) in counts.iter().enumerate() {
+        // With subnormals, the least values are subnormal rather than tiny normal ones.
+        assert!(*count > 0 || (kind == 3 && !subnormals) || (kind == 5 && subnormals), "no values of kind {} in {:?}", kind, counts);
+    }
+    // Same seed, same value.
+    let (mut x, mut y) = (UniFloat::<C>::arbitrary_from_seed(42), UniFloat::<C>::arbitrary_from_seed(42));
+    x.copied();
+    y.copied();
+    assert!(x == y || (x.is_nan() && y.is_nan()));
+}
+
+#[test]
+fn arbitrary_values_are_spread() {
+    assert_spread::<{ UniFloatChoice::F32 }>(true);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_spread::<{ UniFloatChoice::F64 }>(true);
+        assert_spread::<{ UniFloatChoice::TwoFloat }>(true);
+        assert_spread::<{ MPFR_100_BITS }>(false);
+    }
+}
+
+/// Finite values of fixed-point formats are on their grid, and within their range.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn arbitrary_fixed_point_values_are_valid() {
+    type Q8_8 = UniFloat<{ UniFloatChoice::fixed(8, 8, FixedOverflow::Saturating) }>;
+    let (mut max, mut min) = (Q8_8::MAX, Q8_8::MIN);
+    max.copied();
+    min.copied();
+    for seed in 0..SEEDS {
+        let mut x = Q8_8::arbitrary_from_seed(seed);
+        x.copied();
+        if x.is_finite() {
+            assert!(!x.gt(&max) && !x.lt(&min), "{:?} is out of range", x);
+            let value = x.to_f64_nearest() * 256.0;
+            assert_eq!(value, libm::trunc(value), "{:?} is off the grid", x);
+        }
+    }
+}
+
+/// Fuzzes addition of F64 against f64 itself.
+#[cfg(all(feature = "quickcheck", not(feature = "f32_only")))]
+#[test]
+fn quickcheck_addition_matches_f64() {
+    fn sum_matches(mut x: UniFloat<{ UniFloatChoice::F64 }>, mut y: UniFloat<{ UniFloatChoice::F64 }>) -> bool {
+        x.copied();
+        y.copied();
+        let mut sum = x;
+        sum.copied();
+        sum += &y;
+        let (expected, actual) = (x.to_f64_nearest() + y.to_f64_nearest(), sum.to_f64_nearest());
+        actual.to_bits() == expected.to_bits() || (actual.is_nan() && expected.is_nan())
+    }
+    quickcheck::quickcheck(sum_matches as fn(UniFloat<{ UniFloatChoice::F64 }>, UniFloat<{ UniFloatChoice::F64 }>) -> bool);
+}
+
+#[cfg(feature = "proptest")]
+proptest::proptest! {
+    /// Negating twice is exact, for any choice.
+    #[test]
+    fn proptest_double_negation(x: UniFloat<{ UniFloatChoice::F32 }>, y: UniFloat<{ MPFR_100_BITS }>) {
+        let (mut x, mut y) = (x, y);
+        x.copied();
+        y.copied();
+        let (mut negated_x, mut negated_y) = (-&x, -&y);
+        negated_x.copied();
+        negated_y.copied();
+        let (mut back_x, mut back_y) = (-&negated_x, -&negated_y);
+        back_x.copied();
+        back_y.copied();
+        proptest::prop_assert!(back_x == x || x.is_nan());
+        proptest::prop_assert!(back_y == y || y.is_nan());
+    }
+}
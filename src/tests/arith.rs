@@ -0,0 +1,259 @@
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+fn assert_assign_ops<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut x = UniFloat::<C>::from_f64(1.5);
+    x.copied();
+    let mut y = UniFloat::<C>::from_f64(2.25);
+    y.copied();
+
+    x += &y;
+    assert_eq!(x.to_f64_nearest(), 3.75);
+    x -= &y;
+    assert_eq!(x.to_f64_nearest(), 1.5);
+    x *= &y;
+    assert_eq!(x.to_f64_nearest(), 3.375);
+    x /= &y;
+    assert_eq!(x.to_f64_nearest(), 1.5);
+    // By value: the right side doesn't need .copied().
+    x += UniFloat::<C>::from_f64(0.5);
+    assert_eq!(x.to_f64_nearest(), 2.0);
+}
+
+fn assert_mul_add<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut x = UniFloat::<C>::from_f64(1.5);
+    x.copied();
+    let mut a = UniFloat::<C>::from_f64(2.0);
+    a.copied();
+    let mut b = UniFloat::<C>::from_f64(-0.25);
+    b.copied();
+    let mut result = x.mul_add(&a, &b);
+    result.copied();
+    assert_eq!(result.to_f64_nearest(), 2.75);
+}
+
+#[test]
+fn mul_add() {
+    assert_mul_add::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_mul_add::<{ UniFloatChoice::F64 }>();
+        assert_mul_add::<{ UniFloatChoice::TwoFloat }>();
+        assert_mul_add::<{ UniFloatChoice::QuadFloat }>();
+        assert_mul_add::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn mul_add_rounds_once() {
+    // (1 + 2^-30)^2 - 1 = 2^-29 + 2^-60. A separate multiplication would lose 2^-60.
+    let x = 1.0 + libm::ldexp(1.0, -30);
+    let mut x = crate::UniF64::from_f64(x);
+    x.copied();
+    let mut minus_one = crate::UniF64::from_f64(-1.0);
+    minus_one.copied();
+    let mut result = x.mul_add(&x, &minus_one);
+    result.copied();
+    assert_eq!(result.to_f64_nearest(), libm::ldexp(1.0, -29) + libm::ldexp(1.0, -60));
+}
+
+/// TwoFloat's product of (1 + 2^-60)^2 loses the 2^-120 of the low parts, so multiplying and then
+/// adding -1 differs from MPFR's fma(), rounded to TwoFloat.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn mul_add_twofloat_rounds_once() {
+    type UniMpfr300bit = UniFloat<{ UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(300) } }>;
+    let x = &crate::UniTwoFloat::ONE + &crate::UniTwoFloat::from_f64(libm::ldexp(1.0, -60));
+    let minus_one = crate::UniTwoFloat::from_f64(-1.0);
+    let x_mpfr = &UniMpfr300bit::ONE + &UniMpfr300bit::from_f64(libm::ldexp(1.0, -60));
+    let expected = x_mpfr.mul_add(&x_mpfr, &UniMpfr300bit::from_f64(-1.0)).convert::<{ UniFloatChoice::TwoFloat }>();
+    assert!(x.mul_add(&x, &minus_one) == expected);
+    assert!(&(&x * &x) + &minus_one != expected);
+}
+
+fn assert_sign_ops<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut x = UniFloat::<C>::from_f64(-2.5);
+    x.copied();
+    let mut positive = UniFloat::<C>::from_f64(0.0);
+    positive.copied();
+    let mut nan = UniFloat::<C>::NAN;
+    nan.copied();
+
+    let mut result = -&x;
+    result.copied();
+    assert_eq!(result.to_f64_nearest(), 2.5);
+    result <<= -x;
+    assert_eq!(result.to_f64_nearest(), 2.5);
+    result <<= x.abs();
+    assert_eq!(result.to_f64_nearest(), 2.5);
+    result <<= x.signum();
+    assert_eq!(result.to_f64_nearest(), -1.0);
+    result <<= positive.signum();
+    assert_eq!(result.to_f64_nearest(), 1.0);
+    result <<= nan.signum();
+    assert!(result.to_f64_nearest().is_nan());
+    result <<= x.copysign(&positive);
+    assert_eq!(result.to_f64_nearest(), 2.5);
+}
+
+#[test]
+fn sign_ops() {
+    assert_sign_ops::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_sign_ops::<{ UniFloatChoice::F64 }>();
+        assert_sign_ops::<{ UniFloatChoice::TwoFloat }>();
+        assert_sign_ops::<{ MPFR_100_BITS }>();
+    }
+}
+
+fn assert_remainders<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    // Each tuple: (x, y, x.rem(y), x.fmod(y)).
+    for &(x, y, rem, fmod) in [(7.0, 2.0, -1.0, 1.0), (5.0, 2.0, 1.0, 1.0), (-7.5, 2.0, 0.5, -1.5)].iter() {
+        let mut x = UniFloat::<C>::from_f64(x);
+        x.copied();
+        let mut y = UniFloat::<C>::from_f64(y);
+        y.copied();
+        let mut result = x.rem(&y);
+        result.copied();
+        assert_eq!(result.to_f64_nearest(), rem);
+        result <<= x.fmod(&y);
+        assert_eq!(result.to_f64_nearest(), fmod);
+    }
+}
+
+#[test]
+fn remainders() {
+    assert_remainders::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_remainders::<{ UniFloatChoice::F64 }>();
+        assert_remainders::<{ UniFloatChoice::TwoFloat }>();
+        assert_remainders::<{ MPFR_100_BITS }>();
+    }
+}
+
+fn assert_hypots<const C: UniFloatChoice>(big: f64, tiny: f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    // Each tuple: (x, a, b, x.hypot(a), x.hypot3(a, b)). Exact.
+    for &(x, a, b, hypot, hypot3) in [(3.0, -4.0, 12.0, 5.0, 13.0), (2.0, 3.0, 6.0, 3.605551275463989, 7.0),
+        (-0.0, 0.0, -0.0, 0.0, 0.0), (f64::INFINITY, f64::NAN, 1.0, f64::INFINITY, f64::INFINITY),
+        (f64::NAN, 1.0, f64::NEG_INFINITY, f64::NAN, f64::INFINITY)].iter() {
+        let (mut x, mut a, mut b) = (UniFloat::<C>::from_f64(x), UniFloat::<C>::from_f64(a), UniFloat::<C>::from_f64(b));
+        x.copied();
+        a.copied();
+        b.copied();
+        let mut result = x.hypot(&a);
+        result.copied();
+        let mut expected = UniFloat::<C>::from_f64(hypot);
+        expected.copied();
+        let (result_f64, expected) = (result.to_f64_nearest(), expected.to_f64_nearest());
+        assert!(result_f64 == expected || (result_f64.is_nan() && expected.is_nan()), "{:?}", (hypot, hypot3));
+        result <<= x.hypot3(&a, &b);
+        assert_eq!(result.to_f64_nearest(), hypot3);
+    }
+
+    // The squares would overflow (or underflow), but the results don't.
+    for &value in [big, tiny].iter() {
+        let mut x = UniFloat::<C>::from_f64(value);
+        x.copied();
+        let mut result = x.hypot(&x);
+        result.copied();
+        assert!(libm::fabs(result.to_f64_nearest() / value - core::f64::consts::SQRT_2) < 1e-4, "{}", value);
+        result <<= x.hypot3(&x, &x);
+        assert!(libm::fabs(result.to_f64_nearest() / value - libm::sqrt(3.0)) < 1e-4, "{}", value);
+    }
+}
+
+#[test]
+fn hypots() {
+    assert_hypots::<{ UniFloatChoice::F32 }>(1e38, 1e-40);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_hypots::<{ UniFloatChoice::F64 }>(1e300, 1e-310);
+        assert_hypots::<{ UniFloatChoice::TwoFloat }>(1e300, 1e-310);
+        assert_hypots::<{ MPFR_100_BITS }>(1e300, 1e-310);
+    }
+}
+
+#[test]
+fn assign_ops() {
+    assert_assign_ops::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_assign_ops::<{ UniFloatChoice::F64 }>();
+        assert_assign_ops::<{ UniFloatChoice::TwoFloat }>();
+        assert_assign_ops::<{ UniFloatChoice::QuadFloat }>();
+        assert_assign_ops::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn mixed_choices_promote() {
+    use crate::{UniF32, UniF64};
+    let mut x = UniF32::from_f64(1.5);
+    x.copied();
+    let mut y = UniF64::from_f64(0.25);
+    y.copied();
+    let mut result: UniF64 = &x + &y;
+    result.copied();
+    assert_eq!(result.to_f64_nearest(), 1.75);
+    result <<= &y - &x;
+    assert_eq!(result.to_f64_nearest(), -1.25);
+    result <<= &x * &y;
+    assert_eq!(result.to_f64_nearest(), 0.375);
+    result <<= &x / &y;
+    assert_eq!(result.to_f64_nearest(), 6.0);
+    result <<= &x % &y;
+    assert_eq!(result.to_f64_nearest(), 0.0);
+
+    // 1 + 2^-80 doesn't fit into f64, but it does fit into 100 bits.
+    let mut one = UniF64::from_f64(1.0);
+    one.copied();
+    let mut tiny = UniFloat::<{ MPFR_100_BITS }>::from_f64(libm::ldexp(1.0, -80));
+    tiny.copied();
+    let mut sum = &one + &tiny;
+    sum.copied();
+    let mut difference: UniFloat<{ MPFR_100_BITS }> = &sum - &one;
+    difference.copied();
+    assert_eq!(difference.to_f64_nearest(), libm::ldexp(1.0, -80));
+
+    // The same choice on both sides.
+    let mut seven = UniF64::from_f64(7.0);
+    seven.copied();
+    let mut two = UniF64::from_f64(2.0);
+    two.copied();
+    let mut result: UniF64 = &seven % &two;
+    result.copied();
+    assert_eq!(result.to_f64_nearest(), 1.0);
+}
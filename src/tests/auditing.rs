@@ -0,0 +1,107 @@
+#![cfg(not(feature = "f32_only"))]
+
+use crate::{AuditSite, MpfrBounds, UniAudited, UniFloat, UniFloatChoice, testing::oracle_choice};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+type Single = UniAudited<{ UniFloatChoice::F32 }, { oracle_choice(UniFloatChoice::F32) }>;
+type Double = UniAudited<{ UniFloatChoice::F64 }, { oracle_choice(UniFloatChoice::F64) }>;
+
+fn single(value: f64) -> Single {
+    let mut value = UniFloat::from_f64(value);
+    value.copied();
+    Single::new(&value)
+}
+
+/// The call site at `line` of this file.
+fn site(line: u32) -> AuditSite {
+    AuditSite::read().find(|site| site.location().file() == file!() && site.location().line() == line).unwrap()
+}
+
+// A single test, as `AuditSite::clear()` would interfere with others.
+#[test]
+fn audited() {
+    let (mut one, mut tiny, mut three) = (single(1.0), single(1e-8), single(3.0));
+    one.copied();
+    tiny.copied();
+    three.copied();
+    assert_eq!(tiny.relative_error(), 0.0);
+    assert_eq!(tiny.value().to_f64_nearest(), tiny.shadow().to_f64_nearest());
+
+    // Exact results have no error.
+    let line = line!() + 1;
+    let mut result = &one + &three;
+    result.copied();
+    assert_eq!(result.value().to_f64_nearest(), 4.0);
+    assert_eq!(site(line).max_relative_error(), 0.0);
+    assert_eq!(site(line).operations(), 1);
+
+    // 1/3 is within half an ulp.
+    let line = line!() + 1;
+    let mut third = &one / &three;
+    third.copied();
+    assert!(third.relative_error() > 0.0 && third.relative_error() <= 1.0 / (1u64 << 24) as f64);
+    assert_eq!(site(line).max_relative_error(), third.relative_error());
+    // Negation is exact, and not recorded.
+    result = -&third;
+    assert_eq!(result.copied().relative_error(), third.relative_error());
+
+    // Cancellation: 1 + 10^-8 is 1 in F32, so (1 + 10^-8) - 1 loses all of 10^-8, while its
+    // shadow keeps it.
+    let mut sum = &one + &tiny;
+    sum.copied();
+    assert!(sum.relative_error() <= 1.0 / (1u64 << 24) as f64);
+    let line = line!() + 1;
+    result = &sum - &one;
+    result.copied();
+    assert_eq!(result.value().to_f64_nearest(), 0.0);
+    assert_eq!(result.relative_error(), 1.0);
+    assert_eq!(site(line).max_relative_error(), 1.0);
+
+    // Sites keep the maximum, and count operations.
+    let mut roots = [0.0; 3];
+    for (root, &value) in roots.iter_mut().zip([4.0, 2.0, 9.0].iter()) {
+        let mut value = single(value);
+        value.copied();
+        let mut square_root = value.sqrt();
+        *root = square_root.copied().relative_error();
+    }
+    let line = line!() - 3;
+    assert_eq!(roots[0], 0.0);
+    assert!(roots[1] > 0.0);
+    assert_eq!(site(line).max_relative_error(), roots[1]);
+    assert_eq!(site(line).operations(), 3);
+
+    // Errors propagate: ln(1 + 10^-8) at F32 is 0, but its shadow is about 10^-8.
+    result = sum.ln();
+    result.copied();
+    assert_eq!(result.value().to_f64_nearest(), 0.0);
+    assert_eq!(result.relative_error(), 1.0);
+
+    let (mut x, mut y) = (UniFloat::from_f64(0.1), UniFloat::from_f64(0.2));
+    x.copied();
+    y.copied();
+    let (mut x, mut y) = (Double::new(&x), Double::new(&y));
+    x.copied();
+    y.copied();
+    let mut sum = &x + &y;
+    sum.copied();
+    assert_eq!(sum.value().to_f64_nearest(), 0.1 + 0.2);
+    assert!(sum.relative_error() > 0.0 && sum.relative_error() <= f64::EPSILON / 2.0);
+    let mut distance = sum.hypot(&x);
+    assert!(distance.copied().relative_error() <= f64::EPSILON);
+
+    assert_eq!(AuditSite::unrecorded(), 0);
+    AuditSite::clear();
+    assert!(AuditSite::read().next().is_none());
+}
+
+#[test]
+#[should_panic(expected="UniAudited needs D at least twice as precise as C (see oracle_choice()).")]
+fn audited_needs_precision() {
+    let mut one = UniFloat::<{ UniFloatChoice::F64 }>::ONE;
+    one.copied();
+    UniAudited::<{ UniFloatChoice::F64 }, { MPFR_100_BITS }>::new(&one);
+}
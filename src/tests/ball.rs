@@ -0,0 +1,108 @@
+#![cfg(not(feature = "f32_only"))]
+
+use crate::{BALL_RADIUS, MpfrBounds, UniBall, UniFloat, UniFloatChoice, UniFloatTracked, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+fn ball<const C: UniFloatChoice>(midpoint: f64, radius: f64) -> UniBall<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut midpoint, mut radius) = (UniFloat::<C>::from_f64(midpoint), UniFloat::<{ BALL_RADIUS }>::from_f64(radius));
+    midpoint.copied();
+    radius.copied();
+    UniBall::new(&midpoint, &radius)
+}
+
+fn assert_ball<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut one, mut two, mut three) = (ball::<C>(1.0, 0.0), ball::<C>(2.0, 0.0), ball::<C>(3.0, 0.0));
+    one.copied();
+    two.copied();
+    three.copied();
+    // Exact results keep radius 0.
+    let mut result = &one + &two;
+    result.copied();
+    assert_eq!(result.midpoint().to_f64_nearest(), 3.0);
+    assert_eq!(result.radius().to_f64_nearest(), 0.0);
+
+    // 1/3 is inexact, but (1/3) * 3 encloses 1.
+    let mut third = &one / &three;
+    third.copied();
+    assert!(third.radius().to_f64_nearest() > 0.0);
+    result = &third * &three;
+    assert!(result.copied().contains(one.midpoint()));
+    result = -&third;
+    assert_eq!(result.copied().midpoint().to_f64_nearest(), -third.midpoint().to_f64_nearest());
+    assert_eq!(result.radius().to_f64_nearest(), third.radius().to_f64_nearest());
+
+    // [0.5, 1.5]^2 = [0.25, 2.25].
+    let mut wide = ball::<C>(1.0, 0.5);
+    wide.copied();
+    result = &wide * &wide;
+    result.copied();
+    assert!(result.contains(UniFloat::<C>::from_f64(0.25).copied()));
+    assert!(result.contains(UniFloat::<C>::from_f64(2.25).copied()));
+    assert!(!result.contains(UniFloat::<C>::from_f64(3.0).copied()));
+    result = &one - &wide;
+    assert!(result.copied().contains(UniFloat::<C>::from_f64(-0.5).copied()));
+
+    // The divisor contains zero.
+    let mut around_zero = ball::<C>(0.5, 1.0);
+    around_zero.copied();
+    result = &one / &around_zero;
+    assert_eq!(result.copied().radius().to_f64_nearest(), f64::INFINITY);
+    assert_eq!(result.certified_digits(), 0.0);
+
+    // Certified digits: all of them for exact values, nearly all for 1/3, and none if the ball
+    // contains zero.
+    let all_digits = C.precision_bits() as f64 * core::f64::consts::LOG10_2;
+    assert_eq!(three.certified_digits(), all_digits);
+    assert!(third.certified_digits() > all_digits - 1.5 && third.certified_digits() < all_digits);
+    assert_eq!(around_zero.certified_digits(), 0.0);
+    let mut thousandth = ball::<C>(1.0, 0.001);
+    thousandth.copied();
+    assert!(thousandth.certified_digits() > 2.99 && thousandth.certified_digits() <= 3.0);
+}
+
+#[test]
+fn ball_arithmetic() {
+    assert_ball::<{ UniFloatChoice::F32 }>();
+    assert_ball::<{ UniFloatChoice::F64 }>();
+    assert_ball::<{ UniFloatChoice::TwoFloat }>();
+    assert_ball::<{ MPFR_100_BITS }>();
+}
+
+#[test]
+fn tracked() {
+    // A running error bound through a few steps: 0.1 (inexact) ten times over stays near 1.
+    let mut tenth = UniFloat::<{ UniFloatChoice::F64 }>::from_f64(0.1);
+    tenth.copied();
+    let (mut tenth, mut sum) = (UniFloatTracked::exact(&tenth), ball::<{ UniFloatChoice::F64 }>(0.0, 0.0));
+    tenth.copied();
+    sum.copied();
+    for _ in 0..10 {
+        sum = &sum + &tenth;
+        sum.copied();
+    }
+    let mut one = UniFloat::<{ UniFloatChoice::F64 }>::ONE;
+    assert!(sum.contains(one.copied()));
+    assert!(sum.certified_digits() > 14.0 && sum.certified_digits() < 15.95);
+}
+
+#[test]
+#[should_panic(expected = "UniBall needs a non-negative radius.")]
+fn negative_radius() {
+    ball::<{ UniFloatChoice::F64 }>(1.0, -1.0);
+}
@@ -0,0 +1,86 @@
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Each tuple: (function name, order (for `jn()` and `yn()`), value, result). The results are
+/// exact.
+const BESSELS: [(&str, i32, f64, f64); 12] = [
+    ("j0", 0, 0.0, 1.0),
+    ("j0", 0, f64::INFINITY, 0.0),
+    ("j1", 0, -0.0, -0.0),
+    ("jn", 2, 0.0, 0.0),
+    ("jn", 3, f64::NAN, f64::NAN),
+    ("y0", 0, 0.0, f64::NEG_INFINITY),
+    ("y0", 0, -1.0, f64::NAN),
+    ("y1", 0, 0.0, f64::NEG_INFINITY),
+    ("y1", 0, f64::INFINITY, 0.0),
+    ("yn", 2, 0.0, f64::NEG_INFINITY),
+    ("yn", 2, -3.0, f64::NAN),
+    ("yn", 5, f64::INFINITY, 0.0),
+];
+
+fn assert_bessels<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(name, n, value, expected) in BESSELS.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        let mut result = match name {
+            "j0" => float.j0(),
+            "j1" => float.j1(),
+            "jn" => float.jn(n),
+            "y0" => float.y0(),
+            "y1" => float.y1(),
+            _ => float.yn(n)
+        };
+        result.copied();
+        let result = result.to_f64_nearest();
+        assert!(result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan()), "{} {} {}", name, n, value);
+    }
+
+    // Orders 0 and 1 of jn() and yn() are j0() and so on.
+    let mut float = UniFloat::<C>::from_f64(2.5);
+    float.copied();
+    for &(mut general, mut special) in [(float.jn(0), float.j0()), (float.jn(1), float.j1()),
+        (float.yn(0), float.y0()), (float.yn(1), float.y1())].iter() {
+        general.copied();
+        special.copied();
+        assert_eq!(general.to_f64_nearest(), special.to_f64_nearest());
+    }
+}
+
+#[test]
+fn bessels() {
+    assert_bessels::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_bessels::<{ UniFloatChoice::F64 }>();
+        assert_bessels::<{ UniFloatChoice::TwoFloat }>();
+        assert_bessels::<{ MPFR_100_BITS }>();
+    }
+}
+
+/// Away from the zeros, f64 (via `libm`) is close to MPFR.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn bessels_f64_close_to_mpfr() {
+    for &value in [0.5, 2.5, 10.0].iter() {
+        let mut float = crate::UniF64::from_f64(value);
+        float.copied();
+        let mut mpfr_float = UniFloat::<{ MPFR_100_BITS }>::from_f64(value);
+        mpfr_float.copied();
+        for &(mut result, mut expected) in [(float.j0(), mpfr_float.j0()), (float.y1(), mpfr_float.y1()),
+            (float.jn(4), mpfr_float.jn(4)), (float.yn(3), mpfr_float.yn(3))].iter() {
+            result.copied();
+            expected.copied();
+            let (result, expected) = (result.to_f64_nearest(), expected.to_f64_nearest());
+            assert!(libm::fabs(result - expected) <= 1e-12 * libm::fabs(expected), "{}: {} {}", value, result, expected);
+        }
+    }
+}
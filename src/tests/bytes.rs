@@ -0,0 +1,78 @@
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, bytes_length, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// `hex` (parsed) has the big endian bytes `be`. All ways round trip.
+fn assert_bytes<const C: UniFloatChoice>(hex: &str, be: &[u8]) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+[u8; bytes_length(C)]: Sized,
+{
+    let mut float = UniFloat::<C>::from_hex_str(hex).unwrap();
+    float.copied();
+    assert_eq!(&float.to_be_bytes()[..], be, "{}", hex);
+    let mut le = float.to_le_bytes();
+    le.reverse();
+    assert_eq!(&le[..], be, "{}", hex);
+
+    let mut parsed = UniFloat::<C>::from_be_bytes(float.to_be_bytes()).unwrap();
+    parsed.copied();
+    assert_eq!(&parsed.to_be_bytes()[..], be, "{}", hex);
+    let mut parsed = UniFloat::<C>::from_le_bytes(float.to_le_bytes()).unwrap();
+    parsed.copied();
+    assert_eq!(&parsed.to_be_bytes()[..], be, "{}", hex);
+}
+
+#[test]
+fn bytes() {
+    assert_eq!(bytes_length(UniFloatChoice::F32), 4);
+    assert_bytes::<{ UniFloatChoice::F32 }>("0x1.8p+0", &[0x3f, 0xc0, 0, 0]);
+    assert_bytes::<{ UniFloatChoice::F32 }>("-0x0p+0", &[0x80, 0, 0, 0]);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_eq!(bytes_length(MPFR_100_BITS), 25);
+        assert_bytes::<{ UniFloatChoice::F64 }>("-inf", &[0xff, 0xf0, 0, 0, 0, 0, 0, 0]);
+        assert_bytes::<{ UniFloatChoice::TwoFloat }>("0x1.000000000000001p+0",
+            &[0x3f, 0xf0, 0, 0, 0, 0, 0, 0, 0x3c, 0x30, 0, 0, 0, 0, 0, 0]);
+        assert_bytes::<{ MPFR_100_BITS }>("0x1.8p+0", &[0, 0, 0, 0, 0, 0, 0, 0, 1,
+            0xc0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_bytes::<{ MPFR_100_BITS }>("-0x1.0000000000000000000000008p-4", &[1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfd,
+            0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x40, 0, 0, 0]);
+        // Special exponents are as on 64-bit platforms.
+        assert_bytes::<{ MPFR_100_BITS }>("-0x0p+0", &[1, 0x80, 0, 0, 0, 0, 0, 0, 1,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_bytes::<{ MPFR_100_BITS }>("inf", &[0, 0x80, 0, 0, 0, 0, 0, 0, 3,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn bytes_invalid() {
+    // The lower part of TwoFloat must be small enough.
+    let mut twofloat = [0; 16];
+    twofloat[..8].copy_from_slice(&1.0f64.to_be_bytes());
+    twofloat[8..].copy_from_slice(&1.0f64.to_be_bytes());
+    assert!(crate::UniTwoFloat::from_be_bytes(twofloat).is_none());
+
+    let mut mpfr = [0; 25];
+    mpfr[8] = 1;
+    mpfr[9] = 0xc0;
+    assert!(UniFloat::<{ MPFR_100_BITS }>::from_be_bytes(mpfr).is_some());
+    // A sign byte other than 0 or 1.
+    mpfr[0] = 2;
+    assert!(UniFloat::<{ MPFR_100_BITS }>::from_be_bytes(mpfr).is_none());
+    mpfr[0] = 0;
+    // Bits below the precision of 100 bits.
+    mpfr[24] = 1;
+    assert!(UniFloat::<{ MPFR_100_BITS }>::from_be_bytes(mpfr).is_none());
+    mpfr[24] = 0;
+    // Not normalized.
+    mpfr[9] = 0x40;
+    assert!(UniFloat::<{ MPFR_100_BITS }>::from_be_bytes(mpfr).is_none());
+}
@@ -0,0 +1,60 @@
+use core::num::FpCategory;
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+fn assert_classified<const C: UniFloatChoice>(value: f64, category: FpCategory) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut float = UniFloat::<C>::from_f64(value);
+    float.copied();
+    assert_eq!(float.classify(), category, "{}", value);
+    assert_eq!(float.is_nan(), category == FpCategory::Nan, "{}", value);
+    assert_eq!(float.is_infinite(), category == FpCategory::Infinite, "{}", value);
+    assert_eq!(float.is_finite(), !matches!(category, FpCategory::Nan | FpCategory::Infinite), "{}", value);
+    assert_eq!(float.is_normal(), category == FpCategory::Normal, "{}", value);
+    assert_eq!(float.is_subnormal(), category == FpCategory::Subnormal, "{}", value);
+    assert_eq!(float.is_sign_negative(), value.is_sign_negative(), "{}", value);
+    assert_eq!(float.is_sign_positive(), value.is_sign_positive(), "{}", value);
+}
+
+fn assert_classifications<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    assert_classified::<C>(1.5, FpCategory::Normal);
+    assert_classified::<C>(-1e10, FpCategory::Normal);
+    assert_classified::<C>(0.0, FpCategory::Zero);
+    assert_classified::<C>(-0.0, FpCategory::Zero);
+    assert_classified::<C>(f64::INFINITY, FpCategory::Infinite);
+    assert_classified::<C>(f64::NEG_INFINITY, FpCategory::Infinite);
+    assert_classified::<C>(f64::NAN, FpCategory::Nan);
+}
+
+#[test]
+fn classify() {
+    assert_classifications::<{ UniFloatChoice::F32 }>();
+    assert_classified::<{ UniFloatChoice::F32 }>(1e-40, FpCategory::Subnormal);
+    assert_classified::<{ UniFloatChoice::F32 }>(1e-300, FpCategory::Zero);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_classifications::<{ UniFloatChoice::F64 }>();
+        assert_classifications::<{ UniFloatChoice::TwoFloat }>();
+        assert_classifications::<{ MPFR_100_BITS }>();
+
+        assert_classified::<{ UniFloatChoice::F64 }>(1e-40, FpCategory::Normal);
+        assert_classified::<{ UniFloatChoice::F64 }>(-1e-310, FpCategory::Subnormal);
+        assert_classified::<{ UniFloatChoice::TwoFloat }>(-1e-310, FpCategory::Subnormal);
+        // MPFR's exponent range is much wider, and it has no subnormals.
+        assert_classified::<{ MPFR_100_BITS }>(-1e-310, FpCategory::Normal);
+    }
+}
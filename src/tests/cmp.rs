@@ -0,0 +1,119 @@
+use core::cmp::Ordering;
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+fn assert_comparisons<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut one = UniFloat::<C>::from_f64(1.0);
+    one.copied();
+    let mut two = UniFloat::<C>::from_f64(2.0);
+    two.copied();
+    let mut zero = UniFloat::<C>::from_f64(0.0);
+    zero.copied();
+    let mut negative_zero = UniFloat::<C>::from_f64(-0.0);
+    negative_zero.copied();
+    let mut nan = UniFloat::<C>::NAN;
+    nan.copied();
+
+    assert!(one < two && one <= two && two > one && two >= one);
+    assert!(one.lt(&two) && !two.lt(&one) && one.le(&one) && one.ge(&one));
+    assert_eq!(one.partial_cmp(&two), Some(Ordering::Less));
+    assert!(zero == negative_zero);
+    assert!(one != two);
+
+    assert!(nan != nan);
+    assert_eq!(nan.partial_cmp(&one), None);
+    assert!(!nan.lt(&one) && !nan.le(&one) && !nan.gt(&one) && !nan.ge(&one));
+    assert!(nan.unordered(&one) && one.unordered(&nan) && !one.unordered(&two));
+}
+
+#[test]
+fn comparisons() {
+    assert_comparisons::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_comparisons::<{ UniFloatChoice::F64 }>();
+        assert_comparisons::<{ UniFloatChoice::TwoFloat }>();
+        assert_comparisons::<{ MPFR_100_BITS }>();
+    }
+}
+
+/// Each tuple: (x, y, min(x, y), max(x, y), min_num(x, y), max_num(x, y)).
+const MIN_MAX: [(f64, f64, f64, f64, f64, f64); 7] = [
+    (1.0, 2.0, 1.0, 2.0, 1.0, 2.0),
+    (-3.0, 2.0, -3.0, 2.0, -3.0, 2.0),
+    (0.0, -0.0, -0.0, 0.0, -0.0, 0.0),
+    (-0.0, 0.0, -0.0, 0.0, -0.0, 0.0),
+    (f64::NAN, 1.0, f64::NAN, f64::NAN, 1.0, 1.0),
+    (-1.0, f64::NAN, f64::NAN, f64::NAN, -1.0, -1.0),
+    (f64::NEG_INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY),
+];
+
+fn assert_min_max<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(x, y, min, max, min_num, max_num) in MIN_MAX.iter() {
+        let (mut float_x, mut float_y) = (UniFloat::<C>::from_f64(x), UniFloat::<C>::from_f64(y));
+        float_x.copied();
+        float_y.copied();
+        let mut results = [
+            ("min", float_x.min(&float_y), min),
+            ("max", float_x.max(&float_y), max),
+            ("min_num", float_x.min_num(&float_y), min_num),
+            ("max_num", float_x.max_num(&float_y), max_num),
+        ];
+        for (name, result, expected) in results.iter_mut() {
+            result.copied();
+            let result = result.to_f64_nearest();
+            assert!(result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan()),
+                "{}({}, {}): {}", name, x, y, result);
+        }
+    }
+
+    let (mut lower, mut upper, mut value) = (UniFloat::<C>::from_f64(-1.0), UniFloat::<C>::from_f64(1.0), UniFloat::<C>::NAN);
+    lower.copied();
+    upper.copied();
+    value.copied();
+    for &(x, expected) in [(0.5, 0.5), (-7.0, -1.0), (3.0, 1.0), (f64::INFINITY, 1.0)].iter() {
+        value <<= UniFloat::<C>::from_f64(x);
+        let mut clamped = value.clamp(&lower, &upper);
+        clamped.copied();
+        assert_eq!(clamped.to_f64_nearest(), expected, "{}", x);
+    }
+    value <<= UniFloat::<C>::from_f64(f64::NAN);
+    let mut clamped = value.clamp(&lower, &upper);
+    clamped.copied();
+    assert!(clamped.to_f64_nearest().is_nan());
+}
+
+#[test]
+fn min_max() {
+    assert_min_max::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_min_max::<{ UniFloatChoice::F64 }>();
+        assert_min_max::<{ UniFloatChoice::TwoFloat }>();
+        assert_min_max::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+#[should_panic(expected="clamp() needs lower <= upper, and neither of them NaN.")]
+fn clamp_reversed_bounds() {
+    let (mut lower, mut upper) = (crate::UniF64::from_f64(1.0), crate::UniF64::from_f64(-1.0));
+    lower.copied();
+    upper.copied();
+    let _ = lower.clamp(&lower, &upper);
+}
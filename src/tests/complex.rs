@@ -0,0 +1,107 @@
+use core::f64::consts::{FRAC_PI_2, PI};
+use crate::{MpfrBounds, UniComplex, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+fn complex<const C: UniFloatChoice>(re: f64, im: f64) -> UniComplex<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut re, mut im) = (UniFloat::<C>::from_f64(re), UniFloat::<C>::from_f64(im));
+    re.copied();
+    im.copied();
+    UniComplex::new(&re, &im)
+}
+
+fn parts<const C: UniFloatChoice>(z: &UniComplex<C>) -> (f64, f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    (z.re().to_f64_nearest(), z.im().to_f64_nearest())
+}
+
+fn assert_near(actual: (f64, f64), expected: (f64, f64)) {
+    assert!((actual.0 - expected.0).abs() < 1e-5 && (actual.1 - expected.1).abs() < 1e-5,
+        "{:?} is not near {:?}", actual, expected);
+}
+
+fn assert_complex<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut a, mut b) = (complex::<C>(1.0, 2.0), complex::<C>(3.0, 4.0));
+    a.copied();
+    b.copied();
+    let mut result = &a + &b;
+    assert_eq!(parts(result.copied()), (4.0, 6.0));
+    result = &a - &b;
+    assert_eq!(parts(result.copied()), (-2.0, -2.0));
+    result = &a * &b;
+    assert_eq!(parts(result.copied()), (-5.0, 10.0));
+    let mut quotient = &result / &b;
+    assert_eq!(parts(quotient.copied()), (1.0, 2.0));
+    // |c| >= |d|, the other branch of Smith's algorithm.
+    result = &result / &*complex::<C>(4.0, 3.0).copied();
+    assert_near(parts(result.copied()), (0.4, 2.2));
+    result = -&a;
+    assert_eq!(parts(result.copied()), (-1.0, -2.0));
+    result = a.conj();
+    assert_eq!(parts(result.copied()), (1.0, -2.0));
+
+    let mut magnitude = b.abs();
+    assert_eq!(magnitude.copied().to_f64_nearest(), 5.0);
+    let mut angle = complex::<C>(0.0, 1.0).copied().arg();
+    assert_near((angle.copied().to_f64_nearest(), 0.0), (FRAC_PI_2, 0.0));
+    let (mut magnitude, mut angle) = b.to_polar();
+    magnitude.copied();
+    angle.copied();
+    result = UniComplex::from_polar(&magnitude, &angle);
+    assert_near(parts(result.copied()), (3.0, 4.0));
+    let mut zero = UniFloat::<C>::ZERO;
+    zero.copied();
+    result = UniComplex::from_polar(UniFloat::<C>::from_f64(2.0).copied(), &zero);
+    assert_eq!(parts(result.copied()), (2.0, 0.0));
+
+    // e^(iπ) = -1, and the principal ln(-1) = iπ.
+    result = complex::<C>(0.0, PI).copied().exp();
+    assert_near(parts(result.copied()), (-1.0, 0.0));
+    result = complex::<C>(1.0, 0.0).copied().exp();
+    assert_near(parts(result.copied()), (core::f64::consts::E, 0.0));
+    assert_eq!(result.im().to_f64_nearest(), 0.0);
+    result = complex::<C>(-1.0, 0.0).copied().ln();
+    assert_near(parts(result.copied()), (0.0, PI));
+    result = b.ln();
+    let mut back = result.copied().exp();
+    assert_near(parts(back.copied()), (3.0, 4.0));
+
+    result = b.sqrt();
+    assert_eq!(parts(result.copied()), (2.0, 1.0));
+    result = complex::<C>(-4.0, 0.0).copied().sqrt();
+    assert_eq!(parts(result.copied()), (0.0, 2.0));
+    result = complex::<C>(-3.0, -4.0).copied().sqrt();
+    assert_eq!(parts(result.copied()), (1.0, -2.0));
+    result = complex::<C>(f64::NAN, f64::INFINITY).copied().sqrt();
+    assert_eq!(parts(result.copied()), (f64::INFINITY, f64::INFINITY));
+}
+
+#[test]
+fn complex_arithmetic() {
+    assert_complex::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_complex::<{ UniFloatChoice::F64 }>();
+        assert_complex::<{ UniFloatChoice::TwoFloat }>();
+        assert_complex::<{ MPFR_100_BITS }>();
+    }
+}
@@ -0,0 +1,200 @@
+#[cfg(not(feature = "f32_only"))]
+use gmp_mpfr_sys::mpfr;
+use crate::{UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length,
+    mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(not(feature = "f32_only"))]
+use crate::{MpfrBounds, UniF64, UniTwoFloat};
+
+#[cfg(not(feature = "f32_only"))]
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+#[cfg(not(feature = "f32_only"))]
+const MPFR_200_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(200)
+};
+
+#[test]
+fn f32_consts_are_core_consts() {
+    use core::f32::consts;
+    for &(mut constant, expected) in [(crate::UniF32::pi(), consts::PI), (crate::UniF32::tau(), consts::TAU),
+        (crate::UniF32::e(), consts::E), (crate::UniF32::ln_10(), consts::LN_10),
+        (crate::UniF32::frac_1_sqrt_2(), consts::FRAC_1_SQRT_2)].iter() {
+        constant.copied();
+        assert_eq!(constant.f32s[0].to_bits(), expected.to_bits());
+    }
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn f64_and_twofloat_consts() {
+    use core::f64::consts;
+    for &(mut constant, mut twofloat, expected) in [(UniF64::pi(), UniTwoFloat::pi(), consts::PI),
+        (UniF64::frac_pi_2(), UniTwoFloat::frac_pi_2(), consts::FRAC_PI_2), (UniF64::ln_2(), UniTwoFloat::ln_2(), consts::LN_2),
+        (UniF64::log2_e(), UniTwoFloat::log2_e(), consts::LOG2_E), (UniF64::sqrt_2(), UniTwoFloat::sqrt_2(), consts::SQRT_2)].iter() {
+        constant.copied();
+        twofloat.copied();
+        assert_eq!(constant.f64s[0].to_bits(), expected.to_bits());
+        assert_eq!(twofloat.twofloats[0].hi().to_bits(), expected.to_bits());
+    }
+}
+
+/// Each MPFR constant at 100 bits is the one at 200 bits rounded, and the TwoFloat constants are
+/// within double-double rounding of it.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn mpfr_consts_are_correctly_rounded() {
+    type UniMpfr100bit = UniFloat<{ MPFR_100_BITS }>;
+    type UniMpfr200bit = UniFloat<{ MPFR_200_BITS }>;
+    let constants = [
+        ("pi", UniMpfr100bit::pi(), UniMpfr200bit::pi(), UniTwoFloat::pi()),
+        ("tau", UniMpfr100bit::tau(), UniMpfr200bit::tau(), UniTwoFloat::tau()),
+        ("frac_pi_2", UniMpfr100bit::frac_pi_2(), UniMpfr200bit::frac_pi_2(), UniTwoFloat::frac_pi_2()),
+        ("e", UniMpfr100bit::e(), UniMpfr200bit::e(), UniTwoFloat::e()),
+        ("ln_2", UniMpfr100bit::ln_2(), UniMpfr200bit::ln_2(), UniTwoFloat::ln_2()),
+        ("ln_10", UniMpfr100bit::ln_10(), UniMpfr200bit::ln_10(), UniTwoFloat::ln_10()),
+        ("log2_e", UniMpfr100bit::log2_e(), UniMpfr200bit::log2_e(), UniTwoFloat::log2_e()),
+        ("log10_e", UniMpfr100bit::log10_e(), UniMpfr200bit::log10_e(), UniTwoFloat::log10_e()),
+        ("sqrt_2", UniMpfr100bit::sqrt_2(), UniMpfr200bit::sqrt_2(), UniTwoFloat::sqrt_2()),
+        ("frac_1_sqrt_2", UniMpfr100bit::frac_1_sqrt_2(), UniMpfr200bit::frac_1_sqrt_2(), UniTwoFloat::frac_1_sqrt_2()),
+    ];
+    for &(name, mut constant, mut reference, mut twofloat) in constants.iter() {
+        constant.copied();
+        reference.copied();
+        twofloat.copied();
+        unsafe {
+            let (constant, reference) = (constant.mpfr_mut(), reference.mpfr_mut());
+            let mut rounded = UniMpfr100bit::NAN;
+            rounded.copied();
+            let rounded = rounded.mpfr_mut();
+            mpfr::set(rounded, reference, mpfr::rnd_t::RNDN);
+            assert!(mpfr::equal_p(constant, rounded) != 0, "{}", name);
+
+            let magnitude = mpfr::get_d(reference, mpfr::rnd_t::RNDN);
+            mpfr::sub_d(reference, reference, twofloat.twofloats[0].hi(), mpfr::rnd_t::RNDN);
+            mpfr::sub_d(reference, reference, twofloat.twofloats[0].lo(), mpfr::rnd_t::RNDN);
+            let error = mpfr::get_d(reference, mpfr::rnd_t::RNDN) / magnitude;
+            assert!(libm::fabs(error) < 1e-31, "{}: {}", name, error);
+        }
+    }
+}
+
+#[test]
+fn euler_and_catalan_f32() {
+    let (mut euler, mut catalan) = (crate::UniF32::const_euler(), crate::UniF32::const_catalan());
+    euler.copied();
+    catalan.copied();
+    assert_eq!(euler.f32s[0], 0.5772157);
+    assert_eq!(catalan.f32s[0], 0.9159656);
+}
+
+/// As `mpfr_consts_are_correctly_rounded()`, and assigning in place gives the same.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn euler_and_catalan() {
+    type UniMpfr100bit = UniFloat<{ MPFR_100_BITS }>;
+    type UniMpfr200bit = UniFloat<{ MPFR_200_BITS }>;
+    let constants = [
+        ("euler", UniMpfr100bit::const_euler(), UniMpfr200bit::const_euler(), UniTwoFloat::const_euler()),
+        ("catalan", UniMpfr100bit::const_catalan(), UniMpfr200bit::const_catalan(), UniTwoFloat::const_catalan()),
+    ];
+    for &(name, mut constant, mut reference, mut twofloat) in constants.iter() {
+        constant.copied();
+        reference.copied();
+        twofloat.copied();
+        let mut assigned = UniMpfr100bit::NAN;
+        assigned.copied();
+        if name == "euler" { assigned.assign_const_euler() } else { assigned.assign_const_catalan() };
+        unsafe {
+            let (constant, reference) = (constant.mpfr_mut(), reference.mpfr_mut());
+            assert!(mpfr::equal_p(constant, assigned.mpfr_src().as_ptr()) != 0, "{}", name);
+            let mut rounded = UniMpfr100bit::NAN;
+            rounded.copied();
+            let rounded = rounded.mpfr_mut();
+            mpfr::set(rounded, reference, mpfr::rnd_t::RNDN);
+            assert!(mpfr::equal_p(constant, rounded) != 0, "{}", name);
+
+            let magnitude = mpfr::get_d(reference, mpfr::rnd_t::RNDN);
+            mpfr::sub_d(reference, reference, twofloat.twofloats[0].hi(), mpfr::rnd_t::RNDN);
+            mpfr::sub_d(reference, reference, twofloat.twofloats[0].lo(), mpfr::rnd_t::RNDN);
+            let error = mpfr::get_d(reference, mpfr::rnd_t::RNDN) / magnitude;
+            assert!(libm::fabs(error) < 1e-31, "{}: {}", name, error);
+        }
+    }
+}
+
+/// `ONE` and `EPSILON` agree with `ulp()`, `MAX` is the largest finite value, and the special
+/// values have the expected signs. `max_is_largest` is false for TwoFloat, which can add a lower
+/// part to `f64::MAX`.
+fn assert_special_consts<const C: UniFloatChoice>(max_is_largest: bool) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut infinity, mut neg_infinity) = (UniFloat::<C>::INFINITY, UniFloat::<C>::NEG_INFINITY);
+    let (mut zero, mut neg_zero) = (UniFloat::<C>::ZERO, UniFloat::<C>::NEG_ZERO);
+    let (mut one, mut epsilon) = (UniFloat::<C>::ONE, UniFloat::<C>::EPSILON);
+    let (mut max, mut min) = (UniFloat::<C>::MAX, UniFloat::<C>::MIN);
+    for constant in [&mut infinity, &mut neg_infinity, &mut zero, &mut neg_zero, &mut one, &mut epsilon, &mut max,
+        &mut min].iter_mut() {
+        constant.copied();
+    }
+    assert!(infinity.is_infinite() && infinity.is_sign_positive());
+    assert!(neg_infinity.is_infinite() && neg_infinity.is_sign_negative());
+    assert!(zero.to_f64_nearest() == 0.0 && zero.is_sign_positive());
+    assert!(neg_zero.to_f64_nearest() == 0.0 && neg_zero.is_sign_negative());
+    assert_eq!(one.to_f64_nearest(), 1.0);
+
+    let mut ulp = one.ulp();
+    ulp.copied();
+    assert_eq!(ulp.to_f64_nearest(), epsilon.to_f64_nearest());
+    assert!(epsilon.to_f64_nearest() > 0.0);
+
+    assert!(max.is_finite() && max.is_sign_positive());
+    assert!(min.is_finite() && min.is_sign_negative());
+    let (mut above, mut below) = (max.next_up(), min.next_down());
+    above.copied();
+    below.copied();
+    assert_eq!(above.is_infinite(), max_is_largest);
+    assert_eq!(below.is_infinite(), max_is_largest);
+}
+
+#[test]
+fn special_consts() {
+    assert_special_consts::<{ UniFloatChoice::F32 }>(true);
+    assert_eq!(crate::UniF32::EPSILON.f32s[0], f32::EPSILON);
+    assert_eq!(crate::UniF32::MAX.f32s[0], f32::MAX);
+    #[cfg(not(feature = "f32_only"))]
+    {
+        assert_special_consts::<{ UniFloatChoice::F64 }>(true);
+        assert_special_consts::<{ UniFloatChoice::TwoFloat }>(false);
+        assert_special_consts::<{ MPFR_100_BITS }>(true);
+        assert_special_consts::<{ MPFR_200_BITS }>(true);
+        assert_eq!(UniF64::MIN.f64s[0], f64::MIN);
+        assert_eq!(UniTwoFloat::EPSILON.twofloats[0].hi(), libm::ldexp(1.0, -105));
+    }
+}
+
+/// MPFR `MAX` is (1 - 2^-precision) * 2^(2^30 - 1): all bits of its precision are set.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn mpfr_max_has_all_bits() {
+    type UniMpfr100bit = UniFloat<{ MPFR_100_BITS }>;
+    let mut max = UniMpfr100bit::MAX;
+    max.copied();
+    unsafe {
+        let source = max.mpfr_src();
+        let max = source.as_ptr();
+        assert_eq!(mpfr::get_exp(max), (1 << 30) - 1);
+        let mut halved = UniMpfr100bit::NAN;
+        halved.copied();
+        let halved = halved.mpfr_mut();
+        // Exact, since MAX / 2^(2^30 - 1) = 1 - 2^-100 fits in 100 bits.
+        assert_eq!(mpfr::div_2si(halved, max, (1 << 30) - 1, mpfr::rnd_t::RNDN), 0);
+        assert_eq!(mpfr::ui_sub(halved, 1, halved, mpfr::rnd_t::RNDN), 0);
+        assert_eq!(mpfr::get_d(halved, mpfr::rnd_t::RNDN), libm::ldexp(1.0, -100));
+    }
+}
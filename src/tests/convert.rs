@@ -0,0 +1,297 @@
+use {core::mem::MaybeUninit, gmp_mpfr_sys::gmp};
+#[cfg(not(feature = "f32_only"))]
+use gmp_mpfr_sys::mpfr;
+use crate::{Conversion, MpfrBounds, ToIntErrorKind, RoundingMode, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_200_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(200)
+};
+
+fn assert_from_primitives<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut float = UniFloat::<C>::from(1.5f32);
+    float.copied();
+    assert_eq!(float.to_f64_nearest(), 1.5);
+    float <<= UniFloat::<C>::from(-0.25f64);
+    assert_eq!(float.to_f64_nearest(), -0.25);
+    float <<= UniFloat::<C>::from(-7i8);
+    assert_eq!(float.to_f64_nearest(), -7.0);
+    float <<= UniFloat::<C>::from(65_535u16);
+    assert_eq!(float.to_f64_nearest(), 65_535.0);
+    float <<= UniFloat::<C>::from(i64::MIN);
+    assert_eq!(float.to_f64_nearest(), -9_223_372_036_854_775_808.0);
+    float <<= UniFloat::<C>::from(i128::MIN);
+    assert_eq!(float.to_f64_nearest(), -170_141_183_460_469_231_731_687_303_715_884_105_728.0);
+    float <<= UniFloat::<C>::from(1u128 << 100);
+    assert_eq!(float.to_f64_nearest(), 1_267_650_600_228_229_401_496_703_205_376.0);
+}
+
+#[test]
+fn from_primitives() {
+    assert_from_primitives::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_from_primitives::<{ UniFloatChoice::F64 }>();
+        assert_from_primitives::<{ UniFloatChoice::TwoFloat }>();
+        assert_from_primitives::<{ MPFR_200_BITS }>();
+    }
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn from_integers_exact_beyond_f64() {
+    // 2^64 - 1 doesn't fit into f64.
+    let mut twofloat = crate::UniTwoFloat::from(u64::MAX);
+    twofloat.copied();
+    assert_eq!(twofloat.twofloats[0].hi(), 18_446_744_073_709_551_616.0);
+    assert_eq!(twofloat.twofloats[0].lo(), -1.0);
+
+    // 2^127 - 1 needs 127 bits.
+    let mut mpfr_based = UniFloat::<{ MPFR_200_BITS }>::from(i128::MAX);
+    mpfr_based.copied();
+    let mut one = UniFloat::<{ MPFR_200_BITS }>::from(1u8);
+    one.copied();
+    mpfr_based += &one;
+    unsafe {
+        assert_eq!(mpfr::cmp_si_2exp(mpfr_based.mpfr_src().as_ptr(), 1, 127), 0);
+    }
+}
+
+fn assert_to_primitives<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut float = UniFloat::<C>::from_f64(-2.5);
+    float.copied();
+    assert_eq!(float.to_f32(RoundingMode::Nearest), Conversion::Exact(-2.5));
+    assert_eq!(float.to_f64(RoundingMode::Up), Conversion::Exact(-2.5));
+    assert_eq!(float.to_i32(RoundingMode::Nearest), Conversion::Rounded(-2));
+    assert_eq!(float.to_i64(RoundingMode::TowardZero), Conversion::Rounded(-2));
+    assert_eq!(float.to_i8(RoundingMode::Up), Conversion::Rounded(-2));
+    assert_eq!(float.to_i128(RoundingMode::Down), Conversion::Rounded(-3));
+    assert_eq!(float.to_u32(RoundingMode::Nearest), Conversion::Overflow(0));
+
+    float <<= UniFloat::<C>::from(300u16);
+    assert_eq!(float.to_u16(RoundingMode::Nearest), Conversion::Exact(300));
+    assert_eq!(float.to_i8(RoundingMode::Nearest), Conversion::Overflow(i8::MAX));
+    assert_eq!(float.to_u8(RoundingMode::Nearest), Conversion::Overflow(u8::MAX));
+
+    float <<= UniFloat::<C>::from(i8::MIN);
+    assert_eq!(float.to_i8(RoundingMode::Nearest), Conversion::Exact(i8::MIN));
+    assert_eq!(float.to_isize(RoundingMode::Nearest), Conversion::Exact(-128));
+
+    float <<= UniFloat::<C>::from(i128::MIN);
+    assert_eq!(float.to_i128(RoundingMode::Nearest), Conversion::Exact(i128::MIN));
+    assert_eq!(float.to_i64(RoundingMode::Nearest), Conversion::Overflow(i64::MIN));
+
+    float <<= UniFloat::<C>::from(f32::NAN);
+    assert!(matches!(float.to_f32(RoundingMode::Nearest), Conversion::NaN(value) if value.is_nan()));
+    assert!(matches!(float.to_f64(RoundingMode::Nearest), Conversion::NaN(value) if value.is_nan()));
+    assert_eq!(float.to_u64(RoundingMode::Nearest), Conversion::NaN(0));
+}
+
+#[test]
+fn to_primitives() {
+    assert_to_primitives::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_to_primitives::<{ UniFloatChoice::F64 }>();
+        assert_to_primitives::<{ UniFloatChoice::TwoFloat }>();
+        assert_to_primitives::<{ MPFR_200_BITS }>();
+    }
+}
+
+fn assert_to_integers_round<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut float = UniFloat::<C>::from_f64(-2.5);
+    float.copied();
+    assert_eq!(float.to_i64_round(RoundingMode::Nearest), Ok(-2));
+    assert_eq!(float.to_i64_round(RoundingMode::ToNearestAway), Ok(-3));
+    assert_eq!(float.to_i32_round(RoundingMode::Up), Ok(-2));
+    assert_eq!(float.to_u128_round(RoundingMode::Down).unwrap_err().kind(), ToIntErrorKind::NegativeOverflow);
+    // -0.5 rounds up to -0, which fits.
+    float <<= UniFloat::<C>::from_f64(-0.5);
+    assert_eq!(float.to_u8_round(RoundingMode::Up), Ok(0));
+
+    float <<= UniFloat::<C>::from_f64(255.5);
+    assert_eq!(float.to_u8_round(RoundingMode::TowardZero), Ok(255));
+    assert_eq!(float.to_u8_round(RoundingMode::Up).unwrap_err().kind(), ToIntErrorKind::PositiveOverflow);
+
+    float <<= UniFloat::<C>::from_f64(f64::NEG_INFINITY);
+    assert_eq!(float.to_isize_round(RoundingMode::Nearest).unwrap_err().kind(), ToIntErrorKind::NegativeOverflow);
+    float <<= UniFloat::<C>::from_f64(f64::NAN);
+    assert_eq!(float.to_u64_round(RoundingMode::Nearest).unwrap_err().kind(), ToIntErrorKind::NaN);
+}
+
+#[test]
+fn to_integers_round() {
+    assert_to_integers_round::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_to_integers_round::<{ UniFloatChoice::F64 }>();
+        assert_to_integers_round::<{ UniFloatChoice::TwoFloat }>();
+        assert_to_integers_round::<{ MPFR_200_BITS }>();
+
+        // Rounded once, from all 200 bits: 2^64 - 1/2 isn't an f64.
+        let mut float = UniFloat::<{ MPFR_200_BITS }>::from(u64::MAX);
+        float.copied();
+        let mut half = UniFloat::<{ MPFR_200_BITS }>::from_f64(0.5);
+        half.copied();
+        float += &half;
+        assert_eq!(float.to_u64_round(RoundingMode::Down), Ok(u64::MAX));
+        assert!(float.to_u64_round(RoundingMode::Up).is_err());
+        assert_eq!(float.to_u128_round(RoundingMode::Up), Ok(u64::MAX as u128 + 1));
+    }
+}
+
+/// `2^shift + addend` (negated if `negative`) to `C`, and back to an integer, rounded by `rounding`.
+/// Returns the float as f64, and whether the integer is the same as the original (false for
+/// infinities).
+fn mpz_round_trip<const C: UniFloatChoice>(shift: u64, addend: u64, negative: bool, rounding: RoundingMode) -> (f64, bool) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    unsafe {
+        let (mut integer, mut back) = (MaybeUninit::uninit(), MaybeUninit::uninit());
+        let (integer, back) = (integer.as_mut_ptr(), back.as_mut_ptr());
+        gmp::mpz_init_set_ui(integer, 1);
+        gmp::mpz_init(back);
+        gmp::mpz_mul_2exp(integer, integer, shift as gmp::bitcnt_t);
+        gmp::mpz_add_ui(integer, integer, addend as _);
+        if negative {
+            gmp::mpz_neg(integer, integer);
+        }
+        let mut float = UniFloat::<C>::from_mpz(&*integer);
+        float.copied();
+        let same = float.to_mpz_round(rounding, &mut *back).is_ok() && gmp::mpz_cmp(integer, back) == 0;
+        gmp::mpz_clear(integer);
+        gmp::mpz_clear(back);
+        (float.to_f64_nearest(), same)
+    }
+}
+
+#[test]
+fn mpz() {
+    type UniF32 = UniFloat<{ UniFloatChoice::F32 }>;
+    let two_pow = |n| libm::ldexp(1.0, n);
+    // Ties to even, and the bits below the highest 64 count, too.
+    assert_eq!(mpz_round_trip::<{ UniFloatChoice::F32 }>(24, 1, true, RoundingMode::Nearest), (-two_pow(24), false));
+    assert_eq!(mpz_round_trip::<{ UniFloatChoice::F32 }>(24, 3, false, RoundingMode::Nearest), (two_pow(24) + 4.0, false));
+    assert_eq!(mpz_round_trip::<{ UniFloatChoice::F32 }>(100, 0, false, RoundingMode::Nearest), (two_pow(100), true));
+    assert_eq!(mpz_round_trip::<{ UniFloatChoice::F32 }>(200, 0, false, RoundingMode::Nearest).0, f64::INFINITY);
+    assert_eq!(mpz_round_trip::<{ UniFloatChoice::F32 }>(0, 4, false, RoundingMode::Nearest), (5.0, true));
+
+    let mut float = UniF32::from_f64(-2.5);
+    float.copied();
+    unsafe {
+        let mut integer = MaybeUninit::uninit();
+        gmp::mpz_init(integer.as_mut_ptr());
+        let mut integer = integer.assume_init();
+        float.to_mpz_round(RoundingMode::Down, &mut integer).unwrap();
+        assert_eq!(gmp::mpz_get_si(&integer), -3);
+        float.to_mpz_round(RoundingMode::ToNearestAway, &mut integer).unwrap();
+        assert_eq!(gmp::mpz_get_si(&integer), -3);
+        float.to_mpz_round(RoundingMode::Nearest, &mut integer).unwrap();
+        assert_eq!(gmp::mpz_get_si(&integer), -2);
+        float <<= UniF32::from_f64(f64::NEG_INFINITY);
+        assert_eq!(float.to_mpz_round(RoundingMode::Nearest, &mut integer).unwrap_err().kind(), ToIntErrorKind::NegativeOverflow);
+        float <<= UniF32::from_f64(f64::NAN);
+        assert_eq!(float.to_mpz_round(RoundingMode::Nearest, &mut integer).unwrap_err().kind(), ToIntErrorKind::NaN);
+        // Unchanged by the errors.
+        assert_eq!(gmp::mpz_get_si(&integer), -2);
+        gmp::mpz_clear(&mut integer);
+    }
+
+    #[cfg(not(feature = "f32_only"))] {
+        assert_eq!(mpz_round_trip::<{ UniFloatChoice::F64 }>(64, 1, false, RoundingMode::Nearest), (two_pow(64), false));
+        assert_eq!(mpz_round_trip::<{ UniFloatChoice::F64 }>(1000, 0, true, RoundingMode::Up), (-two_pow(1000), true));
+        assert_eq!(mpz_round_trip::<{ UniFloatChoice::F64 }>(1024, 0, false, RoundingMode::Nearest).0, f64::INFINITY);
+        // Double-double holds 2^200 + 1 (the lower part is 1), but not 2^200 + 2^63 + 1.
+        assert_eq!(mpz_round_trip::<{ UniFloatChoice::TwoFloat }>(64, 1, true, RoundingMode::Nearest), (-two_pow(64), true));
+        assert_eq!(mpz_round_trip::<{ UniFloatChoice::TwoFloat }>(200, 1, false, RoundingMode::Nearest), (two_pow(200), true));
+        assert_eq!(mpz_round_trip::<{ UniFloatChoice::TwoFloat }>(200, (1 << 63) + 1, false, RoundingMode::Nearest),
+            (two_pow(200), false));
+        assert_eq!(mpz_round_trip::<{ MPFR_200_BITS }>(199, 1, true, RoundingMode::Nearest), (-two_pow(199), true));
+        assert_eq!(mpz_round_trip::<{ MPFR_200_BITS }>(300, 1, false, RoundingMode::Nearest), (two_pow(300), false));
+    }
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn to_f32_rounds_in_direction() {
+    // 1 + 2^-30 is between two neighbouring f32 values: 1 and 1 + 2^-23.
+    let mut float = crate::UniF64::from_f64(1.0 + 1.0 / (1u64 << 30) as f64);
+    float.copied();
+    let above = 1.0 + f32::EPSILON;
+    assert_eq!(float.to_f32(RoundingMode::Nearest), Conversion::Rounded(1.0));
+    assert_eq!(float.to_f32(RoundingMode::Down), Conversion::Rounded(1.0));
+    assert_eq!(float.to_f32(RoundingMode::Up), Conversion::Rounded(above));
+
+    let mut float = crate::UniF64::from_f64(-1e300);
+    float.copied();
+    assert_eq!(float.to_f32(RoundingMode::Nearest), Conversion::Overflow(f32::NEG_INFINITY));
+    assert_eq!(float.to_f32(RoundingMode::Up), Conversion::Overflow(-f32::MAX));
+
+    // 2^64 - 1 doesn't fit into f64.
+    let mut twofloat = crate::UniTwoFloat::from(u64::MAX);
+    twofloat.copied();
+    assert_eq!(twofloat.to_f64(RoundingMode::Down), Conversion::Rounded(18_446_744_073_709_549_568.0));
+    assert_eq!(twofloat.to_f64(RoundingMode::Up), Conversion::Rounded(18_446_744_073_709_551_616.0));
+    assert_eq!(twofloat.to_u64(RoundingMode::Nearest), Conversion::Exact(u64::MAX));
+
+    let mut mpfr_based = UniFloat::<{ MPFR_200_BITS }>::from(u128::MAX);
+    mpfr_based.copied();
+    assert_eq!(mpfr_based.to_u128(RoundingMode::Nearest), Conversion::Exact(u128::MAX));
+    assert_eq!(mpfr_based.to_f32(RoundingMode::TowardZero), Conversion::Rounded(f32::MAX));
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn promoted_choices() {
+    let mpfr_100_bits = UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(100) };
+    assert!(UniFloatChoice::F32.promoted(&UniFloatChoice::F64) == UniFloatChoice::F64);
+    assert!(UniFloatChoice::TwoFloat.promoted(&UniFloatChoice::F64) == UniFloatChoice::TwoFloat);
+    assert!(UniFloatChoice::F64.promoted(&mpfr_100_bits) == mpfr_100_bits);
+    assert!(mpfr_100_bits.promoted(&UniFloatChoice::TwoFloat)
+        == UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(106) });
+    assert!(mpfr_100_bits.promoted(&MPFR_200_BITS) == MPFR_200_BITS);
+    assert!(mpfr_100_bits.promoted(&mpfr_100_bits) == mpfr_100_bits);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn convert_between_choices() {
+    // 2^64 - 1 doesn't fit into f64.
+    let mut twofloat = crate::UniTwoFloat::from(u64::MAX);
+    twofloat.copied();
+    let mut mpfr_based = twofloat.convert::<{ MPFR_200_BITS }>();
+    mpfr_based.copied();
+    assert_eq!(mpfr_based.to_u64(RoundingMode::Nearest), Conversion::Exact(u64::MAX));
+
+    let mut back = mpfr_based.convert::<{ UniFloatChoice::TwoFloat }>();
+    back.copied();
+    assert_eq!(back.twofloats[0].hi(), 18_446_744_073_709_551_616.0);
+    assert_eq!(back.twofloats[0].lo(), -1.0);
+
+    let mut single = twofloat.convert::<{ UniFloatChoice::F32 }>();
+    single.copied();
+    assert_eq!(single.f32s[0], 18_446_744_073_709_551_616.0);
+
+    let mut double = mpfr_based.convert::<{ UniFloatChoice::F64 }>();
+    double.copied();
+    assert_eq!(double.f64s[0], 18_446_744_073_709_551_616.0);
+}
@@ -0,0 +1,43 @@
+extern crate std;
+
+use std::format;
+use crate::{MpfrBounds, UniFloat, UniFloatChoice};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+#[test]
+fn debug() {
+    let mut float = crate::UniF32::from_f64(1.5);
+    float.copied();
+    let mutated = if cfg!(debug_assertions) { "Some(false)" } else { "None" };
+    assert_eq!(format!("{:?}", float),
+        format!("UniFloat {{ value: 1.5, choice: F32, precision_bits: 24, mutated: {} }}", mutated));
+    assert_eq!(format!("{:.2?}", float),
+        format!("UniFloat {{ value: 1.50, choice: F32, precision_bits: 24, mutated: {} }}", mutated));
+
+    // Used through `OperandMutated`, and not cleared. Debug works anyway.
+    let mut float = crate::UniF32::from_f64(-0.25);
+    let _ = float.mutate();
+    let mutated = if cfg!(debug_assertions) { "Some(true)" } else { "None" };
+    assert_eq!(format!("{:?}", float),
+        format!("UniFloat {{ value: -0.25, choice: F32, precision_bits: 24, mutated: {} }}", mutated));
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn debug_mpfr() {
+    let mut float = UniFloat::<{ MPFR_100_BITS }>::from_f64(1.5);
+    let mutated = if cfg!(debug_assertions) { "Some(false)" } else { "None" };
+    assert_eq!(format!("{:?}", float), format!("UniFloat {{ value: 1.5, choice: Mpfr {{ bounds: MpfrBounds {{ \
+        precision_bits: 100, limb_parts: 2, min_exponent: -9223372036854775808, max_exponent: 9223372036854775807, \
+        underflow: Rounded, fixed: None }} }}, \
+        precision_bits: 100, mutated: {} }}", mutated));
+    let _ = float.mutate();
+    float.copied();
+    assert!(format!("{:?}", float).ends_with(&format!("mutated: {} }}", mutated)));
+    let mut twofloat = crate::UniTwoFloat::NAN;
+    twofloat.copied();
+    assert!(format!("{:?}", twofloat).starts_with("UniFloat { value: NaN, choice: TwoFloat, precision_bits: 106"));
+}
@@ -0,0 +1,178 @@
+extern crate std;
+
+use std::{format, string::String};
+use crate::{DECIMAL_EXPONENT_LIMIT, RoundingMode, UniDecimal};
+#[cfg(not(feature = "f32_only"))]
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, UniRational};
+
+type Decimal64 = UniDecimal<16>;
+
+fn decimal(s: &str) -> Decimal64 {
+    s.parse().unwrap()
+}
+
+fn text(value: Decimal64) -> String {
+    format!("{}", value)
+}
+
+#[test]
+fn decimal_arithmetic() {
+    assert!(decimal("0.1") + decimal("0.2") == decimal("0.3"));
+    assert_eq!(text(decimal("0.1") + decimal("0.2")), "0.3");
+    // Exact results keep the quantum.
+    assert_eq!(text(decimal("1.50") + decimal("1.5")), "3.00");
+    assert_eq!(text(Decimal64::new(2, 0) * Decimal64::new(150, -2)), "3.00");
+    assert_eq!(text(decimal("6") / decimal("3")), "2");
+    assert_eq!(text(decimal("1.20") / decimal("3")), "0.40");
+    assert_eq!(text(decimal("1") / decimal("3")), "0.3333333333333333");
+    assert_eq!(text(decimal("2") / decimal("3")), "0.6666666666666667");
+    assert_eq!(text(-decimal("2.5")), "-2.5");
+    assert_eq!(text(decimal("-2.5").abs()), "2.5");
+
+    // Far apart: the lesser operand only decides the rounding.
+    let (one, tiny) = (Decimal64::new(1, 0), Decimal64::new(1, -40));
+    assert_eq!(text(one + tiny), "1.000000000000000");
+    assert_eq!(text(one.add_round(&tiny, RoundingMode::Up)), "1.000000000000001");
+    assert_eq!(text(one - tiny), "1.000000000000000");
+    assert_eq!(text(one.sub_round(&tiny, RoundingMode::TowardZero)), "0.9999999999999999");
+
+    let zero = one - one;
+    assert!(zero.is_zero() && !zero.is_sign_negative());
+    assert!(one.sub_round(&one, RoundingMode::Down).is_sign_negative());
+
+    assert!((Decimal64::INFINITY - Decimal64::INFINITY).is_nan());
+    assert!((Decimal64::INFINITY * Decimal64::ZERO).is_nan());
+    assert!((one / Decimal64::ZERO) == Decimal64::INFINITY);
+    assert!((-one / Decimal64::ZERO) == Decimal64::NEG_INFINITY);
+    assert!((Decimal64::ZERO / Decimal64::ZERO).is_nan());
+    assert!((one / Decimal64::INFINITY).is_zero());
+}
+
+#[test]
+fn decimal_rounding() {
+    let quantized = |s: &str, exponent, rounding| text(decimal(s).quantize(exponent, rounding));
+    assert_eq!(quantized("2.5", 0, RoundingMode::Nearest), "2");
+    assert_eq!(quantized("3.5", 0, RoundingMode::Nearest), "4");
+    assert_eq!(quantized("2.5", 0, RoundingMode::Faithful), "2");
+    assert_eq!(quantized("2.5", 0, RoundingMode::ToNearestAway), "3");
+    assert_eq!(quantized("2.1", 0, RoundingMode::Up), "3");
+    assert_eq!(quantized("-2.1", 0, RoundingMode::Down), "-3");
+    assert_eq!(quantized("-2.9", 0, RoundingMode::TowardZero), "-2");
+    assert_eq!(quantized("1.005", -2, RoundingMode::Nearest), "1.00");
+    assert_eq!(quantized("1.005", -2, RoundingMode::ToNearestAway), "1.01");
+    assert_eq!(quantized("0.004", -2, RoundingMode::Nearest), "0.00");
+    assert_eq!(quantized("1", -15, RoundingMode::Nearest), "1.000000000000000");
+    assert!(decimal("1").quantize(-16, RoundingMode::Nearest).is_nan());
+    assert!(decimal("9999999999999999.5").quantize(0, RoundingMode::Nearest).is_nan());
+    assert!(Decimal64::INFINITY.quantize(0, RoundingMode::Nearest).is_nan());
+
+    // Overflow, and clamping with trailing zeros.
+    let largest = Decimal64::new(9_999_999_999_999_999, DECIMAL_EXPONENT_LIMIT);
+    assert!(largest * Decimal64::new(10, 0) == Decimal64::INFINITY);
+    assert!(largest.mul_round(&Decimal64::new(10, 0), RoundingMode::TowardZero) == largest);
+    assert!(largest.mul_round(&Decimal64::new(-10, 0), RoundingMode::Up) == -largest);
+    let clamped = Decimal64::new(1, DECIMAL_EXPONENT_LIMIT) * Decimal64::new(1, 3);
+    assert_eq!((clamped.coefficient(), clamped.exponent()), (1000, DECIMAL_EXPONENT_LIMIT));
+
+    // Gradual underflow.
+    let tiny = Decimal64::new(3, -DECIMAL_EXPONENT_LIMIT) / Decimal64::new(4, 0);
+    assert_eq!((tiny.coefficient(), tiny.exponent()), (1, -DECIMAL_EXPONENT_LIMIT));
+    assert!((Decimal64::new(1, -DECIMAL_EXPONENT_LIMIT) / Decimal64::new(4, 0)).is_zero());
+}
+
+#[test]
+fn decimal_text() {
+    let parsed = decimal("1.50");
+    assert_eq!((parsed.coefficient(), parsed.exponent()), (150, -2));
+    let parsed = decimal("12345678901234567890");
+    assert_eq!((parsed.coefficient(), parsed.exponent()), (1_234_567_890_123_457, 4));
+    assert_eq!(text(parsed), "1.234567890123457E+19");
+    assert_eq!(text(decimal("-0.000")), "-0.000");
+    assert_eq!(text(decimal("1.5e-3")), "0.0015");
+    assert_eq!(text(Decimal64::new(123, 3)), "1.23E+5");
+    assert_eq!(text(Decimal64::new(123, -10)), "1.23E-8");
+    assert_eq!(text(Decimal64::new(1, -6)), "0.000001");
+    assert_eq!(text(Decimal64::new(1, -7)), "1E-7");
+    assert_eq!(text(Decimal64::new(-1250, -2)), "-12.50");
+    assert_eq!(text(Decimal64::new(0, 2)), "0E+2");
+    assert_eq!(text(decimal("-inf")), "-Infinity");
+    assert_eq!(text(decimal("NaN")), "NaN");
+    assert!("".parse::<Decimal64>().is_err());
+    assert!("1.2.3".parse::<Decimal64>().is_err());
+
+    assert!(decimal("1.0") == decimal("1.00"));
+    assert!(decimal("-0") == decimal("0"));
+    assert!(decimal("0.1") < decimal("0.11"));
+    assert!(decimal("1E+2") > decimal("99"));
+    assert!(decimal("-1E+2") < decimal("-99"));
+    assert!(decimal("-inf") < decimal("-1E+100"));
+    assert!(decimal("nan") != decimal("nan"));
+    assert_eq!(decimal("nan").partial_cmp(&decimal("1")), None);
+}
+
+#[cfg(not(feature = "f32_only"))]
+#[test]
+fn decimal_conversions() {
+    const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+        bounds: MpfrBounds::for_precision_binary(100)
+    };
+    let mut tenth = UniFloat::<{ UniFloatChoice::F64 }>::from_f64(0.1);
+    tenth.copied();
+    // 0.1 as f64 is a little more than 0.1.
+    let converted = Decimal64::from_unifloat_round(&tenth, RoundingMode::Nearest);
+    assert_eq!(text(converted), "0.1000000000000000");
+    assert_eq!(text(Decimal64::from_unifloat_round(&tenth, RoundingMode::Up)), "0.1000000000000001");
+    let mut half = UniFloat::<{ UniFloatChoice::F32 }>::from_f64(-0.5);
+    let converted = Decimal64::from_unifloat_round(half.copied(), RoundingMode::Nearest);
+    assert_eq!((converted.coefficient(), converted.exponent(), converted.is_sign_negative()), (5, -1, true));
+
+    let mut nearest = decimal("0.1").to_unifloat_round::<{ UniFloatChoice::F64 }>(RoundingMode::Nearest).unwrap();
+    assert_eq!(nearest.copied().to_f64_nearest(), 0.1);
+    let mut down = decimal("0.1").to_unifloat_round::<{ UniFloatChoice::F64 }>(RoundingMode::Down).unwrap();
+    assert_eq!(down.copied().to_f64_nearest(), f64::from_bits(0.1f64.to_bits() - 1));
+    assert!(decimal("0.1").to_unifloat_round::<{ UniFloatChoice::TwoFloat }>(RoundingMode::Up).is_err());
+
+    let mut precise = decimal("0.1").to_unifloat_round::<{ MPFR_100_BITS }>(RoundingMode::Nearest).unwrap();
+    assert!(Decimal64::from_unifloat_round(precise.copied(), RoundingMode::Nearest) == decimal("0.1"));
+    let mut infinity = Decimal64::NEG_INFINITY.to_unifloat_round::<{ UniFloatChoice::F64 }>(RoundingMode::Nearest).unwrap();
+    assert_eq!(infinity.copied().to_f64_nearest(), f64::NEG_INFINITY);
+    let mut nan = UniFloat::<{ UniFloatChoice::F64 }>::NAN;
+    assert!(Decimal64::from_unifloat_round(nan.copied(), RoundingMode::Nearest).is_nan());
+}
+
+#[cfg(not(feature = "f32_only"))]
+#[test]
+fn decimal_choice() {
+    const DECIMAL16: UniFloatChoice = UniFloatChoice::decimal(16);
+    type UniDecimal16 = UniFloat<{ DECIMAL16 }>;
+    let uni = |s: &str| decimal(s).to_unifloat_round::<{ DECIMAL16 }>(RoundingMode::Nearest).unwrap();
+    let back = |value: &UniDecimal16| Decimal64::from_unifloat_round(value, RoundingMode::Nearest);
+
+    // The same arithmetic as `UniDecimal`, quantum included.
+    let mut sum = uni("0.1");
+    sum += &uni("0.2");
+    assert!(back(&sum) == decimal("0.3"));
+    assert_eq!(format!("{}", sum), "0.3");
+    let mut quotient = uni("2");
+    quotient /= &uni("3");
+    assert_eq!(text(back(&quotient)), "0.6666666666666667");
+    assert_eq!(text(back(&uni("1.50"))), "1.50");
+    assert!(back(&uni("0.25").sqrt()) == decimal("0.5"));
+    assert_eq!(text(back(&uni("1").next_up())), "1.000000000000001");
+    assert!(back(&UniDecimal16::EPSILON) == Decimal64::new(1, -15));
+    assert!(back(&UniDecimal16::MAX) == Decimal64::new(9_999_999_999_999_999, DECIMAL_EXPONENT_LIMIT));
+    assert!(back(&UniDecimal16::exact_sum(&[uni("0.1"), uni("1e20"), uni("0.2"), uni("-1e20")])) == decimal("0.3"));
+
+    // Literals, text and binary values round once, to decimal digits.
+    assert!(back(&UniDecimal16::from_literal("0.1")) == decimal("0.1"));
+    let parsed: UniDecimal16 = "0.1".parse().unwrap();
+    assert!(back(&parsed) == decimal("0.1"));
+    assert_eq!(uni("0.1").to_f64_nearest(), 0.1);
+    assert_eq!(text(back(&UniDecimal16::from_f64(0.1))), "0.1000000000000000");
+
+    // Rationals round exactly, even on decimal ties (which aren't binary).
+    let tie = UniRational::from_integers(3, 20);
+    let rounded = tie.to_unifloat::<{ UniFloatChoice::decimal(1) }>();
+    assert!(UniDecimal::<1>::from_unifloat_round(&rounded, RoundingMode::Nearest) == UniDecimal::<1>::new(2, -1));
+    assert!(UniRational::from_unifloat(&uni("0.15")).unwrap() == tie);
+}
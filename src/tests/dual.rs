@@ -0,0 +1,137 @@
+#[cfg(not(feature = "f32_only"))]
+use crate::MpfrBounds;
+use crate::{UniDual, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+#[cfg(not(feature = "f32_only"))]
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+fn variable<const C: UniFloatChoice>(value: f64) -> UniDual<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut value = UniFloat::<C>::from_f64(value);
+    value.copied();
+    UniDual::variable(&value)
+}
+
+fn constant<const C: UniFloatChoice>(value: f64) -> UniDual<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut value = UniFloat::<C>::from_f64(value);
+    value.copied();
+    UniDual::constant(&value)
+}
+
+/// (value, derivative) of `dual` (which needs .copied()), as f64.
+fn parts<const C: UniFloatChoice>(mut dual: UniDual<C>) -> (f64, f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    dual.copied();
+    (dual.value().to_f64_nearest(), dual.derivative().to_f64_nearest())
+}
+
+fn assert_near(actual: (f64, f64), expected: (f64, f64)) {
+    assert!((actual.0 - expected.0).abs() < 1e-5 && (actual.1 - expected.1).abs() < 1e-5,
+        "{:?} is not near {:?}", actual, expected);
+}
+
+fn assert_dual<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut x, mut two, mut three) = (variable::<C>(2.0), constant::<C>(2.0), constant::<C>(3.0));
+    x.copied();
+    two.copied();
+    three.copied();
+
+    // Exact: (3x + 2)' = 3, (x / 2)' = 1/2, (1 / x)' = -1/x^2, (x^3)' = 3x^2, and negation.
+    let mut product = &three * &x;
+    product.copied();
+    assert_eq!(parts(&product + &two), (8.0, 3.0));
+    assert_eq!(parts(&x / &two), (1.0, 0.5));
+    let mut quotient = &three / &x;
+    quotient.copied();
+    assert_eq!(parts(&two - &quotient), (0.5, 0.75));
+    assert_eq!(parts(x.powi(3)), (8.0, 12.0));
+    let mut negated = -&x;
+    negated.copied();
+    assert_eq!(parts(negated), (-2.0, -1.0));
+    assert_eq!(parts(negated.abs()), (2.0, 1.0));
+    assert_eq!(parts(three.sqrt()).1, 0.0);
+
+    // The chain rule, through functions.
+    let mut half = variable::<C>(0.5);
+    half.copied();
+    let (mut square, mut sine) = (&half * &half, half.sin());
+    square.copied();
+    sine.copied();
+    // (x^2 sin x)' = 2x sin x + x^2 cos x.
+    assert_near(parts(&square * &sine), (0.25 * 0.5f64.sin(), 0.5f64.sin() + 0.25 * 0.5f64.cos()));
+    let mut exp = half.exp();
+    assert_near(parts(exp.copied().ln()), (0.5, 1.0));
+    assert_near(parts(half.sqrt()), (0.5f64.sqrt(), 0.5 / 0.5f64.sqrt()));
+    assert_near(parts(half.cos()), (0.5f64.cos(), -0.5f64.sin()));
+    assert_near(parts(half.tan()), (0.5f64.tan(), 1.0 / (0.5f64.cos() * 0.5f64.cos())));
+    assert_near(parts(half.asin()), (0.5f64.asin(), 1.0 / 0.75f64.sqrt()));
+    assert_near(parts(half.acos()), (0.5f64.acos(), -1.0 / 0.75f64.sqrt()));
+    assert_near(parts(half.atan()), (0.5f64.atan(), 0.8));
+    assert_near(parts(half.tanh()), (0.5f64.tanh(), 1.0 - 0.5f64.tanh() * 0.5f64.tanh()));
+    assert_near(parts(half.atanh()), (0.5f64.atanh(), 1.0 / 0.75));
+    assert_near(parts(x.acosh()), (2.0f64.acosh(), 1.0 / 3.0f64.sqrt()));
+    assert_near(parts(x.log2()), (1.0, 1.0 / (2.0 * core::f64::consts::LN_2)));
+    assert_near(parts(x.cbrt()), (2.0f64.cbrt(), 1.0 / (3.0 * 4.0f64.cbrt())));
+    // x^x' = x^x (ln x + 1).
+    assert_near(parts(x.pow(&x)), (4.0, 4.0 * (core::f64::consts::LN_2 + 1.0)));
+    // d/dx atan2(3, x) = -3 / (x^2 + 9), and d/dx hypot(x, 3) = x / hypot.
+    assert_near(parts(three.atan2(&x)), (3.0f64.atan2(2.0), -3.0 / 13.0));
+    assert_near(parts(x.hypot(&three)), (13.0f64.sqrt(), 2.0 / 13.0f64.sqrt()));
+    assert_near(parts(x.mul_add(&x, &three)), (7.0, 4.0));
+
+    // Newton's method for x^2 = 2, from 1.
+    let mut root = variable::<C>(1.0);
+    root.copied();
+    for _ in 0..8 {
+        let mut square = &root * &root;
+        square.copied();
+        let mut residual = &square - &two;
+        residual.copied();
+        let mut step = *residual.value();
+        step.copied();
+        step /= square.derivative();
+        let mut next = *root.value();
+        next.copied();
+        next -= &step;
+        root = UniDual::variable(&next);
+        root.copied();
+    }
+    let mut expected = two.value().sqrt();
+    expected.copied();
+    assert!(root.value().ulp_distance(&expected).unwrap() <= 1);
+}
+
+#[test]
+fn dual() {
+    assert_dual::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_dual::<{ UniFloatChoice::F64 }>();
+        assert_dual::<{ UniFloatChoice::TwoFloat }>();
+        assert_dual::<{ MPFR_100_BITS }>();
+    }
+}
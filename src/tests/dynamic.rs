@@ -0,0 +1,110 @@
+use core::mem::MaybeUninit;
+use crate::{MpfrBounds, UniFloat, UniFloatBounds, UniFloatBoundsBase, UniFloatChoice, UniFloatBoundsToChoice, UniFloatDyn,
+    TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// `UniFloatDyn` with the choice `C` (but decided at runtime) gives the same results as `UniFloat<C>`.
+fn assert_same_as_unifloat<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut x_limbs, mut y_limbs): ([MpfrLimbPart; 4], [MpfrLimbPart; 4]) = ([MaybeUninit::uninit(); 4], [MaybeUninit::uninit(); 4]);
+    let mut x = UniFloatDyn::new_in(C, &mut x_limbs);
+    let mut y = UniFloatDyn::new_in(C, &mut y_limbs);
+    assert!(x.to_f64_nearest().is_nan());
+    assert_eq!(x.choice(), C);
+    x.set_f64(2.0);
+    x.sqrt_assign();
+    y.set_f64(3.0);
+    x *= &y;
+    x += &y;
+    x /= &y;
+    x -= &y;
+
+    let (mut two, mut three) = (UniFloat::<C>::from_f64(2.0), UniFloat::<C>::from_f64(3.0));
+    two.copied();
+    three.copied();
+    let mut expected = two.sqrt();
+    expected.copied();
+    expected *= &three;
+    expected += &three;
+    expected /= &three;
+    expected -= &three;
+    assert_eq!(x.to_f64_nearest(), expected.to_f64_nearest());
+
+    y.assign(&x);
+    assert_eq!(y.to_f64_nearest(), x.to_f64_nearest());
+}
+
+#[test]
+fn same_as_unifloat() {
+    assert_same_as_unifloat::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))]
+    {
+        assert_same_as_unifloat::<{ UniFloatChoice::F64 }>();
+        assert_same_as_unifloat::<{ UniFloatChoice::TwoFloat }>();
+        assert_same_as_unifloat::<{ MPFR_100_BITS }>();
+    }
+}
+
+/// Precision and exponent range read at runtime.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn runtime_bounds() {
+    // As if from a configuration file.
+    let precision = 100;
+    let choice = UniFloatBounds::<{ UniFloatBoundsBase::BINARY }>::new(precision, -10, 10).to_choice();
+    assert_eq!(choice, MPFR_100_BITS);
+    assert_eq!(UniFloatDyn::limbs_needed(choice), 2);
+    let (mut x_limbs, mut y_limbs): ([MpfrLimbPart; 2], [MpfrLimbPart; 2]) = ([MaybeUninit::uninit(); 2], [MaybeUninit::uninit(); 2]);
+    let (mut x, mut y) = (UniFloatDyn::new_in(choice, &mut x_limbs), UniFloatDyn::new_in(choice, &mut y_limbs));
+    x.set_f64(1.0);
+    y.set_f64(libm::ldexp(1.0, -80));
+    x += &y;
+    y.set_f64(1.0);
+    x -= &y;
+    assert_eq!(x.to_f64_nearest(), libm::ldexp(1.0, -80));
+
+    let half = UniFloatChoice::Mpfr { bounds: MpfrBounds::for_bounds(&UniFloatBounds::new(11, -13, 16)) };
+    let mut limbs: [MpfrLimbPart; 1] = [MaybeUninit::uninit(); 1];
+    let mut z = UniFloatDyn::new_in(half, &mut limbs);
+    z.set_f64(65520.0);
+    assert_eq!(z.to_f64_nearest(), f64::INFINITY);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+#[should_panic(expected = "UniFloatDyn operands need the same UniFloatChoice.")]
+fn different_choices() {
+    let (mut x_limbs, mut y_limbs): ([MpfrLimbPart; 0], [MpfrLimbPart; 0]) = ([], []);
+    let mut x = UniFloatDyn::new_in(UniFloatChoice::F64, &mut x_limbs);
+    let y = UniFloatDyn::new_in(UniFloatChoice::TwoFloat, &mut y_limbs);
+    x += &y;
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+#[should_panic(expected = "The limb buffer is too short for the UniFloatChoice. See UniFloatDyn::limbs_needed().")]
+fn short_buffer() {
+    let mut limbs: [MpfrLimbPart; 1] = [MaybeUninit::uninit(); 1];
+    UniFloatDyn::new_in(MPFR_100_BITS, &mut limbs);
+}
+
+#[test]
+#[cfg(all(feature = "alloc", not(feature = "f32_only")))]
+fn heap() {
+    let mut x = UniFloatDyn::new(MPFR_100_BITS);
+    let mut y = UniFloatDyn::new(MPFR_100_BITS);
+    x.set_f64(1.5);
+    y.set_f64(2.5);
+    x *= &y;
+    // Limbs on the heap stay in place when the instance moves.
+    let moved = [x];
+    assert_eq!(moved[0].to_f64_nearest(), 3.75);
+}
@@ -0,0 +1,120 @@
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// The result is as of the operator, and the result with the error add up to the exact value.
+fn assert_pair<const C: UniFloatChoice>(x: f64, y: f64, operation: &str) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut x, mut y) = (UniFloat::<C>::from_f64(x), UniFloat::<C>::from_f64(y));
+    x.copied();
+    y.copied();
+    let (mut result, mut error) = match operation {
+        "two_sum" => x.two_sum(&y),
+        "fast_two_sum" => x.fast_two_sum(&y),
+        _ => x.two_prod(&y)
+    };
+    result.copied();
+    error.copied();
+    let mut expected = x;
+    expected.copied();
+    let mut rest = if operation == "two_prod" {
+        expected *= &y;
+        let (mut factors, mut others) = ([x, -&result, -&error], [y, UniFloat::<C>::ONE, UniFloat::<C>::ONE]);
+        for value in factors.iter_mut().chain(others.iter_mut()) {
+            value.copied();
+        }
+        UniFloat::exact_dot(&factors, &others)
+    } else {
+        expected += &y;
+        let mut terms = [x, y, -&result, -&error];
+        for term in terms.iter_mut() {
+            term.copied();
+        }
+        UniFloat::exact_sum(&terms)
+    };
+    rest.copied();
+    assert!(result.eq(&expected), "{}", operation);
+    assert_eq!(rest.to_f64_nearest(), 0.0, "{}", operation);
+}
+
+fn assert_error_free<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(x, y) in [(1.0, 3.0 * libm::ldexp(1.0, -100)), (0.1, 0.2), (-0.7, 1e-20), (1.0 / 3.0, 3.0)].iter() {
+        assert_pair::<C>(x, y, "two_sum");
+        assert_pair::<C>(y, x, "two_sum");
+        assert_pair::<C>(x, y, "two_prod");
+        let (high, low) = if libm::fabs(x) >= libm::fabs(y) { (x, y) } else { (y, x) };
+        assert_pair::<C>(high, low, "fast_two_sum");
+    }
+
+    // 1 + 2^-100 rounds to 1 in f32, f64 and MPFR at 100 bits, and the error keeps the rest.
+    let mut one = UniFloat::<C>::ONE;
+    one.copied();
+    let mut tiny = UniFloat::<C>::from_f64(libm::ldexp(1.0, -100));
+    tiny.copied();
+    let (mut sum, mut error) = one.two_sum(&tiny);
+    sum.copied();
+    error.copied();
+    assert!(sum.eq(&one) && error.eq(&tiny) || error.to_f64_nearest() == 0.0);
+
+    let mut infinity = UniFloat::<C>::INFINITY;
+    infinity.copied();
+    let (mut sum, mut error) = infinity.two_sum(&one);
+    sum.copied();
+    error.copied();
+    assert!(!sum.is_finite() && error.to_f64_nearest() == 0.0);
+    let mut max = UniFloat::<C>::MAX;
+    max.copied();
+    let mut two = UniFloat::<C>::from_f64(2.0);
+    two.copied();
+    let (mut product, mut error) = max.two_prod(&two);
+    product.copied();
+    error.copied();
+    assert!(!product.is_finite() && error.to_f64_nearest() == 0.0);
+}
+
+#[test]
+fn error_free_transformations() {
+    assert_error_free::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_error_free::<{ UniFloatChoice::F64 }>();
+        assert_error_free::<{ UniFloatChoice::TwoFloat }>();
+        assert_error_free::<{ UniFloatChoice::QuadFloat }>();
+        assert_error_free::<{ MPFR_100_BITS }>();
+    }
+}
+
+/// The errors of f32 and f64 are exact, where the operator loses them.
+#[test]
+fn error_free_rounding_errors() {
+    let mut x = UniFloat::<{ UniFloatChoice::F32 }>::from_f64(1.0 + libm::ldexp(1.0, -12));
+    x.copied();
+    let (mut square, mut error) = x.two_prod(&x);
+    square.copied();
+    error.copied();
+    assert_eq!((square.to_f64_nearest(), error.to_f64_nearest()), (1.0 + libm::ldexp(1.0, -11), libm::ldexp(1.0, -24)));
+    #[cfg(not(feature = "f32_only"))] {
+        let (mut tenth, mut fifth) = (UniFloat::<{ UniFloatChoice::F64 }>::from_f64(0.1),
+            UniFloat::<{ UniFloatChoice::F64 }>::from_f64(0.2));
+        tenth.copied();
+        fifth.copied();
+        let (mut sum, mut error) = tenth.two_sum(&fifth);
+        sum.copied();
+        error.copied();
+        assert_eq!(sum.to_f64_nearest(), 0.1 + 0.2);
+        assert_eq!(error.to_f64_nearest(), -libm::ldexp(1.0, -55));
+    }
+}
@@ -0,0 +1,124 @@
+use core::f64::consts::{FRAC_PI_2, SQRT_2};
+#[cfg(not(feature = "f32_only"))]
+use gmp_mpfr_sys::mpfr;
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Each tuple: (function name, value, other value (only for "agm"), result). The results are exact,
+/// or π/2 (which gets compared after rounding it to the tested choice).
+const EXACT: [(&str, f64, f64, f64); 14] = [
+    ("agm", 4.0, 4.0, 4.0),
+    ("agm", 0.0, 1.0, 0.0),
+    ("agm", -1.0, 1.0, f64::NAN),
+    ("agm", f64::INFINITY, 1.0, f64::INFINITY),
+    ("agm", f64::INFINITY, 0.0, f64::NAN),
+    ("agm", 2.0, f64::NAN, f64::NAN),
+    ("elliptic_k", 0.0, 0.0, FRAC_PI_2),
+    ("elliptic_k", 1.0, 0.0, f64::INFINITY),
+    ("elliptic_k", 2.0, 0.0, f64::NAN),
+    ("elliptic_k", f64::NEG_INFINITY, 0.0, 0.0),
+    ("elliptic_e", 0.0, 0.0, FRAC_PI_2),
+    ("elliptic_e", 1.0, 0.0, 1.0),
+    ("elliptic_e", f64::NEG_INFINITY, 0.0, f64::INFINITY),
+    ("elliptic_e", f64::NAN, 0.0, f64::NAN),
+];
+
+/// Each tuple: (function name, value, other value (only for "agm"), result rounded to f64).
+/// agm(1, sqrt(2)) is the reciprocal of Gauss's constant.
+const APPROXIMATE: [(&str, f64, f64, f64); 3] = [
+    ("agm", 1.0, SQRT_2, 1.1981402347355922),
+    ("elliptic_k", 0.5, 0.0, 1.8540746773013719),
+    ("elliptic_e", 0.5, 0.0, 1.3506438810476755),
+];
+
+fn evaluate<const C: UniFloatChoice>(name: &str, value: f64, other: f64) -> f64 where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut float, mut other) = (UniFloat::<C>::from_f64(value), UniFloat::<C>::from_f64(other));
+    float.copied();
+    other.copied();
+    let mut result = match name {
+        "agm" => float.agm(&other),
+        "elliptic_k" => float.elliptic_k(),
+        _ => float.elliptic_e()
+    };
+    result.copied();
+    result.to_f64_nearest()
+}
+
+fn assert_elliptic<const C: UniFloatChoice>(tolerance: f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(name, value, other, expected) in EXACT.iter() {
+        let result = evaluate::<C>(name, value, other);
+        let mut expected = UniFloat::<C>::from_f64(expected);
+        expected.copied();
+        let expected = expected.to_f64_nearest();
+        assert!(result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan()),
+            "{}({}, {})", name, value, other);
+    }
+    for &(name, value, other, expected) in APPROXIMATE.iter() {
+        let result = evaluate::<C>(name, value, other);
+        assert!(libm::fabs(result - expected) <= tolerance * expected, "{}({}, {}): {}", name, value, other, result);
+    }
+}
+
+#[test]
+fn elliptic() {
+    assert_elliptic::<{ UniFloatChoice::F32 }>(1e-7);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_elliptic::<{ UniFloatChoice::F64 }>(1e-15);
+        assert_elliptic::<{ UniFloatChoice::TwoFloat }>(1e-15);
+        assert_elliptic::<{ MPFR_100_BITS }>(1e-15);
+    }
+}
+
+#[cfg(not(feature = "f32_only"))]
+const MPFR_200_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(200)
+};
+
+/// TwoFloat results have relative error below 1e-26, compared to MPFR at 200 bits. That includes
+/// m near 1 and large negative m.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn elliptic_twofloat_accuracy() {
+    type UniMpfr200bit = UniFloat<{ MPFR_200_BITS }>;
+    for &(name, value, other) in [("agm", 1.0, 3.0), ("agm", 1e-5, 7.0), ("agm", 1e300, 1e-300),
+        ("elliptic_k", 0.3, 0.0), ("elliptic_k", -5.0, 0.0), ("elliptic_k", 0.999999, 0.0), ("elliptic_k", -1e6, 0.0),
+        ("elliptic_e", 1e-10, 0.0), ("elliptic_e", 0.3, 0.0), ("elliptic_e", -5.0, 0.0), ("elliptic_e", 0.999999, 0.0)].iter() {
+        let (mut twofloat, mut twofloat_other) = (crate::UniTwoFloat::from_f64(value), crate::UniTwoFloat::from_f64(other));
+        twofloat.copied();
+        twofloat_other.copied();
+        let (mut reference, mut reference_other) = (UniMpfr200bit::from_f64(value), UniMpfr200bit::from_f64(other));
+        reference.copied();
+        reference_other.copied();
+        let (mut result, mut expected) = match name {
+            "agm" => (twofloat.agm(&twofloat_other), reference.agm(&reference_other)),
+            "elliptic_k" => (twofloat.elliptic_k(), reference.elliptic_k()),
+            _ => (twofloat.elliptic_e(), reference.elliptic_e())
+        };
+        result.copied();
+        expected.copied();
+        let error = unsafe {
+            let expected = expected.mpfr_mut();
+            let magnitude = mpfr::get_d(expected, mpfr::rnd_t::RNDN);
+            mpfr::sub_d(expected, expected, result.twofloats[0].hi(), mpfr::rnd_t::RNDN);
+            mpfr::sub_d(expected, expected, result.twofloats[0].lo(), mpfr::rnd_t::RNDN);
+            mpfr::get_d(expected, mpfr::rnd_t::RNDN) / magnitude
+        };
+        assert!(libm::fabs(error) < 1e-26, "{}({}, {}): {}", name, value, other, error);
+    }
+}
@@ -0,0 +1,77 @@
+#![cfg(not(feature = "f32_only"))]
+
+use crate::{ESCALATION_CHOICES, Escalation, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, NotConverged, UniFloat, UniFloatChoice, escalate,
+    f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// 2^-n.
+fn tiny(n: i32) -> f64 {
+    libm::ldexp(1.0, -n)
+}
+
+/// (2^100 + 1 + 2^-150) - 2^100 - 1, which needs 251 bits to be exact.
+struct Cancellation {
+    choices: [Option<UniFloatChoice>; 8],
+    attempts: usize,
+    never_converges: bool,
+}
+
+impl Cancellation {
+    fn new(never_converges: bool) -> Self {
+        Self { choices: [None; 8], attempts: 0, never_converges }
+    }
+}
+
+impl Escalation for Cancellation {
+    fn compute<const C: UniFloatChoice>(&mut self) -> UniFloat<C> where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+    {
+        self.choices[self.attempts] = Some(C);
+        self.attempts += 1;
+        let (mut big, mut one, mut small) = (UniFloat::<C>::from_f64(libm::ldexp(1.0, 100)),
+            UniFloat::<C>::from_f64(1.0), UniFloat::<C>::from_f64(tiny(150)));
+        big.copied();
+        one.copied();
+        small.copied();
+        let mut result = UniFloat::<C>::NAN;
+        result <<= &big;
+        result += &one;
+        result += &small;
+        result -= &big;
+        result -= &one;
+        result
+    }
+
+    fn converged<const C: UniFloatChoice>(&mut self, result: &UniFloat<C>) -> bool where
+    [f32; f32_parts_length(C)]: Sized,
+    [f64; f64_parts_length(C)]: Sized,
+    [TwoFloatPart; twofloat_parts_length(C)]: Sized,
+    [MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+    [MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+    {
+        !self.never_converges && result.to_f64_nearest() == tiny(150)
+    }
+}
+
+#[test]
+fn escalates_until_converged() {
+    let mut cancellation = Cancellation::new(false);
+    let mut result = escalate::<{ UniFloatChoice::F64 }, _>(&mut cancellation).unwrap();
+    result.copied();
+    assert_eq!(result.to_f64_nearest(), tiny(150));
+    // F64, TwoFloat and 128 bits lose 2^-150.
+    assert_eq!(cancellation.attempts, 4);
+    for (tried, &choice) in cancellation.choices[..4].iter().zip(ESCALATION_CHOICES.iter()) {
+        assert_eq!(*tried, Some(choice));
+    }
+}
+
+#[test]
+fn not_converged() {
+    let mut cancellation = Cancellation::new(true);
+    assert_eq!(escalate::<{ UniFloatChoice::F64 }, _>(&mut cancellation).unwrap_err(), NotConverged);
+    assert_eq!(cancellation.attempts, ESCALATION_CHOICES.len());
+}
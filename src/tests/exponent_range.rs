@@ -0,0 +1,245 @@
+#![cfg(not(feature = "f32_only"))]
+
+use gmp_mpfr_sys::mpfr;
+use crate::{Flags, MpfrBounds, Underflow, UniFloat, UniFloatBounds, UniFloatBoundsBase, UniFloatChoice, UniF32, UniF64,
+    UniTwoFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length,
+    mpfr_limb_parts_length};
+
+/// Like IEEE 754 binary16, but without subnormals: 11 bits, from 2^-14 to 65504.
+const HALF: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_bounds(&UniFloatBounds::new(11, -13, 16))
+};
+
+/// IEEE 754 binary16, with subnormals.
+const HALF_SUBNORMAL: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_bounds(&UniFloatBounds::new(11, -13, 16)).with_underflow(Underflow::Subnormal)
+};
+
+const HALF_FLUSH_TO_ZERO: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_bounds(&UniFloatBounds::new(11, -13, 16)).with_underflow(Underflow::FlushToZero)
+};
+
+type UniHalf = UniFloat<{ HALF }>;
+
+fn half(value: f64) -> f64 {
+    let mut float = UniHalf::from_f64(value);
+    float.copied();
+    float.to_f64_nearest()
+}
+
+fn half_subnormal(value: f64) -> (f64, bool) {
+    let mut float = UniFloat::<{ HALF_SUBNORMAL }>::from_f64(value);
+    float.copied();
+    (float.to_f64_nearest(), float.is_subnormal())
+}
+
+fn half_flush_to_zero(value: f64) -> f64 {
+    let mut float = UniFloat::<{ HALF_FLUSH_TO_ZERO }>::from_f64(value);
+    float.copied();
+    float.to_f64_nearest()
+}
+
+#[test]
+fn bounds() {
+    assert_eq!(HALF.bounds::<{ UniFloatBoundsBase::BINARY }>(), UniFloatBounds::new(11, -13, 16));
+    assert!(HALF.covers_exponents(-13, 16));
+    assert!(!HALF.covers_exponents(-14, 16) && !HALF.covers_exponents(-13, 17));
+    let default = UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(11) };
+    assert_eq!(default.bounds::<{ UniFloatBoundsBase::BINARY }>(), UniFloatBounds::new(11, isize::MIN, isize::MAX));
+    let same_space = HALF.most_precise_for_same_space().bounds::<{ UniFloatBoundsBase::BINARY }>();
+    assert_eq!((same_space.min_exponent, same_space.max_exponent), (-13, 16));
+}
+
+#[test]
+fn overflow() {
+    let emax = unsafe { mpfr::get_emax() };
+    assert_eq!(half(65504.0), 65504.0);
+    assert_eq!(half(65519.0), 65504.0);
+    assert_eq!(half(65520.0), f64::INFINITY);
+    assert_eq!(half(-1e10), f64::NEG_INFINITY);
+
+    let mut x = UniHalf::from_f64(60000.0);
+    x.copied();
+    let mut sum = &x + &x;
+    sum.copied();
+    assert!(sum.is_infinite() && sum.is_sign_positive());
+
+    let (mut max, mut min) = (UniHalf::MAX, UniHalf::MIN);
+    max.copied();
+    min.copied();
+    assert_eq!((max.to_f64_nearest(), min.to_f64_nearest()), (65504.0, -65504.0));
+    let mut above = max.next_up();
+    above.copied();
+    assert!(above.is_infinite());
+    // MPFR's own range is back to what it was.
+    assert_eq!(unsafe { mpfr::get_emax() }, emax);
+}
+
+#[test]
+fn underflow() {
+    let emin = unsafe { mpfr::get_emin() };
+    let least = libm::ldexp(1.0, -14);
+    assert_eq!(half(least), least);
+    // No subnormals: values below the range round to zero or to the least value.
+    assert_eq!(half(least * 0.75), least);
+    assert_eq!(half(least / 4.0), 0.0);
+    assert!(half(-least / 4.0).is_sign_negative());
+
+    let mut x = UniHalf::from_f64(least);
+    x.copied();
+    let mut product = &x * &x;
+    product.copied();
+    assert_eq!(product.to_f64_nearest(), 0.0);
+    assert_eq!(unsafe { mpfr::get_emin() }, emin);
+}
+
+#[test]
+fn subnormals() {
+    let least_normal = libm::ldexp(1.0, -14);
+    let least = libm::ldexp(1.0, -24);
+    assert_eq!(half_subnormal(least_normal), (least_normal, false));
+    assert_eq!(half_subnormal(least_normal * 0.75), (least_normal * 0.75, true));
+    assert_eq!(half_subnormal(least), (least, true));
+    // Subnormals have 10 bits at most: 1 + 2^-10 is rounded (to even).
+    assert_eq!(half_subnormal(libm::ldexp(1.0 + libm::ldexp(1.0, -10), -15)), (libm::ldexp(1.0, -15), true));
+    // Halfway to the least subnormal rounds to even (zero).
+    assert_eq!(half_subnormal(least / 2.0).0, 0.0);
+    assert_eq!(half_subnormal(least * 0.75).0, least);
+
+    let (mut x, mut y) = (UniFloat::<{ HALF_SUBNORMAL }>::from_f64(least_normal), UniFloat::<{ HALF_SUBNORMAL }>::from_f64(0.125));
+    x.copied();
+    y.copied();
+    let mut product = &x * &y;
+    product.copied();
+    assert!(product.is_subnormal());
+    assert_eq!(product.to_f64_nearest(), least_normal / 8.0);
+    let mut flushed = product.flushed_to_zero();
+    flushed.copied();
+    assert_eq!(flushed.to_f64_nearest(), 0.0);
+}
+
+#[test]
+fn flush_to_zero() {
+    let least_normal = libm::ldexp(1.0, -14);
+    assert_eq!(half_flush_to_zero(least_normal), least_normal);
+    assert_eq!(half_flush_to_zero(least_normal * 0.75), 0.0);
+    assert_eq!(half_flush_to_zero(-least_normal * 0.75).to_bits(), (-0.0f64).to_bits());
+    // Rounded up to the least normal value, so that's not flushed.
+    assert_eq!(half_flush_to_zero(least_normal * (1.0 - libm::ldexp(1.0, -13))), least_normal);
+    Flags::clear();
+    half_flush_to_zero(least_normal / 2.0);
+    let flags = Flags::read();
+    assert!(flags.underflow() && flags.inexact());
+}
+
+#[test]
+fn parse_limited() {
+    let parsed = |s: &str| s.parse::<UniHalf>().unwrap().to_f64_nearest();
+    assert_eq!(parsed("65504"), 65504.0);
+    assert_eq!(parsed("65520"), f64::INFINITY);
+    assert_eq!(parsed("-1e10"), f64::NEG_INFINITY);
+    assert_eq!(parsed("1e-6"), 0.0);
+    assert_eq!(parsed("4.6e-5"), libm::ldexp(1.0, -14));
+    let least = libm::ldexp(1.0, -24);
+    let subnormal = "6e-8".parse::<UniFloat<{ HALF_SUBNORMAL }>>().unwrap();
+    assert_eq!((subnormal.to_f64_nearest(), subnormal.is_subnormal()), (least, true));
+    assert_eq!("4.6e-5".parse::<UniFloat<{ HALF_FLUSH_TO_ZERO }>>().unwrap().to_f64_nearest(), 0.0);
+
+    assert_eq!(UniHalf::from_hex_str("0x1.ffcp15").unwrap().to_f64_nearest(), 65504.0);
+    assert_eq!(UniHalf::from_hex_str("0x1p16").unwrap().to_f64_nearest(), f64::INFINITY);
+    assert_eq!(UniHalf::from_hex_str("-0x1p-20").unwrap().to_f64_nearest().to_bits(), (-0.0f64).to_bits());
+    assert_eq!(UniHalf::from_str_radix("-1@30", 2).unwrap().to_f64_nearest(), f64::NEG_INFINITY);
+}
+
+#[test]
+fn primitives_flushed_to_zero() {
+    let mut f32_subnormal = UniF32::from_f64(-1e-40);
+    f32_subnormal.copied();
+    assert!(f32_subnormal.is_subnormal());
+    let mut flushed = f32_subnormal.flushed_to_zero();
+    flushed.copied();
+    assert_eq!(flushed.f32s[0].to_bits(), (-0.0f32).to_bits());
+
+    let mut f64_normal = UniF64::from_f64(f64::MIN_POSITIVE);
+    f64_normal.copied();
+    let mut kept = f64_normal.flushed_to_zero();
+    kept.copied();
+    assert_eq!(kept.f64s[0], f64::MIN_POSITIVE);
+
+    let mut twofloat_subnormal = UniTwoFloat::from_f64(f64::MIN_POSITIVE / 4.0);
+    twofloat_subnormal.copied();
+    let mut flushed = twofloat_subnormal.flushed_to_zero();
+    flushed.copied();
+    assert_eq!(flushed.twofloats[0].hi(), 0.0);
+}
+
+/// The sum of 0.1 (rounded for choice `C`) one hundred times, in choice `C`.
+fn sum_of_tenths<const C: UniFloatChoice>() -> f64 where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut sum, mut tenth) = (UniFloat::<C>::ZERO, UniFloat::<C>::from_f64(0.1));
+    sum.copied();
+    tenth.copied();
+    for _ in 0..100 {
+        sum += &tenth;
+    }
+    sum.to_f64_nearest()
+}
+
+#[test]
+fn half_precision_choices() {
+    assert_eq!(UniFloatChoice::F16, HALF_SUBNORMAL);
+    assert_eq!(UniFloatChoice::BF16.bounds::<{ UniFloatBoundsBase::BINARY }>(),
+        UniFloatBounds::new(8, f32::MIN_EXP as isize, f32::MAX_EXP as isize));
+
+    // The same generic code, at 8, 11, 24 and 256 bits.
+    assert_eq!(sum_of_tenths::<{ UniFloatChoice::BF16 }>(), 10.0625);
+    assert_eq!(sum_of_tenths::<{ UniFloatChoice::F16 }>(), 10.078125);
+    assert_eq!(sum_of_tenths::<{ UniFloatChoice::F32 }>(), 10.000001907348633);
+    let mpfr_256_bits = sum_of_tenths::<{ UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(256) } }>();
+    assert_eq!(mpfr_256_bits, 10.0);
+
+    let mut large = UniFloat::<{ UniFloatChoice::BF16 }>::from_f64(1e38);
+    large.copied();
+    assert_eq!(large.to_f64_nearest(), 99_692_099_683_868_690_467_785_529_521_025_843_200.0);
+    let mut subnormal = UniFloat::<{ UniFloatChoice::F16 }>::from_f64(libm::ldexp(1.0, -24));
+    assert!(subnormal.copied().is_subnormal());
+}
+
+#[test]
+fn x87_extended_choice() {
+    type UniF80 = UniFloat<{ UniFloatChoice::F80 }>;
+    assert_eq!(UniFloatChoice::F80.bounds::<{ UniFloatBoundsBase::BINARY }>(), UniFloatBounds::new(64, -16381, 16384));
+
+    // 0.1 rounded to 64 bits, added up a hundred times: 10 + 2^-59.
+    let (mut sum, mut tenth, mut ten) = (UniF80::ZERO, UniF80::ONE, UniF80::from_f64(10.0));
+    sum.copied();
+    tenth.copied();
+    ten.copied();
+    tenth /= &ten;
+    for _ in 0..100 {
+        sum += &tenth;
+    }
+    sum -= &ten;
+    assert_eq!(sum.to_f64_nearest(), libm::ldexp(1.0, -59));
+
+    let (mut one, mut zero, mut max) = (UniF80::ONE, UniF80::ZERO, UniF80::MAX);
+    one.copied();
+    zero.copied();
+    max.copied();
+    let mut least = one.ldexp(-16445);
+    assert!(least.copied().is_subnormal());
+    // Half of it is a tie, to even (zero).
+    let mut half_of_least = least.ldexp(-1);
+    half_of_least.copied();
+    assert!(half_of_least.eq(&zero));
+    let mut scaled = max.ldexp(-16384);
+    scaled.copied();
+    assert_eq!(scaled.to_f64_nearest(), 1.0);
+    let mut overflow = max.ldexp(1);
+    assert!(overflow.copied().is_infinite());
+}
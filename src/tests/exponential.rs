@@ -0,0 +1,88 @@
+#[cfg(not(feature = "f32_only"))]
+use gmp_mpfr_sys::mpfr;
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Each tuple: (function name, value, result). The results are exact.
+const EXPONENTIALS: [(&str, f64, f64); 12] = [
+    ("exp", 0.0, 1.0),
+    ("exp", f64::NEG_INFINITY, 0.0),
+    ("exp", f64::INFINITY, f64::INFINITY),
+    ("exp", f64::NAN, f64::NAN),
+    ("exp2", 10.0, 1024.0),
+    ("exp2", -1.0, 0.5),
+    ("exp10", 2.0, 100.0),
+    ("exp10", -0.0, 1.0),
+    ("exp10", f64::NEG_INFINITY, 0.0),
+    ("exp_m1", -0.0, -0.0),
+    ("exp_m1", 0.0, 0.0),
+    ("exp_m1", f64::NEG_INFINITY, -1.0),
+];
+
+fn assert_exponentials<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(name, value, expected) in EXPONENTIALS.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        let mut result = match name {
+            "exp" => float.exp(),
+            "exp2" => float.exp2(),
+            "exp10" => float.exp10(),
+            _ => float.exp_m1()
+        };
+        result.copied();
+        let result = result.to_f64_nearest();
+        assert!(result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan()), "{}({})", name, value);
+    }
+}
+
+#[test]
+fn exponentials() {
+    assert_exponentials::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_exponentials::<{ UniFloatChoice::F64 }>();
+        assert_exponentials::<{ UniFloatChoice::TwoFloat }>();
+        assert_exponentials::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[cfg(not(feature = "f32_only"))]
+const MPFR_200_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(200)
+};
+
+/// TwoFloat results have relative error below 1e-26, compared to MPFR at 200 bits.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn exponentials_twofloat_accuracy() {
+    type UniMpfr200bit = UniFloat<{ MPFR_200_BITS }>;
+    for &value in [1.0, -0.5, 10.0, 1e-10, -1e-20, 123.456, -70.0].iter() {
+        let mut twofloat = crate::UniTwoFloat::from_f64(value);
+        twofloat.copied();
+        let mut reference = UniMpfr200bit::from_f64(value);
+        reference.copied();
+        for &(name, mut result, mut expected) in [("exp", twofloat.exp(), reference.exp()),
+            ("exp2", twofloat.exp2(), reference.exp2()), ("exp10", twofloat.exp10(), reference.exp10()),
+            ("exp_m1", twofloat.exp_m1(), reference.exp_m1())].iter() {
+            result.copied();
+            expected.copied();
+            let error = unsafe {
+                let expected = expected.mpfr_mut();
+                let magnitude = mpfr::get_d(expected, mpfr::rnd_t::RNDN);
+                mpfr::sub_d(expected, expected, result.twofloats[0].hi(), mpfr::rnd_t::RNDN);
+                mpfr::sub_d(expected, expected, result.twofloats[0].lo(), mpfr::rnd_t::RNDN);
+                mpfr::get_d(expected, mpfr::rnd_t::RNDN) / magnitude
+            };
+            assert!(libm::fabs(error) < 1e-26, "{}({}): {}", name, value, error);
+        }
+    }
+}
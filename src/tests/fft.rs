@@ -0,0 +1,158 @@
+use core::f64::consts::PI;
+use crate::{MpfrBounds, UniComplex, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+fn complex<const C: UniFloatChoice>(re: f64, im: f64) -> UniComplex<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut re, mut im) = (UniFloat::<C>::from_f64(re), UniFloat::<C>::from_f64(im));
+    re.copied();
+    im.copied();
+    UniComplex::new(&re, &im)
+}
+
+fn parts<const C: UniFloatChoice>(z: &UniComplex<C>) -> (f64, f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    (z.re().to_f64_nearest(), z.im().to_f64_nearest())
+}
+
+/// x[j], to be transformed.
+fn sample(j: usize) -> (f64, f64) {
+    ((j + 1) as f64, (j % 3) as f64 - 1.0)
+}
+
+/// X[k] of `sample()` of length `n`, by the definition (in f64).
+fn dft(n: usize, k: usize) -> (f64, f64) {
+    (0..n).fold((0.0, 0.0), |(re, im), j| {
+        let (x, y) = sample(j);
+        let angle = -2.0 * PI * ((j * k) % n) as f64 / n as f64;
+        let (sine, cosine) = (libm::sin(angle), libm::cos(angle));
+        (re + x * cosine - y * sine, im + x * sine + y * cosine)
+    })
+}
+
+fn assert_near(actual: (f64, f64), expected: (f64, f64), tolerance: f64) {
+    assert!((actual.0 - expected.0).abs() < tolerance && (actual.1 - expected.1).abs() < tolerance,
+        "{:?} is not near {:?}", actual, expected);
+}
+
+/// `fft()` and `ifft()` (or the mixed-radix ones) of `sample()`, of length `N`.
+fn assert_transform<const C: UniFloatChoice, const N: usize>(mixed: bool, tolerance: f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut values = [complex::<C>(0.0, 0.0); N];
+    for (j, value) in values.iter_mut().enumerate() {
+        let (re, im) = sample(j);
+        *value = complex::<C>(re, im);
+        value.copied();
+    }
+    let mut scratch = [complex::<C>(0.0, 0.0); N];
+    for value in scratch.iter_mut() {
+        value.copied();
+    }
+    if mixed {
+        UniComplex::fft_mixed(&mut values, &mut scratch);
+    } else {
+        UniComplex::fft(&mut values);
+    }
+    for (k, value) in values.iter().enumerate() {
+        assert_near(parts(value), dft(N, k), tolerance);
+    }
+    if mixed {
+        UniComplex::ifft_mixed(&mut values, &mut scratch);
+    } else {
+        UniComplex::ifft(&mut values);
+    }
+    for (j, value) in values.iter().enumerate() {
+        assert_near(parts(value), sample(j), tolerance);
+    }
+}
+
+fn assert_fft<const C: UniFloatChoice>(tolerance: f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    assert_transform::<C, 1>(false, tolerance);
+    assert_transform::<C, 8>(false, tolerance);
+    assert_transform::<C, 16>(false, tolerance);
+    assert_transform::<C, 8>(true, tolerance);
+    assert_transform::<C, 12>(true, tolerance);
+    // Prime: a plain DFT.
+    assert_transform::<C, 7>(true, tolerance);
+
+    // An impulse transforms into ones, exactly.
+    let mut values = [complex::<C>(0.0, 0.0); 6];
+    for value in values.iter_mut() {
+        value.copied();
+    }
+    values[0] = complex::<C>(1.0, 0.0);
+    values[0].copied();
+    let mut scratch = values;
+    for value in scratch.iter_mut() {
+        value.copied();
+    }
+    UniComplex::fft_mixed(&mut values, &mut scratch);
+    for value in values.iter() {
+        assert_eq!(parts(value), (1.0, 0.0));
+    }
+    // And ones into an impulse.
+    UniComplex::fft(&mut values[..4]);
+    for (k, value) in values[..4].iter().enumerate() {
+        assert_eq!(parts(value), (if k == 0 { 4.0 } else { 0.0 }, 0.0));
+    }
+    UniComplex::fft(&mut values[..0]);
+}
+
+#[test]
+fn fft_matches_dft() {
+    assert_fft::<{ UniFloatChoice::F32 }>(1e-4);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_fft::<{ UniFloatChoice::F64 }>(1e-10);
+        assert_fft::<{ UniFloatChoice::TwoFloat }>(1e-10);
+        assert_fft::<{ MPFR_100_BITS }>(1e-10);
+    }
+}
+
+#[test]
+#[should_panic(expected="fft() and ifft() need a length that's a power of two.")]
+fn fft_needs_power_of_two() {
+    let mut values = [complex::<{ UniFloatChoice::F32 }>(0.0, 0.0); 6];
+    for value in values.iter_mut() {
+        value.copied();
+    }
+    UniComplex::fft(&mut values);
+}
+
+#[test]
+#[should_panic(expected="fft_mixed() and ifft_mixed() need scratch at least as long as values.")]
+fn fft_mixed_needs_scratch() {
+    let mut values = [complex::<{ UniFloatChoice::F32 }>(0.0, 0.0); 6];
+    for value in values.iter_mut() {
+        value.copied();
+    }
+    let mut scratch = values;
+    for value in scratch.iter_mut() {
+        value.copied();
+    }
+    UniComplex::fft_mixed(&mut values, &mut scratch[..5]);
+}
@@ -0,0 +1,139 @@
+#![cfg(not(feature = "f32_only"))]
+
+use crate::{FixedOverflow, Flags, MpfrBounds, UniFloat, UniFloatChoice};
+
+/// Q16.16: 16 bits (with the sign) before the binary point, 16 after it.
+const Q16_16: UniFloatChoice = UniFloatChoice::fixed(16, 16, FixedOverflow::Saturating);
+/// Like i8.
+const WRAPPING_I8: UniFloatChoice = UniFloatChoice::fixed(8, 0, FixedOverflow::Wrapping);
+
+type UniQ16 = UniFloat<{ Q16_16 }>;
+type UniI8 = UniFloat<{ WRAPPING_I8 }>;
+
+fn q16(value: f64) -> UniQ16 {
+    let mut x = UniQ16::from_f64(value);
+    x.copied();
+    x
+}
+
+fn i8(value: f64) -> UniI8 {
+    let mut x = UniI8::from_f64(value);
+    x.copied();
+    x
+}
+
+fn step(n: i32) -> f64 {
+    libm::ldexp(n as f64, -16)
+}
+
+#[test]
+fn fixed_rounds_to_its_grid() {
+    let mut third = &q16(1.0) / &q16(3.0);
+    third.copied();
+    assert_eq!(third.to_f64_nearest(), step(21845));
+    let mut product = &q16(1000.5) * &q16(3.0);
+    product.copied();
+    assert_eq!(product.to_f64_nearest(), 3001.5);
+    // Half a step is a tie, to even (zero). Three quarters of a step round up.
+    assert_eq!(q16(libm::ldexp(1.0, -17)).to_f64_nearest(), 0.0);
+    assert_eq!(q16(libm::ldexp(3.0, -18)).to_f64_nearest(), step(1));
+    assert_eq!(q16(step(5) + libm::ldexp(1.0, -17)).to_f64_nearest(), step(6));
+    // Within the grid, yet more than 32 significant bits.
+    let mut sum = &q16(-30000.0) + &q16(step(-3));
+    sum.copied();
+    assert_eq!(sum.to_f64_nearest(), -30000.0 - step(3));
+    // No -0.
+    let mut zero = &q16(-1.0) * &q16(0.0);
+    zero.copied();
+    assert!(zero.is_sign_positive());
+}
+
+#[test]
+fn fixed_saturates_or_wraps() {
+    let (max, min) = (32768.0 - step(1), -32768.0);
+    Flags::clear();
+    let mut sum = &q16(30000.0) + &q16(30000.0);
+    sum.copied();
+    assert_eq!(sum.to_f64_nearest(), max);
+    assert!(Flags::read().overflow());
+    let mut difference = &q16(-30000.0) - &q16(30000.0);
+    difference.copied();
+    assert_eq!(difference.to_f64_nearest(), min);
+    let mut quotient = &q16(1.0) / &q16(0.0);
+    quotient.copied();
+    assert_eq!(quotient.to_f64_nearest(), max);
+    let mut last = q16(max).next_up();
+    last.copied();
+    assert_eq!(last.to_f64_nearest(), max);
+
+    let mut wrapped = &i8(100.0) + &i8(100.0);
+    wrapped.copied();
+    assert_eq!(wrapped.to_f64_nearest(), -56.0);
+    let mut wrapped = &i8(-100.0) - &i8(100.0);
+    wrapped.copied();
+    assert_eq!(wrapped.to_f64_nearest(), 56.0);
+    let mut wrapped = &i8(127.0) + &i8(1.0);
+    wrapped.copied();
+    assert_eq!(wrapped.to_f64_nearest(), -128.0);
+    let mut wrapped = i8(127.0).next_up();
+    wrapped.copied();
+    assert_eq!(wrapped.to_f64_nearest(), -128.0);
+}
+
+#[test]
+fn fixed_constants_and_steps() {
+    assert_eq!(UniQ16::MAX.to_f64_nearest(), 32768.0 - step(1));
+    assert_eq!(UniQ16::MIN.to_f64_nearest(), -32768.0 + step(1));
+    assert_eq!(UniQ16::EPSILON.to_f64_nearest(), step(1));
+    assert_eq!(UniQ16::MIN_POSITIVE.to_f64_nearest(), step(1));
+    assert_eq!(UniI8::MAX.to_f64_nearest(), 127.0);
+    assert_eq!(UniI8::EPSILON.to_f64_nearest(), 1.0);
+    // 2^(64 + 1 - 1) - 1 needs the lower limb to be clear.
+    assert_eq!(UniFloat::<{ UniFloatChoice::fixed(64, 1, FixedOverflow::Saturating) }>::MAX.to_f64_nearest(),
+        libm::ldexp(1.0, 63));
+
+    let one = q16(1.0);
+    let mut up = one.next_up();
+    up.copied();
+    assert_eq!(up.to_f64_nearest(), 1.0 + step(1));
+    let mut down = q16(0.0).next_down();
+    down.copied();
+    assert_eq!(down.to_f64_nearest(), step(-1));
+    let mut toward = one.next_toward(&q16(-5.0));
+    toward.copied();
+    assert_eq!(toward.to_f64_nearest(), 1.0 - step(1));
+    let mut ulp = q16(12345.0).ulp();
+    ulp.copied();
+    assert_eq!(ulp.to_f64_nearest(), step(1));
+    assert_eq!(one.ulp_distance(&q16(2.0)), Some(1 << 16));
+    assert_eq!(q16(-1.0).ulp_distance(&q16(step(3))), Some((1 << 16) + 3));
+    assert_eq!(q16(-32768.0).ulp_distance(&q16(0.0)), Some(1 << 31));
+}
+
+#[test]
+fn fixed_parse() {
+    let parse = |s: &str| s.parse::<UniQ16>().unwrap().to_f64_nearest();
+    assert_eq!(parse("0.1"), step(6554));
+    assert_eq!(parse("-1000.25"), -1000.25);
+    // A third of a step rounds to zero, with no -0.
+    let tiny: UniQ16 = "-5e-6".parse().unwrap();
+    assert_eq!(tiny.to_f64_nearest(), 0.0);
+    assert!(tiny.is_sign_positive());
+    assert_eq!(parse("1e10"), 32768.0 - step(1));
+    assert_eq!(parse("-1e10"), -32768.0);
+    assert_eq!(UniQ16::from_hex_str("0x1.00008p0").unwrap().to_f64_nearest(), 1.0);
+    assert_eq!(UniQ16::from_hex_str("0x1.00018p0").unwrap().to_f64_nearest(), 1.0 + step(2));
+
+    let parse = |s: &str| s.parse::<UniI8>().unwrap().to_f64_nearest();
+    assert_eq!(parse("2.5"), 2.0);
+    assert_eq!(parse("200"), -56.0);
+    assert_eq!(UniI8::from_str_radix("-10000001", 2).unwrap().to_f64_nearest(), 127.0);
+}
+
+#[test]
+fn fixed_choice() {
+    assert_eq!(Q16_16, UniFloatChoice::Fixed { int_bits: 16, frac_bits: 16, overflow: FixedOverflow::Saturating });
+    assert_eq!(Q16_16.precision_bits(), 32);
+    assert_eq!(Q16_16.promoted(&UniFloatChoice::F32), Q16_16);
+    assert_eq!(Q16_16.promoted(&UniFloatChoice::F64), UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(53) });
+}
@@ -0,0 +1,64 @@
+use crate::{Flags, MpfrBounds, RoundingMode, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Flags after `op(x, y)`, cleared before. Other tests may raise flags at the same time (the
+/// emulated ones are shared by all threads), so tests here only check flags that get raised.
+fn flags_after<const C: UniFloatChoice>(x: f64, y: f64, op: impl FnOnce(&UniFloat<C>, &UniFloat<C>) -> UniFloat<C>) -> Flags where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut x, mut y) = (UniFloat::<C>::from_f64(x), UniFloat::<C>::from_f64(y));
+    x.copied();
+    y.copied();
+    Flags::clear();
+    let mut result = op(&x, &y);
+    result.copied();
+    Flags::read()
+}
+
+fn assert_flags<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let divide = |x: &UniFloat<C>, y: &UniFloat<C>| {
+        let mut quotient = UniFloat::<C>::NAN;
+        quotient <<= x;
+        quotient /= y;
+        quotient.released()
+    };
+    assert!(flags_after::<C>(1.0, 0.0, divide).divide_by_zero());
+    assert!(flags_after::<C>(-1.0, 0.0, |x, _| x.ln()).invalid());
+    assert!(flags_after::<C>(0.0, 0.0, |x, _| x.ln()).divide_by_zero());
+    assert!(flags_after::<C>(0.0, 0.0, divide).invalid());
+    assert!(flags_after::<C>(-4.0, 0.0, |x, _| x.sqrt()).invalid());
+    assert!(flags_after::<C>(f64::INFINITY, f64::INFINITY, |x, y| x.sub_round(y, RoundingMode::Nearest).unwrap()).invalid());
+}
+
+#[test]
+fn flags() {
+    assert_flags::<{ UniFloatChoice::F32 }>();
+    let overflow = flags_after::<{ UniFloatChoice::F32 }>(1e30, 1e30, |x, y| x.mul_round(y, RoundingMode::Nearest).unwrap());
+    assert!(overflow.overflow() && overflow.inexact() && overflow.any());
+    assert!(flags_after::<{ UniFloatChoice::F32 }>(1e-30, 1e-10, |x, y| x.mul_round(y, RoundingMode::Nearest).unwrap()).underflow());
+    #[cfg(not(feature = "f32_only"))] {
+        assert_flags::<{ UniFloatChoice::F64 }>();
+        assert_flags::<{ UniFloatChoice::TwoFloat }>();
+        assert_flags::<{ MPFR_100_BITS }>();
+
+        let overflow = flags_after::<{ UniFloatChoice::F64 }>(710.0, 0.0, |x, _| x.exp());
+        assert!(overflow.overflow() && overflow.inexact());
+        assert!(flags_after::<{ UniFloatChoice::F64 }>(1e-300, 1e-10, |x, y| x.mul_round(y, RoundingMode::Nearest).unwrap()).underflow());
+        assert!(flags_after::<{ UniFloatChoice::TwoFloat }>(710.0, 0.0, |x, _| x.exp()).overflow());
+        assert!(flags_after::<{ MPFR_100_BITS }>(1.0, 3.0, |x, y| x.div_round(y, RoundingMode::Nearest).unwrap()).inexact());
+    }
+}
@@ -0,0 +1,164 @@
+extern crate std;
+
+use std::{format, str};
+use crate::{FormatOptions, MpfrBounds, Notation, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Values that are exact in all choices, and that have short decimal representations. All
+/// choices must format them the same way as f64.
+const VALUES: [f64; 9] = [1.5, -0.0625, 100.0, 1234.5, 0.0078125, -1.5e9, 0.0, f64::INFINITY, f64::NAN];
+
+fn assert_same_as_f64<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &value in VALUES.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        assert_eq!(format!("{}", float), format!("{}", value));
+        assert_eq!(format!("{:e}", float), format!("{:e}", value));
+        assert_eq!(format!("{:E}", float), format!("{:E}", value));
+        assert_eq!(format!("{:.3}", float), format!("{:.3}", value));
+        assert_eq!(format!("{:.0}", float), format!("{:.0}", value));
+        assert_eq!(format!("{:+.2e}", float), format!("{:+.2e}", value));
+        assert_eq!(format!("{:>12}", float), format!("{:>12}", value));
+        assert_eq!(format!("{:*<12.1}", float), format!("{:*<12.1}", value));
+        assert_eq!(format!("{:^12}", float), format!("{:^12}", value));
+        assert_eq!(format!("{:012}", float), format!("{:012}", value));
+    }
+}
+
+#[test]
+fn format_same_as_f64() {
+    assert_same_as_f64::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_same_as_f64::<{ UniFloatChoice::F64 }>();
+        assert_same_as_f64::<{ UniFloatChoice::TwoFloat }>();
+        assert_same_as_f64::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn format_shortest_round_trip() {
+    let mut tenth: UniFloat<{ MPFR_100_BITS }> = "0.1".parse().unwrap();
+    tenth.copied();
+    assert_eq!(format!("{}", tenth), "0.1");
+    assert_eq!(format!("{:.40}", tenth), "0.1000000000000000000000000000000197215226");
+
+    let mut third = UniFloat::<{ MPFR_100_BITS }>::from_f64(1.0);
+    third.copied();
+    let mut three = UniFloat::<{ MPFR_100_BITS }>::from_f64(3.0);
+    three.copied();
+    third /= &three;
+    // 100 bits need 31 digits.
+    assert_eq!(format!("{}", third), "0.3333333333333333333333333333335");
+    assert_eq!(format!("{:e}", third), "3.333333333333333333333333333335e-1");
+    let mut parsed: UniFloat<{ MPFR_100_BITS }> = format!("{}", third).parse().unwrap();
+    parsed.copied();
+    assert!(parsed == third);
+
+    // 1 + 2^-60 needs more digits than f64.
+    let mut twofloat: crate::UniTwoFloat = "1.000000000000000000867361737988403547205962240695953369140625".parse().unwrap();
+    twofloat.copied();
+    let mut parsed: crate::UniTwoFloat = format!("{}", twofloat).parse().unwrap();
+    parsed.copied();
+    assert!(parsed == twofloat);
+    assert!(format!("{}", twofloat).len() < 40);
+
+    // Rounding at the precision may carry into a new digit.
+    let mut float = UniFloat::<{ MPFR_100_BITS }>::from_f64(9.96);
+    float.copied();
+    assert_eq!(format!("{:.1}", float), "10.0");
+    assert_eq!(format!("{:.1e}", float), "1.0e1");
+    let mut small = UniFloat::<{ MPFR_100_BITS }>::from_f64(-0.0007);
+    small.copied();
+    assert_eq!(format!("{:.3}", small), "-0.001");
+    assert_eq!(format!("{:.2}", small), "-0.00");
+}
+
+const NOTATIONS: [Notation; 3] = [Notation::Fixed, Notation::LowerExp, Notation::UpperExp];
+
+/// `write_to()` into a big enough buffer, as a `String`.
+fn written<const C: UniFloatChoice>(float: &UniFloat<C>, options: FormatOptions) -> std::string::String where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut buffer = [0u8; 2048];
+    let length = float.write_to(&mut buffer, options).unwrap();
+    str::from_utf8(&buffer[..length]).unwrap().into()
+}
+
+fn assert_write_to_same_as_format<const C: UniFloatChoice>(values: &[f64]) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &value in values.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        for &notation in NOTATIONS.iter() {
+            for &precision in [None, Some(0), Some(3), Some(25)].iter() {
+                for &sign_plus in [false, true].iter() {
+                    let options = FormatOptions { notation, precision, sign_plus };
+                    let expected = match (notation, precision, sign_plus) {
+                        (Notation::Fixed, None, false) => format!("{}", float),
+                        (Notation::Fixed, None, true) => format!("{:+}", float),
+                        (Notation::Fixed, Some(p), false) => format!("{:.*}", p, float),
+                        (Notation::Fixed, Some(p), true) => format!("{:+.*}", p, float),
+                        (Notation::LowerExp, None, false) => format!("{:e}", float),
+                        (Notation::LowerExp, None, true) => format!("{:+e}", float),
+                        (Notation::LowerExp, Some(p), false) => format!("{:.*e}", p, float),
+                        (Notation::LowerExp, Some(p), true) => format!("{:+.*e}", p, float),
+                        (Notation::UpperExp, None, false) => format!("{:E}", float),
+                        (Notation::UpperExp, None, true) => format!("{:+E}", float),
+                        (Notation::UpperExp, Some(p), false) => format!("{:.*E}", p, float),
+                        (Notation::UpperExp, Some(p), true) => format!("{:+.*E}", p, float),
+                    };
+                    assert_eq!(written(&float, options), expected, "{:?} {:?}", value, options);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn write_to_same_as_format() {
+    // Not exact in most choices, and with long exact expansions. F32 and F64 format through
+    // `core`, so they check the digits of `write_to()`.
+    let values = [0.1, -2.0 / 3.0, 1e300, -5e-324, 123456789.0, 9.5, 0.000123, f64::MAX];
+    assert_write_to_same_as_format::<{ UniFloatChoice::F32 }>(&VALUES);
+    assert_write_to_same_as_format::<{ UniFloatChoice::F32 }>(&values[..2]);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_write_to_same_as_format::<{ UniFloatChoice::F64 }>(&VALUES);
+        assert_write_to_same_as_format::<{ UniFloatChoice::F64 }>(&values);
+        assert_write_to_same_as_format::<{ UniFloatChoice::TwoFloat }>(&VALUES);
+        assert_write_to_same_as_format::<{ UniFloatChoice::TwoFloat }>(&values);
+        assert_write_to_same_as_format::<{ MPFR_100_BITS }>(&VALUES);
+        assert_write_to_same_as_format::<{ MPFR_100_BITS }>(&values);
+    }
+}
+
+#[test]
+fn write_to_buffer_too_small() {
+    let mut float = UniFloat::<{ UniFloatChoice::F32 }>::from_f64(-1.5);
+    float.copied();
+    let mut buffer = [0u8; 4];
+    assert_eq!(float.write_to(&mut buffer, FormatOptions::default()), Ok(4));
+    assert_eq!(&buffer, b"-1.5");
+    let mut buffer = [0u8; 3];
+    let error = float.write_to(&mut buffer, FormatOptions::default()).unwrap_err();
+    assert_eq!(error.required(), 4);
+}
@@ -0,0 +1,112 @@
+extern crate std;
+
+use std::string::String;
+use crate::{MpfrBounds, ParseErrorKind, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Each tuple: (value, hex). The values are exact in all choices.
+const HEX: [(f64, &str); 8] = [
+    (1.5, "0x1.8p+0"),
+    (-0.0625, "-0x1p-4"),
+    (255.0, "0x1.fep+7"),
+    (0.1015625, "0x1.ap-4"),
+    (0.0, "0x0p+0"),
+    (-0.0, "-0x0p+0"),
+    (f64::NEG_INFINITY, "-inf"),
+    (f64::NAN, "nan"),
+];
+
+fn assert_hex<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(value, hex) in HEX.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        let mut written = String::new();
+        float.write_hex(&mut written).unwrap();
+        assert_eq!(written, hex);
+
+        let mut parsed = UniFloat::<C>::from_hex_str(hex).unwrap();
+        parsed.copied();
+        let parsed = parsed.to_f64_nearest();
+        assert!(parsed.to_bits() == value.to_bits() || (parsed.is_nan() && value.is_nan()), "{}", hex);
+    }
+    let mut parsed = UniFloat::<C>::from_hex_str("0X1.8P1").unwrap();
+    parsed.copied();
+    assert_eq!(parsed.to_f64_nearest(), 3.0);
+    let mut parsed = UniFloat::<C>::from_hex_str("0x.8").unwrap();
+    parsed.copied();
+    assert_eq!(parsed.to_f64_nearest(), 0.5);
+
+    for &(string, kind, position) in [("", ParseErrorKind::Empty, 0), ("1.5", ParseErrorKind::InvalidCharacter, 0),
+        ("0y1", ParseErrorKind::InvalidCharacter, 1), ("0x", ParseErrorKind::MissingDigits, 2),
+        ("0x1p", ParseErrorKind::MissingDigits, 4), ("0x1g", ParseErrorKind::InvalidCharacter, 3)].iter() {
+        let error = UniFloat::<C>::from_hex_str(string).unwrap_err();
+        assert_eq!((error.kind(), error.position()), (kind, position), "{:?}", string);
+    }
+}
+
+#[test]
+fn hex() {
+    assert_hex::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_hex::<{ UniFloatChoice::F64 }>();
+        assert_hex::<{ UniFloatChoice::TwoFloat }>();
+        assert_hex::<{ UniFloatChoice::QuadFloat }>();
+        assert_hex::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+fn hex_rounds_correctly() {
+    // 1 + 2^-24 is a tie between 1 and 1 + 2^-23. It rounds to even.
+    let mut float = crate::UniF32::from_hex_str("0x1.000001p+0").unwrap();
+    float.copied();
+    assert_eq!(float.to_f64_nearest(), 1.0);
+    // Anything above the tie rounds up.
+    let mut float = crate::UniF32::from_hex_str("0x1.0000010000000000000000001p+0").unwrap();
+    float.copied();
+    assert_eq!(float.to_f64_nearest(), 1.0 + f32::EPSILON as f64);
+    // The least subnormal f32, and half of it (a tie, to even zero).
+    let mut float = crate::UniF32::from_hex_str("0x1p-149").unwrap();
+    float.copied();
+    assert_eq!(float.to_f64_nearest(), libm::ldexp(1.0, -149));
+    let mut float = crate::UniF32::from_hex_str("0x1p-150").unwrap();
+    float.copied();
+    assert_eq!(float.to_f64_nearest(), 0.0);
+    let mut float = crate::UniF32::from_hex_str("0x1p+128").unwrap();
+    float.copied();
+    assert_eq!(float.to_f64_nearest(), f64::INFINITY);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn hex_beyond_f64() {
+    // 1 + 2^-60 is exact in double-double, and in 100 bits.
+    let mut twofloat = crate::UniTwoFloat::from_hex_str("0x1.000000000000001p+0").unwrap();
+    twofloat.copied();
+    assert_eq!(twofloat.twofloats[0].lo(), libm::ldexp(1.0, -60));
+    let mut written = String::new();
+    twofloat.write_hex(&mut written).unwrap();
+    assert_eq!(written, "0x1.000000000000001p+0");
+
+    let mut mpfr_based = UniFloat::<{ MPFR_100_BITS }>::from_hex_str("-0x1.000000000000001p-3000").unwrap();
+    mpfr_based.copied();
+    let mut written = String::new();
+    mpfr_based.write_hex(&mut written).unwrap();
+    assert_eq!(written, "-0x1.000000000000001p-3000");
+
+    let mut subnormal = crate::UniF64::from_hex_str("0x1p-1074").unwrap();
+    subnormal.copied();
+    let mut written = String::new();
+    subnormal.write_hex(&mut written).unwrap();
+    assert_eq!(written, "0x1p-1074");
+}
@@ -0,0 +1,106 @@
+#[cfg(not(feature = "f32_only"))]
+use gmp_mpfr_sys::mpfr;
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Each tuple: (function name, value, result). The results are exact.
+const HYPERBOLICS: [(&str, f64, f64); 16] = [
+    ("sinh", -0.0, -0.0),
+    ("sinh", f64::NEG_INFINITY, f64::NEG_INFINITY),
+    ("sinh", 1e4, f64::INFINITY),
+    ("cosh", -0.0, 1.0),
+    ("cosh", f64::NEG_INFINITY, f64::INFINITY),
+    ("tanh", -0.0, -0.0),
+    ("tanh", f64::NEG_INFINITY, -1.0),
+    ("tanh", 100.0, 1.0),
+    ("asinh", -0.0, -0.0),
+    ("asinh", f64::NEG_INFINITY, f64::NEG_INFINITY),
+    ("acosh", 1.0, 0.0),
+    ("acosh", 0.5, f64::NAN),
+    ("acosh", f64::INFINITY, f64::INFINITY),
+    ("atanh", -0.0, -0.0),
+    ("atanh", -1.0, f64::NEG_INFINITY),
+    ("atanh", 2.0, f64::NAN),
+];
+
+fn assert_hyperbolics<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(name, value, expected) in HYPERBOLICS.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        let mut result = match name {
+            "sinh" => float.sinh(),
+            "cosh" => float.cosh(),
+            "tanh" => float.tanh(),
+            "asinh" => float.asinh(),
+            "acosh" => float.acosh(),
+            _ => float.atanh()
+        };
+        result.copied();
+        let result = result.to_f64_nearest();
+        assert!(result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan()), "{}({})", name, value);
+    }
+}
+
+#[test]
+fn hyperbolics() {
+    assert_hyperbolics::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_hyperbolics::<{ UniFloatChoice::F64 }>();
+        assert_hyperbolics::<{ UniFloatChoice::TwoFloat }>();
+        assert_hyperbolics::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[cfg(not(feature = "f32_only"))]
+const MPFR_200_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(200)
+};
+
+/// TwoFloat results have relative error below 1e-26, compared to MPFR at 200 bits. That includes
+/// values near zero (and near 1 for `acosh()` and `atanh()`), where formulations with `exp()` and
+/// `ln()` would cancel.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn hyperbolics_twofloat_accuracy() {
+    type UniMpfr200bit = UniFloat<{ MPFR_200_BITS }>;
+    const NEAR_ONE: f64 = 1.0 - 1.0 / (1u64 << 40) as f64;
+    for &(name, value) in [("sinh", 1e-20), ("sinh", -1e-10), ("sinh", 0.001), ("sinh", 3.0), ("sinh", 709.5),
+        ("cosh", -1e-10), ("cosh", 0.5), ("cosh", -30.0), ("cosh", 709.5),
+        ("tanh", 1e-20), ("tanh", -0.001), ("tanh", 3.0), ("tanh", -30.0),
+        ("asinh", -1e-20), ("asinh", 0.001), ("asinh", -3.0), ("asinh", 1e20),
+        ("acosh", 2.0 - NEAR_ONE), ("acosh", 1.5), ("acosh", 1e3), ("acosh", 1e20),
+        ("atanh", 1e-20), ("atanh", -0.001), ("atanh", 0.5), ("atanh", -NEAR_ONE)].iter() {
+        let mut twofloat = crate::UniTwoFloat::from_f64(value);
+        twofloat.copied();
+        let mut reference = UniMpfr200bit::from_f64(value);
+        reference.copied();
+        let (mut result, mut expected) = match name {
+            "sinh" => (twofloat.sinh(), reference.sinh()),
+            "cosh" => (twofloat.cosh(), reference.cosh()),
+            "tanh" => (twofloat.tanh(), reference.tanh()),
+            "asinh" => (twofloat.asinh(), reference.asinh()),
+            "acosh" => (twofloat.acosh(), reference.acosh()),
+            _ => (twofloat.atanh(), reference.atanh())
+        };
+        result.copied();
+        expected.copied();
+        let error = unsafe {
+            let expected = expected.mpfr_mut();
+            let magnitude = mpfr::get_d(expected, mpfr::rnd_t::RNDN);
+            mpfr::sub_d(expected, expected, result.twofloats[0].hi(), mpfr::rnd_t::RNDN);
+            mpfr::sub_d(expected, expected, result.twofloats[0].lo(), mpfr::rnd_t::RNDN);
+            mpfr::get_d(expected, mpfr::rnd_t::RNDN) / magnitude
+        };
+        assert!(libm::fabs(error) < 1e-26, "{}({}): {}", name, value, error);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, UniInterval, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+fn value<const C: UniFloatChoice>(x: f64) -> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    UniFloat::<C>::from_f64(x)
+}
+
+fn interval<const C: UniFloatChoice>(lower: f64, upper: f64) -> UniInterval<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut lower, mut upper) = (value::<C>(lower), value::<C>(upper));
+    lower.copied();
+    upper.copied();
+    UniInterval::new(&lower, &upper)
+}
+
+fn bounds<const C: UniFloatChoice>(interval: &UniInterval<C>) -> (f64, f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    (interval.lower().to_f64_nearest(), interval.upper().to_f64_nearest())
+}
+
+fn assert_interval<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut a, mut b) = (interval::<C>(1.0, 2.0), interval::<C>(3.0, 4.0));
+    a.copied();
+    b.copied();
+    let mut result = &a + &b;
+    assert_eq!(bounds(result.copied()), (4.0, 6.0));
+    result = &a - &b;
+    assert_eq!(bounds(result.copied()), (-3.0, -1.0));
+    result = &a * &b;
+    assert_eq!(bounds(result.copied()), (3.0, 8.0));
+    result = -&a;
+    assert_eq!(bounds(result.copied()), (-2.0, -1.0));
+    let mut negated = -&b;
+    negated.copied();
+    result = &a * &negated;
+    assert_eq!(bounds(result.copied()), (-8.0, -3.0));
+    result = &a / &*interval::<C>(-1.0, 1.0).copied();
+    assert_eq!(bounds(result.copied()), (f64::NEG_INFINITY, f64::INFINITY));
+
+    // Inexact: the bounds are apart, and they enclose 1/3, so (1/3) * 3 encloses 1.
+    let (mut one, mut three) = (interval::<C>(1.0, 1.0), interval::<C>(3.0, 3.0));
+    one.copied();
+    three.copied();
+    let mut third = &one / &three;
+    third.copied();
+    assert!(third.lower().lt(third.upper()));
+    result = &third * &three;
+    assert!(result.copied().contains(one.lower()));
+    let mut width = third.width();
+    assert!(width.gt(&UniFloat::<C>::ZERO));
+    assert!(!third.contains(value::<C>(0.5).copied()));
+
+    assert!(a.overlaps(interval::<C>(2.0, 3.0).copied()));
+    assert!(!a.overlaps(&b));
+    assert!(a.encloses(interval::<C>(1.5, 2.0).copied()));
+    assert!(!a.encloses(&b));
+    let mut midpoint = a.midpoint();
+    assert_eq!(midpoint.copied().to_f64_nearest(), 1.5);
+    width = a.width();
+    assert_eq!(width.copied().to_f64_nearest(), 1.0);
+
+    let mut nearest = value::<C>(1.0);
+    nearest.copied();
+    nearest /= three.lower();
+    let mut point = UniInterval::point(&nearest);
+    point.copied();
+    let mut around = UniInterval::with_ulp_error(&nearest);
+    around.copied();
+    assert!(around.encloses(&point));
+    let mut next = nearest.next_up();
+    assert!(around.contains(next.copied()));
+    assert!(!point.contains(&next));
+}
+
+#[test]
+fn interval_arithmetic() {
+    assert_interval::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_interval::<{ UniFloatChoice::F64 }>();
+        assert_interval::<{ UniFloatChoice::TwoFloat }>();
+        assert_interval::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+#[should_panic(expected = "UniInterval needs lower <= upper.")]
+fn reversed_bounds() {
+    interval::<{ UniFloatChoice::F32 }>(2.0, 1.0);
+}
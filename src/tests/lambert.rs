@@ -0,0 +1,112 @@
+#[cfg(not(feature = "f32_only"))]
+use gmp_mpfr_sys::mpfr;
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Each tuple: (branch, value, result). The results are exact.
+const EXACT: [(&str, f64, f64); 10] = [
+    ("w0", 0.0, 0.0),
+    ("w0", -0.0, -0.0),
+    ("w0", f64::INFINITY, f64::INFINITY),
+    ("w0", f64::NEG_INFINITY, f64::NAN),
+    ("w0", -1.0, f64::NAN),
+    ("w0", f64::NAN, f64::NAN),
+    ("wm1", 0.0, f64::NEG_INFINITY),
+    ("wm1", -0.0, f64::NEG_INFINITY),
+    ("wm1", 0.5, f64::NAN),
+    ("wm1", -1.0, f64::NAN),
+];
+
+/// Each tuple: (branch, value, result rounded to f64). W0(1) is the omega constant.
+const APPROXIMATE: [(&str, f64, f64); 7] = [
+    ("w0", 1.0, 0.5671432904097838),
+    ("w0", 10.0, 1.7455280027406994),
+    ("w0", -0.3, -0.4894022271802149),
+    ("w0", 1e300, 684.2472086297608),
+    ("wm1", -0.3, -1.7813370234216277),
+    ("wm1", -0.1, -3.577152063957297),
+    ("wm1", -1e-300, -697.3227762954601),
+];
+
+fn evaluate<const C: UniFloatChoice>(branch: &str, value: f64) -> f64 where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut float = UniFloat::<C>::from_f64(value);
+    float.copied();
+    let mut result = if branch == "w0" { float.lambert_w0() } else { float.lambert_wm1() };
+    result.copied();
+    result.to_f64_nearest()
+}
+
+fn assert_lambert_w<const C: UniFloatChoice>(approximate: &[(&str, f64, f64)], tolerance: f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(branch, value, expected) in EXACT.iter() {
+        let result = evaluate::<C>(branch, value);
+        assert!(result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan()), "{}({})", branch, value);
+    }
+    for &(branch, value, expected) in approximate.iter() {
+        let result = evaluate::<C>(branch, value);
+        assert!(libm::fabs(result - expected) <= tolerance * libm::fabs(expected), "{}({}): {}", branch, value, result);
+    }
+}
+
+#[test]
+fn lambert_w() {
+    // f32 can't hold 1e300 or 1e-300.
+    assert_lambert_w::<{ UniFloatChoice::F32 }>(&APPROXIMATE[..3], 1e-7);
+    assert_lambert_w::<{ UniFloatChoice::F32 }>(&APPROXIMATE[4..6], 1e-7);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_lambert_w::<{ UniFloatChoice::F64 }>(&APPROXIMATE, 2e-16);
+        assert_lambert_w::<{ UniFloatChoice::TwoFloat }>(&APPROXIMATE, 2e-16);
+        assert_lambert_w::<{ MPFR_100_BITS }>(&APPROXIMATE, 2e-16);
+    }
+}
+
+#[cfg(not(feature = "f32_only"))]
+const MPFR_200_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(200)
+};
+
+/// TwoFloat results have relative error below 1e-26, compared to MPFR at 200 bits. That includes
+/// tiny and huge values, and values where the iterations switch between Halley's and Newton's
+/// methods.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn lambert_w_twofloat_accuracy() {
+    type UniMpfr200bit = UniFloat<{ MPFR_200_BITS }>;
+    for &(branch, value) in [("w0", 1e-300), ("w0", -0.2759), ("w0", 2.0), ("w0", 14.7), ("w0", 1.7e308),
+        ("wm1", -0.2759), ("wm1", -0.27), ("wm1", -0.01), ("wm1", -5e-324)].iter() {
+        let mut twofloat = crate::UniTwoFloat::from_f64(value);
+        twofloat.copied();
+        let mut reference = UniMpfr200bit::from_f64(value);
+        reference.copied();
+        let (mut result, mut expected) = if branch == "w0" {
+            (twofloat.lambert_w0(), reference.lambert_w0())
+        } else {
+            (twofloat.lambert_wm1(), reference.lambert_wm1())
+        };
+        result.copied();
+        expected.copied();
+        let error = unsafe {
+            let expected = expected.mpfr_mut();
+            let magnitude = mpfr::get_d(expected, mpfr::rnd_t::RNDN);
+            mpfr::sub_d(expected, expected, result.twofloats[0].hi(), mpfr::rnd_t::RNDN);
+            mpfr::sub_d(expected, expected, result.twofloats[0].lo(), mpfr::rnd_t::RNDN);
+            mpfr::get_d(expected, mpfr::rnd_t::RNDN) / magnitude
+        };
+        assert!(libm::fabs(error) < 1e-26, "{}({}): {}", branch, value, error);
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(not(feature = "f32_only"))]
+use crate::{FixedOverflow, MpfrBounds, Underflow, UniFloatBounds, UniTwoFloat};
+use crate::{UniF32, UniFloat, UniFloatChoice, unifloat};
+#[cfg(not(feature = "f32_only"))]
+use super::MPFR_100_BITS;
+
+/// IEEE 754 binary16 exponents, with each kind of underflow.
+#[cfg(not(feature = "f32_only"))]
+const HALF: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_bounds(&UniFloatBounds::new(11, -13, 16))
+};
+#[cfg(not(feature = "f32_only"))]
+const HALF_SUBNORMAL: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_bounds(&UniFloatBounds::new(11, -13, 16)).with_underflow(Underflow::Subnormal)
+};
+#[cfg(not(feature = "f32_only"))]
+const HALF_FLUSH_TO_ZERO: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_bounds(&UniFloatBounds::new(11, -13, 16)).with_underflow(Underflow::FlushToZero)
+};
+#[cfg(not(feature = "f32_only"))]
+const Q16_16: UniFloatChoice = UniFloatChoice::fixed(16, 16, FixedOverflow::Saturating);
+#[cfg(not(feature = "f32_only"))]
+const WRAPPING_I8: UniFloatChoice = UniFloatChoice::fixed(8, 0, FixedOverflow::Wrapping);
+
+/// Decimal literals, some of them inexact, or beyond the range of f32 (or f64), or of the narrower
+/// choices.
+const DECIMAL: [&str; 19] = [
+    "0",
+    "-0",
+    "+1",
+    "0.1",
+    "-2.5e-3",
+    ".5",
+    "3.",
+    "3.14159265358979323846264338327950288419716939937510",
+    "123456789012345678901234567890",
+    "1e23",
+    "1.7976931348623158e308",
+    "1.8e308",
+    "4.9e-324",
+    "2.4703282292062328e-324",
+    "-1e-400",
+    "-Infinity",
+    "65519.99",
+    "-3.0517578125e-5",
+    "-250.5",
+];
+
+/// Hex literals, as `from_hex_str()` parses them.
+const HEX: [&str; 7] = ["0x1.8p3", "-0X.8", "0x1.fffffffffffff8p0", "0x1.0000000000001p-1075", "0x123456789abcdef0123p-100",
+    "0x1.004p-25", "-0x1.00008p-15"];
+
+choice_fixture! {
+    fn assert_same<const C: UniFloatChoice>(literal: &str, expected: &UniFloat<C>) {
+        let value = UniFloat::<C>::from_literal(literal);
+        if expected.is_nan() {
+            assert!(value.is_nan(), "{}", literal);
+            return;
+        }
+        assert!(value == *expected, "{}", literal);
+        assert_eq!(value.is_sign_negative(), expected.is_sign_negative(), "{}", literal);
+    }
+}
+
+choice_fixture! {
+    fn assert_literal<const C: UniFloatChoice>() {
+        for &literal in DECIMAL.iter() {
+            let expected: UniFloat<C> = literal.parse().unwrap();
+            assert_same(literal, &expected);
+        }
+        for &literal in HEX.iter() {
+            let expected = UniFloat::<C>::from_hex_str(literal).unwrap();
+            assert_same(literal, &expected);
+        }
+        assert!(UniFloat::<C>::from_literal("NaN").is_nan());
+    }
+}
+
+test_choices!(literal, assert_literal, UniFloatChoice::QuadFloat);
+
+/// As parsed, MPFR-based literals are limited to the exponent range, and fixed-point ones are on
+/// their grid, and saturated or wrapped.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn literal_bounded() {
+    assert_literal::<{ HALF }>();
+    assert_literal::<{ HALF_SUBNORMAL }>();
+    assert_literal::<{ HALF_FLUSH_TO_ZERO }>();
+    assert_literal::<{ Q16_16 }>();
+    assert_literal::<{ WRAPPING_I8 }>();
+
+    const LEAST: UniFloat<{ HALF }> = unifloat!("4e-5");
+    assert_eq!(LEAST.to_f64_nearest(), 1.0 / 16384.0);
+    const SUBNORMAL: UniFloat<{ HALF_SUBNORMAL }> = unifloat!("4e-5");
+    assert_eq!(SUBNORMAL.to_f64_nearest(), 671.0 / 16777216.0);
+    assert_eq!(unifloat!(UniFloat<{ HALF }>, "1e5").to_f64_nearest(), f64::INFINITY);
+    assert_eq!(unifloat!(UniFloat<{ Q16_16 }>, "0.1").to_f64_nearest(), 6554.0 / 65536.0);
+    assert_eq!(unifloat!(UniFloat<{ Q16_16 }>, "-1e6").to_f64_nearest(), -32768.0);
+    assert_eq!(unifloat!(UniFloat<{ WRAPPING_I8 }>, "300").to_f64_nearest(), 44.0);
+    assert!(unifloat!(UniFloat<{ WRAPPING_I8 }>, "-inf").is_nan());
+}
+
+#[test]
+fn literal_macro() {
+    const HALF: UniF32 = unifloat!("0.5");
+    assert_eq!(HALF.to_f64_nearest(), 0.5);
+    assert_eq!(unifloat!(UniF32, "-0x1p-3").to_f64_nearest(), -0.125);
+    #[cfg(not(feature = "f32_only"))] {
+        let tenth = unifloat!(UniTwoFloat, "0.1");
+        let expected: UniTwoFloat = "0.1".parse().unwrap();
+        assert!(tenth == expected);
+        const THIRD: UniFloat<{ MPFR_100_BITS }> = unifloat!("0.333333333333333333333333333333333");
+        let expected: UniFloat<{ MPFR_100_BITS }> = "0.333333333333333333333333333333333".parse().unwrap();
+        assert!(THIRD == expected);
+    }
+}
+
+choice_fixture! {
+    fn assert_decimal_parts<const C: UniFloatChoice>() {
+        let parts: [(bool, &[u8], i32, &str); 6] = [
+            (false, &[3, 1, 4, 1, 5, 9], -5, "3.14159"),
+            (true, &[1], -1, "-0.1"),
+            (false, &[0, 0, 7], 2, "700"),
+            (true, &[], 0, "-0"),
+            (false, &[2, 4, 7, 0, 3, 2, 8, 2, 2, 9, 2, 0, 6, 2, 3, 2, 8], -340, "2.4703282292062328e-324"),
+            (false, &[1], 400, "1e400"),
+        ];
+        for &(negative, digits, exp10, literal) in parts.iter() {
+            let expected = UniFloat::<C>::from_literal(literal);
+            let value = UniFloat::<C>::from_decimal_parts(negative, digits, exp10);
+            assert!(value == expected, "{}", literal);
+            assert_eq!(value.is_sign_negative(), expected.is_sign_negative(), "{}", literal);
+        }
+    }
+}
+
+test_choices!(decimal_parts, assert_decimal_parts, UniFloatChoice::QuadFloat);
+
+#[test]
+fn decimal_parts_table() {
+    const TABLE: [UniF32; 3] = [
+        UniF32::from_decimal_parts(false, &[2, 5], -1),
+        UniF32::from_decimal_parts(true, &[1, 2, 5], -3),
+        UniF32::from_decimal_parts(false, &[6, 4], 0),
+    ];
+    let values = [TABLE[0].to_f64_nearest(), TABLE[1].to_f64_nearest(), TABLE[2].to_f64_nearest()];
+    assert_eq!(values, [2.5, -0.125, 64.0]);
+}
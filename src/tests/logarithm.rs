@@ -0,0 +1,102 @@
+#[cfg(not(feature = "f32_only"))]
+use gmp_mpfr_sys::mpfr;
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Each tuple: (function name, value, base (only for "log"), result). The results are exact.
+const LOGARITHMS: [(&str, f64, f64, f64); 18] = [
+    ("ln", 1.0, 0.0, 0.0),
+    ("ln", 0.0, 0.0, f64::NEG_INFINITY),
+    ("ln", -1.0, 0.0, f64::NAN),
+    ("ln", f64::INFINITY, 0.0, f64::INFINITY),
+    ("log2", 1024.0, 0.0, 10.0),
+    ("log2", 0.5, 0.0, -1.0),
+    ("log10", 1.0, 0.0, 0.0),
+    ("log10", -0.0, 0.0, f64::NEG_INFINITY),
+    ("ln_1p", -0.0, 0.0, -0.0),
+    ("ln_1p", -1.0, 0.0, f64::NEG_INFINITY),
+    ("ln_1p", -2.0, 0.0, f64::NAN),
+    ("log", 1000.0, 10.0, 3.0),
+    ("log", 8.0, 2.0, 3.0),
+    ("log", 2.0, 4.0, 0.5),
+    ("log", 1.0, 10.0, 0.0),
+    ("log", 0.0, 10.0, f64::NEG_INFINITY),
+    ("log", 5.0, 1.0, f64::INFINITY),
+    ("log", -1.0, 10.0, f64::NAN),
+];
+
+fn assert_logarithms<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(name, value, base, expected) in LOGARITHMS.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        let mut base = UniFloat::<C>::from_f64(base);
+        base.copied();
+        let mut result = match name {
+            "ln" => float.ln(),
+            "log2" => float.log2(),
+            "log10" => float.log10(),
+            "ln_1p" => float.ln_1p(),
+            _ => float.log(&base)
+        };
+        result.copied();
+        let result = result.to_f64_nearest();
+        assert!(result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan()), "{}({})", name, value);
+    }
+}
+
+#[test]
+fn logarithms() {
+    assert_logarithms::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_logarithms::<{ UniFloatChoice::F64 }>();
+        assert_logarithms::<{ UniFloatChoice::TwoFloat }>();
+        assert_logarithms::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[cfg(not(feature = "f32_only"))]
+const MPFR_200_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(200)
+};
+
+/// MPFR's log() to any base is the correctly rounded quotient: the same as computing the
+/// quotient at a much higher precision, and rounding that.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn log_mpfr_rounds_once() {
+    type UniMpfr200bit = UniFloat<{ MPFR_200_BITS }>;
+    for &(value, base) in [(3.0, 7.0), (0.1, 10.0), (1e300, 1.5), (2.0, 0.5), (1.0 + 1e-15, 3.0)].iter() {
+        let mut float = UniFloat::<{ MPFR_100_BITS }>::from_f64(value);
+        float.copied();
+        let mut base_float = UniFloat::<{ MPFR_100_BITS }>::from_f64(base);
+        base_float.copied();
+        let mut log = float.log(&base_float);
+        log.copied();
+
+        let mut expected = UniFloat::<{ MPFR_100_BITS }>::NAN;
+        expected.copied();
+        let (mut numerator, mut denominator) = (UniMpfr200bit::NAN, UniMpfr200bit::NAN);
+        numerator.copied();
+        denominator.copied();
+        unsafe {
+            let (numerator, denominator) = (numerator.mpfr_mut(), denominator.mpfr_mut());
+            mpfr::set_d(numerator, value, mpfr::rnd_t::RNDN);
+            mpfr::log(numerator, numerator, mpfr::rnd_t::RNDN);
+            mpfr::set_d(denominator, base, mpfr::rnd_t::RNDN);
+            mpfr::log(denominator, denominator, mpfr::rnd_t::RNDN);
+            mpfr::div(numerator, numerator, denominator, mpfr::rnd_t::RNDN);
+            mpfr::set(expected.mpfr_mut(), numerator, mpfr::rnd_t::RNDN);
+        }
+        assert!(log == expected, "log({}) to base {}", value, base);
+    }
+}
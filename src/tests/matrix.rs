@@ -0,0 +1,110 @@
+#![cfg(feature = "alloc")]
+
+extern crate std;
+
+use {gmp_mpfr_sys::mpfr, std::vec::Vec};
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, UniMat, UniVec, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+fn vector<const C: UniFloatChoice>(values: &[f64]) -> UniVec<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut result = UniVec::new();
+    for value in values {
+        let mut value = UniFloat::<C>::from_f64(*value);
+        result.push(value.copied());
+    }
+    result
+}
+
+fn values<const C: UniFloatChoice>(items: &[UniFloat<C>]) -> Vec<f64> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    items.iter().map(|item| item.to_f64_nearest()).collect()
+}
+
+fn assert_vectors_and_matrices<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    // Pushing grows the buffer many times, and the items stay usable by reference.
+    let many = vector::<C>(&[1.0; 100]);
+    let mut sum = UniFloat::<C>::sum_compensated(many.as_slice());
+    assert_eq!(sum.copied().to_f64_nearest(), 100.0);
+
+    let (mut two, mut zero, mut nan) = (UniFloat::<C>::from_f64(2.0), UniFloat::<C>::ZERO, UniFloat::<C>::NAN);
+    two.copied();
+    zero.copied();
+    nan.copied();
+    let x = vector::<C>(&[1.0, 2.0, 3.0]);
+    let mut y = vector::<C>(&[10.0, 20.0, 30.0]);
+    y.axpy(&two, &x);
+    assert_eq!(values(y.as_slice()), [12.0, 24.0, 36.0]);
+    let mut dot = x.dot(&y);
+    assert_eq!(dot.copied().to_f64_nearest(), 12.0 + 48.0 + 108.0);
+    let copy = y.clone();
+    y.set(1, &two);
+    assert_eq!(y[1].to_f64_nearest(), 2.0);
+    assert_eq!(copy[1].to_f64_nearest(), 24.0);
+
+    // [1 2 3]
+    // [4 5 6]
+    let a = UniMat::from_row_major(2, 3, vector::<C>(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).as_slice());
+    let transposed = a.transpose();
+    assert_eq!((transposed.rows(), transposed.cols()), (3, 2));
+    assert_eq!(values(transposed.as_slice()), [1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+    assert_eq!(transposed[(2, 1)].to_f64_nearest(), 6.0);
+
+    let mut result = vector::<C>(&[1.0, 1.0]);
+    a.gemv(&two, &x, &two, &mut result);
+    assert_eq!(values(result.as_slice()), [2.0 * 14.0 + 2.0, 2.0 * 32.0 + 2.0]);
+    // A zero beta ignores what's in y, even NaN.
+    let mut result = vector::<C>(&[f64::NAN, f64::NAN]);
+    a.gemv(&two, &x, &zero, &mut result);
+    assert_eq!(values(result.as_slice()), [28.0, 64.0]);
+
+    let mut product = UniMat::zeros(2, 2);
+    product.set(0, 0, &nan);
+    a.gemm(&two, &transposed, &zero, &mut product);
+    assert_eq!(values(product.as_slice()), [28.0, 64.0, 64.0, 154.0]);
+    let mut product = UniMat::zeros(3, 3);
+    transposed.gemm(&two, &a, &two, &mut product);
+    assert_eq!(values(product.row(2)), [2.0 * 27.0, 2.0 * 36.0, 2.0 * 45.0]);
+    let copy = product.clone();
+    assert_eq!(values(copy.row(0)), [34.0, 44.0, 54.0]);
+}
+
+#[test]
+fn vectors_and_matrices() {
+    assert_vectors_and_matrices::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_vectors_and_matrices::<{ UniFloatChoice::F64 }>();
+        assert_vectors_and_matrices::<{ UniFloatChoice::TwoFloat }>();
+        assert_vectors_and_matrices::<{ UniFloatChoice::QuadFloat }>();
+        assert_vectors_and_matrices::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+#[should_panic(expected="gemv() needs x as long as a row, and y as long as a column.")]
+fn gemv_needs_matching_lengths() {
+    let mut one = UniFloat::<{ UniFloatChoice::F32 }>::ONE;
+    one.copied();
+    let a = UniMat::zeros(2, 3);
+    a.gemv(&one, &UniVec::zeros(2), &one, &mut UniVec::zeros(2));
+}
@@ -1,11 +1,107 @@
 #![cfg(test)]
 
+/// Defines a generic fixture `fn name<const C: UniFloatChoice>(...)`, with the where-clause that
+/// `UniFloat<C>` needs.
+macro_rules! choice_fixture {
+    ($(#[$attr:meta])* fn $name:ident<const $c:ident: $choice:ty>($($params:tt)*) $body:block) => {
+        $(#[$attr])*
+        fn $name<const $c: $choice>($($params)*) where
+        [f32; crate::f32_parts_length($c)]: Sized,
+        [f64; crate::f64_parts_length($c)]: Sized,
+        [crate::TwoFloatPart; crate::twofloat_parts_length($c)]: Sized,
+        [crate::MpfrFixedPart; crate::mpfr_fixed_parts_length($c)]: Sized,
+        [crate::MpfrLimbPart; crate::mpfr_limb_parts_length($c)]: Sized,
+        $body
+    };
+}
+
+/// Defines the test `test`, which runs the `choice_fixture!` `fixture` for F32, and (unless
+/// f32_only) for F64, TwoFloat and MPFR_100_BITS, and then for the further choices, if any.
+macro_rules! test_choices {
+    ($(#[$attr:meta])* $test:ident, $fixture:ident $(, $choice:expr)* $(,)?) => {
+        $(#[$attr])*
+        #[test]
+        fn $test() {
+            $fixture::<{ crate::UniFloatChoice::F32 }>();
+            #[cfg(not(feature = "f32_only"))] {
+                $fixture::<{ crate::UniFloatChoice::F64 }>();
+                $fixture::<{ crate::UniFloatChoice::TwoFloat }>();
+                $fixture::<{ crate::tests::MPFR_100_BITS }>();
+                $($fixture::<{ $choice }>();)*
+            }
+        }
+    };
+}
+
+mod approximating;
+mod arbitrary;
+mod arith;
+mod auditing;
+mod ball;
+mod bessel;
+mod bytes;
+mod classify;
+mod cmp;
+mod complex;
+mod consts;
+mod convert;
+mod debug;
+mod decimal;
+mod dual;
+mod dynamic;
+mod eft;
+mod elliptic;
+mod escalation;
+mod exponent_range;
+mod exponential;
+mod fft;
+mod fixed;
+mod flags;
+mod format;
+mod hex;
+mod hyperbolic;
+mod interval;
+mod lambert;
+mod literal;
+mod logarithm;
+mod matrix;
+mod neighbours;
+mod num;
+mod parse;
+mod polynomial;
+mod posit;
+mod power;
+mod quadfloat;
+mod radix;
+mod rational;
+mod real_field;
+mod roots;
+mod rounded;
+mod rounding;
+mod sampling;
+mod scaling;
+mod serializing;
+mod softmpfr;
+mod special;
+mod statistics;
+mod stochastic;
+mod summation;
+mod ternary;
+mod testing;
+mod trigonometry;
 mod type_sizes;
 mod unifloat_bounds;
+mod view;
+mod zeroizing;
 
 use crate::{MpfrBounds, ONE_LIMB_PRECISION, UniFloat, UniFloatChoice, UniF32,
     UniF64, UniTwoFloat, UniMpfrLimb1Prec1, UniMpfrLimb2PrecAll};
 
+#[cfg(not(feature = "f32_only"))]
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
 
 #[test]
 fn call_copied_exactly_where_needed() {
@@ -47,24 +143,40 @@ fn no_need_to_call_copied_before_receiving_by_value() {
 }
 
 #[test]
-#[should_panic(expected="Must call .copied() first, or assign with <<= instead of =.")]
-fn must_call_copied_before_receiving_by_reference_debug_mode() {
-    //let uf = UniFloat::<{ UniFloatChoice::F32 }>::default();
-    let orig = UniF32::NAN;
+fn no_need_to_call_copied_before_receiving_by_reference() {
+    let orig = UniF32::from_f64(1.5);
     let mut copy = UniF32::NAN;
     copy <<= &orig;
-    if !cfg!(debug_assertions) {
-        panic!("Must call .copied() first, or assign with <<= instead of =.");
+    assert!(copy == orig);
+}
+
+/// MPFR values moved (or copied) without .copied() read their own limbs, wherever they are.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn no_need_to_call_copied_after_moving_mpfr() {
+    type UniMpfr100bit = UniFloat<{ UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(100) } }>;
+    fn third() -> UniMpfr100bit {
+        let (one, three) = (UniMpfr100bit::ONE, UniMpfr100bit::from_f64(3.0));
+        &one / &three
     }
+    let mut values = [UniMpfr100bit::NAN; 3];
+    for value in values.iter_mut() {
+        *value = third();
+    }
+    let moved = values;
+    // 3 * (1/3 rounded) is 1 + 2^-101, which rounds to 1.
+    let mut sum = moved[0];
+    sum += &moved[1];
+    sum += &moved[2];
+    let one = UniMpfr100bit::ONE;
+    assert!(sum == one);
+    assert!(moved[0] < one && moved[0].to_f64_nearest() == 1.0 / 3.0);
 }
 
 #[test]
-#[should_panic(expected="Have already called .copied(), or assigned with <<= instead of =. Do not call .copied() now.")]
-fn must_not_call_copied_twice() {
-    let mut orig = UniF32::NAN;
+fn calling_copied_twice_is_harmless() {
+    let mut orig = UniF32::from_f64(0.5);
     orig.copied();
     orig.copied();
-    if !cfg!(debug_assertions) {
-        panic!("Have already called .copied(), or assigned with <<= instead of =. Do not call .copied() now.");
-    }
+    assert!(orig.to_f64_nearest() == 0.5);
 }
\ No newline at end of file
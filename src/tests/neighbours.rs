@@ -0,0 +1,210 @@
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Same precision as f64. The exponent range is wider, though.
+const MPFR_53_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(53)
+};
+
+/// `next_up()`, `next_down()` and `next_toward(towards)` of `value`, as f64.
+fn neighbours<const C: UniFloatChoice>(value: f64, towards: f64) -> (f64, f64, f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut float, mut other) = (UniFloat::<C>::from_f64(value), UniFloat::<C>::from_f64(towards));
+    float.copied();
+    other.copied();
+    let (mut up, mut down, mut toward) = (float.next_up(), float.next_down(), float.next_toward(&other));
+    up.copied();
+    down.copied();
+    toward.copied();
+    (up.to_f64_nearest(), down.to_f64_nearest(), toward.to_f64_nearest())
+}
+
+fn same(result: f64, expected: f64) -> bool {
+    result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan())
+}
+
+#[test]
+fn f32_neighbours() {
+    let (up, down, toward) = neighbours::<{ UniFloatChoice::F32 }>(1.0, 0.0);
+    assert_eq!((up, down, toward), (1.0 + f32::EPSILON as f64, 1.0 - f32::EPSILON as f64 / 2.0, 1.0 - f32::EPSILON as f64 / 2.0));
+    let least = f32::from_bits(1) as f64;
+    let (up, down, toward) = neighbours::<{ UniFloatChoice::F32 }>(-0.0, 0.0);
+    assert_eq!((up, down), (least, -least));
+    assert!(same(toward, 0.0));
+    let (up, down, _) = neighbours::<{ UniFloatChoice::F32 }>(f64::INFINITY, 0.0);
+    assert_eq!((up, down), (f64::INFINITY, f32::MAX as f64));
+    let (up, down, toward) = neighbours::<{ UniFloatChoice::F32 }>(f64::NAN, 0.0);
+    assert!(up.is_nan() && down.is_nan() && toward.is_nan());
+    assert!(neighbours::<{ UniFloatChoice::F32 }>(1.0, f64::NAN).2.is_nan());
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn f64_and_mpfr_neighbours() {
+    let above_one = 1.0 + f64::EPSILON;
+    let below_one = 1.0 - f64::EPSILON / 2.0;
+    let least = f64::from_bits(1);
+    for &(value, towards, up, down, toward) in [
+        (1.0, 2.0, above_one, below_one, above_one),
+        (1.0, -5.0, above_one, below_one, below_one),
+        (-1.0, -1.0, -below_one, -above_one, -1.0),
+        (3.0, f64::NAN, 3.0 + 2.0 * f64::EPSILON, 3.0 - 2.0 * f64::EPSILON, f64::NAN),
+    ].iter() {
+        for &(name, (up_result, down_result, toward_result)) in [
+            ("F64", neighbours::<{ UniFloatChoice::F64 }>(value, towards)),
+            ("MPFR", neighbours::<{ MPFR_53_BITS }>(value, towards)),
+        ].iter() {
+            assert!(same(up_result, up) && same(down_result, down) && same(toward_result, toward),
+                "{}: {} toward {}: {:?}", name, value, towards, (up_result, down_result, toward_result));
+        }
+    }
+    let (up, down, toward) = neighbours::<{ UniFloatChoice::F64 }>(f64::NEG_INFINITY, 0.0);
+    assert_eq!((up, down, toward), (-f64::MAX, f64::NEG_INFINITY, -f64::MAX));
+    let (up, down, toward) = neighbours::<{ UniFloatChoice::F64 }>(0.0, -1.0);
+    assert_eq!((up, down, toward), (least, -least, -least));
+    let (up, _, _) = neighbours::<{ UniFloatChoice::F64 }>(f64::MAX, 0.0);
+    assert_eq!(up, f64::INFINITY);
+    // MPFR has a wider exponent range.
+    let (up, _, _) = neighbours::<{ MPFR_53_BITS }>(f64::MAX, 0.0);
+    assert!(up > f64::MAX);
+    let (up, _, _) = neighbours::<{ MPFR_53_BITS }>(0.0, 0.0);
+    assert!(up > 0.0 && up < least);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn twofloat_neighbours() {
+    type UniTwoFloat = UniFloat<{ UniFloatChoice::TwoFloat }>;
+    let mut one = UniTwoFloat::from_f64(1.0);
+    one.copied();
+    let (mut up, mut down) = (one.next_up(), one.next_down());
+    up.copied();
+    down.copied();
+    assert_eq!((up.twofloats[0].hi(), up.twofloats[0].lo()), (1.0, f64::from_bits(1)));
+    assert_eq!((down.twofloats[0].hi(), down.twofloats[0].lo()), (1.0, -f64::from_bits(1)));
+    assert!(down < one && one < up);
+    let mut toward = up.next_toward(&one);
+    toward.copied();
+    assert!(toward == one);
+    let (up, down, _) = neighbours::<{ UniFloatChoice::TwoFloat }>(f64::INFINITY, 0.0);
+    assert_eq!((up, down), (f64::INFINITY, f64::MAX));
+}
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+fn ulp<const C: UniFloatChoice>(value: f64) -> f64 where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut float = UniFloat::<C>::from_f64(value);
+    float.copied();
+    let mut ulp = float.ulp();
+    ulp.copied();
+    ulp.to_f64_nearest()
+}
+
+/// `x + low` and `y`, both as `C` (where `low` is added exactly, as for TwoFloat).
+fn ulp_distance<const C: UniFloatChoice>(x: f64, low: f64, y: f64) -> Option<u64> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut float_x, mut float_low, mut float_y) = (UniFloat::<C>::from_f64(x), UniFloat::<C>::from_f64(low),
+        UniFloat::<C>::from_f64(y));
+    float_x.copied();
+    float_low.copied();
+    float_y.copied();
+    float_x += &float_low;
+    let distance = float_x.ulp_distance(&float_y);
+    assert_eq!(float_y.ulp_distance(&float_x), distance);
+    distance
+}
+
+/// 2^n.
+fn two_pow(n: i32) -> f64 {
+    libm::ldexp(1.0, n)
+}
+
+#[test]
+fn f32_ulps() {
+    assert_eq!(ulp::<{ UniFloatChoice::F32 }>(1.0), two_pow(-23));
+    assert_eq!(ulp::<{ UniFloatChoice::F32 }>(-3.0), two_pow(-22));
+    assert_eq!(ulp::<{ UniFloatChoice::F32 }>(-0.0), two_pow(-149));
+    assert_eq!(ulp::<{ UniFloatChoice::F32 }>(f32::MAX as f64), two_pow(104));
+    assert_eq!(ulp::<{ UniFloatChoice::F32 }>(f64::NEG_INFINITY), f64::INFINITY);
+    assert!(ulp::<{ UniFloatChoice::F32 }>(f64::NAN).is_nan());
+
+    assert_eq!(ulp_distance::<{ UniFloatChoice::F32 }>(1.0, 0.0, 1.0 + f32::EPSILON as f64), Some(1));
+    assert_eq!(ulp_distance::<{ UniFloatChoice::F32 }>(1.0, 0.0, 2.0), Some(1 << 23));
+    assert_eq!(ulp_distance::<{ UniFloatChoice::F32 }>(-two_pow(-149), 0.0, two_pow(-149)), Some(2));
+    assert_eq!(ulp_distance::<{ UniFloatChoice::F32 }>(0.0, 0.0, -0.0), Some(0));
+    assert_eq!(ulp_distance::<{ UniFloatChoice::F32 }>(f64::INFINITY, 0.0, f64::INFINITY), Some(0));
+    assert_eq!(ulp_distance::<{ UniFloatChoice::F32 }>(f32::MAX as f64, 0.0, f64::INFINITY), None);
+    assert_eq!(ulp_distance::<{ UniFloatChoice::F32 }>(f64::NAN, 0.0, 1.0), None);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn f64_and_mpfr_ulps() {
+    for &(value, expected) in [(1.0, two_pow(-52)), (-3.0, two_pow(-51)), (two_pow(-1050), two_pow(-1074))].iter() {
+        assert_eq!(ulp::<{ UniFloatChoice::F64 }>(value), expected);
+    }
+    assert_eq!(ulp::<{ UniFloatChoice::F64 }>(f64::MAX), two_pow(971));
+    assert_eq!(ulp::<{ MPFR_53_BITS }>(-3.0), two_pow(-51));
+    assert_eq!(ulp::<{ MPFR_100_BITS }>(1.0), two_pow(-99));
+    // The least value of MPFR's exponent range, far below f64's.
+    let mut zero = UniFloat::<{ MPFR_100_BITS }>::from_f64(0.0);
+    zero.copied();
+    let mut least = zero.ulp();
+    least.copied();
+    assert!(least.is_normal() && least.is_sign_positive() && least.to_f64_nearest() == 0.0);
+
+    let one_step = 1.0 + f64::EPSILON;
+    for &(x, y, expected) in [(1.0, one_step, 1), (1.0, 2.0, 1 << 52), (1.0, 3.0, 3 << 51), (-2.0, -1.0, 1 << 52)].iter() {
+        assert_eq!(ulp_distance::<{ UniFloatChoice::F64 }>(x, 0.0, y), Some(expected), "{} {}", x, y);
+        assert_eq!(ulp_distance::<{ MPFR_53_BITS }>(x, 0.0, y), Some(expected), "{} {}", x, y);
+    }
+    assert_eq!(ulp_distance::<{ UniFloatChoice::F64 }>(-1.0, 0.0, 1.0), Some(2 * 0x3FF0_0000_0000_0000));
+    // Across zero, MPFR counts through its whole exponent range.
+    assert_eq!(ulp_distance::<{ MPFR_53_BITS }>(-1.0, 0.0, 1.0), None);
+    assert_eq!(ulp_distance::<{ MPFR_100_BITS }>(1.0, two_pow(-99), 1.0), Some(1));
+    assert_eq!(ulp_distance::<{ MPFR_100_BITS }>(1.0, 0.0, 2.0), None);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn twofloat_ulps() {
+    assert_eq!(ulp::<{ UniFloatChoice::TwoFloat }>(1.0), two_pow(-105));
+    assert_eq!(ulp::<{ UniFloatChoice::TwoFloat }>(-0.75), two_pow(-106));
+    assert_eq!(ulp::<{ UniFloatChoice::TwoFloat }>(two_pow(-1000)), two_pow(-1074));
+
+    // Just below 1, the binade is that of 0.5.
+    let mut below_one = UniFloat::<{ UniFloatChoice::TwoFloat }>::from_f64(1.0);
+    below_one.copied();
+    let mut tiny = UniFloat::<{ UniFloatChoice::TwoFloat }>::from_f64(-two_pow(-110));
+    tiny.copied();
+    below_one += &tiny;
+    let mut ulp = below_one.ulp();
+    ulp.copied();
+    assert_eq!(ulp.to_f64_nearest(), two_pow(-106));
+
+    assert_eq!(ulp_distance::<{ UniFloatChoice::TwoFloat }>(1.0, two_pow(-105), 1.0), Some(1));
+    assert_eq!(ulp_distance::<{ UniFloatChoice::TwoFloat }>(1.0, -two_pow(-106), 1.0), Some(1));
+    assert_eq!(ulp_distance::<{ UniFloatChoice::TwoFloat }>(0.0, two_pow(-1074), -0.0), Some(1));
+    // Off the 106-bit grid: less than a step.
+    assert_eq!(ulp_distance::<{ UniFloatChoice::TwoFloat }>(1.0, two_pow(-1074), 1.0), Some(0));
+    assert_eq!(ulp_distance::<{ UniFloatChoice::TwoFloat }>(1.0, 0.0, 2.0), None);
+    assert_eq!(ulp_distance::<{ UniFloatChoice::TwoFloat }>(f64::INFINITY, 0.0, f64::MAX), None);
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "num-traits")]
+
+use {core::num::FpCategory, gmp_mpfr_sys::mpfr, num_traits::{Float, FromPrimitive, Num, NumCast, One, ToPrimitive, Zero}};
+use crate::{FixedOverflow, MpfrBounds, UniFloat, UniFloatChoice, UniNum, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Generic code, as num_traits users write it: the integral of `f` over [`a`, `b`] by Simpson's
+/// rule, with `n` (even) intervals.
+fn simpson<F: Float>(f: impl Fn(F) -> F, a: F, b: F, n: usize) -> F {
+    let h = (b - a) / F::from(n).unwrap();
+    let (two, four) = (F::from(2).unwrap(), F::from(4).unwrap());
+    let mut sum = f(a) + f(b);
+    for i in 1..n {
+        let weight = if i % 2 == 1 { four } else { two };
+        sum = sum + weight * f(a + h * F::from(i).unwrap());
+    }
+    sum * h / F::from(3).unwrap()
+}
+
+fn num<const C: UniFloatChoice>(value: f64) -> UniNum<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    <UniNum<C> as NumCast>::from(value).unwrap()
+}
+
+fn assert_num_traits<const C: UniFloatChoice>(tolerance: f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    // The integral of sin over [0, π] is 2.
+    let pi = UniNum::new(UniFloat::<C>::pi().copied());
+    let integral = simpson(|x: UniNum<C>| x.sin(), UniNum::zero(), pi, 128);
+    assert!((integral.to_f64().unwrap() - 2.0).abs() < tolerance);
+
+    // Values by value, without .copied(), and compared by reference.
+    let (two, three) = (num::<C>(2.0), num::<C>(3.0));
+    let sum = two + three;
+    assert!(sum == num(5.0) && sum > three && two < sum);
+    let mut x = sum;
+    x *= two;
+    x -= three;
+    assert_eq!(x.to_f64(), Some(7.0));
+    assert_eq!((x % two).to_f64(), Some(1.0));
+    assert_eq!((-x).to_f64(), Some(-7.0));
+    assert_eq!(two.powi(10).to_f64(), Some(1024.0));
+    assert_eq!(two.mul_add(three, x).to_f64(), Some(13.0));
+    assert_eq!(num::<C>(16.0).sqrt().to_f64(), Some(4.0));
+    assert_eq!(two.max(three), three);
+    assert_eq!(num::<C>(-2.5).round().to_f64(), Some(-3.0));
+    assert_eq!(num::<C>(0.0).recip(), UniNum::infinity());
+    assert!(UniNum::<C>::nan().is_nan() && UniNum::<C>::neg_zero().is_sign_negative());
+    assert_eq!(UniNum::<C>::min_positive_value().classify(), FpCategory::Normal);
+    assert_eq!((UniNum::<C>::one() + UniNum::epsilon()).classify(), FpCategory::Normal);
+    assert!(UniNum::<C>::one() + UniNum::epsilon() > UniNum::one());
+
+    // Conversions.
+    assert_eq!(num::<C>(-7.9).to_i64(), Some(-7));
+    assert_eq!(num::<C>(-1.0).to_u64(), None);
+    assert_eq!(UniNum::<C>::nan().to_i64(), None);
+    assert_eq!(<UniNum<C> as NumCast>::from(7u8).unwrap().to_i32(), Some(7));
+    assert_eq!(<UniNum<C> as NumCast>::from(2.5f32).unwrap().to_f64(), Some(2.5));
+    assert_eq!(UniNum::<C>::from_str_radix("-1.1", 2).unwrap().to_f64(), Some(-1.5));
+    assert_eq!(num::<C>(1.5).integer_decode(), 1.5f64.integer_decode());
+    assert_eq!(UniNum::<C>::from_i8(-100).and_then(|x| x.to_i8()), Some(-100));
+    assert_eq!(UniNum::<C>::from_f32(f32::MAX).and_then(|x| x.to_f32()), Some(f32::MAX));
+    assert_eq!(UniNum::<C>::from_f64(f64::NEG_INFINITY).and_then(|x| x.to_f64()), Some(f64::NEG_INFINITY));
+    assert!(UniNum::<C>::from_f64(f64::NAN).unwrap().to_f32().unwrap().is_nan());
+    assert_eq!(num::<C>(300.0).to_u8(), None);
+}
+
+#[test]
+fn num_traits_float() {
+    assert_num_traits::<{ UniFloatChoice::F32 }>(1e-4);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_num_traits::<{ UniFloatChoice::F64 }>(1e-7);
+        assert_num_traits::<{ UniFloatChoice::TwoFloat }>(1e-7);
+        assert_num_traits::<{ MPFR_100_BITS }>(1e-7);
+
+        // Integers that f64 can't hold exactly.
+        let big = <UniNum<{ MPFR_100_BITS }> as NumCast>::from(u64::MAX).unwrap();
+        assert_eq!(big.to_u64(), Some(u64::MAX));
+        assert_eq!(<UniNum<{ MPFR_100_BITS }> as NumCast>::from(i64::MIN + 1).unwrap().to_i64(), Some(i64::MIN + 1));
+    }
+}
+
+#[test]
+fn num_traits_casts_out_of_range() {
+    type Single = UniNum<{ UniFloatChoice::F32 }>;
+    assert_eq!(Single::from_f64(1e300), None);
+    assert_eq!(Single::from_f64(-1e300), None);
+    assert_eq!(Single::from_u128(u128::MAX), None);
+    assert_eq!(<Single as NumCast>::from(1e39), None);
+    assert_eq!(Single::from_f64(f64::INFINITY), Some(Single::infinity()));
+    assert_eq!(Single::from_u128(1 << 127).and_then(|x| x.to_u128()), Some(1 << 127));
+    assert_eq!(Single::max_value().to_f64(), Some(f32::MAX as f64));
+    #[cfg(not(feature = "f32_only"))] {
+        type Double = UniNum<{ UniFloatChoice::F64 }>;
+        assert_eq!(Double::from_f64(1e300).and_then(|x| x.to_f32()), None);
+        assert_eq!(Double::from_u128(u128::MAX).and_then(|x| x.to_u128()), None);
+        type Wide = UniNum<{ MPFR_100_BITS }>;
+        assert_eq!(Wide::max_value().to_f64(), None);
+        assert_eq!(Wide::max_value().to_i128(), None);
+        assert!(Wide::max_value().is_finite());
+
+        // Fixed-point formats neither saturate nor wrap.
+        type Q16 = UniNum<{ UniFloatChoice::fixed(16, 16, FixedOverflow::Saturating) }>;
+        assert_eq!(Q16::from_i32(32767).and_then(|x| x.to_i32()), Some(32767));
+        assert_eq!(Q16::from_i32(32768), None);
+        assert_eq!(Q16::from_i32(-32767).and_then(|x| x.to_i32()), Some(-32767));
+        assert_eq!(Q16::from_i32(-32768), None);
+        assert_eq!(Q16::from_f64(32767.5).and_then(|x| x.to_f64()), Some(32767.5));
+        assert_eq!(Q16::from_f64(40000.0), None);
+        type I8 = UniNum<{ UniFloatChoice::fixed(8, 0, FixedOverflow::Wrapping) }>;
+        assert_eq!(I8::from_u8(127).and_then(|x| x.to_u8()), Some(127));
+        assert_eq!(I8::from_u8(200), None);
+        assert_eq!(<I8 as NumCast>::from(-129.0), None);
+    }
+}
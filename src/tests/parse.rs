@@ -0,0 +1,87 @@
+#[cfg(not(feature = "f32_only"))]
+use gmp_mpfr_sys::mpfr;
+use crate::{MpfrBounds, ParseErrorKind, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Each tuple: (string, expected), where `expected` is exact in all choices.
+const VALID: [(&str, f64); 8] = [
+    ("0", 0.0),
+    ("-1.5", -1.5),
+    ("+.25", 0.25),
+    ("3.", 3.0),
+    ("12.5e2", 1250.0),
+    ("625E-4", 0.0625),
+    ("-inf", f64::NEG_INFINITY),
+    ("Infinity", f64::INFINITY),
+];
+
+/// Each tuple: (string, kind, position).
+const INVALID: [(&str, ParseErrorKind, usize); 7] = [
+    ("", ParseErrorKind::Empty, 0),
+    ("-", ParseErrorKind::MissingDigits, 1),
+    (".", ParseErrorKind::MissingDigits, 1),
+    ("1e", ParseErrorKind::MissingDigits, 2),
+    ("1.2.3", ParseErrorKind::InvalidCharacter, 3),
+    ("1e+x", ParseErrorKind::InvalidCharacter, 3),
+    (" 1", ParseErrorKind::InvalidCharacter, 0),
+];
+
+fn assert_parse<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(string, expected) in VALID.iter() {
+        let mut float: UniFloat<C> = string.parse().unwrap();
+        float.copied();
+        assert_eq!(float.to_f64_nearest(), expected, "{}", string);
+    }
+    let mut float: UniFloat<C> = "nan".parse().unwrap();
+    float.copied();
+    assert!(float.to_f64_nearest().is_nan());
+
+    for &(string, kind, position) in INVALID.iter() {
+        let error = string.parse::<UniFloat<C>>().unwrap_err();
+        assert_eq!((error.kind(), error.position()), (kind, position), "{:?}", string);
+    }
+}
+
+#[test]
+fn parse() {
+    assert_parse::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_parse::<{ UniFloatChoice::F64 }>();
+        assert_parse::<{ UniFloatChoice::TwoFloat }>();
+        assert_parse::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn parse_rounds_correctly() {
+    // 0.1 isn't exact in binary. Parsing at 100 bits must match MPFR's own division.
+    let mut tenth: UniFloat<{ MPFR_100_BITS }> = "0.1".parse().unwrap();
+    tenth.copied();
+    let mut expected = UniFloat::<{ MPFR_100_BITS }>::from_f64(1.0);
+    expected.copied();
+    unsafe {
+        mpfr::div_ui(expected.mpfr_mut(), expected.mpfr_src().as_ptr(), 10, mpfr::rnd_t::RNDN);
+        assert_ne!(mpfr::equal_p(tenth.mpfr_src().as_ptr(), expected.mpfr_src().as_ptr()), 0);
+    }
+
+    // 1 + 2^-60 is exact in double-double, but not in f64.
+    let mut twofloat: crate::UniTwoFloat = "1.000000000000000000867361737988403547205962240695953369140625".parse().unwrap();
+    twofloat.copied();
+    assert_eq!(twofloat.twofloats[0].hi(), 1.0);
+    assert_eq!(twofloat.twofloats[0].lo(), libm::ldexp(1.0, -60));
+
+    let mut huge: crate::UniF64 = "1e400".parse().unwrap();
+    huge.copied();
+    assert_eq!(huge.to_f64_nearest(), f64::INFINITY);
+}
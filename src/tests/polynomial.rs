@@ -0,0 +1,123 @@
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+#[cfg(not(feature = "f32_only"))]
+const MPFR_300_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(300)
+};
+
+fn coefficients<const C: UniFloatChoice>(values: &[f64]) -> ([UniFloat<C>; 4], usize) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut coefficients = [UniFloat::<C>::NAN; 4];
+    for (coefficient, &value) in coefficients.iter_mut().zip(values) {
+        *coefficient = UniFloat::<C>::from_f64(value);
+    }
+    (coefficients, values.len())
+}
+
+fn eval<const C: UniFloatChoice>(values: &[f64], x: f64) -> f64 where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut coefficients, count) = coefficients::<C>(values);
+    for coefficient in coefficients.iter_mut() {
+        coefficient.copied();
+    }
+    let mut x = UniFloat::<C>::from_f64(x);
+    x.copied();
+    let mut result = UniFloat::eval_polynomial(&coefficients[..count], &x);
+    result.copied();
+    result.to_f64_nearest()
+}
+
+fn assert_eval_polynomial<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    assert_eq!(eval::<C>(&[1.0, 2.0, 3.0], 2.0), 17.0);
+    assert_eq!(eval::<C>(&[-1.0, 3.0, -3.0, 1.0], 3.0), 8.0);
+    assert_eq!(eval::<C>(&[5.0], 1e30), 5.0);
+    assert_eq!(eval::<C>(&[], 2.0), 0.0);
+    assert!(eval::<C>(&[1.0, 1.0], f64::NAN).is_nan());
+}
+
+#[test]
+fn eval_polynomial() {
+    assert_eval_polynomial::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_eval_polynomial::<{ UniFloatChoice::F64 }>();
+        assert_eval_polynomial::<{ UniFloatChoice::TwoFloat }>();
+        assert_eval_polynomial::<{ UniFloatChoice::QuadFloat }>();
+        assert_eval_polynomial::<{ MPFR_100_BITS }>();
+    }
+}
+
+/// The error against the polynomial at 300 bits is within the bound, and the bound is within
+/// `max_bound`.
+#[cfg(not(feature = "f32_only"))]
+fn assert_error_bound<const C: UniFloatChoice>(values: &[f64], x: f64, max_bound: f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut coefficients, count) = coefficients::<C>(values);
+    let mut exact_coefficients = [UniFloat::<{ MPFR_300_BITS }>::NAN; 4];
+    for (coefficient, exact) in coefficients.iter_mut().zip(exact_coefficients.iter_mut()) {
+        coefficient.copied();
+        *exact = coefficient.convert();
+        exact.copied();
+    }
+    let mut x = UniFloat::<C>::from_f64(x);
+    x.copied();
+    let mut exact_x = x.convert::<{ MPFR_300_BITS }>();
+    exact_x.copied();
+    let (mut result, mut bound) = UniFloat::eval_polynomial_with_error_bound(&coefficients[..count], &x);
+    result.copied();
+    bound.copied();
+    let mut exact = UniFloat::eval_polynomial(&exact_coefficients[..count], &exact_x);
+    exact.copied();
+    let mut error = result.convert::<{ MPFR_300_BITS }>();
+    error.copied();
+    error -= &exact;
+    let (error, bound) = (libm::fabs(error.to_f64_nearest()), bound.to_f64_nearest());
+    assert!(error <= bound && bound <= max_bound, "{} {}", error, bound);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn eval_polynomial_with_error_bound() {
+    // exp(x) to the third order, and (x - 1)^3 near 1 (with heavy cancellation).
+    let (taylor, cube) = ([1.0, 1.0, 0.5, 1.0 / 6.0], [-1.0, 3.0, -3.0, 1.0]);
+    let near_one = 1.0 + libm::ldexp(1.0, -20);
+    assert_error_bound::<{ UniFloatChoice::F32 }>(&taylor, 0.1, 1e-6);
+    assert_error_bound::<{ UniFloatChoice::F32 }>(&cube, near_one, 1e-5);
+    assert_error_bound::<{ UniFloatChoice::F64 }>(&taylor, 0.1, 1e-15);
+    assert_error_bound::<{ UniFloatChoice::F64 }>(&cube, near_one, 1e-14);
+    assert_error_bound::<{ UniFloatChoice::TwoFloat }>(&taylor, 0.1, 1e-29);
+    assert_error_bound::<{ UniFloatChoice::QuadFloat }>(&taylor, 0.1, 1e-60);
+    assert_error_bound::<{ MPFR_100_BITS }>(&taylor, 0.1, 1e-28);
+    assert_error_bound::<{ MPFR_100_BITS }>(&cube, near_one, 1e-28);
+
+    let mut one = UniFloat::<{ UniFloatChoice::F64 }>::ONE;
+    one.copied();
+    let (mut exact, mut bound) = UniFloat::eval_polynomial_with_error_bound(&[], &one);
+    exact.copied();
+    bound.copied();
+    assert_eq!((exact.to_f64_nearest(), bound.to_f64_nearest()), (0.0, 0.0));
+}
@@ -0,0 +1,180 @@
+extern crate std;
+
+use std::format;
+use crate::{UniFloat, UniFloatChoice, UniPosit, valid_posit, Assert, IsTrue};
+#[cfg(not(feature = "f32_only"))]
+use crate::MpfrBounds;
+
+type Posit8 = UniPosit<8, 0>;
+type Posit16 = UniPosit<16, 1>;
+type Posit32 = UniPosit<32, 2>;
+
+const VALUES: [f64; 10] = [1.0, -2.5, 0.3, 7.0, -0.0625, 1000.0, 1e-5, 3.0e5, 0.0, 1.0 / 3.0];
+
+/// Each operation on posits must round the exact result once. The results in f64 are exact (or
+/// too far from ties to round twice) for posits of up to 16 bits.
+fn assert_same_as_f64<const NBITS: u32, const ES: u32>() where
+Assert<{ valid_posit(NBITS, ES) }>: IsTrue,
+{
+    for &x in VALUES.iter() {
+        let x = UniPosit::<NBITS, ES>::from_f64(x);
+        assert!(UniPosit::<NBITS, ES>::from_f64(x.to_f64()) == x);
+        assert!(x.abs().sqrt() == UniPosit::from_f64(libm::sqrt(x.abs().to_f64())), "sqrt({})", x);
+        for &y in VALUES.iter() {
+            let y = UniPosit::<NBITS, ES>::from_f64(y);
+            let (a, b) = (x.to_f64(), y.to_f64());
+            assert!(x + y == UniPosit::from_f64(a + b), "{} + {}", x, y);
+            assert!(x - y == UniPosit::from_f64(a - b), "{} - {}", x, y);
+            assert!(x * y == UniPosit::from_f64(a * b), "{} * {}", x, y);
+            if !y.is_zero() {
+                assert!(x / y == UniPosit::from_f64(a / b), "{} / {}", x, y);
+            }
+        }
+    }
+}
+
+#[test]
+fn posit_arithmetic() {
+    assert_same_as_f64::<8, 0>();
+    assert_same_as_f64::<16, 1>();
+    assert_same_as_f64::<12, 2>();
+
+    assert_eq!(Posit16::from_f64(1.0).to_bits(), Posit16::ONE.to_bits());
+    assert_eq!((Posit16::ONE / Posit16::from_f64(3.0)).to_bits(), 0x2555);
+    assert_eq!(Posit16::from_f64(2.0).sqrt().to_bits(), 0x46a1);
+    assert!(Posit16::from_f64(4.0).sqrt() == Posit16::from_f64(2.0));
+    assert!(Posit16::from_f64(-4.0).sqrt().is_nar());
+    assert!((Posit16::ONE / Posit16::ZERO).is_nar());
+    assert!((Posit16::ZERO / Posit16::ONE).is_zero());
+    assert!((Posit16::NAR + Posit16::ONE).is_nar());
+    assert!((Posit16::NAR * Posit16::ZERO).is_nar());
+    assert!((Posit16::ONE - Posit16::ONE).is_zero());
+    assert!(-Posit16::NAR == Posit16::NAR && -Posit16::ZERO == Posit16::ZERO);
+    assert!((-Posit16::ONE).is_sign_negative() && (-Posit16::ONE).abs() == Posit16::ONE);
+
+    // Saturation, rather than overflow or underflow.
+    assert_eq!(Posit8::MAX.to_f64(), 64.0);
+    assert_eq!(Posit8::MIN_POSITIVE.to_f64(), 1.0 / 64.0);
+    assert_eq!(Posit32::MAX.to_f64(), f64::from_bits((1023 + 120) << 52));
+    assert!(Posit8::from_f64(1000.0) == Posit8::MAX);
+    assert!(Posit8::MAX * Posit8::MAX == Posit8::MAX);
+    assert!(Posit8::from_f64(1e-10) == Posit8::MIN_POSITIVE);
+    assert!(Posit8::from_f64(-1e-10) == -Posit8::MIN_POSITIVE);
+    assert!(Posit8::MIN_POSITIVE / Posit8::MAX == Posit8::MIN_POSITIVE);
+    assert!(Posit8::from_f64(f64::INFINITY).is_nar() && Posit8::from_f64(f64::NAN).is_nar());
+    // Ties to even: the spacing at 1 is 1/32.
+    assert_eq!(Posit8::from_f64(1.0 + 1.0 / 64.0).to_f64(), 1.0);
+    assert_eq!(Posit8::from_f64(1.0 + 3.0 / 64.0).to_f64(), 1.0625);
+
+    // Tapered accuracy: better than f32 near 1.
+    let tenth = Posit32::from_f64(0.1).to_f64();
+    assert!((tenth - 0.1).abs() < (0.1f32 as f64 - 0.1).abs());
+}
+
+#[test]
+fn posit_order() {
+    let ordered = [Posit16::NAR, -Posit16::MAX, -Posit16::ONE, -Posit16::MIN_POSITIVE, Posit16::ZERO,
+        Posit16::MIN_POSITIVE, Posit16::ONE, Posit16::MAX];
+    for pair in ordered.windows(2) {
+        assert!(pair[0] < pair[1]);
+    }
+    assert!(Posit16::NAR == Posit16::NAR);
+    assert!(Posit16::from_bits(0x1_0000 | Posit16::ONE.to_bits()) == Posit16::ONE);
+    assert_eq!(format!("{}", Posit16::from_f64(-2.5)), "-2.5");
+    assert_eq!(format!("{}", Posit16::NAR), "NaR");
+    assert_eq!(Posit16::default().to_bits(), 0);
+}
+
+#[test]
+fn posit_conversions() {
+    let third = Posit32::ONE / Posit32::from_f64(3.0);
+    let mut single = UniFloat::<{ UniFloatChoice::F32 }>::from_f64(1.0 / 3.0);
+    assert!(Posit16::from_unifloat(single.copied()) == Posit16::ONE / Posit16::from_f64(3.0));
+    let mut back = third.to_unifloat::<{ UniFloatChoice::F32 }>();
+    assert_eq!(back.copied().to_f64_nearest(), third.to_f64() as f32 as f64);
+    let mut nar = Posit32::NAR.to_unifloat::<{ UniFloatChoice::F32 }>();
+    assert!(nar.copied().is_nan());
+
+    #[cfg(not(feature = "f32_only"))] {
+        const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+            bounds: MpfrBounds::for_precision_binary(100)
+        };
+        let mut exact = third.to_unifloat::<{ UniFloatChoice::F64 }>();
+        assert_eq!(exact.copied().to_f64_nearest(), third.to_f64());
+        let mut precise = UniFloat::<{ MPFR_100_BITS }>::ONE;
+        precise.copied();
+        precise /= UniFloat::<{ MPFR_100_BITS }>::from_f64(3.0);
+        assert!(Posit32::from_unifloat(&precise) == third);
+        let mut double = UniFloat::<{ UniFloatChoice::TwoFloat }>::from_f64(1.0 / 3.0);
+        assert!(Posit32::from_unifloat(double.copied()) == third);
+        let mut huge = UniFloat::<{ MPFR_100_BITS }>::from_f64(1e300);
+        huge.copied();
+        huge *= UniFloat::<{ MPFR_100_BITS }>::from_f64(1e300);
+        assert!(Posit32::from_unifloat(&huge) == Posit32::MAX);
+        let mut infinity = UniFloat::<{ UniFloatChoice::F64 }>::INFINITY;
+        assert!(Posit32::from_unifloat(infinity.copied()).is_nar());
+    }
+}
+
+#[cfg(not(feature = "f32_only"))]
+#[test]
+fn posit_choice() {
+    const POSIT16: UniFloatChoice = UniFloatChoice::posit(16, 1);
+    type UniPosit16 = UniFloat<{ POSIT16 }>;
+    let uni = |x: Posit16| {
+        let mut value = x.to_unifloat::<{ POSIT16 }>();
+        value.copied();
+        value
+    };
+
+    // The same roundings as `UniPosit`.
+    for &x in VALUES.iter() {
+        let x = Posit16::from_f64(x);
+        assert!(Posit16::from_unifloat(&uni(x.abs()).sqrt()) == x.abs().sqrt(), "sqrt({})", x);
+        for &y in VALUES.iter() {
+            let y = Posit16::from_f64(y);
+            let (mut sum, mut difference, mut product, mut quotient) = (uni(x), uni(x), uni(x), uni(x));
+            sum += &uni(y);
+            difference -= &uni(y);
+            product *= &uni(y);
+            quotient /= &uni(y);
+            assert!(Posit16::from_unifloat(&sum) == x + y, "{} + {}", x, y);
+            assert!(Posit16::from_unifloat(&difference) == x - y, "{} - {}", x, y);
+            assert!(Posit16::from_unifloat(&product) == x * y, "{} * {}", x, y);
+            assert!(Posit16::from_unifloat(&quotient) == x / y, "{} / {}", x, y);
+        }
+    }
+
+    assert!(Posit16::from_unifloat(&UniPosit16::MAX) == Posit16::MAX);
+    assert!(Posit16::from_unifloat(&UniPosit16::MIN_POSITIVE) == Posit16::MIN_POSITIVE);
+    let mut epsilon = UniPosit16::ONE.next_up();
+    epsilon.copied();
+    epsilon -= &UniPosit16::ONE;
+    assert!(Posit16::from_unifloat(&epsilon) == Posit16::from_unifloat(&UniPosit16::EPSILON));
+    // Saturation, and NaN for NaR.
+    let mut huge = uni(Posit16::MAX);
+    huge *= &uni(Posit16::MAX);
+    assert!(Posit16::from_unifloat(&huge) == Posit16::MAX);
+    let mut tiny = uni(-Posit16::MIN_POSITIVE);
+    tiny /= &uni(Posit16::MAX);
+    assert!(Posit16::from_unifloat(&tiny) == -Posit16::MIN_POSITIVE);
+    let mut nar = uni(Posit16::ONE);
+    nar /= &UniPosit16::ZERO;
+    assert!(nar.is_nan());
+
+    assert!(Posit16::from_unifloat(&UniPosit16::from_literal("0.1")) == Posit16::from_f64(0.1));
+    assert!(Posit16::from_unifloat(&UniPosit16::from_literal("-1e30")) == -Posit16::MAX);
+    assert!(Posit16::from_unifloat(&UniPosit16::from_literal("1e-30")) == Posit16::MIN_POSITIVE);
+    assert!(UniPosit16::from_literal("inf").copied().is_nan());
+    let mut parsed: UniPosit16 = "0.1".parse().unwrap();
+    parsed += &UniPosit16::ZERO;
+    assert!(Posit16::from_unifloat(&parsed) == Posit16::from_f64(0.1));
+
+    // Neighbours are those of the bit patterns.
+    assert!(Posit16::from_unifloat(&UniPosit16::ZERO.next_up()) == Posit16::MIN_POSITIVE);
+    assert!(Posit16::from_unifloat(&uni(Posit16::from_f64(3.0)).next_down()) == Posit16::from_bits(0x57ff));
+    assert!(UniPosit16::MAX.next_up().copied().is_nan());
+    assert_eq!(uni(-Posit16::MIN_POSITIVE).ulp_distance(&uni(Posit16::MIN_POSITIVE)), Some(2));
+    assert_eq!(uni(-Posit16::ONE).ulp_distance(&uni(Posit16::ONE)), Some(2 * Posit16::ONE.to_bits() as u64));
+    assert!(Posit16::from_unifloat(&uni(Posit16::ONE).ulp()) == Posit16::from_unifloat(&UniPosit16::EPSILON));
+}
@@ -0,0 +1,137 @@
+#[cfg(not(feature = "f32_only"))]
+use gmp_mpfr_sys::mpfr;
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Each tuple: (base, exponent, power). The powers are exact.
+const POWERS: [(f64, f64, f64); 12] = [
+    (2.0, 10.0, 1024.0),
+    (-2.0, 3.0, -8.0),
+    (4.0, 0.5, 2.0),
+    (2.0, -2.0, 0.25),
+    (f64::NAN, 0.0, 1.0),
+    (1.0, f64::NAN, 1.0),
+    (-8.0, 1.0 / 3.0, f64::NAN),
+    (-0.0, -1.0, f64::NEG_INFINITY),
+    (0.0, 3.0, 0.0),
+    (f64::INFINITY, -2.0, 0.0),
+    (-1.0, f64::INFINITY, 1.0),
+    (0.5, f64::INFINITY, 0.0),
+];
+
+fn assert_powers<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(base, exponent, expected) in POWERS.iter() {
+        let (mut float, mut exp) = (UniFloat::<C>::from_f64(base), UniFloat::<C>::from_f64(exponent));
+        float.copied();
+        exp.copied();
+        let mut power = float.pow(&exp);
+        power.copied();
+        let power = power.to_f64_nearest();
+        assert!(power.to_bits() == expected.to_bits() || (power.is_nan() && expected.is_nan()), "{} {}", base, exponent);
+
+        if exponent.is_finite() && exponent == libm::trunc(exponent) {
+            let mut power = float.powi(exponent as i64);
+            power.copied();
+            let power = power.to_f64_nearest();
+            assert!(power.to_bits() == expected.to_bits() || (power.is_nan() && expected.is_nan()), "{} {}", base, exponent);
+        }
+    }
+
+    // The sign of huge odd powers doesn't get lost.
+    let mut minus_one = UniFloat::<C>::from_f64(-1.0);
+    minus_one.copied();
+    for &(n, expected) in [(i64::MAX, -1.0), (i64::MIN, 1.0), (i64::MIN + 1, -1.0)].iter() {
+        let mut power = minus_one.powi(n);
+        power.copied();
+        assert_eq!(power.to_f64_nearest(), expected, "{}", n);
+    }
+}
+
+#[test]
+fn powers() {
+    assert_powers::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_powers::<{ UniFloatChoice::F64 }>();
+        assert_powers::<{ UniFloatChoice::TwoFloat }>();
+        assert_powers::<{ MPFR_100_BITS }>();
+    }
+}
+
+fn assert_checked_pow<const C: UniFloatChoice>(overflowing_exponent: f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut ten = UniFloat::<C>::from_f64(10.0);
+    ten.copied();
+    let mut exp = UniFloat::<C>::from_f64(overflowing_exponent);
+    exp.copied();
+    assert!(ten.checked_pow(&exp).is_none());
+
+    for &(base, exponent) in [(10.0, 2.0), (0.0, -1.0), (f64::INFINITY, 2.0), (10.0, f64::INFINITY), (f64::NAN, 1.0)].iter() {
+        let (mut float, mut exp) = (UniFloat::<C>::from_f64(base), UniFloat::<C>::from_f64(exponent));
+        float.copied();
+        exp.copied();
+        let mut power = float.checked_pow(&exp).unwrap();
+        power.copied();
+        let mut expected = float.pow(&exp);
+        expected.copied();
+        assert_eq!(power.to_f64_nearest().to_bits(), expected.to_f64_nearest().to_bits(), "{} {}", base, exponent);
+    }
+}
+
+#[test]
+fn checked_pow() {
+    assert_checked_pow::<{ UniFloatChoice::F32 }>(40.0);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_checked_pow::<{ UniFloatChoice::F64 }>(400.0);
+        assert_checked_pow::<{ UniFloatChoice::TwoFloat }>(400.0);
+        assert_checked_pow::<{ MPFR_100_BITS }>(1e300);
+    }
+}
+
+#[cfg(not(feature = "f32_only"))]
+const MPFR_200_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(200)
+};
+
+/// TwoFloat results have relative error below 1e-28 for integer exponents, and below 1e-26
+/// otherwise, compared to MPFR at 200 bits.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn pow_twofloat_accuracy() {
+    type UniMpfr200bit = UniFloat<{ MPFR_200_BITS }>;
+    for &(base, exponent, bound) in [(1.1, 100.0, 1e-28), (3.0, -7.0, 1e-28), (0.9, 1000.0, 1e-28),
+        (2.5, 0.3, 1e-26), (0.01, -2.75, 1e-26)].iter() {
+        let (mut twofloat, mut exp) = (crate::UniTwoFloat::from_f64(base), crate::UniTwoFloat::from_f64(exponent));
+        twofloat.copied();
+        exp.copied();
+        let mut result = twofloat.pow(&exp);
+        result.copied();
+        let (mut reference, mut reference_exp) = (UniMpfr200bit::from_f64(base), UniMpfr200bit::from_f64(exponent));
+        reference.copied();
+        reference_exp.copied();
+        let mut expected = reference.pow(&reference_exp);
+        expected.copied();
+        let error = unsafe {
+            let expected = expected.mpfr_mut();
+            let magnitude = mpfr::get_d(expected, mpfr::rnd_t::RNDN);
+            mpfr::sub_d(expected, expected, result.twofloats[0].hi(), mpfr::rnd_t::RNDN);
+            mpfr::sub_d(expected, expected, result.twofloats[0].lo(), mpfr::rnd_t::RNDN);
+            mpfr::get_d(expected, mpfr::rnd_t::RNDN) / magnitude
+        };
+        assert!(libm::fabs(error) < bound, "{} {}: {}", base, exponent, error);
+    }
+}
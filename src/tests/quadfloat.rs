@@ -0,0 +1,160 @@
+#![cfg(not(feature = "f32_only"))]
+
+extern crate std;
+
+use std::string::String;
+use crate::{Conversion, MpfrBounds, RoundingMode, UniFloat, UniFloatBounds, UniFloatBoundsBase, UniFloatBoundsToChoice,
+    UniFloatChoice, UniQuadFloat};
+
+const MPFR_300_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(300)
+};
+
+/// log2 of the relative error of `x`, against `exact` (at 300 bits).
+fn error_log2(x: &UniQuadFloat, exact: &UniFloat<{ MPFR_300_BITS }>) -> f64 {
+    let mut x = x.convert::<{ MPFR_300_BITS }>();
+    x.copied();
+    let mut error = &x - exact;
+    error.copied();
+    let mut relative = &error / exact;
+    relative.copied();
+    libm::log2(relative.abs().to_f64_nearest())
+}
+
+#[test]
+fn quadfloat_is_between_twofloat_and_mpfr() {
+    let binary = |precision, min_exponent, max_exponent| UniFloatBounds::<{ UniFloatBoundsBase::BINARY }>::new(
+        precision, min_exponent, max_exponent);
+    assert_eq!(binary(106, -1021, 1024).to_choice(), UniFloatChoice::TwoFloat);
+    assert_eq!(binary(150, -1021, 1024).to_choice(), UniFloatChoice::QuadFloat);
+    assert_eq!(binary(212, -1021, 1024).to_choice(), UniFloatChoice::QuadFloat);
+    assert_eq!(binary(213, -1021, 1024).to_choice(),
+        UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(213) });
+    // Exponents beyond f64.
+    assert_eq!(binary(150, -1021, 1025).to_choice(),
+        UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(150) });
+}
+
+#[test]
+fn quadfloat_native_arithmetic() {
+    // 1 + 2^-100 + 2^-200 is beyond TwoFloat, but exact in QuadFloat.
+    let mut one = UniQuadFloat::ONE;
+    one.copied();
+    let mut small = UniQuadFloat::from_f64(libm::ldexp(1.0, -100));
+    small.copied();
+    let mut x = UniQuadFloat::from_f64(libm::ldexp(1.0, -200));
+    x.copied();
+    x += &one;
+    x += &small;
+    x -= &one;
+    x -= &small;
+    assert_eq!(x.to_f64_nearest(), libm::ldexp(1.0, -200));
+
+    let mut three = UniQuadFloat::from_f64(3.0);
+    three.copied();
+    let mut third = UniQuadFloat::ONE;
+    third.copied();
+    third /= &three;
+    let mut exact = UniFloat::<{ MPFR_300_BITS }>::ONE;
+    exact.copied();
+    let mut three_exact = UniFloat::<{ MPFR_300_BITS }>::from_f64(3.0);
+    three_exact.copied();
+    exact /= &three_exact;
+    assert!(error_log2(&third, &exact) < -207.0);
+    third *= &three;
+    third -= &one;
+    assert!(libm::fabs(third.to_f64_nearest()) < libm::ldexp(1.0, -207));
+}
+
+#[test]
+fn quadfloat_runs_the_rest_in_mpfr() {
+    let mut two = UniQuadFloat::from_f64(2.0);
+    two.copied();
+    let mut root = two.sqrt();
+    root.copied();
+    let mut exact = UniFloat::<{ MPFR_300_BITS }>::from_f64(2.0);
+    exact.copied();
+    let mut exact_root = exact.sqrt();
+    exact_root.copied();
+    // Rounded to 212 bits, so within half an ulp.
+    assert!(error_log2(&root, &exact_root) <= -212.0);
+
+    let mut up = two.sqrt_round(RoundingMode::Up).unwrap();
+    up.copied();
+    let mut down = two.sqrt_round(RoundingMode::Down).unwrap();
+    down.copied();
+    assert!(down.lt(&up) && (down.eq(&root) || up.eq(&root)));
+    let mut step = down.ulp();
+    step.copied();
+    let mut difference = &up - &down;
+    difference.copied();
+    assert!(difference.eq(&step));
+}
+
+#[test]
+fn quadfloat_specials() {
+    let mut two = UniQuadFloat::from_f64(2.0);
+    two.copied();
+    let mut big = UniQuadFloat::MAX;
+    big.copied();
+    big *= &two;
+    assert!(big.is_infinite() && big.is_sign_positive());
+
+    let mut zero = UniQuadFloat::NEG_ZERO;
+    zero.copied();
+    zero *= &two;
+    assert!(zero.to_f64_nearest() == 0.0 && zero.is_sign_negative());
+    zero /= &two;
+    assert!(zero.to_f64_nearest() == 0.0 && zero.is_sign_negative());
+
+    let mut nan = UniQuadFloat::ZERO;
+    nan.copied();
+    let mut zero = UniQuadFloat::ZERO;
+    zero.copied();
+    nan /= &zero;
+    assert!(nan.is_nan());
+
+    let mut epsilon = UniQuadFloat::EPSILON;
+    epsilon.copied();
+    assert_eq!(epsilon.to_f64_nearest(), libm::ldexp(1.0, -211));
+    let mut one = UniQuadFloat::ONE;
+    one.copied();
+    let mut step = one.ulp();
+    step.copied();
+    assert!(step.eq(&epsilon));
+}
+
+#[test]
+fn quadfloat_conversions() {
+    let mut max = UniQuadFloat::from(u128::MAX);
+    max.copied();
+    assert_eq!(max.to_u128(RoundingMode::Nearest), Conversion::Exact(u128::MAX));
+
+    // 1 + 2^-100 + 2^-200.
+    let mut x = UniQuadFloat::from_f64(libm::ldexp(1.0, -200));
+    x.copied();
+    x += UniQuadFloat::ONE;
+    x += UniQuadFloat::from_f64(libm::ldexp(1.0, -100));
+    let mut hex = String::new();
+    x.write_hex(&mut hex).unwrap();
+    assert_eq!(hex, std::format!("0x1.{0}1{0}1p+0", "0".repeat(24)));
+    let mut parsed = UniQuadFloat::from_hex_str(&hex).unwrap();
+    parsed.copied();
+    assert!(parsed.eq(&x));
+
+    let mut back = UniQuadFloat::from_be_bytes(x.to_be_bytes()).unwrap();
+    back.copied();
+    assert!(back.eq(&x));
+    let mut back = UniQuadFloat::from_le_bytes(x.to_le_bytes()).unwrap();
+    back.copied();
+    assert!(back.eq(&x));
+
+    let mut twofloat = x.convert::<{ UniFloatChoice::TwoFloat }>();
+    twofloat.copied();
+    assert_eq!((twofloat.twofloats[0].hi(), twofloat.twofloats[0].lo()), (1.0, libm::ldexp(1.0, -100)));
+    let mut wide = x.convert::<{ MPFR_300_BITS }>();
+    wide.copied();
+    let mut narrowed = wide.convert::<{ UniFloatChoice::QuadFloat }>();
+    narrowed.copied();
+    assert!(narrowed.eq(&x));
+}
@@ -0,0 +1,138 @@
+extern crate std;
+
+use std::string::String;
+use crate::{MpfrBounds, ParseErrorKind, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Each tuple: (value, base, text). The values are exact in all choices.
+const RADIX: [(f64, u32, &str); 11] = [
+    (1.5, 36, "1.i"),
+    (-0.0625, 2, "-1@-4"),
+    (255.0, 16, "ff"),
+    (1295.0, 36, "zz"),
+    (-10.5, 16, "-a.8"),
+    (97.0, 62, "1Z"),
+    (123.0, 62, "1z"),
+    (0.0, 10, "0"),
+    (-0.0, 7, "-0"),
+    (f64::NEG_INFINITY, 36, "-@inf@"),
+    (f64::NAN, 3, "@nan@"),
+];
+
+fn assert_radix<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(value, base, text) in RADIX.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        let mut written = String::new();
+        float.write_radix(&mut written, base, 0).unwrap();
+        assert_eq!(written, text);
+
+        let mut parsed = UniFloat::<C>::from_str_radix(text, base).unwrap();
+        parsed.copied();
+        let parsed = parsed.to_f64_nearest();
+        assert!(parsed.to_bits() == value.to_bits() || (parsed.is_nan() && value.is_nan()), "{}", text);
+    }
+    for &(text, base, value) in [("0.I", 36, 0.5), ("+Z", 36, 35.0), ("1@2", 10, 100.0),
+        ("00.100@+1", 2, 1.0), ("-0.000", 5, -0.0)].iter() {
+        let mut parsed = UniFloat::<C>::from_str_radix(text, base).unwrap();
+        parsed.copied();
+        assert_eq!(parsed.to_f64_nearest().to_bits(), f64::to_bits(value), "{}", text);
+    }
+
+    for &(text, kind, position) in [("", ParseErrorKind::Empty, 0), ("12", ParseErrorKind::InvalidCharacter, 1),
+        ("1@", ParseErrorKind::MissingDigits, 2), ("@", ParseErrorKind::MissingDigits, 0),
+        (".", ParseErrorKind::MissingDigits, 1), ("1.1.1", ParseErrorKind::InvalidCharacter, 3),
+        ("inf", ParseErrorKind::InvalidCharacter, 0), ("1@x", ParseErrorKind::InvalidCharacter, 2)].iter() {
+        let error = UniFloat::<C>::from_str_radix(text, 2).unwrap_err();
+        assert_eq!((error.kind(), error.position()), (kind, position), "{:?}", text);
+    }
+}
+
+/// Values with many digits in any base, and with the least and the greatest exponents.
+const ROUND_TRIPS: [f64; 5] = [0.1, -1e300, 5e-324, 2.0 / 3.0, 123456789.0];
+
+fn assert_round_trips<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &value in ROUND_TRIPS.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        for &base in [2, 3, 10, 36, 62].iter() {
+            let mut written = String::new();
+            float.write_radix(&mut written, base, 0).unwrap();
+            let mut parsed = UniFloat::<C>::from_str_radix(&written, base).unwrap();
+            parsed.copied();
+            assert!(parsed == float, "{} in base {}: {}", value, base, written);
+        }
+    }
+}
+
+#[test]
+fn radix() {
+    assert_radix::<{ UniFloatChoice::F32 }>();
+    assert_round_trips::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_radix::<{ UniFloatChoice::F64 }>();
+        assert_radix::<{ UniFloatChoice::TwoFloat }>();
+        assert_radix::<{ MPFR_100_BITS }>();
+        assert_round_trips::<{ UniFloatChoice::F64 }>();
+        assert_round_trips::<{ UniFloatChoice::TwoFloat }>();
+        assert_round_trips::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+fn radix_rounds_correctly() {
+    // Decimal, compared to Rust's own (correctly rounded) parsing. With ties, subnormals,
+    // underflows and overflows.
+    for &text in ["0.1", "3.4028235@38", "3.4028236@38", "1.4@-45", "7.006492321624086@-46", "16777217",
+        "123456789012345678901234567890", "1@-50"].iter() {
+        let mut float = crate::UniF32::from_str_radix(text, 10).unwrap();
+        float.copied();
+        assert_eq!(float.to_f64_nearest(), text.replace('@', "e").parse::<f32>().unwrap() as f64, "{}", text);
+    }
+    #[cfg(not(feature = "f32_only"))]
+    for &text in ["0.1", "1.7976931348623157@308", "1.7976931348623159@308", "4.9406564584124654@-324",
+        "2.4703282292062328@-324", "2.4703282292062327@-324", "9007199254740993", "9007199254740995",
+        "0.30000000000000004", "1@400", "-1@-400"].iter() {
+        let mut float = crate::UniF64::from_str_radix(text, 10).unwrap();
+        float.copied();
+        assert_eq!(float.to_f64_nearest().to_bits(), text.replace('@', "e").parse::<f64>().unwrap().to_bits(), "{}", text);
+    }
+
+    // A third is exact in base 3. Rounding carries over into a new digit.
+    let mut third = crate::UniF32::from_str_radix("0.1", 3).unwrap();
+    third.copied();
+    assert_eq!(third.to_f64_nearest(), (1.0f32 / 3.0) as f64);
+    let mut written = String::new();
+    third.write_radix(&mut written, 3, 5).unwrap();
+    assert_eq!(written, "1@-1");
+    #[cfg(not(feature = "f32_only"))] {
+        let mut third = UniFloat::<{ MPFR_100_BITS }>::from_str_radix("0.1", 3).unwrap();
+        third.copied();
+        let mut written = String::new();
+        third.write_radix(&mut written, 3, 5).unwrap();
+        assert_eq!(written, "1@-1");
+        // 2^53 + 1 is a tie, to even. Base 62 has upper and lower case letters.
+        let mut tie = crate::UniF64::from_str_radix("fFgnDxSe9", 62).unwrap();
+        tie.copied();
+        assert_eq!(tie.to_f64_nearest(), 9007199254740992.0);
+        let mut written = String::new();
+        tie.write_radix(&mut written, 62, 0).unwrap();
+        assert_eq!(written, "fFgnDxSe8");
+    }
+}
@@ -0,0 +1,57 @@
+#![cfg(not(feature = "f32_only"))]
+
+use crate::{MpfrBounds, RoundingMode, UniFloat, UniFloatChoice, UniRational};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+#[test]
+fn rational_arithmetic() {
+    let (third, sixth) = (UniRational::from_integers(1, 3), UniRational::from_integers(-1, 6));
+    assert!(&third + &sixth == UniRational::from_integers(1, 6));
+    assert!(&third - &sixth == UniRational::from_integers(1, 2));
+    assert!(&third * &UniRational::from_integers(3, 1) == UniRational::from_integers(1, 1));
+    assert!(&third / &sixth == UniRational::from_integers(-2, 1));
+    assert!(-&third == UniRational::from_integers(-2, 6));
+    assert!(sixth < third && -&third < sixth);
+    assert_eq!(sixth.signum(), -1);
+    assert!(sixth.abs() == sixth.recip().recip().abs());
+    let mut sum = UniRational::new();
+    sum += &third;
+    sum *= &UniRational::from_integers(6, 1);
+    sum -= &UniRational::from_integers(1, 1);
+    sum /= &UniRational::from_integers(2, 1);
+    assert!(sum == UniRational::from_integers(1, 2));
+}
+
+#[test]
+fn rational_conversions() {
+    let third = UniRational::from_integers(1, 3);
+    let mut nearest = third.to_unifloat::<{ UniFloatChoice::F64 }>();
+    assert_eq!(nearest.copied().to_f64_nearest(), 1.0 / 3.0);
+    let mut up = third.to_unifloat_round::<{ UniFloatChoice::F64 }>(RoundingMode::Up).unwrap();
+    assert_eq!(up.copied().to_f64_nearest(), f64::from_bits((1.0f64 / 3.0).to_bits() + 1));
+    let mut single = third.to_unifloat::<{ UniFloatChoice::F32 }>();
+    assert_eq!(single.copied().to_f64_nearest(), (1.0f32 / 3.0) as f64);
+    assert!(third.to_unifloat_round::<{ UniFloatChoice::TwoFloat }>(RoundingMode::Down).is_err());
+
+    // Every finite float is a rational, so it converts back exactly.
+    let mut precise = third.to_unifloat::<{ MPFR_100_BITS }>();
+    precise.copied();
+    let exact = UniRational::from_unifloat(&precise).unwrap();
+    assert!(exact != third);
+    let back = exact.to_unifloat::<{ MPFR_100_BITS }>();
+    assert!(back.ge(&precise) && back.le(&precise));
+    let mut down = third.to_unifloat_round::<{ MPFR_100_BITS }>(RoundingMode::Down).unwrap();
+    assert!(UniRational::from_unifloat(down.copied()).unwrap() < third);
+
+    let mut nan = UniFloat::<{ UniFloatChoice::F64 }>::NAN;
+    assert!(UniRational::from_unifloat(nan.copied()).is_none());
+}
+
+#[test]
+#[should_panic(expected = "UniRational division by zero.")]
+fn division_by_zero() {
+    let _ = &UniRational::from_integers(1, 1) / &UniRational::new();
+}
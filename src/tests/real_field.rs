@@ -0,0 +1,51 @@
+#![cfg(feature = "real_field")]
+
+use {gmp_mpfr_sys::mpfr, nalgebra::{ComplexField, Matrix3, RealField}, num_traits::{FromPrimitive, ToPrimitive}};
+use crate::{MpfrBounds, UniFloatChoice, UniNum, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// The 2-norm condition number of the 3x3 Hilbert matrix.
+const HILBERT_3_CONDITION: f64 = 524.0567775860644;
+
+fn assert_decompositions<const C: UniFloatChoice>(tolerance: f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let one = UniNum::<C>::from_u8(1).unwrap();
+    let hilbert = Matrix3::from_fn(|i, j| one / UniNum::from_usize(i + j + 1).unwrap());
+
+    let qr = hilbert.qr();
+    let residual = (qr.q() * qr.r() - hilbert).norm();
+    assert!(residual.to_f64().unwrap() < tolerance);
+
+    let singular_values = hilbert.svd(false, false).singular_values;
+    let condition = singular_values.max() / singular_values.min();
+    assert!((condition.to_f64().unwrap() - HILBERT_3_CONDITION).abs() < HILBERT_3_CONDITION * tolerance);
+
+    let inverse = hilbert.try_inverse().unwrap();
+    assert!((inverse * hilbert - Matrix3::identity()).norm().to_f64().unwrap() < HILBERT_3_CONDITION * tolerance);
+
+    // Constants and scalar functions.
+    assert!((<UniNum<C> as RealField>::frac_pi_3().to_f64().unwrap() - core::f64::consts::FRAC_PI_3).abs() < tolerance);
+    assert_eq!(ComplexField::try_sqrt(-one), None);
+    assert_eq!(ComplexField::try_sqrt(one), Some(one));
+    assert_eq!(ComplexField::argument(-one), RealField::pi());
+    assert_eq!(nalgebra::convert::<f64, UniNum<C>>(0.5).to_f64(), Some(0.5));
+}
+
+#[test]
+fn nalgebra_decompositions() {
+    assert_decompositions::<{ UniFloatChoice::F32 }>(1e-3);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_decompositions::<{ UniFloatChoice::F64 }>(1e-12);
+        assert_decompositions::<{ UniFloatChoice::TwoFloat }>(1e-12);
+        assert_decompositions::<{ MPFR_100_BITS }>(1e-12);
+    }
+}
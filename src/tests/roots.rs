@@ -0,0 +1,84 @@
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Each tuple: (value, k, the k-th root). The roots are exact.
+const ROOTS: [(f64, u32, f64); 12] = [
+    (2.25, 2, 1.5),
+    (-27.0, 3, -3.0),
+    (16.0, 4, 2.0),
+    (-32.0, 5, -2.0),
+    (5.0, 1, 5.0),
+    (-0.0, 3, -0.0),
+    (-0.0, 4, 0.0),
+    (f64::NEG_INFINITY, 3, f64::NEG_INFINITY),
+    (f64::INFINITY, 6, f64::INFINITY),
+    (f64::NEG_INFINITY, 2, f64::NAN),
+    (-1.0, 2, f64::NAN),
+    (1.0, 0, f64::NAN),
+];
+
+fn assert_roots<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(value, k, expected) in ROOTS.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        let mut root = float.root_n(k);
+        root.copied();
+        let root = root.to_f64_nearest();
+        assert!(root.to_bits() == expected.to_bits() || (root.is_nan() && expected.is_nan()), "{} {}", value, k);
+    }
+
+    // The same as root_n(), except for the root of -0.
+    for &value in [2.0, 3.0, -0.5, 1e300, 0.0, -0.0, f64::NEG_INFINITY].iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        let (mut sqrt, mut cbrt) = (float.sqrt(), float.cbrt());
+        sqrt.copied();
+        cbrt.copied();
+        let (mut square_root, mut cube_root) = (float.root_n(2), float.root_n(3));
+        square_root.copied();
+        cube_root.copied();
+        if value == 0.0 {
+            assert_eq!(sqrt.to_f64_nearest().to_bits(), value.to_bits());
+        } else {
+            assert!(sqrt == square_root || (value < 0.0 && sqrt.to_f64_nearest().is_nan()), "{}", value);
+        }
+        assert!(cbrt == cube_root, "{}", value);
+    }
+}
+
+#[test]
+fn roots() {
+    assert_roots::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_roots::<{ UniFloatChoice::F64 }>();
+        assert_roots::<{ UniFloatChoice::TwoFloat }>();
+        assert_roots::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn root_n_twofloat_accuracy() {
+    for &(value, k) in [(3.0, 7), (0.1, 5), (1e-200, 9), (12345.678, 4)].iter() {
+        let mut float = crate::UniTwoFloat::from_f64(value);
+        float.copied();
+        let mut root = float.root_n(k);
+        root.copied();
+        let root = root.twofloats[0];
+        let mut power = root;
+        for _ in 1..k {
+            power = power * root;
+        }
+        assert!(((power - value) / value).hi().abs() < 1e-30, "{} {}", value, k);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{MpfrBounds, RoundingMode, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// Same precision as f64, so that both round the same (apart from overflows and underflows,
+/// since MPFR has a wider exponent range).
+const MPFR_53_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(53)
+};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// 2^-n.
+fn tiny(n: i32) -> f64 {
+    libm::ldexp(1.0, -n)
+}
+
+fn assert_rounded<const C: UniFloatChoice>(name: &str, x: f64, y: f64, rounding: RoundingMode, expected: f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut float_x, mut float_y) = (UniFloat::<C>::from_f64(x), UniFloat::<C>::from_f64(y));
+    float_x.copied();
+    float_y.copied();
+    let mut result = match name {
+        "add" => float_x.add_round(&float_y, rounding),
+        "sub" => float_x.sub_round(&float_y, rounding),
+        "mul" => float_x.mul_round(&float_y, rounding),
+        "div" => float_x.div_round(&float_y, rounding),
+        _ => float_x.sqrt_round(rounding)
+    }.unwrap();
+    result.copied();
+    let result = result.to_f64_nearest();
+    assert_eq!(result.to_bits(), expected.to_bits(), "{}({}, {}, {:?}): {}", name, x, y, rounding, result);
+}
+
+#[test]
+fn f32_rounded() {
+    let one_up = 1.0 + f32::EPSILON as f64;
+    let third_down = f32::from_bits(0x3EAA_AAAA) as f64;
+    for &(name, x, y, rounding, expected) in [
+        ("add", 1.0, tiny(30), RoundingMode::Nearest, 1.0),
+        ("add", 1.0, tiny(30), RoundingMode::Up, one_up),
+        ("add", 1.0, tiny(24), RoundingMode::Nearest, 1.0),
+        ("add", 1.0, tiny(24), RoundingMode::ToNearestAway, one_up),
+        ("sub", 1.0, 1.0, RoundingMode::Down, -0.0),
+        ("mul", one_up, one_up, RoundingMode::Down, 1.0 + 2.0 * f32::EPSILON as f64),
+        ("div", 1.0, 3.0, RoundingMode::Down, third_down),
+        ("div", 1.0, 3.0, RoundingMode::TowardZero, third_down),
+        ("div", 1.0, 3.0, RoundingMode::Up, (1.0f32 / 3.0) as f64),
+        ("mul", f32::MAX as f64, 2.0, RoundingMode::TowardZero, f32::MAX as f64),
+        ("mul", f32::MAX as f64, -2.0, RoundingMode::Down, f64::NEG_INFINITY),
+    ].iter() {
+        assert_rounded::<{ UniFloatChoice::F32 }>(name, x, y, rounding, expected);
+    }
+}
+
+/// Each tuple: (function name, x, y, rounding, result). The same for f64 and MPFR at 53 bits.
+#[cfg(not(feature = "f32_only"))]
+fn f64_roundings() -> [(&'static str, f64, f64, RoundingMode, f64); 16] {
+    let one_up = 1.0 + f64::EPSILON;
+    let (third, third_up) = (1.0 / 3.0, f64::from_bits(0x3FD5_5555_5555_5556));
+    let (sqrt_2, sqrt_2_down) = (core::f64::consts::SQRT_2, f64::from_bits(0x3FF6_A09E_667F_3BCC));
+    [
+        ("add", 1.0, tiny(60), RoundingMode::Nearest, 1.0),
+        ("add", 1.0, tiny(60), RoundingMode::Up, one_up),
+        ("add", 1.0, tiny(60), RoundingMode::Faithful, 1.0),
+        ("add", 1.0, tiny(53), RoundingMode::Nearest, 1.0),
+        ("add", 1.0, tiny(53), RoundingMode::ToNearestAway, one_up),
+        ("add", -1.0, -tiny(53), RoundingMode::ToNearestAway, -one_up),
+        ("sub", -1.0, tiny(60), RoundingMode::Down, -one_up),
+        ("sub", -1.0, tiny(60), RoundingMode::TowardZero, -1.0),
+        ("sub", 1.0, 1.0, RoundingMode::Nearest, 0.0),
+        ("sub", 1.0, 1.0, RoundingMode::Down, -0.0),
+        ("mul", one_up, one_up, RoundingMode::Up, 1.0 + 3.0 * f64::EPSILON),
+        ("mul", one_up, one_up, RoundingMode::Down, 1.0 + 2.0 * f64::EPSILON),
+        ("div", 1.0, 3.0, RoundingMode::Down, third),
+        ("div", 1.0, 3.0, RoundingMode::Up, third_up),
+        ("sqrt", 2.0, 0.0, RoundingMode::Down, sqrt_2_down),
+        ("sqrt", 2.0, 0.0, RoundingMode::Up, sqrt_2),
+    ]
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn f64_and_mpfr_rounded() {
+    for &(name, x, y, rounding, expected) in f64_roundings().iter() {
+        assert_rounded::<{ UniFloatChoice::F64 }>(name, x, y, rounding, expected);
+        assert_rounded::<{ MPFR_53_BITS }>(name, x, y, rounding, expected);
+    }
+    // Overflow and underflow keep the direction.
+    assert_rounded::<{ UniFloatChoice::F64 }>("mul", f64::MAX, 2.0, RoundingMode::TowardZero, f64::MAX);
+    assert_rounded::<{ UniFloatChoice::F64 }>("mul", f64::MAX, -2.0, RoundingMode::Up, -f64::MAX);
+    assert_rounded::<{ UniFloatChoice::F64 }>("add", f64::MAX, f64::MAX, RoundingMode::Nearest, f64::INFINITY);
+    assert_rounded::<{ UniFloatChoice::F64 }>("mul", tiny(600), tiny(600), RoundingMode::Up, f64::from_bits(1));
+    assert_rounded::<{ UniFloatChoice::F64 }>("mul", tiny(600), -tiny(600), RoundingMode::TowardZero, -0.0);
+    assert_rounded::<{ UniFloatChoice::F64 }>("div", -tiny(1000), 1e300, RoundingMode::Down, -f64::from_bits(1));
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn twofloat_rounded() {
+    type UniTwoFloat = UniFloat<{ UniFloatChoice::TwoFloat }>;
+    let (mut one, mut three) = (UniTwoFloat::from_f64(1.0), UniTwoFloat::from_f64(3.0));
+    one.copied();
+    three.copied();
+    for &rounding in [RoundingMode::TowardZero, RoundingMode::Up, RoundingMode::Down, RoundingMode::ToNearestAway].iter() {
+        let error = one.div_round(&three, rounding).unwrap_err();
+        assert_eq!(error.rounding(), rounding);
+        assert!(one.sqrt_round(rounding).is_err());
+    }
+    let mut third = one.div_round(&three, RoundingMode::Faithful).unwrap();
+    third.copied();
+    let mut expected = UniTwoFloat::NAN;
+    expected <<= &one;
+    expected /= &three;
+    assert_eq!(third.twofloats[0], expected.twofloats[0]);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn ties_away_to_primitives() {
+    let midpoint = 1.0 + tiny(24);
+    let mut float = UniFloat::<{ UniFloatChoice::F64 }>::from_f64(midpoint);
+    float.copied();
+    assert_eq!(float.to_f32(RoundingMode::Nearest).value(), 1.0);
+    assert_eq!(float.to_f32(RoundingMode::ToNearestAway).value(), 1.0 + f32::EPSILON);
+    let mut float = UniFloat::<{ MPFR_100_BITS }>::from_f64(-midpoint);
+    float.copied();
+    assert_eq!(float.to_f32(RoundingMode::ToNearestAway).value(), -1.0 - f32::EPSILON);
+    assert_eq!(float.to_f32(RoundingMode::Faithful).value(), -1.0);
+
+    let mut float = UniFloat::<{ MPFR_100_BITS }>::from_f64(1.0);
+    float.copied();
+    let mut low = UniFloat::<{ MPFR_100_BITS }>::from_f64(tiny(53));
+    low.copied();
+    float += &low;
+    assert_eq!(float.to_f64(RoundingMode::Nearest).value(), 1.0);
+    assert_eq!(float.to_f64(RoundingMode::ToNearestAway).value(), 1.0 + f64::EPSILON);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn convert_round() {
+    type UniMpfr100bit = UniFloat<{ MPFR_100_BITS }>;
+    let (mut one, mut three) = (UniMpfr100bit::from_f64(1.0), UniMpfr100bit::from_f64(3.0));
+    one.copied();
+    three.copied();
+    let mut third = UniMpfr100bit::NAN;
+    third <<= &one / &three;
+
+    let mut converted = third.convert_round::<{ UniFloatChoice::F64 }>(RoundingMode::Up).unwrap();
+    converted.copied();
+    assert_eq!(converted.to_f64_nearest().to_bits(), 0x3FD5_5555_5555_5556);
+    let mut converted = third.convert_round::<{ MPFR_53_BITS }>(RoundingMode::Down).unwrap();
+    converted.copied();
+    assert_eq!(converted.to_f64_nearest(), 1.0 / 3.0);
+    let mut converted = third.convert_round::<{ UniFloatChoice::F32 }>(RoundingMode::Down).unwrap();
+    converted.copied();
+    assert_eq!(converted.to_f64_nearest(), f32::from_bits(0x3EAA_AAAA) as f64);
+
+    // TwoFloat has no directed rounding, unless the result is exact.
+    assert_eq!(third.convert_round::<{ UniFloatChoice::TwoFloat }>(RoundingMode::Up).unwrap_err().rounding(),
+        RoundingMode::Up);
+    assert!(third.convert_round::<{ UniFloatChoice::TwoFloat }>(RoundingMode::Nearest).is_ok());
+    assert!(one.convert_round::<{ UniFloatChoice::TwoFloat }>(RoundingMode::Up).is_ok());
+}
@@ -0,0 +1,133 @@
+use crate::{RoundingMode, UniFloat, UniFloatChoice};
+
+/// Each tuple: (value, value.round(), value.round_ties_even()).
+const ROUNDINGS: [(f64, f64, f64); 7] = [
+    (2.5, 3.0, 2.0),
+    (3.5, 4.0, 4.0),
+    (-2.5, -3.0, -2.0),
+    (-3.5, -4.0, -4.0),
+    (2.4, 2.0, 2.0),
+    (2.6, 3.0, 3.0),
+    (0.5, 1.0, 0.0),
+];
+
+choice_fixture! {
+    fn assert_roundings<const C: UniFloatChoice>() {
+        for &(value, round, round_ties_even) in ROUNDINGS.iter() {
+            let float = UniFloat::<C>::from_f64(value);
+
+            let rounded = float.round();
+            assert_eq!(rounded.to_f64_nearest(), round, "{}.round()", value);
+
+            let rounded = float.round_ties_even();
+            assert_eq!(rounded.to_f64_nearest(), round_ties_even, "{}.round_ties_even()", value);
+        }
+    }
+}
+
+/// Each tuple: (value, value.floor(), value.ceil(), value.trunc()).
+const INTEGER_PARTS: [(f64, f64, f64, f64); 7] = [
+    (2.5, 2.0, 3.0, 2.0),
+    (-2.5, -3.0, -2.0, -2.0),
+    (3.0, 3.0, 3.0, 3.0),
+    (-0.5, -1.0, -0.0, -0.0),
+    (1e300, 1e300, 1e300, 1e300),
+    (f64::INFINITY, f64::INFINITY, f64::INFINITY, f64::INFINITY),
+    (f64::NAN, f64::NAN, f64::NAN, f64::NAN),
+];
+
+choice_fixture! {
+    fn assert_integer_parts<const C: UniFloatChoice>() {
+        for &(value, floor, ceil, trunc) in INTEGER_PARTS.iter() {
+            let float = UniFloat::<C>::from_f64(value);
+            // from_f64() rounds 1e300 for F32, so the expected values get rounded, too.
+            for &(name, rounded, expected, rounding) in [("floor", float.floor(), floor, RoundingMode::Down),
+                ("ceil", float.ceil(), ceil, RoundingMode::Up), ("trunc", float.trunc(), trunc, RoundingMode::TowardZero)].iter() {
+                let expected = UniFloat::<C>::from_f64(expected);
+                let (result, expected) = (rounded.to_f64_nearest(), expected.to_f64_nearest());
+                assert!(result == expected || (result.is_nan() && expected.is_nan()), "{}.{}()", value, name);
+                let rint = float.rint(rounding);
+                let rint = rint.to_f64_nearest();
+                assert!(rint == expected || (rint.is_nan() && expected.is_nan()), "{}.rint({:?})", value, rounding);
+            }
+        }
+        // Ties of rint() for the nearest modes.
+        let float = UniFloat::<C>::from_f64(-2.5);
+        for &(rounding, expected) in [(RoundingMode::Nearest, -2.0), (RoundingMode::Faithful, -2.0),
+            (RoundingMode::ToNearestAway, -3.0)].iter() {
+            let rint = float.rint(rounding);
+            assert_eq!(rint.to_f64_nearest(), expected, "-2.5.rint({:?})", rounding);
+        }
+    }
+}
+
+test_choices!(floor_ceil_trunc_rint, assert_integer_parts);
+
+/// Each tuple: (value, integer part, fractional part). The signs of zeros count.
+const TRUNC_FRACTS: [(f64, f64, f64); 7] = [
+    (2.75, 2.0, 0.75),
+    (-2.75, -2.0, -0.75),
+    (3.0, 3.0, 0.0),
+    (-3.0, -3.0, -0.0),
+    (f64::INFINITY, f64::INFINITY, 0.0),
+    (f64::NEG_INFINITY, f64::NEG_INFINITY, -0.0),
+    (f64::NAN, f64::NAN, f64::NAN),
+];
+
+choice_fixture! {
+    fn assert_trunc_fracts<const C: UniFloatChoice>() {
+        let same = |result: f64, expected: f64| result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan());
+        for &(value, integer, fraction) in TRUNC_FRACTS.iter() {
+            let float = UniFloat::<C>::from_f64(value);
+            let (integer_part, fractional_part) = float.trunc_fract();
+            assert!(same(integer_part.to_f64_nearest(), integer), "{}.trunc_fract().0", value);
+            assert!(same(fractional_part.to_f64_nearest(), fraction), "{}.trunc_fract().1", value);
+
+            let (integer_part, fractional_part) = (float.int_part(), float.fract());
+            assert!(same(integer_part.to_f64_nearest(), integer), "{}.int_part()", value);
+            assert!(same(fractional_part.to_f64_nearest(), fraction), "{}.fract()", value);
+        }
+    }
+}
+
+test_choices!(trunc_fract, assert_trunc_fracts);
+
+/// Each tuple: (value, step, rounding, expected).
+const QUANTIZATIONS: [(f64, f64, RoundingMode, f64); 8] = [
+    (0.27, 0.05, RoundingMode::Nearest, 0.25),
+    (0.27, 0.05, RoundingMode::TowardZero, 0.25),
+    (0.27, 0.05, RoundingMode::Up, 0.30),
+    (0.27, 0.05, RoundingMode::Down, 0.25),
+    (-0.27, 0.05, RoundingMode::Nearest, -0.25),
+    (-0.27, 0.05, RoundingMode::TowardZero, -0.25),
+    (-0.27, 0.05, RoundingMode::Up, -0.25),
+    (-0.27, 0.05, RoundingMode::Down, -0.30),
+];
+
+choice_fixture! {
+    fn assert_quantizations<const C: UniFloatChoice>() {
+        for &(value, step, rounding, expected) in QUANTIZATIONS.iter() {
+            let float = UniFloat::<C>::from_f64(value);
+            let step = UniFloat::<C>::from_f64(step);
+
+            let quantized = float.quantize(&step, rounding);
+            assert!(libm::fabs(quantized.to_f64_nearest() - expected) < 1e-6,
+                "{}.quantize({:?}, {:?})", value, step.to_f64_nearest(), rounding);
+        }
+        let float = UniFloat::<C>::from_f64(0.27);
+        let zero = UniFloat::<C>::from_f64(0.0);
+        let quantized = float.quantize(&zero, RoundingMode::Nearest);
+        assert!(quantized.to_f64_nearest().is_nan());
+
+        let step = UniFloat::<C>::from_f64(0.25);
+        for &(value, expected) in [(0.3, 0.25), (-0.4, -0.5), (0.375, 0.5), (0.125, 0.0)].iter() {
+            let float = UniFloat::<C>::from_f64(value);
+            let rounded = float.round_to_multiple(&step);
+            assert_eq!(rounded.to_f64_nearest(), expected, "{}.round_to_multiple(0.25)", value);
+        }
+    }
+}
+
+test_choices!(quantize, assert_quantizations);
+
+test_choices!(round_and_round_ties_even, assert_roundings);
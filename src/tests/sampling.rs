@@ -0,0 +1,135 @@
+#![cfg(feature = "rand")]
+
+use {gmp_mpfr_sys::mpfr, rand::{Rng, SeedableRng, distributions::Open01, rngs::StdRng}};
+use crate::{MpfrBounds, UniExponential, UniFloat, UniFloatChoice, UniNormal, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(not(feature = "f32_only"))]
+use crate::FixedOverflow;
+
+const MPFR_200_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(200)
+};
+
+const SAMPLES: usize = 1000;
+
+/// Whether `x` * 2^`exponent` is an integer.
+fn is_integer_scaled<const C: UniFloatChoice>(x: &UniFloat<C>, exponent: i64) -> bool where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut scaled = x.ldexp(exponent);
+    scaled.copied();
+    let mut fraction = scaled.fract();
+    fraction.copied().to_f64_nearest() == 0.0
+}
+
+/// Samples of `Standard` and `Open01`, with `bits` random bits each.
+fn assert_samples<const C: UniFloatChoice>(bits: i64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut rng = StdRng::seed_from_u64(7);
+    let (mut sum, mut lowest_bit_set) = (0.0, 0);
+    for _ in 0..SAMPLES {
+        let mut x: UniFloat<C> = rng.gen();
+        x.copied();
+        let value = x.to_f64_nearest();
+        assert!((0.0..1.0).contains(&value));
+        sum += value;
+        assert!(is_integer_scaled(&x, bits));
+        if !is_integer_scaled(&x, bits - 1) {
+            lowest_bit_set += 1;
+        }
+    }
+    assert!((sum / SAMPLES as f64 - 0.5).abs() < 0.05);
+    // The lowest bit is random, too, rather than missing (as it would be if widened from f64).
+    assert!(lowest_bit_set > SAMPLES / 3 && lowest_bit_set < SAMPLES * 2 / 3);
+
+    for _ in 0..SAMPLES {
+        let mut x: UniFloat<C> = rng.sample(Open01);
+        x.copied();
+        let value = x.to_f64_nearest();
+        assert!(value > 0.0 && value < 1.0);
+        assert!(is_integer_scaled(&x, bits) && !is_integer_scaled(&x, bits - 1));
+    }
+}
+
+#[test]
+fn uniform_samples() {
+    assert_samples::<{ UniFloatChoice::F32 }>(24);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_samples::<{ UniFloatChoice::F64 }>(53);
+        assert_samples::<{ UniFloatChoice::TwoFloat }>(106);
+        assert_samples::<{ MPFR_200_BITS }>(200);
+        assert_samples::<{ UniFloatChoice::fixed(16, 8, FixedOverflow::Saturating) }>(8);
+    }
+}
+
+/// Sample mean and variance of `SAMPLES` samples of N(3, 0.5^2) and of Exp(4).
+fn assert_distributions<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut rng = StdRng::seed_from_u64(11);
+    let (mut mean, mut sigma) = (UniFloat::<C>::from_f64(3.0), UniFloat::<C>::from_f64(0.5));
+    mean.copied();
+    sigma.copied();
+    let normal = UniNormal::new(&mean, &sigma);
+    let (mut sum, mut sum_of_squares) = (0.0, 0.0);
+    // The distribution is moved into the iterator, so it must not need .copied().
+    for mut x in rng.clone().sample_iter(normal).take(SAMPLES) {
+        let value = x.copied().to_f64_nearest();
+        sum += value;
+        sum_of_squares += value * value;
+    }
+    let sample_mean = sum / SAMPLES as f64;
+    assert!((sample_mean - 3.0).abs() < 0.1);
+    assert!((sum_of_squares / SAMPLES as f64 - sample_mean * sample_mean - 0.25).abs() < 0.05);
+
+    let mut lambda = UniFloat::<C>::from_f64(4.0);
+    lambda.copied();
+    let exponential = UniExponential::new(&lambda);
+    let mut sum = 0.0;
+    for _ in 0..SAMPLES {
+        let mut x = rng.sample(exponential);
+        let value = x.copied().to_f64_nearest();
+        assert!(value > 0.0);
+        sum += value;
+    }
+    assert!((sum / SAMPLES as f64 - 0.25).abs() < 0.05);
+}
+
+#[test]
+fn normal_and_exponential() {
+    assert_distributions::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_distributions::<{ UniFloatChoice::F64 }>();
+        assert_distributions::<{ UniFloatChoice::TwoFloat }>();
+        assert_distributions::<{ MPFR_200_BITS }>();
+    }
+}
+
+#[test]
+#[should_panic(expected="UniNormal needs a finite mean, and a finite sigma >= 0.")]
+fn normal_needs_non_negative_sigma() {
+    let (mut mean, mut sigma) = (UniFloat::<{ UniFloatChoice::F32 }>::ZERO, UniFloat::<{ UniFloatChoice::F32 }>::from_f64(-1.0));
+    mean.copied();
+    sigma.copied();
+    UniNormal::new(&mean, &sigma);
+}
+
+#[test]
+#[should_panic(expected="Open01 needs a fixed-point format with frac_bits > 0.")]
+#[cfg(not(feature = "f32_only"))]
+fn open01_needs_fraction_bits() {
+    let _: UniFloat<{ UniFloatChoice::fixed(8, 0, FixedOverflow::Wrapping) }> = StdRng::seed_from_u64(7).sample(Open01);
+}
@@ -0,0 +1,117 @@
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// 2^n.
+fn two_pow(n: i32) -> f64 {
+    libm::ldexp(1.0, n)
+}
+
+/// Each tuple: (value, mantissa, exponent). The same for every choice.
+const FREXPS: [(f64, f64, i64); 6] = [
+    (1.0, 0.5, 1),
+    (-3.0, -0.75, 2),
+    (0.1875, 0.75, -2),
+    (1000.0, 0.9765625, 10),
+    (0.0, 0.0, 0),
+    (f64::NEG_INFINITY, f64::NEG_INFINITY, 0),
+];
+
+fn assert_scaling<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(value, mantissa, exponent) in FREXPS.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        let (mut result, result_exponent) = float.frexp();
+        result.copied();
+        assert_eq!((result.to_f64_nearest(), result_exponent), (mantissa, exponent), "{}", value);
+        if value.is_finite() {
+            let mut scaled = result.ldexp(result_exponent);
+            scaled.copied();
+            assert!(scaled == float, "{}", value);
+        }
+    }
+    let mut nan = UniFloat::<C>::NAN;
+    nan.copied();
+    let (mut mantissa, exponent) = nan.frexp();
+    mantissa.copied();
+    assert!(mantissa.is_nan() && exponent == 0);
+
+    let mut float = UniFloat::<C>::from_f64(-1.5);
+    float.copied();
+    for &(exponent, expected) in [(3, -12.0), (-4, -0.09375), (0, -1.5), (100, -1.5 * two_pow(100))].iter() {
+        let (mut scaled, mut multiplied) = (float.ldexp(exponent), float.mul_2i(exponent));
+        scaled.copied();
+        multiplied.copied();
+        assert_eq!(scaled.to_f64_nearest(), expected);
+        assert!(scaled == multiplied);
+    }
+    let mut infinite = float.ldexp(i64::MAX);
+    infinite.copied();
+    assert!(infinite.is_infinite() && infinite.is_sign_negative());
+    let mut zero = float.ldexp(i64::MIN);
+    zero.copied();
+    assert!(zero.to_f64_nearest() == 0.0);
+}
+
+#[test]
+fn scaling() {
+    assert_scaling::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_scaling::<{ UniFloatChoice::F64 }>();
+        assert_scaling::<{ UniFloatChoice::TwoFloat }>();
+        assert_scaling::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+fn subnormals() {
+    let mut float = UniFloat::<{ UniFloatChoice::F32 }>::from_f64(two_pow(-140));
+    float.copied();
+    let (mut mantissa, exponent) = float.frexp();
+    mantissa.copied();
+    assert_eq!((mantissa.to_f64_nearest(), exponent), (0.5, -139));
+    // Halving the least subnormal is a tie, which rounds to the even zero.
+    float <<= UniFloat::<{ UniFloatChoice::F32 }>::from_f64(two_pow(-149));
+    let mut halved = float.ldexp(-1);
+    halved.copied();
+    assert_eq!(halved.to_f64_nearest(), 0.0);
+    #[cfg(not(feature = "f32_only"))] {
+        let mut float = UniFloat::<{ UniFloatChoice::F64 }>::from_f64(3.0 * two_pow(-1074));
+        float.copied();
+        let (mut mantissa, exponent) = float.frexp();
+        mantissa.copied();
+        assert_eq!((mantissa.to_f64_nearest(), exponent), (0.75, -1072));
+        // MPFR has no subnormals: scaling far down is still exact.
+        let mut float = UniFloat::<{ MPFR_100_BITS }>::from_f64(3.0);
+        float.copied();
+        let mut scaled = float.ldexp(-5000);
+        scaled.copied();
+        let (mut mantissa, exponent) = scaled.frexp();
+        mantissa.copied();
+        assert_eq!((mantissa.to_f64_nearest(), exponent), (0.75, -4998));
+    }
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn twofloat_frexp() {
+    // 1 - 2^-80 is below 1, so its mantissa is below 1 as well.
+    type UniTwoFloat = UniFloat<{ UniFloatChoice::TwoFloat }>;
+    let (mut float, mut low) = (UniTwoFloat::from_f64(1.0), UniTwoFloat::from_f64(-two_pow(-80)));
+    float.copied();
+    low.copied();
+    float += &low;
+    let (mut mantissa, exponent) = float.frexp();
+    mantissa.copied();
+    assert_eq!(exponent, 0);
+    assert_eq!((mantissa.twofloats[0].hi(), mantissa.twofloats[0].lo()), (1.0, -two_pow(-80)));
+}
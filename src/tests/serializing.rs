@@ -0,0 +1,170 @@
+#![cfg(feature = "serde")]
+
+extern crate std;
+
+use core::fmt;
+use gmp_mpfr_sys::mpfr;
+use serde::{Deserialize, Deserializer};
+use serde_test::{Configure, Token, assert_de_tokens, assert_de_tokens_error, assert_ser_tokens};
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// A deserialized value, compared bit by bit. That doesn't need it to be "copy fixed".
+struct Loaded<const C: UniFloatChoice> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    float: UniFloat<C>
+}
+
+impl <const C: UniFloatChoice> PartialEq for Loaded<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let (mut first, mut second) = (self.float, other.float);
+        first.copied();
+        second.copied();
+        let same_sign_exponent = match C {
+            #[cfg(not(feature = "f32_only"))]
+            UniFloatChoice::Mpfr { .. } => first.mpfr_sign_exponent() == second.mpfr_sign_exponent(),
+            _ => true
+        };
+        same_sign_exponent && (0..UniFloat::<C>::word_count()).all(|index| first.word(index) == second.word(index))
+    }
+}
+
+impl <const C: UniFloatChoice> fmt::Debug for Loaded<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.float, f)
+    }
+}
+
+impl <'de, const C: UniFloatChoice> Deserialize<'de> for Loaded<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Loaded { float: UniFloat::<C>::deserialize(deserializer)? })
+    }
+}
+
+/// Serialize `hex` (parsed), and deserialize it back, both ways.
+fn assert_serde<const C: UniFloatChoice>(hex: &str, choice: &'static str, negative: bool, exponent: i64, limbs: &[u64]) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut float = UniFloat::<C>::from_hex_str(hex).unwrap();
+    float.copied();
+
+    let readable = [
+        Token::Struct { name: "UniFloat", len: 2 },
+        Token::Str("choice"), Token::Str(choice),
+        Token::Str("value"), Token::Str(hex),
+        Token::StructEnd
+    ];
+    assert_ser_tokens(&(&float).readable(), &readable);
+    assert_de_tokens(&Loaded { float }.readable(), &readable);
+
+    let mut compact = std::vec![
+        Token::Struct { name: "UniFloat", len: 4 },
+        Token::Str("choice"), Token::Str(choice),
+        Token::Str("negative"), Token::Bool(negative),
+        Token::Str("exponent"), Token::I64(exponent),
+        Token::Str("limbs"), Token::Tuple { len: limbs.len() }
+    ];
+    compact.extend(limbs.iter().map(|&limb| Token::U64(limb)));
+    compact.extend([Token::TupleEnd, Token::StructEnd].iter().cloned());
+    assert_ser_tokens(&(&float).compact(), &compact);
+    assert_de_tokens(&Loaded { float }.compact(), &compact);
+}
+
+#[test]
+fn serde() {
+    assert_serde::<{ UniFloatChoice::F32 }>("0x1.8p+0", "F32", false, 0, &[0x3fc0_0000]);
+    assert_serde::<{ UniFloatChoice::F32 }>("-inf", "F32", false, 0, &[0xff80_0000]);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_serde::<{ UniFloatChoice::F64 }>("-0x1p-4", "F64", false, 0, &[0xbfb0_0000_0000_0000]);
+        assert_serde::<{ UniFloatChoice::TwoFloat }>("0x1.000000000000001p+0", "TwoFloat", false, 0,
+            &[0x3ff0_0000_0000_0000, 0x3c30_0000_0000_0000]);
+        assert_serde::<{ MPFR_100_BITS }>("0x1.8p+0", "Mpfr(100)", false, 1, &[0, 0xc000_0000_0000_0000]);
+        assert_serde::<{ MPFR_100_BITS }>("-0x1.0000000000000000000000008p-4", "Mpfr(100)", true, -3,
+            &[0x0000_0000_4000_0000, 0x8000_0000_0000_0000]);
+    }
+}
+
+#[test]
+fn serde_errors() {
+    type LoadedF32 = Loaded<{ UniFloatChoice::F32 }>;
+    assert_de_tokens_error::<serde_test::Readable<LoadedF32>>(&[
+        Token::Struct { name: "UniFloat", len: 2 },
+        Token::Str("choice"), Token::Str("F64")
+    ], "invalid value: string \"F64\", expected choice \"F32\"");
+    assert_de_tokens_error::<serde_test::Readable<LoadedF32>>(&[
+        Token::Struct { name: "UniFloat", len: 2 },
+        Token::Str("choice"), Token::Str("F32"),
+        Token::StructEnd
+    ], "missing field `value`");
+    // Sign and exponent are only for MPFR.
+    assert_de_tokens_error::<serde_test::Compact<LoadedF32>>(&[
+        Token::Struct { name: "UniFloat", len: 4 },
+        Token::Str("choice"), Token::Str("F32"),
+        Token::Str("negative"), Token::Bool(false),
+        Token::Str("exponent"), Token::I64(1),
+        Token::Str("limbs"), Token::Tuple { len: 1 }, Token::U64(0), Token::TupleEnd,
+        Token::StructEnd
+    ], "invalid sign, exponent or limbs");
+    assert_de_tokens_error::<serde_test::Compact<LoadedF32>>(&[
+        Token::Struct { name: "UniFloat", len: 4 },
+        Token::Str("choice"), Token::Str("F32"),
+        Token::Str("limbs"), Token::Tuple { len: 1 }, Token::U64(1 << 32)
+    ], "invalid value: integer `4294967296`, expected a tuple of 1 limbs");
+
+    #[cfg(not(feature = "f32_only"))] {
+        type LoadedMpfr100 = Loaded<{ MPFR_100_BITS }>;
+        assert_de_tokens_error::<serde_test::Compact<LoadedMpfr100>>(&[
+            Token::Struct { name: "UniFloat", len: 4 },
+            Token::Str("choice"), Token::Str("Mpfr(101)")
+        ], "invalid value: string \"Mpfr(101)\", expected choice \"Mpfr(100)\"");
+        // Not normalized: the highest bit is clear.
+        assert_de_tokens_error::<serde_test::Compact<LoadedMpfr100>>(&[
+            Token::Struct { name: "UniFloat", len: 4 },
+            Token::Str("choice"), Token::Str("Mpfr(100)"),
+            Token::Str("negative"), Token::Bool(false),
+            Token::Str("exponent"), Token::I64(1),
+            Token::Str("limbs"), Token::Tuple { len: 2 }, Token::U64(0), Token::U64(1), Token::TupleEnd,
+            Token::StructEnd
+        ], "invalid sign, exponent or limbs");
+        // Bits below the precision of 100 bits.
+        assert_de_tokens_error::<serde_test::Compact<LoadedMpfr100>>(&[
+            Token::Struct { name: "UniFloat", len: 4 },
+            Token::Str("choice"), Token::Str("Mpfr(100)"),
+            Token::Str("negative"), Token::Bool(false),
+            Token::Str("exponent"), Token::I64(1),
+            Token::Str("limbs"), Token::Tuple { len: 2 }, Token::U64(1), Token::U64(1 << 63), Token::TupleEnd,
+            Token::StructEnd
+        ], "invalid sign, exponent or limbs");
+    }
+}
@@ -0,0 +1,176 @@
+#![cfg(feature = "softmpfr")]
+
+use core::cmp::Ordering;
+use crate::{MpfrBounds, RoundingMode, SoftMpfr, UniFloat, UniFloatChoice};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+type Mpfr100 = UniFloat<{ MPFR_100_BITS }>;
+
+const VALUES: [f64; 9] = [1.0, -2.5, 0.1, 7.0e10, -1.0e-7, 3.0, 1.0 / 3.0, -1.0e300, 2.0e-290];
+const ROUNDINGS: [RoundingMode; 5] = [RoundingMode::Nearest, RoundingMode::TowardZero, RoundingMode::Up,
+    RoundingMode::Down, RoundingMode::ToNearestAway];
+const OPERATIONS: [&str; 5] = ["add", "sub", "mul", "div", "sqrt"];
+
+fn soft<const LIMBS: usize>(precision: usize, value: f64) -> SoftMpfr<LIMBS> {
+    let mut x = SoftMpfr::new(precision);
+    assert_eq!(x.set_f64(value, RoundingMode::Nearest), Ordering::Equal);
+    x
+}
+
+fn soft_op<const LIMBS: usize>(precision: usize, name: &str, x: f64, y: f64, rounding: RoundingMode)
+-> (SoftMpfr<LIMBS>, Ordering) {
+    let (x, y) = (soft::<LIMBS>(precision, x), soft::<LIMBS>(precision, y));
+    let mut result = SoftMpfr::new(precision);
+    let ternary = match name {
+        "add" => result.add(&x, &y, rounding),
+        "sub" => result.sub(&x, &y, rounding),
+        "mul" => result.mul(&x, &y, rounding),
+        "div" => result.div(&x, &y, rounding),
+        _ => result.sqrt(&x, rounding)
+    };
+    (result, ternary)
+}
+
+fn mpfr_op(name: &str, x: f64, y: f64, rounding: RoundingMode) -> Mpfr100 {
+    let (mut x, mut y) = (Mpfr100::from_f64(x), Mpfr100::from_f64(y));
+    x.copied();
+    y.copied();
+    match name {
+        "add" => x.add_round(&y, rounding),
+        "sub" => x.sub_round(&y, rounding),
+        "mul" => x.mul_round(&y, rounding),
+        "div" => x.div_round(&y, rounding),
+        _ => x.sqrt_round(rounding)
+    }.unwrap()
+}
+
+/// A value of up to 106 bits, as the f64 nearest to it and the rest.
+fn soft_parts(x: &SoftMpfr<2>) -> (f64, f64) {
+    let high = x.to_f64(RoundingMode::Nearest);
+    if !high.is_finite() || high == 0.0 {
+        return (high, 0.0);
+    }
+    let mut rest = SoftMpfr::<2>::new(128);
+    rest.sub(x, &soft(128, high), RoundingMode::Nearest);
+    (high, rest.to_f64(RoundingMode::Nearest))
+}
+
+fn mpfr_parts(x: &Mpfr100) -> (f64, f64) {
+    let high = x.to_f64_nearest();
+    if !high.is_finite() || high == 0.0 {
+        return (high, 0.0);
+    }
+    let mut high_mpfr = Mpfr100::from_f64(high);
+    high_mpfr.copied();
+    let mut rest = x - &high_mpfr;
+    rest.copied();
+    (high, rest.to_f64_nearest())
+}
+
+fn same(x: f64, y: f64) -> bool {
+    x == y && x.is_sign_negative() == y.is_sign_negative() || x.is_nan() && y.is_nan()
+}
+
+/// At 53 bits, the results must be as of f64 (with its operators, which round to nearest).
+#[test]
+fn softmpfr_as_f64() {
+    for &x in VALUES.iter() {
+        for &y in VALUES.iter() {
+            for &name in OPERATIONS.iter() {
+                let (result, _) = soft_op::<1>(53, name, x, y, RoundingMode::Nearest);
+                let expected = match name {
+                    "add" => x + y,
+                    "sub" => x - y,
+                    "mul" => x * y,
+                    "div" => x / y,
+                    _ => libm::sqrt(x)
+                };
+                assert!(same(result.to_f64(RoundingMode::Nearest), expected), "{}({}, {})", name, x, y);
+            }
+        }
+    }
+}
+
+/// At 100 bits, the results (and the ternary values) must be as of MPFR, in any direction.
+#[test]
+fn softmpfr_as_mpfr() {
+    for &x in VALUES.iter() {
+        for &y in VALUES.iter() {
+            for &name in OPERATIONS.iter() {
+                let mut up = mpfr_op(name, x, y, RoundingMode::Up);
+                up.copied();
+                let mut down = mpfr_op(name, x, y, RoundingMode::Down);
+                down.copied();
+                for &rounding in ROUNDINGS.iter() {
+                    let (result, ternary) = soft_op::<2>(100, name, x, y, rounding);
+                    let mut expected = mpfr_op(name, x, y, rounding);
+                    expected.copied();
+                    let (parts, expected_parts) = (soft_parts(&result), mpfr_parts(&expected));
+                    assert!(same(parts.0, expected_parts.0) && same(parts.1, expected_parts.1),
+                        "{}({}, {}) {:?}", name, x, y, rounding);
+                    let expected_ternary = if expected.is_nan() || up.eq(&down) {
+                        Ordering::Equal
+                    } else if expected.eq(&up) {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Less
+                    };
+                    assert_eq!(ternary, expected_ternary, "{}({}, {}) {:?}", name, x, y, rounding);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn softmpfr_specials() {
+    let (zero, negative_zero, one) = (soft::<1>(53, 0.0), soft::<1>(53, -0.0), soft::<1>(53, 1.0));
+    let mut result = SoftMpfr::<1>::new(53);
+    result.sub(&one, &one, RoundingMode::Nearest);
+    assert!(result.is_zero() && !result.is_sign_negative());
+    result.sub(&one, &one, RoundingMode::Down);
+    assert!(result.is_zero() && result.is_sign_negative());
+    result.add(&negative_zero, &negative_zero, RoundingMode::Nearest);
+    assert!(result.is_zero() && result.is_sign_negative());
+    result.sqrt(&negative_zero, RoundingMode::Nearest);
+    assert!(result.is_zero() && result.is_sign_negative());
+    result.div(&one, &zero, RoundingMode::Nearest);
+    assert!(result.is_infinite() && !result.is_sign_negative());
+    let infinity = result;
+    result.mul(&infinity, &zero, RoundingMode::Nearest);
+    assert!(result.is_nan());
+    result.sub(&infinity, &infinity, RoundingMode::Nearest);
+    assert!(result.is_nan() && result.partial_cmp(&result).is_none());
+    result.sqrt(&soft(53, -1.0), RoundingMode::Nearest);
+    assert!(result.is_nan());
+    assert!(negative_zero == zero && zero < one && one < infinity);
+}
+
+#[test]
+fn softmpfr_rounds_to_f64_subnormals() {
+    // 2^-1074 * (1 + 2^-60): 61 bits, beyond f64's subnormals.
+    let mut x = SoftMpfr::<1>::new(61);
+    let mut least = soft::<1>(61, f64::from_bits(1));
+    x.set(&least, RoundingMode::Nearest);
+    let mut tiny = soft::<1>(61, libm::ldexp(1.0, -1134));
+    least.add(&x, &tiny, RoundingMode::Nearest);
+    assert_eq!(least.to_f64(RoundingMode::Nearest), f64::from_bits(1));
+    assert_eq!(least.to_f64(RoundingMode::Down), f64::from_bits(1));
+    assert_eq!(least.to_f64(RoundingMode::Up), f64::from_bits(2));
+    // Half of it is a tie, to even (zero).
+    tiny.set_f64(libm::ldexp(1.0, -1075), RoundingMode::Nearest);
+    assert_eq!(tiny.to_f64(RoundingMode::Nearest), 0.0);
+    assert_eq!(tiny.to_f64(RoundingMode::ToNearestAway), f64::from_bits(1));
+    assert_eq!(tiny.to_f64(RoundingMode::Up), f64::from_bits(1));
+    x.set_f64(-libm::ldexp(1.0, -1100), RoundingMode::Nearest);
+    assert!(same(x.to_f64(RoundingMode::Up), -0.0));
+    assert_eq!(x.to_f64(RoundingMode::Down), -f64::from_bits(1));
+
+    x.set_f64(f64::MAX, RoundingMode::Nearest);
+    let mut twice = SoftMpfr::<1>::new(61);
+    twice.add(&x, &x, RoundingMode::Nearest);
+    assert_eq!(twice.to_f64(RoundingMode::Nearest), f64::INFINITY);
+    assert_eq!(twice.to_f64(RoundingMode::TowardZero), f64::MAX);
+}
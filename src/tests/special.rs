@@ -0,0 +1,290 @@
+#![cfg(not(feature = "f32_only"))]
+
+use {core::f64::consts::PI, gmp_mpfr_sys::mpfr};
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, UniF64};
+
+const MPFR_200_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(200)
+};
+type UniMpfr200bit = UniFloat<{ MPFR_200_BITS }>;
+
+/// Assert that `value` and `expected` differ by a few units in the last place (at 200 bits) at most.
+fn assert_mpfr_close(value: &UniMpfr200bit, expected: &UniMpfr200bit) {
+    let mut difference = UniMpfr200bit::NAN;
+    difference.copied();
+    unsafe {
+        mpfr::sub(difference.mpfr_mut(), value.mpfr_src().as_ptr(),
+            expected.mpfr_src().as_ptr(), mpfr::rnd_t::RNDN);
+        assert_eq!(mpfr::nan_p(difference.mpfr_src().as_ptr()), 0);
+        if mpfr::zero_p(difference.mpfr_src().as_ptr()) == 0 {
+            assert!(mpfr::get_exp(difference.mpfr_src().as_ptr())
+                <= mpfr::get_exp(expected.mpfr_src().as_ptr()) - 195);
+        }
+    }
+}
+
+#[test]
+fn li2_of_one_is_pi_squared_over_six() {
+    let mut one = UniMpfr200bit::from_f64(1.0);
+    one.copied();
+    let mut li2 = one.li2();
+    li2.copied();
+
+    let mut expected = UniMpfr200bit::NAN;
+    expected.copied();
+    unsafe {
+        let expected = expected.mpfr_mut();
+        mpfr::const_pi(expected, mpfr::rnd_t::RNDN);
+        mpfr::sqr(expected, expected, mpfr::rnd_t::RNDN);
+        mpfr::div_ui(expected, expected, 6, mpfr::rnd_t::RNDN);
+    }
+    assert_mpfr_close(&li2, &expected);
+
+    let mut one = UniF64::from_f64(1.0);
+    one.copied();
+    let mut li2 = one.li2();
+    li2.copied();
+    assert!(libm::fabs(li2.to_f64_nearest() - PI * PI / 6.0) < 1e-15);
+}
+
+#[test]
+fn digamma_of_one_is_minus_euler_mascheroni() {
+    let mut one = UniMpfr200bit::from_f64(1.0);
+    one.copied();
+    let mut digamma = one.digamma();
+    digamma.copied();
+
+    let mut expected = UniMpfr200bit::NAN;
+    expected.copied();
+    unsafe {
+        let expected = expected.mpfr_mut();
+        mpfr::const_euler(expected, mpfr::rnd_t::RNDN);
+        mpfr::neg(expected, expected, mpfr::rnd_t::RNDN);
+    }
+    assert_mpfr_close(&digamma, &expected);
+
+    const EULER_MASCHERONI: f64 = 0.577_215_664_901_532_9;
+    let mut one = UniF64::from_f64(1.0);
+    one.copied();
+    let mut digamma = one.digamma();
+    digamma.copied();
+    assert!(libm::fabs(digamma.to_f64_nearest() + EULER_MASCHERONI) < 1e-14);
+}
+
+#[test]
+fn digamma_poles() {
+    for &(value, pole) in [(-2.0, f64::NAN), (0.0, f64::NEG_INFINITY), (-0.0, f64::INFINITY)].iter() {
+        let mut float = UniF64::from_f64(value);
+        float.copied();
+        let mut digamma = float.digamma();
+        digamma.copied();
+        assert_eq!(digamma.to_f64_nearest().to_bits(), pole.to_bits());
+
+        let mut float = UniMpfr200bit::from_f64(value);
+        float.copied();
+        let mut digamma = float.digamma();
+        digamma.copied();
+        assert_eq!(digamma.to_f64_nearest().is_nan(), pole.is_nan());
+        if !pole.is_nan() {
+            assert_eq!(digamma.to_f64_nearest(), pole);
+        }
+    }
+}
+
+#[test]
+fn gamma_of_integers_and_halves() {
+    let mut half = UniMpfr200bit::from_f64(0.5);
+    half.copied();
+    let mut gamma = half.gamma();
+    gamma.copied();
+    let mut squared = UniMpfr200bit::NAN;
+    squared.copied();
+    let mut expected = UniMpfr200bit::NAN;
+    expected.copied();
+    unsafe {
+        mpfr::sqr(squared.mpfr_mut(), gamma.mpfr_src().as_ptr(), mpfr::rnd_t::RNDN);
+        mpfr::const_pi(expected.mpfr_mut(), mpfr::rnd_t::RNDN);
+    }
+    assert_mpfr_close(&squared, &expected);
+
+    for &(value, expected) in [(5.0, 24.0), (1.0, 1.0), (-0.0, f64::NEG_INFINITY), (-3.0, f64::NAN)].iter() {
+        let mut float = UniF64::from_f64(value);
+        float.copied();
+        let mut gamma = float.gamma();
+        gamma.copied();
+        let mut twofloat = crate::UniTwoFloat::from_f64(value);
+        twofloat.copied();
+        let mut twofloat_gamma = twofloat.gamma();
+        twofloat_gamma.copied();
+        let mut mpfr_float = UniMpfr200bit::from_f64(value);
+        mpfr_float.copied();
+        let mut mpfr_gamma = mpfr_float.gamma();
+        mpfr_gamma.copied();
+        for &result in [gamma.to_f64_nearest(), twofloat_gamma.to_f64_nearest(), mpfr_gamma.to_f64_nearest()].iter() {
+            assert!(result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan()), "{}", value);
+        }
+    }
+}
+
+#[test]
+fn ln_gamma_sign() {
+    // Gamma(-0.5) = -2 * sqrt(π).
+    let expected = libm::log(2.0 * libm::sqrt(PI));
+    let mut float = UniF64::from_f64(-0.5);
+    float.copied();
+    let (mut ln, sign) = float.ln_gamma();
+    ln.copied();
+    assert_eq!(sign, -1);
+    assert!(libm::fabs(ln.to_f64_nearest() - expected) < 1e-15);
+
+    let mut twofloat = crate::UniTwoFloat::from_f64(-0.5);
+    twofloat.copied();
+    let (mut ln, sign) = twofloat.ln_gamma();
+    ln.copied();
+    assert_eq!(sign, -1);
+    assert!(libm::fabs(ln.to_f64_nearest() - expected) < 1e-15);
+
+    let mut mpfr_float = UniMpfr200bit::from_f64(-0.5);
+    mpfr_float.copied();
+    let (mut ln, sign) = mpfr_float.ln_gamma();
+    ln.copied();
+    assert_eq!(sign, -1);
+    assert!(libm::fabs(ln.to_f64_nearest() - expected) < 1e-15);
+
+    for &value in [1.0, 2.0].iter() {
+        let mut twofloat = crate::UniTwoFloat::from_f64(value);
+        twofloat.copied();
+        let (mut ln, sign) = twofloat.ln_gamma();
+        ln.copied();
+        assert_eq!((ln.to_f64_nearest(), sign), (0.0, 1));
+    }
+}
+
+/// TwoFloat results of `gamma()` have relative error below 1e-26, and `ln_gamma()` and
+/// `digamma()` have absolute error below 1e-28, compared to MPFR at 200 bits.
+#[test]
+fn gamma_twofloat_accuracy() {
+    for &value in [0.3, 1.5, 4.7, -2.5, 30.5, 100.25, -7.3, 1e-5].iter() {
+        let mut twofloat = crate::UniTwoFloat::from_f64(value);
+        twofloat.copied();
+        let mut reference = UniMpfr200bit::from_f64(value);
+        reference.copied();
+        let (twofloat_ln, _) = twofloat.ln_gamma();
+        let (reference_ln, _) = reference.ln_gamma();
+        for &(name, mut result, mut expected, bound) in [("gamma", twofloat.gamma(), reference.gamma(), 1e-26),
+            ("ln_gamma", twofloat_ln, reference_ln, 1e-28),
+            ("digamma", twofloat.digamma(), reference.digamma(), 1e-28)].iter() {
+            result.copied();
+            expected.copied();
+            let error = unsafe {
+                let expected = expected.mpfr_mut();
+                let magnitude = mpfr::get_d(expected, mpfr::rnd_t::RNDN);
+                mpfr::sub_d(expected, expected, result.twofloats[0].hi(), mpfr::rnd_t::RNDN);
+                mpfr::sub_d(expected, expected, result.twofloats[0].lo(), mpfr::rnd_t::RNDN);
+                let error = mpfr::get_d(expected, mpfr::rnd_t::RNDN);
+                if name == "gamma" { error / magnitude } else { error }
+            };
+            assert!(libm::fabs(error) < bound, "{}({}): {}", name, value, error);
+        }
+    }
+}
+
+#[test]
+fn error_functions_special_values() {
+    for &(name, value, expected) in [("erf", 0.0, 0.0), ("erf", -0.0, -0.0), ("erf", f64::INFINITY, 1.0),
+        ("erf", f64::NEG_INFINITY, -1.0), ("erf", f64::NAN, f64::NAN), ("erfc", 0.0, 1.0),
+        ("erfc", f64::INFINITY, 0.0), ("erfc", f64::NEG_INFINITY, 2.0), ("erfc", 1e3, 0.0)].iter() {
+        let mut float = UniF64::from_f64(value);
+        float.copied();
+        let mut twofloat = crate::UniTwoFloat::from_f64(value);
+        twofloat.copied();
+        let mut mpfr_float = UniMpfr200bit::from_f64(value);
+        mpfr_float.copied();
+        let (mut result, mut twofloat_result, mut mpfr_result) = if name == "erf" {
+            (float.erf(), twofloat.erf(), mpfr_float.erf())
+        } else {
+            (float.erfc(), twofloat.erfc(), mpfr_float.erfc())
+        };
+        result.copied();
+        twofloat_result.copied();
+        mpfr_result.copied();
+        for &result in [result.to_f64_nearest(), twofloat_result.to_f64_nearest(), mpfr_result.to_f64_nearest()].iter() {
+            assert!(result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan()), "{}({})", name, value);
+        }
+    }
+}
+
+/// TwoFloat results have relative error below 1e-26, compared to MPFR at 200 bits. That includes
+/// `erfc()` of large values (where 1 - erf() would cancel).
+#[test]
+fn error_functions_twofloat_accuracy() {
+    for &value in [1e-10, 0.3, -1.7, 2.0, 3.5, -5.0, 9.0, 20.0].iter() {
+        let mut twofloat = crate::UniTwoFloat::from_f64(value);
+        twofloat.copied();
+        let mut reference = UniMpfr200bit::from_f64(value);
+        reference.copied();
+        for &(name, mut result, mut expected) in [("erf", twofloat.erf(), reference.erf()),
+            ("erfc", twofloat.erfc(), reference.erfc())].iter() {
+            result.copied();
+            expected.copied();
+            let error = unsafe {
+                let expected = expected.mpfr_mut();
+                let magnitude = mpfr::get_d(expected, mpfr::rnd_t::RNDN);
+                mpfr::sub_d(expected, expected, result.twofloats[0].hi(), mpfr::rnd_t::RNDN);
+                mpfr::sub_d(expected, expected, result.twofloats[0].lo(), mpfr::rnd_t::RNDN);
+                mpfr::get_d(expected, mpfr::rnd_t::RNDN) / magnitude
+            };
+            assert!(libm::fabs(error) < 1e-26, "{}({}): {}", name, value, error);
+        }
+    }
+}
+
+#[test]
+fn zeta_of_two_is_pi_squared_over_six() {
+    let mut expected = UniMpfr200bit::NAN;
+    expected.copied();
+    unsafe {
+        let expected = expected.mpfr_mut();
+        mpfr::const_pi(expected, mpfr::rnd_t::RNDN);
+        mpfr::sqr(expected, expected, mpfr::rnd_t::RNDN);
+        mpfr::div_ui(expected, expected, 6, mpfr::rnd_t::RNDN);
+    }
+    let mut two = UniMpfr200bit::from_f64(2.0);
+    two.copied();
+    let mut zeta = two.zeta();
+    zeta.copied();
+    assert_mpfr_close(&zeta, &expected);
+
+    let mut zeta = UniMpfr200bit::zeta_ui(2);
+    zeta.copied();
+    assert_mpfr_close(&zeta, &expected);
+}
+
+#[test]
+fn airy_and_exponential_integral() {
+    // Ai(0) = 1 / (3^(2/3) * Gamma(2/3)), and Ei(1).
+    for &(name, value, expected) in [("ai", 0.0, 0.355_028_053_887_817_2), ("ai", 2.0, 0.034_924_130_423_274_38),
+        ("eint", 1.0, 1.895_117_816_355_936_8), ("eint", 0.0, f64::NEG_INFINITY)].iter() {
+        let mut float = UniMpfr200bit::from_f64(value);
+        float.copied();
+        let mut result = if name == "ai" { float.ai() } else { float.eint() };
+        result.copied();
+        let result = result.to_f64_nearest();
+        assert!(result == expected || libm::fabs(result - expected) < 1e-15 * libm::fabs(expected), "{}({})", name, value);
+    }
+}
+
+#[test]
+fn mpfr_only_special_functions_are_nan_otherwise() {
+    let mut two = UniF64::from_f64(2.0);
+    two.copied();
+    let mut twofloat_two = crate::UniTwoFloat::from_f64(2.0);
+    twofloat_two.copied();
+    for &(mut result, mut twofloat_result) in [(two.zeta(), twofloat_two.zeta()), (two.ai(), twofloat_two.ai()),
+        (two.eint(), twofloat_two.eint()), (UniF64::zeta_ui(2), crate::UniTwoFloat::zeta_ui(2))].iter() {
+        result.copied();
+        twofloat_result.copied();
+        assert!(result.to_f64_nearest().is_nan());
+        assert!(twofloat_result.to_f64_nearest().is_nan());
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// `statistic` of `values` (up to 8 of them), as f64 and whether it's negative.
+fn statistic<const C: UniFloatChoice>(values: &[f64], statistic: fn(&[UniFloat<C>]) -> UniFloat<C>) -> (f64, bool) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut floats = [UniFloat::<C>::NAN; 8];
+    for (float, &value) in floats.iter_mut().zip(values) {
+        *float = UniFloat::<C>::from_f64(value);
+        float.copied();
+    }
+    let mut result = statistic(&floats[..values.len()]);
+    result.copied();
+    (result.to_f64_nearest(), result.is_sign_negative())
+}
+
+fn assert_statistics<const C: UniFloatChoice>(offset: f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    assert_eq!(statistic::<C>(&values, UniFloat::mean).0, 5.0);
+    assert_eq!(statistic::<C>(&values, UniFloat::population_variance).0, 4.0);
+    assert_eq!(statistic::<C>(&values, UniFloat::population_std_dev).0, 2.0);
+    assert_eq!(statistic::<C>(&[1.0, 3.0], UniFloat::variance).0, 2.0);
+    assert_eq!(statistic::<C>(&[0.0, 6.0, 3.0], UniFloat::std_dev).0, 3.0);
+
+    // The naive formula (the mean of the squares minus the square of the mean) loses all digits
+    // here.
+    let shifted = [offset + 4.0, offset + 7.0, offset + 13.0, offset + 16.0];
+    assert_eq!(statistic::<C>(&shifted, UniFloat::mean).0, offset + 10.0);
+    assert_eq!(statistic::<C>(&shifted, UniFloat::variance).0, 30.0);
+    assert_eq!(statistic::<C>(&shifted, UniFloat::population_variance).0, 22.5);
+
+    assert!(statistic::<C>(&[], UniFloat::mean).0.is_nan());
+    assert!(statistic::<C>(&[3.0], UniFloat::variance).0.is_nan());
+    assert_eq!(statistic::<C>(&[3.0], UniFloat::population_variance).0, 0.0);
+    assert!(statistic::<C>(&[1.0, f64::NAN], UniFloat::std_dev).0.is_nan());
+
+    assert_eq!(statistic::<C>(&[3.0, -0.0, 0.0, -1.0, 7.0], UniFloat::min_of).0, -1.0);
+    assert_eq!(statistic::<C>(&[3.0, -0.0, 0.0, -1.0, 7.0], UniFloat::max_of).0, 7.0);
+    assert_eq!(statistic::<C>(&[0.0, -0.0], UniFloat::min_of), (0.0, true));
+    assert_eq!(statistic::<C>(&[-0.0, 0.0], UniFloat::max_of), (0.0, false));
+    assert!(statistic::<C>(&[1.0, f64::NAN, 2.0], UniFloat::max_of).0.is_nan());
+    assert!(statistic::<C>(&[], UniFloat::min_of).0.is_nan());
+}
+
+#[test]
+fn statistics() {
+    assert_statistics::<{ UniFloatChoice::F32 }>(1e6);
+    #[cfg(not(feature = "f32_only"))] {
+        assert_statistics::<{ UniFloatChoice::F64 }>(1e15);
+        assert_statistics::<{ UniFloatChoice::TwoFloat }>(1e15);
+        assert_statistics::<{ UniFloatChoice::QuadFloat }>(1e15);
+        assert_statistics::<{ MPFR_100_BITS }>(1e15);
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(not(feature = "f32_only"))]
+use crate::MpfrBounds;
+use crate::{UniFloat, UniFloatChoice, UniStochastic, cestac, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+#[cfg(not(feature = "f32_only"))]
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+fn stochastic<const C: UniFloatChoice>(value: f64) -> UniStochastic<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut value = UniFloat::<C>::from_f64(value);
+    value.copied();
+    UniStochastic::new(&value)
+}
+
+fn assert_stochastic<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let all_digits = C.precision_bits() as f64 * core::f64::consts::LOG10_2;
+    let (mut one, mut two, mut three) = (stochastic::<C>(1.0), stochastic::<C>(2.0), stochastic::<C>(3.0));
+    one.copied();
+    two.copied();
+    three.copied();
+
+    // Exact results don't depend on the rounding direction.
+    let (mut mean, digits) = cestac::<C, 3>(|| {
+        let mut sum = &one + &two;
+        sum.copied();
+        let mut product = &sum * &three;
+        product.copied();
+        let mut root = product.sqrt();
+        root.copied();
+        -&(&root / &three)
+    });
+    assert_eq!(mean.copied().to_f64_nearest(), -1.0);
+    assert_eq!(digits, all_digits);
+
+    // Adding 0.1 a thousand times rounds each time, and the results spread.
+    let (mut mean, digits) = cestac::<C, 3>(|| {
+        let mut tenth = stochastic::<C>(0.1);
+        tenth.copied();
+        let mut sum = stochastic::<C>(0.0);
+        sum.copied();
+        for _ in 0..1000 {
+            let mut next = &sum + &tenth;
+            next.copied();
+            sum = next;
+            sum.copied();
+        }
+        sum
+    });
+    assert!((mean.copied().to_f64_nearest() - 100.0).abs() < 0.01);
+    assert!(digits > all_digits - 4.0 && digits < all_digits);
+}
+
+#[test]
+fn stochastic_cestac() {
+    assert_stochastic::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_stochastic::<{ UniFloatChoice::F64 }>();
+        assert_stochastic::<{ UniFloatChoice::QuadFloat }>();
+        assert_stochastic::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+fn stochastic_cancellation() {
+    // 1 + 10^-6 isn't exact in F32: rounding it either way changes ((1 + 10^-6) - 1) / 10^-6 by
+    // about 12%, so hardly one digit is significant.
+    let (mut one, mut small) = (stochastic::<{ UniFloatChoice::F32 }>(1.0), stochastic::<{ UniFloatChoice::F32 }>(1e-6));
+    one.copied();
+    small.copied();
+    let (mut mean, digits) = cestac::<{ UniFloatChoice::F32 }, 20>(|| {
+        let mut sum = &one + &small;
+        sum.copied();
+        let mut difference = &sum - &one;
+        difference.copied();
+        &difference / &small
+    });
+    assert!((mean.copied().to_f64_nearest() - 1.0).abs() < 0.2);
+    assert!(digits < 2.5);
+
+    // Zero up to rounding errors has no significant digits.
+    let (_, digits) = cestac::<{ UniFloatChoice::F32 }, 20>(|| {
+        let mut sum = &one + &small;
+        sum.copied();
+        let mut difference = &sum - &one;
+        difference.copied();
+        &difference - &small
+    });
+    assert!(digits < 1.0);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+#[should_panic(expected="UniStochastic needs directed rounding, which TwoFloat doesn't have.")]
+fn stochastic_twofloat() {
+    stochastic::<{ UniFloatChoice::TwoFloat }>(1.0);
+}
+
+#[test]
+#[should_panic(expected="cestac() needs at least 2 runs.")]
+fn cestac_needs_runs() {
+    cestac::<{ UniFloatChoice::F32 }, 1>(|| stochastic(1.0));
+}
@@ -0,0 +1,275 @@
+extern crate std;
+
+use crate::{MpfrBounds, PAIRWISE_BLOCK_SIZE, SumCompensated, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+fn sum<const C: UniFloatChoice>(values: &[f64]) -> (f64, bool) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut floats = [UniFloat::<C>::NAN; 4];
+    for (float, &value) in floats.iter_mut().zip(values) {
+        *float = UniFloat::<C>::from_f64(value);
+        float.copied();
+    }
+    let floats = &floats[..values.len()];
+    let mut from_slice = UniFloat::sum_compensated(floats);
+    from_slice.copied();
+    let mut from_iterator = floats.iter().sum_compensated();
+    from_iterator.copied();
+    let result = from_slice.to_f64_nearest();
+    assert!(result.to_bits() == from_iterator.to_f64_nearest().to_bits() || result.is_nan());
+    (result, from_slice.is_sign_negative())
+}
+
+fn assert_sum_compensated<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    // The naive sum is 0 (for f32, f64 and MPFR at 100 bits).
+    assert_eq!(sum::<C>(&[1.0, 1e30, 1.0, -1e30]).0, 2.0);
+    assert_eq!(sum::<C>(&[0.1, 0.2, -0.3]).0, sum::<C>(&[0.2, -0.3, 0.1]).0);
+    assert_eq!(sum::<C>(&[]), (0.0, false));
+    assert_eq!(sum::<C>(&[-0.0, -0.0]), (0.0, true));
+    assert_eq!(sum::<C>(&[f64::INFINITY, 1.0, 1e30]).0, f64::INFINITY);
+    assert!(sum::<C>(&[f64::INFINITY, 1.0, f64::NEG_INFINITY]).0.is_nan());
+}
+
+#[test]
+fn sum_compensated() {
+    assert_sum_compensated::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_sum_compensated::<{ UniFloatChoice::F64 }>();
+        assert_sum_compensated::<{ UniFloatChoice::TwoFloat }>();
+        assert_sum_compensated::<{ UniFloatChoice::QuadFloat }>();
+        assert_sum_compensated::<{ MPFR_100_BITS }>();
+    }
+}
+
+/// MPFR accumulates exactly, whatever the span of the exponents.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn sum_compensated_mpfr_is_exact() {
+    assert_eq!(sum::<{ MPFR_100_BITS }>(&[1e300, 1e-300, 1.0, -1e300]).0, 1.0 + 1e-300);
+    assert_eq!(sum::<{ MPFR_100_BITS }>(&[1e300, 1e-300, -1e300]).0, 1e-300);
+}
+
+fn exact_sum<const C: UniFloatChoice>(values: &[f64]) -> (f64, bool) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut floats = [UniFloat::<C>::NAN; 4];
+    for (float, &value) in floats.iter_mut().zip(values) {
+        *float = UniFloat::<C>::from_f64(value);
+        float.copied();
+    }
+    let mut result = UniFloat::exact_sum(&floats[..values.len()]);
+    result.copied();
+    (result.to_f64_nearest(), result.is_sign_negative())
+}
+
+fn exact_dot<const C: UniFloatChoice>(x: &[f64], y: &[f64]) -> (f64, bool) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut x_floats, mut y_floats) = ([UniFloat::<C>::NAN; 4], [UniFloat::<C>::NAN; 4]);
+    for (float, &value) in x_floats.iter_mut().zip(x).chain(y_floats.iter_mut().zip(y)) {
+        *float = UniFloat::<C>::from_f64(value);
+        float.copied();
+    }
+    let mut result = UniFloat::exact_dot(&x_floats[..x.len()], &y_floats[..y.len()]);
+    result.copied();
+    (result.to_f64_nearest(), result.is_sign_negative())
+}
+
+fn assert_exact_sum_and_dot<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    assert_eq!(exact_sum::<C>(&[1.0, 1e30, 1.0, -1e30]).0, 2.0);
+    assert_eq!(exact_sum::<C>(&[1e30, 1.0, -1e30, 1.0]).0, 2.0);
+    assert_eq!(exact_sum::<C>(&[]), (0.0, false));
+    assert_eq!(exact_sum::<C>(&[-0.0, -0.0]), (0.0, true));
+    assert_eq!(exact_sum::<C>(&[-0.0, 0.0]), (0.0, false));
+    assert_eq!(exact_sum::<C>(&[f64::INFINITY, 1.0, 1e30]).0, f64::INFINITY);
+    assert!(exact_sum::<C>(&[f64::INFINITY, 1.0, f64::NEG_INFINITY]).0.is_nan());
+
+    // 1e15 * 1e15 is not exact in f64 (nor in f32), the rest cancels it.
+    let (x, y) = ([1e15, 1.0, -1e15], [1e15, 3.0, 1e15]);
+    assert_eq!(exact_dot::<C>(&x, &y).0, 3.0);
+    assert_eq!(exact_dot::<C>(&[], &[]), (0.0, false));
+    assert_eq!(exact_dot::<C>(&[-1.0, 0.0], &[0.0, 5.0]), (0.0, false));
+    assert_eq!(exact_dot::<C>(&[-1.0], &[0.0]), (0.0, true));
+    assert!(exact_dot::<C>(&[f64::INFINITY, 1.0], &[0.0, 1.0]).0.is_nan());
+}
+
+#[test]
+fn exact_sum_and_dot() {
+    assert_exact_sum_and_dot::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_exact_sum_and_dot::<{ UniFloatChoice::F64 }>();
+        assert_exact_sum_and_dot::<{ UniFloatChoice::TwoFloat }>();
+        assert_exact_sum_and_dot::<{ UniFloatChoice::QuadFloat }>();
+        assert_exact_sum_and_dot::<{ MPFR_100_BITS }>();
+    }
+}
+
+/// The lower parts of TwoFloat add up too. MPFR products are exact, whatever the span of the
+/// exponents.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn exact_sum_and_dot_are_exact() {
+    let mut three = UniFloat::<{ UniFloatChoice::TwoFloat }>::from_f64(3.0);
+    three.copied();
+    let mut third = UniFloat::<{ UniFloatChoice::TwoFloat }>::ONE;
+    third.copied();
+    third /= &three;
+    let (high, low) = (third.twofloats[0].hi(), third.twofloats[0].lo());
+    let mut values = [third, UniFloat::<{ UniFloatChoice::TwoFloat }>::from_f64(-high)];
+    for value in values.iter_mut() {
+        value.copied();
+    }
+    let mut rest = UniFloat::exact_sum(&values);
+    rest.copied();
+    assert_eq!(rest.to_f64_nearest(), low);
+
+    assert_eq!(exact_dot::<{ MPFR_100_BITS }>(&[1e300, 1.0, -1e300], &[1e300, 1e-300, 1e300]).0, 1e-300);
+}
+
+#[test]
+#[should_panic]
+fn exact_dot_needs_same_length() {
+    exact_dot::<{ UniFloatChoice::F32 }>(&[1.0, 2.0], &[1.0]);
+}
+
+fn assert_sum_pairwise<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut floats = [UniFloat::<C>::NAN; 7];
+    for (index, float) in floats.iter_mut().enumerate() {
+        *float = UniFloat::<C>::from_f64(index as f64 + 1.0);
+        float.copied();
+    }
+    for &block_size in [1, 2, 3, PAIRWISE_BLOCK_SIZE].iter() {
+        let mut sum = UniFloat::sum_pairwise_blocks(&floats, block_size);
+        sum.copied();
+        assert_eq!(sum.to_f64_nearest(), 28.0);
+        let mut sum = UniFloat::sum_pairwise_blocks(&floats[..0], block_size);
+        sum.copied();
+        assert!(sum.to_f64_nearest() == 0.0 && sum.is_sign_positive());
+    }
+    let mut zeros = [UniFloat::<C>::NEG_ZERO; 3];
+    for zero in zeros.iter_mut() {
+        zero.copied();
+    }
+    let mut sum = UniFloat::sum_pairwise_blocks(&zeros, 1);
+    sum.copied();
+    assert!(sum.to_f64_nearest() == 0.0 && sum.is_sign_negative());
+}
+
+#[test]
+fn sum_pairwise() {
+    assert_sum_pairwise::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_sum_pairwise::<{ UniFloatChoice::F64 }>();
+        assert_sum_pairwise::<{ UniFloatChoice::TwoFloat }>();
+        assert_sum_pairwise::<{ UniFloatChoice::QuadFloat }>();
+        assert_sum_pairwise::<{ MPFR_100_BITS }>();
+    }
+}
+
+/// 2^16 times 0.1 in f32: all additions are exact for pairs, while the naive loop drifts off.
+#[test]
+fn sum_pairwise_error_growth() {
+    let mut tenths = std::vec![UniFloat::<{ UniFloatChoice::F32 }>::from_f64(0.1); 1 << 16];
+    for tenth in tenths.iter_mut() {
+        tenth.copied();
+    }
+    let exact = (0.1f32 as f64) * (1 << 16) as f64;
+    let mut pairwise = UniFloat::sum_pairwise_blocks(&tenths, 1);
+    pairwise.copied();
+    assert_eq!(pairwise.to_f64_nearest(), exact);
+    let mut blocked = UniFloat::sum_pairwise(&tenths);
+    blocked.copied();
+    let mut naive = UniFloat::sum_pairwise_blocks(&tenths, tenths.len());
+    naive.copied();
+    let (blocked_error, naive_error) = (libm::fabs(blocked.to_f64_nearest() - exact), libm::fabs(naive.to_f64_nearest() - exact));
+    assert!(blocked_error < exact * 1e-5 && naive_error > 10.0 * blocked_error, "{} {}", blocked_error, naive_error);
+}
+
+#[test]
+#[should_panic]
+fn sum_pairwise_needs_blocks() {
+    UniFloat::<{ UniFloatChoice::F32 }>::sum_pairwise_blocks(&[], 0);
+}
+
+fn norm2<const C: UniFloatChoice>(values: &[f64]) -> (f64, bool) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut floats = [UniFloat::<C>::NAN; 4];
+    for (float, &value) in floats.iter_mut().zip(values) {
+        *float = UniFloat::<C>::from_f64(value);
+        float.copied();
+    }
+    let mut result = UniFloat::norm2(&floats[..values.len()]);
+    result.copied();
+    (result.to_f64_nearest(), result.is_sign_negative())
+}
+
+/// Squares of 3 * `large` overflow, and squares of 3 * `small` underflow.
+fn assert_norm2<const C: UniFloatChoice>(large: f64, small: f64) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    assert_eq!(norm2::<C>(&[3.0, -4.0]).0, 5.0);
+    assert_eq!(norm2::<C>(&[1.0, 2.0, -2.0]).0, 3.0);
+    assert_eq!(norm2::<C>(&[2.0, 4.0, 5.0, 6.0]).0, 9.0);
+    assert_eq!(norm2::<C>(&[3.0 * large, 4.0 * large]).0, 5.0 * large);
+    assert_eq!(norm2::<C>(&[3.0 * small, 4.0 * small]).0, 5.0 * small);
+    assert_eq!(norm2::<C>(&[]), (0.0, false));
+    assert_eq!(norm2::<C>(&[-0.0]), (0.0, false));
+    assert_eq!(norm2::<C>(&[f64::NAN, f64::NEG_INFINITY]).0, f64::INFINITY);
+    assert!(norm2::<C>(&[1.0, f64::NAN]).0.is_nan());
+}
+
+#[test]
+fn norm2_without_overflow() {
+    assert_norm2::<{ UniFloatChoice::F32 }>(libm::ldexp(1.0, 120), libm::ldexp(1.0, -140));
+    #[cfg(not(feature = "f32_only"))] {
+        let (large, small) = (libm::ldexp(1.0, 1000), libm::ldexp(1.0, -1060));
+        assert_norm2::<{ UniFloatChoice::F64 }>(large, small);
+        assert_norm2::<{ UniFloatChoice::TwoFloat }>(large, small);
+        assert_norm2::<{ UniFloatChoice::QuadFloat }>(large, small);
+        assert_norm2::<{ MPFR_100_BITS }>(large, small);
+    }
+}
@@ -0,0 +1,159 @@
+use core::cmp::Ordering;
+use crate::{MpfrBounds, RoundingMode, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+#[cfg(not(feature = "f32_only"))]
+const MPFR_24_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(24)
+};
+
+/// 2^-n.
+fn tiny(n: i32) -> f64 {
+    libm::ldexp(1.0, -n)
+}
+
+fn ternary<const C: UniFloatChoice>(name: &str, x: f64, y: f64) -> Ordering where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let (mut float_x, mut float_y) = (UniFloat::<C>::from_f64(x), UniFloat::<C>::from_f64(y));
+    float_x.copied();
+    float_y.copied();
+    let (mut result, ternary) = match name {
+        "add" => float_x.add_with_ternary(&float_y),
+        "sub" => float_x.sub_with_ternary(&float_y),
+        "mul" => float_x.mul_with_ternary(&float_y),
+        "div" => float_x.div_with_ternary(&float_y),
+        _ => float_x.sqrt_with_ternary()
+    };
+    result.copied();
+    // The result is the same as without the ternary value.
+    let mut expected = UniFloat::<C>::NAN;
+    expected <<= &float_x;
+    match name {
+        "add" => expected += &float_y,
+        "sub" => expected -= &float_y,
+        "mul" => expected *= &float_y,
+        "div" => expected /= &float_y,
+        _ => expected <<= float_x.sqrt()
+    }
+    let (result, expected) = (result.to_f64_nearest(), expected.to_f64_nearest());
+    assert!(result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan()), "{}({}, {})", name, x, y);
+    ternary
+}
+
+/// Each tuple: (function name, x, y). The results are exact for every choice.
+const EXACT: [(&str, f64, f64); 8] = [
+    ("add", 1.0, 2.0),
+    ("sub", 0.5, 0.25),
+    ("mul", 3.0, -7.0),
+    ("div", 1.0, 8.0),
+    ("sqrt", 4.0, 0.0),
+    ("div", 1.0, 0.0),
+    ("sqrt", -1.0, 0.0),
+    ("add", f64::INFINITY, 1.0),
+];
+
+fn assert_exact<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(name, x, y) in EXACT.iter() {
+        assert_eq!(ternary::<C>(name, x, y), Ordering::Equal, "{}({}, {})", name, x, y);
+    }
+}
+
+#[test]
+fn exact_ternary() {
+    assert_exact::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_exact::<{ UniFloatChoice::F64 }>();
+        assert_exact::<{ UniFloatChoice::TwoFloat }>();
+        assert_exact::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[test]
+fn f32_ternary() {
+    assert_eq!(ternary::<{ UniFloatChoice::F32 }>("div", 1.0, 3.0), Ordering::Greater);
+    assert_eq!(ternary::<{ UniFloatChoice::F32 }>("sqrt", 2.0, 0.0), Ordering::Less);
+    assert_eq!(ternary::<{ UniFloatChoice::F32 }>("add", 1.0, tiny(30)), Ordering::Less);
+    assert_eq!(ternary::<{ UniFloatChoice::F32 }>("sub", -1.0, tiny(30)), Ordering::Greater);
+    assert_eq!(ternary::<{ UniFloatChoice::F32 }>("mul", f32::MAX as f64, -2.0), Ordering::Less);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn f64_ternary() {
+    let one_up = 1.0 + f64::EPSILON;
+    assert_eq!(ternary::<{ UniFloatChoice::F64 }>("div", 1.0, 3.0), Ordering::Less);
+    assert_eq!(ternary::<{ UniFloatChoice::F64 }>("div", -1.0, 3.0), Ordering::Greater);
+    assert_eq!(ternary::<{ UniFloatChoice::F64 }>("sqrt", 2.0, 0.0), Ordering::Greater);
+    assert_eq!(ternary::<{ UniFloatChoice::F64 }>("add", 1.0, tiny(60)), Ordering::Less);
+    assert_eq!(ternary::<{ UniFloatChoice::F64 }>("mul", one_up, one_up), Ordering::Less);
+    assert_eq!(ternary::<{ UniFloatChoice::F64 }>("mul", f64::MAX, 2.0), Ordering::Greater);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn twofloat_and_mpfr_ternary() {
+    // Double-double holds sums and products of two f64 exactly.
+    let one_up = 1.0 + f64::EPSILON;
+    assert_eq!(ternary::<{ UniFloatChoice::TwoFloat }>("add", 1.0, tiny(120)), Ordering::Equal);
+    assert_eq!(ternary::<{ UniFloatChoice::TwoFloat }>("mul", one_up, one_up), Ordering::Equal);
+    assert_ne!(ternary::<{ UniFloatChoice::TwoFloat }>("div", 1.0, 3.0), Ordering::Equal);
+    assert_ne!(ternary::<{ UniFloatChoice::TwoFloat }>("sqrt", 2.0, 0.0), Ordering::Equal);
+
+    assert_eq!(ternary::<{ MPFR_100_BITS }>("div", 1.0, 3.0), Ordering::Greater);
+    assert_eq!(ternary::<{ MPFR_100_BITS }>("sqrt", 2.0, 0.0), Ordering::Less);
+    assert_eq!(ternary::<{ MPFR_100_BITS }>("add", 1.0, tiny(120)), Ordering::Less);
+}
+
+#[test]
+fn prec_round() {
+    let mut half = UniFloat::<{ UniFloatChoice::F32 }>::from_f64(0.5);
+    half.copied();
+    let (mut result, ternary) = half.prec_round::<{ UniFloatChoice::F32 }>(RoundingMode::Up).unwrap();
+    result.copied();
+    assert_eq!((result.to_f64_nearest(), ternary), (0.5, Ordering::Equal));
+
+    #[cfg(not(feature = "f32_only"))] {
+        let mut third = UniFloat::<{ UniFloatChoice::F64 }>::from_f64(1.0 / 3.0);
+        third.copied();
+        let (mut result, ternary) = third.prec_round::<{ UniFloatChoice::F32 }>(RoundingMode::Nearest).unwrap();
+        result.copied();
+        assert_eq!((result.to_f64_nearest(), ternary), ((1.0f64 / 3.0) as f32 as f64, Ordering::Greater));
+        assert_eq!(third.prec_round::<{ UniFloatChoice::F32 }>(RoundingMode::Down).unwrap().1, Ordering::Less);
+        let (mut result, ternary) = third.prec_round::<{ MPFR_24_BITS }>(RoundingMode::Up).unwrap();
+        result.copied();
+        assert_eq!((result.to_f64_nearest(), ternary), ((1.0f64 / 3.0) as f32 as f64, Ordering::Greater));
+        // TwoFloat holds any f64.
+        assert_eq!(third.prec_round::<{ UniFloatChoice::TwoFloat }>(RoundingMode::Up).unwrap().1, Ordering::Equal);
+
+        let (mut third, mut three) = (UniFloat::<{ MPFR_100_BITS }>::from_f64(1.0), UniFloat::<{ MPFR_100_BITS }>::from_f64(3.0));
+        third.copied();
+        three.copied();
+        third /= &three;
+        let (mut result, ternary) = third.prec_round::<{ UniFloatChoice::F64 }>(RoundingMode::Nearest).unwrap();
+        result.copied();
+        assert_eq!((result.to_f64_nearest(), ternary), (1.0 / 3.0, Ordering::Less));
+        assert_eq!(third.prec_round::<{ UniFloatChoice::F64 }>(RoundingMode::Up).unwrap().1, Ordering::Greater);
+        assert_eq!(third.prec_round::<{ MPFR_24_BITS }>(RoundingMode::TowardZero).unwrap().1, Ordering::Less);
+        assert_eq!(third.prec_round::<{ MPFR_100_BITS }>(RoundingMode::Down).unwrap().1, Ordering::Equal);
+        let error = third.prec_round::<{ UniFloatChoice::TwoFloat }>(RoundingMode::Up).unwrap_err();
+        assert_eq!(error.rounding(), RoundingMode::Up);
+
+        let mut nan = UniFloat::<{ UniFloatChoice::F64 }>::from_f64(f64::NAN);
+        nan.copied();
+        assert_eq!(nan.prec_round::<{ UniFloatChoice::F32 }>(RoundingMode::Nearest).unwrap().1, Ordering::Equal);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{UniFloat, UniFloatChoice, assert_uni_ulps_eq};
+#[cfg(not(feature = "f32_only"))]
+use crate::{MpfrBounds, testing::{oracle, oracle_choice}};
+
+#[cfg(not(feature = "f32_only"))]
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+type Single = UniFloat<{ UniFloatChoice::F32 }>;
+
+#[test]
+fn ulps_eq() {
+    let (mut one, mut nan, mut zero, mut neg_zero) = (Single::ONE, Single::NAN, Single::ZERO, Single::NEG_ZERO);
+    one.copied();
+    nan.copied();
+    zero.copied();
+    neg_zero.copied();
+    let mut three_up = one.next_up().next_up().next_up();
+    three_up.copied();
+    assert_uni_ulps_eq!(one, one, 0);
+    assert_uni_ulps_eq!(one, three_up, 3);
+    assert_uni_ulps_eq!(&three_up, &one, 4,);
+    assert_uni_ulps_eq!(zero, neg_zero, 0);
+    assert_uni_ulps_eq!(nan, nan, 0);
+}
+
+#[test]
+#[should_panic(expected="assertion failed: `(left == right within 2 ulps)`")]
+fn ulps_eq_fails_beyond_ulps() {
+    let mut one = Single::ONE;
+    one.copied();
+    let mut three_up = one.next_up().next_up().next_up();
+    three_up.copied();
+    assert_uni_ulps_eq!(one, three_up, 2);
+}
+
+#[test]
+#[should_panic(expected="distance: `None` ulps")]
+fn ulps_eq_fails_for_nan_and_number() {
+    let (mut one, mut nan) = (Single::ONE, Single::NAN);
+    one.copied();
+    nan.copied();
+    assert_uni_ulps_eq!(nan, one, 1000);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn oracle_results() {
+    // exp() and hypot() of F32, F64 and MPFR are within an ulp of correctly rounded results.
+    let (mut x, mut y) = (Single::from_f64(1.7), Single::from_f64(-0.3));
+    x.copied();
+    y.copied();
+    let mut expected = oracle::<{ UniFloatChoice::F32 }, { oracle_choice(UniFloatChoice::F32) }, 1>([&x], |[x]| x.exp());
+    let mut actual = x.exp();
+    assert_uni_ulps_eq!(actual.copied(), expected.copied(), 1);
+    let mut expected = oracle::<{ UniFloatChoice::F32 }, { oracle_choice(UniFloatChoice::F32) }, 2>([&x, &y], |[x, y]| x.hypot(y));
+    let mut actual = x.hypot(&y);
+    assert_uni_ulps_eq!(actual.copied(), expected.copied(), 1);
+
+    let mut x = UniFloat::<{ UniFloatChoice::F64 }>::from_f64(0.1);
+    x.copied();
+    let mut expected = oracle::<{ UniFloatChoice::F64 }, { oracle_choice(UniFloatChoice::F64) }, 1>([&x], |[x]| x.exp_m1());
+    let mut actual = x.exp_m1();
+    assert_uni_ulps_eq!(actual.copied(), expected.copied(), 1);
+
+    let mut x = UniFloat::<{ MPFR_100_BITS }>::from_f64(2.5);
+    x.copied();
+    let mut expected = oracle::<{ MPFR_100_BITS }, { oracle_choice(MPFR_100_BITS) }, 1>([&x], |[x]| x.ln());
+    let mut actual = x.ln();
+    // MPFR rounds correctly.
+    assert_uni_ulps_eq!(actual.copied(), expected.copied(), 0);
+
+    // The oracle sees the inputs exactly, and rounds once: (1 + 2^-12)^2 - 1 is 2^-11 + 2^-24 (where
+    // F32 would round the square to 1 + 2^-11).
+    let mut x = Single::from_f64(1.0 + 1.0 / (1u64 << 12) as f64);
+    x.copied();
+    let mut expected = oracle::<{ UniFloatChoice::F32 }, { oracle_choice(UniFloatChoice::F32) }, 1>([&x], |[x]| {
+        let mut square = x * x;
+        square.copied();
+        let mut one = UniFloat::ONE;
+        one.copied();
+        square -= &one;
+        square
+    });
+    assert_eq!(expected.copied().to_f64_nearest(), 1.0 / (1u64 << 11) as f64 + 1.0 / (1u64 << 24) as f64);
+}
+
+#[test]
+#[cfg(not(feature = "f32_only"))]
+#[should_panic(expected="oracle() needs D at least twice as precise as C (see oracle_choice()).")]
+fn oracle_needs_precision() {
+    let mut x = UniFloat::<{ UniFloatChoice::F64 }>::ONE;
+    x.copied();
+    oracle::<{ UniFloatChoice::F64 }, { MPFR_100_BITS }, 1>([&x], |[x]| x.exp());
+}
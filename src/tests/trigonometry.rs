@@ -0,0 +1,207 @@
+use core::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+#[cfg(not(feature = "f32_only"))]
+use gmp_mpfr_sys::mpfr;
+use crate::{MpfrBounds, UniFloat, UniFloatChoice, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length,
+    twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(100)
+};
+
+/// Each tuple: (function name, value, result). The results are exact, or multiples of π/4 (which
+/// get compared after rounding them to the tested choice).
+const INVERSES: [(&str, f64, f64); 12] = [
+    ("asin", 0.0, 0.0),
+    ("asin", -0.0, -0.0),
+    ("asin", 1.0, FRAC_PI_2),
+    ("asin", -1.0, -FRAC_PI_2),
+    ("asin", 1.5, f64::NAN),
+    ("acos", 1.0, 0.0),
+    ("acos", -1.0, PI),
+    ("acos", f64::NAN, f64::NAN),
+    ("atan", -0.0, -0.0),
+    ("atan", 1.0, FRAC_PI_4),
+    ("atan", f64::INFINITY, FRAC_PI_2),
+    ("atan", f64::NEG_INFINITY, -FRAC_PI_2),
+];
+
+/// Each tuple: (y, x, atan2(y, x)).
+const ATAN2S: [(f64, f64, f64); 12] = [
+    (0.0, 1.0, 0.0),
+    (-0.0, 1.0, -0.0),
+    (0.0, -1.0, PI),
+    (-0.0, -1.0, -PI),
+    (0.0, 0.0, 0.0),
+    (-0.0, -0.0, -PI),
+    (1.0, 0.0, FRAC_PI_2),
+    (-1.0, -0.0, -FRAC_PI_2),
+    (1.0, 1.0, FRAC_PI_4),
+    (f64::INFINITY, f64::NEG_INFINITY, 3.0 * FRAC_PI_4),
+    (-5.0, f64::INFINITY, -0.0),
+    (f64::NAN, 1.0, f64::NAN),
+];
+
+fn assert_same<const C: UniFloatChoice>(result: UniFloat<C>, expected: f64, message: (&str, f64, f64)) where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    let mut result = result;
+    result.copied();
+    let mut expected = UniFloat::<C>::from_f64(expected);
+    expected.copied();
+    let (result, expected) = (result.to_f64_nearest(), expected.to_f64_nearest());
+    assert!(result.to_bits() == expected.to_bits() || (result.is_nan() && expected.is_nan()), "{:?}", message);
+}
+
+fn assert_inverses<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(name, value, expected) in INVERSES.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        let result = match name {
+            "asin" => float.asin(),
+            "acos" => float.acos(),
+            _ => float.atan()
+        };
+        assert_same(result, expected, (name, value, 0.0));
+    }
+    for &(y, x, expected) in ATAN2S.iter() {
+        let (mut float_y, mut float_x) = (UniFloat::<C>::from_f64(y), UniFloat::<C>::from_f64(x));
+        float_y.copied();
+        float_x.copied();
+        assert_same(float_y.atan2(&float_x), expected, ("atan2", y, x));
+    }
+}
+
+#[test]
+fn inverse_trigonometric() {
+    assert_inverses::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_inverses::<{ UniFloatChoice::F64 }>();
+        assert_inverses::<{ UniFloatChoice::TwoFloat }>();
+        assert_inverses::<{ MPFR_100_BITS }>();
+    }
+}
+
+/// Each tuple: (function name, value, result). The results are exact.
+const HALF_REVOLUTIONS: [(&str, f64, f64); 20] = [
+    ("sin_pi", 0.0, 0.0),
+    ("sin_pi", -0.0, -0.0),
+    ("sin_pi", 0.5, 1.0),
+    ("sin_pi", -0.5, -1.0),
+    ("sin_pi", 1.5, -1.0),
+    ("sin_pi", -3.0, -0.0),
+    ("sin_pi", 1e30, 0.0),
+    ("sin_pi", f64::INFINITY, f64::NAN),
+    ("cos_pi", -0.0, 1.0),
+    ("cos_pi", 0.5, 0.0),
+    ("cos_pi", -2.5, 0.0),
+    ("cos_pi", 3.0, -1.0),
+    ("cos_pi", -1e30, 1.0),
+    ("cos_pi", f64::NAN, f64::NAN),
+    ("tan_pi", -0.0, -0.0),
+    ("tan_pi", 0.25, 1.0),
+    ("tan_pi", -1.25, -1.0),
+    ("tan_pi", 3.0, -0.0),
+    ("tan_pi", 2.5, f64::INFINITY),
+    ("tan_pi", -0.5, f64::NEG_INFINITY),
+];
+
+fn assert_half_revolutions<const C: UniFloatChoice>() where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    for &(name, value, expected) in HALF_REVOLUTIONS.iter() {
+        let mut float = UniFloat::<C>::from_f64(value);
+        float.copied();
+        let result = match name {
+            "sin_pi" => float.sin_pi(),
+            "cos_pi" => float.cos_pi(),
+            _ => float.tan_pi()
+        };
+        assert_same(result, expected, (name, value, 0.0));
+    }
+}
+
+#[test]
+fn half_revolutions() {
+    assert_half_revolutions::<{ UniFloatChoice::F32 }>();
+    #[cfg(not(feature = "f32_only"))] {
+        assert_half_revolutions::<{ UniFloatChoice::F64 }>();
+        assert_half_revolutions::<{ UniFloatChoice::TwoFloat }>();
+        assert_half_revolutions::<{ MPFR_100_BITS }>();
+    }
+}
+
+#[cfg(not(feature = "f32_only"))]
+const MPFR_200_BITS: UniFloatChoice = UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_precision_binary(200)
+};
+
+/// TwoFloat results have relative error below 1e-26, compared to MPFR at 200 bits. That includes
+/// the multiples of π/4.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn inverse_trigonometric_twofloat_accuracy() {
+    type UniMpfr200bit = UniFloat<{ MPFR_200_BITS }>;
+    for &(y, x) in [(0.5, 1.0), (-0.3, 2.0), (1.0, -3.0), (-1e-10, -1.0), (1.0, 0.0), (-0.0, -2.0)].iter() {
+        let (mut twofloat_y, mut twofloat_x) = (crate::UniTwoFloat::from_f64(y), crate::UniTwoFloat::from_f64(x));
+        twofloat_y.copied();
+        twofloat_x.copied();
+        let (mut reference_y, mut reference_x) = (UniMpfr200bit::from_f64(y), UniMpfr200bit::from_f64(x));
+        reference_y.copied();
+        reference_x.copied();
+        for &(name, mut result, mut expected) in [("asin", twofloat_y.asin(), reference_y.asin()),
+            ("acos", twofloat_y.acos(), reference_y.acos()), ("atan", twofloat_x.atan(), reference_x.atan()),
+            ("atan2", twofloat_y.atan2(&twofloat_x), reference_y.atan2(&reference_x))].iter() {
+            result.copied();
+            expected.copied();
+            let error = unsafe {
+                let expected = expected.mpfr_mut();
+                let magnitude = mpfr::get_d(expected, mpfr::rnd_t::RNDN);
+                mpfr::sub_d(expected, expected, result.twofloats[0].hi(), mpfr::rnd_t::RNDN);
+                mpfr::sub_d(expected, expected, result.twofloats[0].lo(), mpfr::rnd_t::RNDN);
+                if magnitude == 0.0 { 0.0 } else { mpfr::get_d(expected, mpfr::rnd_t::RNDN) / magnitude }
+            };
+            assert!(libm::fabs(error) < 1e-26, "{}({}, {}): {}", name, y, x, error);
+        }
+    }
+}
+
+/// TwoFloat results have relative error below 1e-26, compared to MPFR at 200 bits, for large
+/// arguments, too.
+#[test]
+#[cfg(not(feature = "f32_only"))]
+fn half_revolutions_twofloat_accuracy() {
+    type UniMpfr200bit = UniFloat<{ MPFR_200_BITS }>;
+    for &value in [0.1, 1.0 / 3.0, -2.7, 0.2499, 1e15 + 0.3, -123456.789].iter() {
+        let mut twofloat = crate::UniTwoFloat::from_f64(value);
+        twofloat.copied();
+        let mut reference = UniMpfr200bit::from_f64(value);
+        reference.copied();
+        for &(name, mut result, mut expected) in [("sin_pi", twofloat.sin_pi(), reference.sin_pi()),
+            ("cos_pi", twofloat.cos_pi(), reference.cos_pi()), ("tan_pi", twofloat.tan_pi(), reference.tan_pi())].iter() {
+            result.copied();
+            expected.copied();
+            let error = unsafe {
+                let expected = expected.mpfr_mut();
+                let magnitude = mpfr::get_d(expected, mpfr::rnd_t::RNDN);
+                mpfr::sub_d(expected, expected, result.twofloats[0].hi(), mpfr::rnd_t::RNDN);
+                mpfr::sub_d(expected, expected, result.twofloats[0].lo(), mpfr::rnd_t::RNDN);
+                mpfr::get_d(expected, mpfr::rnd_t::RNDN) / magnitude
+            };
+            assert!(libm::fabs(error) < 1e-26, "{}({}): {}", name, value, error);
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{UniFloat, UniFloatBounds, UniFloatBoundsBase, UniFloatChoice};
+use crate::{MpfrBounds, UniF32, UniFloat, UniFloatBounds, UniFloatBoundsBase, UniFloatBoundsToChoice, UniFloatChoice, UniQuadFloat,
+    unifloat_type};
 
 extern crate std;
 
@@ -18,6 +19,121 @@ fn narrow_to_decimal() {
     //assert_eq!(bin_prec_4_min_neg4_max4.narrow_to_decimal(), dec_prec_1_min_neg1_max1);
 }
 
+#[test]
+fn decimal_to_choice() {
+    let decimal = |precision, min_exponent, max_exponent| UniFloatBounds::<{ UniFloatBoundsBase::DECIMAL }>::new(
+        precision, min_exponent, max_exponent);
+    assert_eq!(UniFloatChoice::F32.bounds::<{ UniFloatBoundsBase::DECIMAL }>().to_choice(), UniFloatChoice::F32);
+    assert_eq!(UniFloatChoice::F64.bounds::<{ UniFloatBoundsBase::DECIMAL }>().to_choice(), UniFloatChoice::F64);
+    assert_eq!(UniFloatChoice::TwoFloat.bounds::<{ UniFloatBoundsBase::DECIMAL }>().to_choice(), UniFloatChoice::TwoFloat);
+    assert_eq!(UniFloatChoice::QuadFloat.bounds::<{ UniFloatBoundsBase::DECIMAL }>().to_choice(), UniFloatChoice::QuadFloat);
+    // d digits need ceil(d * log2(10)) + 1 bits: 6 digits need 21 bits, 7 digits need 25 bits.
+    assert_eq!(decimal(6, -37, 38).to_choice(), UniFloatChoice::F32);
+    assert_eq!(decimal(7, -37, 38).to_choice(), UniFloatChoice::F64);
+    // 10^39 > f32::MAX, and 10^-38 < f32::MIN_POSITIVE.
+    assert_eq!(decimal(6, -37, 39).to_choice(), UniFloatChoice::F64);
+    assert_eq!(decimal(6, -38, 38).to_choice(), UniFloatChoice::F64);
+    assert_eq!(decimal(31, -307, 308).to_choice(), UniFloatChoice::TwoFloat);
+    // 32 digits need 108 bits, more than TwoFloat has. 64 digits need 214 bits, more than
+    // QuadFloat has.
+    assert_eq!(decimal(32, -307, 308).to_choice(), UniFloatChoice::QuadFloat);
+    assert_eq!(decimal(63, -307, 308).to_choice(), UniFloatChoice::QuadFloat);
+    assert_eq!(decimal(64, -307, 308).to_choice(), UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(214) });
+    assert_eq!(decimal(15, -307, 309).to_choice(), UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(51) });
+    assert_eq!(decimal(15, -308, 308).to_choice(), UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(51) });
+}
+
+unifloat_type!(Digits6, digits = 6, exp10 = -37..38);
+unifloat_type!(pub(crate) Digits40, digits = 40, exp10 = -100..100);
+unifloat_type!(Digits64, digits = 64, exp10 = -307..308);
+
+#[test]
+fn unifloat_type() {
+    // Each assignment compiles only if the types are the same.
+    let _: UniF32 = Digits6::ONE;
+    let _: UniQuadFloat = Digits40::ONE;
+    let _: UniFloat<{ UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(214) } }> = Digits64::ONE;
+    const DECIMAL: UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> = UniFloatBounds::new(15, -307, 308);
+    const CHOICE: UniFloatChoice = DECIMAL.covering_choice();
+    assert_eq!(CHOICE, DECIMAL.to_choice());
+    assert_eq!(CHOICE, UniFloatChoice::F64);
+}
+
+#[test]
+fn union_intersect_and_widen_precision() {
+    let (f32_bounds, f64_bounds) = (UniFloatChoice::F32.bounds::<{ UniFloatBoundsBase::BINARY }>(),
+        UniFloatChoice::F64.bounds::<{ UniFloatBoundsBase::BINARY }>());
+    let (precise, wide) = (UniFloatBounds::<{ UniFloatBoundsBase::BINARY }>::new(60, -10, 10),
+        UniFloatBounds::<{ UniFloatBoundsBase::BINARY }>::new(10, -2000, 2000));
+    assert_eq!(precise.union(&wide), UniFloatBounds::new(60, -2000, 2000));
+    assert_eq!(precise.union(&wide), wide.union(&precise));
+    assert_eq!(precise.intersect(&wide), UniFloatBounds::new(10, -10, 10));
+    assert_eq!(f32_bounds.union(&f64_bounds), f64_bounds);
+    assert_eq!(f32_bounds.intersect(&f64_bounds), f32_bounds);
+    assert!(f32_bounds.union(&precise).covers(&f32_bounds) && f32_bounds.union(&precise).covers(&precise));
+    // Disjoint exponent ranges.
+    let high = UniFloatBounds::<{ UniFloatBoundsBase::BINARY }>::new(10, 100, 200);
+    assert_eq!(precise.intersect(&high), UniFloatBounds::new(10, 100, 10));
+
+    assert_eq!(f32_bounds.widen_precision(29).to_choice(), UniFloatChoice::F64);
+    assert_eq!(f32_bounds.widen_precision(30).to_choice(), UniFloatChoice::TwoFloat);
+    assert_eq!(precise.widen_precision(usize::MAX).precision, usize::MAX);
+    assert_eq!(precise.widen_precision(46).to_choice(), UniFloatChoice::TwoFloat);
+    // Exponents beyond f64.
+    assert_eq!(precise.union(&wide).to_choice(), UniFloatChoice::Mpfr { bounds: MpfrBounds::for_precision_binary(60) });
+}
+
+#[test]
+fn binary_to_decimal_matches_core() {
+    assert_eq!(UniFloatChoice::F32.bounds::<{ UniFloatBoundsBase::DECIMAL }>(),
+        UniFloatBounds::new(f32::DIGITS as usize, f32::MIN_10_EXP as isize, f32::MAX_10_EXP as isize));
+    assert_eq!(UniFloatChoice::F64.bounds::<{ UniFloatBoundsBase::DECIMAL }>(),
+        UniFloatBounds::new(f64::DIGITS as usize, f64::MIN_10_EXP as isize, f64::MAX_10_EXP as isize));
+    // floor(105 * log10(2)) = 31.
+    assert_eq!(UniFloatChoice::TwoFloat.bounds::<{ UniFloatBoundsBase::DECIMAL }>(),
+        UniFloatBounds::new(31, f64::MIN_10_EXP as isize, f64::MAX_10_EXP as isize));
+    // floor(211 * log10(2)) = 63.
+    assert_eq!(UniFloatChoice::QuadFloat.bounds::<{ UniFloatBoundsBase::DECIMAL }>(),
+        UniFloatBounds::new(63, f64::MIN_10_EXP as isize, f64::MAX_10_EXP as isize));
+    const F64_DECIMAL: UniFloatBounds<{ UniFloatBoundsBase::DECIMAL }> = UniFloatBounds::new(15, -307, 308);
+    const F64_BINARY: UniFloatBounds<{ UniFloatBoundsBase::BINARY }> = F64_DECIMAL.to_binary();
+    assert_eq!(F64_BINARY, UniFloatBounds::new(51, -1019, 1024));
+}
+
+/// Conversions match the exact formulas (evaluated in f64, which is accurate enough for these
+/// exponents), and round trips stay on the conservative side.
+#[test]
+fn binary_decimal_round_trips() {
+    for exponent in -20_000..=20_000isize {
+        let binary = UniFloatBounds::<{ UniFloatBoundsBase::BINARY }>::new(1 + exponent.abs() as usize, exponent, exponent);
+        let decimal = binary.to_decimal();
+        let precision = binary.precision as f64;
+        let exponent = exponent as f64;
+        assert_eq!(decimal.precision as f64, libm::floor((precision - 1.0) * core::f64::consts::LOG10_2));
+        assert_eq!(decimal.min_exponent as f64, libm::ceil((exponent - 1.0) * core::f64::consts::LOG10_2));
+        assert_eq!(decimal.max_exponent as f64, libm::ceil(exponent * core::f64::consts::LOG10_2) - 1.0);
+        assert!(binary.covers(&decimal.to_binary()));
+
+        let decimal = UniFloatBounds::<{ UniFloatBoundsBase::DECIMAL }>::new(binary.precision - 1, binary.min_exponent,
+            binary.max_exponent);
+        let binary = decimal.to_binary();
+        assert_eq!(binary.precision as f64, libm::ceil(decimal.precision as f64 * core::f64::consts::LOG2_10) + 1.0);
+        assert_eq!(binary.min_exponent as f64, libm::floor(exponent * core::f64::consts::LOG2_10) + 1.0);
+        assert_eq!(binary.max_exponent, binary.min_exponent);
+        assert!(binary.to_decimal().covers(&decimal));
+    }
+    for &(min_exponent, max_exponent) in [(isize::MIN, isize::MAX), (isize::MIN + 1, isize::MAX - 1), (-1, 0)].iter() {
+        for &precision in [1, 2, usize::MAX / 4, usize::MAX].iter() {
+            let binary = UniFloatBounds::<{ UniFloatBoundsBase::BINARY }>::new(precision, min_exponent, max_exponent);
+            assert!(binary.covers(&binary.to_decimal().to_binary()));
+            // Decimal exponents that don't saturate in binary.
+            let decimal = UniFloatBounds::<{ UniFloatBoundsBase::DECIMAL }>::new(precision / 4, min_exponent / 4,
+                max_exponent / 4);
+            assert!(decimal.to_binary().to_decimal().covers(&decimal));
+        }
+    }
+}
+
 #[test]
 fn narrow_to_binary() {
 
@@ -0,0 +1,74 @@
+#![cfg(not(feature = "f32_only"))]
+
+use core::mem::MaybeUninit;
+use crate::{MpfrBounds, MpfrLimbPart, UniFloat, UniFloatBounds, UniFloatChoice, UniFloatMut};
+
+const BOUNDS_100_BITS: MpfrBounds = MpfrBounds::for_precision_binary(100);
+const MPFR_100_BITS: UniFloatChoice = UniFloatChoice::Mpfr { bounds: BOUNDS_100_BITS };
+
+#[test]
+fn same_as_unifloat() {
+    let (mut x_limbs, mut y_limbs): ([MpfrLimbPart; 2], [MpfrLimbPart; 2]) = ([MaybeUninit::uninit(); 2], [MaybeUninit::uninit(); 2]);
+    let mut x = UniFloatMut::new_in(BOUNDS_100_BITS, &mut x_limbs);
+    let mut y = UniFloatMut::new_in(BOUNDS_100_BITS, &mut y_limbs);
+    assert!(x.to_f64_nearest().is_nan());
+    assert_eq!(x.bounds(), BOUNDS_100_BITS);
+    x.set_f64(2.0);
+    x.sqrt_assign();
+    y.set_f64(3.0);
+    let y = y.view();
+    x *= &y;
+    x += &y;
+    x /= &y;
+    x -= &y;
+
+    let (mut two, mut three) = (UniFloat::<MPFR_100_BITS>::from_f64(2.0), UniFloat::<MPFR_100_BITS>::from_f64(3.0));
+    two.copied();
+    three.copied();
+    let mut expected = two.sqrt();
+    expected.copied();
+    expected *= &three;
+    expected += &three;
+    expected /= &three;
+    expected -= &three;
+    assert_eq!(x.to_f64_nearest(), expected.to_f64_nearest());
+}
+
+/// For normal results only.
+fn power_of_two(exponent: i64) -> f64 {
+    f64::from_bits(((1023 + exponent) as u64) << 52)
+}
+
+#[test]
+fn slice_of_runtime_length() {
+    let mut buffer: [MpfrLimbPart; 8] = [MaybeUninit::uninit(); 8];
+    // As if from a configuration file.
+    let bounds = MpfrBounds::for_bounds(&UniFloatBounds::new(300, -300, 100));
+    let length = UniFloatMut::limbs_needed(bounds);
+    assert!(length < buffer.len());
+    let (x_limbs, rest) = buffer.split_at_mut(length);
+    let mut x = UniFloatMut::new_in(bounds, x_limbs);
+    x.set_f64(1.0);
+
+    // Fewer bits than 1 + 2^-200.
+    let mut y = UniFloatMut::new_in(BOUNDS_100_BITS, rest);
+    y.set_f64(power_of_two(-200));
+    x += &y.view();
+    y.assign(&x.view());
+    assert_eq!(y.to_f64_nearest(), 1.0);
+    x -= &y.view();
+    assert_eq!(x.to_f64_nearest(), power_of_two(-200));
+
+    // Overflows beyond the exponent range.
+    x.set_f64(power_of_two(99));
+    y.assign(&x.view());
+    x *= &y.view();
+    assert_eq!(x.to_f64_nearest(), f64::INFINITY);
+}
+
+#[test]
+#[should_panic(expected = "The limb slice is too short for the MpfrBounds. See UniFloatMut::limbs_needed().")]
+fn short_slice() {
+    let mut limbs: [MpfrLimbPart; 1] = [MaybeUninit::uninit(); 1];
+    UniFloatMut::new_in(BOUNDS_100_BITS, &mut limbs);
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "zeroize")]
+
+use gmp_mpfr_sys::mpfr;
+use zeroize::Zeroize;
+use crate::{UniFloat, UniFloatChoice, UniF32, UniF64, UniTwoFloat, UniMpfrLimb2PrecAll};
+
+#[test]
+fn zeroize_native() {
+    let mut f32_based = UniF32::NAN;
+    f32_based.f32s[0] = 1.0 / 3.0;
+    f32_based.zeroize();
+    assert_eq!(f32_based.f32s[0].to_bits(), 0);
+
+    let mut f64_based = UniF64::NAN;
+    f64_based.f64s[0] = 1.0 / 3.0;
+    f64_based.zeroize();
+    assert_eq!(f64_based.f64s[0].to_bits(), 0);
+
+    let mut twofloat_based = UniTwoFloat::NAN;
+    twofloat_based.twofloats[0] = twofloat::TwoFloat::new_add(1.0, 1e-20);
+    twofloat_based.zeroize();
+    assert_eq!(twofloat_based.twofloats[0].hi().to_bits(), 0);
+    assert_eq!(twofloat_based.twofloats[0].lo().to_bits(), 0);
+}
+
+#[test]
+fn zeroize_mpfr_limbs() {
+    let mut mpfr_based = UniMpfrLimb2PrecAll::NAN;
+    mpfr_based.copied();
+    unsafe {
+        mpfr::const_pi(mpfr_based.mpfr_mut(), mpfr::rnd_t::RNDN);
+    }
+    assert!(mpfr_based.mpfr_limbs.iter().any(|limb| unsafe { limb.assume_init() } != 0));
+
+    mpfr_based.zeroize();
+    for limb in mpfr_based.mpfr_limbs.iter() {
+        assert_eq!(unsafe { limb.assume_init() }, 0);
+    }
+    // Reading as zero.
+    unsafe {
+        assert_ne!(mpfr::zero_p(mpfr_based.mpfr_src().as_ptr()), 0);
+        assert!(mpfr::signbit(mpfr_based.mpfr_src().as_ptr()) == 0);
+    }
+}
+
+#[test]
+fn zeroize_decimal() {
+    type UniDecimal16 = UniFloat<{ UniFloatChoice::decimal(16) }>;
+    let mut decimal_based: UniDecimal16 = "-0.1".parse().unwrap();
+    decimal_based.zeroize();
+    // Positive zero, not the NaN that all zero words would read as.
+    assert!(decimal_based.is_sign_positive());
+    assert_eq!(format!("{}", decimal_based), "0");
+}
@@ -0,0 +1,296 @@
+use core::f64::consts::{FRAC_PI_4, PI};
+#[cfg(feature = "twofloat")]
+use crate::TwoFloat;
+#[cfg(feature = "mpfr")]
+use {core::mem::MaybeUninit, crate::mpfr};
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+
+/// π as double-double: the nearest f64, and the rest.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+pub(crate) const PI_TWOFLOAT: (f64, f64) = (PI, 1.2246467991473532e-16);
+/// π/4 as double-double: the nearest f64, and the rest.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+pub(crate) const FRAC_PI_4_TWOFLOAT: (f64, f64) = (FRAC_PI_4, 3.061616997868383e-17);
+
+/// Inverse trigonometric functions. Accuracy:
+/// - MPFR: correctly rounded.
+/// - f32: computed with f64, so nearly always correctly rounded.
+/// - f64: within 1 ulp (as `libm`).
+/// - TwoFloat: TwoFloat's own algorithms, with relative error below 1e-26.
+///
+/// Results that are whole multiples of π/4 (like `asin(1)` or `atan2(y, x)` on an axis or with
+/// both sides infinite) are π/4 multiplied in full precision, so TwoFloat gets them to
+/// double-double precision, too. Signed zeros and infinities are as in IEEE 754.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// Arcsine, in [-π/2, π/2]. NaN for |self| > 1.
+    pub fn asin(&self) -> Self {
+        self.unary(|x| libm::asin(x as f64) as f32, libm::asin,
+            twofloat_op!(|x| twofloat_or_quarter_turns(x, libm::asin(x.hi()), libm::fabs(x.hi()) < 1.0, |x| x.asin())),
+            mpfr_op!(|r, x| unsafe { mpfr::asin(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Arccosine, in [0, π]. NaN for |self| > 1.
+    pub fn acos(&self) -> Self {
+        self.unary(|x| libm::acos(x as f64) as f32, libm::acos,
+            twofloat_op!(|x| twofloat_or_quarter_turns(x, libm::acos(x.hi()), libm::fabs(x.hi()) < 1.0, |x| x.acos())),
+            mpfr_op!(|r, x| unsafe { mpfr::acos(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Arctangent, in [-π/2, π/2].
+    pub fn atan(&self) -> Self {
+        self.unary(|x| libm::atan(x as f64) as f32, libm::atan,
+            twofloat_op!(|x| twofloat_or_quarter_turns(x, libm::atan(x.hi()), x.hi().is_finite(), |x| x.atan())),
+            mpfr_op!(|r, x| unsafe { mpfr::atan(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    /// Four-quadrant arctangent of `self` (y) and `x`, in [-π, π]: the angle of point (x, y).
+    /// As in IEEE 754, its sign is the sign of `self`, even for zeros: `atan2(±0, -0)` is ±π and
+    /// `atan2(±0, +0)` is ±0.
+    pub fn atan2(&self, x: &Self) -> Self {
+        self.binary(x, |y, x| libm::atan2(y as f64, x as f64) as f32, libm::atan2,
+            twofloat_op!(|y, x| {
+                let regular = y.hi().is_finite() && y.hi() != 0.0 && x.hi().is_finite() && x.hi() != 0.0;
+                twofloat_or_quarter_turns(y, libm::atan2(y.hi(), x.hi()), regular, |y| y.atan2(x))
+            }),
+            mpfr_op!(|r, y, x| unsafe { mpfr::atan2(r, y, x, mpfr::rnd_t::RNDN) }))
+    }
+}
+
+/// Trigonometric functions of radians. Accuracy:
+/// - MPFR: correctly rounded.
+/// - f32: computed with f64, so nearly always correctly rounded.
+/// - f64: within 1 ulp (as `libm`).
+/// - TwoFloat: TwoFloat's own algorithms, which lose accuracy for large arguments (unlike
+///   `sin_pi()` and the others below).
+///
+/// NaN for NaN and infinities.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    pub fn sin(&self) -> Self {
+        self.unary(|x| libm::sin(x as f64) as f32, libm::sin, twofloat_op!(|x| finite_twofloat(x, |x| x.sin())),
+            mpfr_op!(|r, x| unsafe { mpfr::sin(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    pub fn cos(&self) -> Self {
+        self.unary(|x| libm::cos(x as f64) as f32, libm::cos, twofloat_op!(|x| finite_twofloat(x, |x| x.cos())),
+            mpfr_op!(|r, x| unsafe { mpfr::cos(r, x, mpfr::rnd_t::RNDN) }))
+    }
+
+    pub fn tan(&self) -> Self {
+        self.unary(|x| libm::tan(x as f64) as f32, libm::tan, twofloat_op!(|x| finite_twofloat(x, |x| x.tan())),
+            mpfr_op!(|r, x| unsafe { mpfr::tan(r, x, mpfr::rnd_t::RNDN) }))
+    }
+}
+
+/// Trigonometric functions of half-revolutions: sin(π * self) etc., without rounding π * self
+/// first. So `sin_pi()` of an integer is zero, `cos_pi(0.5)` is zero, and `tan_pi(0.25)` is 1, and
+/// none of them loses accuracy for large arguments. The argument is reduced exactly, by a whole
+/// number of half-revolutions. Accuracy:
+/// - MPFR: correctly rounded.
+/// - f32 and f64: computed with TwoFloat, so nearly always correctly rounded. They need feature
+///   `twofloat`.
+/// - TwoFloat: TwoFloat's own sine and cosine of the reduced argument, which is within [-π/4, π/4],
+///   with relative error below 1e-26.
+///
+/// NaN for NaN and infinities. Zeros and infinities of exact results are as in IEEE 754:
+/// `sin_pi(n)` has the sign of `n`, `cos_pi(n + 0.5)` is +0, `tan_pi(n)` is +0 for positive
+/// even and negative odd `n` (and -0 otherwise), and `tan_pi(n + 0.5)` is +Inf for even `n`,
+/// and -Inf for odd `n`.
+impl <const C: UniFloatChoice> UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    /// sin(π * self).
+    pub fn sin_pi(&self) -> Self {
+        self.trig_pi(TrigPi::Sin)
+    }
+
+    /// cos(π * self).
+    pub fn cos_pi(&self) -> Self {
+        self.trig_pi(TrigPi::Cos)
+    }
+
+    /// tan(π * self).
+    pub fn tan_pi(&self) -> Self {
+        self.trig_pi(TrigPi::Tan)
+    }
+
+    #[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(unused_variables))]
+    fn trig_pi(&self, function: TrigPi) -> Self {
+        self.unary(twofloat_op!(|x| trig_pi_twofloat(TwoFloat::from(x as f64), function).hi() as f32),
+            twofloat_op!(|x| trig_pi_twofloat(TwoFloat::from(x), function).hi()),
+            twofloat_op!(|x| trig_pi_twofloat(x, function)),
+            mpfr_op!(|r, x| unsafe { mpfr_trig_pi(r, x, function) }))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TrigPi {
+    Sin,
+    Cos,
+    Tan
+}
+
+/// For the argument reduced by `quadrant` half-revolutions (modulo 4): whether the result comes
+/// from the cosine of the reduced argument (for `tan_pi()`: whether it's the cotangent, rather
+/// than the tangent), and whether it's negated.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+fn trig_pi_quadrant(function: TrigPi, quadrant: i64) -> (bool, bool) {
+    match function {
+        TrigPi::Sin => (quadrant % 2 == 1, quadrant >= 2),
+        TrigPi::Cos => (quadrant % 2 == 0, quadrant == 1 || quadrant == 2),
+        TrigPi::Tan => (quadrant % 2 == 1, quadrant % 2 == 1)
+    }
+}
+
+/// The result where the argument is reduced to zero, that is, for whole multiples of 1/2. It's
+/// +-0, +-1 or +-Inf.
+#[cfg_attr(all(not(feature = "mpfr"), not(feature = "twofloat")), allow(dead_code))]
+fn trig_pi_exact(function: TrigPi, quadrant: i64, negative: bool) -> f64 {
+    let signed_zero = |negative| if negative { -0.0 } else { 0.0 };
+    match (function, quadrant) {
+        (TrigPi::Sin, 1) | (TrigPi::Cos, 0) => 1.0,
+        (TrigPi::Sin, 3) | (TrigPi::Cos, 2) => -1.0,
+        (TrigPi::Sin, _) => signed_zero(negative),
+        (TrigPi::Cos, _) => 0.0,
+        (TrigPi::Tan, 1) => f64::INFINITY,
+        (TrigPi::Tan, 3) => f64::NEG_INFINITY,
+        (TrigPi::Tan, _) => signed_zero(negative != (quadrant == 2))
+    }
+}
+
+/// It reduces `x` modulo 2 part by part (`libm::fmod()` is exact), and then by the nearest whole
+/// number of half-revolutions, to within [-1/4, 1/4]. Both steps are exact.
+#[cfg(feature = "twofloat")]
+pub(crate) fn trig_pi_twofloat(x: TwoFloat, function: TrigPi) -> TwoFloat {
+    if !x.hi().is_finite() {
+        return TwoFloat::from(f64::NAN);
+    }
+    let modulo_2 = TwoFloat::new_add(libm::fmod(x.hi(), 2.0), libm::fmod(x.lo(), 2.0));
+    let half_turns = (modulo_2 * 2.0).round();
+    let reduced = modulo_2 - half_turns * 0.5;
+    let quadrant = (half_turns.hi() as i64).rem_euclid(4);
+    if reduced.hi() == 0.0 {
+        return TwoFloat::from(trig_pi_exact(function, quadrant, x.hi().is_sign_negative()));
+    }
+    let angle = reduced * TwoFloat::new_add(PI_TWOFLOAT.0, PI_TWOFLOAT.1);
+    // Below 1e-17 the next terms of the Taylor series are less than 1e-34 relative.
+    let (sine, cosine) = if libm::fabs(angle.hi()) < 1e-17 {
+        (angle, TwoFloat::from(1.0))
+    } else {
+        (angle.sin(), angle.cos())
+    };
+    let (use_cosine, negate) = trig_pi_quadrant(function, quadrant);
+    let result = match (function, use_cosine) {
+        (TrigPi::Tan, false) => sine / cosine,
+        (TrigPi::Tan, true) => cosine / sine,
+        (_, false) => sine,
+        (_, true) => cosine
+    };
+    if negate { -result } else { result }
+}
+
+/// Correctly rounded sin(π * x) etc., by Ziv's strategy (as for `mpfr_log()` in the logarithm
+/// module). `mpfr_remquo()` reduces `x` exactly (at the precision of `x`), so only the reduced
+/// argument gets multiplied by π.
+///
+/// For a non-zero reduced argument, the only exact result is +-1 of `tan_pi()` (of odd multiples of
+/// 1/4). There the result never gets decided, so the working precision is limited, and then the
+/// result is rounded as it is (correctly).
+#[cfg(feature = "mpfr")]
+unsafe fn mpfr_trig_pi(r: mpfr::mpfr_ptr, x: mpfr::mpfr_srcptr, function: TrigPi) -> i32 {
+    if mpfr::nan_p(x) != 0 || mpfr::inf_p(x) != 0 {
+        mpfr::set_nan(r);
+        return 0;
+    }
+    let (mut reduced, mut half) = (MaybeUninit::uninit(), MaybeUninit::uninit());
+    mpfr::init2(reduced.as_mut_ptr(), mpfr::get_prec(x));
+    mpfr::init2(half.as_mut_ptr(), 2);
+    let (reduced, half) = (reduced.as_mut_ptr(), half.as_mut_ptr());
+    mpfr::set_d(half, 0.5, mpfr::rnd_t::RNDN);
+    // `mpfr::exp_t` is `c_long`, the same as the quotient of `mpfr_remquo()`. It gets (at least)
+    // the low three bits of the number of half-revolutions, with its sign.
+    let mut half_turns: mpfr::exp_t = 0;
+    mpfr::remquo(reduced, &mut half_turns, x, half, mpfr::rnd_t::RNDN);
+    let quadrant = i64::from(half_turns).rem_euclid(4);
+    let ternary = if mpfr::zero_p(reduced) != 0 {
+        mpfr::set_d(r, trig_pi_exact(function, quadrant, mpfr::signbit(x) != 0), mpfr::rnd_t::RNDN)
+    } else {
+        let precision = mpfr::get_prec(r);
+        let mut working = precision + 32;
+        let (mut angle, mut sine, mut cosine) = (MaybeUninit::uninit(), MaybeUninit::uninit(), MaybeUninit::uninit());
+        mpfr::init2(angle.as_mut_ptr(), working);
+        mpfr::init2(sine.as_mut_ptr(), working);
+        mpfr::init2(cosine.as_mut_ptr(), working);
+        let (angle, sine, cosine) = (angle.as_mut_ptr(), sine.as_mut_ptr(), cosine.as_mut_ptr());
+        let (use_cosine, negate) = trig_pi_quadrant(function, quadrant);
+        let (result, other) = if use_cosine { (cosine, sine) } else { (sine, cosine) };
+        let ternary = loop {
+            mpfr::const_pi(angle, mpfr::rnd_t::RNDN);
+            mpfr::mul(angle, angle, reduced, mpfr::rnd_t::RNDN);
+            mpfr::sin_cos(sine, cosine, angle, mpfr::rnd_t::RNDN);
+            if function == TrigPi::Tan {
+                mpfr::div(result, result, other, mpfr::rnd_t::RNDN);
+            }
+            if negate {
+                mpfr::neg(result, result, mpfr::rnd_t::RNDN);
+            }
+            // Up to four roundings (π, the angle, sine or cosine, and the quotient), each with
+            // relative error up to 2^-working. The angle is within [-π/4, π/4], so its relative
+            // error carries over to its sine and cosine (at most 1.2 times). That's less than
+            // 2^(3 - working) relative, hence less than 2^(exponent + 3 - working) absolute.
+            if mpfr::can_round(result, (working - 3) as mpfr::exp_t, mpfr::rnd_t::RNDN, mpfr::rnd_t::RNDZ, precision + 1) != 0
+                || working > 2 * precision + 256 {
+                break mpfr::set(r, result, mpfr::rnd_t::RNDN);
+            }
+            working += working / 2;
+            mpfr::set_prec(angle, working);
+            mpfr::set_prec(sine, working);
+            mpfr::set_prec(cosine, working);
+        };
+        mpfr::clear(angle);
+        mpfr::clear(sine);
+        mpfr::clear(cosine);
+        ternary
+    };
+    mpfr::clear(reduced);
+    mpfr::clear(half);
+    ternary
+}
+
+/// `twofloat_op(x)` if `regular` and `approximation` (the result in f64) is non-zero. Otherwise
+/// `approximation` (for NaN and zeros), or (if it's a whole multiple of π/4) that multiple of π/4
+/// in double-double.
+#[cfg(feature = "twofloat")]
+fn twofloat_or_quarter_turns(x: TwoFloat, approximation: f64, regular: bool, twofloat_op: impl FnOnce(TwoFloat) -> TwoFloat) -> TwoFloat {
+    if regular && approximation != 0.0 {
+        return twofloat_op(x);
+    }
+    let quarter_turns = libm::round(approximation / FRAC_PI_4);
+    if approximation != 0.0 && libm::fabs(approximation - quarter_turns * FRAC_PI_4) <= 4.0 * f64::EPSILON {
+        TwoFloat::new_add(FRAC_PI_4_TWOFLOAT.0, FRAC_PI_4_TWOFLOAT.1) * quarter_turns
+    } else {
+        TwoFloat::from(approximation)
+    }
+}
+
+/// TwoFloat's own algorithms don't handle infinities.
+#[cfg(feature = "twofloat")]
+fn finite_twofloat(x: TwoFloat, op: fn(TwoFloat) -> TwoFloat) -> TwoFloat {
+    if x.hi().is_finite() { op(x) } else { TwoFloat::from(f64::NAN) }
+}
@@ -0,0 +1,133 @@
+use {core::{fmt, marker::PhantomData, ops, ptr}, crate::{gmp, mpfr}};
+
+use crate::parts::limit_exponent_range;
+use crate::{MpfrBounds, MpfrLimbPart, NAN_MPFR_EXP};
+
+/// A read-only MPFR number whose limbs are in a slice borrowed from the caller (for example, a
+/// static buffer, a DMA region or a stack array of runtime-chosen length). Its precision is
+/// decided at runtime by `MpfrBounds`, rather than by a const generic parameter as for `UniFloat`.
+///
+/// Get it from `UniFloatMut::view()`.
+#[derive(Clone, Copy)]
+pub struct UniFloatRef<'a> {
+    bounds: MpfrBounds,
+    /// Its limbs are in the borrowed slice.
+    mpfr: mpfr::mpfr_t,
+    limbs: PhantomData<&'a [MpfrLimbPart]>,
+}
+
+/// A mutable MPFR number whose limbs are in a slice borrowed from the caller, like `UniFloatRef`.
+/// The limbs don't move with the instance.
+///
+/// New instances are NaN, as for `UniFloat`. Operands may have other precisions than `self`.
+/// Results are rounded to nearest, to the precision and the exponent range of `self`.
+pub struct UniFloatMut<'a> {
+    bounds: MpfrBounds,
+    /// Its limbs are in the borrowed slice.
+    mpfr: mpfr::mpfr_t,
+    limbs: PhantomData<&'a mut [MpfrLimbPart]>,
+}
+
+impl UniFloatRef<'_> {
+    pub fn bounds(&self) -> MpfrBounds {
+        self.bounds
+    }
+
+    /// Rounded to nearest.
+    pub fn to_f64_nearest(&self) -> f64 {
+        unsafe { mpfr::get_d(&self.mpfr, mpfr::rnd_t::RNDN) }
+    }
+}
+
+impl <'a> UniFloatMut<'a> {
+    /// Length of the limb slice that `new_in()` needs for `bounds`.
+    pub const fn limbs_needed(bounds: MpfrBounds) -> usize {
+        bounds.limb_parts
+    }
+
+    /// NaN, with its limbs in `limbs`. That needs to be at least `limbs_needed(bounds)` long. Any
+    /// extra limbs are left unused.
+    pub fn new_in(bounds: MpfrBounds, limbs: &'a mut [MpfrLimbPart]) -> Self {
+        assert!(limbs.len() >= Self::limbs_needed(bounds),
+            "The limb slice is too short for the MpfrBounds. See UniFloatMut::limbs_needed().");
+        Self {
+            bounds,
+            mpfr: mpfr::mpfr_t {
+                prec: bounds.precision_bits as mpfr::prec_t,
+                sign: 1,
+                exp: NAN_MPFR_EXP,
+                // Slices (even empty ones) have non-null pointers.
+                d: unsafe { ptr::NonNull::new_unchecked(limbs.as_mut_ptr() as *mut gmp::limb_t) }
+            },
+            limbs: PhantomData
+        }
+    }
+
+    pub fn bounds(&self) -> MpfrBounds {
+        self.bounds
+    }
+
+    /// Borrows `self` as read-only, for use as an operand.
+    pub fn view(&self) -> UniFloatRef<'_> {
+        UniFloatRef { bounds: self.bounds, mpfr: self.mpfr, limbs: PhantomData }
+    }
+
+    /// Rounded to nearest.
+    pub fn to_f64_nearest(&self) -> f64 {
+        self.view().to_f64_nearest()
+    }
+
+    /// Sets `self` to `value`, rounded to nearest.
+    pub fn set_f64(&mut self, value: f64) {
+        self.assign_with(|r| unsafe { mpfr::set_d(r, value, mpfr::rnd_t::RNDN) });
+    }
+
+    /// Sets `self` to the value of `other`, rounded to nearest.
+    pub fn assign(&mut self, other: &UniFloatRef<'_>) {
+        self.assign_with(|r| unsafe { mpfr::set(r, &other.mpfr, mpfr::rnd_t::RNDN) });
+    }
+
+    pub fn sqrt_assign(&mut self) {
+        self.assign_with(|r| unsafe { mpfr::sqrt(r, r, mpfr::rnd_t::RNDN) });
+    }
+
+    fn assign_with(&mut self, mpfr_op: impl FnOnce(mpfr::mpfr_ptr) -> i32) {
+        let ternary = mpfr_op(&mut self.mpfr);
+        limit_exponent_range(self.bounds, &mut self.mpfr, ternary);
+    }
+}
+
+// Modify-and-assign operators (+=, -=, *=, /=), as for `UniFloat`.
+macro_rules! impl_assign_op {
+    ($trait:ident, $method:ident, $mpfr_op:path) => {
+        impl ops::$trait<&UniFloatRef<'_>> for UniFloatMut<'_> {
+            #[inline]
+            fn $method(&mut self, rhs: &UniFloatRef<'_>) {
+                self.assign_with(|r| unsafe { $mpfr_op(r, r, &rhs.mpfr, mpfr::rnd_t::RNDN) });
+            }
+        }
+    };
+}
+
+impl_assign_op!(AddAssign, add_assign, mpfr::add);
+impl_assign_op!(SubAssign, sub_assign, mpfr::sub);
+impl_assign_op!(MulAssign, mul_assign, mpfr::mul);
+impl_assign_op!(DivAssign, div_assign, mpfr::div);
+
+impl fmt::Debug for UniFloatRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UniFloatRef")
+            .field("value", &self.to_f64_nearest())
+            .field("bounds", &self.bounds)
+            .finish()
+    }
+}
+
+impl fmt::Debug for UniFloatMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UniFloatMut")
+            .field("value", &self.to_f64_nearest())
+            .field("bounds", &self.bounds)
+            .finish()
+    }
+}
@@ -0,0 +1,52 @@
+use core::{mem, ptr, sync::atomic};
+use zeroize::Zeroize;
+
+use crate::{UniFloatChoice, UniFloat, TwoFloatPart, MpfrFixedPart, MpfrLimbPart, f32_parts_length, f64_parts_length, twofloat_parts_length, mpfr_fixed_parts_length, mpfr_limb_parts_length};
+#[cfg(not(feature = "f32_only"))]
+use crate::{ZERO_MPFR_EXP, Dec};
+
+/// Overwrite the value with (positive) zero, so that a secret value can't be recovered from
+/// memory. The sensitive part of MPFR-based instances are the limbs (which hold the significand).
+/// Decimals keep their whole value in the limbs, so those get the words of positive zero (with
+/// exponent 0) rather than all zero bits, which would read as NaN. All writes are volatile, so that they don't get optimized away.
+///
+/// This doesn't change whether `.mutate()` has been cleared (see `OperandMutated`): the instance
+/// can be read from as zero either way.
+impl <const C: UniFloatChoice> Zeroize for UniFloat<C> where
+[f32; f32_parts_length(C)]: Sized,
+[f64; f64_parts_length(C)]: Sized,
+[TwoFloatPart; twofloat_parts_length(C)]: Sized,
+[MpfrFixedPart; mpfr_fixed_parts_length(C)]: Sized,
+[MpfrLimbPart; mpfr_limb_parts_length(C)]: Sized,
+{
+    fn zeroize(&mut self) {
+        for f in self.f32s.iter_mut() {
+            unsafe { ptr::write_volatile(f, 0.0) };
+        }
+        #[cfg(not(feature = "f32_only"))] {
+            for f in self.f64s.iter_mut() {
+                unsafe { ptr::write_volatile(f, 0.0) };
+            }
+            #[cfg(feature = "twofloat")]
+            for twofloat in self.twofloats.iter_mut() {
+                unsafe { ptr::write_volatile(twofloat, twofloat::TwoFloat::from(0.0)) };
+            }
+            let zero_limbs = match C {
+                UniFloatChoice::Decimal { digits } => Self::decimal_limbs(Dec::finite(digits, false, 0, 0)),
+                _ => [mem::MaybeUninit::new(0); mpfr_limb_parts_length(C)]
+            };
+            for (limb, zero) in self.mpfr_limbs.iter_mut().zip(zero_limbs.iter()) {
+                unsafe { ptr::write_volatile(limb, *zero) };
+            }
+            // Leave `prec` and `d` as they are. They are not secret, and `d` is re-pointed on
+            // each access anyway.
+            for fixed in self.mpfr_fixeds.iter_mut() {
+                unsafe {
+                    ptr::write_volatile(&mut fixed.sign, 1);
+                    ptr::write_volatile(&mut fixed.exp, ZERO_MPFR_EXP);
+                }
+            }
+        }
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,6 @@
+#[test]
+fn choice_covers_bounds_checked_at_compile_time() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/choice_covers_bounds.rs");
+    cases.compile_fail("tests/ui/choice_not_covering_bounds.rs");
+}
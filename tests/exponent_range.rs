@@ -0,0 +1,6 @@
+#[test]
+fn exponent_range_checked_at_compile_time() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/exponent_range_covered.rs");
+    cases.compile_fail("tests/ui/exponent_range_not_covered.rs");
+}
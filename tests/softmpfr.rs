@@ -0,0 +1,62 @@
+#![cfg(feature = "softmpfr")]
+#![cfg(not(feature = "mpfr"))]
+#![cfg(not(feature = "f32_only"))]
+
+use unifloat::{MpfrBounds, RoundingMode, UniFloatBounds, UniFloatChoice, UniFloat};
+
+// MPFR's default exponent range: SoftMpfr has no other.
+type UniMpfr100 = UniFloat<{ UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_bounds(&UniFloatBounds::new(100, isize::MIN, isize::MAX))
+} }>;
+
+#[test]
+fn mpfr_choice_computes_by_softmpfr_without_mpfr_feature() {
+    let (one, three) = (UniMpfr100::from(1.0), UniMpfr100::from(3.0));
+    let third = &one / &three;
+    assert!(third.to_f64(RoundingMode::Nearest).value() == 1.0 / 3.0);
+    assert!(third.to_f64(RoundingMode::Down).value() < third.to_f64(RoundingMode::Up).value());
+    // 100 bits keep 2^-60 next to 1.
+    let tiny = UniMpfr100::from(2f64.powi(-60));
+    let sum = &one + &tiny;
+    assert!(sum > one);
+    assert!((&sum - &one) == tiny);
+
+    let two = UniMpfr100::from(2.0);
+    let root = two.sqrt();
+    let square = &root * &root;
+    assert!(square != two);
+    assert!((&square - &two).to_f64(RoundingMode::Nearest).value().abs() < 2f64.powi(-97));
+    assert!((-&root).is_sign_negative());
+    assert!(UniMpfr100::from(-1.0).sqrt().is_nan());
+}
+
+#[test]
+fn mpfr_choice_formats_and_parses_by_softmpfr_without_mpfr_feature() {
+    let third = &UniMpfr100::from(1.0) / &UniMpfr100::from(3.0);
+    let text = format!("{}", third);
+    assert!(text.starts_with("0.333333333333333333333333333333"), "{}", text);
+    assert!(text.parse::<UniMpfr100>().unwrap() == third);
+    assert!(format!("{:e}", third).parse::<UniMpfr100>().unwrap() == third);
+    // Correctly rounded to 100 bits, as the division is.
+    let tenth = "0.1".parse::<UniMpfr100>().unwrap();
+    assert!((&UniMpfr100::from(1.0) / &UniMpfr100::from(10.0)) == tenth);
+    assert!(tenth != UniMpfr100::from(0.1));
+    assert_eq!(format!("{}", tenth), "0.1");
+    assert_eq!(format!("{}", "-2.5".parse::<UniMpfr100>().unwrap()), "-2.5");
+    assert_eq!(format!("{:e}", "-1.5e-300000".parse::<UniMpfr100>().unwrap()), "-1.5e-300000");
+    assert!("1e999999999".parse::<UniMpfr100>().unwrap().is_infinite());
+    assert!("1e-999999999".parse::<UniMpfr100>().unwrap() == UniMpfr100::from(0.0));
+    assert!("-inf".parse::<UniMpfr100>().unwrap() < UniMpfr100::from(f64::MIN));
+    assert!("nan".parse::<UniMpfr100>().unwrap().is_nan());
+    assert!("1.2.3".parse::<UniMpfr100>().is_err());
+}
+
+#[cfg(feature = "twofloat")]
+#[test]
+fn mpfr_choice_converts_from_and_to_twofloat_by_softmpfr() {
+    type UniTwoFloat = UniFloat<{ UniFloatChoice::TwoFloat }>;
+    let sum = &UniTwoFloat::from(1.0) + &UniTwoFloat::from(2f64.powi(-60));
+    let wide: UniMpfr100 = sum.convert();
+    assert!((&wide - &UniMpfr100::from(1.0)) == UniMpfr100::from(2f64.powi(-60)));
+    assert!(wide.convert::<{ UniFloatChoice::TwoFloat }>() == sum);
+}
@@ -0,0 +1,10 @@
+#![allow(incomplete_features)]
+#![feature(const_generics, const_evaluatable_checked, const_panic)]
+use unifloat::{assert_choice_covers, UniFloatBounds, UniFloatBoundsBase, UniFloatChoice};
+
+const REQUIRED: UniFloatBounds<{ UniFloatBoundsBase::BINARY }> = UniFloatBounds::new(50, -1000, 1000);
+
+const _: () = assert_choice_covers!(UniFloatChoice::F64, REQUIRED);
+const _: () = assert_choice_covers!(UniFloatChoice::TwoFloat, REQUIRED);
+
+fn main() {}
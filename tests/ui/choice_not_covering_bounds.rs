@@ -0,0 +1,10 @@
+#![allow(incomplete_features)]
+#![feature(const_generics, const_evaluatable_checked, const_panic)]
+use unifloat::{assert_choice_covers, UniFloatBounds, UniFloatBoundsBase, UniFloatChoice};
+
+// f32 has 24 bits of precision only.
+const REQUIRED: UniFloatBounds<{ UniFloatBoundsBase::BINARY }> = UniFloatBounds::new(50, -100, 100);
+
+const _: () = assert_choice_covers!(UniFloatChoice::F32, REQUIRED);
+
+fn main() {}
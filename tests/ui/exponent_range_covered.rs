@@ -0,0 +1,9 @@
+#![allow(incomplete_features)]
+#![feature(const_generics, const_evaluatable_checked)]
+use unifloat::{UniFloatChoice, UniFloat};
+
+fn main() {
+    // f32 covers binary exponents -125..128, f64 covers -1021..1024.
+    let _ = UniFloat::<{UniFloatChoice::F32}>::nan_covering_exponents::<-100, 100>();
+    let _ = UniFloat::<{UniFloatChoice::F64}>::nan_covering_exponents::<-1000, 1000>();
+}
@@ -0,0 +1,8 @@
+#![allow(incomplete_features)]
+#![feature(const_generics, const_evaluatable_checked)]
+use unifloat::{UniFloatChoice, UniFloat};
+
+fn main() {
+    // f32 covers binary exponents -125..128 only.
+    let _ = UniFloat::<{UniFloatChoice::F32}>::nan_covering_exponents::<-100, 1000>();
+}
@@ -0,0 +1,48 @@
+#![cfg(not(feature = "mpfr"))]
+#![cfg(not(feature = "f32_only"))]
+#![cfg(feature = "twofloat")]
+
+use unifloat::{RoundingMode, UniFloatChoice, UniFloat};
+
+type UniF64 = UniFloat<{ UniFloatChoice::F64 }>;
+type UniTwoFloat = UniFloat<{ UniFloatChoice::TwoFloat }>;
+
+#[test]
+fn f64_and_twofloat_compute_without_mpfr_feature() {
+    let (one, three) = (UniF64::from(1.0), UniF64::from(3.0));
+    assert!((&one / &three).to_f64(RoundingMode::Nearest).value() == 1.0 / 3.0);
+
+    // Double-double keeps 2^-60 next to 1.
+    let (one, tiny) = (UniTwoFloat::from(1.0), UniTwoFloat::from(2f64.powi(-60)));
+    let sum = &one + &tiny;
+    assert!(sum.to_f64(RoundingMode::Nearest).value() == 1.0);
+    assert!((&sum - &one) == tiny);
+}
+
+#[test]
+fn twofloat_formats_and_parses_without_mpfr_feature() {
+    let sum = &UniTwoFloat::from(1.0) + &UniTwoFloat::from(2f64.powi(-60));
+    let text = format!("{}", sum);
+    assert!(text.len() > 20);
+    assert!(text.parse::<UniTwoFloat>().unwrap() == sum);
+    assert!(format!("{:e}", sum).parse::<UniTwoFloat>().unwrap() == sum);
+
+    let tenth = "0.1".parse::<UniTwoFloat>().unwrap();
+    assert_eq!(format!("{}", tenth), "0.1");
+    assert!(tenth.to_f64(RoundingMode::Nearest).value() == 0.1);
+    assert!(tenth != UniTwoFloat::from(0.1));
+    assert_eq!(format!("{:e}", "-1.5e300".parse::<UniTwoFloat>().unwrap()), "-1.5e300");
+    assert!("nan".parse::<UniTwoFloat>().unwrap().is_nan());
+    assert!("1.2.3".parse::<UniTwoFloat>().is_err());
+
+    let mut hex = String::new();
+    sum.write_hex(&mut hex).unwrap();
+    assert_eq!(hex, "0x1.000000000000001p+0");
+    assert!(UniTwoFloat::from_hex_str(&hex).unwrap() == sum);
+
+    for &base in [2, 8, 32].iter() {
+        let mut written = String::new();
+        sum.write_radix(&mut written, base, 0).unwrap();
+        assert!(UniTwoFloat::from_str_radix(&written, base).unwrap() == sum, "{}", written);
+    }
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "mpfr")]
+#![cfg(not(feature = "twofloat"))]
+#![cfg(not(feature = "f32_only"))]
+
+use unifloat::{MpfrBounds, RoundingMode, UniFloatBounds, UniFloatChoice, UniFloat};
+
+type UniF32 = UniFloat<{ UniFloatChoice::F32 }>;
+type UniF64 = UniFloat<{ UniFloatChoice::F64 }>;
+type UniMpfr100 = UniFloat<{ UniFloatChoice::Mpfr {
+    bounds: MpfrBounds::for_bounds(&UniFloatBounds::new(100, isize::MIN, isize::MAX))
+} }>;
+
+#[test]
+fn f32_f64_and_mpfr_compute_without_twofloat_feature() {
+    let third = &UniF32::from(1.0) / &UniF32::from(3.0);
+    assert!(third.to_f32(RoundingMode::Nearest).value() == 1.0f32 / 3.0);
+    let root = UniF64::from(2.0).sqrt();
+    assert!(root.to_f64(RoundingMode::Nearest).value() == 2f64.sqrt());
+
+    // 100 bits keep 2^-60 next to 1.
+    let (one, tiny) = (UniMpfr100::from(1.0), UniMpfr100::from(2f64.powi(-60)));
+    let sum = &one + &tiny;
+    assert!(sum > one);
+    assert!((&sum - &one) == tiny);
+    assert!(sum.to_f64(RoundingMode::Nearest).value() == 1.0);
+    let sine = UniMpfr100::from(1.0).sin().to_f64(RoundingMode::Nearest).value();
+    assert!((sine - 1f64.sin()).abs() < 1e-15);
+    assert!(sum.convert::<{ UniFloatChoice::F64 }>() == UniF64::from(1.0));
+}
+
+#[test]
+fn f32_f64_and_mpfr_format_and_parse_without_twofloat_feature() {
+    assert_eq!(format!("{}", "0.1".parse::<UniF32>().unwrap()), "0.1");
+    assert_eq!(format!("{:e}", "-1.5e300".parse::<UniF64>().unwrap()), "-1.5e300");
+
+    let tenth = "0.1".parse::<UniMpfr100>().unwrap();
+    assert_eq!(format!("{}", tenth), "0.1");
+    assert!(tenth.to_f64(RoundingMode::Nearest).value() == 0.1);
+    assert!(tenth != UniMpfr100::from(0.1));
+    let third = &UniMpfr100::from(1.0) / &UniMpfr100::from(3.0);
+    assert!(format!("{}", third).parse::<UniMpfr100>().unwrap() == third);
+    assert!("nan".parse::<UniMpfr100>().unwrap().is_nan());
+    assert!("1.2.3".parse::<UniMpfr100>().is_err());
+
+    let mut hex = String::new();
+    UniF64::from(0.75).write_hex(&mut hex).unwrap();
+    assert_eq!(hex, "0x1.8p-1");
+    assert!(UniF64::from_hex_str(&hex).unwrap() == UniF64::from(0.75));
+}